@@ -2,7 +2,7 @@
 
 use gpui::{AppContext, Context, Entity, Global, IntoElement, Render, Window};
 use tusk_ui::key_bindings::register_key_bindings;
-use tusk_ui::{register_text_input_bindings, ContextMenuLayer, ModalLayer, Workspace};
+use tusk_ui::{ContextMenuLayer, ModalLayer, Workspace};
 
 /// Global reference to the workspace entity for menu action dispatching.
 pub struct WorkspaceHandle(pub Entity<Workspace>);
@@ -19,7 +19,6 @@ impl TuskApp {
     pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
         // Register global key bindings
         register_key_bindings(cx);
-        register_text_input_bindings(cx);
 
         // Register ModalLayer as global for modal management (T093)
         cx.set_global(ModalLayer::new());
@@ -33,7 +32,9 @@ impl TuskApp {
         // Store workspace handle globally for menu action dispatching
         cx.set_global(WorkspaceHandle(workspace.clone()));
 
-        // App starts disconnected - user connects via File > New Connection (Cmd+N)
+        // App starts disconnected unless a saved connection is set to connect on
+        // startup; otherwise the user connects via File > New Connection (Cmd+N)
+        workspace.update(cx, |workspace, cx| workspace.connect_on_startup(cx));
 
         Self { workspace }
     }
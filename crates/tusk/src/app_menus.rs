@@ -5,8 +5,9 @@
 
 use gpui::{App, Menu, MenuItem, OsAction};
 use tusk_ui::key_bindings::{
-    About, CloseActiveTab, CloseWindow, Minimize, NewConnection, NewQueryTab, Quit, Settings,
-    ShowKeyboardShortcuts, SplitDown, SplitRight, ToggleBottomDock, ToggleLeftDock, Zoom,
+    About, CloseActiveTab, CloseWindow, ExportResults, Minimize, NewConnection, NewQueryTab,
+    OpenSqlFile, Quit, SaveSqlFile, SaveSqlFileAs, Settings, ShowKeyboardShortcuts, SplitDown,
+    SplitRight, ToggleBottomDock, ToggleLeftDock, Zoom, ZoomPane,
 };
 use tusk_ui::{Copy, Cut, Paste, Redo, SelectAll, Undo};
 
@@ -35,6 +36,12 @@ pub fn app_menus(_cx: &mut App) -> Vec<Menu> {
                 MenuItem::action("New Connection...", NewConnection),
                 MenuItem::action("New Query Tab", NewQueryTab),
                 MenuItem::separator(),
+                MenuItem::action("Open SQL File...", OpenSqlFile),
+                MenuItem::action("Save", SaveSqlFile),
+                MenuItem::action("Save As...", SaveSqlFileAs),
+                MenuItem::separator(),
+                MenuItem::action("Export Results...", ExportResults),
+                MenuItem::separator(),
                 MenuItem::action("Close Tab", CloseActiveTab),
                 MenuItem::action("Close Window", CloseWindow),
             ],
@@ -65,6 +72,8 @@ pub fn app_menus(_cx: &mut App) -> Vec<Menu> {
                     items: vec![
                         MenuItem::action("Split Right", SplitRight),
                         MenuItem::action("Split Down", SplitDown),
+                        MenuItem::separator(),
+                        MenuItem::action("Zoom Pane", ZoomPane),
                     ],
                 }),
             ],
@@ -12,7 +12,7 @@ use tusk_core::state::TuskState;
 use tusk_ui::key_bindings::{
     About, CloseWindow, Minimize, NewConnection, NewQueryTab, Quit, ShowKeyboardShortcuts, Zoom,
 };
-use tusk_ui::{show_keyboard_shortcuts, TuskTheme};
+use tusk_ui::{show_keyboard_shortcuts, Assets, TuskTheme};
 
 fn main() {
     // Initialize logging before TuskState (FR-022, FR-023, FR-024)
@@ -21,12 +21,21 @@ fn main() {
 
     tracing::info!("Starting Tusk");
 
-    Application::new().run(|cx: &mut App| {
+    Application::new().with_assets(Assets).run(|cx: &mut App| {
         // Initialize TuskState and set as global (FR-005, SC-002)
         match TuskState::new() {
             Ok(state) => {
                 cx.set_global(state);
                 tracing::info!("TuskState initialized successfully");
+
+                // Stop running queries on the server before exiting
+                cx.on_app_quit(|cx| {
+                    if let Some(state) = cx.try_global::<TuskState>() {
+                        state.shutdown();
+                    }
+                    async {}
+                })
+                .detach();
             }
             Err(e) => {
                 tracing::error!(error = %e, "Failed to initialize TuskState");
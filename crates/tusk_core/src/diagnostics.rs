@@ -0,0 +1,240 @@
+//! Diagnostics bundles for bug reports.
+//!
+//! A bundle is a zip archive holding the app version, platform, storage
+//! schema version, connection names and pool statuses, and the tail of the
+//! current log file. Passwords, connection details beyond names, and SQL
+//! text are never written to it; log lines are redacted before inclusion.
+
+use std::io::Write;
+use std::path::Path;
+
+use serde_json::json;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use crate::error::TuskError;
+use crate::models::{ConnectionStatus, PoolStatus};
+
+/// Lines of the current log file included in a bundle.
+pub const LOG_TAIL_LINES: usize = 500;
+
+/// Bundle entry with version, platform, and storage details.
+pub const INFO_ENTRY: &str = "info.json";
+
+/// Bundle entry with connection names and pool statuses.
+pub const CONNECTIONS_ENTRY: &str = "connections.json";
+
+/// Bundle entry with the redacted log tail.
+pub const LOG_ENTRY: &str = "log_tail.txt";
+
+/// Log fields whose values are replaced in the log tail.
+const REDACTED_FIELDS: &[&str] =
+    &["password", "sql", "query", "secret", "token", "host", "database", "username", "user"];
+
+/// Replacement for redacted field values.
+const REDACTED: &str = "[redacted]";
+
+/// A connection as it appears in a diagnostics bundle.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsConnection {
+    /// Display name of the connection
+    pub name: String,
+    /// Current status, or None for saved connections that aren't open
+    pub status: Option<ConnectionStatus>,
+    /// Pool statistics for open connections
+    pub pool: Option<PoolStatus>,
+}
+
+/// Everything written to a diagnostics bundle.
+#[derive(Debug, Clone)]
+pub struct DiagnosticsReport {
+    /// Application version
+    pub app_version: String,
+    /// Local storage migration step
+    pub schema_version: i32,
+    /// Saved and open connections
+    pub connections: Vec<DiagnosticsConnection>,
+    /// Tail of the current log file, already redacted
+    pub log_tail: String,
+}
+
+impl DiagnosticsReport {
+    /// Write the report to a zip archive at `path`.
+    pub fn write_bundle(&self, path: &Path) -> Result<(), TuskError> {
+        let file = std::fs::File::create(path).map_err(|e| {
+            TuskError::storage_with_source(
+                format!("Failed to create diagnostics bundle: {}", path.display()),
+                e,
+            )
+        })?;
+
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        let entries = [
+            (INFO_ENTRY, self.info_json()),
+            (CONNECTIONS_ENTRY, self.connections_json()),
+            (LOG_ENTRY, self.log_tail.clone()),
+        ];
+        for (name, contents) in entries {
+            zip.start_file(name, options).map_err(|e| {
+                TuskError::storage(format!("Failed to add {name} to bundle: {e}"), None)
+            })?;
+            zip.write_all(contents.as_bytes()).map_err(|e| {
+                TuskError::storage(format!("Failed to write {name} to bundle: {e}"), None)
+            })?;
+        }
+        zip.finish().map_err(|e| {
+            TuskError::storage(format!("Failed to finish diagnostics bundle: {e}"), None)
+        })?;
+
+        Ok(())
+    }
+
+    fn info_json(&self) -> String {
+        let info = json!({
+            "app_version": self.app_version,
+            "os": std::env::consts::OS,
+            "arch": std::env::consts::ARCH,
+            "storage_schema_version": self.schema_version,
+            "connection_count": self.connections.len(),
+            "generated_at": chrono::Utc::now().to_rfc3339(),
+        });
+        serde_json::to_string_pretty(&info).unwrap_or_default()
+    }
+
+    fn connections_json(&self) -> String {
+        let connections: Vec<_> = self
+            .connections
+            .iter()
+            .map(|connection| {
+                json!({
+                    "name": connection.name,
+                    "status": connection.status.as_ref().map(status_label),
+                    "pool": connection.pool.map(|pool| json!({
+                        "max_size": pool.max_size,
+                        "size": pool.size,
+                        "available": pool.available,
+                        "waiting": pool.waiting,
+                    })),
+                })
+            })
+            .collect();
+        serde_json::to_string_pretty(&connections).unwrap_or_default()
+    }
+}
+
+/// Status name without error messages, which may quote server details.
+fn status_label(status: &ConnectionStatus) -> &'static str {
+    match status {
+        ConnectionStatus::Disconnected => "disconnected",
+        ConnectionStatus::Connecting => "connecting",
+        ConnectionStatus::Connected => "connected",
+        ConnectionStatus::Error { .. } => "error",
+    }
+}
+
+/// The last `count` lines of `text`, each redacted.
+pub fn redacted_log_tail(text: &str, count: usize) -> String {
+    let lines: Vec<&str> = text.lines().collect();
+    let start = lines.len().saturating_sub(count);
+    lines[start..].iter().map(|line| redact_log_line(line)).collect::<Vec<_>>().join("\n")
+}
+
+/// Replace the values of sensitive `key=value` fields in a log line.
+///
+/// Quoted values are replaced up to the closing quote. Bare values may
+/// contain spaces (SQL logged with `%`), so they run up to the next
+/// `key=` field or the end of the line.
+pub fn redact_log_line(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+
+    while let Some((key_start, key)) = next_sensitive_field(rest) {
+        let value_start = key_start + key.len() + 1;
+        out.push_str(&rest[..value_start]);
+        out.push_str(REDACTED);
+
+        let value = &rest[value_start..];
+        let value_len = match value.strip_prefix('"') {
+            Some(quoted) => quoted.find('"').map_or(value.len(), |end| end + 2),
+            None => bare_value_len(value),
+        };
+        rest = &value[value_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Length of an unquoted field value: up to the whitespace before the next
+/// `key=` field.
+fn bare_value_len(value: &str) -> usize {
+    value
+        .char_indices()
+        .filter(|(_, c)| c.is_whitespace())
+        .find(|(index, c)| {
+            let next = value[index + c.len_utf8()..].trim_start();
+            let key_len = next.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(0);
+            key_len > 0 && next[key_len..].starts_with('=')
+        })
+        .map_or(value.len(), |(index, _)| index)
+}
+
+/// Find the first sensitive `key=` in `text`, returning its offset and key.
+fn next_sensitive_field(text: &str) -> Option<(usize, &'static str)> {
+    REDACTED_FIELDS
+        .iter()
+        .filter_map(|key| {
+            text.match_indices(key).find_map(|(index, _)| {
+                let at_word_start = !matches!(
+                    text[..index].chars().next_back(),
+                    Some(c) if c.is_alphanumeric() || c == '_'
+                );
+                let followed_by_eq = text[index + key.len()..].starts_with('=');
+                (at_word_start && followed_by_eq).then_some((index, *key))
+            })
+        })
+        .min_by_key(|(index, _)| *index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_redact_log_line_fields() {
+        assert_eq!(
+            redact_log_line("INFO tusk: Connecting host=db password=hunter2 port=5432"),
+            "INFO tusk: Connecting host=[redacted] password=[redacted] port=5432"
+        );
+        // Where the user connects, and as whom
+        assert_eq!(
+            redact_log_line(
+                "ERROR tusk_core::services::connection: Failed to create connection pool \
+                 host=db.internal.example.com database=payroll username=alice user=alice \
+                 error=timed out"
+            ),
+            "ERROR tusk_core::services::connection: Failed to create connection pool \
+             host=[redacted] database=[redacted] username=[redacted] user=[redacted] \
+             error=timed out"
+        );
+        assert_eq!(
+            redact_log_line(r#"DEBUG tusk: Query sql="SELECT * FROM users" rows=3"#),
+            "DEBUG tusk: Query sql=[redacted] rows=3"
+        );
+        // Unquoted SQL runs until the next field
+        assert_eq!(
+            redact_log_line("DEBUG tusk: Query sql=SELECT * FROM users WHERE id = 1 rows=3"),
+            "DEBUG tusk: Query sql=[redacted] rows=3"
+        );
+        // Keys that merely end in a sensitive word are left alone
+        assert_eq!(redact_log_line("no_sql=1 mysql=2"), "no_sql=1 mysql=2");
+        assert_eq!(redact_log_line("plain message"), "plain message");
+    }
+
+    #[test]
+    fn test_redacted_log_tail_keeps_last_lines() {
+        let text = "one\ntwo password=a\nthree\nfour token=b";
+        assert_eq!(redacted_log_tail(text, 2), "three\nfour token=[redacted]");
+        assert_eq!(redacted_log_tail(text, 10).lines().count(), 4);
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Provides comprehensive error handling with PostgreSQL-specific details (FR-001 through FR-004).
 
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 use uuid::Uuid;
 
@@ -156,6 +157,14 @@ impl TuskError {
         Self::Connection { message: message.into(), source: Some(Box::new(source)) }
     }
 
+    /// Create a connection timeout error (E04).
+    pub fn connection_timeout(timeout: std::time::Duration) -> Self {
+        Self::connection(format!(
+            "Connection timed out after {}s - Server may be slow or unreachable. Check network connectivity",
+            timeout.as_secs()
+        ))
+    }
+
     /// Create a new authentication error.
     pub fn authentication(message: impl Into<String>) -> Self {
         Self::Authentication {
@@ -260,6 +269,25 @@ impl TuskError {
         matches!(self, Self::Connection { .. })
     }
 
+    /// Check whether a failed connection attempt may succeed if retried.
+    ///
+    /// True when the server was unreachable, slow, starting up, shutting down, or out of
+    /// connection slots. Authentication, SSL, and SSH failures, and a
+    /// database that doesn't exist, won't fix themselves.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::Connection { source, .. } => !matches!(
+                source
+                    .as_deref()
+                    .and_then(|e| e.downcast_ref::<tokio_postgres::Error>())
+                    .and_then(tokio_postgres::Error::code),
+                Some(code) if *code == tokio_postgres::error::SqlState::INVALID_CATALOG_NAME
+            ),
+            Self::PoolTimeout { .. } => true,
+            _ => false,
+        }
+    }
+
     /// Get the error category name.
     pub fn category(&self) -> &'static str {
         match self {
@@ -514,7 +542,11 @@ impl TuskError {
 }
 
 /// User-displayable error information (FR-003, FR-019, FR-020, FR-021).
-#[derive(Debug, Clone)]
+///
+/// Serializes with camelCase keys so the shape is stable for consumers
+/// outside Rust (e.g. `errorType`, `technicalDetail`).
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ErrorInfo {
     /// Category name (e.g., "Connection Error").
     pub error_type: String,
@@ -534,6 +566,14 @@ pub struct ErrorInfo {
     pub recoverable: bool,
 }
 
+/// Errors serialize through [`TuskError::to_error_info`] so every error
+/// crossing a serialization boundary has the same [`ErrorInfo`] shape.
+impl Serialize for TuskError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_error_info().serialize(serializer)
+    }
+}
+
 // ========== Error Conversions (FR-004) ==========
 
 /// Convert from tokio_postgres::Error to TuskError (T066, T067).
@@ -620,6 +660,14 @@ impl From<tokio_postgres::Error> for TuskError {
                     }
                 }
 
+                // Server still starting up or recovering; it will accept connections soon
+                "57P03" => {
+                    return TuskError::Connection {
+                        message: "Database server is starting up".to_string(),
+                        source: Some(Box::new(err)),
+                    }
+                }
+
                 // Query cancelled (E12, E13)
                 "57014" => {
                     // This is handled separately via QueryCancelled, but just in case
@@ -706,3 +754,83 @@ impl From<keyring::Error> for TuskError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    fn keys(value: &Value) -> Vec<&str> {
+        let mut keys: Vec<&str> = value.as_object().unwrap().keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    #[test]
+    fn test_error_info_serializes_camel_case_keys() {
+        let value = serde_json::to_value(TuskError::connection("refused").to_error_info()).unwrap();
+        assert_eq!(
+            keys(&value),
+            ["code", "errorType", "hint", "message", "position", "recoverable", "technicalDetail"]
+        );
+    }
+
+    #[test]
+    fn test_query_error_serialization() {
+        let error = TuskError::Query {
+            message: "relation \"users\" does not exist".to_string(),
+            detail: None,
+            hint: None,
+            position: Some(15),
+            code: Some("42P01".to_string()),
+        };
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["errorType"], "Query Error");
+        assert_eq!(value["code"], "42P01");
+        assert_eq!(value["position"], 15);
+        assert_eq!(value["recoverable"], true);
+        assert!(value["hint"].is_string());
+    }
+
+    #[test]
+    fn test_cancelled_and_storage_serialization() {
+        let cancelled = serde_json::to_value(TuskError::query_cancelled(Uuid::nil())).unwrap();
+        assert_eq!(cancelled["errorType"], "Query Cancelled");
+        assert_eq!(cancelled["hint"], Value::Null);
+        assert_eq!(cancelled["code"], Value::Null);
+
+        let storage = serde_json::to_value(TuskError::storage("disk full", None)).unwrap();
+        assert_eq!(storage["errorType"], "Storage Error");
+        assert_eq!(storage["recoverable"], false);
+        assert_eq!(keys(&storage), keys(&cancelled));
+    }
+
+    #[test]
+    fn test_is_retryable_by_category() {
+        assert!(TuskError::connection("Failed to establish connection: refused").is_retryable());
+        assert!(TuskError::connection_timeout(std::time::Duration::from_secs(5)).is_retryable());
+        assert!(TuskError::pool_timeout("exhausted", 2).is_retryable());
+
+        assert!(!TuskError::authentication("password authentication failed").is_retryable());
+        assert!(!TuskError::ssl("certificate verify failed").is_retryable());
+        assert!(!TuskError::ssh("key rejected").is_retryable());
+        assert!(!TuskError::config("bad port").is_retryable());
+        assert!(!TuskError::query("syntax error", None, None, Some(1), None).is_retryable());
+        assert!(!TuskError::query_cancelled(Uuid::nil()).is_retryable());
+    }
+
+    #[test]
+    fn test_serialized_error_matches_error_info() {
+        let error = TuskError::authentication("password authentication failed");
+        let expected = json!({
+            "errorType": "Authentication Failed",
+            "message": "password authentication failed",
+            "hint": "Check username and password",
+            "technicalDetail": null,
+            "position": null,
+            "code": null,
+            "recoverable": true,
+        });
+        assert_eq!(serde_json::to_value(&error).unwrap(), expected);
+    }
+}
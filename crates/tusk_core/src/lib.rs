@@ -2,27 +2,42 @@
 //!
 //! This crate provides the backend service layer for Tusk:
 //!
+//! - **diagnostics**: Redacted diagnostics bundles for bug reports
 //! - **error**: Error handling with PostgreSQL-specific details
 //! - **models**: Data structures for connections, queries, and history
 //! - **services**: Connection pooling, query execution, credentials, storage
+//! - **sql_lex**: Shared helpers for skipping SQL literals and comments
 //! - **state**: Application state management
 //! - **logging**: Structured logging setup
 
+pub mod diagnostics;
 pub mod error;
 pub mod logging;
 pub mod models;
 pub mod services;
+pub mod sql_lex;
 pub mod state;
 
+#[cfg(test)]
+mod test_support;
 #[cfg(test)]
 mod verification_tests;
 
 pub use error::TuskError;
 pub use models::{
-    ColumnDetail, ColumnInfo, ConnectionConfig, ConnectionOptions, ConnectionStatus,
-    DatabaseSchema, FunctionInfo, PoolStatus, QueryEvent, QueryHandle, QueryHistoryEntry,
-    QueryResult, QueryType, SchemaCache, SchemaInfo, SshAuthMethod, SshTunnelConfig, SslMode,
-    TableInfo, ViewInfo,
+    format_execution_time, BatchSummary, ClipboardFormat, ColumnDetail, ColumnInfo, ColumnSample,
+    ConfirmationReason, ConnectionConfig, ConnectionEnvironment, ConnectionOptions,
+    ConnectionStatus, ConstraintInfo, ConstraintKind, CopyFormat, CopyOptions, DatabaseSchema,
+    DiffKey, ExportFormat, FunctionInfo, IndexInfo, MarkdownOptions, NoticeSeverity, Notification,
+    NotificationEvent, ObjectEntry, ObjectIndex, PoolStatus, QueryEstimate, QueryEvent,
+    QueryHandle, QueryHistoryEntry, QueryPlan, QueryPlanNode, QueryResult, QueryType, ResultDiff,
+    RowChange, SchemaCache, SchemaInfo, SchemaObjectKind, ScriptEvent, SearchHit, SearchMatchKind,
+    Snippet, SshAuthMethod, SshTunnelConfig, SslMode, TableInfo, TransactionControl,
+    TransactionStatus, ValueCount, ViewInfo,
+};
+pub use services::{
+    ConnectionPool, CredentialBackend, CredentialService, DdlDumpOptions, EditTarget, InsertTarget,
+    LocalStorage, NoticeCapture, NotificationListener, QueryService, RecentConnection, RetryPolicy,
+    SchemaService, TransactionHandle,
 };
-pub use services::{ConnectionPool, CredentialService, LocalStorage, QueryService, SchemaService};
-pub use state::{ConnectionEntry, TuskState};
+pub use state::{ConnectionEntry, QueryRegistration, TuskState};
@@ -1,17 +1,40 @@
 //! Structured logging setup with console and file output.
 //!
 //! Provides:
-//! - Daily rotating log files (FR-023)
+//! - Daily rotating log files (FR-023), pruned by count and total size
 //! - Build-type conditional log levels (FR-024)
 //! - Console-only fallback when file logging fails (FR-024a)
 //! - Environment variable override via TUSK_LOG or RUST_LOG
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use chrono::NaiveDate;
 use tracing_appender::non_blocking::WorkerGuard;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_subscriber::fmt::writer::MakeWriterExt;
 use tracing_subscriber::EnvFilter;
 
+/// Prefix of rotated log file names (`tusk.2024-01-15.log`).
+const LOG_FILE_PREFIX: &str = "tusk";
+
+/// Suffix of rotated log file names.
+const LOG_FILE_SUFFIX: &str = "log";
+
+/// How many rotated log files are kept, and how much space they may use.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogRetention {
+    /// Most daily log files kept, including today's
+    pub max_files: usize,
+    /// Most bytes all kept log files may use together
+    pub max_total_bytes: u64,
+}
+
+impl Default for LogRetention {
+    fn default() -> Self {
+        Self { max_files: 14, max_total_bytes: 100 * 1024 * 1024 }
+    }
+}
+
 /// Logging configuration.
 pub struct LogConfig {
     /// Directory for log files
@@ -20,12 +43,19 @@ pub struct LogConfig {
     pub is_pty: bool,
     /// Optional custom log filter
     pub log_filter: Option<String>,
+    /// Which old log files are pruned
+    pub retention: LogRetention,
 }
 
 impl LogConfig {
     /// Create a new logging configuration.
     pub fn new(log_dir: PathBuf) -> Self {
-        Self { log_dir, is_pty: atty::is(atty::Stream::Stdout), log_filter: None }
+        Self {
+            log_dir,
+            is_pty: atty::is(atty::Stream::Stdout),
+            log_filter: None,
+            retention: LogRetention::default(),
+        }
     }
 
     /// Set custom log filter.
@@ -33,6 +63,12 @@ impl LogConfig {
         self.log_filter = Some(filter.into());
         self
     }
+
+    /// Set the retention policy for old log files.
+    pub fn with_retention(mut self, retention: LogRetention) -> Self {
+        self.retention = retention;
+        self
+    }
 }
 
 /// Guard that must be held for the lifetime of the application.
@@ -86,11 +122,17 @@ fn init_file_logging(config: &LogConfig) -> Result<WorkerGuard, Box<dyn std::err
     // Create log directory if needed
     std::fs::create_dir_all(&config.log_dir)?;
 
-    // Create daily rotating file appender (FR-023)
+    // Drop logs from earlier runs beyond the retention policy; failing to
+    // prune shouldn't cost the session its log file
+    let pruned = prune_log_files(&config.log_dir, config.retention);
+
+    // Create daily rotating file appender (FR-023); it also drops files
+    // beyond the count limit as days roll over
     let file_appender = RollingFileAppender::builder()
         .rotation(Rotation::DAILY)
-        .filename_prefix("tusk")
-        .filename_suffix("log")
+        .filename_prefix(LOG_FILE_PREFIX)
+        .filename_suffix(LOG_FILE_SUFFIX)
+        .max_log_files(config.retention.max_files.max(1))
         .build(&config.log_dir)?;
 
     // Non-blocking writes (SC-007: <100ms latency)
@@ -110,9 +152,73 @@ fn init_file_logging(config: &LogConfig) -> Result<WorkerGuard, Box<dyn std::err
         .with_thread_ids(false)
         .init();
 
+    match pruned {
+        Ok(0) => {}
+        Ok(pruned) => tracing::debug!(pruned, "Pruned old log files"),
+        Err(error) => tracing::warn!(%error, "Failed to prune old log files"),
+    }
     Ok(guard)
 }
 
+/// Delete rotated log files in `dir` that the retention policy doesn't keep.
+///
+/// Returns the number of files deleted. Files that aren't rotated logs are
+/// left alone.
+pub fn prune_log_files(dir: &Path, retention: LogRetention) -> std::io::Result<usize> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            if let Some(name) = entry.file_name().to_str() {
+                files.push((name.to_string(), metadata.len()));
+            }
+        }
+    }
+
+    let prune = log_files_to_prune(&files, retention);
+    for name in &prune {
+        std::fs::remove_file(dir.join(name))?;
+    }
+    Ok(prune.len())
+}
+
+/// Choose which rotated log files to delete, given file names and sizes.
+///
+/// The newest files are kept while they fit within both `max_files` and
+/// `max_total_bytes`; the newest file is always kept. Names that aren't
+/// dated log files are ignored.
+pub fn log_files_to_prune(files: &[(String, u64)], retention: LogRetention) -> Vec<String> {
+    let mut logs: Vec<(NaiveDate, &str, u64)> = files
+        .iter()
+        .filter_map(|(name, size)| Some((log_file_date(name)?, name.as_str(), *size)))
+        .collect();
+    // Newest first
+    logs.sort_by(|a, b| b.0.cmp(&a.0));
+
+    let mut kept_bytes = 0u64;
+    let mut prune = Vec::new();
+    for (index, (_, name, size)) in logs.into_iter().enumerate() {
+        kept_bytes = kept_bytes.saturating_add(size);
+        let keep =
+            index == 0 || (index < retention.max_files && kept_bytes <= retention.max_total_bytes);
+        if !keep {
+            prune.push(name.to_string());
+        }
+    }
+    prune
+}
+
+/// Parse the date of a rotated log file name like `tusk.2024-01-15.log`.
+fn log_file_date(name: &str) -> Option<NaiveDate> {
+    let date = name
+        .strip_prefix(LOG_FILE_PREFIX)?
+        .strip_prefix('.')?
+        .strip_suffix(LOG_FILE_SUFFIX)?
+        .strip_suffix('.')?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
 /// Build the environment filter from config or defaults (FR-024).
 fn build_env_filter(custom_filter: Option<&str>) -> EnvFilter {
     // Priority: custom filter > TUSK_LOG > RUST_LOG > default
@@ -139,5 +245,100 @@ pub fn default_log_filter() -> &'static str {
 
 /// Get the default log directory.
 pub fn log_dir() -> PathBuf {
-    crate::services::storage::default_data_dir().join("logs")
+    log_dir_in(&crate::services::storage::default_data_dir())
+}
+
+/// Get the log directory under a given data directory.
+pub fn log_dir_in(data_dir: &Path) -> PathBuf {
+    data_dir.join("logs")
+}
+
+/// Find the newest rotated log file in `dir`.
+pub fn latest_log_file(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            Some((log_file_date(&name)?, name))
+        })
+        .max()
+        .map(|(_, name)| dir.join(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logs(names: &[(&str, u64)]) -> Vec<(String, u64)> {
+        names.iter().map(|(name, size)| (name.to_string(), *size)).collect()
+    }
+
+    fn sorted(mut names: Vec<String>) -> Vec<String> {
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_prune_keeps_newest_files() {
+        let files = logs(&[
+            ("tusk.2024-01-03.log", 10),
+            ("tusk.2024-01-01.log", 10),
+            ("tusk.2024-01-05.log", 10),
+            ("tusk.2024-01-02.log", 10),
+            ("tusk.2024-01-04.log", 10),
+        ]);
+        let retention = LogRetention { max_files: 3, max_total_bytes: u64::MAX };
+        assert_eq!(
+            sorted(log_files_to_prune(&files, retention)),
+            vec!["tusk.2024-01-01.log", "tusk.2024-01-02.log"]
+        );
+
+        let retention = LogRetention { max_files: 10, max_total_bytes: u64::MAX };
+        assert!(log_files_to_prune(&files, retention).is_empty());
+    }
+
+    #[test]
+    fn test_prune_caps_total_size() {
+        let files = logs(&[
+            ("tusk.2024-03-01.log", 400),
+            ("tusk.2024-03-02.log", 400),
+            ("tusk.2024-03-03.log", 400),
+        ]);
+        let retention = LogRetention { max_files: 10, max_total_bytes: 1000 };
+        assert_eq!(log_files_to_prune(&files, retention), vec!["tusk.2024-03-01.log"]);
+
+        // Today's log is kept even when it alone is over the cap
+        let files = logs(&[("tusk.2024-03-02.log", 10), ("tusk.2024-03-03.log", 5000)]);
+        assert_eq!(log_files_to_prune(&files, retention), vec!["tusk.2024-03-02.log"]);
+    }
+
+    #[test]
+    fn test_prune_ignores_other_files() {
+        let files = logs(&[
+            ("tusk.2024-01-01.log", 10),
+            ("tusk.2024-01-02.log", 10),
+            ("tusk.db", 10),
+            ("tusk.2024-13-01.log", 10),
+            ("other.2023-01-01.log", 10),
+            ("tusk.2024-01-01.log.bak", 10),
+        ]);
+        let retention = LogRetention { max_files: 1, max_total_bytes: u64::MAX };
+        assert_eq!(log_files_to_prune(&files, retention), vec!["tusk.2024-01-01.log"]);
+    }
+
+    #[test]
+    fn test_prune_log_files_deletes_from_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in
+            [("tusk.2024-01-01.log", "a"), ("tusk.2024-01-02.log", "b"), ("notes.txt", "c")]
+        {
+            std::fs::write(dir.path().join(name), contents).unwrap();
+        }
+
+        let retention = LogRetention { max_files: 1, max_total_bytes: u64::MAX };
+        assert_eq!(prune_log_files(dir.path(), retention).unwrap(), 1);
+        assert!(!dir.path().join("tusk.2024-01-01.log").exists());
+        assert!(dir.path().join("tusk.2024-01-02.log").exists());
+        assert!(dir.path().join("notes.txt").exists());
+    }
 }
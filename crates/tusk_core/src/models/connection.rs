@@ -110,6 +110,59 @@ impl SslMode {
     }
 }
 
+/// Deployment environment a connection is tagged with.
+///
+/// Production connections are highlighted in the UI and every query that
+/// writes must be confirmed before it runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionEnvironment {
+    /// Development (default)
+    #[default]
+    Dev,
+    /// Staging
+    Staging,
+    /// Production
+    Prod,
+}
+
+impl ConnectionEnvironment {
+    /// All environments, in display order.
+    pub const ALL: [Self; 3] = [Self::Dev, Self::Staging, Self::Prod];
+
+    /// Convert to string representation for storage.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Dev => "dev",
+            Self::Staging => "staging",
+            Self::Prod => "prod",
+        }
+    }
+
+    /// Parse from string representation.
+    pub fn parse(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "staging" => Self::Staging,
+            "prod" | "production" => Self::Prod,
+            _ => Self::Dev,
+        }
+    }
+
+    /// Label shown in the UI.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Dev => "Dev",
+            Self::Staging => "Staging",
+            Self::Prod => "Prod",
+        }
+    }
+
+    /// Whether this is a production environment.
+    pub fn is_production(&self) -> bool {
+        *self == Self::Prod
+    }
+}
+
 /// SSH authentication method.
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -212,8 +265,47 @@ pub struct ConnectionOptions {
     pub read_only: bool,
     /// Application name sent to PostgreSQL
     pub application_name: String,
+    /// Rows a SELECT without its own LIMIT returns (None = no limit)
+    #[serde(default)]
+    pub row_limit: Option<u32>,
+    /// Schema put first on the search_path for new sessions (None = server default)
+    #[serde(default)]
+    pub default_schema: Option<String>,
+    /// Reuse prepared statements for the catalog queries Tusk runs itself
+    #[serde(default = "default_cache_prepared_statements")]
+    pub cache_prepared_statements: bool,
+    /// Rows collected before a streamed result is sent to the UI
+    #[serde(default = "default_stream_batch_size")]
+    pub stream_batch_size: u32,
+    /// Connect automatically when the app starts, retrying while the server comes up
+    #[serde(default)]
+    pub connect_on_startup: bool,
+    /// Rows per FETCH when streaming SELECTs through a server-side cursor
+    /// (None = stream the whole result from a single query)
+    #[serde(default)]
+    pub cursor_fetch_size: Option<u32>,
+}
+
+fn default_cache_prepared_statements() -> bool {
+    true
+}
+
+fn default_stream_batch_size() -> u32 {
+    DEFAULT_STREAM_BATCH_SIZE
 }
 
+/// Default row limit applied to SELECTs without a LIMIT.
+pub const DEFAULT_ROW_LIMIT: u32 = 1000;
+
+/// Default rows per streamed batch (FR-012).
+///
+/// Smaller batches update the results grid more often; larger ones send
+/// fewer channel messages for big results.
+pub const DEFAULT_STREAM_BATCH_SIZE: u32 = 1000;
+
+/// Largest allowed stream batch size.
+pub const MAX_STREAM_BATCH_SIZE: u32 = 100_000;
+
 impl Default for ConnectionOptions {
     fn default() -> Self {
         Self {
@@ -221,6 +313,12 @@ impl Default for ConnectionOptions {
             statement_timeout_secs: None,
             read_only: false,
             application_name: "Tusk".to_string(),
+            row_limit: Some(DEFAULT_ROW_LIMIT),
+            default_schema: None,
+            cache_prepared_statements: true,
+            stream_batch_size: DEFAULT_STREAM_BATCH_SIZE,
+            connect_on_startup: false,
+            cursor_fetch_size: None,
         }
     }
 }
@@ -251,6 +349,9 @@ pub struct ConnectionConfig {
     pub options: ConnectionOptions,
     /// UI accent color (hex format, e.g., "#FF5733")
     pub color: Option<String>,
+    /// Environment tag (production connections confirm writes)
+    #[serde(default)]
+    pub environment: ConnectionEnvironment,
 }
 
 impl ConnectionConfig {
@@ -272,6 +373,7 @@ impl ConnectionConfig {
             ssh_tunnel: None,
             options: ConnectionOptions::default(),
             color: None,
+            environment: ConnectionEnvironment::default(),
         }
     }
 
@@ -307,6 +409,14 @@ impl ConnectionConfig {
         Ok(())
     }
 
+    /// Check if both configurations reach the same host, port, database and user.
+    pub fn same_target(&self, other: &ConnectionConfig) -> bool {
+        self.host.eq_ignore_ascii_case(&other.host)
+            && self.port == other.port
+            && self.database == other.database
+            && self.username == other.username
+    }
+
     /// Get the display connection string (without password).
     pub fn display_url(&self) -> String {
         format!("postgresql://{}@{}:{}/{}", self.username, self.host, self.port, self.database)
@@ -325,6 +435,7 @@ pub struct ConnectionConfigBuilder {
     ssh_tunnel: Option<SshTunnelConfig>,
     options: ConnectionOptions,
     color: Option<String>,
+    environment: ConnectionEnvironment,
 }
 
 impl ConnectionConfigBuilder {
@@ -400,6 +511,18 @@ impl ConnectionConfigBuilder {
         self
     }
 
+    /// Set the row limit for SELECTs without a LIMIT (None = no limit).
+    pub fn row_limit(mut self, row_limit: Option<u32>) -> Self {
+        self.options.row_limit = row_limit;
+        self
+    }
+
+    /// Set the environment tag.
+    pub fn environment(mut self, environment: ConnectionEnvironment) -> Self {
+        self.environment = environment;
+        self
+    }
+
     /// Build the configuration.
     pub fn build(self) -> Result<ConnectionConfig, String> {
         let config = ConnectionConfig {
@@ -413,6 +536,7 @@ impl ConnectionConfigBuilder {
             ssh_tunnel: self.ssh_tunnel,
             options: self.options,
             color: self.color,
+            environment: self.environment,
         };
         config.validate()?;
         Ok(config)
@@ -420,7 +544,7 @@ impl ConnectionConfigBuilder {
 }
 
 /// Connection pool status (FR-013, SC-010).
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PoolStatus {
     /// Maximum pool capacity
     pub max_size: usize,
@@ -0,0 +1,346 @@
+//! Row-by-row comparison of two query results.
+//!
+//! Rows are paired either by position or by the values of key columns, then
+//! reported as added, removed, changed, or unchanged with the changed cells
+//! marked. Columns are matched by name; columns present on only one side are
+//! listed but never count as cell changes.
+
+use std::collections::{HashMap, VecDeque};
+
+use super::export::cell_text;
+use super::query::{ColumnInfo, QueryResult};
+
+/// How the rows of two results are paired up.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum DiffKey {
+    /// Pair the nth row with the nth row.
+    #[default]
+    Position,
+    /// Pair rows with equal values in these columns (e.g. the primary key).
+    Columns(Vec<String>),
+}
+
+/// What happened to a row between the two results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowChange {
+    /// Same values on both sides.
+    Unchanged,
+    /// Paired with a row whose values differ.
+    Changed,
+    /// Only in the second result.
+    Added,
+    /// Only in the first result.
+    Removed,
+}
+
+/// A column of the compared results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffColumn {
+    /// Column name
+    pub name: String,
+    /// Whether the first result has the column
+    pub in_before: bool,
+    /// Whether the second result has the column
+    pub in_after: bool,
+}
+
+/// A row of the comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffRow {
+    /// What happened to the row
+    pub change: RowChange,
+    /// Values in the first result, one per diff column (None for added rows)
+    pub before: Option<Vec<Option<String>>>,
+    /// Values in the second result, one per diff column (None for removed rows)
+    pub after: Option<Vec<Option<String>>>,
+    /// Diff columns whose value changed
+    pub changed_cells: Vec<usize>,
+}
+
+/// Comparison of two results, from [`ResultDiff::compute`] or [`QueryResult::diff`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResultDiff {
+    /// Columns of the first result, then columns only in the second
+    pub columns: Vec<DiffColumn>,
+    /// Rows in the first result's order, followed by added rows
+    pub rows: Vec<DiffRow>,
+    /// How rows were paired; position when a key column is missing on either side
+    pub key: DiffKey,
+}
+
+impl ResultDiff {
+    /// Compare two results given as columns and decoded rows (None for NULL).
+    pub fn compute(
+        before_columns: &[ColumnInfo],
+        before_rows: &[Vec<Option<String>>],
+        after_columns: &[ColumnInfo],
+        after_rows: &[Vec<Option<String>>],
+        key: &DiffKey,
+    ) -> Self {
+        let mut columns: Vec<DiffColumn> = Vec::new();
+        for column in before_columns {
+            if !columns.iter().any(|c| c.name == column.name) {
+                let in_after = after_columns.iter().any(|c| c.name == column.name);
+                columns.push(DiffColumn { name: column.name.clone(), in_before: true, in_after });
+            }
+        }
+        for column in after_columns {
+            if !columns.iter().any(|c| c.name == column.name) {
+                columns.push(DiffColumn {
+                    name: column.name.clone(),
+                    in_before: false,
+                    in_after: true,
+                });
+            }
+        }
+
+        // Source column of each diff column, per side
+        let lookup = |side: &[ColumnInfo]| -> Vec<Option<usize>> {
+            columns.iter().map(|c| side.iter().position(|s| s.name == c.name)).collect()
+        };
+        let before_index = lookup(before_columns);
+        let after_index = lookup(after_columns);
+        let align = |row: &Vec<Option<String>>, index: &[Option<usize>]| -> Vec<Option<String>> {
+            index.iter().map(|i| i.and_then(|i| row.get(i).cloned().flatten())).collect()
+        };
+        let before: Vec<_> = before_rows.iter().map(|row| align(row, &before_index)).collect();
+        let after: Vec<_> = after_rows.iter().map(|row| align(row, &after_index)).collect();
+
+        // Fall back to position when a key column is missing on either side
+        let key_columns: Option<Vec<usize>> = match key {
+            DiffKey::Position => None,
+            DiffKey::Columns(names) if names.is_empty() => None,
+            DiffKey::Columns(names) => names
+                .iter()
+                .map(|name| {
+                    columns.iter().position(|c| &c.name == name && c.in_before && c.in_after)
+                })
+                .collect(),
+        };
+
+        let pairs = match &key_columns {
+            Some(key_columns) => pair_by_key(&before, &after, key_columns),
+            None => pair_by_position(before.len(), after.len()),
+        };
+
+        let rows = pairs
+            .into_iter()
+            .map(|(b, a)| {
+                let before = b.map(|i| before[i].clone());
+                let after = a.map(|i| after[i].clone());
+                let (change, changed_cells) = match (&before, &after) {
+                    (Some(before), Some(after)) => {
+                        let changed: Vec<usize> = (0..columns.len())
+                            .filter(|&i| columns[i].in_before && columns[i].in_after)
+                            .filter(|&i| before[i] != after[i])
+                            .collect();
+                        if changed.is_empty() {
+                            (RowChange::Unchanged, changed)
+                        } else {
+                            (RowChange::Changed, changed)
+                        }
+                    }
+                    (Some(_), None) => (RowChange::Removed, Vec::new()),
+                    _ => (RowChange::Added, Vec::new()),
+                };
+                DiffRow { change, before, after, changed_cells }
+            })
+            .collect();
+
+        let key = match key_columns {
+            Some(_) => key.clone(),
+            None => DiffKey::Position,
+        };
+        Self { columns, rows, key }
+    }
+
+    /// Number of rows only in the second result.
+    pub fn added(&self) -> usize {
+        self.count(RowChange::Added)
+    }
+
+    /// Number of rows only in the first result.
+    pub fn removed(&self) -> usize {
+        self.count(RowChange::Removed)
+    }
+
+    /// Number of paired rows with different values.
+    pub fn changed(&self) -> usize {
+        self.count(RowChange::Changed)
+    }
+
+    /// Whether both results have the same columns and rows.
+    pub fn is_identical(&self) -> bool {
+        self.columns.iter().all(|c| c.in_before && c.in_after)
+            && self.rows.iter().all(|r| r.change == RowChange::Unchanged)
+    }
+
+    fn count(&self, change: RowChange) -> usize {
+        self.rows.iter().filter(|r| r.change == change).count()
+    }
+}
+
+impl QueryResult {
+    /// Compare this result with `other`, pairing rows by position.
+    pub fn diff(&self, other: &QueryResult) -> ResultDiff {
+        self.diff_by(other, &DiffKey::Position)
+    }
+
+    /// Compare this result with `other`, pairing rows as `key` says.
+    pub fn diff_by(&self, other: &QueryResult, key: &DiffKey) -> ResultDiff {
+        let decode = |result: &QueryResult| -> Vec<Vec<Option<String>>> {
+            result
+                .rows
+                .iter()
+                .map(|row| (0..result.columns.len()).map(|i| cell_text(row, i)).collect())
+                .collect()
+        };
+        ResultDiff::compute(&self.columns, &decode(self), &other.columns, &decode(other), key)
+    }
+}
+
+/// Pair rows by index, leaving the longer side's extra rows unpaired.
+fn pair_by_position(before: usize, after: usize) -> Vec<(Option<usize>, Option<usize>)> {
+    (0..before.max(after)).map(|i| ((i < before).then_some(i), (i < after).then_some(i))).collect()
+}
+
+/// Pair rows with equal key values, in order when a key repeats.
+///
+/// Unpaired rows of the second result are appended in their own order.
+fn pair_by_key(
+    before: &[Vec<Option<String>>],
+    after: &[Vec<Option<String>>],
+    key_columns: &[usize],
+) -> Vec<(Option<usize>, Option<usize>)> {
+    let key_of = |row: &Vec<Option<String>>| -> Vec<Option<String>> {
+        key_columns.iter().map(|&i| row[i].clone()).collect()
+    };
+
+    let mut unpaired: HashMap<Vec<Option<String>>, VecDeque<usize>> = HashMap::new();
+    for (i, row) in after.iter().enumerate() {
+        unpaired.entry(key_of(row)).or_default().push_back(i);
+    }
+
+    let mut paired = vec![false; after.len()];
+    let mut pairs: Vec<(Option<usize>, Option<usize>)> = before
+        .iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let matched = unpaired.get_mut(&key_of(row)).and_then(VecDeque::pop_front);
+            if let Some(j) = matched {
+                paired[j] = true;
+            }
+            (Some(i), matched)
+        })
+        .collect();
+    pairs.extend((0..after.len()).filter(|&j| !paired[j]).map(|j| (None, Some(j))));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(names: &[&str]) -> Vec<ColumnInfo> {
+        names
+            .iter()
+            .map(|name| ColumnInfo {
+                name: name.to_string(),
+                type_oid: 0,
+                type_name: "text".to_string(),
+                table_oid: None,
+                column_id: None,
+            })
+            .collect()
+    }
+
+    fn rows(cells: &[&[Option<&str>]]) -> Vec<Vec<Option<String>>> {
+        cells.iter().map(|row| row.iter().map(|c| c.map(String::from)).collect()).collect()
+    }
+
+    fn changes(diff: &ResultDiff) -> Vec<RowChange> {
+        diff.rows.iter().map(|r| r.change).collect()
+    }
+
+    #[test]
+    fn test_diff_aligns_by_position() {
+        let cols = columns(&["id", "name"]);
+        let before = rows(&[&[Some("1"), Some("a")], &[Some("2"), Some("b")]]);
+        let after = rows(&[&[Some("1"), Some("a")], &[Some("3"), Some("c")], &[Some("4"), None]]);
+
+        let diff = ResultDiff::compute(&cols, &before, &cols, &after, &DiffKey::Position);
+        assert_eq!(diff.key, DiffKey::Position);
+        assert_eq!(changes(&diff), [RowChange::Unchanged, RowChange::Changed, RowChange::Added]);
+        assert_eq!(diff.rows[1].changed_cells, [0, 1]);
+        assert_eq!(diff.rows[2].before, None);
+        assert_eq!((diff.added(), diff.removed(), diff.changed()), (1, 0, 1));
+        assert!(!diff.is_identical());
+
+        // Shorter second result: trailing rows are removed
+        let diff = ResultDiff::compute(&cols, &after, &cols, &before, &DiffKey::Position);
+        assert_eq!(changes(&diff), [RowChange::Unchanged, RowChange::Changed, RowChange::Removed]);
+
+        assert!(
+            ResultDiff::compute(&cols, &before, &cols, &before, &DiffKey::Position).is_identical()
+        );
+    }
+
+    #[test]
+    fn test_diff_aligns_by_key_column() {
+        let cols = columns(&["id", "name"]);
+        let before =
+            rows(&[&[Some("1"), Some("a")], &[Some("2"), Some("b")], &[Some("3"), Some("c")]]);
+        // Reordered, one changed, one removed, one added
+        let after =
+            rows(&[&[Some("4"), Some("d")], &[Some("3"), Some("c")], &[Some("1"), Some("A")]]);
+
+        let key = DiffKey::Columns(vec!["id".to_string()]);
+        let diff = ResultDiff::compute(&cols, &before, &cols, &after, &key);
+        assert_eq!(diff.key, key);
+        assert_eq!(
+            changes(&diff),
+            [RowChange::Changed, RowChange::Removed, RowChange::Unchanged, RowChange::Added]
+        );
+        assert_eq!(diff.rows[0].changed_cells, [1]);
+        assert_eq!(diff.rows[0].after.as_ref().unwrap()[1].as_deref(), Some("A"));
+        assert_eq!(diff.rows[3].after.as_ref().unwrap()[0].as_deref(), Some("4"));
+
+        // A key column missing from one side falls back to position
+        let missing = DiffKey::Columns(vec!["nope".to_string()]);
+        let diff = ResultDiff::compute(&cols, &before, &cols, &after, &missing);
+        assert_eq!(diff.key, DiffKey::Position);
+    }
+
+    #[test]
+    fn test_diff_with_different_columns() {
+        let before = rows(&[&[Some("1"), Some("a"), Some("x")]]);
+        let after = rows(&[&[Some("y"), Some("1"), Some("10")]]);
+        let diff = ResultDiff::compute(
+            &columns(&["id", "name", "extra"]),
+            &before,
+            &columns(&["extra", "id", "score"]),
+            &after,
+            &DiffKey::Columns(vec!["id".to_string()]),
+        );
+
+        let names: Vec<_> =
+            diff.columns.iter().map(|c| (c.name.as_str(), c.in_before, c.in_after)).collect();
+        assert_eq!(
+            names,
+            [
+                ("id", true, true),
+                ("name", true, false),
+                ("extra", true, true),
+                ("score", false, true)
+            ]
+        );
+        // Only shared columns count as changes; values line up by name
+        assert_eq!(changes(&diff), [RowChange::Changed]);
+        assert_eq!(diff.rows[0].changed_cells, [2]);
+        assert_eq!(
+            diff.rows[0].after.as_deref(),
+            Some(&[Some("1".to_string()), None, Some("y".to_string()), Some("10".to_string())][..])
+        );
+        assert!(!diff.is_identical());
+    }
+}
@@ -0,0 +1,699 @@
+//! Text exports of query results.
+//!
+//! Cells are decoded to their text form with [`cell_text`], then rendered by
+//! format-specific writers. Supports GitHub-flavored Markdown tables for
+//! pasting into documents and pull requests, CSV/JSON files via
+//! [`export_to_file`], and clipboard text for a range of cells via
+//! [`ClipboardFormat`].
+
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use tokio_postgres::types::{FromSql, Type};
+use tokio_postgres::Row;
+
+use super::query::{ColumnInfo, QueryResult};
+use super::value::scalar_text;
+use crate::error::TuskError;
+
+/// Options for rendering results as a Markdown table.
+#[derive(Debug, Clone)]
+pub struct MarkdownOptions {
+    /// Maximum number of rows to include (None for all rows)
+    pub max_rows: Option<usize>,
+    /// Text shown for NULL cells
+    pub null_text: String,
+}
+
+impl Default for MarkdownOptions {
+    fn default() -> Self {
+        Self { max_rows: Some(1000), null_text: "NULL".to_string() }
+    }
+}
+
+impl QueryResult {
+    /// Render the result as a GitHub-flavored Markdown table.
+    ///
+    /// Numeric columns are right-aligned and text columns left-aligned. When
+    /// rows are capped by `opts.max_rows`, a note with the full count follows
+    /// the table.
+    pub fn to_markdown(&self, opts: &MarkdownOptions) -> String {
+        let limit = opts.max_rows.unwrap_or(usize::MAX);
+        let rows: Vec<Vec<Option<String>>> = self
+            .rows
+            .iter()
+            .take(limit)
+            .map(|row| (0..self.columns.len()).map(|i| cell_text(row, i)).collect())
+            .collect();
+        markdown_table(&self.columns, &rows, self.row_count(), opts)
+    }
+}
+
+/// Render decoded rows as a GitHub-flavored Markdown table.
+///
+/// `total_rows` is the full result size; when it exceeds the rendered rows a
+/// "Showing N of M rows" note is appended.
+pub fn markdown_table(
+    columns: &[ColumnInfo],
+    rows: &[Vec<Option<String>>],
+    total_rows: usize,
+    opts: &MarkdownOptions,
+) -> String {
+    if columns.is_empty() {
+        return String::new();
+    }
+
+    let limit = opts.max_rows.unwrap_or(usize::MAX).min(rows.len());
+    let rows = &rows[..limit];
+    let numeric: Vec<bool> = columns.iter().map(|c| is_numeric_type(&c.type_name)).collect();
+
+    let header: Vec<String> = columns.iter().map(|c| escape_markdown_cell(&c.name)).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            (0..columns.len())
+                .map(|i| match row.get(i).and_then(Option::as_deref) {
+                    Some(value) => escape_markdown_cell(value),
+                    None => escape_markdown_cell(&opts.null_text),
+                })
+                .collect()
+        })
+        .collect();
+
+    // Pad columns so the raw Markdown lines up; separators need three dashes
+    let widths: Vec<usize> = (0..columns.len())
+        .map(|i| {
+            body.iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(header[i].chars().count()))
+                .max()
+                .unwrap_or(0)
+                .max(3)
+        })
+        .collect();
+
+    let mut out = String::new();
+    push_row(&mut out, &header, &widths, &numeric);
+    let separator: Vec<String> = widths
+        .iter()
+        .zip(&numeric)
+        .map(|(&width, &is_numeric)| {
+            if is_numeric {
+                format!("{}:", "-".repeat(width - 1))
+            } else {
+                format!(":{}", "-".repeat(width - 1))
+            }
+        })
+        .collect();
+    push_row(&mut out, &separator, &widths, &numeric);
+    for row in &body {
+        push_row(&mut out, row, &widths, &numeric);
+    }
+
+    if total_rows > rows.len() {
+        out.push_str(&format!("\n_Showing {} of {} rows_\n", rows.len(), total_rows));
+    }
+    out
+}
+
+/// Append one table row, padding each cell to its column width.
+fn push_row(out: &mut String, cells: &[String], widths: &[usize], numeric: &[bool]) {
+    out.push('|');
+    for ((cell, &width), &is_numeric) in cells.iter().zip(widths).zip(numeric) {
+        let pad = " ".repeat(width.saturating_sub(cell.chars().count()));
+        if is_numeric {
+            out.push_str(&format!(" {}{} |", pad, cell));
+        } else {
+            out.push_str(&format!(" {}{} |", cell, pad));
+        }
+    }
+    out.push('\n');
+}
+
+/// Escape text for a Markdown table cell.
+///
+/// Pipes would end the cell and newlines the row, so pipes are
+/// backslash-escaped and line breaks become `<br>`.
+pub fn escape_markdown_cell(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '|' => escaped.push_str("\\|"),
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => escaped.push_str("<br>"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// File formats results can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row (RFC 4180). NULL is an empty field.
+    Csv,
+    /// Objects keyed by column name: a JSON array, or one object per line
+    /// (JSON Lines) when `lines` is set.
+    Json { lines: bool },
+}
+
+impl ExportFormat {
+    /// Pick the format from a file extension (case-insensitive).
+    ///
+    /// `.csv` is CSV, `.json` is a JSON array, and `.jsonl`/`.ndjson` are
+    /// JSON Lines. Other extensions return None.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "json" => Some(Self::Json { lines: false }),
+            "jsonl" | "ndjson" => Some(Self::Json { lines: true }),
+            _ => None,
+        }
+    }
+
+    /// Display name of the format.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Json { lines: false } => "JSON",
+            Self::Json { lines: true } => "JSON Lines",
+        }
+    }
+}
+
+/// Text formats a range of result cells can be copied to the clipboard as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardFormat {
+    /// Tab-separated values without a header, for pasting into spreadsheets.
+    /// NULL is an empty cell.
+    Tsv,
+    /// Comma-separated values with a header row. NULL is an empty field.
+    Csv,
+    /// GitHub-flavored Markdown table. NULL is written as `NULL`.
+    Markdown,
+}
+
+impl ClipboardFormat {
+    /// Display name of the format.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tsv => "TSV",
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown Table",
+        }
+    }
+
+    /// Render decoded rows (NULL cells as None) for the clipboard.
+    pub fn format(&self, columns: &[ColumnInfo], rows: &[Vec<Option<String>>]) -> String {
+        match self {
+            Self::Tsv => rows.iter().map(|row| tsv_record(row)).collect::<Vec<_>>().join("\n"),
+            Self::Csv => {
+                let header = csv_record(columns.iter().map(|c| Some(c.name.as_str())));
+                std::iter::once(header)
+                    .chain(rows.iter().map(|row| csv_record(row.iter().map(|c| c.as_deref()))))
+                    .map(|line| line + "\n")
+                    .collect()
+            }
+            Self::Markdown => markdown_table(
+                columns,
+                rows,
+                rows.len(),
+                &MarkdownOptions { max_rows: None, ..MarkdownOptions::default() },
+            ),
+        }
+    }
+}
+
+/// Data formats for `COPY ... TO STDOUT` exports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// CSV as PostgreSQL writes it. NULL is an unquoted empty field.
+    Csv,
+    /// PostgreSQL's text format: tab-separated, NULL written as `\N`.
+    Text,
+}
+
+impl CopyFormat {
+    /// Keyword for the `FORMAT` option.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            Self::Csv => "csv",
+            Self::Text => "text",
+        }
+    }
+
+    /// Display name of the format.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Csv => "CSV",
+            Self::Text => "Text",
+        }
+    }
+}
+
+/// Options for streaming data with `COPY`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CopyOptions {
+    /// Data format
+    pub format: CopyFormat,
+    /// Column delimiter (None for the format's default: comma for CSV, tab for text)
+    pub delimiter: Option<char>,
+    /// Whether the first line holds column names
+    pub header: bool,
+}
+
+impl Default for CopyOptions {
+    fn default() -> Self {
+        Self { format: CopyFormat::Csv, delimiter: None, header: true }
+    }
+}
+
+impl CopyOptions {
+    /// Build the parenthesized option list for a `COPY` statement, e.g.
+    /// `(FORMAT csv, HEADER true)`.
+    ///
+    /// PostgreSQL only accepts a single one-byte delimiter that is not a
+    /// line break, so anything else is refused here.
+    pub fn with_clause(&self) -> Result<String, TuskError> {
+        let mut options = vec![format!("FORMAT {}", self.format.keyword())];
+        if let Some(delimiter) = self.delimiter {
+            if !delimiter.is_ascii() || matches!(delimiter, '\n' | '\r' | '\0') {
+                return Err(TuskError::query(
+                    format!("{delimiter:?} can't be used as a COPY delimiter"),
+                    None,
+                    Some("Choose a single ASCII character other than a line break".to_string()),
+                    None,
+                    None,
+                ));
+            }
+            let literal = if delimiter == '\'' { "''".to_string() } else { delimiter.to_string() };
+            options.push(format!("DELIMITER '{literal}'"));
+        }
+        options.push(format!("HEADER {}", self.header));
+        Ok(format!("({})", options.join(", ")))
+    }
+}
+
+/// Rows written between progress callbacks in [`write_export`].
+pub const EXPORT_PROGRESS_INTERVAL: usize = 1000;
+
+/// Write decoded rows to `out` in the given format.
+///
+/// `on_progress` is called with the number of rows written every
+/// [`EXPORT_PROGRESS_INTERVAL`] rows and once at the end.
+pub fn write_export<W: Write>(
+    format: ExportFormat,
+    columns: &[ColumnInfo],
+    rows: &[Vec<Option<String>>],
+    mut out: W,
+    mut on_progress: impl FnMut(usize),
+) -> io::Result<()> {
+    match format {
+        ExportFormat::Csv => {
+            let header: Vec<&str> = columns.iter().map(|c| c.name.as_str()).collect();
+            writeln!(out, "{}", csv_record(header.iter().map(|name| Some(*name))))?;
+        }
+        ExportFormat::Json { lines: false } => out.write_all(b"[")?,
+        ExportFormat::Json { lines: true } => {}
+    }
+
+    for (index, row) in rows.iter().enumerate() {
+        match format {
+            ExportFormat::Csv => {
+                writeln!(out, "{}", csv_record(row.iter().map(|cell| cell.as_deref())))?;
+            }
+            ExportFormat::Json { lines } => {
+                let object = json_object(columns, row);
+                if lines {
+                    writeln!(out, "{}", object)?;
+                } else {
+                    let separator = if index == 0 { "\n  " } else { ",\n  " };
+                    write!(out, "{}{}", separator, object)?;
+                }
+            }
+        }
+        if (index + 1) % EXPORT_PROGRESS_INTERVAL == 0 {
+            on_progress(index + 1);
+        }
+    }
+
+    if format == (ExportFormat::Json { lines: false }) {
+        out.write_all(if rows.is_empty() { b"]\n" } else { b"\n]\n" })?;
+    }
+    out.flush()?;
+    on_progress(rows.len());
+    Ok(())
+}
+
+/// Directory offered when choosing where to save an export.
+///
+/// The user's Downloads folder, falling back to the home directory.
+pub fn default_export_dir() -> PathBuf {
+    dirs::download_dir().or_else(dirs::home_dir).unwrap_or_else(std::env::temp_dir)
+}
+
+/// Export decoded rows to a file, returning the number of rows written.
+///
+/// The file is created (or truncated) and written through a buffer; see
+/// [`write_export`] for the progress callback.
+pub fn export_to_file(
+    path: &Path,
+    format: ExportFormat,
+    columns: &[ColumnInfo],
+    rows: &[Vec<Option<String>>],
+    on_progress: impl FnMut(usize),
+) -> Result<usize, TuskError> {
+    let file = File::create(path)?;
+    write_export(format, columns, rows, BufWriter::new(file), on_progress)?;
+    Ok(rows.len())
+}
+
+/// Format one CSV record, quoting fields that need it. None is an empty field.
+fn csv_record<'a>(fields: impl Iterator<Item = Option<&'a str>>) -> String {
+    fields
+        .map(|field| match field {
+            Some(value) if value.contains([',', '"', '\n', '\r']) => {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            }
+            Some(value) => value.to_string(),
+            None => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Format one TSV record the way spreadsheets read it. None is an empty cell.
+///
+/// Fields holding tabs or line breaks, or starting with a quote, are quoted
+/// with embedded quotes doubled; everything else is copied as is.
+fn tsv_record(row: &[Option<String>]) -> String {
+    row.iter()
+        .map(|cell| match cell.as_deref() {
+            Some(value) if value.contains(['\t', '\n', '\r']) || value.starts_with('"') => {
+                format!("\"{}\"", value.replace('"', "\"\""))
+            }
+            Some(value) => value.to_string(),
+            None => String::new(),
+        })
+        .collect::<Vec<_>>()
+        .join("\t")
+}
+
+/// Format one row as a JSON object, keeping the column order.
+///
+/// Integer, float, and boolean columns become JSON numbers and booleans;
+/// `json`/`jsonb` columns are embedded as JSON when they parse; everything
+/// else (including `numeric`, to keep its precision) is a string.
+fn json_object(columns: &[ColumnInfo], row: &[Option<String>]) -> String {
+    use serde_json::Value;
+
+    let fields: Vec<String> = columns
+        .iter()
+        .zip(row)
+        .map(|(column, cell)| {
+            let value = match cell {
+                None => Value::Null,
+                Some(text) => match column.type_name.as_str() {
+                    "int2" | "int4" | "int8" | "oid" | "float4" | "float8" => text
+                        .parse::<serde_json::Number>()
+                        .map(Value::Number)
+                        .unwrap_or_else(|_| Value::String(text.clone())),
+                    "bool" => match text.as_str() {
+                        "true" | "t" => Value::Bool(true),
+                        "false" | "f" => Value::Bool(false),
+                        _ => Value::String(text.clone()),
+                    },
+                    "json" | "jsonb" => {
+                        serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.clone()))
+                    }
+                    _ => Value::String(text.clone()),
+                },
+            };
+            format!("{}:{}", Value::String(column.name.clone()), value)
+        })
+        .collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+/// Check if a PostgreSQL type name is numeric.
+pub fn is_numeric_type(type_name: &str) -> bool {
+    matches!(
+        type_name,
+        "int2" | "int4" | "int8" | "float4" | "float8" | "numeric" | "oid" | "money"
+    )
+}
+
+/// Decode a cell to text, or None for NULL.
+///
+/// Values are decoded by [`scalar_text`]; types that have no text decoding
+/// are shown as `<type>`.
+pub fn cell_text(row: &Row, index: usize) -> Option<String> {
+    match row.try_get::<_, Option<CellText>>(index) {
+        Ok(value) => value.map(|CellText(text)| text),
+        Err(_) => Some(format!("<{}>", row.columns()[index].type_().name())),
+    }
+}
+
+/// Text form of a value of any type.
+struct CellText(String);
+
+impl<'a> FromSql<'a> for CellText {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn Error + Sync + Send>> {
+        let text = scalar_text(ty, raw)?.unwrap_or_else(|| format!("<{}>", ty.name()));
+        Ok(CellText(text))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns(specs: &[(&str, &str)]) -> Vec<ColumnInfo> {
+        specs
+            .iter()
+            .map(|(name, type_name)| ColumnInfo {
+                name: name.to_string(),
+                type_oid: 0,
+                type_name: type_name.to_string(),
+                table_oid: None,
+                column_id: None,
+            })
+            .collect()
+    }
+
+    fn row(cells: &[Option<&str>]) -> Vec<Option<String>> {
+        cells.iter().map(|c| c.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn test_copy_options_build_with_clause() {
+        assert_eq!(CopyOptions::default().with_clause().unwrap(), "(FORMAT csv, HEADER true)");
+
+        let text = CopyOptions { format: CopyFormat::Text, delimiter: Some('|'), header: false };
+        assert_eq!(text.with_clause().unwrap(), "(FORMAT text, DELIMITER '|', HEADER false)");
+
+        let quote = CopyOptions { delimiter: Some('\''), ..CopyOptions::default() };
+        assert_eq!(quote.with_clause().unwrap(), "(FORMAT csv, DELIMITER '''', HEADER true)");
+
+        let newline = CopyOptions { delimiter: Some('\n'), ..CopyOptions::default() };
+        assert!(newline.with_clause().unwrap_err().hint().is_some());
+        assert!(CopyOptions { delimiter: Some('é'), ..CopyOptions::default() }
+            .with_clause()
+            .is_err());
+    }
+
+    #[test]
+    fn test_markdown_escapes_pipes_and_newlines() {
+        assert_eq!(escape_markdown_cell("a|b"), "a\\|b");
+        assert_eq!(escape_markdown_cell("line 1\r\nline 2\nline 3"), "line 1<br>line 2<br>line 3");
+
+        let cols = columns(&[("expr", "text")]);
+        let rows = vec![row(&[Some("x || y")])];
+        let table = markdown_table(&cols, &rows, 1, &MarkdownOptions::default());
+
+        assert!(table.contains("| x \\|\\| y |"), "{}", table);
+        // Every line still has exactly two unescaped cell borders
+        for line in table.lines() {
+            assert_eq!(line.replace("\\|", "").matches('|').count(), 2, "{}", line);
+        }
+    }
+
+    #[test]
+    fn test_markdown_aligns_numeric_right_and_text_left() {
+        let cols = columns(&[("id", "int4"), ("name", "text"), ("total", "numeric")]);
+        let rows =
+            vec![row(&[Some("7"), Some("Ada"), Some("12.50")]), row(&[Some("42"), None, None])];
+        let table = markdown_table(&cols, &rows, 2, &MarkdownOptions::default());
+
+        assert_eq!(
+            table,
+            "\
+|  id | name | total |
+| --: | :--- | ----: |
+|   7 | Ada  | 12.50 |
+|  42 | NULL |  NULL |
+"
+        );
+    }
+
+    #[test]
+    fn test_markdown_caps_rows() {
+        let cols = columns(&[("n", "int8")]);
+        let rows: Vec<_> = (0..5).map(|n| vec![Some(n.to_string())]).collect();
+        let opts = MarkdownOptions { max_rows: Some(2), ..Default::default() };
+        let table = markdown_table(&cols, &rows, 5, &opts);
+
+        assert_eq!(table.lines().filter(|l| l.starts_with('|')).count(), 4);
+        assert!(table.ends_with("_Showing 2 of 5 rows_\n"));
+
+        let all = markdown_table(&cols, &rows, 5, &MarkdownOptions { max_rows: None, ..opts });
+        assert!(!all.contains("Showing"));
+    }
+
+    #[test]
+    fn test_markdown_without_columns_is_empty() {
+        assert_eq!(markdown_table(&[], &[], 0, &MarkdownOptions::default()), "");
+    }
+
+    #[test]
+    fn test_export_format_from_extension() {
+        let format = |path: &str| ExportFormat::from_path(Path::new(path));
+
+        assert_eq!(format("results.csv"), Some(ExportFormat::Csv));
+        assert_eq!(format("/tmp/Results.CSV"), Some(ExportFormat::Csv));
+        assert_eq!(format("results.json"), Some(ExportFormat::Json { lines: false }));
+        assert_eq!(format("results.jsonl"), Some(ExportFormat::Json { lines: true }));
+        assert_eq!(format("results.ndjson"), Some(ExportFormat::Json { lines: true }));
+        assert_eq!(format("results.txt"), None);
+        assert_eq!(format("results"), None);
+    }
+
+    #[test]
+    fn test_write_csv_quotes_fields() {
+        let cols = columns(&[("id", "int4"), ("note", "text")]);
+        let rows = vec![
+            row(&[Some("1"), Some("plain")]),
+            row(&[Some("2"), Some("a, \"quoted\"\nvalue")]),
+            row(&[Some("3"), None]),
+        ];
+        let mut out = Vec::new();
+        write_export(ExportFormat::Csv, &cols, &rows, &mut out, |_| {}).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "id,note\n1,plain\n2,\"a, \"\"quoted\"\"\nvalue\"\n3,\n"
+        );
+    }
+
+    #[test]
+    fn test_clipboard_formats() {
+        let cols = columns(&[("id", "int4"), ("note", "text")]);
+        let rows = vec![
+            row(&[Some("1"), Some("say \"hi\"")]),
+            row(&[Some("2"), None]),
+            row(&[Some("3"), Some("tab\there")]),
+        ];
+
+        // Spreadsheets read NULL as an empty cell; a lone cell isn't quoted
+        assert_eq!(
+            ClipboardFormat::Tsv.format(&cols, &rows),
+            "1\tsay \"hi\"\n2\t\n3\t\"tab\there\""
+        );
+        assert_eq!(ClipboardFormat::Tsv.format(&cols[..1], &[row(&[Some("7")])]), "7");
+        assert_eq!(
+            ClipboardFormat::Csv.format(&cols, &rows),
+            "id,note\n1,\"say \"\"hi\"\"\"\n2,\n3,tab\there\n"
+        );
+        let markdown = ClipboardFormat::Markdown.format(&cols, &rows);
+        assert!(markdown.starts_with("|  id | note     |\n"));
+        assert!(markdown.contains("|   2 | NULL     |\n"));
+        assert!(!markdown.contains("Showing"));
+    }
+
+    #[test]
+    fn test_write_json_types_values() {
+        let cols = columns(&[("id", "int8"), ("ok", "bool"), ("total", "numeric"), ("b", "text")]);
+        let rows = vec![
+            row(&[Some("1"), Some("true"), Some("12.50"), Some("x")]),
+            row(&[Some("2"), None, None, Some("y")]),
+        ];
+
+        let mut out = Vec::new();
+        write_export(ExportFormat::Json { lines: false }, &cols, &rows, &mut out, |_| {}).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(
+            parsed,
+            serde_json::json!([
+                {"id": 1, "ok": true, "total": "12.50", "b": "x"},
+                {"id": 2, "ok": null, "total": null, "b": "y"},
+            ])
+        );
+        // Column order is kept
+        assert!(text.contains(r#"{"id":1,"ok":true,"total":"12.50","b":"x"}"#), "{}", text);
+
+        let mut out = Vec::new();
+        write_export(ExportFormat::Json { lines: true }, &cols, &rows, &mut out, |_| {}).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.lines().all(|line| serde_json::from_str::<serde_json::Value>(line).is_ok()));
+
+        let mut out = Vec::new();
+        write_export(ExportFormat::Json { lines: false }, &cols, &[], &mut out, |_| {}).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "[]\n");
+    }
+
+    #[test]
+    fn test_export_to_file_reports_progress() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("numbers.csv");
+        let cols = columns(&[("n", "int4")]);
+        let rows: Vec<_> = (0..2_500).map(|n| vec![Some(n.to_string())]).collect();
+
+        let mut progress = Vec::new();
+        let written =
+            export_to_file(&path, ExportFormat::Csv, &cols, &rows, |n| progress.push(n)).unwrap();
+
+        assert_eq!(written, 2_500);
+        assert_eq!(progress, [1_000, 2_000, 2_500]);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2_501);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_cell_text_decodes_server_values() {
+        let pool = crate::test_support::local_pool(1).await;
+        let conn = pool.get().await.unwrap();
+        let rows = conn
+            .query(
+                "SELECT '{\"b\": [1, 2.50], \"a\": null}'::jsonb, '{\"b\":1}'::json, \
+                 12.50::numeric, '2024-03-01 09:30:00.25+00'::timestamptz, \
+                 '0a1b2c3d-0000-4000-8000-000000000001'::uuid, '10.0.0.1/24'::inet, \
+                 'NULL'::text, NULL::jsonb",
+                &[],
+            )
+            .await
+            .unwrap();
+        let texts: Vec<Option<String>> = (0..8).map(|i| cell_text(&rows[0], i)).collect();
+        let expected = [
+            Some(r#"{"a": null, "b": [1, 2.50]}"#),
+            Some(r#"{"b":1}"#),
+            Some("12.50"),
+            Some("2024-03-01 09:30:00.25+00"),
+            Some("0a1b2c3d-0000-4000-8000-000000000001"),
+            Some("10.0.0.1/24"),
+            Some("NULL"),
+            None,
+        ];
+        assert_eq!(texts, expected.map(|text| text.map(String::from)));
+    }
+}
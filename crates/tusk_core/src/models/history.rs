@@ -17,6 +17,10 @@ pub struct QueryHistoryEntry {
     pub sql: String,
     /// Time to execute (None if not completed)
     pub execution_time_ms: Option<i64>,
+    /// Time to execute in microseconds (None if not completed or recorded
+    /// before microsecond timing)
+    #[serde(default)]
+    pub execution_time_us: Option<i64>,
     /// Rows returned/affected
     pub row_count: Option<i64>,
     /// Error message if query failed
@@ -33,12 +37,32 @@ impl QueryHistoryEntry {
             connection_id,
             sql: sql.into(),
             execution_time_ms: Some(result.execution_time_ms as i64),
+            execution_time_us: Some(result.execution_time_us as i64),
             row_count: Some(result.rows.len() as i64),
             error_message: None,
             executed_at: Utc::now(),
         }
     }
 
+    /// Create a history entry from a completed run's row count and timing.
+    pub fn from_run(
+        connection_id: Uuid,
+        sql: impl Into<String>,
+        row_count: Option<u64>,
+        execution_time_us: u64,
+    ) -> Self {
+        Self {
+            id: 0, // Set by database
+            connection_id,
+            sql: sql.into(),
+            execution_time_ms: Some((execution_time_us / 1000) as i64),
+            execution_time_us: Some(execution_time_us as i64),
+            row_count: row_count.map(|rows| rows as i64),
+            error_message: None,
+            executed_at: Utc::now(),
+        }
+    }
+
     /// Create a history entry from a failed query.
     pub fn from_error(
         connection_id: Uuid,
@@ -50,6 +74,7 @@ impl QueryHistoryEntry {
             connection_id,
             sql: sql.into(),
             execution_time_ms: None,
+            execution_time_us: None,
             row_count: None,
             error_message: Some(error.to_string()),
             executed_at: Utc::now(),
@@ -63,6 +88,7 @@ impl QueryHistoryEntry {
             connection_id,
             sql: sql.into(),
             execution_time_ms: None,
+            execution_time_us: None,
             row_count: None,
             error_message: None,
             executed_at: Utc::now(),
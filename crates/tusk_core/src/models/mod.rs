@@ -2,21 +2,55 @@
 //!
 //! This module contains all core data structures:
 //! - `connection` - ConnectionConfig, ConnectionStatus, SslMode, SshTunnelConfig, PoolStatus
-//! - `query` - QueryHandle, QueryResult, QueryEvent, QueryType, ColumnInfo
+//! - `connection_url` - Parsing libpq connection URIs and keyword/value strings
+//! - `psql_files` - Parsing `.pg_service.conf` and `.pgpass` files for import
+//! - `diff` - Row-by-row comparison of two query results
+//! - `export` - Text exports of query results (Markdown tables, CSV/JSON files, clipboard
+//!   text, COPY options)
+//! - `query` - QueryHandle, QueryResult, QueryEvent, NoticeSeverity, ScriptEvent, QueryType, ColumnInfo
+//! - `query_builder` - Quoted SQL for common table actions (select, count, truncate)
 //! - `history` - QueryHistoryEntry
+//! - `notification` - Notification, NotificationEvent for LISTEN subscriptions
+//! - `object_index` - Fuzzy "go to object" index over a loaded schema
+//! - `snippet` - Snippet, placeholder expansion, and suggestions from history
 //! - `schema` - Schema introspection models, SchemaCache
+//! - `transaction` - TransactionStatus, TransactionControl for explicit transactions
+//! - `value` - Text form of binary PostgreSQL values (numeric, temporal, uuid, json, inet, ...)
 
 pub mod connection;
+pub mod connection_url;
+pub mod diff;
+pub mod export;
 pub mod history;
+pub mod notification;
+pub mod object_index;
+pub mod psql_files;
 pub mod query;
+pub mod query_builder;
 pub mod schema;
+pub mod snippet;
+pub mod transaction;
+pub mod value;
 
 pub use connection::{
-    ConnectionConfig, ConnectionOptions, ConnectionStatus, PoolStatus, SshAuthMethod,
-    SshTunnelConfig, SslMode,
+    ConnectionConfig, ConnectionEnvironment, ConnectionOptions, ConnectionStatus, PoolStatus,
+    SshAuthMethod, SshTunnelConfig, SslMode,
 };
+pub use diff::{DiffColumn, DiffKey, DiffRow, ResultDiff, RowChange};
+pub use export::{ClipboardFormat, CopyFormat, CopyOptions, ExportFormat, MarkdownOptions};
 pub use history::QueryHistoryEntry;
-pub use query::{ColumnInfo, QueryEvent, QueryHandle, QueryResult, QueryType};
+pub use notification::{Notification, NotificationEvent};
+pub use object_index::{ObjectEntry, ObjectIndex};
+pub use psql_files::PgPassEntry;
+pub use query::{
+    command_status, estimate_result_bytes, format_execution_time, BatchSummary, ColumnInfo,
+    ConfirmationReason, FirstRowTimer, NoticeSeverity, PgCancelGuard, QueryEstimate, QueryEvent,
+    QueryHandle, QueryPlan, QueryPlanNode, QueryResult, QueryType, RowBatcher, ScriptEvent,
+};
 pub use schema::{
-    ColumnDetail, DatabaseSchema, FunctionInfo, SchemaCache, SchemaInfo, TableInfo, ViewInfo,
+    ColumnDetail, ColumnSample, ConstraintInfo, ConstraintKind, DatabaseSchema, FunctionInfo,
+    IndexInfo, SchemaCache, SchemaCounts, SchemaInfo, SchemaObjectKind, SearchHit, SearchMatchKind,
+    TableInfo, ValueCount, ViewInfo,
 };
+pub use snippet::{suggest_snippets, Snippet};
+pub use transaction::{TransactionControl, TransactionStatus};
@@ -0,0 +1,31 @@
+//! Notifications received on channels subscribed with `LISTEN`.
+
+use crate::error::TuskError;
+
+/// A notification sent with `NOTIFY` or `pg_notify()`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// Channel the notification was sent on
+    pub channel: String,
+    /// Payload text, empty if none was given
+    pub payload: String,
+    /// Process ID of the notifying backend
+    pub pid: i32,
+}
+
+/// Events delivered to a `LISTEN` subscription.
+#[derive(Debug)]
+pub enum NotificationEvent {
+    /// A notification arrived on the channel.
+    Notification(Notification),
+
+    /// The listening connection failed or closed. Always the last event.
+    Error(TuskError),
+}
+
+impl NotificationEvent {
+    /// Check if this is a terminal event (Error).
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Error(_))
+    }
+}
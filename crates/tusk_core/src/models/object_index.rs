@@ -0,0 +1,347 @@
+//! Fuzzy "go to object" index over a loaded schema.
+//!
+//! [`ObjectIndex`] flattens a [`DatabaseSchema`] into one entry per table,
+//! view, function, and column, then ranks entries against a typed query.
+//! As the query grows, only the entries that matched the shorter query are
+//! rescanned, which keeps typing responsive with thousands of objects.
+
+use super::schema::{DatabaseSchema, SchemaObjectKind, SearchHit, SearchMatchKind};
+
+/// One navigable object in an [`ObjectIndex`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObjectEntry {
+    /// Kind of the object, or of the table or view a column belongs to.
+    pub kind: SchemaObjectKind,
+    /// Schema containing the object.
+    pub schema: String,
+    /// Object name; functions include their arguments (e.g. `add(integer, integer)`).
+    pub object: String,
+    /// Column name, for column entries.
+    pub column: Option<String>,
+    /// Lowercased name the query is ranked against (column or bare object name).
+    name: String,
+    /// Lowercased dotted path, matched when the name alone doesn't match.
+    path: String,
+}
+
+impl ObjectEntry {
+    fn new(
+        kind: SchemaObjectKind,
+        schema: &str,
+        object: String,
+        name: &str,
+        column: Option<&str>,
+    ) -> Self {
+        let path = match column {
+            Some(column) => format!("{}.{}.{}", schema, name, column),
+            None => format!("{}.{}", schema, name),
+        };
+        Self {
+            kind,
+            schema: schema.to_string(),
+            object,
+            column: column.map(String::from),
+            name: column.unwrap_or(name).to_lowercase(),
+            path: path.to_lowercase(),
+        }
+    }
+
+    /// The column for column entries, otherwise the object.
+    pub fn name(&self) -> &str {
+        self.column.as_deref().unwrap_or(&self.object)
+    }
+
+    /// The entry as a search hit, for revealing it in the schema browser.
+    pub fn to_hit(&self) -> SearchHit {
+        SearchHit {
+            object_kind: self.kind,
+            schema: self.schema.clone(),
+            object: self.object.clone(),
+            column: self.column.clone(),
+            match_kind: if self.column.is_some() {
+                SearchMatchKind::Column
+            } else {
+                SearchMatchKind::Name
+            },
+            matched_text: self.name().to_string(),
+        }
+    }
+}
+
+/// How an entry matched a query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchTier {
+    /// The name is the query.
+    Exact,
+    /// The name starts with the query.
+    Prefix,
+    /// The name contains the query.
+    Substring,
+    /// The query's characters appear in order in the name.
+    Fuzzy,
+    /// The query's characters appear in order in the dotted path only.
+    Path,
+}
+
+/// A flattened, searchable list of every object in a schema.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectIndex {
+    entries: Vec<ObjectEntry>,
+    /// Normalized query of the last search.
+    query: String,
+    /// Entries that matched `query`, in index order.
+    candidates: Vec<usize>,
+}
+
+impl ObjectIndex {
+    /// Index every table, view, function, and column in `schema`.
+    pub fn build(schema: &DatabaseSchema) -> Self {
+        let mut entries = Vec::new();
+        for table in &schema.tables {
+            entries.push(ObjectEntry::new(
+                SchemaObjectKind::Table,
+                &table.schema,
+                table.name.clone(),
+                &table.name,
+                None,
+            ));
+        }
+        for view in &schema.views {
+            entries.push(ObjectEntry::new(
+                SchemaObjectKind::View,
+                &view.schema,
+                view.name.clone(),
+                &view.name,
+                None,
+            ));
+        }
+        for func in &schema.functions {
+            entries.push(ObjectEntry::new(
+                SchemaObjectKind::Function,
+                &func.schema,
+                format!("{}({})", func.name, func.arguments),
+                &func.name,
+                None,
+            ));
+        }
+        let relations = schema
+            .table_columns
+            .iter()
+            .map(|entry| (SchemaObjectKind::Table, entry))
+            .chain(schema.view_columns.iter().map(|entry| (SchemaObjectKind::View, entry)));
+        for (kind, ((schema_name, relation), columns)) in relations {
+            for column in columns {
+                entries.push(ObjectEntry::new(
+                    kind,
+                    schema_name,
+                    relation.clone(),
+                    relation,
+                    Some(&column.name),
+                ));
+            }
+        }
+
+        Self { entries, query: String::new(), candidates: Vec::new() }
+    }
+
+    /// Number of indexed objects and columns.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the schema had nothing to index.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Rank the entries matching `query`, best first, keeping at most `limit`.
+    ///
+    /// Matching is case-insensitive. Exact names rank above prefixes, then
+    /// substrings, then fuzzy (in-order) name matches, then entries only
+    /// their dotted path matches, such as `sales.ord` for `sales.orders`.
+    /// Objects rank above columns, then shorter names first.
+    pub fn search(&mut self, query: &str, limit: usize) -> Vec<&ObjectEntry> {
+        let query = query.trim().to_lowercase();
+        if query.is_empty() {
+            self.query.clear();
+            self.candidates.clear();
+            return Vec::new();
+        }
+
+        // Anything matching the longer query matched its prefix, so only the
+        // previous candidates need checking.
+        let narrowing = !self.query.is_empty() && query.starts_with(&self.query);
+        let pool = if narrowing {
+            std::mem::take(&mut self.candidates)
+        } else {
+            (0..self.entries.len()).collect()
+        };
+
+        let mut ranked: Vec<(MatchTier, usize, usize)> = pool
+            .into_iter()
+            .filter_map(|ix| {
+                rank(&self.entries[ix], &query).map(|(tier, spread)| (tier, spread, ix))
+            })
+            .collect();
+        self.candidates = ranked.iter().map(|&(_, _, ix)| ix).collect();
+        self.query = query;
+
+        let entries = &self.entries;
+        ranked.sort_unstable_by(|a, b| {
+            let (x, y) = (&entries[a.2], &entries[b.2]);
+            (a.0, a.1, x.column.is_some(), x.name.len())
+                .cmp(&(b.0, b.1, y.column.is_some(), y.name.len()))
+                .then_with(|| x.path.cmp(&y.path))
+        });
+        ranked.into_iter().take(limit).map(|(_, _, ix)| &self.entries[ix]).collect()
+    }
+}
+
+/// Rank one entry against a normalized query, or None if it doesn't match.
+///
+/// The second value is how spread out a fuzzy match is (0 when contiguous).
+fn rank(entry: &ObjectEntry, query: &str) -> Option<(MatchTier, usize)> {
+    let name = entry.name.as_str();
+    if name == query {
+        Some((MatchTier::Exact, 0))
+    } else if name.starts_with(query) {
+        Some((MatchTier::Prefix, 0))
+    } else if name.contains(query) {
+        Some((MatchTier::Substring, 0))
+    } else if let Some(spread) = fuzzy_spread(name, query) {
+        Some((MatchTier::Fuzzy, spread))
+    } else {
+        fuzzy_spread(&entry.path, query).map(|spread| (MatchTier::Path, spread))
+    }
+}
+
+/// Check if `query`'s characters appear in order in `text`, returning how
+/// many unmatched characters lie between the first and last match.
+fn fuzzy_spread(text: &str, query: &str) -> Option<usize> {
+    let mut wanted = query.chars().peekable();
+    let mut first = None;
+    let mut matched = 0;
+    for (ix, ch) in text.chars().enumerate() {
+        if wanted.peek() == Some(&ch) {
+            wanted.next();
+            first.get_or_insert(ix);
+            matched += 1;
+            if wanted.peek().is_none() {
+                return Some(ix + 1 - first.unwrap_or(ix) - matched);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schema::fixtures::table;
+    use crate::models::schema::{ColumnDetail, FunctionInfo, ViewInfo};
+
+    fn column(name: &str) -> ColumnDetail {
+        ColumnDetail {
+            name: name.to_string(),
+            data_type: "text".to_string(),
+            is_nullable: true,
+            is_primary_key: false,
+            default_value: None,
+            ordinal_position: 1,
+            comment: None,
+        }
+    }
+
+    fn index() -> ObjectIndex {
+        let mut schema = DatabaseSchema {
+            tables: vec![
+                table("public", "orders"),
+                table("public", "order_lines"),
+                table("sales", "customer_orders"),
+                table("public", "users"),
+            ],
+            views: vec![ViewInfo {
+                schema: "public".to_string(),
+                name: "open_orders".to_string(),
+                owner: "postgres".to_string(),
+                is_materialized: false,
+                comment: None,
+            }],
+            functions: vec![FunctionInfo {
+                schema: "public".to_string(),
+                name: "order_total".to_string(),
+                return_type: "numeric".to_string(),
+                arguments: "integer".to_string(),
+                volatility: "STABLE".to_string(),
+                comment: None,
+            }],
+            ..Default::default()
+        };
+        schema.table_columns.insert(
+            ("public".to_string(), "users".to_string()),
+            vec![column("id"), column("orders_count")],
+        );
+        ObjectIndex::build(&schema)
+    }
+
+    fn paths(entries: Vec<&ObjectEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.to_hit().path()).collect()
+    }
+
+    #[test]
+    fn test_prefix_matches_rank_above_substrings() {
+        let mut index = index();
+        assert_eq!(index.len(), 8);
+        assert_eq!(
+            paths(index.search("Orders", 10)),
+            [
+                "public.orders",
+                "public.users.orders_count",
+                "public.open_orders",
+                "sales.customer_orders",
+                "public.order_lines",
+            ]
+        );
+        assert_eq!(
+            paths(index.search("order", 3)),
+            ["public.orders", "public.order_lines", "public.order_total(integer)"]
+        );
+    }
+
+    #[test]
+    fn test_fuzzy_and_path_matches() {
+        let mut index = index();
+        // Characters in order, tightest match first
+        assert_eq!(
+            paths(index.search("ordl", 10)),
+            ["public.order_lines", "public.order_total(integer)"]
+        );
+        // The schema only appears in the path
+        assert_eq!(paths(index.search("sales.cus", 10)), ["sales.customer_orders"]);
+        assert!(index.search("zzz", 10).is_empty());
+        assert!(index.search("   ", 10).is_empty());
+
+        let hit = index.search("orders_count", 1)[0].to_hit();
+        assert_eq!(hit.object_kind, SchemaObjectKind::Table);
+        assert_eq!(hit.match_kind, SearchMatchKind::Column);
+        assert_eq!(hit.column.as_deref(), Some("orders_count"));
+    }
+
+    #[test]
+    fn test_narrowing_matches_full_search() {
+        let mut incremental = index();
+        for query in ["o", "or", "ord", "orde", "order", "order_"] {
+            let narrowed = paths(incremental.search(query, 10));
+            assert_eq!(narrowed, paths(index().search(query, 10)), "query {query:?}");
+        }
+        // Backspacing starts over from the whole index
+        assert_eq!(paths(incremental.search("us", 2)), ["public.users", "sales.customer_orders"]);
+    }
+
+    #[test]
+    fn test_fuzzy_spread() {
+        assert_eq!(fuzzy_spread("orders", "ord"), Some(0));
+        assert_eq!(fuzzy_spread("order_lines", "ordl"), Some(3));
+        assert_eq!(fuzzy_spread("orders", "sro"), None);
+    }
+}
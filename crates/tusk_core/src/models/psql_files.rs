@@ -0,0 +1,235 @@
+//! Parsing of the connection files psql reads.
+//!
+//! - `.pg_service.conf`: INI-style sections of libpq keywords, one per service
+//! - `.pgpass`: `hostname:port:database:username:password` lines, where `*`
+//!   matches anything and `\:` / `\\` escape a colon or backslash
+//!
+//! Passwords are returned separately so they can go to the credential
+//! service instead of the configuration.
+
+use crate::error::TuskError;
+
+use super::connection::ConnectionConfig;
+
+/// Wildcard matching any value in a `.pgpass` field.
+const PGPASS_WILDCARD: &str = "*";
+
+/// Parse a `.pg_service.conf` file into one configuration per service.
+///
+/// Each configuration is named after its section. Keywords are the ones
+/// [`ConnectionConfig::from_url`] recognizes; a section without `user`
+/// falls back to the operating system user, as libpq does, and is skipped
+/// if that is unknown too.
+pub fn parse_pg_service(
+    contents: &str,
+) -> Result<Vec<(ConnectionConfig, Option<String>)>, TuskError> {
+    let mut sections: Vec<(String, Vec<(String, String)>)> = Vec::new();
+
+    for (number, line) in contents.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[') {
+            let name = name.strip_suffix(']').ok_or_else(|| {
+                TuskError::config(format!("Line {}: unterminated service name", number + 1))
+            })?;
+            sections.push((name.trim().to_string(), Vec::new()));
+            continue;
+        }
+        let (key, value) = line.split_once('=').ok_or_else(|| {
+            TuskError::config(format!("Line {}: expected keyword=value", number + 1))
+        })?;
+        let (_, params) = sections.last_mut().ok_or_else(|| {
+            TuskError::config(format!("Line {}: keyword outside of a [service]", number + 1))
+        })?;
+        params.push((key.trim().to_string(), value.trim().to_string()));
+    }
+
+    let mut services = Vec::with_capacity(sections.len());
+    for (name, mut params) in sections {
+        if !params.iter().any(|(key, value)| key == "user" && !value.is_empty()) {
+            let Some(user) = os_user() else {
+                tracing::warn!(service = %name, "Skipping service without a user");
+                continue;
+            };
+            // Ahead of the section's own keywords so dbname still defaults to it
+            params.insert(0, ("user".to_string(), user));
+        }
+        let (mut config, password) = ConnectionConfig::from_params(params)?;
+        config.name = name;
+        services.push((config, password));
+    }
+    Ok(services)
+}
+
+/// Operating system user name, the libpq default for `user`.
+fn os_user() -> Option<String> {
+    ["USER", "USERNAME", "LOGNAME"]
+        .into_iter()
+        .find_map(|var| std::env::var(var).ok().filter(|user| !user.is_empty()))
+}
+
+/// A line of a `.pgpass` file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgPassEntry {
+    /// Host name, or `*`
+    pub host: String,
+    /// Port, or `*`
+    pub port: String,
+    /// Database name, or `*`
+    pub database: String,
+    /// User name, or `*`
+    pub username: String,
+    /// Password for matching connections
+    pub password: String,
+}
+
+impl PgPassEntry {
+    /// Turn the entry into a connection configuration and its password.
+    ///
+    /// Wildcard hosts become `localhost`, wildcard ports 5432 and wildcard
+    /// databases the user name. Entries with a wildcard user or an invalid
+    /// port do not describe a single connection and return None.
+    pub fn to_config(&self) -> Option<(ConnectionConfig, String)> {
+        if self.username == PGPASS_WILDCARD || self.username.is_empty() {
+            return None;
+        }
+        let host = if self.host == PGPASS_WILDCARD { "localhost" } else { self.host.as_str() };
+        let database =
+            if self.database == PGPASS_WILDCARD { &self.username } else { &self.database };
+        let mut config = ConnectionConfig::new(
+            format!("{}@{}", database, host),
+            host,
+            database.as_str(),
+            self.username.as_str(),
+        );
+        if self.port != PGPASS_WILDCARD {
+            config.port = self.port.parse().ok().filter(|&port| port > 0)?;
+        }
+        Some((config, self.password.clone()))
+    }
+}
+
+/// Parse a `.pgpass` file.
+///
+/// Comment lines and lines with fewer than five fields are skipped, as libpq
+/// does. Colons after the fourth field belong to the password.
+pub fn parse_pgpass(contents: &str) -> Vec<PgPassEntry> {
+    contents
+        .lines()
+        .filter(|line| !line.trim_start().starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = split_pgpass_line(line.trim_end_matches('\r'));
+            if fields.len() != 5 {
+                return None;
+            }
+            let password = fields.pop()?;
+            let username = fields.pop()?;
+            let database = fields.pop()?;
+            let port = fields.pop()?;
+            let host = fields.pop()?;
+            Some(PgPassEntry { host, port, database, username, password })
+        })
+        .collect()
+}
+
+/// Split a `.pgpass` line on unescaped colons into at most five fields.
+fn split_pgpass_line(line: &str) -> Vec<String> {
+    let mut fields = vec![String::new()];
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                if let Some(escaped) = chars.next() {
+                    fields.last_mut().expect("at least one field").push(escaped);
+                }
+            }
+            ':' if fields.len() < 5 => fields.push(String::new()),
+            _ => fields.last_mut().expect("at least one field").push(c),
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::SslMode;
+
+    const SERVICES: &str = "\
+# Local development
+[dev]
+host=localhost
+port=5433
+dbname=app_dev
+user=alice
+
+[reporting]
+host = reports.internal
+dbname = warehouse
+user = analyst
+password = s3cret
+sslmode = require
+";
+
+    #[test]
+    fn test_parse_pg_service_sections() {
+        let services = parse_pg_service(SERVICES).unwrap();
+        assert_eq!(services.len(), 2);
+
+        let (dev, password) = &services[0];
+        assert_eq!(dev.name, "dev");
+        assert_eq!(dev.host, "localhost");
+        assert_eq!(dev.port, 5433);
+        assert_eq!(dev.database, "app_dev");
+        assert_eq!(dev.username, "alice");
+        assert_eq!(*password, None);
+
+        let (reporting, password) = &services[1];
+        assert_eq!(reporting.name, "reporting");
+        assert_eq!(reporting.host, "reports.internal");
+        assert_eq!(reporting.port, 5432);
+        assert_eq!(reporting.database, "warehouse");
+        assert_eq!(reporting.username, "analyst");
+        assert_eq!(reporting.ssl_mode, SslMode::Require);
+        assert_eq!(password.as_deref(), Some("s3cret"));
+    }
+
+    #[test]
+    fn test_parse_pg_service_rejects_malformed_lines() {
+        assert!(parse_pg_service("host=localhost\n").is_err());
+        assert!(parse_pg_service("[dev\nhost=localhost\n").is_err());
+        assert!(parse_pg_service("[dev]\nhost\n").is_err());
+        assert!(parse_pg_service("[dev]\nuser=a\nport=http\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_pgpass_escapes_and_wildcards() {
+        let entries = parse_pgpass(
+            "# comment\n\
+             db.example.com:5432:app:alice:pa\\:ss\n\
+             *:*:*:bob:x:y\n\
+             incomplete:5432\n",
+        );
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].password, "pa:ss");
+        assert_eq!(entries[1].password, "x:y");
+
+        let (config, password) = entries[0].to_config().unwrap();
+        assert_eq!(config.host, "db.example.com");
+        assert_eq!(config.database, "app");
+        assert_eq!(password, "pa:ss");
+
+        let (config, _) = entries[1].to_config().unwrap();
+        assert_eq!(config.host, "localhost");
+        assert_eq!(config.port, 5432);
+        assert_eq!(config.database, "bob");
+    }
+
+    #[test]
+    fn test_pgpass_wildcard_user_is_not_a_connection() {
+        let entries = parse_pgpass("localhost:5432:app:*:secret\nlocalhost:0:app:me:secret\n");
+        assert!(entries.iter().all(|entry| entry.to_config().is_none()));
+    }
+}
@@ -3,6 +3,7 @@
 use crate::error::TuskError;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
@@ -17,10 +18,25 @@ pub enum QueryType {
     Update,
     /// DELETE operation
     Delete,
-    /// DDL, COPY, or other operations
+    /// EXPLAIN returning a query plan; with ANALYZE the statement also runs
+    Explain,
+    /// CREATE, ALTER, DROP, TRUNCATE, or COMMENT, which may change the schema
+    Ddl,
+    /// Session and maintenance commands (SET, SHOW, VACUUM, ANALYZE, ...)
+    Utility,
+    /// COPY, transaction control, or other operations
     Other,
 }
 
+/// Why a script must be confirmed before it runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfirmationReason {
+    /// Statements that drop or erase data (DROP, TRUNCATE, unfiltered DELETE/UPDATE).
+    Destructive(Vec<String>),
+    /// The connection is tagged production and the script may write.
+    Production,
+}
+
 /// Column metadata from query results (FR-014).
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ColumnInfo {
@@ -30,6 +46,71 @@ pub struct ColumnInfo {
     pub type_oid: u32,
     /// Human-readable type name
     pub type_name: String,
+    /// OID of the table the column comes from (None for computed columns)
+    #[serde(default)]
+    pub table_oid: Option<u32>,
+    /// Attribute number of the column within `table_oid`
+    #[serde(default)]
+    pub column_id: Option<i16>,
+}
+
+/// Result size, in estimated bytes, above which rendering is discouraged.
+pub const LARGE_RESULT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// Assumed width of a text, JSON, bytea, or array value, whose size varies.
+const VARIABLE_WIDTH_BYTES: u64 = 48;
+
+/// Bookkeeping per value (its offset range within the row).
+const VALUE_OVERHEAD_BYTES: u64 = 16;
+
+/// Bookkeeping per row (its buffer and range table).
+const ROW_OVERHEAD_BYTES: u64 = 64;
+
+impl ColumnInfo {
+    /// Estimate the bytes a value of this column takes in memory.
+    ///
+    /// Fixed-width types use their wire size; variable-width types like text
+    /// and JSON assume a typical value width.
+    pub fn estimated_value_bytes(&self) -> u64 {
+        use tokio_postgres::types::Type;
+
+        let width = match Type::from_oid(self.type_oid) {
+            Some(Type::BOOL | Type::CHAR) => 1,
+            Some(Type::INT2) => 2,
+            Some(Type::INT4 | Type::OID | Type::FLOAT4 | Type::DATE) => 4,
+            Some(
+                Type::INT8
+                | Type::FLOAT8
+                | Type::MONEY
+                | Type::TIME
+                | Type::TIMESTAMP
+                | Type::TIMESTAMPTZ,
+            ) => 8,
+            Some(Type::TIMETZ) => 12,
+            Some(Type::UUID | Type::INTERVAL | Type::NUMERIC) => 16,
+            _ => VARIABLE_WIDTH_BYTES,
+        };
+        width + VALUE_OVERHEAD_BYTES
+    }
+}
+
+/// Estimate the in-memory size of `rows` rows with the given columns.
+pub fn estimate_result_bytes(columns: &[ColumnInfo], rows: usize) -> u64 {
+    let row_bytes: u64 =
+        ROW_OVERHEAD_BYTES + columns.iter().map(ColumnInfo::estimated_value_bytes).sum::<u64>();
+    row_bytes.saturating_mul(rows as u64)
+}
+
+impl From<&tokio_postgres::Column> for ColumnInfo {
+    fn from(col: &tokio_postgres::Column) -> Self {
+        Self {
+            name: col.name().to_string(),
+            type_oid: col.type_().oid(),
+            type_name: col.type_().name().to_string(),
+            table_oid: col.table_oid(),
+            column_id: col.column_id(),
+        }
+    }
 }
 
 /// Stream events during query execution (FR-011, FR-012, FR-014).
@@ -39,15 +120,23 @@ pub struct ColumnInfo {
 ///
 /// ## Event Ordering
 /// 1. `Columns` - Always sent first (for grid setup)
-/// 2. `Rows` - Sent in batches as rows are retrieved
-/// 3. `Progress` - Optional, for large queries (>10,000 rows)
-/// 4. `Complete` or `Error` - Exactly one, as final event
+/// 2. `FirstRow` - Once, when the first row arrives (not sent for empty results)
+/// 3. `Rows` - Sent in batches as rows are retrieved
+/// 4. `Progress` - Optional, for large queries (>10,000 rows)
+/// 5. `Complete` or `Error` - Exactly one, as final event
 #[derive(Debug)]
 pub enum QueryEvent {
     /// Column metadata for result grid setup (FR-014).
     /// Always sent first, before any Rows events.
     Columns(Vec<ColumnInfo>),
 
+    /// Time until the first row arrived, for responsiveness metrics (SC-002).
+    /// Sent once, before any Rows events.
+    FirstRow {
+        /// Milliseconds from sending the query to receiving its first row
+        elapsed_ms: u64,
+    },
+
     /// Batch of result rows with running total (FR-011, FR-012).
     /// Default batch size is 1000 rows.
     Rows {
@@ -69,15 +158,84 @@ pub enum QueryEvent {
     Complete {
         /// Final row count
         total_rows: usize,
-        /// Query execution time in milliseconds
+        /// Query execution time in milliseconds (at least 1 if any time passed)
         execution_time_ms: u64,
+        /// Query execution time in microseconds
+        execution_time_us: u64,
         /// Rows affected (for INSERT/UPDATE/DELETE, None for SELECT)
         rows_affected: Option<u64>,
+        /// Command tag for statements that return no columns (e.g. "SET")
+        command_tag: Option<String>,
     },
 
     /// Query failed with error (FR-019, FR-020, FR-021).
     /// Mutually exclusive with Complete; exactly one is sent.
     Error(TuskError),
+
+    /// A notice the server raised while the query ran (`RAISE NOTICE`,
+    /// warnings, and the like). Not terminal; may arrive between any of the
+    /// other events.
+    Notice {
+        /// Severity the server reported
+        severity: NoticeSeverity,
+        /// Primary message text
+        message: String,
+    },
+}
+
+/// Severity of a notice raised by the server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoticeSeverity {
+    /// DEBUG1 through DEBUG5
+    Debug,
+    /// LOG
+    Log,
+    /// INFO
+    Info,
+    /// NOTICE
+    Notice,
+    /// WARNING
+    Warning,
+}
+
+impl NoticeSeverity {
+    /// Map the severity tokio-postgres parsed from a notice, treating anything
+    /// unrecognized as a NOTICE.
+    pub fn from_pg(severity: Option<tokio_postgres::error::Severity>) -> Self {
+        use tokio_postgres::error::Severity;
+        match severity {
+            Some(Severity::Warning) => Self::Warning,
+            Some(Severity::Info) => Self::Info,
+            Some(Severity::Log) => Self::Log,
+            Some(Severity::Debug) => Self::Debug,
+            _ => Self::Notice,
+        }
+    }
+
+    /// Get the severity as PostgreSQL spells it.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Debug => "DEBUG",
+            Self::Log => "LOG",
+            Self::Info => "INFO",
+            Self::Notice => "NOTICE",
+            Self::Warning => "WARNING",
+        }
+    }
+}
+
+/// A [`QueryEvent`] from one statement of a multi-statement script.
+///
+/// Sent by `QueryService::execute_script`. Each statement's events follow
+/// the usual [`QueryEvent`] ordering, and statements arrive in script order.
+#[derive(Debug)]
+pub struct ScriptEvent {
+    /// Position of the statement in the script, starting at 1
+    pub statement: usize,
+    /// Byte offset of the statement in the script
+    pub offset: usize,
+    /// The statement's event
+    pub event: QueryEvent,
 }
 
 impl QueryEvent {
@@ -86,6 +244,11 @@ impl QueryEvent {
         Self::Columns(columns)
     }
 
+    /// Create a FirstRow event from the time the first row took to arrive.
+    pub fn first_row(elapsed: Duration) -> Self {
+        Self::FirstRow { elapsed_ms: duration_ms(elapsed) }
+    }
+
     /// Create a Rows event.
     pub fn rows(rows: Vec<tokio_postgres::Row>, total_so_far: usize) -> Self {
         Self::Rows { rows, total_so_far }
@@ -96,9 +259,30 @@ impl QueryEvent {
         Self::Progress { rows_so_far }
     }
 
-    /// Create a Complete event.
-    pub fn complete(total_rows: usize, execution_time_ms: u64, rows_affected: Option<u64>) -> Self {
-        Self::Complete { total_rows, execution_time_ms, rows_affected }
+    /// Create a Complete event from the measured execution time.
+    pub fn complete(total_rows: usize, elapsed: Duration, rows_affected: Option<u64>) -> Self {
+        Self::Complete {
+            total_rows,
+            execution_time_ms: duration_ms(elapsed),
+            execution_time_us: duration_us(elapsed),
+            rows_affected,
+            command_tag: None,
+        }
+    }
+
+    /// Create a Complete event for a statement that returns no columns.
+    pub fn command_complete(
+        command_tag: impl Into<String>,
+        elapsed: Duration,
+        rows_affected: Option<u64>,
+    ) -> Self {
+        Self::Complete {
+            total_rows: 0,
+            execution_time_ms: duration_ms(elapsed),
+            execution_time_us: duration_us(elapsed),
+            rows_affected,
+            command_tag: Some(command_tag.into()),
+        }
     }
 
     /// Create an Error event.
@@ -106,12 +290,84 @@ impl QueryEvent {
         Self::Error(err)
     }
 
+    /// Create a Notice event.
+    pub fn notice(severity: NoticeSeverity, message: impl Into<String>) -> Self {
+        Self::Notice { severity, message: message.into() }
+    }
+
     /// Check if this is a terminal event (Complete or Error).
     pub fn is_terminal(&self) -> bool {
         matches!(self, Self::Complete { .. } | Self::Error(_))
     }
 }
 
+/// Produces the [`QueryEvent::FirstRow`] event for a streaming query.
+///
+/// Every arriving row is recorded; only the first yields an event.
+#[derive(Debug)]
+pub struct FirstRowTimer {
+    /// When the query was sent
+    start: Instant,
+    /// Whether the first row has been seen
+    fired: bool,
+}
+
+impl FirstRowTimer {
+    /// Start timing from when the query was sent.
+    pub fn new(start: Instant) -> Self {
+        Self { start, fired: false }
+    }
+
+    /// Record an arriving row, returning the FirstRow event for the first one.
+    pub fn record_row(&mut self) -> Option<QueryEvent> {
+        if self.fired {
+            return None;
+        }
+        self.fired = true;
+        Some(QueryEvent::first_row(self.start.elapsed()))
+    }
+}
+
+/// Groups streamed rows into [`QueryEvent::Rows`] batches (FR-012).
+///
+/// A batch is released each time `batch_size` rows have been pushed, and any
+/// remainder when the stream ends.
+#[derive(Debug)]
+pub struct RowBatcher<T> {
+    /// Rows per batch (at least 1)
+    batch_size: usize,
+    /// Rows collected since the last batch
+    rows: Vec<T>,
+}
+
+impl<T> RowBatcher<T> {
+    /// Create a batcher releasing `batch_size` rows at a time.
+    pub fn new(batch_size: usize) -> Self {
+        let batch_size = batch_size.max(1);
+        Self { batch_size, rows: Vec::with_capacity(batch_size) }
+    }
+
+    /// Add a row, returning the full batch once `batch_size` rows are held.
+    pub fn push(&mut self, row: T) -> Option<Vec<T>> {
+        self.rows.push(row);
+        (self.rows.len() >= self.batch_size)
+            .then(|| std::mem::replace(&mut self.rows, Vec::with_capacity(self.batch_size)))
+    }
+
+    /// Take the rows left when the stream ends, if any.
+    pub fn finish(self) -> Option<Vec<T>> {
+        (!self.rows.is_empty()).then_some(self.rows)
+    }
+}
+
+/// PostgreSQL cancel token of the connection a query runs on, with the
+/// runtime the cancel request is sent from.
+#[derive(Clone)]
+struct PgCancel {
+    token: tokio_postgres::CancelToken,
+    runtime: Option<tokio::runtime::Handle>,
+}
+
 /// Handle for tracking and cancelling a running query (FR-014, FR-015, FR-016).
 pub struct QueryHandle {
     /// Unique query identifier
@@ -120,10 +376,12 @@ pub struct QueryHandle {
     connection_id: Uuid,
     /// The SQL being executed
     sql: String,
+    /// The SQL as the user wrote it, when `sql` was rewritten (e.g. a row limit added)
+    source_sql: Option<String>,
     /// Cancellation token for interrupting the query (tokio-util)
     cancel_token: CancellationToken,
     /// PostgreSQL cancel token for sending cancel to server
-    pg_cancel_token: std::sync::RwLock<Option<tokio_postgres::CancelToken>>,
+    pg_cancel: std::sync::RwLock<Option<PgCancel>>,
     /// Execution start time
     started_at: DateTime<Utc>,
 }
@@ -135,24 +393,56 @@ impl QueryHandle {
             id: Uuid::new_v4(),
             connection_id,
             sql: sql.into(),
+            source_sql: None,
             cancel_token: CancellationToken::new(),
-            pg_cancel_token: std::sync::RwLock::new(None),
+            pg_cancel: std::sync::RwLock::new(None),
             started_at: Utc::now(),
         }
     }
 
+    /// Note the SQL the user wrote, when the executed SQL was rewritten from it.
+    pub fn with_source_sql(mut self, sql: impl Into<String>) -> Self {
+        self.source_sql = Some(sql.into());
+        self
+    }
+
     /// Set the PostgreSQL cancel token for server-side cancellation.
     ///
     /// This should be called when the query starts executing on a connection.
+    /// Cancel requests are sent from the runtime this is called on. Prefer
+    /// [`Self::track_pg_cancel`], which also clears the token afterwards.
     pub fn set_pg_cancel_token(&self, token: tokio_postgres::CancelToken) {
-        if let Ok(mut guard) = self.pg_cancel_token.write() {
-            *guard = Some(token);
+        let runtime = tokio::runtime::Handle::try_current().ok();
+        if let Ok(mut guard) = self.pg_cancel.write() {
+            *guard = Some(PgCancel { token, runtime });
+        }
+    }
+
+    /// Set the PostgreSQL cancel token until the returned guard is dropped.
+    ///
+    /// Pooled connections are reused once a query finishes, so the token
+    /// must not outlive the query or a late cancel would interrupt whatever
+    /// runs on the connection next.
+    pub fn track_pg_cancel(&self, token: tokio_postgres::CancelToken) -> PgCancelGuard<'_> {
+        self.set_pg_cancel_token(token);
+        PgCancelGuard { handle: self }
+    }
+
+    /// Forget the PostgreSQL cancel token, once the query has finished.
+    pub fn clear_pg_cancel_token(&self) {
+        if let Ok(mut guard) = self.pg_cancel.write() {
+            *guard = None;
         }
     }
 
     /// Get the PostgreSQL cancel token (if set).
     pub fn get_pg_cancel_token(&self) -> Option<tokio_postgres::CancelToken> {
-        self.pg_cancel_token.read().ok().and_then(|guard| guard.clone())
+        self.pg_cancel.read().ok().and_then(|guard| guard.as_ref().map(|c| c.token.clone()))
+    }
+
+    /// Take the PostgreSQL cancel token, so [`Self::cancel`] won't send it.
+    pub(crate) fn take_pg_cancel_token(&self) -> Option<tokio_postgres::CancelToken> {
+        self.pg_cancel.write().ok().and_then(|mut guard| guard.take()).map(|c| c.token)
     }
 
     /// Get the unique query identifier.
@@ -170,6 +460,11 @@ impl QueryHandle {
         &self.sql
     }
 
+    /// Get the SQL as the user wrote it, for history and logs.
+    pub fn source_sql(&self) -> &str {
+        self.source_sql.as_deref().unwrap_or(&self.sql)
+    }
+
     /// Get when execution started.
     pub fn started_at(&self) -> DateTime<Utc> {
         self.started_at
@@ -186,9 +481,47 @@ impl QueryHandle {
     }
 
     /// Request cancellation of the query.
+    ///
+    /// Signals the cancellation token and, while the query runs on a
+    /// connection, sends PostgreSQL a cancel request over a separate
+    /// connection so a statement already executing on the server stops too
+    /// (SC-003). Only the first call sends a request.
     pub fn cancel(&self) {
+        if self.cancel_token.is_cancelled() {
+            return;
+        }
         tracing::debug!(query_id = %self.id, "Cancellation requested");
         self.cancel_token.cancel();
+        self.send_pg_cancel();
+    }
+
+    /// Send the PostgreSQL cancel request, if the query is on a connection.
+    fn send_pg_cancel(&self) {
+        let Some(PgCancel { token, runtime }) = self.pg_cancel.read().ok().and_then(|g| g.clone())
+        else {
+            return;
+        };
+        let Some(runtime) = runtime.or_else(|| tokio::runtime::Handle::try_current().ok()) else {
+            tracing::warn!(query_id = %self.id, "No runtime to send PostgreSQL cancel request");
+            return;
+        };
+        let query_id = self.id;
+        runtime.spawn(async move {
+            // Use NoTls for cancel request (same as connection)
+            match token.cancel_query(tokio_postgres::NoTls).await {
+                Ok(()) => {
+                    tracing::debug!(query_id = %query_id, "PostgreSQL cancel request sent");
+                }
+                Err(e) => {
+                    // Cancel request failed, but cooperative cancellation still works
+                    tracing::warn!(
+                        query_id = %query_id,
+                        error = %e,
+                        "Failed to send PostgreSQL cancel request"
+                    );
+                }
+            }
+        });
     }
 
     /// Check if cancellation has been requested.
@@ -207,6 +540,19 @@ impl QueryHandle {
     }
 }
 
+/// Clears a query's PostgreSQL cancel token when dropped.
+///
+/// Returned by [`QueryHandle::track_pg_cancel`].
+pub struct PgCancelGuard<'a> {
+    handle: &'a QueryHandle,
+}
+
+impl Drop for PgCancelGuard<'_> {
+    fn drop(&mut self) {
+        self.handle.clear_pg_cancel_token();
+    }
+}
+
 impl std::fmt::Debug for QueryHandle {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("QueryHandle")
@@ -229,10 +575,14 @@ pub struct QueryResult {
     pub rows: Vec<tokio_postgres::Row>,
     /// Rows affected (for INSERT/UPDATE/DELETE)
     pub rows_affected: Option<u64>,
-    /// Time to execute in milliseconds
+    /// Time to execute in milliseconds (at least 1 if any time passed)
     pub execution_time_ms: u64,
+    /// Time to execute in microseconds
+    pub execution_time_us: u64,
     /// Type of query
     pub query_type: QueryType,
+    /// Command tag for statements that return no columns (e.g. "SET", "CALL")
+    pub command_tag: Option<String>,
 }
 
 impl QueryResult {
@@ -246,10 +596,27 @@ impl QueryResult {
         self.rows.is_empty()
     }
 
+    /// Estimate the result's in-memory size from its column types and row count.
+    ///
+    /// Compare with [`LARGE_RESULT_BYTES`] to decide whether to render it.
+    pub fn estimated_bytes(&self) -> u64 {
+        estimate_result_bytes(&self.columns, self.rows.len())
+    }
+
     /// Get column names.
     pub fn column_names(&self) -> Vec<&str> {
         self.columns.iter().map(|c| c.name.as_str()).collect()
     }
+
+    /// Check if the statement returned no columns, only a command status.
+    pub fn is_command(&self) -> bool {
+        self.command_tag.is_some()
+    }
+
+    /// Status message for a command result, like psql prints ("SET", "UPDATE 3").
+    pub fn command_status(&self) -> Option<String> {
+        self.command_tag.as_deref().map(|tag| command_status(tag, self.rows_affected))
+    }
 }
 
 impl std::fmt::Debug for QueryResult {
@@ -260,7 +627,493 @@ impl std::fmt::Debug for QueryResult {
             .field("row_count", &self.rows.len())
             .field("rows_affected", &self.rows_affected)
             .field("execution_time_ms", &self.execution_time_ms)
+            .field("execution_time_us", &self.execution_time_us)
             .field("query_type", &self.query_type)
+            .field("command_tag", &self.command_tag)
             .finish()
     }
 }
+
+/// Totals for a batch, e.g. "3 statements, 2 succeeded, 1 failed, total 412ms".
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BatchSummary {
+    /// Statements that succeeded
+    pub succeeded: usize,
+    /// Statements that failed
+    pub failed: usize,
+    /// Statements skipped after a failure
+    pub not_run: usize,
+    /// Time spent across every statement that ran, in microseconds
+    pub total_time_us: u64,
+}
+
+impl BatchSummary {
+    /// Get the number of statements in the batch, run or not.
+    pub fn statements(&self) -> usize {
+        self.succeeded + self.failed + self.not_run
+    }
+
+    /// Summary line for the Messages panel.
+    pub fn message(&self) -> String {
+        let statements = self.statements();
+        let mut message = format!(
+            "{} statement{}, {} succeeded, {} failed",
+            statements,
+            if statements == 1 { "" } else { "s" },
+            self.succeeded,
+            self.failed
+        );
+        if self.not_run > 0 {
+            message.push_str(&format!(", {} not run", self.not_run));
+        }
+        message.push_str(&format!(", total {}", format_execution_time(self.total_time_us)));
+        message
+    }
+}
+
+/// Planner estimate for a statement, from `EXPLAIN (FORMAT JSON)`.
+///
+/// Taken from the top-level plan node, so it covers the whole statement.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryEstimate {
+    /// Node type of the top-level plan node (e.g. "Seq Scan")
+    pub node_type: String,
+    /// Estimated rows returned
+    pub plan_rows: f64,
+    /// Estimated cost before the first row
+    pub startup_cost: f64,
+    /// Estimated total cost
+    pub total_cost: f64,
+}
+
+impl QueryEstimate {
+    /// Extract the estimate from `EXPLAIN (FORMAT JSON)` output.
+    ///
+    /// The output is an array holding one object whose `Plan` is the
+    /// top-level node. Returns None if `Plan Rows` or `Total Cost` is missing.
+    pub fn from_plan_json(json: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        let plan = value.get(0)?.get("Plan")?;
+        Some(Self {
+            node_type: plan.get("Node Type").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            plan_rows: plan.get("Plan Rows")?.as_f64()?,
+            startup_cost: plan.get("Startup Cost").and_then(|v| v.as_f64()).unwrap_or(0.0),
+            total_cost: plan.get("Total Cost")?.as_f64()?,
+        })
+    }
+
+    /// One-line summary, e.g. "~1,200 rows, cost 35.50 (Seq Scan)".
+    pub fn summary(&self) -> String {
+        let rows = self.plan_rows.round().max(0.0) as u64;
+        let mut summary = format!(
+            "~{} row{}, cost {:.2}",
+            format_count(rows),
+            if rows == 1 { "" } else { "s" },
+            self.total_cost
+        );
+        if !self.node_type.is_empty() {
+            summary.push_str(&format!(" ({})", self.node_type));
+        }
+        summary
+    }
+}
+
+/// A parsed `EXPLAIN (FORMAT JSON)` plan.
+///
+/// Timings are only present when the plan was captured with `ANALYZE`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlan {
+    /// Top-level plan node
+    pub root: QueryPlanNode,
+    /// Time spent planning, in milliseconds
+    pub planning_time_ms: Option<f64>,
+    /// Time spent running the statement, in milliseconds (ANALYZE only)
+    pub execution_time_ms: Option<f64>,
+}
+
+impl QueryPlan {
+    /// Parse the output of `EXPLAIN (FORMAT JSON[, ANALYZE true])`.
+    ///
+    /// Returns None if the output has no `Plan` or a node lacks its estimate.
+    pub fn from_plan_json(json: &str) -> Option<Self> {
+        let value: serde_json::Value = serde_json::from_str(json).ok()?;
+        let explain = value.get(0)?;
+        Some(Self {
+            root: QueryPlanNode::from_json(explain.get("Plan")?)?,
+            planning_time_ms: explain.get("Planning Time").and_then(|v| v.as_f64()),
+            execution_time_ms: explain.get("Execution Time").and_then(|v| v.as_f64()),
+        })
+    }
+
+    /// Check if the plan carries actual rows and timings.
+    pub fn is_analyzed(&self) -> bool {
+        self.execution_time_ms.is_some() || self.root.actual_rows.is_some()
+    }
+}
+
+/// One node of a query plan, with its child nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryPlanNode {
+    /// Node type (e.g. "Seq Scan", "Hash Join")
+    pub node_type: String,
+    /// Relation scanned or modified by the node, if any
+    pub relation_name: Option<String>,
+    /// Estimated rows returned per loop
+    pub plan_rows: f64,
+    /// Estimated cost before the first row
+    pub startup_cost: f64,
+    /// Estimated total cost
+    pub total_cost: f64,
+    /// Actual rows returned per loop (ANALYZE only)
+    pub actual_rows: Option<f64>,
+    /// Number of times the node ran (ANALYZE only)
+    pub actual_loops: Option<f64>,
+    /// Milliseconds until the first row, per loop (ANALYZE only)
+    pub actual_startup_time_ms: Option<f64>,
+    /// Milliseconds until the last row, per loop (ANALYZE only)
+    pub actual_total_time_ms: Option<f64>,
+    /// Child nodes, in plan order
+    pub children: Vec<QueryPlanNode>,
+}
+
+impl QueryPlanNode {
+    fn from_json(plan: &serde_json::Value) -> Option<Self> {
+        let number = |key: &str| plan.get(key).and_then(|v| v.as_f64());
+        let children = match plan.get("Plans").and_then(|v| v.as_array()) {
+            Some(plans) => plans.iter().map(Self::from_json).collect::<Option<Vec<_>>>()?,
+            None => Vec::new(),
+        };
+        Some(Self {
+            node_type: plan.get("Node Type").and_then(|v| v.as_str()).unwrap_or("").to_string(),
+            relation_name: plan
+                .get("Relation Name")
+                .and_then(|v| v.as_str())
+                .map(|name| name.to_string()),
+            plan_rows: number("Plan Rows")?,
+            startup_cost: number("Startup Cost").unwrap_or(0.0),
+            total_cost: number("Total Cost")?,
+            actual_rows: number("Actual Rows"),
+            actual_loops: number("Actual Loops"),
+            actual_startup_time_ms: number("Actual Startup Time"),
+            actual_total_time_ms: number("Actual Total Time"),
+            children,
+        })
+    }
+
+    /// Label for the node, e.g. "Seq Scan on orders".
+    pub fn label(&self) -> String {
+        match &self.relation_name {
+            Some(relation) => format!("{} on {}", self.node_type, relation),
+            None => self.node_type.clone(),
+        }
+    }
+
+    /// Get the actual rows across all loops (ANALYZE only).
+    pub fn total_actual_rows(&self) -> Option<f64> {
+        Some(self.actual_rows? * self.actual_loops.unwrap_or(1.0))
+    }
+
+    /// Ratio of actual to estimated rows per loop (ANALYZE only).
+    ///
+    /// Both sides are clamped to at least one row, so 1.0 means the
+    /// estimate was right and values far from 1.0 flag a misestimate.
+    pub fn row_estimate_factor(&self) -> Option<f64> {
+        Some(self.actual_rows?.max(1.0) / self.plan_rows.max(1.0))
+    }
+
+    /// Count this node and all nodes below it.
+    pub fn node_count(&self) -> usize {
+        1 + self.children.iter().map(Self::node_count).sum::<usize>()
+    }
+}
+
+/// Format a count with thousands separators.
+fn format_count(n: u64) -> String {
+    let digits = n.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+    grouped
+}
+
+/// Format a command tag as a status message, like psql prints it.
+///
+/// Tags of commands that count rows get the count appended ("UPDATE 3");
+/// INSERT keeps PostgreSQL's legacy OID field ("INSERT 0 3").
+pub fn command_status(tag: &str, rows_affected: Option<u64>) -> String {
+    match rows_affected {
+        Some(rows) if tag == "INSERT" => format!("INSERT 0 {rows}"),
+        Some(rows) => format!("{tag} {rows}"),
+        None => tag.to_string(),
+    }
+}
+
+/// Whole milliseconds in a duration, rounding sub-millisecond durations up to
+/// 1 so a query that took any time never reports 0ms.
+pub fn duration_ms(elapsed: Duration) -> u64 {
+    let ms = elapsed.as_millis() as u64;
+    if ms == 0 && !elapsed.is_zero() {
+        1
+    } else {
+        ms
+    }
+}
+
+/// Whole microseconds in a duration.
+pub fn duration_us(elapsed: Duration) -> u64 {
+    elapsed.as_micros() as u64
+}
+
+/// Format an execution time given in microseconds.
+///
+/// Sub-millisecond times keep one decimal ("0.3ms") so fast queries don't
+/// read as 0ms; longer times use whole milliseconds, seconds, or minutes.
+pub fn format_execution_time(us: u64) -> String {
+    match us {
+        0 => "0ms".to_string(),
+        1..=99 => "<0.1ms".to_string(),
+        100..=999 => format!("{:.1}ms", us as f64 / 1000.0),
+        1_000..=999_999 => format!("{}ms", us / 1000),
+        1_000_000..=59_999_999 => format!("{:.2}s", us as f64 / 1_000_000.0),
+        _ => format!("{}m {}s", us / 60_000_000, (us % 60_000_000) / 1_000_000),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_estimate_reads_top_level_plan() {
+        let json = r#"[{"Plan": {
+            "Node Type": "Hash Join", "Startup Cost": 12.5, "Total Cost": 4821.75,
+            "Plan Rows": 125000, "Plan Width": 64,
+            "Plans": [{"Node Type": "Seq Scan", "Total Cost": 99.0, "Plan Rows": 10}]
+        }}]"#;
+        let estimate = QueryEstimate::from_plan_json(json).unwrap();
+        assert_eq!(estimate.node_type, "Hash Join");
+        assert_eq!(estimate.plan_rows, 125000.0);
+        assert_eq!(estimate.startup_cost, 12.5);
+        assert_eq!(estimate.total_cost, 4821.75);
+        assert_eq!(estimate.summary(), "~125,000 rows, cost 4821.75 (Hash Join)");
+    }
+
+    #[test]
+    fn test_estimate_requires_rows_and_cost() {
+        assert!(QueryEstimate::from_plan_json("not json").is_none());
+        assert!(QueryEstimate::from_plan_json("[]").is_none());
+        assert!(QueryEstimate::from_plan_json(r#"[{"Plan": {"Plan Rows": 1}}]"#).is_none());
+        let estimate =
+            QueryEstimate::from_plan_json(r#"[{"Plan": {"Plan Rows": 1, "Total Cost": 0.01}}]"#)
+                .unwrap();
+        assert_eq!(estimate.summary(), "~1 row, cost 0.01");
+    }
+
+    #[test]
+    fn test_plan_parses_analyzed_tree() {
+        let json = r#"[{"Plan": {
+            "Node Type": "Hash Join", "Startup Cost": 12.5, "Total Cost": 48.0,
+            "Plan Rows": 100, "Actual Startup Time": 0.2, "Actual Total Time": 1.75,
+            "Actual Rows": 4000, "Actual Loops": 1,
+            "Plans": [
+                {"Node Type": "Seq Scan", "Relation Name": "orders", "Total Cost": 20.0,
+                 "Plan Rows": 1000, "Actual Rows": 1000, "Actual Loops": 1},
+                {"Node Type": "Hash", "Total Cost": 10.0, "Plan Rows": 10,
+                 "Actual Rows": 0, "Actual Loops": 2,
+                 "Plans": [{"Node Type": "Index Scan", "Relation Name": "users",
+                            "Total Cost": 8.0, "Plan Rows": 10}]}
+            ]
+        }, "Planning Time": 0.3, "Execution Time": 2.1}]"#;
+        let plan = QueryPlan::from_plan_json(json).unwrap();
+        assert!(plan.is_analyzed());
+        assert_eq!(plan.planning_time_ms, Some(0.3));
+        assert_eq!(plan.execution_time_ms, Some(2.1));
+        assert_eq!(plan.root.node_count(), 4);
+        assert_eq!(plan.root.actual_total_time_ms, Some(1.75));
+        assert_eq!(plan.root.row_estimate_factor(), Some(40.0));
+        assert_eq!(plan.root.children[0].label(), "Seq Scan on orders");
+        assert_eq!(plan.root.children[1].total_actual_rows(), Some(0.0));
+        assert_eq!(plan.root.children[1].row_estimate_factor(), Some(0.1));
+        assert_eq!(plan.root.children[1].children[0].actual_rows, None);
+    }
+
+    #[test]
+    fn test_plan_without_analyze_has_no_actuals() {
+        let plan = QueryPlan::from_plan_json(
+            r#"[{"Plan": {"Node Type": "Result", "Plan Rows": 1, "Total Cost": 0.01}}]"#,
+        )
+        .unwrap();
+        assert!(!plan.is_analyzed());
+        assert_eq!(plan.root.label(), "Result");
+        assert_eq!(plan.root.total_actual_rows(), None);
+        assert!(QueryPlan::from_plan_json(
+            r#"[{"Plan": {"Plan Rows": 1, "Total Cost": 1, "Plans": [{"Plan Rows": 1}]}}]"#
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_notice_is_not_terminal() {
+        use tokio_postgres::error::Severity;
+
+        let notice = QueryEvent::notice(NoticeSeverity::from_pg(Some(Severity::Warning)), "late");
+        assert!(!notice.is_terminal());
+        assert!(matches!(
+            notice,
+            QueryEvent::Notice { severity: NoticeSeverity::Warning, ref message } if message == "late"
+        ));
+        assert_eq!(NoticeSeverity::from_pg(Some(Severity::Info)), NoticeSeverity::Info);
+        assert_eq!(NoticeSeverity::from_pg(None), NoticeSeverity::Notice);
+    }
+
+    #[test]
+    fn test_sub_millisecond_duration_rounds_up() {
+        assert_eq!(duration_ms(Duration::ZERO), 0);
+        assert_eq!(duration_ms(Duration::from_nanos(1)), 1);
+        assert_eq!(duration_ms(Duration::from_micros(300)), 1);
+        assert_eq!(duration_ms(Duration::from_micros(2_700)), 2);
+        assert_eq!(duration_us(Duration::from_micros(300)), 300);
+        assert_eq!(duration_us(Duration::from_nanos(999)), 0);
+    }
+
+    #[test]
+    fn test_format_execution_time() {
+        assert_eq!(format_execution_time(0), "0ms");
+        assert_eq!(format_execution_time(42), "<0.1ms");
+        assert_eq!(format_execution_time(300), "0.3ms");
+        assert_eq!(format_execution_time(940), "0.9ms");
+        assert_eq!(format_execution_time(3_000), "3ms");
+        assert_eq!(format_execution_time(250_400), "250ms");
+        assert_eq!(format_execution_time(1_500_000), "1.50s");
+        assert_eq!(format_execution_time(65_000_000), "1m 5s");
+    }
+
+    #[test]
+    fn test_batch_summary_message() {
+        let summary = BatchSummary { succeeded: 2, failed: 1, not_run: 0, total_time_us: 412_400 };
+
+        assert_eq!(summary.statements(), 3);
+        assert_eq!(summary.message(), "3 statements, 2 succeeded, 1 failed, total 412ms");
+
+        let skipped = BatchSummary { not_run: 2, ..summary };
+        assert_eq!(
+            skipped.message(),
+            "5 statements, 2 succeeded, 1 failed, 2 not run, total 412ms"
+        );
+
+        let empty = BatchSummary::default();
+        assert_eq!(empty.message(), "0 statements, 0 succeeded, 0 failed, total 0ms");
+    }
+
+    #[tokio::test]
+    async fn test_first_row_event_fires_once() {
+        use futures_util::StreamExt;
+
+        let start = Instant::now();
+        let mut timer = FirstRowTimer::new(start);
+
+        // A fake row stream whose first row takes a while to arrive
+        let rows = futures_util::stream::iter(0..2_500).then(|row| async move {
+            if row == 0 {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            row
+        });
+        let mut rows = std::pin::pin!(rows);
+
+        let mut events = Vec::new();
+        while rows.next().await.is_some() {
+            events.extend(timer.record_row());
+        }
+
+        assert_eq!(events.len(), 1);
+        let QueryEvent::FirstRow { elapsed_ms } = events[0] else {
+            panic!("expected a FirstRow event, got {:?}", events[0]);
+        };
+        assert!(elapsed_ms >= 20);
+        assert!(elapsed_ms <= duration_ms(start.elapsed()));
+    }
+
+    /// Number and sizes of the batches released for `rows` rows.
+    fn batch_sizes(rows: usize, batch_size: usize) -> Vec<usize> {
+        let mut batcher = RowBatcher::new(batch_size);
+        let mut sizes: Vec<usize> =
+            (0..rows).filter_map(|row| batcher.push(row)).map(|b| b.len()).collect();
+        sizes.extend(batcher.finish().map(|b| b.len()));
+        sizes
+    }
+
+    #[test]
+    fn test_row_batcher_event_counts() {
+        assert_eq!(batch_sizes(2500, 1000), vec![1000, 1000, 500]);
+        assert_eq!(batch_sizes(2000, 1000), vec![1000, 1000]);
+        assert_eq!(batch_sizes(999, 1000), vec![999]);
+        assert_eq!(batch_sizes(0, 1000), Vec::<usize>::new());
+        assert_eq!(batch_sizes(5, 1).len(), 5);
+        assert_eq!(batch_sizes(100_000, 50_000), vec![50_000, 50_000]);
+        // A zero batch size still makes progress
+        assert_eq!(batch_sizes(3, 0), vec![1, 1, 1]);
+    }
+
+    #[test]
+    fn test_row_batcher_keeps_row_order() {
+        let mut batcher = RowBatcher::new(2);
+        assert_eq!(batcher.push("a"), None);
+        assert_eq!(batcher.push("b"), Some(vec!["a", "b"]));
+        assert_eq!(batcher.push("c"), None);
+        assert_eq!(batcher.finish(), Some(vec!["c"]));
+    }
+
+    fn typed_column(name: &str, type_oid: u32) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            type_oid,
+            type_name: String::new(),
+            table_oid: None,
+            column_id: None,
+        }
+    }
+
+    #[test]
+    fn test_estimate_grows_with_row_count() {
+        let columns = [typed_column("id", 23), typed_column("name", 25)];
+        let sizes: Vec<u64> = [0, 1, 10, 1_000, 1_000_000]
+            .iter()
+            .map(|&rows| estimate_result_bytes(&columns, rows))
+            .collect();
+        assert_eq!(sizes[0], 0);
+        assert!(sizes.windows(2).all(|pair| pair[0] < pair[1]), "{sizes:?}");
+        assert_eq!(sizes[4], sizes[1] * 1_000_000);
+    }
+
+    #[test]
+    fn test_estimate_larger_for_wide_text_columns() {
+        let narrow = [typed_column("id", 23), typed_column("flag", 16)];
+        let text = [typed_column("id", 23), typed_column("body", 25)];
+        let wide = [
+            typed_column("id", 23),
+            typed_column("body", 25),
+            typed_column("summary", 1043),
+            typed_column("payload", 3802),
+        ];
+        let rows = 10_000;
+        assert!(estimate_result_bytes(&text, rows) > estimate_result_bytes(&narrow, rows));
+        assert!(estimate_result_bytes(&wide, rows) > estimate_result_bytes(&text, rows));
+        // bigint is wider than integer
+        assert!(
+            typed_column("n", 20).estimated_value_bytes()
+                > typed_column("n", 23).estimated_value_bytes()
+        );
+    }
+
+    #[test]
+    fn test_large_result_threshold() {
+        let columns = [typed_column("id", 20), typed_column("body", 25), typed_column("at", 1184)];
+        assert!(estimate_result_bytes(&columns, 10_000) < LARGE_RESULT_BYTES);
+        assert!(estimate_result_bytes(&columns, 5_000_000) > LARGE_RESULT_BYTES);
+    }
+}
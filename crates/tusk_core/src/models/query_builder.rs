@@ -0,0 +1,82 @@
+//! SQL for common table actions.
+//!
+//! The schema browser's context-menu actions build their statements here
+//! rather than formatting names into SQL themselves. Every schema and table
+//! name goes through [`quote_ident`], so names with quotes, spaces, capitals,
+//! or reserved words produce valid SQL.
+
+use crate::services::ddl::quote_ident;
+
+/// Schema-qualify and quote a table name.
+pub fn qualified_name(schema: &str, table: &str) -> String {
+    format!("{}.{}", quote_ident(schema), quote_ident(table))
+}
+
+/// `SELECT *` from a table, optionally limited to `limit` rows.
+pub fn select_all(schema: &str, table: &str, limit: Option<u32>) -> String {
+    match limit {
+        Some(limit) => format!("SELECT * FROM {} LIMIT {};", qualified_name(schema, table), limit),
+        None => format!("SELECT * FROM {};", qualified_name(schema, table)),
+    }
+}
+
+/// Count the rows of a table.
+pub fn count(schema: &str, table: &str) -> String {
+    format!("SELECT count(*) FROM {};", qualified_name(schema, table))
+}
+
+/// Remove all rows from a table.
+pub fn truncate(schema: &str, table: &str) -> String {
+    format!("TRUNCATE TABLE {};", qualified_name(schema, table))
+}
+
+/// Remove a table and its data.
+pub fn drop_table(schema: &str, table: &str) -> String {
+    format!("DROP TABLE {};", qualified_name(schema, table))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_names_stay_bare() {
+        assert_eq!(
+            select_all("public", "users", Some(100)),
+            "SELECT * FROM public.users LIMIT 100;"
+        );
+        assert_eq!(select_all("public", "users", None), "SELECT * FROM public.users;");
+        assert_eq!(count("app", "order_items"), "SELECT count(*) FROM app.order_items;");
+        assert_eq!(truncate("app", "events_2024"), "TRUNCATE TABLE app.events_2024;");
+        assert_eq!(drop_table("app", "events_2024"), "DROP TABLE app.events_2024;");
+    }
+
+    #[test]
+    fn test_names_with_quotes_and_spaces_are_escaped() {
+        assert_eq!(
+            count("my schema", "Order Lines"),
+            "SELECT count(*) FROM \"my schema\".\"Order Lines\";"
+        );
+        assert_eq!(
+            select_all("public", "say \"hi\"", Some(10)),
+            "SELECT * FROM public.\"say \"\"hi\"\"\" LIMIT 10;"
+        );
+        // A quote can't end the identifier early
+        assert_eq!(
+            truncate("public", "x\"; DROP TABLE users; --"),
+            "TRUNCATE TABLE public.\"x\"\"; DROP TABLE users; --\";"
+        );
+        assert_eq!(qualified_name("Sales", "Users"), "\"Sales\".\"Users\"");
+    }
+
+    #[test]
+    fn test_reserved_words_are_quoted() {
+        assert_eq!(
+            select_all("public", "order", Some(1)),
+            "SELECT * FROM public.\"order\" LIMIT 1;"
+        );
+        assert_eq!(count("user", "table"), "SELECT count(*) FROM \"user\".\"table\";");
+        assert_eq!(truncate("select", "from"), "TRUNCATE TABLE \"select\".\"from\";");
+        assert_eq!(drop_table("public", "user"), "DROP TABLE public.\"user\";");
+    }
+}
@@ -2,8 +2,10 @@
 //!
 //! Data structures representing PostgreSQL database objects for the schema browser.
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::time::{Duration, Instant};
 use uuid::Uuid;
 
@@ -29,6 +31,36 @@ pub struct TableInfo {
     pub estimated_rows: i64,
     /// Table size in bytes.
     pub size_bytes: i64,
+    /// Last manual VACUUM, from pg_stat_user_tables.
+    #[serde(default)]
+    pub last_vacuum: Option<DateTime<Utc>>,
+    /// Last autovacuum run.
+    #[serde(default)]
+    pub last_autovacuum: Option<DateTime<Utc>>,
+    /// Last manual ANALYZE.
+    #[serde(default)]
+    pub last_analyze: Option<DateTime<Utc>>,
+    /// Last autoanalyze run.
+    #[serde(default)]
+    pub last_autoanalyze: Option<DateTime<Utc>>,
+    /// Estimated number of dead tuples.
+    #[serde(default)]
+    pub dead_tuples: i64,
+    /// Table comment.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl TableInfo {
+    /// Most recent vacuum, manual or automatic.
+    pub fn last_vacuumed(&self) -> Option<DateTime<Utc>> {
+        self.last_vacuum.max(self.last_autovacuum)
+    }
+
+    /// Most recent analyze, manual or automatic.
+    pub fn last_analyzed(&self) -> Option<DateTime<Utc>> {
+        self.last_analyze.max(self.last_autoanalyze)
+    }
 }
 
 /// A PostgreSQL view.
@@ -42,6 +74,9 @@ pub struct ViewInfo {
     pub owner: String,
     /// Whether this is a materialized view.
     pub is_materialized: bool,
+    /// View comment.
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 /// A PostgreSQL function.
@@ -57,6 +92,9 @@ pub struct FunctionInfo {
     pub arguments: String,
     /// Function volatility (IMMUTABLE, STABLE, VOLATILE).
     pub volatility: String,
+    /// Function comment.
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 /// A PostgreSQL column.
@@ -74,6 +112,132 @@ pub struct ColumnDetail {
     pub default_value: Option<String>,
     /// Column position (1-based ordinal).
     pub ordinal_position: i32,
+    /// Column comment.
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// A PostgreSQL index on a table.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexInfo {
+    /// Schema name containing the indexed table.
+    pub schema: String,
+    /// Indexed table name.
+    pub table: String,
+    /// Index name.
+    pub name: String,
+    /// Key columns in index order. Expression keys hold the expression text.
+    pub columns: Vec<String>,
+    /// Whether the index enforces uniqueness.
+    pub is_unique: bool,
+    /// Whether the index backs the table's primary key.
+    pub is_primary: bool,
+    /// Access method (e.g., "btree", "gin").
+    pub method: String,
+    /// Predicate of a partial index, if any.
+    pub predicate: Option<String>,
+}
+
+impl IndexInfo {
+    /// Describe the index in one line, e.g.
+    /// `(lower(email)) UNIQUE USING hash WHERE active`.
+    ///
+    /// The access method is omitted for the default btree.
+    pub fn summary(&self) -> String {
+        let mut summary = format!("({})", self.columns.join(", "));
+        if self.is_primary {
+            summary.push_str(" PRIMARY KEY");
+        } else if self.is_unique {
+            summary.push_str(" UNIQUE");
+        }
+        if self.method != "btree" {
+            summary.push_str(" USING ");
+            summary.push_str(&self.method);
+        }
+        if let Some(predicate) = &self.predicate {
+            summary.push_str(" WHERE ");
+            summary.push_str(predicate);
+        }
+        summary
+    }
+}
+
+/// Kind of a table constraint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConstraintKind {
+    /// `PRIMARY KEY`
+    PrimaryKey,
+    /// `FOREIGN KEY ... REFERENCES`
+    ForeignKey,
+    /// `UNIQUE`
+    Unique,
+    /// `CHECK`
+    Check,
+    /// `EXCLUDE`
+    Exclusion,
+}
+
+impl ConstraintKind {
+    /// Parse a `pg_constraint.contype` code, or None for kinds not shown
+    /// under tables (such as constraint triggers).
+    pub fn from_code(code: &str) -> Option<Self> {
+        match code {
+            "p" => Some(Self::PrimaryKey),
+            "f" => Some(Self::ForeignKey),
+            "u" => Some(Self::Unique),
+            "c" => Some(Self::Check),
+            "x" => Some(Self::Exclusion),
+            _ => None,
+        }
+    }
+
+    /// SQL keyword for the kind.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::PrimaryKey => "PRIMARY KEY",
+            Self::ForeignKey => "FOREIGN KEY",
+            Self::Unique => "UNIQUE",
+            Self::Check => "CHECK",
+            Self::Exclusion => "EXCLUDE",
+        }
+    }
+}
+
+/// A PostgreSQL table constraint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConstraintInfo {
+    /// Schema name containing the constrained table.
+    pub schema: String,
+    /// Constrained table name.
+    pub table: String,
+    /// Constraint name.
+    pub name: String,
+    /// Constraint kind.
+    pub kind: ConstraintKind,
+    /// Constrained columns in key order (empty for table-level CHECKs).
+    pub columns: Vec<String>,
+    /// Definition as reported by `pg_get_constraintdef`,
+    /// e.g. `FOREIGN KEY (user_id) REFERENCES users(id)`.
+    pub definition: String,
+    /// Schema of the table a foreign key references.
+    pub referenced_schema: Option<String>,
+    /// Table a foreign key references.
+    pub referenced_table: Option<String>,
+    /// Referenced columns of a foreign key, matching `columns` in order.
+    pub referenced_columns: Vec<String>,
+    /// Whether checking can be deferred to the end of the transaction.
+    pub is_deferrable: bool,
+    /// Whether checking is deferred by default (`INITIALLY DEFERRED`).
+    pub is_deferred: bool,
+    /// Whether existing rows have been validated (false after `NOT VALID`).
+    pub is_validated: bool,
+}
+
+impl ConstraintInfo {
+    /// Schema and name of the table a foreign key references.
+    pub fn references(&self) -> Option<(&str, &str)> {
+        Some((self.referenced_schema.as_deref()?, self.referenced_table.as_deref()?))
+    }
 }
 
 /// Complete schema information for a database.
@@ -88,9 +252,200 @@ pub struct DatabaseSchema {
     /// All functions in the database.
     pub functions: Vec<FunctionInfo>,
     /// Columns for each table, keyed by (schema, table_name).
+    ///
+    /// Tables missing here haven't had their columns loaded yet; see
+    /// [`DatabaseSchema::columns_loaded`].
     pub table_columns: HashMap<(String, String), Vec<ColumnDetail>>,
-    /// Columns for each view, keyed by (schema, view_name).
+    /// Columns for each view, keyed by (schema, view_name), loaded like `table_columns`.
     pub view_columns: HashMap<(String, String), Vec<ColumnDetail>>,
+    /// All indexes on tables in the database.
+    pub indexes: Vec<IndexInfo>,
+    /// All constraints on tables in the database.
+    pub constraints: Vec<ConstraintInfo>,
+}
+
+impl DatabaseSchema {
+    /// Count the objects of each kind across all schemas.
+    pub fn counts(&self) -> SchemaCounts {
+        SchemaCounts {
+            schemas: self.schemas.len(),
+            tables: self.tables.len(),
+            views: self.views.len(),
+            materialized_views: self.views.iter().filter(|v| v.is_materialized).count(),
+            functions: self.functions.len(),
+        }
+    }
+
+    /// Indexes on one table, in name order.
+    pub fn table_indexes<'a>(
+        &'a self,
+        schema: &'a str,
+        table: &'a str,
+    ) -> impl Iterator<Item = &'a IndexInfo> + 'a {
+        self.indexes.iter().filter(move |index| index.schema == schema && index.table == table)
+    }
+
+    /// Constraints on one table, in name order.
+    pub fn table_constraints<'a>(
+        &'a self,
+        schema: &'a str,
+        table: &'a str,
+    ) -> impl Iterator<Item = &'a ConstraintInfo> + 'a {
+        self.constraints.iter().filter(move |c| c.schema == schema && c.table == table)
+    }
+
+    /// Check if the columns of a table or view have been loaded.
+    ///
+    /// Large schemas are loaded without columns, which are then filled in
+    /// per object with [`DatabaseSchema::set_columns`].
+    pub fn columns_loaded(&self, schema: &str, name: &str) -> bool {
+        let key = (schema.to_string(), name.to_string());
+        self.table_columns.contains_key(&key) || self.view_columns.contains_key(&key)
+    }
+
+    /// Store the columns of a table or view loaded on demand.
+    pub fn set_columns(&mut self, schema: &str, name: &str, columns: Vec<ColumnDetail>) {
+        let key = (schema.to_string(), name.to_string());
+        if self.views.iter().any(|v| v.schema == schema && v.name == name) {
+            self.view_columns.insert(key, columns);
+        } else {
+            self.table_columns.insert(key, columns);
+        }
+    }
+}
+
+/// Object counts for a database, shown as the schema browser summary.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SchemaCounts {
+    /// Number of schemas (namespaces).
+    pub schemas: usize,
+    /// Number of tables.
+    pub tables: usize,
+    /// Number of views, including materialized views.
+    pub views: usize,
+    /// Number of materialized views (also counted in `views`).
+    pub materialized_views: usize,
+    /// Number of functions.
+    pub functions: usize,
+}
+
+impl fmt::Display for SchemaCounts {
+    /// Formats as e.g. `12 schemas, 340 tables, 45 views, 1 function`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: usize, noun: &str| {
+            format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" })
+        };
+        write!(
+            f,
+            "{}, {}, {}, {}",
+            plural(self.schemas, "schema"),
+            plural(self.tables, "table"),
+            plural(self.views, "view"),
+            plural(self.functions, "function"),
+        )
+    }
+}
+
+/// A distinct column value and how many rows hold it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueCount {
+    /// The value as text.
+    pub value: String,
+    /// Number of rows with this value.
+    pub count: i64,
+}
+
+/// Value distribution of a column: its most frequent values and null share.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnSample {
+    /// Most frequent non-null values, most common first.
+    pub top_values: Vec<ValueCount>,
+    /// Rows in the table.
+    pub total_rows: i64,
+    /// Rows where the column is NULL.
+    pub null_rows: i64,
+}
+
+impl ColumnSample {
+    /// Build a sample from per-value counts, where a `None` value counts NULLs.
+    ///
+    /// Keeps at most `limit` non-null values, in the given order.
+    pub fn from_counts(
+        counts: impl IntoIterator<Item = (Option<String>, i64)>,
+        total_rows: i64,
+        null_rows: i64,
+        limit: usize,
+    ) -> Self {
+        let top_values = counts
+            .into_iter()
+            .filter_map(|(value, count)| value.map(|value| ValueCount { value, count }))
+            .take(limit)
+            .collect();
+        Self { top_values, total_rows, null_rows }
+    }
+
+    /// Fraction of rows that are NULL (0.0 for an empty table).
+    pub fn null_fraction(&self) -> f64 {
+        self.fraction(self.null_rows)
+    }
+
+    /// Fraction of rows that `count` represents (0.0 for an empty table).
+    pub fn fraction(&self, count: i64) -> f64 {
+        if self.total_rows > 0 {
+            count as f64 / self.total_rows as f64
+        } else {
+            0.0
+        }
+    }
+}
+
+/// Kind of schema object returned by a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SchemaObjectKind {
+    /// A table.
+    Table,
+    /// A view or materialized view.
+    View,
+    /// A function.
+    Function,
+}
+
+/// Which part of an object matched a search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SearchMatchKind {
+    /// The object's name.
+    Name,
+    /// One of the object's columns.
+    Column,
+    /// The comment on the object or one of its columns.
+    Comment,
+}
+
+/// A match from searching the cached schema.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchHit {
+    /// Kind of object that matched.
+    pub object_kind: SchemaObjectKind,
+    /// Schema containing the object.
+    pub schema: String,
+    /// Object name; functions include their arguments (e.g. `add(integer, integer)`).
+    pub object: String,
+    /// Column, for column name and column comment matches.
+    pub column: Option<String>,
+    /// Which part of the object matched.
+    pub match_kind: SearchMatchKind,
+    /// The text that matched (name, column name, or comment).
+    pub matched_text: String,
+}
+
+impl SearchHit {
+    /// Dotted path to the object or column (e.g. `public.users.email`).
+    pub fn path(&self) -> String {
+        match &self.column {
+            Some(column) => format!("{}.{}.{}", self.schema, self.object, column),
+            None => format!("{}.{}", self.schema, self.object),
+        }
+    }
 }
 
 /// Default schema cache time-to-live (5 minutes).
@@ -102,6 +457,7 @@ const DEFAULT_SCHEMA_CACHE_TTL_SECS: u64 = 300;
 /// The cache is invalidated when:
 /// - TTL expires (default 5 minutes)
 /// - User explicitly refreshes
+/// - DDL runs on the connection
 /// - Connection is closed
 #[derive(Debug, Clone)]
 pub struct SchemaCache {
@@ -113,6 +469,8 @@ pub struct SchemaCache {
     loaded_at: Instant,
     /// Time-to-live for cache validity
     ttl: Duration,
+    /// Set when the schema changed since loading (e.g. after DDL)
+    invalidated: bool,
 }
 
 impl SchemaCache {
@@ -123,12 +481,13 @@ impl SchemaCache {
             schema,
             loaded_at: Instant::now(),
             ttl: Duration::from_secs(DEFAULT_SCHEMA_CACHE_TTL_SECS),
+            invalidated: false,
         }
     }
 
     /// Create a schema cache with custom TTL.
     pub fn with_ttl(connection_id: Uuid, schema: DatabaseSchema, ttl: Duration) -> Self {
-        Self { connection_id, schema, loaded_at: Instant::now(), ttl }
+        Self { connection_id, schema, loaded_at: Instant::now(), ttl, invalidated: false }
     }
 
     /// Get the connection ID this cache belongs to.
@@ -163,7 +522,18 @@ impl SchemaCache {
 
     /// Check if the cache is still valid.
     pub fn is_valid(&self) -> bool {
-        !self.is_expired()
+        !self.invalidated && !self.is_expired()
+    }
+
+    /// Mark the cache stale before its TTL, keeping the data for display
+    /// until a refresh replaces it.
+    pub fn invalidate(&mut self) {
+        self.invalidated = true;
+    }
+
+    /// Check if the cache was invalidated before its TTL.
+    pub fn is_invalidated(&self) -> bool {
+        self.invalidated
     }
 
     /// Get time remaining until expiration.
@@ -178,10 +548,11 @@ impl SchemaCache {
 
     /// Refresh the cache with new schema data (FR-017).
     ///
-    /// Resets the loaded_at timestamp.
+    /// Resets the loaded_at timestamp and clears any invalidation.
     pub fn refresh(&mut self, schema: DatabaseSchema) {
         self.schema = schema;
         self.loaded_at = Instant::now();
+        self.invalidated = false;
     }
 
     /// Consume the cache and return the schema.
@@ -189,3 +560,178 @@ impl SchemaCache {
         self.schema
     }
 }
+
+/// Schema objects for tests, shared with dependent crates through the
+/// `test-support` feature.
+#[cfg(any(test, feature = "test-support"))]
+pub mod fixtures {
+    use super::TableInfo;
+
+    /// A table owned by `postgres` with no statistics or comment.
+    pub fn table(schema: &str, name: &str) -> TableInfo {
+        TableInfo {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            owner: "postgres".to_string(),
+            estimated_rows: 0,
+            size_bytes: 0,
+            last_vacuum: None,
+            last_autovacuum: None,
+            last_analyze: None,
+            last_autoanalyze: None,
+            dead_tuples: 0,
+            comment: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::table;
+    use super::*;
+
+    fn view(schema: &str, name: &str, is_materialized: bool) -> ViewInfo {
+        ViewInfo {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            owner: "postgres".to_string(),
+            is_materialized,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_counts_across_schemas() {
+        let schema = DatabaseSchema {
+            schemas: ["public", "sales", "audit"]
+                .map(|name| SchemaInfo { name: name.to_string(), owner: "postgres".to_string() })
+                .to_vec(),
+            tables: vec![
+                table("public", "users"),
+                table("sales", "orders"),
+                table("sales", "invoices"),
+                table("audit", "events"),
+            ],
+            views: vec![view("public", "active_users", false), view("sales", "totals", true)],
+            functions: vec![FunctionInfo {
+                schema: "sales".to_string(),
+                name: "tax".to_string(),
+                return_type: "numeric".to_string(),
+                arguments: "numeric".to_string(),
+                volatility: "IMMUTABLE".to_string(),
+                comment: None,
+            }],
+            ..Default::default()
+        };
+
+        let counts = schema.counts();
+        assert_eq!(
+            counts,
+            SchemaCounts { schemas: 3, tables: 4, views: 2, materialized_views: 1, functions: 1 }
+        );
+        assert_eq!(counts.to_string(), "3 schemas, 4 tables, 2 views, 1 function");
+    }
+
+    #[test]
+    fn test_index_summary() {
+        let mut index = IndexInfo {
+            schema: "public".to_string(),
+            table: "users".to_string(),
+            name: "users_pkey".to_string(),
+            columns: vec!["id".to_string()],
+            is_unique: true,
+            is_primary: true,
+            method: "btree".to_string(),
+            predicate: None,
+        };
+        assert_eq!(index.summary(), "(id) PRIMARY KEY");
+
+        index.is_primary = false;
+        index.columns = vec!["tenant_id".to_string(), "lower(email)".to_string()];
+        index.predicate = Some("deleted_at IS NULL".to_string());
+        assert_eq!(index.summary(), "(tenant_id, lower(email)) UNIQUE WHERE deleted_at IS NULL");
+
+        index.is_unique = false;
+        index.method = "gin".to_string();
+        index.columns = vec!["tags".to_string()];
+        index.predicate = None;
+        assert_eq!(index.summary(), "(tags) USING gin");
+    }
+
+    #[test]
+    fn test_constraint_kinds_and_references() {
+        let kinds: Vec<_> =
+            ["p", "f", "u", "c", "x", "t"].iter().map(|c| ConstraintKind::from_code(c)).collect();
+        assert_eq!(
+            kinds,
+            [
+                Some(ConstraintKind::PrimaryKey),
+                Some(ConstraintKind::ForeignKey),
+                Some(ConstraintKind::Unique),
+                Some(ConstraintKind::Check),
+                Some(ConstraintKind::Exclusion),
+                None,
+            ]
+        );
+        assert_eq!(ConstraintKind::ForeignKey.label(), "FOREIGN KEY");
+
+        let mut fk = ConstraintInfo {
+            schema: "sales".to_string(),
+            table: "orders".to_string(),
+            name: "orders_customer_id_fkey".to_string(),
+            kind: ConstraintKind::ForeignKey,
+            columns: vec!["customer_id".to_string()],
+            definition: "FOREIGN KEY (customer_id) REFERENCES customers(id)".to_string(),
+            referenced_schema: Some("public".to_string()),
+            referenced_table: Some("customers".to_string()),
+            referenced_columns: vec!["id".to_string()],
+            is_deferrable: false,
+            is_deferred: false,
+            is_validated: true,
+        };
+        assert_eq!(fk.references(), Some(("public", "customers")));
+        fk.referenced_table = None;
+        assert_eq!(fk.references(), None);
+    }
+
+    #[test]
+    fn test_columns_loaded_on_demand() {
+        let mut schema = DatabaseSchema {
+            views: vec![view("public", "active_users", false)],
+            ..Default::default()
+        };
+        assert!(!schema.columns_loaded("public", "users"));
+        assert!(!schema.columns_loaded("public", "active_users"));
+
+        // A table without columns still counts as loaded
+        schema.set_columns("public", "users", Vec::new());
+        schema.set_columns("public", "active_users", Vec::new());
+        assert!(schema.columns_loaded("public", "users"));
+        assert!(schema.columns_loaded("public", "active_users"));
+        assert!(schema.table_columns.contains_key(&("public".to_string(), "users".to_string())));
+        assert!(schema
+            .view_columns
+            .contains_key(&("public".to_string(), "active_users".to_string())));
+    }
+
+    #[test]
+    fn test_counts_of_empty_schema() {
+        let counts = DatabaseSchema::default().counts();
+        assert_eq!(counts, SchemaCounts::default());
+        assert_eq!(counts.to_string(), "0 schemas, 0 tables, 0 views, 0 functions");
+    }
+
+    #[test]
+    fn test_invalidate_until_refresh() {
+        let mut cache = SchemaCache::new(Uuid::new_v4(), DatabaseSchema::default());
+        assert!(cache.is_valid());
+
+        cache.invalidate();
+        assert!(cache.is_invalidated());
+        assert!(!cache.is_valid());
+
+        cache.refresh(DatabaseSchema::default());
+        assert!(!cache.is_invalidated());
+        assert!(cache.is_valid());
+    }
+}
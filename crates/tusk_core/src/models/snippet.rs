@@ -0,0 +1,518 @@
+//! Query snippets with placeholder expansion.
+//!
+//! A snippet body contains `${name}` placeholders that are substituted when
+//! the snippet is inserted into the editor. Placeholders without a value are
+//! left in place so the user can fill them in. A literal `${` is written as
+//! `\${`; other `$` sequences (`$1`, `$$`, `$tag$`) pass through unchanged.
+//!
+//! Suggested snippets are generated per connection: tables that recent
+//! queries touched most often become ready-to-run `SELECT` snippets.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use super::history::QueryHistoryEntry;
+use super::query_builder;
+use super::schema::{DatabaseSchema, TableInfo};
+use crate::sql_lex;
+
+/// Most snippets returned by [`suggest_snippets`].
+pub const MAX_SUGGESTED_SNIPPETS: usize = 5;
+
+/// Row limit of suggested `SELECT` snippets.
+const SUGGESTED_ROW_LIMIT: u32 = 100;
+
+/// A reusable SQL snippet stored in the user's library.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Snippet {
+    /// Unique identifier
+    pub id: Uuid,
+    /// Display name
+    pub name: String,
+    /// SQL body with `${name}` placeholders
+    pub body: String,
+    /// Placeholder names in order of first appearance
+    pub placeholders: Vec<String>,
+    /// Creation timestamp
+    pub created_at: DateTime<Utc>,
+    /// Last update timestamp
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Snippet {
+    /// Create a new snippet, collecting its placeholders from the body.
+    pub fn new(name: impl Into<String>, body: impl Into<String>) -> Self {
+        let body = body.into();
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            name: name.into(),
+            placeholders: placeholder_names(&body),
+            body,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Expand this snippet with the given placeholder values.
+    pub fn expand(&self, values: &HashMap<String, String>) -> String {
+        expand(&self.body, values)
+    }
+}
+
+/// A parsed piece of a snippet body.
+enum Segment<'a> {
+    /// Literal text copied to the output.
+    Text(&'a str),
+    /// A `${name}` placeholder (the full token and its name).
+    Placeholder { token: &'a str, name: &'a str },
+}
+
+/// Split a snippet body into literal text and placeholders.
+fn segments(body: &str) -> Vec<Segment<'_>> {
+    let bytes = body.as_bytes();
+    let mut segments = Vec::new();
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        if bytes[i] == b'\\' && bytes.get(i + 1) == Some(&b'$') {
+            // Escaped dollar: drop the backslash, keep the `$` as text
+            segments.push(Segment::Text(&body[literal_start..i]));
+            literal_start = i + 1;
+            i += 2;
+            continue;
+        }
+
+        if bytes[i] == b'$' && bytes.get(i + 1) == Some(&b'{') {
+            let name_start = i + 2;
+            let name_len = bytes[name_start..]
+                .iter()
+                .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+                .count();
+            let name_end = name_start + name_len;
+            if name_len > 0 && bytes.get(name_end) == Some(&b'}') {
+                segments.push(Segment::Text(&body[literal_start..i]));
+                segments.push(Segment::Placeholder {
+                    token: &body[i..=name_end],
+                    name: &body[name_start..name_end],
+                });
+                i = name_end + 1;
+                literal_start = i;
+                continue;
+            }
+        }
+
+        i += 1;
+    }
+
+    segments.push(Segment::Text(&body[literal_start..]));
+    segments
+}
+
+/// Get the distinct placeholder names in a snippet body, in order of first appearance.
+pub fn placeholder_names(body: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for segment in segments(body) {
+        if let Segment::Placeholder { name, .. } = segment {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+/// Substitute `${name}` placeholders in `body` with values.
+///
+/// Placeholders without a value are kept verbatim as tab-stops for the user
+/// to fill in, and `\$` escapes produce a literal `$`.
+pub fn expand(body: &str, values: &HashMap<String, String>) -> String {
+    let mut output = String::with_capacity(body.len());
+    for segment in segments(body) {
+        match segment {
+            Segment::Text(text) => output.push_str(text),
+            Segment::Placeholder { token, name } => match values.get(name) {
+                Some(value) => output.push_str(value),
+                None => output.push_str(token),
+            },
+        }
+    }
+    output
+}
+
+/// Suggest `SELECT` snippets for the tables recent queries used most.
+///
+/// Only history for `connection_id` counts, and only tables that exist in
+/// `schema` are suggested. The most used table comes first.
+pub fn suggest_snippets(
+    connection_id: Uuid,
+    schema: &DatabaseSchema,
+    recent_history: &[QueryHistoryEntry],
+) -> Vec<Snippet> {
+    let history: Vec<&QueryHistoryEntry> =
+        recent_history.iter().filter(|entry| entry.connection_id == connection_id).collect();
+
+    rank_tables_by_usage(schema, &history)
+        .into_iter()
+        .take(MAX_SUGGESTED_SNIPPETS)
+        .map(|table| {
+            Snippet::new(
+                format!("Select from {}.{}", table.schema, table.name),
+                query_builder::select_all(&table.schema, &table.name, Some(SUGGESTED_ROW_LIMIT)),
+            )
+        })
+        .collect()
+}
+
+/// Tables in `schema` ordered by how many history entries reference them.
+///
+/// Ties go to the table used most recently, then to the name. Tables no
+/// entry references are left out.
+pub fn rank_tables_by_usage<'a>(
+    schema: &'a DatabaseSchema,
+    history: &[&QueryHistoryEntry],
+) -> Vec<&'a TableInfo> {
+    let mut usage: HashMap<(&str, &str), TableUsage> = HashMap::new();
+    for entry in history {
+        let mut seen = Vec::new();
+        for reference in table_references(&entry.sql) {
+            let Some(table) = resolve_table(&schema.tables, &reference) else {
+                continue;
+            };
+            let key = (table.schema.as_str(), table.name.as_str());
+            if seen.contains(&key) {
+                continue;
+            }
+            seen.push(key);
+
+            let used = usage.entry(key).or_insert(TableUsage {
+                count: 0,
+                last_used: entry.executed_at,
+                table,
+            });
+            used.count += 1;
+            used.last_used = used.last_used.max(entry.executed_at);
+        }
+    }
+
+    let mut ranked: Vec<_> = usage.into_values().collect();
+    ranked.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then(b.last_used.cmp(&a.last_used))
+            .then_with(|| (&a.table.schema, &a.table.name).cmp(&(&b.table.schema, &b.table.name)))
+    });
+    ranked.into_iter().map(|used| used.table).collect()
+}
+
+/// How often and how recently history used a table.
+struct TableUsage<'a> {
+    count: usize,
+    last_used: DateTime<Utc>,
+    table: &'a TableInfo,
+}
+
+/// A table named in SQL: optional schema, then table name.
+type TableRef = (Option<String>, String);
+
+/// A token of SQL text, as far as table references need.
+#[derive(Debug, PartialEq)]
+enum Token {
+    /// Identifier or keyword; unquoted words are folded to lower case.
+    Word {
+        text: String,
+        quoted: bool,
+    },
+    Dot,
+    Comma,
+    Other,
+}
+
+/// Split SQL into words and punctuation, skipping strings and comments.
+///
+/// String and dollar-quoted literals become a single [`Token::Other`].
+fn tokens(sql: &str) -> Vec<Token> {
+    let bytes = sql.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\'' => {
+                let escapes = sql_lex::has_escape_prefix(bytes, i);
+                i = sql_lex::skip_quoted(bytes, i, b'\'', escapes).unwrap_or(bytes.len());
+                tokens.push(Token::Other);
+            }
+            b'"' => {
+                let end = sql_lex::skip_quoted(bytes, i, b'"', false);
+                let body = &sql[i + 1..end.map_or(bytes.len(), |end| end - 1)];
+                tokens.push(Token::Word { text: body.replace("\"\"", "\""), quoted: true });
+                i = end.unwrap_or(bytes.len());
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => i = sql_lex::skip_line_comment(bytes, i),
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = sql_lex::skip_block_comment(bytes, i).unwrap_or(bytes.len());
+            }
+            b'$' => {
+                i = match sql_lex::dollar_tag(bytes, i) {
+                    Some(tag) => sql_lex::skip_dollar_quoted(bytes, i, tag).unwrap_or(bytes.len()),
+                    None => i + 1,
+                };
+                tokens.push(Token::Other);
+            }
+            // Bytes of non-ASCII characters are taken as letters
+            b if b.is_ascii_alphabetic() || b == b'_' || !b.is_ascii() => {
+                let start = i;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric()
+                        || matches!(bytes[i], b'_' | b'$')
+                        || !bytes[i].is_ascii())
+                {
+                    i += 1;
+                }
+                tokens.push(Token::Word { text: sql[start..i].to_lowercase(), quoted: false });
+            }
+            b'.' => {
+                tokens.push(Token::Dot);
+                i += 1;
+            }
+            b',' => {
+                tokens.push(Token::Comma);
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                tokens.push(Token::Other);
+                i += 1;
+            }
+        }
+    }
+
+    tokens
+}
+
+/// Find the tables a statement reads or writes, in order of appearance.
+///
+/// Looks at names after `FROM`, `JOIN`, `UPDATE`, `INTO`, and `TABLE`,
+/// including comma-separated `FROM` lists with aliases.
+fn table_references(sql: &str) -> Vec<TableRef> {
+    let tokens = tokens(sql);
+    let keyword = |i: usize, names: &[&str]| matches!(tokens.get(i), Some(Token::Word { text, quoted: false }) if names.contains(&text.as_str()));
+    let word = |i: usize| match tokens.get(i) {
+        Some(Token::Word { text, .. }) => Some(text.clone()),
+        _ => None,
+    };
+
+    let mut references = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        if !keyword(i, &["from", "join", "update", "into", "table"]) {
+            i += 1;
+            continue;
+        }
+        let list = keyword(i, &["from"]);
+        i += 1;
+
+        loop {
+            if keyword(i, &["only"]) {
+                i += 1;
+            }
+            let Some(first) = word(i) else {
+                break;
+            };
+            if tokens.get(i + 1) == Some(&Token::Dot) {
+                let Some(second) = word(i + 2) else {
+                    break;
+                };
+                references.push((Some(first), second));
+                i += 3;
+            } else {
+                references.push((None, first));
+                i += 1;
+            }
+
+            // `FROM a x, b AS y` lists more tables after the alias
+            if keyword(i, &["as"]) {
+                i += 1;
+            }
+            if word(i).is_some() && !keyword(i, &CLAUSE_KEYWORDS) {
+                i += 1;
+            }
+            if list && tokens.get(i) == Some(&Token::Comma) {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+    }
+
+    references
+}
+
+/// Keywords that can follow a table name, so they aren't taken as aliases.
+const CLAUSE_KEYWORDS: [&str; 16] = [
+    "where",
+    "join",
+    "inner",
+    "left",
+    "right",
+    "full",
+    "cross",
+    "on",
+    "group",
+    "order",
+    "limit",
+    "set",
+    "values",
+    "returning",
+    "union",
+    "natural",
+];
+
+/// Find the table a reference names; unqualified names prefer `public`.
+fn resolve_table<'a>(tables: &'a [TableInfo], (schema, name): &TableRef) -> Option<&'a TableInfo> {
+    let mut matches = tables.iter().filter(|table| {
+        table.name == *name
+            && match schema {
+                Some(schema) => table.schema == *schema,
+                None => true,
+            }
+    });
+    let first = matches.next()?;
+    if first.schema == "public" || schema.is_some() {
+        return Some(first);
+    }
+    Some(matches.find(|table| table.schema == "public").unwrap_or(first))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schema::fixtures::table;
+
+    fn values(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_expand_substitutes_all_values() {
+        let body = "SELECT * FROM ${table} WHERE ${col} = ${val}";
+        let expanded = expand(body, &values(&[("table", "users"), ("col", "id"), ("val", "42")]));
+        assert_eq!(expanded, "SELECT * FROM users WHERE id = 42");
+    }
+
+    #[test]
+    fn test_expand_leaves_missing_values_as_tab_stops() {
+        let body = "SELECT * FROM ${table} WHERE ${col} = ${val}";
+        let expanded = expand(body, &values(&[("table", "users")]));
+        assert_eq!(expanded, "SELECT * FROM users WHERE ${col} = ${val}");
+    }
+
+    #[test]
+    fn test_expand_escaped_dollar_is_literal() {
+        let body = r"SELECT '\${table}' AS raw, ${table}.id FROM ${table}";
+        let expanded = expand(body, &values(&[("table", "orders")]));
+        assert_eq!(expanded, "SELECT '${table}' AS raw, orders.id FROM orders");
+    }
+
+    #[test]
+    fn test_expand_preserves_other_dollar_sequences() {
+        let body = "SELECT $1, $$body$$, ${ not_a_name }, ${}";
+        assert_eq!(expand(body, &HashMap::new()), body);
+    }
+
+    #[test]
+    fn test_placeholder_names_are_distinct_and_ordered() {
+        let snippet =
+            Snippet::new("lookup", r"SELECT ${col} FROM ${table} WHERE ${col} > \${skip}");
+        assert_eq!(snippet.placeholders, vec!["col", "table"]);
+    }
+
+    fn schema() -> DatabaseSchema {
+        DatabaseSchema {
+            tables: vec![
+                table("public", "users"),
+                table("public", "orders"),
+                table("billing", "invoices"),
+                table("audit", "users"),
+                table("public", "Line Items"),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn history(connection_id: Uuid, statements: &[&str]) -> Vec<QueryHistoryEntry> {
+        let start = Utc::now();
+        statements
+            .iter()
+            .enumerate()
+            .map(|(i, sql)| {
+                let mut entry = QueryHistoryEntry::new(connection_id, *sql);
+                entry.executed_at = start - chrono::Duration::minutes(i as i64);
+                entry
+            })
+            .collect()
+    }
+
+    fn names(tables: &[&TableInfo]) -> Vec<String> {
+        tables.iter().map(|table| format!("{}.{}", table.schema, table.name)).collect()
+    }
+
+    #[test]
+    fn test_rank_tables_by_frequency() {
+        let schema = schema();
+        let entries = history(
+            Uuid::new_v4(),
+            &[
+                "SELECT * FROM orders o JOIN users u ON u.id = o.user_id",
+                "select * from billing.invoices",
+                "UPDATE orders SET status = 'from users' WHERE id = 1",
+                "INSERT INTO orders (id) VALUES (1)",
+                "SELECT 1 -- from invoices",
+                "SELECT * FROM missing_table",
+                "SELECT * FROM users, audit.users AS au",
+                "DO $body$ BEGIN DELETE FROM users; DELETE FROM users; END $body$",
+            ],
+        );
+        let refs: Vec<&QueryHistoryEntry> = entries.iter().collect();
+
+        assert_eq!(
+            names(&rank_tables_by_usage(&schema, &refs)),
+            vec!["public.orders", "public.users", "billing.invoices", "audit.users"]
+        );
+    }
+
+    #[test]
+    fn test_rank_ties_prefer_recent_use() {
+        let schema = schema();
+        // The first entry is the most recent
+        let entries = history(
+            Uuid::new_v4(),
+            &["SELECT * FROM \"Line Items\"", "SELECT * FROM orders", "TABLE users"],
+        );
+        let refs: Vec<&QueryHistoryEntry> = entries.iter().collect();
+
+        assert_eq!(
+            names(&rank_tables_by_usage(&schema, &refs)),
+            vec!["public.Line Items", "public.orders", "public.users"]
+        );
+    }
+
+    #[test]
+    fn test_suggest_snippets_for_connection() {
+        let schema = schema();
+        let connection_id = Uuid::new_v4();
+        let mut entries = history(connection_id, &["SELECT * FROM users", "SELECT * FROM users"]);
+        entries.extend(history(Uuid::new_v4(), &["SELECT * FROM orders"; 5]));
+
+        let snippets = suggest_snippets(connection_id, &schema, &entries);
+        assert_eq!(snippets.len(), 1);
+        assert_eq!(snippets[0].name, "Select from public.users");
+        assert_eq!(snippets[0].body, "SELECT * FROM public.users LIMIT 100;");
+
+        assert!(suggest_snippets(connection_id, &schema, &[]).is_empty());
+    }
+}
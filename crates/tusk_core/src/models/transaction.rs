@@ -0,0 +1,95 @@
+//! Transaction state of a connection running statements from the editor.
+
+/// Whether a connection is inside an explicit transaction.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionStatus {
+    /// No transaction is open; every statement commits on its own.
+    #[default]
+    Idle,
+    /// A transaction is open and statements run inside it.
+    InTransaction,
+    /// A statement failed inside the transaction. Everything up to the
+    /// next `ROLLBACK` (or `ROLLBACK TO SAVEPOINT`) is rejected.
+    Failed,
+}
+
+/// A statement that opens or closes a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionControl {
+    /// `BEGIN` or `START TRANSACTION`
+    Begin,
+    /// `COMMIT`, `END`, or `PREPARE TRANSACTION`
+    Commit,
+    /// `ROLLBACK` or `ABORT`
+    Rollback,
+    /// `ROLLBACK TO SAVEPOINT`, which keeps the transaction open
+    RollbackToSavepoint,
+    /// `COMMIT AND CHAIN` or `ROLLBACK AND CHAIN`, which end the transaction
+    /// and immediately start another
+    Chain,
+}
+
+impl TransactionStatus {
+    /// Status after running a statement in this status.
+    ///
+    /// `control` is the statement's effect on the transaction (None for
+    /// ordinary statements) and `succeeded` whether it ran without error.
+    pub fn after(self, control: Option<TransactionControl>, succeeded: bool) -> Self {
+        match control {
+            // COMMIT of a failed transaction rolls it back; either way it's over
+            Some(TransactionControl::Commit | TransactionControl::Rollback) => Self::Idle,
+            Some(TransactionControl::Begin) if self == Self::Idle && succeeded => {
+                Self::InTransaction
+            }
+            Some(TransactionControl::RollbackToSavepoint | TransactionControl::Chain)
+                if self != Self::Idle && succeeded =>
+            {
+                Self::InTransaction
+            }
+            _ if self == Self::Idle => Self::Idle,
+            _ if self == Self::InTransaction && succeeded => Self::InTransaction,
+            _ => Self::Failed,
+        }
+    }
+
+    /// Check if a transaction is open, failed or not.
+    pub fn is_open(&self) -> bool {
+        *self != Self::Idle
+    }
+
+    /// Display name of the status.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Idle => "Idle",
+            Self::InTransaction => "In transaction",
+            Self::Failed => "Transaction failed",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_follows_transaction_statements() {
+        use TransactionControl::*;
+
+        let open = TransactionStatus::Idle.after(Some(Begin), true);
+        assert_eq!(open, TransactionStatus::InTransaction);
+        assert_eq!(open.after(None, true), TransactionStatus::InTransaction);
+        assert_eq!(TransactionStatus::Idle.after(None, false), TransactionStatus::Idle);
+
+        let failed = open.after(None, false);
+        assert_eq!(failed, TransactionStatus::Failed);
+        assert_eq!(failed.after(None, false), TransactionStatus::Failed);
+        assert_eq!(failed.after(Some(RollbackToSavepoint), true), TransactionStatus::InTransaction);
+        assert_eq!(failed.after(Some(Commit), true), TransactionStatus::Idle);
+
+        // A failed COMMIT (e.g. a deferred constraint) still ends the transaction
+        assert_eq!(open.after(Some(Commit), false), TransactionStatus::Idle);
+        assert_eq!(open.after(Some(Rollback), true), TransactionStatus::Idle);
+        assert!(!TransactionStatus::Idle.after(Some(RollbackToSavepoint), true).is_open());
+        assert_eq!(failed.after(Some(Chain), true), TransactionStatus::InTransaction);
+    }
+}
@@ -0,0 +1,428 @@
+//! Text form of PostgreSQL binary values.
+//!
+//! Query results arrive in the binary wire format, and tokio-postgres only
+//! decodes a handful of scalar types to Rust values without extra features.
+//! [`scalar_text`] turns the binary form of the common built-in types into
+//! the same text psql would show (with timestamps in UTC), so results can be
+//! displayed, copied, and exported without a second text-mode query.
+
+use std::error::Error;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime};
+use tokio_postgres::types::{FromSql, Kind, Type};
+
+/// Error returned when a binary value is malformed.
+pub type DecodeError = Box<dyn Error + Sync + Send>;
+
+/// Microseconds per second.
+const USECS_PER_SEC: i64 = 1_000_000;
+
+/// Decode the binary form of a scalar value to its text form.
+///
+/// Domains are decoded as their base type and enums as their label.
+/// Returns None for types that have no decoder here, such as extension
+/// types or ranges.
+pub fn scalar_text(ty: &Type, raw: &[u8]) -> Result<Option<String>, DecodeError> {
+    let text = match *ty {
+        Type::BOOL => bool::from_sql(ty, raw)?.to_string(),
+        Type::INT2 => i16::from_sql(ty, raw)?.to_string(),
+        Type::INT4 => i32::from_sql(ty, raw)?.to_string(),
+        Type::INT8 => i64::from_sql(ty, raw)?.to_string(),
+        Type::OID => u32::from_sql(ty, raw)?.to_string(),
+        Type::FLOAT4 => {
+            let value = f32::from_sql(ty, raw)?;
+            float_text(value.into(), value)
+        }
+        Type::FLOAT8 => {
+            let value = f64::from_sql(ty, raw)?;
+            float_text(value, value)
+        }
+        Type::NUMERIC => numeric_text(raw)?,
+        Type::MONEY => money_text(read_i64(raw)?),
+        Type::DATE => date_text(read_i32(raw)?),
+        Type::TIME => time_text(read_i64(raw)?),
+        Type::TIMETZ => timetz_text(raw)?,
+        Type::TIMESTAMP => timestamp_text(read_i64(raw)?, ""),
+        Type::TIMESTAMPTZ => timestamp_text(read_i64(raw)?, "+00"),
+        Type::INTERVAL => interval_text(raw)?,
+        Type::UUID => uuid_text(raw)?,
+        Type::JSON => std::str::from_utf8(raw)?.to_string(),
+        Type::JSONB => match raw.split_first() {
+            Some((1, json)) => std::str::from_utf8(json)?.to_string(),
+            _ => return Err("unsupported jsonb version".into()),
+        },
+        Type::INET | Type::CIDR => inet_text(raw)?,
+        Type::MACADDR | Type::MACADDR8 => {
+            raw.iter().map(|b| format!("{b:02x}")).collect::<Vec<_>>().join(":")
+        }
+        Type::BIT | Type::VARBIT => bit_text(raw)?,
+        Type::BYTEA => {
+            let mut text = String::with_capacity(2 + raw.len() * 2);
+            text.push_str("\\x");
+            for b in raw {
+                text.push_str(&format!("{b:02x}"));
+            }
+            text
+        }
+        Type::XML => std::str::from_utf8(raw)?.to_string(),
+        _ if <String as FromSql>::accepts(ty) => String::from_sql(ty, raw)?,
+        _ => match ty.kind() {
+            Kind::Domain(inner) => return scalar_text(inner, raw),
+            Kind::Enum(_) => std::str::from_utf8(raw)?.to_string(),
+            _ => return Ok(None),
+        },
+    };
+    Ok(Some(text))
+}
+
+/// Format a float the way PostgreSQL does for special values.
+///
+/// `display` is the value at its own precision, so `float4` values print
+/// without `f64` widening noise.
+fn float_text(value: f64, display: impl ToString) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string()
+    } else {
+        display.to_string()
+    }
+}
+
+fn read_i16(raw: &[u8], at: usize) -> Result<i16, DecodeError> {
+    raw.get(at..at + 2)
+        .map(|b| i16::from_be_bytes([b[0], b[1]]))
+        .ok_or_else(|| "unexpected end of binary value".into())
+}
+
+fn read_i32(raw: &[u8]) -> Result<i32, DecodeError> {
+    let bytes: [u8; 4] = raw.get(..4).and_then(|b| b.try_into().ok()).ok_or("invalid length")?;
+    Ok(i32::from_be_bytes(bytes))
+}
+
+fn read_i64(raw: &[u8]) -> Result<i64, DecodeError> {
+    let bytes: [u8; 8] = raw.get(..8).and_then(|b| b.try_into().ok()).ok_or("invalid length")?;
+    Ok(i64::from_be_bytes(bytes))
+}
+
+/// Format `numeric`: base-10000 digits with a weight and display scale.
+fn numeric_text(raw: &[u8]) -> Result<String, DecodeError> {
+    let ndigits = read_i16(raw, 0)?.max(0) as usize;
+    let weight = read_i16(raw, 2)? as i32;
+    let sign = read_i16(raw, 4)? as u16;
+    let dscale = read_i16(raw, 6)?.max(0) as usize;
+    match sign {
+        0xC000 => return Ok("NaN".to_string()),
+        0xD000 => return Ok("Infinity".to_string()),
+        0xF000 => return Ok("-Infinity".to_string()),
+        _ => {}
+    }
+    let digits = (0..ndigits).map(|i| read_i16(raw, 8 + i * 2)).collect::<Result<Vec<_>, _>>()?;
+    let digit = |index: i32| -> i16 {
+        usize::try_from(index).ok().and_then(|i| digits.get(i)).copied().unwrap_or(0)
+    };
+
+    let mut text = String::new();
+    if sign == 0x4000 {
+        text.push('-');
+    }
+    if weight < 0 {
+        text.push('0');
+    } else {
+        text.push_str(&digit(0).to_string());
+        for index in 1..=weight {
+            text.push_str(&format!("{:04}", digit(index)));
+        }
+    }
+    if dscale > 0 {
+        let mut fraction = String::with_capacity(dscale + 4);
+        let mut index = weight + 1;
+        while fraction.len() < dscale {
+            fraction.push_str(&format!("{:04}", digit(index)));
+            index += 1;
+        }
+        fraction.truncate(dscale);
+        text.push('.');
+        text.push_str(&fraction);
+    }
+    Ok(text)
+}
+
+/// Format `money` from cents, without a currency symbol.
+fn money_text(cents: i64) -> String {
+    let sign = if cents < 0 { "-" } else { "" };
+    let cents = cents.unsigned_abs();
+    format!("{}{}.{:02}", sign, cents / 100, cents % 100)
+}
+
+/// Midnight on 2000-01-01, the PostgreSQL epoch.
+fn pg_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("2000-01-01 is a valid date")
+}
+
+/// Format `date` from days since 2000-01-01.
+fn date_text(days: i32) -> String {
+    match days {
+        i32::MAX => "infinity".to_string(),
+        i32::MIN => "-infinity".to_string(),
+        _ => pg_epoch()
+            .date()
+            .checked_add_signed(Duration::days(days.into()))
+            .map(|date| {
+                let (text, era) = civil_date(date);
+                text + era
+            })
+            .unwrap_or_else(|| format!("<date {days}>")),
+    }
+}
+
+/// Format a date as `YYYY-MM-DD`, returning the `" BC"` suffix PostgreSQL
+/// puts after the whole value for years before 1 AD.
+fn civil_date(date: NaiveDate) -> (String, &'static str) {
+    let (year, era) = match date.year() {
+        year if year > 0 => (year, ""),
+        // Year 0 is 1 BC
+        year => (1 - year, " BC"),
+    };
+    (format!("{:04}-{:02}-{:02}", year, date.month(), date.day()), era)
+}
+
+/// Format a time of day in microseconds as `HH:MM:SS[.ffffff]`.
+fn time_text(usecs: i64) -> String {
+    let secs = usecs.div_euclid(USECS_PER_SEC);
+    let mut text = format!("{:02}:{:02}:{:02}", secs / 3600, (secs / 60) % 60, secs % 60);
+    push_fraction(&mut text, usecs.rem_euclid(USECS_PER_SEC));
+    text
+}
+
+/// Append microseconds as a fraction with trailing zeros removed.
+fn push_fraction(text: &mut String, usecs: i64) {
+    if usecs != 0 {
+        let fraction = format!(".{usecs:06}");
+        text.push_str(fraction.trim_end_matches('0'));
+    }
+}
+
+/// Format `timetz`: time of day followed by the UTC offset.
+fn timetz_text(raw: &[u8]) -> Result<String, DecodeError> {
+    let usecs = read_i64(raw)?;
+    // Stored as seconds west of UTC
+    let offset = -read_i32(raw.get(8..).ok_or("invalid length")?)?;
+    Ok(format!("{}{}", time_text(usecs), offset_text(offset)))
+}
+
+/// Format a UTC offset in seconds as `+HH`, `+HH:MM`, or `+HH:MM:SS`.
+fn offset_text(offset: i32) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let offset = offset.unsigned_abs();
+    let mut text = format!("{}{:02}", sign, offset / 3600);
+    if offset % 3600 != 0 {
+        text.push_str(&format!(":{:02}", (offset / 60) % 60));
+        if offset % 60 != 0 {
+            text.push_str(&format!(":{:02}", offset % 60));
+        }
+    }
+    text
+}
+
+/// Format `timestamp`/`timestamptz` from microseconds since 2000-01-01.
+fn timestamp_text(usecs: i64, zone: &str) -> String {
+    match usecs {
+        i64::MAX => "infinity".to_string(),
+        i64::MIN => "-infinity".to_string(),
+        _ => {
+            let secs = usecs.div_euclid(USECS_PER_SEC);
+            match pg_epoch().checked_add_signed(Duration::seconds(secs)) {
+                Some(at) => {
+                    let (mut text, era) = civil_date(at.date());
+                    text.push_str(&at.format(" %H:%M:%S").to_string());
+                    push_fraction(&mut text, usecs.rem_euclid(USECS_PER_SEC));
+                    text.push_str(zone);
+                    text.push_str(era);
+                    text
+                }
+                None => format!("<timestamp {usecs}>"),
+            }
+        }
+    }
+}
+
+/// Format `interval` like PostgreSQL's default output style.
+fn interval_text(raw: &[u8]) -> Result<String, DecodeError> {
+    let usecs = read_i64(raw)?;
+    let days = read_i32(raw.get(8..).ok_or("invalid length")?)?;
+    let months = read_i32(raw.get(12..).ok_or("invalid length")?)?;
+
+    let unit =
+        |value: i32, name: &str| format!("{} {}{}", value, name, if value == 1 { "" } else { "s" });
+    let mut parts = Vec::new();
+    if months / 12 != 0 {
+        parts.push(unit(months / 12, "year"));
+    }
+    if months % 12 != 0 {
+        parts.push(unit(months % 12, "mon"));
+    }
+    if days != 0 {
+        parts.push(unit(days, "day"));
+    }
+    if usecs != 0 || parts.is_empty() {
+        let sign = if usecs < 0 { "-" } else { "" };
+        parts.push(format!("{}{}", sign, time_text(usecs.abs())));
+    }
+    Ok(parts.join(" "))
+}
+
+/// Format a `uuid` as lowercase hyphenated hex.
+fn uuid_text(raw: &[u8]) -> Result<String, DecodeError> {
+    if raw.len() != 16 {
+        return Err("invalid uuid length".into());
+    }
+    let hex: String = raw.iter().map(|b| format!("{b:02x}")).collect();
+    Ok(format!("{}-{}-{}-{}-{}", &hex[0..8], &hex[8..12], &hex[12..16], &hex[16..20], &hex[20..32]))
+}
+
+/// Format `inet`/`cidr`: family, prefix bits, cidr flag, then the address.
+///
+/// The prefix is shown for `cidr` values and for `inet` values that are not
+/// a single host.
+fn inet_text(raw: &[u8]) -> Result<String, DecodeError> {
+    let [family, bits, is_cidr, len, address @ ..] = raw else {
+        return Err("invalid inet value".into());
+    };
+    if address.len() != usize::from(*len) {
+        return Err("invalid inet address length".into());
+    }
+    let (text, max_bits) = match (family, address) {
+        (2, &[a, b, c, d]) => (Ipv4Addr::new(a, b, c, d).to_string(), 32),
+        (3, address) if address.len() == 16 => {
+            let mut octets = [0u8; 16];
+            octets.copy_from_slice(address);
+            (Ipv6Addr::from(octets).to_string(), 128)
+        }
+        _ => return Err("invalid inet address family".into()),
+    };
+    if *is_cidr != 0 || *bits != max_bits {
+        Ok(format!("{text}/{bits}"))
+    } else {
+        Ok(text)
+    }
+}
+
+/// Format `bit`/`varbit` as a string of 0s and 1s.
+fn bit_text(raw: &[u8]) -> Result<String, DecodeError> {
+    let len = usize::try_from(read_i32(raw)?).map_err(|_| "invalid bit length")?;
+    let bytes = &raw[4..];
+    if bytes.len() * 8 < len {
+        return Err("unexpected end of binary value".into());
+    }
+    Ok((0..len).map(|i| if bytes[i / 8] & (0x80 >> (i % 8)) != 0 { '1' } else { '0' }).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(ty: Type, raw: &[u8]) -> String {
+        scalar_text(&ty, raw).unwrap().unwrap()
+    }
+
+    /// Binary `numeric` from its header fields and base-10000 digits.
+    fn numeric(weight: i16, sign: u16, dscale: i16, digits: &[i16]) -> Vec<u8> {
+        let mut raw = Vec::new();
+        raw.extend_from_slice(&(digits.len() as i16).to_be_bytes());
+        raw.extend_from_slice(&weight.to_be_bytes());
+        raw.extend_from_slice(&sign.to_be_bytes());
+        raw.extend_from_slice(&dscale.to_be_bytes());
+        for digit in digits {
+            raw.extend_from_slice(&digit.to_be_bytes());
+        }
+        raw
+    }
+
+    #[test]
+    fn test_numeric_text() {
+        // 12345.678
+        assert_eq!(text(Type::NUMERIC, &numeric(1, 0, 3, &[1, 2345, 6780])), "12345.678");
+        // -0.0012
+        assert_eq!(text(Type::NUMERIC, &numeric(-1, 0x4000, 4, &[12])), "-0.0012");
+        // 10000 (trailing zero groups are not stored)
+        assert_eq!(text(Type::NUMERIC, &numeric(1, 0, 0, &[1])), "10000");
+        // 1.50 keeps its scale
+        assert_eq!(text(Type::NUMERIC, &numeric(0, 0, 2, &[1, 5000])), "1.50");
+        assert_eq!(text(Type::NUMERIC, &numeric(0, 0, 0, &[])), "0");
+        assert_eq!(text(Type::NUMERIC, &numeric(0, 0xC000, 0, &[])), "NaN");
+    }
+
+    #[test]
+    fn test_temporal_text() {
+        let day_usecs = 86_400 * USECS_PER_SEC;
+        assert_eq!(text(Type::DATE, &0i32.to_be_bytes()), "2000-01-01");
+        assert_eq!(text(Type::DATE, &(-1i32).to_be_bytes()), "1999-12-31");
+        assert_eq!(text(Type::DATE, &i32::MAX.to_be_bytes()), "infinity");
+        // 44 BC is year -43
+        let ides = NaiveDate::from_ymd_opt(-43, 3, 15).unwrap();
+        let days = (ides - pg_epoch().date()).num_days() as i32;
+        assert_eq!(text(Type::DATE, &days.to_be_bytes()), "0044-03-15 BC");
+        let noon = (i64::from(days) * 86_400 + 43_200) * USECS_PER_SEC;
+        assert_eq!(text(Type::TIMESTAMPTZ, &noon.to_be_bytes()), "0044-03-15 12:00:00+00 BC");
+
+        let at = 366 * day_usecs + 3_723 * USECS_PER_SEC + 500_000;
+        assert_eq!(text(Type::TIMESTAMP, &at.to_be_bytes()), "2001-01-01 01:02:03.5");
+        assert_eq!(text(Type::TIMESTAMPTZ, &at.to_be_bytes()), "2001-01-01 01:02:03.5+00");
+        assert_eq!(text(Type::TIMESTAMP, &(-1i64).to_be_bytes()), "1999-12-31 23:59:59.999999");
+
+        assert_eq!(text(Type::TIME, &(45_296 * USECS_PER_SEC).to_be_bytes()), "12:34:56");
+        let mut timetz = (45_296 * USECS_PER_SEC).to_be_bytes().to_vec();
+        timetz.extend_from_slice(&(-19_800i32).to_be_bytes());
+        assert_eq!(text(Type::TIMETZ, &timetz), "12:34:56+05:30");
+    }
+
+    #[test]
+    fn test_interval_text() {
+        let interval = |usecs: i64, days: i32, months: i32| {
+            let mut raw = usecs.to_be_bytes().to_vec();
+            raw.extend_from_slice(&days.to_be_bytes());
+            raw.extend_from_slice(&months.to_be_bytes());
+            text(Type::INTERVAL, &raw)
+        };
+        assert_eq!(interval(14_706 * USECS_PER_SEC, 3, 14), "1 year 2 mons 3 days 04:05:06");
+        assert_eq!(interval(0, 1, 0), "1 day");
+        assert_eq!(interval(-90 * USECS_PER_SEC, -2, 0), "-2 days -00:01:30");
+        assert_eq!(interval(0, 0, 0), "00:00:00");
+    }
+
+    #[test]
+    fn test_identifier_and_document_text() {
+        let uuid: Vec<u8> = (0u8..16).collect();
+        assert_eq!(text(Type::UUID, &uuid), "00010203-0405-0607-0809-0a0b0c0d0e0f");
+
+        assert_eq!(text(Type::JSON, br#"{"a": 1}"#), r#"{"a": 1}"#);
+        assert_eq!(text(Type::JSONB, b"\x01{\"a\": 1}"), r#"{"a": 1}"#);
+        assert!(scalar_text(&Type::JSONB, b"\x02{}").is_err());
+
+        assert_eq!(text(Type::INET, &[2, 32, 0, 4, 192, 168, 0, 1]), "192.168.0.1");
+        assert_eq!(text(Type::INET, &[2, 24, 0, 4, 10, 0, 0, 1]), "10.0.0.1/24");
+        assert_eq!(text(Type::CIDR, &[2, 32, 1, 4, 10, 0, 0, 1]), "10.0.0.1/32");
+        let mut v6 = vec![3, 128, 0, 16];
+        v6.extend_from_slice(&Ipv6Addr::LOCALHOST.octets());
+        assert_eq!(text(Type::INET, &v6), "::1");
+
+        assert_eq!(text(Type::BYTEA, &[0xde, 0xad, 0x01]), "\\xdead01");
+        assert_eq!(text(Type::MACADDR, &[8, 0, 0x2b, 1, 2, 3]), "08:00:2b:01:02:03");
+        assert_eq!(text(Type::VARBIT, &[0, 0, 0, 5, 0b1010_1000]), "10101");
+        assert_eq!(text(Type::MONEY, &(-123_45i64).to_be_bytes()), "-123.45");
+    }
+
+    #[test]
+    fn test_scalar_text_basics() {
+        assert_eq!(text(Type::BOOL, &[1]), "true");
+        assert_eq!(text(Type::INT4, &42i32.to_be_bytes()), "42");
+        assert_eq!(text(Type::FLOAT8, &f64::INFINITY.to_be_bytes()), "Infinity");
+        assert_eq!(text(Type::FLOAT4, &0.1f32.to_be_bytes()), "0.1");
+        assert_eq!(text(Type::TEXT, b"hello"), "hello");
+        assert_eq!(scalar_text(&Type::POINT, b"").unwrap(), None);
+    }
+}
@@ -4,22 +4,43 @@
 //! - Connection validation on pool creation (FR-011)
 //! - Pool status reporting (FR-013)
 //! - Configurable timeout on pool exhaustion (FR-013a)
+//! - Connect timeout bounding the whole initial connection attempt (E04)
 //! - Session defaults (statement_timeout, idle_in_transaction_session_timeout)
+//! - Warmup of idle connections and per-connection prepared statement caching
+//! - Retries with exponential backoff for unattended connects (startup)
+//! - LISTEN subscriptions on dedicated connections outside the pool
 
 use crate::error::TuskError;
-use crate::models::{ConnectionConfig, PoolStatus};
+use crate::models::connection::MAX_STREAM_BATCH_SIZE;
+use crate::models::{
+    ConnectionConfig, NoticeSeverity, Notification, NotificationEvent, PoolStatus, QueryEvent,
+    QueryHandle, TransactionControl, TransactionStatus,
+};
+use crate::services::ddl::quote_ident;
 
 use chrono::{DateTime, Utc};
-use deadpool_postgres::{Manager, ManagerConfig, Pool, RecyclingMethod, Runtime};
+use deadpool_postgres::{Manager, ManagerConfig, Pool, PoolError, RecyclingMethod, Runtime};
+use futures_util::future::{try_join_all, BoxFuture};
+use futures_util::StreamExt;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio_postgres::NoTls;
+use tokio::io::AsyncReadExt;
+use tokio::select;
+use tokio::sync::mpsc;
+use tokio_postgres::error::SqlState;
+use tokio_postgres::{AsyncMessage, NoTls};
+use tokio_util::sync::{CancellationToken, DropGuard};
 use uuid::Uuid;
 
 /// Default idle_in_transaction_session_timeout in seconds (5 minutes).
 /// Prevents abandoned transactions from holding locks indefinitely.
 const DEFAULT_IDLE_IN_TRANSACTION_TIMEOUT_SECS: u32 = 300;
 
+/// Capacity of a LISTEN subscription's event channel.
+const NOTIFICATION_BUFFER: usize = 100;
+
 /// A managed pool of database connections for a single ConnectionConfig.
 ///
 /// Wraps deadpool-postgres to provide connection reuse, health checking,
@@ -36,6 +57,16 @@ pub struct ConnectionPool {
     created_at: DateTime<Utc>,
     /// SQL to set session defaults (statement_timeout, idle_in_transaction_session_timeout)
     session_defaults_sql: Option<String>,
+    /// Settings for connections opened outside the pool (LISTEN)
+    pg_config: tokio_postgres::Config,
+    /// Where notices raised on pooled connections are delivered
+    notices: Arc<NoticeRouter>,
+    /// Connection pinned for an open explicit transaction
+    transaction: parking_lot::Mutex<Option<Arc<TransactionHandle>>>,
+    /// Whether a committed transaction ran DDL not yet reflected in the schema
+    committed_schema_change: AtomicBool,
+    /// search_path from the last `SET search_path`, applied on every checkout
+    search_path: parking_lot::RwLock<Option<String>>,
 }
 
 impl ConnectionPool {
@@ -47,6 +78,17 @@ impl ConnectionPool {
         Self::with_pool_config(config, password, 4, Duration::from_secs(30)).await
     }
 
+    /// Check that a connection can be established, without keeping it.
+    ///
+    /// Opens a single-connection pool, validates it, and closes it again.
+    /// Waits no longer than the connection's own connect timeout.
+    pub async fn test(config: ConnectionConfig, password: &str) -> Result<(), TuskError> {
+        let timeout = Duration::from_secs(config.options.connect_timeout_secs as u64);
+        let pool = Self::with_pool_config(config, password, 1, timeout).await?;
+        pool.close();
+        Ok(())
+    }
+
     /// Create a connection pool with custom pool settings.
     ///
     /// # Arguments
@@ -74,10 +116,12 @@ impl ConnectionPool {
         pg_config.keepalives(true);
         pg_config.keepalives_idle(Duration::from_secs(60));
 
-        // Create manager with recycling for connection health
-        let manager = Manager::from_config(
-            pg_config,
-            NoTls,
+        // Create manager with recycling for connection health. Connections
+        // are opened by NoticeConnect so their notices aren't discarded.
+        let notices = Arc::new(NoticeRouter::default());
+        let manager = Manager::from_connect(
+            pg_config.clone(),
+            NoticeConnect { notices: notices.clone() },
             ManagerConfig { recycling_method: RecyclingMethod::Fast },
         );
 
@@ -101,39 +145,57 @@ impl ConnectionPool {
         // Build session defaults SQL (statement_timeout, idle_in_transaction_session_timeout)
         let session_defaults_sql = Self::build_session_defaults_sql(&config);
 
-        // Validate connection by establishing a test connection (FR-011)
-        let client = pool.get().await.map_err(|e| {
-            tracing::error!(
-                host = %config.host,
-                database = %config.database,
-                error = %e,
-                "Failed to establish initial connection"
-            );
-            TuskError::connection(format!("Failed to establish connection: {e}"))
-        })?;
-
-        // Apply session defaults on the validation connection
-        if let Some(ref sql) = session_defaults_sql {
-            client.execute(sql.as_str(), &[]).await.map_err(|e| {
+        // Validate connection by establishing a test connection (FR-011). The
+        // whole attempt is bounded by the connect timeout, which otherwise only
+        // covers opening the socket, not a server that never answers.
+        let validate = async {
+            let client = pool.get().await.map_err(|e| {
                 tracing::error!(
-                    connection_id = %config.id,
+                    host = %config.host,
+                    database = %config.database,
                     error = %e,
-                    "Failed to set session defaults on initial connection"
+                    "Failed to establish initial connection"
                 );
-                TuskError::connection(format!("Failed to set session defaults: {e}"))
+                if Self::is_connect_timeout(&e) {
+                    TuskError::connection_timeout(connect_timeout)
+                } else {
+                    Self::connect_error(e)
+                }
             })?;
-        }
 
-        // Execute a simple query to verify the connection is working
-        client.execute("SELECT 1", &[]).await.map_err(|e| {
+            // Apply session defaults on the validation connection
+            if let Some(ref sql) = session_defaults_sql {
+                client.batch_execute(sql).await.map_err(|e| {
+                    tracing::error!(
+                        connection_id = %config.id,
+                        error = %e,
+                        "Failed to set session defaults on initial connection"
+                    );
+                    TuskError::connection(format!("Failed to set session defaults: {e}"))
+                })?;
+            }
+
+            // Execute a simple query to verify the connection is working
+            client.execute("SELECT 1", &[]).await.map_err(|e| {
+                tracing::error!(
+                    host = %config.host,
+                    database = %config.database,
+                    error = %e,
+                    "Connection validation query failed"
+                );
+                TuskError::connection(format!("Connection validation failed: {e}"))
+            })?;
+            Ok::<_, TuskError>(())
+        };
+        tokio::time::timeout(connect_timeout, validate).await.map_err(|_| {
             tracing::error!(
                 host = %config.host,
                 database = %config.database,
-                error = %e,
-                "Connection validation query failed"
+                timeout_secs = connect_timeout.as_secs(),
+                "Connection attempt timed out"
             );
-            TuskError::connection(format!("Connection validation failed: {e}"))
-        })?;
+            TuskError::connection_timeout(connect_timeout)
+        })??;
 
         tracing::info!(
             connection_id = %config.id,
@@ -148,10 +210,112 @@ impl ConnectionPool {
             pool,
             created_at: Utc::now(),
             session_defaults_sql,
+            pg_config,
+            notices,
+            transaction: parking_lot::Mutex::new(None),
+            committed_schema_change: AtomicBool::new(false),
+            search_path: parking_lot::RwLock::new(None),
         })
     }
 
-    /// Build SQL to set session defaults (statement_timeout, idle_in_transaction_session_timeout).
+    /// Create a pool without connecting, for tests that need no server.
+    #[cfg(test)]
+    pub(crate) fn unvalidated(config: ConnectionConfig) -> Self {
+        let manager = Manager::from_config(
+            tokio_postgres::Config::new(),
+            NoTls,
+            ManagerConfig { recycling_method: RecyclingMethod::Fast },
+        );
+        let pool = Pool::builder(manager)
+            .max_size(1)
+            .runtime(Runtime::Tokio1)
+            .build()
+            .expect("pool without timeouts builds");
+        Self {
+            id: config.id,
+            config: Arc::new(config),
+            pool,
+            created_at: Utc::now(),
+            session_defaults_sql: None,
+            pg_config: tokio_postgres::Config::new(),
+            notices: Arc::new(NoticeRouter::default()),
+            transaction: parking_lot::Mutex::new(None),
+            committed_schema_change: AtomicBool::new(false),
+            search_path: parking_lot::RwLock::new(None),
+        }
+    }
+
+    /// Connect, retrying transient failures with exponential backoff.
+    ///
+    /// Used when nobody is waiting at the dialog, such as connecting at
+    /// startup while the server may still be coming up. Errors retrying
+    /// won't fix (see [`TuskError::is_retryable`]) are returned after the
+    /// first attempt. Cancelling `handle` stops the attempts.
+    pub async fn connect_with_retry(
+        config: ConnectionConfig,
+        password: &str,
+        policy: RetryPolicy,
+        handle: &QueryHandle,
+    ) -> Result<Self, TuskError> {
+        let mut attempt = 1;
+        loop {
+            let result = tokio::select! {
+                result = Self::new(config.clone(), password) => result,
+                _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+            };
+            let error = match result {
+                Ok(pool) => return Ok(pool),
+                Err(error) => error,
+            };
+            if !policy.should_retry(attempt, &error) {
+                return Err(error);
+            }
+
+            let delay = policy.delay_before_retry(attempt);
+            tracing::warn!(
+                connection_id = %config.id,
+                attempt,
+                max_attempts = policy.max_attempts,
+                delay_ms = delay.as_millis() as u64,
+                error = %error,
+                "Connection attempt failed, retrying"
+            );
+            tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Map a failure to open the first connection.
+    ///
+    /// Errors reported by the server (bad password, unknown database) keep
+    /// their PostgreSQL mapping so they aren't mistaken for an unreachable
+    /// server.
+    fn connect_error(err: PoolError) -> TuskError {
+        match err {
+            PoolError::Backend(e) if e.as_db_error().is_some() => TuskError::from(e),
+            e => TuskError::connection(format!("Failed to establish connection: {e}")),
+        }
+    }
+
+    /// Check whether getting a connection failed because connecting took too long.
+    ///
+    /// Either the pool's create timeout or the socket connect timeout can
+    /// expire first; both mean the server didn't answer in time.
+    fn is_connect_timeout(err: &PoolError) -> bool {
+        match err {
+            PoolError::Timeout(_) => true,
+            PoolError::Backend(e) => std::error::Error::source(e)
+                .and_then(|source| source.downcast_ref::<std::io::Error>())
+                .is_some_and(|io| io.kind() == std::io::ErrorKind::TimedOut),
+            _ => false,
+        }
+    }
+
+    /// Build SQL to set session defaults (statement_timeout,
+    /// idle_in_transaction_session_timeout, search_path).
     fn build_session_defaults_sql(config: &ConnectionConfig) -> Option<String> {
         let mut statements = Vec::new();
 
@@ -167,6 +331,16 @@ impl ConnectionPool {
         let idle_timeout_ms = DEFAULT_IDLE_IN_TRANSACTION_TIMEOUT_SECS as u64 * 1000;
         statements.push(format!("SET idle_in_transaction_session_timeout = {idle_timeout_ms}"));
 
+        // Put the connection's default schema first, keeping public for extensions
+        if let Some(schema) = config.options.default_schema.as_deref().filter(|s| !s.is_empty()) {
+            let schema = quote_ident(schema);
+            if schema == "public" {
+                statements.push("SET search_path TO public".to_string());
+            } else {
+                statements.push(format!("SET search_path TO {schema}, public"));
+            }
+        }
+
         if statements.is_empty() {
             None
         } else {
@@ -174,6 +348,35 @@ impl ConnectionPool {
         }
     }
 
+    /// SQL run on each acquired connection: the session defaults followed by
+    /// any search_path set with [`Self::set_search_path`].
+    fn checkout_sql(&self) -> Option<String> {
+        let search_path = match self.search_path.read().as_deref() {
+            None => None,
+            Some("") => Some("RESET search_path".to_string()),
+            Some(search_path) => Some(format!("SET search_path TO {search_path}")),
+        };
+        match (&self.session_defaults_sql, search_path) {
+            (Some(defaults), Some(search_path)) => Some(format!("{defaults}; {search_path}")),
+            (defaults, search_path) => search_path.or_else(|| defaults.clone()),
+        }
+    }
+
+    /// Use a search_path for every connection acquired from now on.
+    ///
+    /// `SET search_path` only changes the backend it ran on, so later
+    /// statements on other pooled connections would not see it. The value
+    /// is as written after `TO`; an empty one (from `RESET`) restores the
+    /// server default.
+    pub fn set_search_path(&self, search_path: &str) {
+        *self.search_path.write() = Some(search_path.to_string());
+    }
+
+    /// The search_path set with [`Self::set_search_path`], if any.
+    pub fn search_path(&self) -> Option<String> {
+        self.search_path.read().clone()
+    }
+
     /// Get the pool's unique identifier.
     pub fn id(&self) -> Uuid {
         self.id
@@ -220,8 +423,8 @@ impl ConnectionPool {
 
         // Apply session defaults on each acquired connection
         // This ensures timeouts are set even for recycled connections
-        if let Some(ref sql) = self.session_defaults_sql {
-            client.execute(sql.as_str(), &[]).await.map_err(|e| {
+        if let Some(sql) = self.checkout_sql() {
+            client.batch_execute(&sql).await.map_err(|e| {
                 tracing::error!(
                     connection_id = %self.id,
                     error = %e,
@@ -231,7 +434,132 @@ impl ConnectionPool {
             })?;
         }
 
-        Ok(PooledConnection { client, connection_id: self.id })
+        Ok(PooledConnection {
+            client,
+            connection_id: self.id,
+            cache_statements: self.config.options.cache_prepared_statements,
+            stream_batch_size: self.config.options.stream_batch_size as usize,
+            cursor_fetch_size: self
+                .config
+                .options
+                .cursor_fetch_size
+                .map(|size| size.clamp(1, MAX_STREAM_BATCH_SIZE) as usize),
+            notices: self.notices.clone(),
+        })
+    }
+
+    /// Pre-establish up to `n` connections so the first queries don't pay
+    /// the connection setup cost.
+    ///
+    /// Connections are opened concurrently and returned to the pool idle.
+    /// Connections already checked out count toward `n`, and the pool's
+    /// maximum size caps it. Returns the number of connections warmed.
+    pub async fn warmup(&self, n: usize) -> Result<usize, TuskError> {
+        let status = self.pool.status();
+        let in_use = status.size.saturating_sub(status.available);
+        let target = n.min(status.max_size).saturating_sub(in_use);
+        if target == 0 {
+            return Ok(0);
+        }
+
+        let connections = try_join_all((0..target).map(|_| self.get())).await?;
+        let warmed = connections.len();
+        drop(connections);
+
+        tracing::debug!(connection_id = %self.id, warmed, "Connection pool warmed up");
+        Ok(warmed)
+    }
+
+    /// Subscribe to notifications on a channel.
+    ///
+    /// Notifications only reach the session that ran `LISTEN`, so this opens
+    /// a dedicated connection outside the pool. Dropping the returned
+    /// listener closes that connection, which unsubscribes. If the
+    /// connection fails, the listener receives a final
+    /// [`NotificationEvent::Error`].
+    pub async fn listen(&self, channel: &str) -> Result<NotificationListener, TuskError> {
+        if channel.is_empty() {
+            return Err(TuskError::query(
+                "Channel name cannot be empty",
+                None,
+                Some("Enter the channel to listen on".to_string()),
+                None,
+                None,
+            ));
+        }
+
+        let connect_timeout = Duration::from_secs(self.config.options.connect_timeout_secs as u64);
+        let (client, mut connection) =
+            tokio::time::timeout(connect_timeout, self.pg_config.connect(NoTls))
+                .await
+                .map_err(|_| TuskError::connection_timeout(connect_timeout))?
+                .map_err(|e| {
+                    tracing::error!(
+                        connection_id = %self.id,
+                        error = %e,
+                        "Failed to open notification connection"
+                    );
+                    TuskError::connection(format!("Failed to open notification connection: {e}"))
+                })?;
+
+        // The connection is polled here rather than by the client, since
+        // notifications only arrive through poll_message
+        let (tx, events) = mpsc::channel(NOTIFICATION_BUFFER);
+        let stop = CancellationToken::new();
+        let stopped = stop.clone();
+        let connection_id = self.id;
+        let listened = channel.to_string();
+        tokio::spawn(async move {
+            let mut messages = futures_util::stream::poll_fn(|cx| connection.poll_message(cx));
+            let error = loop {
+                let message = select! {
+                    message = messages.next() => message,
+                    _ = stopped.cancelled() => return,
+                };
+                match message {
+                    Some(Ok(AsyncMessage::Notification(notification))) => {
+                        let notification = Notification {
+                            channel: notification.channel().to_string(),
+                            payload: notification.payload().to_string(),
+                            pid: notification.process_id(),
+                        };
+                        if tx.send(NotificationEvent::Notification(notification)).await.is_err() {
+                            return;
+                        }
+                    }
+                    Some(Ok(AsyncMessage::Notice(notice))) => {
+                        tracing::debug!(
+                            connection_id = %connection_id,
+                            notice = %notice.message(),
+                            "Notice on notification connection"
+                        );
+                    }
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => {
+                        break TuskError::connection(format!("Notification connection failed: {e}"))
+                    }
+                    None => break TuskError::connection("Notification connection closed"),
+                }
+            };
+            tracing::warn!(
+                connection_id = %connection_id,
+                channel = %listened,
+                error = %error,
+                "LISTEN subscription ended"
+            );
+            let _ = tx.send(NotificationEvent::Error(error)).await;
+        });
+        let stop = stop.drop_guard();
+
+        client.batch_execute(&format!("LISTEN {}", quote_ident(channel))).await?;
+        tracing::info!(connection_id = %self.id, channel, "Listening for notifications");
+
+        Ok(NotificationListener {
+            channel: channel.to_string(),
+            events,
+            _stop: stop,
+            _client: client,
+        })
     }
 
     /// Get current pool status (FR-013, SC-010).
@@ -245,8 +573,119 @@ impl ConnectionPool {
         }
     }
 
+    /// Get the transaction pinned to one of the pool's connections, if any.
+    pub fn transaction(&self) -> Option<Arc<TransactionHandle>> {
+        self.transaction.lock().clone()
+    }
+
+    /// Get the status of the pinned transaction, Idle if there is none.
+    pub fn transaction_status(&self) -> TransactionStatus {
+        self.transaction().map_or(TransactionStatus::Idle, |txn| txn.status())
+    }
+
+    /// Pin a connection for a transaction about to begin.
+    ///
+    /// Returns the already pinned transaction if there is one. The pinned
+    /// connection stays out of the pool until [`Self::record_transaction`]
+    /// sees the transaction end, so every statement in between runs in it.
+    pub async fn pin_transaction(&self) -> Result<Arc<TransactionHandle>, TuskError> {
+        if let Some(txn) = self.transaction() {
+            return Ok(txn);
+        }
+        let txn = Arc::new(TransactionHandle::new(self.get().await?));
+        // Another statement may have pinned one while this waited for a connection
+        let txn = self.transaction.lock().get_or_insert(txn).clone();
+        tracing::debug!(
+            connection_id = %self.id,
+            transaction_id = %txn.id(),
+            "Pinned transaction connection"
+        );
+        Ok(txn)
+    }
+
+    /// Record a statement run on the pinned connection, returning the new status.
+    ///
+    /// `control` is the statement's effect on the transaction (see
+    /// [`QueryService::transaction_control`](crate::services::QueryService::transaction_control)).
+    /// Once the transaction ends, or its connection is lost, the connection
+    /// is unpinned and returns to the pool.
+    pub fn record_transaction(
+        &self,
+        txn: &TransactionHandle,
+        control: Option<TransactionControl>,
+        succeeded: bool,
+    ) -> TransactionStatus {
+        let status = txn.record(control, succeeded);
+        let committed =
+            matches!(control, Some(TransactionControl::Commit | TransactionControl::Chain));
+        if succeeded && committed && txn.schema_changed.swap(false, Ordering::AcqRel) {
+            self.committed_schema_change.store(true, Ordering::Release);
+        }
+        if !status.is_open() {
+            let mut pinned = self.transaction.lock();
+            if pinned.as_ref().is_some_and(|pinned| pinned.id() == txn.id()) {
+                *pinned = None;
+                tracing::debug!(
+                    connection_id = %self.id,
+                    transaction_id = %txn.id(),
+                    "Released transaction connection"
+                );
+            }
+        }
+        status
+    }
+
+    /// Check whether a transaction that ran DDL committed since the last
+    /// call, clearing the flag.
+    ///
+    /// DDL inside a transaction is only recorded with
+    /// [`TransactionHandle::defer_schema_change`]; other connections can't see
+    /// it until the commit.
+    pub fn take_committed_schema_change(&self) -> bool {
+        self.committed_schema_change.swap(false, Ordering::AcqRel)
+    }
+
+    /// Open a transaction on a pinned connection.
+    pub async fn begin(&self) -> Result<Arc<TransactionHandle>, TuskError> {
+        let txn = self.pin_transaction().await?;
+        let result = txn.connection().simple_execute("BEGIN").await;
+        self.record_transaction(&txn, Some(TransactionControl::Begin), result.is_ok());
+        result.map(|_| txn)
+    }
+
+    /// Commit the pinned transaction. Does nothing if none is open.
+    pub async fn commit(&self) -> Result<(), TuskError> {
+        self.end_transaction("COMMIT", TransactionControl::Commit).await
+    }
+
+    /// Roll back the pinned transaction. Does nothing if none is open.
+    pub async fn rollback(&self) -> Result<(), TuskError> {
+        self.end_transaction("ROLLBACK", TransactionControl::Rollback).await
+    }
+
+    /// Run the statement that ends the pinned transaction.
+    async fn end_transaction(
+        &self,
+        sql: &str,
+        control: TransactionControl,
+    ) -> Result<(), TuskError> {
+        let Some(txn) = self.transaction() else {
+            return Ok(());
+        };
+        let result = txn.connection().simple_execute(sql).await;
+        self.record_transaction(&txn, Some(control), result.is_ok());
+        tracing::info!(
+            connection_id = %self.id,
+            transaction_id = %txn.id(),
+            statement = sql,
+            "Transaction ended"
+        );
+        result.map(|_| ())
+    }
+
     /// Close the pool, dropping all connections.
     pub fn close(&self) {
+        self.transaction.lock().take();
         self.pool.close();
         tracing::info!(connection_id = %self.id, "Connection pool closed");
     }
@@ -257,12 +696,332 @@ impl ConnectionPool {
     }
 }
 
+/// A `LISTEN` subscription on a dedicated connection.
+///
+/// Created by [`ConnectionPool::listen`]. Dropping the listener closes the
+/// connection, which unsubscribes.
+pub struct NotificationListener {
+    /// Channel subscribed to
+    channel: String,
+    /// Events from the task polling the connection
+    events: mpsc::Receiver<NotificationEvent>,
+    /// Stops the polling task when dropped
+    _stop: DropGuard,
+    /// Keeps the session, and with it the subscription, open
+    _client: tokio_postgres::Client,
+}
+
+impl NotificationListener {
+    /// Get the channel subscribed to.
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    /// Wait for the next event.
+    ///
+    /// Returns None once the terminal [`NotificationEvent::Error`] has been
+    /// received.
+    pub async fn recv(&mut self) -> Option<NotificationEvent> {
+        self.events.recv().await
+    }
+}
+
+/// A connection pinned for an explicit transaction.
+///
+/// Created by [`ConnectionPool::pin_transaction`] when a transaction begins.
+/// The pool runs statements on this connection until the transaction ends,
+/// then returns it to the pool.
+pub struct TransactionHandle {
+    /// Identifies this transaction in logs
+    id: Uuid,
+    /// The pinned connection
+    conn: PooledConnection,
+    /// Status after the last statement run in the transaction
+    status: parking_lot::Mutex<TransactionStatus>,
+    /// When the connection was pinned
+    started_at: DateTime<Utc>,
+    /// Whether DDL ran in the transaction, to reload the schema on commit
+    schema_changed: AtomicBool,
+}
+
+impl TransactionHandle {
+    fn new(mut conn: PooledConnection) -> Self {
+        // A cursor stream runs in a transaction of its own, whose COMMIT or
+        // ROLLBACK would end the user's; stream from a single query instead
+        conn.cursor_fetch_size = None;
+        Self {
+            id: Uuid::new_v4(),
+            conn,
+            status: parking_lot::Mutex::new(TransactionStatus::Idle),
+            started_at: Utc::now(),
+            schema_changed: AtomicBool::new(false),
+        }
+    }
+
+    /// Get the transaction's ID.
+    pub fn id(&self) -> Uuid {
+        self.id
+    }
+
+    /// Get the pinned connection.
+    pub fn connection(&self) -> &PooledConnection {
+        &self.conn
+    }
+
+    /// Get the status after the last statement run in the transaction.
+    pub fn status(&self) -> TransactionStatus {
+        *self.status.lock()
+    }
+
+    /// Get when the connection was pinned.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Note that DDL ran in the transaction.
+    ///
+    /// The schema is reloaded once the transaction commits, when the change
+    /// becomes visible to the other connections in the pool.
+    pub fn defer_schema_change(&self) {
+        self.schema_changed.store(true, Ordering::Release);
+    }
+
+    /// Create a savepoint in the transaction.
+    pub async fn savepoint(&self, name: &str) -> Result<(), TuskError> {
+        self.run_savepoint_command("SAVEPOINT", name, None).await
+    }
+
+    /// Roll back to a savepoint, undoing what ran after it.
+    ///
+    /// Also recovers a failed transaction, so statements can run again.
+    pub async fn rollback_to(&self, name: &str) -> Result<(), TuskError> {
+        self.run_savepoint_command(
+            "ROLLBACK TO SAVEPOINT",
+            name,
+            Some(TransactionControl::RollbackToSavepoint),
+        )
+        .await
+    }
+
+    /// Release a savepoint, keeping what ran after it.
+    pub async fn release(&self, name: &str) -> Result<(), TuskError> {
+        self.run_savepoint_command("RELEASE SAVEPOINT", name, None).await
+    }
+
+    /// Run a savepoint command, refusing outside an open transaction.
+    ///
+    /// Names are folded to lower case like PostgreSQL folds unquoted
+    /// identifiers, so they match savepoints named in the editor.
+    async fn run_savepoint_command(
+        &self,
+        command: &str,
+        name: &str,
+        control: Option<TransactionControl>,
+    ) -> Result<(), TuskError> {
+        validate_savepoint_name(name)?;
+        if !self.status().is_open() {
+            return Err(TuskError::query(
+                format!("Cannot use savepoint {name}: no transaction is open"),
+                None,
+                Some("Run BEGIN first; savepoints only exist inside a transaction".to_string()),
+                None,
+                None,
+            ));
+        }
+        let name = name.to_ascii_lowercase();
+        let result = self.conn.simple_execute(&format!("{command} {}", quote_ident(&name))).await;
+        let status = self.record(control, result.is_ok());
+        tracing::debug!(
+            transaction_id = %self.id,
+            command,
+            savepoint = %name,
+            status = status.label(),
+            "Ran savepoint command"
+        );
+        result.map(|_| ())
+    }
+
+    /// Update the status after a statement ran on the pinned connection.
+    ///
+    /// A lost connection takes its transaction with it, so the status drops
+    /// back to Idle.
+    fn record(&self, control: Option<TransactionControl>, succeeded: bool) -> TransactionStatus {
+        let mut status = self.status.lock();
+        *status = if self.conn.is_closed() {
+            TransactionStatus::Idle
+        } else {
+            status.after(control, succeeded)
+        };
+        *status
+    }
+}
+
+/// Longest identifier PostgreSQL keeps (NAMEDATALEN - 1).
+const MAX_IDENTIFIER_BYTES: usize = 63;
+
+/// Check that a savepoint name is a plain identifier: a letter or
+/// underscore followed by letters, digits, underscores or `$`.
+fn validate_savepoint_name(name: &str) -> Result<(), TuskError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && name.len() <= MAX_IDENTIFIER_BYTES;
+    if valid {
+        return Ok(());
+    }
+    Err(TuskError::query(
+        format!("Invalid savepoint name: {name:?}"),
+        None,
+        Some(format!(
+            "Use a letter or underscore followed by letters, digits, underscores or $, \
+             at most {MAX_IDENTIFIER_BYTES} characters"
+        )),
+        None,
+        None,
+    ))
+}
+
+impl std::fmt::Debug for TransactionHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionHandle")
+            .field("id", &self.id)
+            .field("connection_id", &self.conn.connection_id())
+            .field("status", &self.status())
+            .field("started_at", &self.started_at)
+            .finish()
+    }
+}
+
+/// Delivers notices from pooled connections to whoever is capturing them.
+///
+/// Sinks are keyed by backend process ID, which each connection's driver
+/// learns when the connection opens.
+#[derive(Debug, Default)]
+struct NoticeRouter {
+    sinks: parking_lot::Mutex<HashMap<i32, mpsc::UnboundedSender<QueryEvent>>>,
+}
+
+impl NoticeRouter {
+    /// Deliver a notice raised on the connection with backend `pid`.
+    fn route(&self, pid: i32, notice: &tokio_postgres::error::DbError) {
+        match self.sinks.lock().get(&pid) {
+            Some(sink) => {
+                let severity = NoticeSeverity::from_pg(notice.parsed_severity());
+                let _ = sink.send(QueryEvent::notice(severity, notice.message()));
+            }
+            None => {
+                tracing::debug!(pid, notice = %notice.message(), "Notice outside a query");
+            }
+        }
+    }
+}
+
+/// Opens pooled connections, driving each one itself so notices reach the
+/// pool's [`NoticeRouter`] rather than being dropped by tokio-postgres.
+#[derive(Debug)]
+struct NoticeConnect {
+    notices: Arc<NoticeRouter>,
+}
+
+impl deadpool_postgres::Connect for NoticeConnect {
+    fn connect(
+        &self,
+        pg_config: &tokio_postgres::Config,
+    ) -> BoxFuture<
+        '_,
+        Result<(tokio_postgres::Client, tokio::task::JoinHandle<()>), tokio_postgres::Error>,
+    > {
+        let pg_config = pg_config.clone();
+        let notices = self.notices.clone();
+        Box::pin(async move {
+            let (client, mut connection) = pg_config.connect(NoTls).await?;
+            let pid = cancel_key_pid(&client).await.unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Could not read backend pid; notices won't be routed");
+                0
+            });
+            let driver = tokio::spawn(async move {
+                let mut messages = futures_util::stream::poll_fn(|cx| connection.poll_message(cx));
+                while let Some(message) = messages.next().await {
+                    match message {
+                        Ok(AsyncMessage::Notice(notice)) => {
+                            notices.route(pid, &notice);
+                        }
+                        Ok(_) => {}
+                        Err(e) => {
+                            tracing::debug!(error = %e, "Pooled connection closed with error");
+                            break;
+                        }
+                    }
+                }
+            });
+            Ok((client, driver))
+        })
+    }
+}
+
+/// Length of a CancelRequest message.
+const CANCEL_REQUEST_LEN: usize = 16;
+
+/// The server process ID of a client's connection.
+///
+/// The server sends its process ID with the cancel key when a connection
+/// opens, and tokio-postgres keeps both only in the client's cancel token.
+/// Writing a cancel request into a local buffer reads them back without
+/// contacting the server.
+async fn cancel_key_pid(client: &tokio_postgres::Client) -> std::io::Result<i32> {
+    let (stream, mut request) = tokio::io::duplex(CANCEL_REQUEST_LEN);
+    client.cancel_token().cancel_query_raw(stream, NoTls).await.map_err(std::io::Error::other)?;
+    let mut message = [0; CANCEL_REQUEST_LEN];
+    request.read_exact(&mut message).await?;
+    // Int32 length, Int32 request code, Int32 process ID, Int32 secret key
+    Ok(i32::from_be_bytes([message[8], message[9], message[10], message[11]]))
+}
+
+/// Notices captured from a pooled connection.
+///
+/// Created by [`PooledConnection::capture_notices`]. Dropping the capture
+/// stops delivery; later notices on the connection are only logged.
+pub struct NoticeCapture {
+    /// Backend process ID of the captured connection
+    pid: i32,
+    /// Router the capture is registered with
+    router: Arc<NoticeRouter>,
+    /// Notices as [`QueryEvent::Notice`] events
+    notices: mpsc::UnboundedReceiver<QueryEvent>,
+}
+
+impl NoticeCapture {
+    /// Wait for the next notice.
+    pub async fn recv(&mut self) -> Option<QueryEvent> {
+        self.notices.recv().await
+    }
+
+    /// Take a notice that has already arrived, without waiting.
+    pub fn try_recv(&mut self) -> Option<QueryEvent> {
+        self.notices.try_recv().ok()
+    }
+}
+
+impl Drop for NoticeCapture {
+    fn drop(&mut self) {
+        self.router.sinks.lock().remove(&self.pid);
+    }
+}
+
 /// A connection acquired from the pool.
 ///
 /// Automatically returns to the pool when dropped.
 pub struct PooledConnection {
     client: deadpool_postgres::Client,
     connection_id: Uuid,
+    /// Whether prepared statements are reused from the connection's cache
+    cache_statements: bool,
+    /// Rows per streamed batch
+    stream_batch_size: usize,
+    /// Rows per FETCH when streaming through a cursor, None to stream from one query
+    cursor_fetch_size: Option<usize>,
+    /// Where notices raised on this connection are delivered
+    notices: Arc<NoticeRouter>,
 }
 
 impl PooledConnection {
@@ -271,6 +1030,36 @@ impl PooledConnection {
         self.connection_id
     }
 
+    /// Get the number of rows sent per batch when streaming results.
+    pub fn stream_batch_size(&self) -> usize {
+        self.stream_batch_size
+    }
+
+    /// Get the rows fetched per round trip when streaming SELECTs through a
+    /// server-side cursor, or None if results stream from a single query.
+    pub fn cursor_fetch_size(&self) -> Option<usize> {
+        self.cursor_fetch_size
+    }
+
+    /// Capture the notices the server raises on this connection (`RAISE
+    /// NOTICE`, warnings) until the returned capture is dropped.
+    ///
+    /// Without a capture, notices are only logged.
+    pub async fn capture_notices(&self) -> Result<NoticeCapture, TuskError> {
+        let pid = self.backend_pid().await?;
+        let (tx, notices) = mpsc::unbounded_channel();
+        self.notices.sinks.lock().insert(pid, tx);
+        Ok(NoticeCapture { pid, router: self.notices.clone(), notices })
+    }
+
+    /// Get the server process ID of this connection.
+    ///
+    /// Read from the connection's cancel key, the same way the pool's notice
+    /// driver learns it, so no query is sent.
+    pub async fn backend_pid(&self) -> Result<i32, TuskError> {
+        Ok(cancel_key_pid(&self.client).await?)
+    }
+
     /// Get a cancel token for this connection.
     ///
     /// The cancel token can be used to request cancellation of a query
@@ -279,29 +1068,124 @@ impl PooledConnection {
         self.client.cancel_token()
     }
 
-    /// Execute a query that returns rows.
-    pub async fn query(
+    /// Execute a query (SQL text or a prepared statement) that returns rows.
+    pub async fn query<T>(
         &self,
-        sql: &str,
+        statement: &T,
         params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
-    ) -> Result<Vec<tokio_postgres::Row>, TuskError> {
-        self.client.query(sql, params).await.map_err(TuskError::from)
+    ) -> Result<Vec<tokio_postgres::Row>, TuskError>
+    where
+        T: ?Sized + tokio_postgres::ToStatement,
+    {
+        self.client.query(statement, params).await.map_err(|e| self.statement_error(e))
     }
 
-    /// Execute a query that doesn't return rows.
-    pub async fn execute(
+    /// Execute a query (SQL text or a prepared statement) that doesn't return rows.
+    pub async fn execute<T>(
         &self,
-        sql: &str,
+        statement: &T,
         params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
-    ) -> Result<u64, TuskError> {
-        self.client.execute(sql, params).await.map_err(TuskError::from)
+    ) -> Result<u64, TuskError>
+    where
+        T: ?Sized + tokio_postgres::ToStatement,
+    {
+        self.client.execute(statement, params).await.map_err(|e| self.statement_error(e))
+    }
+
+    /// Execute a single statement through the simple query protocol.
+    ///
+    /// The statement runs on its own rather than in the implicit transaction
+    /// of an extended-protocol exchange. Returns the rows affected as reported
+    /// by the last command.
+    pub async fn simple_execute(&self, sql: &str) -> Result<u64, TuskError> {
+        let messages = self.client.simple_query(sql).await.map_err(TuskError::from)?;
+        Ok(messages
+            .iter()
+            .rev()
+            .find_map(|message| match message {
+                tokio_postgres::SimpleQueryMessage::CommandComplete(rows) => Some(*rows),
+                _ => None,
+            })
+            .unwrap_or(0))
+    }
+
+    /// Start a `COPY ... TO STDOUT`, returning the stream of data it sends.
+    pub async fn copy_out(&self, sql: &str) -> Result<tokio_postgres::CopyOutStream, TuskError> {
+        self.client.copy_out(sql).await.map_err(TuskError::from)
+    }
+
+    /// Start a `COPY ... FROM STDIN`, returning the sink its data is sent to.
+    ///
+    /// Dropping the sink without finishing it aborts the copy.
+    pub async fn copy_in(
+        &self,
+        sql: &str,
+    ) -> Result<tokio_postgres::CopyInSink<std::io::Cursor<Vec<u8>>>, TuskError> {
+        self.client.copy_in(sql).await.map_err(TuskError::from)
     }
 
-    /// Prepare a statement for repeated execution.
+    /// Prepare a statement for execution.
+    ///
+    /// Never cached: this is how user SQL runs, and caching ad-hoc editor
+    /// text would keep a server-side statement alive for every query typed.
     pub async fn prepare(&self, sql: &str) -> Result<tokio_postgres::Statement, TuskError> {
         self.client.prepare(sql).await.map_err(TuskError::from)
     }
 
+    /// Run one of Tusk's own fixed queries, reusing the statement already
+    /// prepared on this connection when statement caching is enabled.
+    ///
+    /// A cached plan the server rejects as stale is dropped and the query
+    /// prepared and run once more.
+    pub async fn query_cached(
+        &self,
+        sql: &'static str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> Result<Vec<tokio_postgres::Row>, TuskError> {
+        if !self.cache_statements {
+            return self.query(sql, params).await;
+        }
+        let statement = self.client.prepare_cached(sql).await?;
+        match self.client.query(&statement, params).await {
+            Err(e) if is_stale_plan(&e) => {
+                tracing::debug!(connection_id = %self.connection_id, "Re-preparing stale cached statement");
+                self.clear_statement_cache();
+                let statement = self.client.prepare_cached(sql).await?;
+                self.query(&statement, params).await
+            }
+            result => result.map_err(TuskError::from),
+        }
+    }
+
+    /// Check if the connection to the server has closed.
+    pub fn is_closed(&self) -> bool {
+        self.client.is_closed()
+    }
+
+    /// Forget the statements cached on this connection.
+    ///
+    /// Called after DDL, which can change the result shape of a cached
+    /// statement and make the server reject it.
+    pub fn clear_statement_cache(&self) {
+        self.client.statement_cache.clear();
+    }
+
+    /// Convert an execution error, dropping cached statements the server
+    /// reports as stale so the next run prepares them afresh.
+    fn statement_error(&self, error: tokio_postgres::Error) -> TuskError {
+        self.forget_stale_statements(&error);
+        TuskError::from(error)
+    }
+
+    /// Clear the statement cache when the server rejects a cached plan
+    /// whose result type changed.
+    fn forget_stale_statements(&self, error: &tokio_postgres::Error) {
+        if is_stale_plan(error) {
+            tracing::debug!(connection_id = %self.connection_id, "Dropping stale cached statements");
+            self.clear_statement_cache();
+        }
+    }
+
     /// Begin a transaction.
     pub async fn transaction(&mut self) -> Result<Transaction<'_>, TuskError> {
         let txn = self.client.transaction().await.map_err(TuskError::from)?;
@@ -311,15 +1195,30 @@ impl PooledConnection {
     /// Execute a query that returns a row stream (for streaming large results).
     ///
     /// This is used by QueryService for streaming query execution.
-    pub async fn query_raw(
+    pub async fn query_raw<T>(
         &self,
-        sql: &str,
+        statement: &T,
         params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
-    ) -> Result<tokio_postgres::RowStream, tokio_postgres::Error> {
-        self.client.query_raw(sql, params.iter().copied()).await
+    ) -> Result<tokio_postgres::RowStream, tokio_postgres::Error>
+    where
+        T: ?Sized + tokio_postgres::ToStatement,
+    {
+        let result = self.client.query_raw(statement, params.iter().copied()).await;
+        if let Err(e) = &result {
+            self.forget_stale_statements(e);
+        }
+        result
     }
 }
 
+/// Check whether the server rejected a cached statement because its
+/// result type changed since it was prepared.
+fn is_stale_plan(error: &tokio_postgres::Error) -> bool {
+    error.as_db_error().is_some_and(|db| {
+        *db.code() == SqlState::FEATURE_NOT_SUPPORTED && db.message().starts_with("cached plan")
+    })
+}
+
 /// A database transaction.
 ///
 /// Automatically rolls back on drop unless committed.
@@ -356,3 +1255,587 @@ impl<'a> Transaction<'a> {
         self.txn.rollback().await.map_err(TuskError::from)
     }
 }
+
+/// How often and how quickly to retry a failed connection attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles for each retry after it
+    pub initial_delay: Duration,
+    /// Upper bound for the delay between attempts
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Four attempts over about 3.5 seconds.
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(8),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay after failed attempt `attempt` (1-based) before trying again.
+    pub fn delay_before_retry(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        self.initial_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Whether to try again after attempt `attempt` (1-based) failed with `error`.
+    pub fn should_retry(&self, attempt: u32, error: &TuskError) -> bool {
+        attempt < self.max_attempts && error.is_retryable()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BatchSummary, ScriptEvent};
+    use crate::test_support::local_pool;
+
+    fn timeout_config(host: &str, port: u16) -> ConnectionConfig {
+        let mut config = ConnectionConfig::new("timeout", host, "postgres", "postgres");
+        config.port = port;
+        config.options.connect_timeout_secs = 1;
+        config
+    }
+
+    /// Run a script through the pool, collecting the rows it returned.
+    async fn run_script(
+        pool: &ConnectionPool,
+        sql: &str,
+    ) -> (BatchSummary, Vec<tokio_postgres::Row>) {
+        use crate::services::QueryService;
+
+        let handle = QueryHandle::new(pool.id(), sql);
+        let (tx, mut rx) = mpsc::channel(64);
+        let summary =
+            QueryService::execute_script_pooled(pool, sql, &handle, tx).await.expect("script");
+        let mut rows = Vec::new();
+        while let Some(ScriptEvent { event, .. }) = rx.recv().await {
+            if let QueryEvent::Rows { rows: batch, .. } = event {
+                rows.extend(batch);
+            }
+        }
+        (summary, rows)
+    }
+
+    fn assert_timed_out(result: Result<ConnectionPool, TuskError>, started: std::time::Instant) {
+        // Generous margin for a loaded machine, well short of a TCP connect's own timeout
+        assert!(started.elapsed() < Duration::from_secs(3), "took {:?}", started.elapsed());
+        let err = result.expect_err("connect should time out");
+        assert!(err.to_string().contains("Connection timed out after 1s"), "{err}");
+        assert_eq!(err.category(), "Connection");
+    }
+
+    #[test]
+    fn test_retry_delays_double_up_to_max() {
+        let policy = RetryPolicy {
+            max_attempts: 6,
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_millis(500),
+        };
+        let delays: Vec<_> = (1..=5).map(|attempt| policy.delay_before_retry(attempt)).collect();
+        assert_eq!(delays, [100, 200, 400, 500, 500].map(Duration::from_millis));
+        // Large attempt counts saturate instead of overflowing
+        assert_eq!(policy.delay_before_retry(u32::MAX), Duration::from_millis(500));
+    }
+
+    #[test]
+    fn test_should_retry_only_transient_errors() {
+        let policy = RetryPolicy { max_attempts: 3, ..RetryPolicy::default() };
+        let refused = TuskError::connection("Failed to establish connection: refused");
+        assert!(policy.should_retry(1, &refused));
+        assert!(policy.should_retry(2, &refused));
+        // The last attempt's error is final
+        assert!(!policy.should_retry(3, &refused));
+
+        let timeout = TuskError::connection_timeout(Duration::from_secs(10));
+        assert!(policy.should_retry(1, &timeout));
+        assert!(!policy.should_retry(1, &TuskError::authentication("bad password")));
+        assert!(!policy.should_retry(1, &TuskError::ssl("certificate rejected")));
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_gives_up_on_refused_port() {
+        // Bind and release a port so connecting to it is refused
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let policy = RetryPolicy {
+            max_attempts: 3,
+            initial_delay: Duration::from_millis(50),
+            max_delay: Duration::from_millis(50),
+        };
+        let handle = QueryHandle::new(Uuid::new_v4(), "connect");
+
+        let started = std::time::Instant::now();
+        let result = ConnectionPool::connect_with_retry(
+            timeout_config("127.0.0.1", port),
+            "secret",
+            policy,
+            &handle,
+        )
+        .await;
+        let err = result.expect_err("nothing listens on the port");
+        assert!(err.is_retryable(), "{err}");
+        // Two backoff delays ran between the three attempts
+        assert!(started.elapsed() >= Duration::from_millis(100), "took {:?}", started.elapsed());
+    }
+
+    #[tokio::test]
+    async fn test_connect_with_retry_stops_when_cancelled() {
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            initial_delay: Duration::from_secs(30),
+            max_delay: Duration::from_secs(30),
+        };
+        let handle = QueryHandle::new(Uuid::new_v4(), "connect");
+        handle.cancel();
+
+        let started = std::time::Instant::now();
+        let result = ConnectionPool::connect_with_retry(
+            timeout_config("127.0.0.1", port),
+            "secret",
+            policy,
+            &handle,
+        )
+        .await;
+        assert!(result.expect_err("cancelled").is_cancelled());
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_connect_times_out_when_server_never_answers() {
+        // Accepts the TCP connection but never completes the startup handshake
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        let started = std::time::Instant::now();
+        let result = ConnectionPool::new(timeout_config("127.0.0.1", port), "secret").await;
+        assert_timed_out(result, started);
+        drop(listener);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a network that drops packets to unroutable addresses"]
+    async fn test_connect_times_out_on_unroutable_host() {
+        let started = std::time::Instant::now();
+        let result = ConnectionPool::new(timeout_config("10.255.255.1", 5432), "secret").await;
+        assert_timed_out(result, started);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_cancel_stops_long_query_on_server() {
+        use crate::services::QueryService;
+
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.expect("connection");
+        let handle = Arc::new(QueryHandle::new(pool.id(), "SELECT pg_sleep(30)"));
+        let canceller = handle.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let started = std::time::Instant::now();
+        let err = QueryService::execute(&conn, "SELECT pg_sleep(30)", &handle)
+            .await
+            .expect_err("query should be cancelled");
+        assert!(err.is_cancelled(), "{err}");
+        // The connection only answers once the server has stopped the sleep
+        conn.query("SELECT 1", &[]).await.expect("connection usable after cancel");
+        assert!(started.elapsed() < Duration::from_secs(1), "took {:?}", started.elapsed());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_unavailable_server_errors_are_retryable() {
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.expect("connection");
+        for code in ["57P03", "57P01", "53300"] {
+            let sql = format!(
+                "DO $$ BEGIN RAISE EXCEPTION 'unavailable' USING ERRCODE = '{code}'; END $$"
+            );
+            let err = conn.client.batch_execute(&sql).await.expect_err("server error");
+            assert_eq!(err.code().map(|c| c.code()), Some(code));
+            let err = TuskError::from(err);
+            assert!(err.is_retryable(), "{code}: {err}");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_backend_pid_matches_server() {
+        let pool = local_pool(2).await;
+        let first = pool.get().await.expect("connection");
+        let second = pool.get().await.expect("connection");
+        for conn in [&first, &second] {
+            let row = conn.query_one("SELECT pg_backend_pid()", &[]).await.expect("query");
+            assert_eq!(conn.backend_pid().await.expect("pid"), row.get::<_, i32>(0));
+        }
+        assert_ne!(first.backend_pid().await.unwrap(), second.backend_pid().await.unwrap());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_streaming_forwards_raise_notice() {
+        use crate::models::NoticeSeverity;
+        use crate::services::QueryService;
+
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.expect("connection");
+        let sql = "DO $$ BEGIN RAISE NOTICE 'hello'; RAISE WARNING 'careful'; END $$";
+        let handle = QueryHandle::new(pool.id(), sql);
+        let (tx, mut rx) = mpsc::channel(16);
+        QueryService::execute_streaming(&conn, sql, &handle, tx).await.expect("streaming");
+
+        let mut notices = Vec::new();
+        while let Some(event) = rx.recv().await {
+            match event {
+                QueryEvent::Notice { severity, message } => notices.push((severity, message)),
+                event if event.is_terminal() => {
+                    assert!(matches!(event, QueryEvent::Complete { .. }), "{event:?}");
+                    break;
+                }
+                _ => {}
+            }
+        }
+        assert_eq!(
+            notices,
+            vec![
+                (NoticeSeverity::Notice, "hello".to_string()),
+                (NoticeSeverity::Warning, "careful".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_copy_out_streams_csv() {
+        use crate::models::{CopyFormat, CopyOptions};
+        use crate::services::QueryService;
+
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.expect("connection");
+        let sql = "SELECT n, 'row ' || n AS label FROM generate_series(1, 3) n";
+        let handle = QueryHandle::new(pool.id(), sql);
+
+        let mut csv = Vec::new();
+        let bytes = QueryService::copy_out(&conn, sql, &CopyOptions::default(), &handle, &mut csv)
+            .await
+            .expect("csv export");
+        assert_eq!(bytes, csv.len() as u64);
+        assert_eq!(String::from_utf8(csv).unwrap(), "n,label\n1,row 1\n2,row 2\n3,row 3\n");
+
+        let options = CopyOptions { format: CopyFormat::Text, delimiter: Some('|'), header: false };
+        let mut text = Vec::new();
+        QueryService::copy_out(&conn, sql, &options, &handle, &mut text)
+            .await
+            .expect("text export");
+        assert_eq!(String::from_utf8(text).unwrap(), "1|row 1\n2|row 2\n3|row 3\n");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_copy_in_rolls_back_bad_rows() {
+        use crate::models::CopyOptions;
+        use crate::services::QueryService;
+
+        // One connection, so the temporary table is visible to every call
+        let pool = local_pool(1).await;
+        pool.get()
+            .await
+            .expect("connection")
+            .simple_execute("CREATE TEMP TABLE copy_target (id int, label text)")
+            .await
+            .expect("create table");
+        let options = CopyOptions::default();
+        let handle = QueryHandle::new(pool.id(), "COPY copy_target");
+        let load = |data: &'static str| {
+            QueryService::copy_in(
+                &pool,
+                "pg_temp",
+                "copy_target",
+                &["id", "label"],
+                &options,
+                &handle,
+                data.as_bytes(),
+            )
+        };
+
+        let rows = load("id,label\n1,one\n2,two\n").await.expect("copy in");
+        assert_eq!(rows, 2);
+
+        let err = load("id,label\n3,three\nfour,4\n").await.expect_err("bad row");
+        let TuskError::Query { detail, .. } = &err else { panic!("{err:?}") };
+        assert!(detail.as_deref().is_some_and(|d| d.contains("line 3")), "{detail:?}");
+
+        let conn = pool.get().await.expect("connection");
+        let count: i64 =
+            conn.query("SELECT count(*) FROM copy_target", &[]).await.expect("count")[0].get(0);
+        assert_eq!(count, 2, "the failed load left no rows");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_transaction_pins_one_connection() {
+        let pool = local_pool(4).await;
+        let backend_pid =
+            |(_, rows): (BatchSummary, Vec<tokio_postgres::Row>)| rows[0].get::<_, i32>(0);
+
+        run_script(&pool, "BEGIN").await;
+        assert_eq!(pool.transaction_status(), TransactionStatus::InTransaction);
+        let first = backend_pid(run_script(&pool, "SELECT pg_backend_pid()").await);
+        let second = backend_pid(run_script(&pool, "SELECT pg_backend_pid()").await);
+        assert_eq!(first, second, "statements in a transaction share a connection");
+
+        assert_eq!(run_script(&pool, "SELECT 1 / 0").await.0.failed, 1);
+        assert_eq!(pool.transaction_status(), TransactionStatus::Failed);
+
+        run_script(&pool, "ROLLBACK").await;
+        assert_eq!(pool.transaction_status(), TransactionStatus::Idle);
+        assert!(pool.transaction().is_none(), "the connection went back to the pool");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_cursor_stream_keeps_open_transaction() {
+        use crate::services::QueryService;
+
+        let pool = crate::test_support::local_pool_with(1, |config| {
+            config.options.cursor_fetch_size = Some(2);
+        })
+        .await;
+        let run = |sql: &'static str| {
+            let pool = &pool;
+            async move {
+                let handle = QueryHandle::new(pool.id(), sql);
+                let (tx, mut rx) = mpsc::channel(16);
+                QueryService::execute_streaming_pooled(pool, sql, &handle, tx)
+                    .await
+                    .expect("streaming");
+                let mut rows = Vec::new();
+                while let Some(event) = rx.recv().await {
+                    match event {
+                        QueryEvent::Rows { rows: batch, .. } => rows.extend(batch),
+                        event if event.is_terminal() => {
+                            assert!(matches!(event, QueryEvent::Complete { .. }), "{event:?}");
+                            break;
+                        }
+                        _ => {}
+                    }
+                }
+                rows
+            }
+        };
+
+        run("BEGIN").await;
+        run("CREATE TEMP TABLE tusk_cursor_txn AS SELECT generate_series(1, 5) AS n").await;
+        assert_eq!(run("SELECT n FROM tusk_cursor_txn").await.len(), 5);
+        // The SELECT must not have committed or rolled back the transaction
+        let open = run("SELECT now() = statement_timestamp()").await;
+        assert!(!open[0].get::<_, bool>(0), "still in the transaction that made the table");
+        assert_eq!(pool.transaction_status(), TransactionStatus::InTransaction);
+
+        run("ROLLBACK").await;
+        assert!(pool.transaction().is_none());
+        let conn = pool.get().await.expect("connection");
+        let exists = conn
+            .query_one("SELECT to_regclass('pg_temp.tusk_cursor_txn') IS NOT NULL", &[])
+            .await
+            .expect("lookup");
+        assert!(!exists.get::<_, bool>(0), "the rollback undid the table");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_finished_query_forgets_cancel_token() {
+        use crate::services::QueryService;
+
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.expect("connection");
+        let handle = QueryHandle::new(pool.id(), "SELECT 1");
+        QueryService::execute(&conn, "SELECT 1", &handle).await.expect("query");
+        // A late cancel must not interrupt the connection's next query
+        assert!(handle.get_pg_cancel_token().is_none());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_listen_receives_notifications() {
+        let pool = local_pool(1).await;
+        let mut listener = pool.listen("tusk_test_listen").await.expect("listen");
+        assert_eq!(listener.channel(), "tusk_test_listen");
+
+        let conn = pool.get().await.expect("connection");
+        conn.execute("SELECT pg_notify('tusk_test_listen', 'hello')", &[]).await.expect("notify");
+        let event = tokio::time::timeout(Duration::from_secs(5), listener.recv())
+            .await
+            .expect("notification in time")
+            .expect("listener open");
+        match event {
+            NotificationEvent::Notification(notification) => {
+                assert_eq!(notification.channel, "tusk_test_listen");
+                assert_eq!(notification.payload, "hello");
+                assert!(notification.pid > 0);
+            }
+            NotificationEvent::Error(e) => panic!("unexpected error: {e}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_listen_refuses_empty_channel() {
+        let pool = ConnectionPool::unvalidated(ConnectionConfig::new("listen", "", "", ""));
+        let err = pool.listen("").await.err().expect("empty channel refused");
+        assert!(err.hint().is_some());
+    }
+
+    #[test]
+    fn test_savepoint_names_are_plain_identifiers() {
+        for name in ["before_update", "_step2", "sp$1", "Before"] {
+            assert!(validate_savepoint_name(name).is_ok(), "{name}");
+        }
+        for name in ["", "1st", "a b", "x; DROP TABLE orders", "\"quoted\"", &"s".repeat(64)] {
+            let err = validate_savepoint_name(name).expect_err(name);
+            assert!(err.hint().is_some());
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_rollback_to_savepoint_recovers_failed_transaction() {
+        let pool = local_pool(2).await;
+        let txn = pool.begin().await.expect("begin");
+        txn.savepoint("before_divide").await.expect("savepoint");
+
+        let (summary, _) = run_script(&pool, "SELECT 1 / 0").await;
+        assert_eq!(summary.failed, 1);
+        assert_eq!(txn.status(), TransactionStatus::Failed);
+
+        txn.rollback_to("before_divide").await.expect("rollback to savepoint");
+        assert_eq!(txn.status(), TransactionStatus::InTransaction);
+        txn.release("before_divide").await.expect("release");
+
+        // Names fold like unquoted identifiers, matching savepoints typed in the editor
+        txn.savepoint("Before").await.expect("savepoint");
+        let (summary, _) = run_script(&pool, "RELEASE before").await;
+        assert_eq!(summary.failed, 0);
+        pool.commit().await.expect("commit");
+
+        let err = txn.savepoint("too_late").await.expect_err("no transaction");
+        assert!(err.hint().is_some());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_schema_change_in_transaction_reported_on_commit() {
+        let pool = local_pool(2).await;
+
+        let txn = pool.begin().await.expect("begin");
+        txn.defer_schema_change();
+        pool.rollback().await.expect("rollback");
+        assert!(!pool.take_committed_schema_change());
+
+        let txn = pool.begin().await.expect("begin");
+        txn.defer_schema_change();
+        assert!(!pool.take_committed_schema_change());
+        pool.commit().await.expect("commit");
+        assert!(pool.take_committed_schema_change());
+        assert!(!pool.take_committed_schema_change());
+    }
+
+    #[test]
+    fn test_checkout_sql_applies_search_path_after_defaults() {
+        let mut config = ConnectionConfig::new("local", "localhost", "app", "postgres");
+        config.options.default_schema = Some("sales".to_string());
+        let mut pool = ConnectionPool::unvalidated(config.clone());
+        pool.session_defaults_sql = ConnectionPool::build_session_defaults_sql(&config);
+        let defaults = pool.session_defaults_sql.clone().unwrap();
+        assert_eq!(pool.checkout_sql().as_deref(), Some(defaults.as_str()));
+
+        pool.set_search_path("reporting, public");
+        assert_eq!(
+            pool.checkout_sql().unwrap(),
+            format!("{defaults}; SET search_path TO reporting, public")
+        );
+
+        pool.set_search_path("");
+        assert!(pool.checkout_sql().unwrap().ends_with("; RESET search_path"));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_search_path_applies_to_every_connection() {
+        let pool = local_pool(2).await;
+
+        pool.set_search_path("pg_catalog");
+        let first = pool.get().await.unwrap();
+        let second = pool.get().await.unwrap();
+        for conn in [&first, &second] {
+            let rows = conn.client.query("SHOW search_path", &[]).await.unwrap();
+            assert_eq!(rows[0].get::<_, String>(0), "pg_catalog");
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_warmup_leaves_connections_available() {
+        let pool = local_pool(4).await;
+
+        assert_eq!(pool.warmup(3).await.unwrap(), 3);
+        let status = pool.status();
+        assert_eq!(status.size, 3);
+        assert_eq!(status.available, 3);
+
+        // Capped at the pool size, and checked-out connections count toward n
+        let held = pool.get().await.unwrap();
+        assert_eq!(pool.warmup(10).await.unwrap(), 3);
+        assert_eq!(pool.status().available, 3);
+        drop(held);
+        assert_eq!(pool.status().available, 4);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_user_sql_is_not_cached() {
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.unwrap();
+
+        let first = conn.prepare("SELECT 1 AS one").await.unwrap();
+        let second = conn.prepare("SELECT 1 AS one").await.unwrap();
+        assert_ne!(first.name(), second.name());
+        assert_eq!(conn.client.statement_cache.size(), 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_query_cached_reprepares_stale_plan() {
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.unwrap();
+        let schema = format!("tusk_stale_{}", Uuid::new_v4().simple());
+        conn.client
+            .batch_execute(&format!("CREATE SCHEMA {schema}; SET search_path = {schema}"))
+            .await
+            .unwrap();
+        conn.client
+            .batch_execute("CREATE TABLE stale_plan (a int); INSERT INTO stale_plan VALUES (1)")
+            .await
+            .unwrap();
+
+        let rows = conn.query_cached("SELECT * FROM stale_plan", &[]).await.unwrap();
+        assert_eq!(rows[0].len(), 1);
+        assert_eq!(conn.client.statement_cache.size(), 1);
+
+        // The cached statement's result type no longer matches the table
+        conn.client.batch_execute("ALTER TABLE stale_plan ADD COLUMN b int").await.unwrap();
+        let rows = conn.query_cached("SELECT * FROM stale_plan", &[]).await.unwrap();
+        assert_eq!(rows[0].len(), 2);
+
+        conn.client.batch_execute(&format!("DROP SCHEMA {schema} CASCADE")).await.unwrap();
+    }
+}
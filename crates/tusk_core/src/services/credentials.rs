@@ -9,18 +9,24 @@
 //!   - Override with `TUSK_USE_KEYCHAIN=1` to force keychain usage
 //! - **Release builds**: OS keychain (macOS Keychain, Windows Credential Manager, Linux Secret Service)
 //!   - Code-signed release builds have stable identity for keychain ACLs
+//! - **Keychain unavailable** (e.g. headless Linux without Secret Service): AES-GCM encrypted
+//!   file under the data directory, keyed from a machine secret
 //!
 //! See `/specs/004-service-integration/keychain-popup-analysis.md` for background.
 
 use crate::error::TuskError;
 
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
 use keyring::Entry;
 use parking_lot::RwLock;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use std::fs;
+use std::future::Future;
 use std::io::Read;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Service name used for keychain entries.
@@ -29,6 +35,57 @@ const KEYRING_SERVICE: &str = "dev.tusk.Tusk";
 /// Environment variable to force keychain usage in debug builds (T101).
 const FORCE_KEYCHAIN_ENV: &str = "TUSK_USE_KEYCHAIN";
 
+/// Keychain entry used to probe whether the keychain is reachable.
+const KEYCHAIN_PROBE_KEY: &str = "tusk:availability-probe";
+
+/// File name of the encrypted credentials store within the data directory.
+const ENCRYPTED_CREDENTIALS_FILE: &str = "credentials.enc";
+
+/// File name of the generated secret used when no machine id is available.
+const CREDENTIALS_SECRET_FILE: &str = ".credentials_secret";
+
+/// Domain separator mixed into the encryption key derivation.
+const KEY_DERIVATION_CONTEXT: &[u8] = b"dev.tusk.Tusk/credentials/v1";
+
+/// Length of the AES-GCM nonce prefixed to the encrypted file.
+const NONCE_LEN: usize = 12;
+
+// ============================================================================
+// CredentialBackend
+// ============================================================================
+
+/// The kind of storage backing the credential service.
+///
+/// Exposed so the UI can tell users where their passwords are kept.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialBackend {
+    /// OS keychain (macOS Keychain, Windows Credential Manager, Secret Service).
+    Keychain,
+    /// AES-GCM encrypted file under the data directory.
+    EncryptedFile,
+    /// Plaintext development file at `~/.config/tusk/dev_credentials.json`.
+    File,
+    /// In-memory only; credentials are lost on exit.
+    Session,
+}
+
+impl CredentialBackend {
+    /// Human-readable description for display.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Keychain => "System keychain",
+            Self::EncryptedFile => "Encrypted file",
+            Self::File => "Development file",
+            Self::Session => "Session only",
+        }
+    }
+
+    /// Whether credentials survive an application restart.
+    pub fn is_persistent(&self) -> bool {
+        !matches!(self, Self::Session)
+    }
+}
+
 // ============================================================================
 // CredentialsProvider Trait (T097)
 // ============================================================================
@@ -55,6 +112,9 @@ pub trait CredentialsProvider: Send + Sync {
 
     /// Provider name for logging.
     fn name(&self) -> &'static str;
+
+    /// The kind of storage this provider uses.
+    fn backend(&self) -> CredentialBackend;
 }
 
 // ============================================================================
@@ -204,6 +264,200 @@ impl CredentialsProvider for FileCredentialsProvider {
     fn name(&self) -> &'static str {
         "FileCredentialsProvider"
     }
+
+    fn backend(&self) -> CredentialBackend {
+        CredentialBackend::File
+    }
+}
+
+// ============================================================================
+// EncryptedFileCredentialsProvider
+// ============================================================================
+
+/// Encrypted file credential storage used when the keychain is unavailable.
+///
+/// Credentials are serialized as JSON and sealed with AES-256-GCM into
+/// `<data_dir>/credentials.enc` (nonce followed by ciphertext). The key is
+/// derived from the machine id, or from a random secret generated once and
+/// kept next to the file with owner-only permissions.
+pub struct EncryptedFileCredentialsProvider {
+    /// Path to the encrypted credentials file.
+    file_path: PathBuf,
+    /// Derived AES-256 key.
+    key: [u8; 32],
+    /// In-memory cache of credentials.
+    cache: RwLock<HashMap<String, String>>,
+}
+
+impl EncryptedFileCredentialsProvider {
+    /// Create an encrypted file provider under the given data directory.
+    pub fn new(data_dir: &Path) -> Result<Self, TuskError> {
+        let secret = machine_secret(data_dir)?;
+        Self::with_key(data_dir.join(ENCRYPTED_CREDENTIALS_FILE), derive_key(&secret))
+    }
+
+    /// Create with an explicit file path and key.
+    pub(crate) fn with_key(file_path: PathBuf, key: [u8; 32]) -> Result<Self, TuskError> {
+        let provider = Self { file_path, key, cache: RwLock::new(HashMap::new()) };
+        provider.load_from_file()?;
+        Ok(provider)
+    }
+
+    fn cipher(&self) -> Aes256Gcm {
+        Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+    }
+
+    /// Decrypt the credentials file into the cache.
+    fn load_from_file(&self) -> Result<(), TuskError> {
+        if !self.file_path.exists() {
+            return Ok(());
+        }
+
+        let contents = fs::read(&self.file_path).map_err(|e| {
+            TuskError::storage(format!("Failed to read encrypted credentials file: {e}"), None)
+        })?;
+
+        if contents.is_empty() {
+            return Ok(());
+        }
+
+        if contents.len() < NONCE_LEN {
+            return Err(TuskError::storage("Encrypted credentials file is truncated", None));
+        }
+
+        let (nonce, ciphertext) = contents.split_at(NONCE_LEN);
+        let plaintext =
+            self.cipher().decrypt(Nonce::from_slice(nonce), ciphertext).map_err(|_| {
+                TuskError::storage(
+                    "Failed to decrypt credentials file",
+                    Some("The machine identity may have changed; re-enter saved passwords"),
+                )
+            })?;
+
+        let creds_file: CredentialsFile = serde_json::from_slice(&plaintext).map_err(|e| {
+            TuskError::storage(format!("Invalid credentials file format: {e}"), None)
+        })?;
+
+        *self.cache.write() = creds_file.credentials;
+        Ok(())
+    }
+
+    /// Encrypt the cache and write it to disk with owner-only permissions.
+    fn save_to_file(&self) -> Result<(), TuskError> {
+        let creds_file = CredentialsFile { credentials: self.cache.read().clone() };
+
+        let json = serde_json::to_vec(&creds_file).map_err(|e| {
+            TuskError::storage(format!("Failed to serialize credentials: {e}"), None)
+        })?;
+
+        let nonce = Aes256Gcm::generate_nonce(OsRng);
+        let ciphertext = self
+            .cipher()
+            .encrypt(&nonce, json.as_slice())
+            .map_err(|_| TuskError::storage("Failed to encrypt credentials", None))?;
+
+        let mut contents = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+        contents.extend_from_slice(&nonce);
+        contents.extend_from_slice(&ciphertext);
+
+        write_private_file(&self.file_path, &contents).map_err(|e| {
+            TuskError::storage(format!("Failed to write encrypted credentials file: {e}"), None)
+        })
+    }
+}
+
+impl CredentialsProvider for EncryptedFileCredentialsProvider {
+    fn store(&self, key: &str, value: &str) -> Result<(), TuskError> {
+        self.cache.write().insert(key.to_string(), value.to_string());
+        self.save_to_file()?;
+        tracing::debug!(key = key, "Credential stored in encrypted file");
+        Ok(())
+    }
+
+    fn get(&self, key: &str) -> Result<Option<String>, TuskError> {
+        Ok(self.cache.read().get(key).cloned())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), TuskError> {
+        self.cache.write().remove(key);
+        self.save_to_file()?;
+        tracing::debug!(key = key, "Credential deleted from encrypted file");
+        Ok(())
+    }
+
+    fn name(&self) -> &'static str {
+        "EncryptedFileCredentialsProvider"
+    }
+
+    fn backend(&self) -> CredentialBackend {
+        CredentialBackend::EncryptedFile
+    }
+}
+
+impl std::fmt::Debug for EncryptedFileCredentialsProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFileCredentialsProvider")
+            .field("file_path", &self.file_path)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Read a stable per-machine secret for key derivation.
+///
+/// Prefers the systemd/dbus machine id; otherwise generates a random secret
+/// once and stores it in the data directory.
+fn machine_secret(data_dir: &Path) -> Result<Vec<u8>, TuskError> {
+    for path in ["/etc/machine-id", "/var/lib/dbus/machine-id"] {
+        if let Ok(id) = fs::read_to_string(path) {
+            let id = id.trim();
+            if !id.is_empty() {
+                return Ok(id.as_bytes().to_vec());
+            }
+        }
+    }
+
+    let secret_path = data_dir.join(CREDENTIALS_SECRET_FILE);
+    if let Ok(secret) = fs::read(&secret_path) {
+        if !secret.is_empty() {
+            return Ok(secret);
+        }
+    }
+
+    let secret = Aes256Gcm::generate_key(OsRng).to_vec();
+    write_private_file(&secret_path, &secret).map_err(|e| {
+        TuskError::storage(format!("Failed to write credentials secret: {e}"), None)
+    })?;
+    tracing::debug!(path = %secret_path.display(), "Generated credentials secret");
+    Ok(secret)
+}
+
+/// Derive a 256-bit key from a machine secret.
+fn derive_key(secret: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(KEY_DERIVATION_CONTEXT);
+    hasher.update(secret);
+    hasher.finalize().into()
+}
+
+/// Write a file readable only by the owner on Unix systems.
+fn write_private_file(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::io::Write;
+        use std::os::unix::fs::OpenOptionsExt;
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(path)?;
+        file.write_all(contents)
+    }
+
+    #[cfg(not(unix))]
+    {
+        fs::write(path, contents)
+    }
 }
 
 // ============================================================================
@@ -236,6 +490,28 @@ impl KeychainCredentialsProvider {
     pub fn with_service(service: impl Into<String>) -> Self {
         Self { service: service.into() }
     }
+
+    /// Check whether the OS keychain can be reached.
+    ///
+    /// Looks up a probe entry; a missing entry means the keychain answered,
+    /// while platform or access failures mean it is unavailable.
+    pub fn is_available(&self) -> bool {
+        let entry = match Entry::new(&self.service, KEYCHAIN_PROBE_KEY) {
+            Ok(entry) => entry,
+            Err(e) => {
+                tracing::debug!(error = %e, "Keychain entry could not be created");
+                return false;
+            }
+        };
+
+        match entry.get_password() {
+            Ok(_) | Err(keyring::Error::NoEntry) => true,
+            Err(e) => {
+                tracing::debug!(error = %e, "Keychain probe failed");
+                false
+            }
+        }
+    }
 }
 
 impl CredentialsProvider for KeychainCredentialsProvider {
@@ -281,6 +557,10 @@ impl CredentialsProvider for KeychainCredentialsProvider {
     fn name(&self) -> &'static str {
         "KeychainCredentialsProvider"
     }
+
+    fn backend(&self) -> CredentialBackend {
+        CredentialBackend::Keychain
+    }
 }
 
 // ============================================================================
@@ -321,6 +601,10 @@ impl CredentialsProvider for SessionCredentialsProvider {
     fn name(&self) -> &'static str {
         "SessionCredentialsProvider"
     }
+
+    fn backend(&self) -> CredentialBackend {
+        CredentialBackend::Session
+    }
 }
 
 // ============================================================================
@@ -328,7 +612,7 @@ impl CredentialsProvider for SessionCredentialsProvider {
 // ============================================================================
 
 /// Select the appropriate credentials provider (T100, T101, T103).
-fn select_provider() -> Box<dyn CredentialsProvider> {
+fn select_provider(data_dir: &Path) -> Box<dyn CredentialsProvider> {
     // Check for environment variable override (T101)
     let force_keychain = std::env::var(FORCE_KEYCHAIN_ENV).map(|v| v == "1").unwrap_or(false);
 
@@ -341,7 +625,7 @@ fn select_provider() -> Box<dyn CredentialsProvider> {
                 reason = "TUSK_USE_KEYCHAIN=1",
                 "Using keychain provider (override)"
             );
-            return Box::new(KeychainCredentialsProvider::new());
+            return keychain_or_fallback(data_dir);
         }
 
         match FileCredentialsProvider::new() {
@@ -368,12 +652,39 @@ fn select_provider() -> Box<dyn CredentialsProvider> {
     // In release builds, use keychain (T100)
     #[cfg(not(debug_assertions))]
     {
+        let _ = force_keychain;
         tracing::debug!(
             provider = "KeychainCredentialsProvider",
             reason = "release build",
             "Using keychain credential storage"
         );
-        Box::new(KeychainCredentialsProvider::new())
+        keychain_or_fallback(data_dir)
+    }
+}
+
+/// Use the keychain when reachable, otherwise the encrypted file provider.
+///
+/// Falls back to session-only storage if the encrypted file cannot be opened.
+fn keychain_or_fallback(data_dir: &Path) -> Box<dyn CredentialsProvider> {
+    let keychain = KeychainCredentialsProvider::new();
+    if keychain.is_available() {
+        return Box::new(keychain);
+    }
+
+    tracing::warn!(
+        data_dir = %data_dir.display(),
+        "OS keychain unavailable, falling back to encrypted file credential storage"
+    );
+
+    match EncryptedFileCredentialsProvider::new(data_dir) {
+        Ok(provider) => Box::new(provider),
+        Err(e) => {
+            tracing::warn!(
+                error = %e,
+                "Failed to open encrypted credential file, falling back to session"
+            );
+            Box::new(SessionCredentialsProvider::new())
+        }
     }
 }
 
@@ -382,8 +693,9 @@ fn select_provider() -> Box<dyn CredentialsProvider> {
 /// Uses a pluggable provider system:
 /// - Debug builds: File-based storage (avoids keychain popup issues)
 /// - Release builds: OS keychain storage
+/// - Keychain unavailable: Encrypted file under the data directory
 ///
-/// The provider is selected automatically based on build type.
+/// The provider is selected automatically based on build type and keychain availability.
 pub struct CredentialService {
     /// The active credential provider.
     provider: Box<dyn CredentialsProvider>,
@@ -395,16 +707,32 @@ impl CredentialService {
     /// Automatically selects the appropriate provider based on build type.
     /// Logs the selected provider at DEBUG level (T103).
     pub fn new() -> Self {
-        let provider = select_provider();
+        Self::with_data_dir(&crate::services::storage::default_data_dir())
+    }
+
+    /// Create a credential service whose file fallback lives in `data_dir`.
+    pub fn with_data_dir(data_dir: &Path) -> Self {
+        let provider = select_provider(data_dir);
         tracing::info!(provider = provider.name(), "Credential service initialized");
         Self { provider }
     }
 
+    /// Create with an explicit provider (for testing).
+    #[cfg(test)]
+    pub(crate) fn with_provider(provider: Box<dyn CredentialsProvider>) -> Self {
+        Self { provider }
+    }
+
     /// Get the name of the active provider.
     pub fn provider_name(&self) -> &'static str {
         self.provider.name()
     }
 
+    /// Get the kind of storage backing this service.
+    pub fn backend(&self) -> CredentialBackend {
+        self.provider.backend()
+    }
+
     /// Check if using file-based storage.
     pub fn is_using_file_storage(&self) -> bool {
         self.provider.name() == "FileCredentialsProvider"
@@ -442,6 +770,53 @@ impl CredentialService {
         self.provider.get(&key)
     }
 
+    /// Replace the stored password for a connection in place.
+    ///
+    /// Overwrites the existing entry, leaving the saved connection untouched.
+    pub fn update_password(
+        &self,
+        connection_id: Uuid,
+        new_password: &str,
+    ) -> Result<(), TuskError> {
+        if new_password.is_empty() {
+            return Err(TuskError::authentication_with_hint(
+                "Password cannot be empty",
+                "Enter the new database password",
+            ));
+        }
+
+        let key = format!("db:{connection_id}");
+        self.provider.store(&key, new_password)?;
+        tracing::debug!(connection_id = %connection_id, "Password updated");
+        Ok(())
+    }
+
+    /// Verify a new password before replacing the stored one.
+    ///
+    /// `verify` receives the candidate password (typically to open a test
+    /// connection). The stored password is only changed if it succeeds.
+    pub async fn update_password_verified<F, Fut>(
+        &self,
+        connection_id: Uuid,
+        new_password: &str,
+        verify: F,
+    ) -> Result<(), TuskError>
+    where
+        F: FnOnce(String) -> Fut,
+        Fut: Future<Output = Result<(), TuskError>>,
+    {
+        if let Err(e) = verify(new_password.to_string()).await {
+            tracing::warn!(
+                connection_id = %connection_id,
+                error = %e,
+                "New password failed verification, keeping stored password"
+            );
+            return Err(e);
+        }
+
+        self.update_password(connection_id, new_password)
+    }
+
     /// Delete a stored password (FR-019).
     pub fn delete_password(&self, connection_id: Uuid) -> Result<(), TuskError> {
         let key = format!("db:{connection_id}");
@@ -584,4 +959,114 @@ mod tests {
         let passphrase = service.get_ssh_passphrase(tunnel_id).unwrap();
         assert_eq!(passphrase, None);
     }
+
+    #[test]
+    fn test_encrypted_file_provider_round_trip() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(ENCRYPTED_CREDENTIALS_FILE);
+        let provider =
+            EncryptedFileCredentialsProvider::with_key(file_path, derive_key(b"machine")).unwrap();
+
+        provider.store("enc_key", "enc_value").unwrap();
+        assert_eq!(provider.get("enc_key").unwrap(), Some("enc_value".to_string()));
+
+        provider.delete("enc_key").unwrap();
+        assert_eq!(provider.get("enc_key").unwrap(), None);
+    }
+
+    #[test]
+    fn test_encrypted_file_provider_persistence() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(ENCRYPTED_CREDENTIALS_FILE);
+        let key = derive_key(b"machine");
+
+        {
+            let provider =
+                EncryptedFileCredentialsProvider::with_key(file_path.clone(), key).unwrap();
+            provider.store("persist_key", "s3cret-value").unwrap();
+        }
+
+        // Ciphertext on disk must not contain the plaintext
+        let raw = fs::read(&file_path).unwrap();
+        assert!(!raw.windows(b"s3cret-value".len()).any(|w| w == b"s3cret-value"));
+
+        let provider = EncryptedFileCredentialsProvider::with_key(file_path.clone(), key).unwrap();
+        assert_eq!(provider.get("persist_key").unwrap(), Some("s3cret-value".to_string()));
+
+        // A different machine secret cannot decrypt the file
+        assert!(
+            EncryptedFileCredentialsProvider::with_key(file_path, derive_key(b"other")).is_err()
+        );
+    }
+
+    #[test]
+    fn test_encrypted_file_provider_generates_secret() {
+        let dir = tempdir().unwrap();
+        let secret = machine_secret(dir.path()).unwrap();
+        assert!(!secret.is_empty());
+        assert_eq!(machine_secret(dir.path()).unwrap(), secret);
+    }
+
+    #[test]
+    fn test_credential_service_encrypted_fallback() {
+        let dir = tempdir().unwrap();
+        let provider = EncryptedFileCredentialsProvider::new(dir.path()).unwrap();
+        let service = CredentialService::with_provider(Box::new(provider));
+        assert_eq!(service.backend(), CredentialBackend::EncryptedFile);
+        assert!(service.backend().is_persistent());
+
+        let connection_id = Uuid::new_v4();
+        service.store_password(connection_id, "fallback_password").unwrap();
+
+        // A fresh service over the same directory reads the stored password
+        let reopened = CredentialService::with_provider(Box::new(
+            EncryptedFileCredentialsProvider::new(dir.path()).unwrap(),
+        ));
+        assert_eq!(
+            reopened.get_password(connection_id).unwrap(),
+            Some("fallback_password".to_string())
+        );
+    }
+
+    #[test]
+    fn test_update_password_overwrites_stored_value() {
+        let service = CredentialService::with_provider(Box::new(SessionCredentialsProvider::new()));
+        let connection_id = Uuid::new_v4();
+
+        service.store_password(connection_id, "old_password").unwrap();
+        service.update_password(connection_id, "new_password").unwrap();
+        assert_eq!(service.get_password(connection_id).unwrap(), Some("new_password".to_string()));
+
+        assert!(service.update_password(connection_id, "").is_err());
+        assert_eq!(service.get_password(connection_id).unwrap(), Some("new_password".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_update_password_verified() {
+        let service = CredentialService::with_provider(Box::new(SessionCredentialsProvider::new()));
+        let connection_id = Uuid::new_v4();
+        service.store_password(connection_id, "old_password").unwrap();
+
+        // Fake pool that rejects the candidate password
+        let result = service
+            .update_password_verified(connection_id, "wrong_password", |_| async {
+                Err(TuskError::authentication("password authentication failed"))
+            })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(service.get_password(connection_id).unwrap(), Some("old_password".to_string()));
+
+        // Fake pool that accepts only the expected password
+        let result = service
+            .update_password_verified(connection_id, "new_password", |candidate| async move {
+                if candidate == "new_password" {
+                    Ok(())
+                } else {
+                    Err(TuskError::authentication("password authentication failed"))
+                }
+            })
+            .await;
+        assert!(result.is_ok());
+        assert_eq!(service.get_password(connection_id).unwrap(), Some("new_password".to_string()));
+    }
 }
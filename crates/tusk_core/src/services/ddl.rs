@@ -0,0 +1,1649 @@
+//! Schema-only DDL generation.
+//!
+//! Reconstructs `CREATE` statements from the PostgreSQL system catalogs
+//! without shelling out to `pg_dump`. Introspection fills a [`DdlCatalog`],
+//! which is then rendered in dependency order:
+//!
+//! 1. Schemas
+//! 2. Types (enums, then domains, then composites)
+//! 3. Sequences
+//! 4. Functions and procedures (with `check_function_bodies` disabled)
+//! 5. Tables, followed by sequence ownership and foreign keys
+//! 6. Views and materialized views, ordered by their dependencies
+//! 7. Indexes that don't back a constraint
+//! 8. Grants (optional)
+
+use std::collections::{HashMap, HashSet};
+
+use crate::error::TuskError;
+use crate::services::connection::PooledConnection;
+use crate::services::schema::SchemaService;
+
+/// Options for [`SchemaService::dump_ddl`].
+#[derive(Debug, Clone)]
+pub struct DdlDumpOptions {
+    /// Schemas to include (empty = all non-system schemas)
+    pub schemas: Vec<String>,
+    /// Emit `COMMENT ON` statements
+    pub include_comments: bool,
+    /// Emit `GRANT` statements for tables and views
+    pub include_grants: bool,
+}
+
+impl Default for DdlDumpOptions {
+    fn default() -> Self {
+        Self { schemas: Vec::new(), include_comments: true, include_grants: false }
+    }
+}
+
+/// Kind of user-defined type, in creation order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum DdlTypeKind {
+    /// `CREATE TYPE ... AS ENUM`
+    Enum,
+    /// `CREATE DOMAIN`
+    Domain,
+    /// `CREATE TYPE ... AS (...)`
+    Composite,
+}
+
+/// A user-defined type.
+#[derive(Debug, Clone)]
+pub struct DdlType {
+    /// Schema name
+    pub schema: String,
+    /// Type name
+    pub name: String,
+    /// Kind of type
+    pub kind: DdlTypeKind,
+    /// Everything after the type name (e.g. `AS ENUM ('a', 'b')`)
+    pub body: String,
+    /// Type comment
+    pub comment: Option<String>,
+}
+
+/// A standalone or serial-owned sequence (identity sequences are excluded).
+#[derive(Debug, Clone)]
+pub struct DdlSequence {
+    /// Schema name
+    pub schema: String,
+    /// Sequence name
+    pub name: String,
+    /// Sequence data type
+    pub data_type: String,
+    /// Start value
+    pub start: i64,
+    /// Increment
+    pub increment: i64,
+    /// Minimum value
+    pub min_value: i64,
+    /// Maximum value
+    pub max_value: i64,
+    /// Cache size
+    pub cache: i64,
+    /// Whether the sequence wraps around
+    pub cycle: bool,
+    /// Owning column as (schema, table, column)
+    pub owned_by: Option<(String, String, String)>,
+}
+
+/// A function or procedure.
+#[derive(Debug, Clone)]
+pub struct DdlFunction {
+    /// Schema name
+    pub schema: String,
+    /// Function name
+    pub name: String,
+    /// Identity arguments, used to address the function in comments
+    pub arguments: String,
+    /// Whether this is a procedure
+    pub is_procedure: bool,
+    /// Full `CREATE OR REPLACE FUNCTION` text from `pg_get_functiondef`
+    pub definition: String,
+    /// Function comment
+    pub comment: Option<String>,
+}
+
+/// A table column.
+#[derive(Debug, Clone)]
+pub struct DdlColumn {
+    /// Column name
+    pub name: String,
+    /// Formatted data type
+    pub data_type: String,
+    /// Whether the column is NOT NULL
+    pub not_null: bool,
+    /// Default (or generation) expression
+    pub default_value: Option<String>,
+    /// Identity kind: `a` (ALWAYS), `d` (BY DEFAULT), or empty
+    pub identity: String,
+    /// Generated kind: `s` (STORED) or empty
+    pub generated: String,
+    /// Column comment
+    pub comment: Option<String>,
+}
+
+/// A table constraint.
+#[derive(Debug, Clone)]
+pub struct DdlConstraint {
+    /// Constraint name
+    pub name: String,
+    /// Constraint type from `pg_constraint.contype` (`p`, `u`, `c`, `f`, `x`)
+    pub kind: char,
+    /// Definition from `pg_get_constraintdef`
+    pub definition: String,
+}
+
+impl DdlConstraint {
+    /// Check if this is a foreign key.
+    pub fn is_foreign_key(&self) -> bool {
+        self.kind == 'f'
+    }
+}
+
+/// A table with its columns and constraints.
+#[derive(Debug, Clone)]
+pub struct DdlTable {
+    /// Schema name
+    pub schema: String,
+    /// Table name
+    pub name: String,
+    /// Columns in ordinal order
+    pub columns: Vec<DdlColumn>,
+    /// Constraints, including foreign keys
+    pub constraints: Vec<DdlConstraint>,
+    /// Partition key from `pg_get_partkeydef`, for partitioned tables
+    pub partition_key: Option<String>,
+    /// Parent table and bound as (schema, name, `FOR VALUES ...`), for partitions
+    pub partition_of: Option<(String, String, String)>,
+    /// Table comment
+    pub comment: Option<String>,
+}
+
+/// A view or materialized view.
+#[derive(Debug, Clone)]
+pub struct DdlView {
+    /// Schema name
+    pub schema: String,
+    /// View name
+    pub name: String,
+    /// Whether this is a materialized view
+    pub is_materialized: bool,
+    /// Whether a materialized view holds data (always true for plain views)
+    pub is_populated: bool,
+    /// Query text from `pg_get_viewdef`
+    pub definition: String,
+    /// Other views this view selects from, as (schema, name)
+    pub depends_on: Vec<(String, String)>,
+    /// View comment
+    pub comment: Option<String>,
+}
+
+/// An index that doesn't back a constraint.
+#[derive(Debug, Clone)]
+pub struct DdlIndex {
+    /// Schema name
+    pub schema: String,
+    /// Table or materialized view the index is on
+    pub table: String,
+    /// Index name
+    pub name: String,
+    /// Full `CREATE INDEX` text from `pg_get_indexdef`
+    pub definition: String,
+}
+
+/// Privileges granted on a table or view.
+#[derive(Debug, Clone)]
+pub struct DdlGrant {
+    /// Schema name
+    pub schema: String,
+    /// Table or view name
+    pub name: String,
+    /// Role receiving the privileges (`PUBLIC` for everyone)
+    pub grantee: String,
+    /// Comma-separated privilege list
+    pub privileges: String,
+}
+
+/// Introspected objects to render as DDL.
+#[derive(Debug, Clone, Default)]
+pub struct DdlCatalog {
+    /// Schemas as (name, comment)
+    pub schemas: Vec<(String, Option<String>)>,
+    /// User-defined types
+    pub types: Vec<DdlType>,
+    /// Sequences
+    pub sequences: Vec<DdlSequence>,
+    /// Functions and procedures
+    pub functions: Vec<DdlFunction>,
+    /// Tables
+    pub tables: Vec<DdlTable>,
+    /// Views and materialized views
+    pub views: Vec<DdlView>,
+    /// Indexes
+    pub indexes: Vec<DdlIndex>,
+    /// Grants
+    pub grants: Vec<DdlGrant>,
+}
+
+impl SchemaService {
+    /// Generate a schema-only SQL dump of the connected database.
+    ///
+    /// Objects are emitted in dependency order so the output can be replayed
+    /// into an empty database.
+    pub async fn dump_ddl(
+        conn: &PooledConnection,
+        opts: &DdlDumpOptions,
+    ) -> Result<String, TuskError> {
+        let schemas = if opts.schemas.is_empty() {
+            Self::load_schemas(conn).await?.into_iter().map(|s| s.name).collect()
+        } else {
+            opts.schemas.clone()
+        };
+
+        tracing::debug!(schema_count = schemas.len(), "Dumping schema DDL");
+
+        let catalog = DdlCatalog {
+            schemas: Self::load_ddl_schemas(conn, &schemas).await?,
+            types: Self::load_ddl_types(conn, &schemas).await?,
+            sequences: Self::load_ddl_sequences(conn, &schemas).await?,
+            functions: Self::load_ddl_functions(conn, &schemas).await?,
+            tables: Self::load_ddl_tables(conn, &schemas, None).await?,
+            views: Self::load_ddl_views(conn, &schemas).await?,
+            indexes: Self::load_ddl_indexes(conn, &schemas, None).await?,
+            grants: if opts.include_grants {
+                Self::load_ddl_grants(conn, &schemas).await?
+            } else {
+                Vec::new()
+            },
+        };
+
+        Ok(render_dump(&catalog, opts))
+    }
+
+    /// Generate the `CREATE TABLE` statement for a table, followed by its indexes.
+    pub async fn get_table_ddl(
+        conn: &PooledConnection,
+        schema: &str,
+        table: &str,
+    ) -> Result<String, TuskError> {
+        let schemas = vec![schema.to_string()];
+        let tables = Self::load_ddl_tables(conn, &schemas, Some(table)).await?;
+        let ddl_table =
+            tables.into_iter().next().ok_or_else(|| Self::not_found("Table", schema, table))?;
+        let indexes = Self::load_ddl_indexes(conn, &schemas, Some(table)).await?;
+
+        let mut out = render_create_table(&ddl_table, true);
+        for index in &indexes {
+            out.push('\n');
+            out.push_str(&terminate(&index.definition));
+        }
+        Ok(out)
+    }
+
+    /// Get the definition of a view or materialized view (via `pg_get_viewdef`).
+    ///
+    /// A materialized view is labelled as such and keeps its current data
+    /// state; see [`render_view_definition`].
+    pub async fn get_view_definition(
+        conn: &PooledConnection,
+        schema: &str,
+        view: &str,
+    ) -> Result<String, TuskError> {
+        let row = conn
+            .query_cached(
+                r#"
+                SELECT c.relkind = 'm' AS is_materialized,
+                       c.relispopulated AS is_populated,
+                       pg_get_viewdef(c.oid, true) AS definition
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('v', 'm')
+                  AND n.nspname = $1
+                  AND c.relname = $2
+                "#,
+                &[&schema, &view],
+            )
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| Self::not_found("View", schema, view))?;
+
+        Ok(render_view_definition(&DdlView {
+            schema: schema.to_string(),
+            name: view.to_string(),
+            is_materialized: row.get("is_materialized"),
+            is_populated: row.get("is_populated"),
+            definition: row.get("definition"),
+            depends_on: Vec::new(),
+            comment: None,
+        }))
+    }
+
+    /// Get the source of a function or procedure (via `pg_get_functiondef`).
+    ///
+    /// `arg_types` is the identity argument list shown in the schema browser
+    /// (e.g. `integer, text`), which disambiguates overloads.
+    pub async fn get_function_definition(
+        conn: &PooledConnection,
+        schema: &str,
+        name: &str,
+        arg_types: &str,
+    ) -> Result<String, TuskError> {
+        let row = conn
+            .query_cached(
+                r#"
+                SELECT pg_get_functiondef(p.oid) AS definition
+                FROM pg_catalog.pg_proc p
+                JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+                WHERE p.prokind IN ('f', 'p')
+                  AND n.nspname = $1
+                  AND p.proname = $2
+                  AND pg_get_function_identity_arguments(p.oid) = $3
+                "#,
+                &[&schema, &name, &arg_types],
+            )
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                Self::not_found("Function", schema, &format!("{}({})", name, arg_types))
+            })?;
+
+        Ok(terminate(row.get("definition")))
+    }
+
+    /// Error for an object that no longer exists in the catalog.
+    fn not_found(kind: &str, schema: &str, name: &str) -> TuskError {
+        TuskError::query(
+            format!("{} {}.{} not found", kind, schema, name),
+            None,
+            Some("Refresh the schema browser; the object may have been dropped or renamed".into()),
+            None,
+            None,
+        )
+    }
+
+    async fn load_ddl_schemas(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<(String, Option<String>)>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS name, obj_description(n.oid, 'pg_namespace') AS comment
+                FROM pg_catalog.pg_namespace n
+                WHERE n.nspname = ANY($1)
+                ORDER BY n.nspname
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        Ok(rows.into_iter().map(|row| (row.get("name"), row.get("comment"))).collect())
+    }
+
+    async fn load_ddl_types(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<DdlType>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, t.typname AS name, 'e' AS kind,
+                       'AS ENUM (' || string_agg(quote_literal(e.enumlabel), ', '
+                                                 ORDER BY e.enumsortorder) || ')' AS body,
+                       obj_description(t.oid, 'pg_type') AS comment
+                FROM pg_catalog.pg_type t
+                JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+                JOIN pg_catalog.pg_enum e ON e.enumtypid = t.oid
+                WHERE n.nspname = ANY($1)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = t.oid AND d.deptype = 'e')
+                GROUP BY n.nspname, t.typname, t.oid
+                UNION ALL
+                SELECT n.nspname, t.typname, 'd',
+                       'AS ' || format_type(t.typbasetype, t.typtypmod)
+                       || COALESCE(' DEFAULT ' || t.typdefault, '')
+                       || CASE WHEN t.typnotnull THEN ' NOT NULL' ELSE '' END
+                       || COALESCE((SELECT string_agg(' CONSTRAINT ' || quote_ident(c.conname)
+                                                      || ' ' || pg_get_constraintdef(c.oid), ''
+                                                      ORDER BY c.conname)
+                                    FROM pg_catalog.pg_constraint c
+                                    WHERE c.contypid = t.oid), ''),
+                       obj_description(t.oid, 'pg_type')
+                FROM pg_catalog.pg_type t
+                JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+                WHERE t.typtype = 'd'
+                  AND n.nspname = ANY($1)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = t.oid AND d.deptype = 'e')
+                UNION ALL
+                SELECT n.nspname, t.typname, 'c',
+                       'AS (' || string_agg(quote_ident(a.attname) || ' '
+                                            || format_type(a.atttypid, a.atttypmod), ', '
+                                            ORDER BY a.attnum) || ')',
+                       obj_description(t.oid, 'pg_type')
+                FROM pg_catalog.pg_type t
+                JOIN pg_catalog.pg_namespace n ON n.oid = t.typnamespace
+                JOIN pg_catalog.pg_class c ON c.oid = t.typrelid AND c.relkind = 'c'
+                JOIN pg_catalog.pg_attribute a
+                  ON a.attrelid = c.oid AND a.attnum > 0 AND NOT a.attisdropped
+                WHERE n.nspname = ANY($1)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = t.oid AND d.deptype = 'e')
+                GROUP BY n.nspname, t.typname, t.oid
+                ORDER BY 1, 2
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let kind: &str = row.get("kind");
+                DdlType {
+                    schema: row.get("schema"),
+                    name: row.get("name"),
+                    kind: match kind {
+                        "e" => DdlTypeKind::Enum,
+                        "d" => DdlTypeKind::Domain,
+                        _ => DdlTypeKind::Composite,
+                    },
+                    body: row.get("body"),
+                    comment: row.get("comment"),
+                }
+            })
+            .collect())
+    }
+
+    async fn load_ddl_sequences(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<DdlSequence>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS name,
+                       format_type(s.seqtypid, NULL) AS data_type,
+                       s.seqstart AS start_value, s.seqincrement AS increment,
+                       s.seqmin AS min_value, s.seqmax AS max_value,
+                       s.seqcache AS cache, s.seqcycle AS cycle,
+                       tn.nspname AS owner_schema, tc.relname AS owner_table,
+                       ta.attname AS owner_column
+                FROM pg_catalog.pg_sequence s
+                JOIN pg_catalog.pg_class c ON c.oid = s.seqrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_catalog.pg_depend d
+                  ON d.classid = 'pg_catalog.pg_class'::regclass
+                 AND d.objid = c.oid
+                 AND d.refclassid = 'pg_catalog.pg_class'::regclass
+                 AND d.deptype IN ('a', 'i')
+                LEFT JOIN pg_catalog.pg_class tc ON tc.oid = d.refobjid
+                LEFT JOIN pg_catalog.pg_namespace tn ON tn.oid = tc.relnamespace
+                LEFT JOIN pg_catalog.pg_attribute ta
+                  ON ta.attrelid = d.refobjid AND ta.attnum = d.refobjsubid
+                WHERE n.nspname = ANY($1)
+                  AND (d.deptype IS NULL OR d.deptype = 'a')
+                ORDER BY n.nspname, c.relname
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let owner_schema: Option<String> = row.get("owner_schema");
+                let owner_table: Option<String> = row.get("owner_table");
+                let owner_column: Option<String> = row.get("owner_column");
+                DdlSequence {
+                    schema: row.get("schema"),
+                    name: row.get("name"),
+                    data_type: row.get("data_type"),
+                    start: row.get("start_value"),
+                    increment: row.get("increment"),
+                    min_value: row.get("min_value"),
+                    max_value: row.get("max_value"),
+                    cache: row.get("cache"),
+                    cycle: row.get("cycle"),
+                    owned_by: match (owner_schema, owner_table, owner_column) {
+                        (Some(s), Some(t), Some(c)) => Some((s, t, c)),
+                        _ => None,
+                    },
+                }
+            })
+            .collect())
+    }
+
+    async fn load_ddl_functions(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<DdlFunction>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, p.proname AS name,
+                       pg_get_function_identity_arguments(p.oid) AS arguments,
+                       p.prokind = 'p' AS is_procedure,
+                       pg_get_functiondef(p.oid) AS definition,
+                       obj_description(p.oid, 'pg_proc') AS comment
+                FROM pg_catalog.pg_proc p
+                JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
+                WHERE n.nspname = ANY($1)
+                  AND p.prokind IN ('f', 'p')
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = p.oid AND d.deptype = 'e')
+                ORDER BY n.nspname, p.proname, p.oid
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DdlFunction {
+                schema: row.get("schema"),
+                name: row.get("name"),
+                arguments: row.get("arguments"),
+                is_procedure: row.get("is_procedure"),
+                definition: row.get("definition"),
+                comment: row.get("comment"),
+            })
+            .collect())
+    }
+
+    async fn load_ddl_tables(
+        conn: &PooledConnection,
+        schemas: &[String],
+        table: Option<&str>,
+    ) -> Result<Vec<DdlTable>, TuskError> {
+        let table_rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS name,
+                       obj_description(c.oid, 'pg_class') AS comment,
+                       CASE WHEN c.relkind = 'p' THEN pg_get_partkeydef(c.oid) END
+                           AS partition_key,
+                       pn.nspname AS parent_schema, p.relname AS parent_name,
+                       pg_get_expr(c.relpartbound, c.oid) AS partition_bound
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_catalog.pg_inherits i ON i.inhrelid = c.oid AND c.relispartition
+                LEFT JOIN pg_catalog.pg_class p ON p.oid = i.inhparent
+                LEFT JOIN pg_catalog.pg_namespace pn ON pn.oid = p.relnamespace
+                WHERE c.relkind IN ('r', 'p')
+                  AND n.nspname = ANY($1)
+                  AND ($2::text IS NULL OR c.relname = $2)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = c.oid AND d.deptype = 'e')
+                ORDER BY n.nspname, c.relname
+                "#,
+                &[&schemas, &table],
+            )
+            .await?;
+
+        let column_rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS table_name, a.attname AS name,
+                       format_type(a.atttypid, a.atttypmod) AS data_type,
+                       a.attnotnull AS not_null,
+                       pg_get_expr(d.adbin, d.adrelid) AS default_value,
+                       a.attidentity::text AS identity,
+                       a.attgenerated::text AS generated,
+                       col_description(c.oid, a.attnum) AS comment
+                FROM pg_catalog.pg_attribute a
+                JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_catalog.pg_attrdef d
+                  ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+                WHERE c.relkind IN ('r', 'p')
+                  AND n.nspname = ANY($1)
+                  AND ($2::text IS NULL OR c.relname = $2)
+                  AND a.attnum > 0
+                  AND NOT a.attisdropped
+                ORDER BY n.nspname, c.relname, a.attnum
+                "#,
+                &[&schemas, &table],
+            )
+            .await?;
+
+        let constraint_rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS table_name, con.conname AS name,
+                       con.contype::text AS kind,
+                       pg_get_constraintdef(con.oid) AS definition
+                FROM pg_catalog.pg_constraint con
+                JOIN pg_catalog.pg_class c ON c.oid = con.conrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('r', 'p')
+                  AND n.nspname = ANY($1)
+                  AND ($2::text IS NULL OR c.relname = $2)
+                  AND con.contype IN ('p', 'u', 'c', 'f', 'x')
+                  AND con.conislocal
+                ORDER BY n.nspname, c.relname,
+                         array_position(ARRAY['p', 'u', 'x', 'c', 'f'], con.contype::text),
+                         con.conname
+                "#,
+                &[&schemas, &table],
+            )
+            .await?;
+
+        let mut columns: HashMap<(String, String), Vec<DdlColumn>> = HashMap::new();
+        for row in column_rows {
+            let key = (row.get("schema"), row.get("table_name"));
+            columns.entry(key).or_default().push(DdlColumn {
+                name: row.get("name"),
+                data_type: row.get("data_type"),
+                not_null: row.get("not_null"),
+                default_value: row.get("default_value"),
+                identity: row.get("identity"),
+                generated: row.get("generated"),
+                comment: row.get("comment"),
+            });
+        }
+
+        let mut constraints: HashMap<(String, String), Vec<DdlConstraint>> = HashMap::new();
+        for row in constraint_rows {
+            let key = (row.get("schema"), row.get("table_name"));
+            let kind: &str = row.get("kind");
+            constraints.entry(key).or_default().push(DdlConstraint {
+                name: row.get("name"),
+                kind: kind.chars().next().unwrap_or('c'),
+                definition: row.get("definition"),
+            });
+        }
+
+        Ok(table_rows
+            .into_iter()
+            .map(|row| {
+                let key: (String, String) = (row.get("schema"), row.get("name"));
+                let parent: Option<String> = row.get("parent_name");
+                DdlTable {
+                    columns: columns.remove(&key).unwrap_or_default(),
+                    constraints: constraints.remove(&key).unwrap_or_default(),
+                    partition_key: row.get("partition_key"),
+                    partition_of: parent
+                        .map(|name| (row.get("parent_schema"), name, row.get("partition_bound"))),
+                    comment: row.get("comment"),
+                    schema: key.0,
+                    name: key.1,
+                }
+            })
+            .collect())
+    }
+
+    async fn load_ddl_views(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<DdlView>, TuskError> {
+        let view_rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS name,
+                       c.relkind = 'm' AS is_materialized,
+                       c.relispopulated AS is_populated,
+                       pg_get_viewdef(c.oid, true) AS definition,
+                       obj_description(c.oid, 'pg_class') AS comment
+                FROM pg_catalog.pg_class c
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('v', 'm')
+                  AND n.nspname = ANY($1)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_depend d
+                                  WHERE d.objid = c.oid AND d.deptype = 'e')
+                ORDER BY n.nspname, c.relname
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        let dependency_rows = conn
+            .query_cached(
+                r#"
+                SELECT DISTINCT vn.nspname AS schema, v.relname AS name,
+                       rn.nspname AS dep_schema, r.relname AS dep_name
+                FROM pg_catalog.pg_depend dep
+                JOIN pg_catalog.pg_rewrite rw ON rw.oid = dep.objid
+                JOIN pg_catalog.pg_class v ON v.oid = rw.ev_class
+                JOIN pg_catalog.pg_namespace vn ON vn.oid = v.relnamespace
+                JOIN pg_catalog.pg_class r ON r.oid = dep.refobjid
+                JOIN pg_catalog.pg_namespace rn ON rn.oid = r.relnamespace
+                WHERE dep.classid = 'pg_catalog.pg_rewrite'::regclass
+                  AND dep.refclassid = 'pg_catalog.pg_class'::regclass
+                  AND v.relkind IN ('v', 'm')
+                  AND r.relkind IN ('v', 'm')
+                  AND r.oid <> v.oid
+                  AND vn.nspname = ANY($1)
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        let mut dependencies: HashMap<(String, String), Vec<(String, String)>> = HashMap::new();
+        for row in dependency_rows {
+            dependencies
+                .entry((row.get("schema"), row.get("name")))
+                .or_default()
+                .push((row.get("dep_schema"), row.get("dep_name")));
+        }
+
+        Ok(view_rows
+            .into_iter()
+            .map(|row| {
+                let key: (String, String) = (row.get("schema"), row.get("name"));
+                DdlView {
+                    is_materialized: row.get("is_materialized"),
+                    is_populated: row.get("is_populated"),
+                    definition: row.get("definition"),
+                    depends_on: dependencies.remove(&key).unwrap_or_default(),
+                    comment: row.get("comment"),
+                    schema: key.0,
+                    name: key.1,
+                }
+            })
+            .collect())
+    }
+
+    async fn load_ddl_indexes(
+        conn: &PooledConnection,
+        schemas: &[String],
+        table: Option<&str>,
+    ) -> Result<Vec<DdlIndex>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT n.nspname AS schema, c.relname AS table_name, i.relname AS name,
+                       pg_get_indexdef(i.oid) AS definition
+                FROM pg_catalog.pg_index x
+                JOIN pg_catalog.pg_class i ON i.oid = x.indexrelid
+                JOIN pg_catalog.pg_class c ON c.oid = x.indrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                WHERE c.relkind IN ('r', 'p', 'm')
+                  AND NOT i.relispartition
+                  AND n.nspname = ANY($1)
+                  AND ($2::text IS NULL OR c.relname = $2)
+                  AND NOT EXISTS (SELECT 1 FROM pg_catalog.pg_constraint con
+                                  WHERE con.conindid = x.indexrelid
+                                    AND con.contype IN ('p', 'u', 'x'))
+                ORDER BY n.nspname, c.relname, i.relname
+                "#,
+                &[&schemas, &table],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DdlIndex {
+                schema: row.get("schema"),
+                table: row.get("table_name"),
+                name: row.get("name"),
+                definition: row.get("definition"),
+            })
+            .collect())
+    }
+
+    async fn load_ddl_grants(
+        conn: &PooledConnection,
+        schemas: &[String],
+    ) -> Result<Vec<DdlGrant>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT table_schema::text AS schema, table_name::text AS name,
+                       grantee::text AS grantee,
+                       string_agg(privilege_type::text, ', ' ORDER BY privilege_type) AS privileges
+                FROM information_schema.role_table_grants
+                WHERE table_schema = ANY($1)
+                  AND grantor <> grantee
+                GROUP BY table_schema, table_name, grantee
+                ORDER BY 1, 2, 3
+                "#,
+                &[&schemas],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| DdlGrant {
+                schema: row.get("schema"),
+                name: row.get("name"),
+                grantee: row.get("grantee"),
+                privileges: row.get("privileges"),
+            })
+            .collect())
+    }
+}
+
+/// Render a catalog as a schema-only dump.
+pub fn render_dump(catalog: &DdlCatalog, opts: &DdlDumpOptions) -> String {
+    let mut out = String::from("-- Schema-only dump generated by Tusk\n\n");
+    out.push_str("SET check_function_bodies = false;\n");
+
+    let comment = |out: &mut String, target: String, text: &Option<String>| {
+        if let (true, Some(text)) = (opts.include_comments, text) {
+            out.push_str(&format!("COMMENT ON {} IS {};\n", target, quote_literal(text)));
+        }
+    };
+
+    if !catalog.schemas.is_empty() {
+        out.push_str("\n-- Schemas\n");
+    }
+    for (name, text) in &catalog.schemas {
+        out.push_str(&format!("CREATE SCHEMA IF NOT EXISTS {};\n", quote_ident(name)));
+        comment(&mut out, format!("SCHEMA {}", quote_ident(name)), text);
+    }
+
+    let mut types: Vec<&DdlType> = catalog.types.iter().collect();
+    types.sort_by_key(|t| t.kind);
+    if !types.is_empty() {
+        out.push_str("\n-- Types\n");
+    }
+    for ty in types {
+        let keyword = if ty.kind == DdlTypeKind::Domain { "DOMAIN" } else { "TYPE" };
+        let name = qualified(&ty.schema, &ty.name);
+        out.push_str(&format!("CREATE {} {} {};\n", keyword, name, ty.body));
+        comment(&mut out, format!("{} {}", keyword, name), &ty.comment);
+    }
+
+    if !catalog.sequences.is_empty() {
+        out.push_str("\n-- Sequences\n");
+    }
+    for seq in &catalog.sequences {
+        out.push_str(&format!(
+            "CREATE SEQUENCE {} AS {} START WITH {} INCREMENT BY {} MINVALUE {} MAXVALUE {} \
+             CACHE {}{};\n",
+            qualified(&seq.schema, &seq.name),
+            seq.data_type,
+            seq.start,
+            seq.increment,
+            seq.min_value,
+            seq.max_value,
+            seq.cache,
+            if seq.cycle { " CYCLE" } else { "" }
+        ));
+    }
+
+    if !catalog.tables.is_empty() {
+        out.push_str("\n-- Tables\n");
+    }
+    for table in order_tables(&catalog.tables) {
+        out.push_str(&render_create_table(table, false));
+        out.push('\n');
+        let name = qualified(&table.schema, &table.name);
+        comment(&mut out, format!("TABLE {}", name), &table.comment);
+        for column in &table.columns {
+            comment(
+                &mut out,
+                format!("COLUMN {}.{}", name, quote_ident(&column.name)),
+                &column.comment,
+            );
+        }
+    }
+
+    for seq in &catalog.sequences {
+        if let Some((schema, table, column)) = &seq.owned_by {
+            out.push_str(&format!(
+                "ALTER SEQUENCE {} OWNED BY {}.{};\n",
+                qualified(&seq.schema, &seq.name),
+                qualified(schema, table),
+                quote_ident(column)
+            ));
+        }
+    }
+
+    // Foreign keys go after every table so references can be satisfied in any order
+    let foreign_keys: Vec<(&DdlTable, &DdlConstraint)> = catalog
+        .tables
+        .iter()
+        .flat_map(|t| t.constraints.iter().filter(|c| c.is_foreign_key()).map(move |c| (t, c)))
+        .collect();
+    if !foreign_keys.is_empty() {
+        out.push_str("\n-- Foreign keys\n");
+    }
+    for (table, fk) in foreign_keys {
+        out.push_str(&format!(
+            "ALTER TABLE ONLY {} ADD CONSTRAINT {} {};\n",
+            qualified(&table.schema, &table.name),
+            quote_ident(&fk.name),
+            fk.definition
+        ));
+    }
+
+    // Functions follow the tables their bodies may reference
+    if !catalog.functions.is_empty() {
+        out.push_str("\n-- Functions\n");
+    }
+    for func in &catalog.functions {
+        out.push_str(&terminate(&func.definition));
+        out.push('\n');
+        let keyword = if func.is_procedure { "PROCEDURE" } else { "FUNCTION" };
+        comment(
+            &mut out,
+            format!("{} {}({})", keyword, qualified(&func.schema, &func.name), func.arguments),
+            &func.comment,
+        );
+    }
+
+    let views = order_views(&catalog.views);
+    if !views.is_empty() {
+        out.push_str("\n-- Views\n");
+    }
+    for view in views {
+        out.push_str(&render_create_view(view));
+        out.push('\n');
+        let keyword = if view.is_materialized { "MATERIALIZED VIEW" } else { "VIEW" };
+        comment(
+            &mut out,
+            format!("{} {}", keyword, qualified(&view.schema, &view.name)),
+            &view.comment,
+        );
+    }
+
+    if !catalog.indexes.is_empty() {
+        out.push_str("\n-- Indexes\n");
+    }
+    for index in &catalog.indexes {
+        out.push_str(&terminate(&index.definition));
+        out.push('\n');
+    }
+
+    if opts.include_grants && !catalog.grants.is_empty() {
+        out.push_str("\n-- Grants\n");
+        for grant in &catalog.grants {
+            let grantee = if grant.grantee.eq_ignore_ascii_case("PUBLIC") {
+                "PUBLIC".to_string()
+            } else {
+                quote_ident(&grant.grantee)
+            };
+            out.push_str(&format!(
+                "GRANT {} ON TABLE {} TO {};\n",
+                grant.privileges,
+                qualified(&grant.schema, &grant.name),
+                grantee
+            ));
+        }
+    }
+
+    out
+}
+
+/// Render a `CREATE TABLE` statement.
+///
+/// Foreign keys are inlined only when `include_foreign_keys` is set; a full
+/// dump adds them afterwards with `ALTER TABLE`. Partitions are created with
+/// `PARTITION OF` and take their columns from the parent.
+pub fn render_create_table(table: &DdlTable, include_foreign_keys: bool) -> String {
+    let mut lines: Vec<String> = match table.partition_of {
+        Some(_) => Vec::new(),
+        None => table.columns.iter().map(render_column).collect(),
+    };
+    lines.extend(
+        table
+            .constraints
+            .iter()
+            .filter(|c| include_foreign_keys || !c.is_foreign_key())
+            .map(|c| format!("CONSTRAINT {} {}", quote_ident(&c.name), c.definition)),
+    );
+
+    let name = qualified(&table.schema, &table.name);
+    let mut out = match &table.partition_of {
+        Some((schema, parent, bound)) if lines.is_empty() => {
+            format!("CREATE TABLE {} PARTITION OF {} {}", name, qualified(schema, parent), bound)
+        }
+        Some((schema, parent, bound)) => format!(
+            "CREATE TABLE {} PARTITION OF {} (\n    {}\n) {}",
+            name,
+            qualified(schema, parent),
+            lines.join(",\n    "),
+            bound
+        ),
+        None => format!("CREATE TABLE {} (\n    {}\n)", name, lines.join(",\n    ")),
+    };
+    if let Some(key) = &table.partition_key {
+        out.push_str(&format!(" PARTITION BY {}", key));
+    }
+    out.push(';');
+    out
+}
+
+/// Render a `CREATE VIEW` or `CREATE MATERIALIZED VIEW` statement.
+///
+/// Materialized views are created `WITH NO DATA`, matching `pg_dump`.
+pub fn render_create_view(view: &DdlView) -> String {
+    let name = qualified(&view.schema, &view.name);
+    let query = view_query(view);
+    if view.is_materialized {
+        format!("CREATE MATERIALIZED VIEW {} AS\n{}\nWITH NO DATA;", name, query)
+    } else {
+        format!("CREATE OR REPLACE VIEW {} AS\n{};", name, query)
+    }
+}
+
+/// Render a single view's definition for display.
+///
+/// Plain views render as in [`render_create_view`]. A materialized view gets a
+/// leading comment and `WITH DATA` or `WITH NO DATA` matching whether it is
+/// currently populated, rather than the dump's unconditional `WITH NO DATA`.
+pub fn render_view_definition(view: &DdlView) -> String {
+    if !view.is_materialized {
+        return render_create_view(view);
+    }
+    let (state, with_data) = if view.is_populated {
+        ("populated", "WITH DATA")
+    } else {
+        ("not populated; REFRESH MATERIALIZED VIEW to load it", "WITH NO DATA")
+    };
+    format!(
+        "-- Materialized view ({})\nCREATE MATERIALIZED VIEW {} AS\n{}\n{};",
+        state,
+        qualified(&view.schema, &view.name),
+        view_query(view),
+        with_data
+    )
+}
+
+/// A view's query without surrounding whitespace or the trailing semicolon.
+fn view_query(view: &DdlView) -> &str {
+    view.definition.trim().trim_end_matches(';').trim_end()
+}
+
+/// Render a single column definition.
+fn render_column(column: &DdlColumn) -> String {
+    let mut def = format!("{} {}", quote_ident(&column.name), column.data_type);
+    match (column.generated.as_str(), &column.default_value) {
+        ("s", Some(expr)) => def.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expr)),
+        (_, Some(expr)) => def.push_str(&format!(" DEFAULT {}", expr)),
+        _ => {}
+    }
+    match column.identity.as_str() {
+        "a" => def.push_str(" GENERATED ALWAYS AS IDENTITY"),
+        "d" => def.push_str(" GENERATED BY DEFAULT AS IDENTITY"),
+        _ => {}
+    }
+    if column.not_null {
+        def.push_str(" NOT NULL");
+    }
+    def
+}
+
+/// Order views so each one follows the views it selects from.
+fn order_views(views: &[DdlView]) -> Vec<&DdlView> {
+    order_by_dependency(
+        views,
+        |v| (v.schema.as_str(), v.name.as_str()),
+        |v| v.depends_on.iter().map(|(s, n)| (s.as_str(), n.as_str())).collect(),
+    )
+}
+
+/// Order tables so each partition follows its parent.
+fn order_tables(tables: &[DdlTable]) -> Vec<&DdlTable> {
+    order_by_dependency(
+        tables,
+        |t| (t.schema.as_str(), t.name.as_str()),
+        |t| t.partition_of.iter().map(|(s, n, _)| (s.as_str(), n.as_str())).collect(),
+    )
+}
+
+/// Order objects so each one follows the objects it depends on.
+///
+/// Dependencies outside `items` are ignored; otherwise catalog order is kept.
+fn order_by_dependency<'a, T>(
+    items: &'a [T],
+    key: impl Fn(&'a T) -> (&'a str, &'a str),
+    depends_on: impl Fn(&'a T) -> Vec<(&'a str, &'a str)>,
+) -> Vec<&'a T> {
+    let names: HashSet<(&str, &str)> = items.iter().map(&key).collect();
+    let mut emitted: HashSet<(&str, &str)> = HashSet::new();
+    let mut ordered = Vec::with_capacity(items.len());
+
+    while ordered.len() < items.len() {
+        let before = ordered.len();
+        for item in items {
+            if emitted.contains(&key(item)) {
+                continue;
+            }
+            let ready = depends_on(item)
+                .into_iter()
+                .all(|dep| !names.contains(&dep) || emitted.contains(&dep));
+            if ready {
+                emitted.insert(key(item));
+                ordered.push(item);
+            }
+        }
+        if ordered.len() == before {
+            // Dependency cycle (not possible for valid catalogs); keep catalog order
+            ordered.extend(items.iter().filter(|&i| !emitted.contains(&key(i))));
+            break;
+        }
+    }
+
+    ordered
+}
+
+/// Append a semicolon to a statement unless it already ends with one.
+fn terminate(statement: &str) -> String {
+    let trimmed = statement.trim_end();
+    if trimmed.ends_with(';') {
+        trimmed.to_string()
+    } else {
+        format!("{};", trimmed)
+    }
+}
+
+/// Schema-qualify and quote an object name.
+fn qualified(schema: &str, name: &str) -> String {
+    format!("{}.{}", quote_ident(schema), quote_ident(name))
+}
+
+/// Quote a string as a SQL literal.
+pub(crate) fn quote_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Reserved keywords that must be quoted when used as identifiers.
+const RESERVED_KEYWORDS: &[&str] = &[
+    "all",
+    "analyse",
+    "analyze",
+    "and",
+    "any",
+    "array",
+    "as",
+    "asc",
+    "asymmetric",
+    "authorization",
+    "binary",
+    "both",
+    "case",
+    "cast",
+    "check",
+    "collate",
+    "collation",
+    "column",
+    "concurrently",
+    "constraint",
+    "create",
+    "cross",
+    "current_catalog",
+    "current_date",
+    "current_role",
+    "current_schema",
+    "current_time",
+    "current_timestamp",
+    "current_user",
+    "default",
+    "deferrable",
+    "desc",
+    "distinct",
+    "do",
+    "else",
+    "end",
+    "except",
+    "false",
+    "fetch",
+    "for",
+    "foreign",
+    "freeze",
+    "from",
+    "full",
+    "grant",
+    "group",
+    "having",
+    "ilike",
+    "in",
+    "initially",
+    "inner",
+    "intersect",
+    "into",
+    "is",
+    "isnull",
+    "join",
+    "lateral",
+    "leading",
+    "left",
+    "like",
+    "limit",
+    "localtime",
+    "localtimestamp",
+    "natural",
+    "not",
+    "notnull",
+    "null",
+    "offset",
+    "on",
+    "only",
+    "or",
+    "order",
+    "outer",
+    "overlaps",
+    "placing",
+    "primary",
+    "references",
+    "returning",
+    "right",
+    "select",
+    "session_user",
+    "similar",
+    "some",
+    "symmetric",
+    "system_user",
+    "table",
+    "tablesample",
+    "then",
+    "to",
+    "trailing",
+    "true",
+    "union",
+    "unique",
+    "user",
+    "using",
+    "variadic",
+    "verbose",
+    "when",
+    "where",
+    "window",
+    "with",
+];
+
+/// Quote an identifier if it isn't a plain lowercase name.
+///
+/// Mirrors PostgreSQL's `quote_ident`: names that are lowercase, start with a
+/// letter or underscore, and aren't reserved keywords are left bare.
+pub fn quote_ident(ident: &str) -> String {
+    let mut chars = ident.chars();
+    let is_plain = chars.next().is_some_and(|c| c.is_ascii_lowercase() || c == '_')
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '$')
+        && !RESERVED_KEYWORDS.contains(&ident);
+
+    if is_plain {
+        ident.to_string()
+    } else {
+        format!("\"{}\"", ident.replace('"', "\"\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::services::QueryService;
+    use crate::test_support::local_pool;
+
+    fn column(name: &str, data_type: &str, not_null: bool) -> DdlColumn {
+        DdlColumn {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            not_null,
+            default_value: None,
+            identity: String::new(),
+            generated: String::new(),
+            comment: None,
+        }
+    }
+
+    fn view(name: &str, definition: &str, depends_on: &[&str]) -> DdlView {
+        DdlView {
+            schema: "app".to_string(),
+            name: name.to_string(),
+            is_materialized: false,
+            is_populated: true,
+            definition: definition.to_string(),
+            depends_on: depends_on.iter().map(|d| ("app".to_string(), d.to_string())).collect(),
+            comment: None,
+        }
+    }
+
+    /// A small schema: an enum used by a table, a serial sequence, a function,
+    /// a foreign key, and two dependent views listed out of order.
+    fn sample_catalog() -> DdlCatalog {
+        let mut id = column("id", "integer", true);
+        id.default_value = Some("nextval('app.orders_id_seq'::regclass)".to_string());
+
+        DdlCatalog {
+            schemas: vec![("app".to_string(), Some("Application data".to_string()))],
+            types: vec![
+                DdlType {
+                    schema: "app".to_string(),
+                    name: "positive_int".to_string(),
+                    kind: DdlTypeKind::Domain,
+                    body: "AS integer CONSTRAINT positive CHECK (VALUE > 0)".to_string(),
+                    comment: None,
+                },
+                DdlType {
+                    schema: "app".to_string(),
+                    name: "order_status".to_string(),
+                    kind: DdlTypeKind::Enum,
+                    body: "AS ENUM ('new', 'shipped')".to_string(),
+                    comment: None,
+                },
+            ],
+            sequences: vec![DdlSequence {
+                schema: "app".to_string(),
+                name: "orders_id_seq".to_string(),
+                data_type: "integer".to_string(),
+                start: 1,
+                increment: 1,
+                min_value: 1,
+                max_value: 2147483647,
+                cache: 1,
+                cycle: false,
+                owned_by: Some(("app".to_string(), "orders".to_string(), "id".to_string())),
+            }],
+            functions: vec![DdlFunction {
+                schema: "app".to_string(),
+                name: "order_count".to_string(),
+                arguments: String::new(),
+                is_procedure: false,
+                definition: "CREATE OR REPLACE FUNCTION app.order_count()\n RETURNS bigint\n \
+                             LANGUAGE sql\nAS $function$ SELECT count(*) FROM app.orders; \
+                             $function$\n"
+                    .to_string(),
+                comment: None,
+            }],
+            tables: vec![
+                DdlTable {
+                    schema: "app".to_string(),
+                    name: "orders".to_string(),
+                    columns: vec![
+                        id,
+                        column("customer_id", "integer", true),
+                        column("status", "app.order_status", true),
+                        column("order", "text", false),
+                    ],
+                    constraints: vec![
+                        DdlConstraint {
+                            name: "orders_pkey".to_string(),
+                            kind: 'p',
+                            definition: "PRIMARY KEY (id)".to_string(),
+                        },
+                        DdlConstraint {
+                            name: "orders_customer_id_fkey".to_string(),
+                            kind: 'f',
+                            definition: "FOREIGN KEY (customer_id) REFERENCES app.customers(id)"
+                                .to_string(),
+                        },
+                    ],
+                    partition_key: None,
+                    partition_of: None,
+                    comment: Some("Customer's orders".to_string()),
+                },
+                DdlTable {
+                    schema: "app".to_string(),
+                    name: "customers".to_string(),
+                    columns: vec![column("id", "app.positive_int", true)],
+                    constraints: vec![DdlConstraint {
+                        name: "customers_pkey".to_string(),
+                        kind: 'p',
+                        definition: "PRIMARY KEY (id)".to_string(),
+                    }],
+                    partition_key: None,
+                    partition_of: None,
+                    comment: None,
+                },
+            ],
+            views: vec![
+                view(
+                    "big_orders",
+                    " SELECT id\n   FROM app.open_orders\n  WHERE id > 100;",
+                    &["open_orders"],
+                ),
+                view("open_orders", " SELECT id\n   FROM app.orders;", &[]),
+            ],
+            indexes: vec![DdlIndex {
+                schema: "app".to_string(),
+                table: "orders".to_string(),
+                name: "orders_status_idx".to_string(),
+                definition: "CREATE INDEX orders_status_idx ON app.orders USING btree (status)"
+                    .to_string(),
+            }],
+            grants: vec![DdlGrant {
+                schema: "app".to_string(),
+                name: "orders".to_string(),
+                grantee: "PUBLIC".to_string(),
+                privileges: "SELECT".to_string(),
+            }],
+        }
+    }
+
+    fn position(haystack: &str, needle: &str) -> usize {
+        haystack.find(needle).unwrap_or_else(|| panic!("missing {needle:?} in:\n{haystack}"))
+    }
+
+    #[test]
+    fn test_dump_orders_types_before_tables_that_use_them() {
+        let dump = render_dump(&sample_catalog(), &DdlDumpOptions::default());
+
+        let schema = position(&dump, "CREATE SCHEMA IF NOT EXISTS app;");
+        let enum_type = position(&dump, "CREATE TYPE app.order_status");
+        let domain = position(&dump, "CREATE DOMAIN app.positive_int");
+        let sequence = position(&dump, "CREATE SEQUENCE app.orders_id_seq");
+        let function = position(&dump, "CREATE OR REPLACE FUNCTION app.order_count()");
+        let orders = position(&dump, "CREATE TABLE app.orders");
+        let customers = position(&dump, "CREATE TABLE app.customers");
+        let fk = position(&dump, "ADD CONSTRAINT orders_customer_id_fkey");
+        let index = position(&dump, "CREATE INDEX orders_status_idx");
+
+        assert!(schema < enum_type);
+        assert!(enum_type < domain, "enums come before domains");
+        assert!(domain < sequence && sequence < orders && sequence < customers);
+        assert!(orders < fk && customers < fk, "foreign keys follow every table");
+        assert!(orders < function, "functions follow the tables they may reference");
+        assert!(fk < index && function < index);
+        assert!(dump.contains("ALTER SEQUENCE app.orders_id_seq OWNED BY app.orders.id;"));
+    }
+
+    #[test]
+    fn test_dump_orders_dependent_views() {
+        let dump = render_dump(&sample_catalog(), &DdlDumpOptions::default());
+
+        let open_orders = position(&dump, "VIEW app.open_orders AS");
+        let big_orders = position(&dump, "VIEW app.big_orders AS");
+        assert!(open_orders < big_orders);
+        assert!(!dump.contains(";;"), "view definitions keep a single terminator");
+    }
+
+    #[test]
+    fn test_dump_is_structurally_valid_sql() {
+        let opts = DdlDumpOptions { include_grants: true, ..Default::default() };
+        let dump = render_dump(&sample_catalog(), &opts);
+        let statements = QueryService::split_statements(&dump);
+
+        // SET, schema + comment, 2 types, sequence, 2 tables + comment, ownership,
+        // FK, function, 2 views, index, grant
+        assert_eq!(statements.len(), 16, "unexpected statements: {statements:#?}");
+        for statement in &statements {
+            // Section header comments precede some statements
+            let first_word = statement
+                .lines()
+                .filter(|line| !line.trim_start().starts_with("--"))
+                .flat_map(str::split_whitespace)
+                .next()
+                .unwrap_or_default();
+            assert!(
+                ["SET", "CREATE", "ALTER", "COMMENT", "GRANT"].contains(&first_word),
+                "unexpected statement start: {statement}"
+            );
+            let opens = statement.matches('(').count();
+            let closes = statement.matches(')').count();
+            assert_eq!(opens, closes, "unbalanced parentheses: {statement}");
+        }
+    }
+
+    #[test]
+    fn test_dump_respects_comment_and_grant_options() {
+        let catalog = sample_catalog();
+
+        let with_comments = render_dump(&catalog, &DdlDumpOptions::default());
+        assert!(with_comments.contains("COMMENT ON TABLE app.orders IS 'Customer''s orders';"));
+        assert!(!with_comments.contains("GRANT"));
+
+        let opts =
+            DdlDumpOptions { include_comments: false, include_grants: true, ..Default::default() };
+        let bare = render_dump(&catalog, &opts);
+        assert!(!bare.contains("COMMENT ON"));
+        assert!(bare.contains("GRANT SELECT ON TABLE app.orders TO PUBLIC;"));
+    }
+
+    #[test]
+    fn test_create_table_inlines_foreign_keys_when_requested() {
+        let catalog = sample_catalog();
+        let orders = &catalog.tables[0];
+
+        let standalone = render_create_table(orders, true);
+        assert!(standalone.contains("CONSTRAINT orders_customer_id_fkey FOREIGN KEY"));
+        assert!(standalone.contains("\"order\" text"));
+        assert!(standalone
+            .contains("id integer DEFAULT nextval('app.orders_id_seq'::regclass) NOT NULL"));
+
+        let in_dump = render_create_table(orders, false);
+        assert!(!in_dump.contains("FOREIGN KEY"));
+    }
+
+    #[test]
+    fn test_dump_creates_partitions_after_their_parent() {
+        let mut catalog = sample_catalog();
+        let mut events = catalog.tables[1].clone();
+        events.name = "events".to_string();
+        events.columns = vec![column("at", "date", true)];
+        events.constraints.clear();
+
+        let mut events_2024 = events.clone();
+        events_2024.name = "events_2024".to_string();
+        events_2024.partition_of = Some((
+            "app".to_string(),
+            "events".to_string(),
+            "FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')".to_string(),
+        ));
+        events.partition_key = Some("RANGE (at)".to_string());
+        catalog.tables = vec![events_2024, events];
+
+        let dump = render_dump(&catalog, &DdlDumpOptions::default());
+        let parent = position(
+            &dump,
+            "CREATE TABLE app.events (\n    at date NOT NULL\n) PARTITION BY RANGE (at);",
+        );
+        let partition = position(
+            &dump,
+            "CREATE TABLE app.events_2024 PARTITION OF app.events \
+             FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');",
+        );
+        assert!(parent < partition);
+    }
+
+    #[test]
+    fn test_render_generated_and_identity_columns() {
+        let mut total = column("total", "numeric", false);
+        total.generated = "s".to_string();
+        total.default_value = Some("(price * qty)".to_string());
+        assert_eq!(
+            render_column(&total),
+            "total numeric GENERATED ALWAYS AS ((price * qty)) STORED"
+        );
+
+        let mut id = column("id", "bigint", true);
+        id.identity = "a".to_string();
+        assert_eq!(render_column(&id), "id bigint GENERATED ALWAYS AS IDENTITY NOT NULL");
+    }
+
+    #[test]
+    fn test_view_definition_contains_select() {
+        let catalog = sample_catalog();
+        let definition = render_create_view(&catalog.views[1]);
+        assert_eq!(
+            definition,
+            "CREATE OR REPLACE VIEW app.open_orders AS\nSELECT id\n   FROM app.orders;"
+        );
+
+        let mut materialized = catalog.views[0].clone();
+        materialized.is_materialized = true;
+        let definition = render_create_view(&materialized);
+        assert!(definition.starts_with("CREATE MATERIALIZED VIEW app.big_orders AS\nSELECT id"));
+        assert!(definition.ends_with("WITH NO DATA;"));
+    }
+
+    #[test]
+    fn test_view_definition_reports_materialized_data_state() {
+        let catalog = sample_catalog();
+        assert_eq!(
+            render_view_definition(&catalog.views[1]),
+            render_create_view(&catalog.views[1])
+        );
+
+        let mut materialized = catalog.views[1].clone();
+        materialized.is_materialized = true;
+        let populated = render_view_definition(&materialized);
+        assert_eq!(
+            populated,
+            "-- Materialized view (populated)\nCREATE MATERIALIZED VIEW app.open_orders AS\n\
+             SELECT id\n   FROM app.orders\nWITH DATA;"
+        );
+
+        materialized.is_populated = false;
+        let empty = render_view_definition(&materialized);
+        assert!(empty.starts_with("-- Materialized view (not populated;"));
+        assert!(empty.ends_with("\nWITH NO DATA;"));
+
+        // Dumps always create materialized views empty
+        assert!(render_create_view(&materialized).ends_with("WITH NO DATA;"));
+    }
+
+    #[test]
+    fn test_quote_ident() {
+        assert_eq!(quote_ident("orders"), "orders");
+        assert_eq!(quote_ident("_tmp1"), "_tmp1");
+        assert_eq!(quote_ident("user"), "\"user\"");
+        assert_eq!(quote_ident("OrderItems"), "\"OrderItems\"");
+        assert_eq!(quote_ident("my table"), "\"my table\"");
+        assert_eq!(quote_ident("a\"b"), "\"a\"\"b\"");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_table_ddl_keeps_column_order_and_keys() {
+        let pool = local_pool(4).await;
+
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let qualified = quote_ident(&schema);
+        let conn = pool.get().await.unwrap();
+        conn.simple_execute(&format!(
+            "CREATE SCHEMA {qualified};
+             CREATE TABLE {qualified}.customers (id integer PRIMARY KEY);
+             CREATE TABLE {qualified}.orders (
+                 id integer PRIMARY KEY,
+                 status text NOT NULL DEFAULT 'open',
+                 dropped integer,
+                 customer_id integer REFERENCES {qualified}.customers (id)
+             );
+             ALTER TABLE {qualified}.orders DROP COLUMN dropped;
+             ALTER TABLE {qualified}.orders ADD COLUMN note text;
+             CREATE TABLE {qualified}.events (at date NOT NULL) PARTITION BY RANGE (at);
+             CREATE TABLE {qualified}.events_2024 PARTITION OF {qualified}.events
+                 FOR VALUES FROM ('2024-01-01') TO ('2025-01-01');"
+        ))
+        .await
+        .unwrap();
+
+        let ddl = SchemaService::get_table_ddl(&conn, &schema, "orders").await;
+        let missing = SchemaService::get_table_ddl(&conn, &schema, "missing").await;
+        let events = SchemaService::get_table_ddl(&conn, &schema, "events").await;
+        let events_2024 = SchemaService::get_table_ddl(&conn, &schema, "events_2024").await;
+        conn.execute(format!("DROP SCHEMA {qualified} CASCADE").as_str(), &[]).await.unwrap();
+
+        let ddl = ddl.unwrap();
+        assert!(ddl.starts_with(&format!("CREATE TABLE {qualified}.orders (")));
+        assert!(!ddl.contains("dropped"));
+        assert!(position(&ddl, "id integer NOT NULL") < position(&ddl, "status text"));
+        assert!(position(&ddl, "status text") < position(&ddl, "customer_id integer"));
+        assert!(position(&ddl, "customer_id integer") < position(&ddl, "note text"));
+        assert!(ddl.contains("status text DEFAULT 'open'::text NOT NULL"));
+        assert!(ddl.contains("CONSTRAINT orders_pkey PRIMARY KEY (id)"));
+        assert!(ddl.contains("FOREIGN KEY (customer_id) REFERENCES"));
+        assert!(missing.is_err());
+        assert!(events.unwrap().contains("PARTITION BY RANGE (at)"));
+        assert!(events_2024.unwrap().contains(&format!(
+            "PARTITION OF {qualified}.events FOR VALUES FROM ('2024-01-01') TO ('2025-01-01')"
+        )));
+    }
+}
@@ -0,0 +1,497 @@
+//! Editing result cells through generated `UPDATE` statements.
+//!
+//! A result set is editable when every column that comes from a table comes
+//! from the same one, that table has a primary key, and all key columns are
+//! in the result. [`EditTarget`] maps result columns back to the table and
+//! builds `UPDATE ... WHERE <pk> = ...` for a changed cell. The statement is
+//! handed back for review rather than run directly.
+//!
+//! [`InsertTarget`] maps result columns back to their table the same way,
+//! without needing a primary key, to copy rows as `INSERT` statements.
+
+use std::collections::HashMap;
+
+use crate::error::TuskError;
+use crate::models::export::is_numeric_type;
+use crate::models::query::ColumnInfo;
+use crate::services::connection::PooledConnection;
+use crate::services::ddl::{quote_ident, quote_literal};
+use crate::services::schema::SchemaService;
+
+/// The table behind an editable result set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditTarget {
+    /// Schema name
+    pub schema: String,
+    /// Table name
+    pub table: String,
+    /// Table column behind each result column (None for computed columns)
+    columns: Vec<Option<String>>,
+    /// Result column holding each primary key column, in key order
+    key: Vec<usize>,
+}
+
+impl EditTarget {
+    /// Find the one table the result's columns come from.
+    ///
+    /// Computed columns are ignored; columns from two different tables
+    /// (e.g. a join) make the result uneditable.
+    pub fn source_table(columns: &[ColumnInfo]) -> Result<u32, TuskError> {
+        match source_tables(columns).as_slice() {
+            [] => Err(refused(
+                "Results can't be edited: no column comes directly from a table",
+                "Select columns from a single table to edit its rows",
+            )),
+            [table] => Ok(*table),
+            _ => Err(refused(
+                "Results can't be edited: columns come from more than one table",
+                "Select columns from a single table to edit its rows",
+            )),
+        }
+    }
+
+    /// Map result columns onto a table's columns and primary key.
+    ///
+    /// `attributes` maps attribute numbers to column names and `primary_key`
+    /// lists the key's attribute numbers in key order.
+    pub fn new(
+        schema: impl Into<String>,
+        table: impl Into<String>,
+        columns: &[ColumnInfo],
+        attributes: &HashMap<i16, String>,
+        primary_key: &[i16],
+    ) -> Result<Self, TuskError> {
+        let schema = schema.into();
+        let table = table.into();
+        let table_oid = Self::source_table(columns)?;
+
+        if primary_key.is_empty() {
+            return Err(refused(
+                format!("Results can't be edited: {}.{} has no primary key", schema, table),
+                "Rows are updated by primary key; add one to the table to edit it here",
+            ));
+        }
+
+        let attnums: Vec<Option<i16>> = columns
+            .iter()
+            .map(|c| c.column_id.filter(|_| c.table_oid == Some(table_oid)))
+            .collect();
+        let key = primary_key
+            .iter()
+            .map(|attnum| {
+                attnums.iter().position(|a| *a == Some(*attnum)).ok_or_else(|| {
+                    let name = attributes.get(attnum).map(String::as_str).unwrap_or("?");
+                    refused(
+                        format!(
+                            "Results can't be edited: primary key column {} is not in the result",
+                            name
+                        ),
+                        "Add the primary key columns to the SELECT list",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let columns =
+            attnums.iter().map(|attnum| attnum.and_then(|a| attributes.get(&a).cloned())).collect();
+
+        Ok(Self { schema, table, columns, key })
+    }
+
+    /// Whether a result column maps to a table column.
+    pub fn is_editable(&self, column: usize) -> bool {
+        self.columns.get(column).is_some_and(Option::is_some)
+    }
+
+    /// Build the `UPDATE` that sets one cell of `row` to `value`.
+    ///
+    /// `row` holds the result row as displayed, with NULL cells as None. The
+    /// row is matched on its primary key values; `None` sets the cell to NULL.
+    pub fn update_sql(
+        &self,
+        row: &[Option<String>],
+        column: usize,
+        value: Option<&str>,
+    ) -> Result<String, TuskError> {
+        let Some(Some(target)) = self.columns.get(column) else {
+            return Err(refused(
+                "This column is computed and can't be edited",
+                "Only columns that come directly from the table can be edited",
+            ));
+        };
+
+        let conditions = self
+            .key
+            .iter()
+            .map(|&index| {
+                let name = self.columns[index].as_deref().unwrap_or_default();
+                match row.get(index) {
+                    Some(Some(key_value)) => {
+                        Ok(format!("{} = {}", quote_ident(name), quote_literal(key_value)))
+                    }
+                    _ => Err(refused(
+                        format!("Row has no value for primary key column {}", name),
+                        "Re-run the query to refresh the row",
+                    )),
+                }
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(format!(
+            "UPDATE {}.{} SET {} = {} WHERE {};",
+            quote_ident(&self.schema),
+            quote_ident(&self.table),
+            quote_ident(target),
+            value.map(quote_literal).unwrap_or_else(|| "NULL".to_string()),
+            conditions.join(" AND ")
+        ))
+    }
+}
+
+/// The table behind result rows copied as `INSERT` statements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsertTarget {
+    /// Schema name
+    pub schema: String,
+    /// Table name
+    pub table: String,
+    /// Table column behind each result column (None for computed columns)
+    columns: Vec<Option<String>>,
+    /// Type name of each result column
+    types: Vec<String>,
+}
+
+impl InsertTarget {
+    /// Find the one table the result's columns come from.
+    ///
+    /// Computed columns are ignored; columns from two different tables
+    /// (e.g. a join) can't be copied as `INSERT`s.
+    pub fn source_table(columns: &[ColumnInfo]) -> Result<u32, TuskError> {
+        match source_tables(columns).as_slice() {
+            [] => Err(refused(
+                "Rows can't be copied as INSERT: no column comes directly from a table",
+                "Select columns from a single table to copy its rows",
+            )),
+            [table] => Ok(*table),
+            _ => Err(refused(
+                "Rows can't be copied as INSERT: columns come from more than one table",
+                "Select columns from a single table to copy its rows",
+            )),
+        }
+    }
+
+    /// Map result columns onto a table's columns.
+    ///
+    /// `attributes` maps attribute numbers to column names.
+    pub fn new(
+        schema: impl Into<String>,
+        table: impl Into<String>,
+        columns: &[ColumnInfo],
+        attributes: &HashMap<i16, String>,
+    ) -> Result<Self, TuskError> {
+        let table_oid = Self::source_table(columns)?;
+        let names: Vec<Option<String>> = columns
+            .iter()
+            .map(|c| {
+                c.column_id
+                    .filter(|_| c.table_oid == Some(table_oid))
+                    .and_then(|attnum| attributes.get(&attnum).cloned())
+            })
+            .collect();
+        if names.iter().all(Option::is_none) {
+            return Err(refused(
+                "Rows can't be copied as INSERT: the source table no longer has these columns",
+                "Re-run the query",
+            ));
+        }
+
+        Ok(Self {
+            schema: schema.into(),
+            table: table.into(),
+            columns: names,
+            types: columns.iter().map(|c| c.type_name.clone()).collect(),
+        })
+    }
+
+    /// Build one `INSERT` per row, leaving out computed columns.
+    ///
+    /// `rows` hold result rows as displayed, with NULL cells as None, which
+    /// are written as `NULL`. Numbers and booleans are written bare and
+    /// everything else as a quoted literal.
+    pub fn insert_sql(&self, rows: &[Vec<Option<String>>]) -> String {
+        let targets: Vec<(usize, &str)> = self
+            .columns
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| name.as_deref().map(|name| (index, name)))
+            .collect();
+        let column_list: Vec<String> = targets.iter().map(|(_, name)| quote_ident(name)).collect();
+        let table = format!("{}.{}", quote_ident(&self.schema), quote_ident(&self.table));
+
+        rows.iter()
+            .map(|row| {
+                let values: Vec<String> = targets
+                    .iter()
+                    .map(|&(index, _)| match row.get(index).and_then(Option::as_deref) {
+                        Some(value) => self.literal(index, value),
+                        None => "NULL".to_string(),
+                    })
+                    .collect();
+                format!(
+                    "INSERT INTO {} ({}) VALUES ({});\n",
+                    table,
+                    column_list.join(", "),
+                    values.join(", ")
+                )
+            })
+            .collect()
+    }
+
+    /// SQL literal for a value of a result column.
+    fn literal(&self, column: usize, value: &str) -> String {
+        let type_name = self.types.get(column).map(String::as_str).unwrap_or_default();
+        let bare = match type_name {
+            "bool" => matches!(value, "true" | "false"),
+            // money and NaN/Infinity need quoting
+            "money" => false,
+            _ if is_numeric_type(type_name) => value.parse::<f64>().is_ok_and(f64::is_finite),
+            _ => false,
+        };
+        if bare {
+            value.to_string()
+        } else {
+            quote_literal(value)
+        }
+    }
+}
+
+/// Tables a result's columns come from, in column order, ignoring computed columns.
+fn source_tables(columns: &[ColumnInfo]) -> Vec<u32> {
+    let mut tables = Vec::new();
+    for table in columns.iter().filter_map(|c| c.table_oid.filter(|&oid| oid != 0)) {
+        if !tables.contains(&table) {
+            tables.push(table);
+        }
+    }
+    tables
+}
+
+/// Name, primary key, and columns of a table.
+struct SourceTable {
+    schema: String,
+    name: String,
+    /// Primary key attribute numbers in key order (empty without a key)
+    primary_key: Vec<i16>,
+    /// Column names by attribute number
+    attributes: HashMap<i16, String>,
+}
+
+/// Look up a table by OID, or None if it no longer exists.
+async fn load_source_table(
+    conn: &PooledConnection,
+    table_oid: u32,
+) -> Result<Option<SourceTable>, TuskError> {
+    let table = conn
+        .query(
+            r#"
+            SELECT
+                n.nspname AS schema,
+                c.relname AS name,
+                COALESCE(k.conkey, '{}') AS primary_key
+            FROM pg_catalog.pg_class c
+            JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+            LEFT JOIN pg_catalog.pg_constraint k
+                ON k.conrelid = c.oid AND k.contype = 'p'
+            WHERE c.oid = $1
+            "#,
+            &[&table_oid],
+        )
+        .await?;
+    let Some(table) = table.first() else {
+        return Ok(None);
+    };
+
+    let attributes: HashMap<i16, String> = conn
+        .query(
+            r#"
+            SELECT a.attnum, a.attname::text AS name
+            FROM pg_catalog.pg_attribute a
+            WHERE a.attrelid = $1
+              AND a.attnum > 0
+              AND NOT a.attisdropped
+            "#,
+            &[&table_oid],
+        )
+        .await?
+        .into_iter()
+        .map(|row| (row.get("attnum"), row.get("name")))
+        .collect();
+
+    Ok(Some(SourceTable {
+        schema: table.get("schema"),
+        name: table.get("name"),
+        primary_key: table.get("primary_key"),
+        attributes,
+    }))
+}
+
+impl SchemaService {
+    /// Resolve the table and primary key behind a result set for editing.
+    ///
+    /// Fails with an explanation when the result can't be edited.
+    pub async fn load_edit_target(
+        conn: &PooledConnection,
+        columns: &[ColumnInfo],
+    ) -> Result<EditTarget, TuskError> {
+        let table_oid = EditTarget::source_table(columns)?;
+        let Some(table) = load_source_table(conn, table_oid).await? else {
+            return Err(refused(
+                "Results can't be edited: the source table no longer exists",
+                "Re-run the query",
+            ));
+        };
+        EditTarget::new(table.schema, table.name, columns, &table.attributes, &table.primary_key)
+    }
+
+    /// Resolve the table behind result rows for copying them as `INSERT`s.
+    ///
+    /// Fails with an explanation when the rows don't come from one table.
+    pub async fn load_insert_target(
+        conn: &PooledConnection,
+        columns: &[ColumnInfo],
+    ) -> Result<InsertTarget, TuskError> {
+        let table_oid = InsertTarget::source_table(columns)?;
+        let Some(table) = load_source_table(conn, table_oid).await? else {
+            return Err(refused(
+                "Rows can't be copied as INSERT: the source table no longer exists",
+                "Re-run the query",
+            ));
+        };
+        InsertTarget::new(table.schema, table.name, columns, &table.attributes)
+    }
+}
+
+/// Error explaining why a result or cell can't be edited or copied.
+fn refused(message: impl Into<String>, hint: &str) -> TuskError {
+    TuskError::query(message, None, Some(hint.into()), None, None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, table_oid: Option<u32>, column_id: Option<i16>) -> ColumnInfo {
+        ColumnInfo {
+            name: name.to_string(),
+            type_oid: 0,
+            type_name: "text".to_string(),
+            table_oid,
+            column_id,
+        }
+    }
+
+    fn attributes(names: &[(i16, &str)]) -> HashMap<i16, String> {
+        names.iter().map(|(attnum, name)| (*attnum, name.to_string())).collect()
+    }
+
+    fn cells(values: &[Option<&str>]) -> Vec<Option<String>> {
+        values.iter().map(|v| v.map(String::from)).collect()
+    }
+
+    #[test]
+    fn test_update_with_composite_key() {
+        // SELECT note, region AS r, now(), id FROM sales."Order Lines"
+        let columns = [
+            column("note", Some(42), Some(3)),
+            column("r", Some(42), Some(1)),
+            column("now", None, None),
+            column("id", Some(42), Some(2)),
+        ];
+        let attributes = attributes(&[(1, "region"), (2, "id"), (3, "note")]);
+        let target = EditTarget::new("sales", "Order Lines", &columns, &attributes, &[1, 2])
+            .expect("editable");
+
+        assert!(target.is_editable(0));
+        assert!(!target.is_editable(2));
+
+        let row = cells(&[Some("old"), Some("eu"), Some("2024-01-01"), Some("7")]);
+        assert_eq!(
+            target.update_sql(&row, 0, Some("it's done")).unwrap(),
+            "UPDATE sales.\"Order Lines\" SET note = 'it''s done' WHERE region = 'eu' AND id = '7';"
+        );
+        // NULL values and backslashes
+        assert_eq!(
+            target.update_sql(&row, 0, None).unwrap(),
+            "UPDATE sales.\"Order Lines\" SET note = NULL WHERE region = 'eu' AND id = '7';"
+        );
+        assert_eq!(
+            target.update_sql(&row, 0, Some(r"C:\tmp")).unwrap(),
+            "UPDATE sales.\"Order Lines\" SET note = 'C:\\tmp' WHERE region = 'eu' AND id = '7';"
+        );
+        // Computed columns can't be edited
+        assert!(target.update_sql(&row, 2, Some("x")).is_err());
+        // A key value the row lacks can't be matched
+        let row = cells(&[Some("old"), None, None, Some("7")]);
+        assert!(target.update_sql(&row, 0, Some("x")).is_err());
+    }
+
+    #[test]
+    fn test_uneditable_results_are_refused() {
+        let attributes = attributes(&[(1, "id"), (2, "name")]);
+
+        // Join across two tables
+        let joined = [column("id", Some(1), Some(1)), column("name", Some(2), Some(2))];
+        let err = EditTarget::new("public", "t", &joined, &attributes, &[1]).unwrap_err();
+        assert!(err.to_string().contains("more than one table"));
+
+        // Only computed columns
+        let computed = [column("?column?", None, None)];
+        assert!(EditTarget::source_table(&computed).is_err());
+
+        // No primary key
+        let single = [column("id", Some(1), Some(1)), column("name", Some(1), Some(2))];
+        let err = EditTarget::new("public", "t", &single, &attributes, &[]).unwrap_err();
+        assert!(err.to_string().contains("no primary key"));
+
+        // Primary key not selected
+        let partial = [column("name", Some(1), Some(2))];
+        let err = EditTarget::new("public", "t", &partial, &attributes, &[1]).unwrap_err();
+        assert!(err.to_string().contains("primary key column id"));
+    }
+
+    #[test]
+    fn test_insert_copies_table_columns() {
+        // SELECT id, total, paid, note, now() FROM sales."Order Lines"
+        let typed = |name: &str, type_name: &str, column_id: i16| ColumnInfo {
+            type_name: type_name.to_string(),
+            ..column(name, Some(42), Some(column_id))
+        };
+        let columns = [
+            typed("id", "int8", 1),
+            typed("total", "numeric", 2),
+            typed("paid", "bool", 3),
+            typed("note", "text", 4),
+            column("now", None, None),
+        ];
+        let attributes = attributes(&[(1, "id"), (2, "total"), (3, "paid"), (4, "Note")]);
+        let target =
+            InsertTarget::new("sales", "Order Lines", &columns, &attributes).expect("copyable");
+
+        let rows = [
+            cells(&[Some("7"), Some("12.50"), Some("true"), Some("it's"), Some("2024-01-01")]),
+            cells(&[Some("8"), Some("NaN"), None, None, None]),
+        ];
+        assert_eq!(
+            target.insert_sql(&rows),
+            "INSERT INTO sales.\"Order Lines\" (id, total, paid, \"Note\") \
+             VALUES (7, 12.50, true, 'it''s');\n\
+             INSERT INTO sales.\"Order Lines\" (id, total, paid, \"Note\") \
+             VALUES (8, 'NaN', NULL, NULL);\n"
+        );
+
+        // Joins can't be copied into one table
+        let joined = [column("id", Some(1), Some(1)), column("name", Some(2), Some(2))];
+        let err = InsertTarget::new("public", "t", &joined, &attributes).unwrap_err();
+        assert!(err.to_string().contains("more than one table"));
+        assert!(InsertTarget::source_table(&[column("?column?", None, None)]).is_err());
+    }
+}
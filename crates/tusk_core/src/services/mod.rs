@@ -6,15 +6,23 @@
 //! - `credentials` - OS keychain integration for secure credential storage
 //! - `storage` - Local SQLite storage for metadata and preferences
 //! - `schema` - Schema introspection for the schema browser
+//! - `ddl` - Schema-only DDL generation
+//! - `edit` - `UPDATE` generation for editing result cells and `INSERT` copies of rows
 
 pub mod connection;
 pub mod credentials;
+pub mod ddl;
+pub mod edit;
 pub mod query;
 pub mod schema;
 pub mod storage;
 
-pub use connection::ConnectionPool;
-pub use credentials::CredentialService;
+pub use connection::{
+    ConnectionPool, NoticeCapture, NotificationListener, RetryPolicy, TransactionHandle,
+};
+pub use credentials::{CredentialBackend, CredentialService};
+pub use ddl::DdlDumpOptions;
+pub use edit::{EditTarget, InsertTarget};
 pub use query::QueryService;
 pub use schema::SchemaService;
-pub use storage::LocalStorage;
+pub use storage::{LocalStorage, RecentConnection, CONNECTION_BUNDLE_VERSION};
@@ -5,23 +5,47 @@
 //! - Cancellation via tokio-util CancellationToken (FR-015)
 //! - Streaming results via mpsc channels (FR-011, FR-012)
 //! - Query type detection for result handling
+//! - Commands that can't run in a transaction block (VACUUM, etc.) sent on their own
+//! - Batch execution of multi-statement scripts, collected or streamed per statement
+//! - A destructive-query guard, stricter on production connections
+//! - Planner estimates via `EXPLAIN` that never run the statement
 
 use crate::error::TuskError;
-use crate::models::{ColumnInfo, QueryEvent, QueryHandle, QueryResult, QueryType};
-use crate::services::connection::PooledConnection;
+use crate::models::query::{duration_ms, duration_us};
+use crate::models::query_builder::qualified_name;
+use crate::models::{
+    BatchSummary, ColumnInfo, ConfirmationReason, ConnectionEnvironment, CopyOptions,
+    FirstRowTimer, QueryEstimate, QueryEvent, QueryHandle, QueryPlan, QueryResult, QueryType,
+    RowBatcher, ScriptEvent, TransactionControl,
+};
+use crate::services::connection::{ConnectionPool, PooledConnection};
+use crate::services::ddl::quote_ident;
+use crate::services::schema::ensure_writable;
+use crate::sql_lex;
 
-use futures_util::StreamExt;
+use futures_util::{SinkExt, StreamExt};
+use std::io::{Cursor, Read, Write};
 use std::pin::pin;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use tokio::select;
 use tokio::sync::mpsc;
-
-/// Default batch size for streaming results (FR-012).
-const DEFAULT_BATCH_SIZE: usize = 1000;
+use tokio_postgres::types::{FromSql, Type};
 
 /// Progress update interval (rows) for large queries.
 const PROGRESS_INTERVAL: usize = 10000;
 
+/// Characters of a query fingerprint kept before it is cut off.
+const FINGERPRINT_MAX_CHARS: usize = 120;
+
+/// Events buffered per statement while a script streams.
+const SCRIPT_EVENT_BUFFER: usize = 100;
+
+/// Name of the cursor SELECTs are streamed through.
+const STREAM_CURSOR: &str = "tusk_stream";
+
+/// Bytes read from the source per chunk sent by `QueryService::copy_in`.
+const COPY_IN_CHUNK_BYTES: usize = 64 * 1024;
+
 /// Service for executing queries with cancellation support.
 pub struct QueryService;
 
@@ -64,11 +88,31 @@ impl QueryService {
             query_type = ?query_type,
             "Executing query"
         );
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+
+        // VACUUM and friends can't run inside a transaction block
+        if Self::uses_simple_protocol(sql, params) {
+            return Self::execute_command(conn, sql, None, params, handle, start).await;
+        }
+
+        // Prepare first so statements without a result set can be told apart
+        let statement = select! {
+            result = conn.prepare(sql) => result?,
+            _ = handle.cancelled() => {
+                tracing::debug!(query_id = %handle.id(), "Query cancelled");
+                return Err(TuskError::query_cancelled(handle.id()));
+            }
+        };
+
+        // SET, CALL, VACUUM and the like only report a command status
+        if statement.columns().is_empty() {
+            return Self::execute_command(conn, sql, Some(&statement), params, handle, start).await;
+        }
 
         // Execute with cancellation support
         let result = select! {
             // Query execution
-            result = conn.query(sql, params) => {
+            result = conn.query(&statement, params) => {
                 result
             }
             // Cancellation check (SC-004: propagation within 50ms)
@@ -76,98 +120,1388 @@ impl QueryService {
                 tracing::debug!(query_id = %handle.id(), "Query cancelled");
                 return Err(TuskError::query_cancelled(handle.id()));
             }
-        };
+        };
+
+        let elapsed = start.elapsed();
+        let execution_time_ms = duration_ms(elapsed);
+        let execution_time_us = duration_us(elapsed);
+
+        // Handle query completed before cancellation could propagate
+        // Per spec: return results normally if query completed (FR race handling)
+        let rows = result?;
+
+        // Extract column information
+        let columns = if rows.is_empty() {
+            Vec::new()
+        } else {
+            rows.first()
+                .map(|row| row.columns().iter().map(ColumnInfo::from).collect())
+                .unwrap_or_default()
+        };
+
+        // Determine rows affected (for non-SELECT queries)
+        let rows_affected = match query_type {
+            QueryType::Select | QueryType::Explain => None,
+            _ => Some(rows.len() as u64),
+        };
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            execution_time_us,
+            row_count = rows.len(),
+            "Query completed"
+        );
+
+        Ok(QueryResult {
+            query_id: handle.id(),
+            columns,
+            rows,
+            rows_affected,
+            execution_time_ms,
+            execution_time_us,
+            query_type,
+            command_tag: None,
+        })
+    }
+
+    /// Execute a statement that returns no columns.
+    ///
+    /// Without a prepared statement the SQL is sent through the simple query
+    /// protocol. The result carries the statement's command tag instead of
+    /// rows, with the affected row count for commands that report one.
+    async fn execute_command(
+        conn: &PooledConnection,
+        sql: &str,
+        statement: Option<&tokio_postgres::Statement>,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+        handle: &QueryHandle,
+        start: Instant,
+    ) -> Result<QueryResult, TuskError> {
+        let execution = async {
+            match statement {
+                Some(statement) => conn.execute(statement, params).await,
+                None => conn.simple_execute(sql).await,
+            }
+        };
+        let affected = select! {
+            result = execution => result?,
+            _ = handle.cancelled() => {
+                tracing::debug!(query_id = %handle.id(), "Query cancelled");
+                return Err(TuskError::query_cancelled(handle.id()));
+            }
+        };
+
+        let elapsed = start.elapsed();
+        let command_tag = Self::command_tag(sql);
+        let rows_affected = Self::command_counts_rows(&command_tag).then_some(affected);
+
+        // DDL can change what cached statements return
+        if ["CREATE", "ALTER", "DROP"].iter().any(|verb| command_tag.starts_with(verb)) {
+            conn.clear_statement_cache();
+        }
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            command_tag = %command_tag,
+            execution_time_us = duration_us(elapsed),
+            "Command completed"
+        );
+
+        Ok(QueryResult {
+            query_id: handle.id(),
+            columns: Vec::new(),
+            rows: Vec::new(),
+            rows_affected,
+            execution_time_ms: duration_ms(elapsed),
+            execution_time_us: duration_us(elapsed),
+            query_type: Self::detect_query_type(sql),
+            command_tag: Some(command_tag),
+        })
+    }
+
+    /// Derive the command tag PostgreSQL reports for a statement.
+    ///
+    /// Mirrors the server's tags for common commands: "SET", "BEGIN",
+    /// "CREATE TABLE", "COMMIT" for END, and so on. Modifiers such as
+    /// OR REPLACE, TEMPORARY, and UNIQUE are dropped from CREATE tags.
+    pub fn command_tag(sql: &str) -> String {
+        let words = Self::top_level_words(sql);
+        let Some(first) = words.first() else {
+            return String::new();
+        };
+
+        match first.as_str() {
+            "BEGIN" => "BEGIN".to_string(),
+            "START" => "START TRANSACTION".to_string(),
+            "END" | "COMMIT" => "COMMIT".to_string(),
+            "ABORT" | "ROLLBACK" => "ROLLBACK".to_string(),
+            "CREATE" | "ALTER" | "DROP" => {
+                let mut object = words[1..].iter().map(String::as_str).skip_while(|word| {
+                    matches!(
+                        *word,
+                        "OR" | "REPLACE"
+                            | "TEMP"
+                            | "TEMPORARY"
+                            | "UNLOGGED"
+                            | "GLOBAL"
+                            | "LOCAL"
+                            | "UNIQUE"
+                            | "TRUSTED"
+                            | "PROCEDURAL"
+                            | "RECURSIVE"
+                    )
+                });
+                match (object.next(), object.next()) {
+                    (
+                        Some(kind @ ("MATERIALIZED" | "FOREIGN" | "EVENT" | "ACCESS" | "DEFAULT")),
+                        Some(rest),
+                    ) => format!("{first} {kind} {rest}"),
+                    (Some(kind), _) => format!("{first} {kind}"),
+                    (None, _) => first.clone(),
+                }
+            }
+            // A WITH statement takes the tag of its main statement
+            "WITH" => words
+                .iter()
+                .find(|word| {
+                    matches!(word.as_str(), "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE")
+                })
+                .cloned()
+                .unwrap_or_else(|| first.clone()),
+            _ => first.clone(),
+        }
+    }
+
+    /// Check if a statement can't run inside a transaction block.
+    ///
+    /// Covers VACUUM, the CONCURRENTLY forms of CREATE/DROP/REINDEX INDEX,
+    /// database-wide REINDEX and CLUSTER, CREATE/DROP DATABASE and
+    /// TABLESPACE, ALTER SYSTEM, ALTER DATABASE ... SET TABLESPACE,
+    /// subscriptions, DISCARD ALL, and COMMIT/ROLLBACK PREPARED.
+    pub fn is_non_transactional(sql: &str) -> bool {
+        let words = Self::top_level_words(sql);
+        let word = |index: usize| words.get(index).map(String::as_str).unwrap_or_default();
+        let has = |target: &str| words.iter().any(|word| word == target);
+
+        match (word(0), word(1)) {
+            ("VACUUM", _) => true,
+            ("CREATE", "INDEX") | ("CREATE", "UNIQUE") | ("DROP", "INDEX") => has("CONCURRENTLY"),
+            ("REINDEX", "DATABASE" | "SYSTEM") => true,
+            ("REINDEX", _) => has("CONCURRENTLY"),
+            ("CREATE" | "DROP", "DATABASE" | "TABLESPACE" | "SUBSCRIPTION") => true,
+            ("ALTER", "SYSTEM" | "SUBSCRIPTION") => true,
+            ("ALTER", "DATABASE") => has("SET") && has("TABLESPACE"),
+            ("DISCARD", "ALL") => true,
+            ("COMMIT" | "ROLLBACK", "PREPARED") => true,
+            // CLUSTER without a table reclusters every table
+            ("CLUSTER", "" | "VERBOSE") => words.len() <= 2,
+            _ => false,
+        }
+    }
+
+    /// Check if a statement should be sent through the simple query protocol.
+    ///
+    /// Non-transactional commands are sent on their own so they aren't part
+    /// of an implicit transaction; parameters need the extended protocol.
+    fn uses_simple_protocol(
+        sql: &str,
+        params: &[&(dyn tokio_postgres::types::ToSql + Sync)],
+    ) -> bool {
+        params.is_empty() && Self::is_non_transactional(sql)
+    }
+
+    /// Check if a command's tag includes a row count.
+    fn command_counts_rows(tag: &str) -> bool {
+        matches!(
+            tag,
+            "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "COPY" | "SELECT" | "MOVE" | "FETCH"
+        )
+    }
+
+    /// Execute a script statement by statement, streaming each one's events.
+    ///
+    /// Statements are split like [`Self::split_statements`] and streamed in
+    /// order like [`Self::execute_streaming`]. Every event is wrapped in a
+    /// [`ScriptEvent`] carrying the statement's position and byte offset in
+    /// the script, so each statement can get its own result. The first
+    /// statement ending in an `Error` event (including cancellation) stops
+    /// the script; the statements after it are not run.
+    ///
+    /// # Arguments
+    /// * `conn` - Pooled database connection
+    /// * `sql` - One or more SQL statements
+    /// * `handle` - Query handle for tracking and cancellation
+    /// * `tx` - Channel sender for the tagged event stream
+    ///
+    /// # Returns
+    /// Totals for the statements that ran and the ones left unrun.
+    pub async fn execute_script(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<ScriptEvent>,
+    ) -> Result<BatchSummary, TuskError> {
+        Self::run_script(conn, sql, handle, tx, |_, _| {}).await
+    }
+
+    /// Execute a script like [`Self::execute_script`] on a pool connection,
+    /// keeping explicit transactions on one connection.
+    ///
+    /// While a transaction is open (see [`ConnectionPool::transaction`]), or
+    /// when the script begins one, every statement runs on the pinned
+    /// connection and the transaction's status is updated statement by
+    /// statement. Other scripts run on any pooled connection.
+    pub async fn execute_script_pooled(
+        pool: &ConnectionPool,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<ScriptEvent>,
+    ) -> Result<BatchSummary, TuskError> {
+        let begins = Self::split_statements(sql).iter().any(|statement| {
+            Self::transaction_control(statement) == Some(TransactionControl::Begin)
+        });
+        let txn = match pool.transaction() {
+            Some(txn) => txn,
+            None if begins => pool.pin_transaction().await?,
+            None => return Self::execute_script(&pool.get().await?, sql, handle, tx).await,
+        };
+
+        let result = Self::run_script(txn.connection(), sql, handle, tx, |statement, succeeded| {
+            pool.record_transaction(&txn, Self::transaction_control(statement), succeeded);
+        })
+        .await;
+        if result.is_err() {
+            pool.record_transaction(&txn, None, false);
+        }
+        result
+    }
+
+    /// Stream a script's statements, calling `on_statement` with each
+    /// statement that ran and whether it succeeded.
+    async fn run_script(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<ScriptEvent>,
+        mut on_statement: impl FnMut(&str, bool),
+    ) -> Result<BatchSummary, TuskError> {
+        let statements = Self::split_statements_with_offsets(sql);
+        let mut summary = BatchSummary::default();
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            statement_count = statements.len(),
+            "Executing script"
+        );
+
+        for (index, &(offset, statement_sql)) in statements.iter().enumerate() {
+            let statement = index + 1;
+            let (statement_tx, mut statement_rx) = mpsc::channel(SCRIPT_EVENT_BUFFER);
+            let forward = async {
+                let mut failed = false;
+                while let Some(event) = statement_rx.recv().await {
+                    failed |= matches!(event, QueryEvent::Error(_));
+                    // Keep draining if the receiver is gone so the statement can finish
+                    let _ = tx.send(ScriptEvent { statement, offset, event }).await;
+                }
+                failed
+            };
+            let start = Instant::now();
+            let (result, failed) = tokio::join!(
+                Self::execute_streaming(conn, statement_sql, handle, statement_tx),
+                forward
+            );
+            result?;
+            on_statement(statement_sql, !failed);
+            summary.total_time_us += duration_us(start.elapsed());
+
+            if failed {
+                summary.failed += 1;
+                summary.not_run = statements.len() - statement;
+                tracing::debug!(
+                    query_id = %handle.id(),
+                    statement,
+                    offset,
+                    not_run = summary.not_run,
+                    "Script stopped at failed statement"
+                );
+                break;
+            }
+            summary.succeeded += 1;
+        }
+
+        Ok(summary)
+    }
+
+    /// Estimate a statement's rows and cost without running it.
+    ///
+    /// Sends the statement to the planner with `EXPLAIN (FORMAT JSON)` and
+    /// reads the top-level plan node. See [`Self::estimate_sql`] for which
+    /// statements are accepted.
+    pub async fn explain(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+    ) -> Result<QueryEstimate, TuskError> {
+        let plan = Self::plan_json(conn, &Self::estimate_sql(sql)?, handle).await?;
+        QueryEstimate::from_plan_json(&plan)
+            .ok_or_else(|| TuskError::internal("Query plan has no row or cost estimate"))
+    }
+
+    /// Build the `EXPLAIN` that estimates a single statement.
+    ///
+    /// The plain `EXPLAIN` of [`Self::explain_plan_sql`], which only plans
+    /// the statement.
+    pub fn estimate_sql(sql: &str) -> Result<String, TuskError> {
+        Self::explain_plan_sql(sql, false, false)
+    }
+
+    /// Capture the plan of a statement as a node tree.
+    ///
+    /// Runs `EXPLAIN (FORMAT JSON)`, or with `analyze` also runs the
+    /// statement to collect actual rows and timings. See
+    /// [`Self::explain_plan_sql`] for which statements are accepted. Unlike
+    /// [`Self::explain`], which only reads the top-level estimate, this keeps
+    /// every node.
+    pub async fn explain_plan(
+        conn: &PooledConnection,
+        sql: &str,
+        analyze: bool,
+        allow_writes: bool,
+        handle: &QueryHandle,
+    ) -> Result<QueryPlan, TuskError> {
+        let explain = Self::explain_plan_sql(sql, analyze, allow_writes)?;
+        let plan = Self::plan_json(conn, &explain, handle).await?;
+        QueryPlan::from_plan_json(&plan)
+            .ok_or_else(|| TuskError::internal("Query plan could not be parsed"))
+    }
+
+    /// Build the `EXPLAIN` that captures a single statement's plan.
+    ///
+    /// Only one SELECT, INSERT, UPDATE, DELETE, MERGE, VALUES or TABLE
+    /// statement is accepted (a WITH query counts as its main statement);
+    /// EXPLAIN statements themselves are refused. Plain `EXPLAIN` only plans
+    /// the statement, but `EXPLAIN ANALYZE` executes it, so with `analyze` a
+    /// statement that is not read-only is refused unless `allow_writes` is set.
+    pub fn explain_plan_sql(
+        sql: &str,
+        analyze: bool,
+        allow_writes: bool,
+    ) -> Result<String, TuskError> {
+        let refuse = |message: &str, hint: &str| {
+            TuskError::query(message, None, Some(hint.to_string()), None, None)
+        };
+        let statement = match Self::split_statements(sql).as_slice() {
+            [statement] => statement.trim(),
+            [] => return Err(refuse("Nothing to explain", "Enter a query first")),
+            _ => {
+                return Err(refuse(
+                    "Only a single statement can be explained",
+                    "Select one statement of the script to explain it",
+                ))
+            }
+        };
+        let words = Self::top_level_words(statement);
+        if !matches!(
+            Self::main_keyword(&words),
+            Some("SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "VALUES" | "TABLE")
+        ) {
+            return Err(refuse(
+                "This statement has no query plan",
+                "Plans are available for SELECT, INSERT, UPDATE, DELETE and MERGE",
+            ));
+        }
+        if !analyze {
+            return Ok(format!("EXPLAIN (FORMAT JSON) {statement}"));
+        }
+        if !allow_writes && !Self::is_read_only_statement(statement) {
+            return Err(refuse(
+                "EXPLAIN ANALYZE would run this statement and change data",
+                "Explain without ANALYZE, or confirm that the statement may run",
+            ));
+        }
+        Ok(format!("EXPLAIN (FORMAT JSON, ANALYZE true) {statement}"))
+    }
+
+    /// Run an `EXPLAIN (FORMAT JSON)` built by [`Self::explain_plan_sql`],
+    /// returning the plan's JSON text.
+    async fn plan_json(
+        conn: &PooledConnection,
+        explain: &str,
+        handle: &QueryHandle,
+    ) -> Result<String, TuskError> {
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+
+        let rows = select! {
+            result = conn.query(explain, &[]) => result?,
+            _ = handle.cancelled() => {
+                tracing::debug!(query_id = %handle.id(), "Explain cancelled");
+                return Err(TuskError::query_cancelled(handle.id()));
+            }
+        };
+        let plan = rows
+            .first()
+            .map(|row| row.try_get::<_, PlanJson>(0))
+            .transpose()
+            .map_err(|e| TuskError::internal(format!("Failed to read query plan: {e}")))?
+            .ok_or_else(|| TuskError::internal("EXPLAIN returned no plan"))?;
+        Ok(plan.0)
+    }
+
+    /// Export a query's results with `COPY (query) TO STDOUT`, writing the
+    /// bytes to `writer` as they arrive.
+    ///
+    /// Rows are never decoded or held in memory, so exports of any size run
+    /// in bounded memory; pass a buffered writer for files. Returns the
+    /// number of bytes written. Cancelling `handle` stops the export on the
+    /// server and returns a cancelled error, leaving whatever was already
+    /// written. See [`Self::copy_out_sql`] for which statements are accepted.
+    pub async fn copy_out(
+        conn: &PooledConnection,
+        sql: &str,
+        options: &CopyOptions,
+        handle: &QueryHandle,
+        mut writer: impl Write,
+    ) -> Result<u64, TuskError> {
+        let copy = Self::copy_out_sql(sql, options)?;
+        let start = Instant::now();
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            format = options.format.label(),
+            "Starting COPY export"
+        );
+
+        let stream = select! {
+            result = conn.copy_out(copy.as_str()) => result?,
+            _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+        };
+        let mut stream = pin!(stream);
+        let mut bytes = 0u64;
+        loop {
+            let chunk = select! {
+                chunk = stream.next() => chunk,
+                _ = handle.cancelled() => {
+                    tracing::debug!(query_id = %handle.id(), bytes, "COPY export cancelled");
+                    return Err(TuskError::query_cancelled(handle.id()));
+                }
+            };
+            let Some(chunk) = chunk else { break };
+            let chunk = chunk?;
+            writer.write_all(&chunk)?;
+            bytes += chunk.len() as u64;
+        }
+        writer.flush()?;
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            bytes,
+            execution_time_us = duration_us(start.elapsed()),
+            "COPY export completed"
+        );
+        Ok(bytes)
+    }
+
+    /// Load data into a table with `COPY ... FROM STDIN`, reading it from
+    /// `reader` in chunks.
+    ///
+    /// `columns` lists the table columns the data fills, in order; leave it
+    /// empty to fill every column. Returns the number of rows loaded. The
+    /// COPY runs as a single statement, so a bad row, a type mismatch, a
+    /// read error or cancelling `handle` rolls it back and leaves the table
+    /// as it was. Server errors keep the line of data they came from (e.g.
+    /// `COPY orders, line 3, column total: "abc"`) in their detail. Refused
+    /// on read-only connections.
+    pub async fn copy_in(
+        pool: &ConnectionPool,
+        schema: &str,
+        table: &str,
+        columns: &[&str],
+        options: &CopyOptions,
+        handle: &QueryHandle,
+        mut reader: impl Read,
+    ) -> Result<u64, TuskError> {
+        ensure_writable(pool.config(), "load data into", schema, table)?;
+        let copy = Self::copy_in_sql(schema, table, columns, options)?;
+        let start = Instant::now();
+        let conn = pool.get().await?;
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+
+        tracing::info!(
+            query_id = %handle.id(),
+            schema = %schema,
+            table = %table,
+            format = options.format.label(),
+            "Starting COPY import"
+        );
+
+        let sink = select! {
+            result = conn.copy_in(copy.as_str()) => result?,
+            _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+        };
+        // Returning early drops the unfinished sink, which aborts the COPY
+        let mut sink = pin!(sink);
+        loop {
+            let mut chunk = vec![0; COPY_IN_CHUNK_BYTES];
+            let read = reader.read(&mut chunk)?;
+            if read == 0 {
+                break;
+            }
+            chunk.truncate(read);
+            select! {
+                result = sink.send(Cursor::new(chunk)) => result.map_err(copy_error)?,
+                _ = handle.cancelled() => {
+                    tracing::debug!(query_id = %handle.id(), "COPY import cancelled");
+                    return Err(TuskError::query_cancelled(handle.id()));
+                }
+            }
+        }
+        let rows = select! {
+            result = sink.as_mut().finish() => result.map_err(copy_error)?,
+            _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+        };
+
+        tracing::info!(
+            query_id = %handle.id(),
+            rows,
+            execution_time_us = duration_us(start.elapsed()),
+            "COPY import completed"
+        );
+        Ok(rows)
+    }
+
+    /// Build the `COPY ... FROM STDIN` that loads data into a table.
+    ///
+    /// The table and column names are quoted; an empty `columns` fills every
+    /// column.
+    pub fn copy_in_sql(
+        schema: &str,
+        table: &str,
+        columns: &[&str],
+        options: &CopyOptions,
+    ) -> Result<String, TuskError> {
+        let target = qualified_name(schema, table);
+        let column_list = if columns.is_empty() {
+            String::new()
+        } else {
+            let quoted: Vec<String> = columns.iter().map(|column| quote_ident(column)).collect();
+            format!(" ({})", quoted.join(", "))
+        };
+        Ok(format!("COPY {target}{column_list} FROM STDIN WITH {}", options.with_clause()?))
+    }
+
+    /// Build the `COPY ... TO STDOUT` that exports a query's results.
+    ///
+    /// Only a single read-only SELECT, VALUES or TABLE query can be exported,
+    /// so an export never changes data.
+    pub fn copy_out_sql(sql: &str, options: &CopyOptions) -> Result<String, TuskError> {
+        let refuse = |message: &str, hint: &str| {
+            TuskError::query(message, None, Some(hint.to_string()), None, None)
+        };
+        let statement = match Self::split_statements(sql).as_slice() {
+            [statement] => statement.trim(),
+            [] => return Err(refuse("Nothing to export", "Enter a query first")),
+            _ => {
+                return Err(refuse(
+                    "Only a single statement can be exported",
+                    "Select one statement of the script to export it",
+                ))
+            }
+        };
+        let words = Self::top_level_words(statement);
+        let is_query = matches!(Self::main_keyword(&words), Some("SELECT" | "VALUES" | "TABLE"));
+        if !is_query || !Self::is_read_only_statement(statement) {
+            return Err(refuse(
+                "Only read-only queries can be exported with COPY",
+                "Export the results of a SELECT, VALUES or TABLE query",
+            ));
+        }
+        Ok(format!("COPY ({statement}) TO STDOUT WITH {}", options.with_clause()?))
+    }
+
+    /// Split SQL text into individual statements on top-level semicolons.
+    ///
+    /// Semicolons inside string literals, quoted identifiers, dollar-quoted
+    /// bodies, and comments do not end a statement. Fragments containing only
+    /// whitespace or comments are dropped.
+    pub fn split_statements(sql: &str) -> Vec<&str> {
+        Self::split_statements_with_offsets(sql).into_iter().map(|(_, s)| s).collect()
+    }
+
+    /// Split SQL text like [`Self::split_statements`], keeping where each
+    /// statement starts.
+    ///
+    /// Returns the byte offset of each trimmed statement in `sql` with the
+    /// statement itself.
+    pub fn split_statements_with_offsets(sql: &str) -> Vec<(usize, &str)> {
+        let bytes = sql.as_bytes();
+        let mut statements = Vec::new();
+        let mut start = 0;
+        let mut has_content = false;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\'' => {
+                    // E'...' strings allow backslash escapes
+                    let escapes = sql_lex::has_escape_prefix(bytes, i);
+                    i = sql_lex::skip_quoted(bytes, i, b'\'', escapes).unwrap_or(bytes.len());
+                    has_content = true;
+                }
+                b'"' => {
+                    i = sql_lex::skip_quoted(bytes, i, b'"', false).unwrap_or(bytes.len());
+                    has_content = true;
+                }
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = sql_lex::skip_line_comment(bytes, i);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = sql_lex::skip_block_comment(bytes, i).unwrap_or(bytes.len());
+                }
+                b'$' => {
+                    i = match sql_lex::dollar_tag(bytes, i) {
+                        Some(tag) => {
+                            sql_lex::skip_dollar_quoted(bytes, i, tag).unwrap_or(bytes.len())
+                        }
+                        None => i + 1,
+                    };
+                    has_content = true;
+                }
+                b';' => {
+                    if has_content {
+                        statements.push(Self::trimmed_statement(sql, start, i));
+                    }
+                    i += 1;
+                    start = i;
+                    has_content = false;
+                }
+                b if b.is_ascii_whitespace() => i += 1,
+                _ => {
+                    has_content = true;
+                    i += 1;
+                }
+            }
+        }
+
+        if has_content {
+            statements.push(Self::trimmed_statement(sql, start, sql.len()));
+        }
+
+        statements
+    }
+
+    /// Trim `sql[start..end]`, returning the trimmed text and its offset.
+    fn trimmed_statement(sql: &str, start: usize, end: usize) -> (usize, &str) {
+        let fragment = &sql[start..end];
+        let leading = fragment.len() - fragment.trim_start().len();
+        (start + leading, fragment.trim())
+    }
+
+    /// Reduce SQL to a one-line fingerprint for the query log.
+    ///
+    /// Comments are dropped, string, dollar-quoted, and numeric literals
+    /// become `?`, and whitespace collapses to single spaces, so values typed
+    /// into a query aren't echoed back. Long statements are cut off with `…`.
+    pub fn fingerprint(sql: &str) -> String {
+        let bytes = sql.as_bytes();
+        let mut out = String::new();
+        let mut space = false;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            let start = i;
+            let literal = match bytes[i] {
+                b'\'' => {
+                    // E'...' strings allow backslash escapes
+                    let escapes = sql_lex::has_escape_prefix(bytes, i);
+                    if escapes {
+                        out.pop();
+                    }
+                    i = sql_lex::skip_quoted(bytes, i, b'\'', escapes).unwrap_or(bytes.len());
+                    true
+                }
+                b'"' => {
+                    i = sql_lex::skip_quoted(bytes, i, b'"', false).unwrap_or(bytes.len());
+                    false
+                }
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = sql_lex::skip_line_comment(bytes, i);
+                    space = true;
+                    continue;
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = sql_lex::skip_block_comment(bytes, i).unwrap_or(bytes.len());
+                    space = true;
+                    continue;
+                }
+                b'$' => match sql_lex::dollar_tag(bytes, i) {
+                    Some(tag) => {
+                        i = sql_lex::skip_dollar_quoted(bytes, i, tag).unwrap_or(bytes.len());
+                        true
+                    }
+                    None => {
+                        i += 1;
+                        false
+                    }
+                },
+                b if b.is_ascii_whitespace() => {
+                    i += 1;
+                    space = true;
+                    continue;
+                }
+                // Numbers, but not digits inside names like t1 or $1
+                b if b.is_ascii_digit()
+                    && (i == 0
+                        || !(bytes[i - 1].is_ascii_alphanumeric()
+                            || bytes[i - 1] == b'_'
+                            || bytes[i - 1] == b'$')) =>
+                {
+                    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.')
+                    {
+                        i += 1;
+                    }
+                    true
+                }
+                _ => {
+                    i += sql[i..].chars().next().map_or(1, char::len_utf8);
+                    false
+                }
+            };
+
+            if space && !out.is_empty() {
+                out.push(' ');
+            }
+            space = false;
+            out.push_str(if literal { "?" } else { &sql[start..i] });
+        }
+
+        match out.char_indices().nth(FINGERPRINT_MAX_CHARS) {
+            Some((cut, _)) => format!("{}…", out[..cut].trim_end()),
+            None => out,
+        }
+    }
+
+    /// The search_path set by the last `SET search_path` in a script.
+    ///
+    /// Returns the value as written (e.g. `sales, public`). `RESET
+    /// search_path`, `RESET ALL` and `SET search_path TO DEFAULT` return an
+    /// empty string. `SET LOCAL` is ignored since it ends with the transaction.
+    pub fn search_path_change(sql: &str) -> Option<String> {
+        Self::split_statements(sql).into_iter().filter_map(Self::statement_search_path).last()
+    }
+
+    /// The search_path value set by a single statement, if it sets one.
+    fn statement_search_path(statement: &str) -> Option<String> {
+        let words = Self::top_level_words(statement);
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+        match words.as_slice() {
+            ["RESET", "SEARCH_PATH" | "ALL"] => return Some(String::new()),
+            ["SET", "SEARCH_PATH", ..] | ["SET", "SESSION", "SEARCH_PATH", ..] => {}
+            _ => return None,
+        }
+
+        let body = Self::skip_leading_comments(statement);
+        let start = body.to_ascii_lowercase().find("search_path")? + "search_path".len();
+        let rest = body[start..].trim_start();
+        let value = match rest.strip_prefix('=') {
+            Some(value) => value,
+            None => match rest.get(..2) {
+                Some(to) if to.eq_ignore_ascii_case("to") => &rest[2..],
+                _ => return None,
+            },
+        };
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("default") {
+            Some(String::new())
+        } else {
+            Some(value.to_string())
+        }
+    }
+
+    /// The statement text after any leading whitespace and comments.
+    fn skip_leading_comments(statement: &str) -> &str {
+        let mut rest = statement.trim_start();
+        loop {
+            if let Some(comment) = rest.strip_prefix("--") {
+                rest = comment.find('\n').map_or("", |end| &comment[end..]).trim_start();
+            } else if rest.starts_with("/*") {
+                rest = rest
+                    [sql_lex::skip_block_comment(rest.as_bytes(), 0).unwrap_or(rest.len())..]
+                    .trim_start();
+            } else {
+                return rest;
+            }
+        }
+    }
+
+    /// Append `LIMIT n` to SELECTs that don't limit their own rows.
+    ///
+    /// Guards against accidentally fetching huge results. Each statement is
+    /// checked on its own (see [`Self::needs_row_limit`]). Returns None when
+    /// no statement needed a limit, so the SQL can run unchanged.
+    pub fn apply_row_limit(sql: &str, limit: u32) -> Option<String> {
+        let mut applied = false;
+        let statements: Vec<String> = Self::split_statements(sql)
+            .into_iter()
+            .map(|statement| {
+                if Self::needs_row_limit(statement) {
+                    applied = true;
+                    // On its own line so a trailing line comment can't swallow it
+                    format!("{statement}\nLIMIT {limit}")
+                } else {
+                    statement.to_string()
+                }
+            })
+            .collect();
+
+        applied.then(|| statements.join(";\n"))
+    }
+
+    /// Check if a statement is a SELECT without a top-level LIMIT or FETCH.
+    ///
+    /// Only the outer query counts: a LIMIT inside a subquery or CTE doesn't
+    /// bound the rows returned. `WITH` statements qualify when their main
+    /// statement is a SELECT. SELECT INTO, EXPLAIN and other statements
+    /// never do.
+    pub fn needs_row_limit(statement: &str) -> bool {
+        let words = Self::top_level_words(statement);
+        Self::main_keyword(&words) == Some("SELECT")
+            && !words.iter().any(|word| matches!(word.as_str(), "LIMIT" | "FETCH" | "INTO"))
+    }
+
+    /// The keyword of a statement's main command, looking past `WITH`.
+    fn main_keyword(words: &[String]) -> Option<&str> {
+        match words.first().map(String::as_str) {
+            // CTE bodies are parenthesized, so the first top-level
+            // statement keyword is the main statement
+            Some("WITH") => words.iter().map(String::as_str).find(|word| {
+                matches!(
+                    *word,
+                    "SELECT" | "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "VALUES" | "TABLE"
+                )
+            }),
+            first => first,
+        }
+    }
+
+    /// Check whether a script must be confirmed before it runs.
+    ///
+    /// Statements the destructive heuristic flags always need confirmation.
+    /// On production connections every statement that may write does too,
+    /// whatever the heuristic says; only plain reads run unprompted.
+    pub fn confirmation_reason(
+        sql: &str,
+        environment: ConnectionEnvironment,
+    ) -> Option<ConfirmationReason> {
+        let destructive = Self::destructive_statements(sql);
+        if !destructive.is_empty() {
+            return Some(ConfirmationReason::Destructive(
+                destructive.into_iter().map(str::to_string).collect(),
+            ));
+        }
+
+        let writes = !Self::split_statements(sql).into_iter().all(Self::is_read_only_statement);
+        (environment.is_production() && writes).then_some(ConfirmationReason::Production)
+    }
+
+    /// Statements in a script that drop or erase data.
+    pub fn destructive_statements(sql: &str) -> Vec<&str> {
+        Self::split_statements(sql).into_iter().filter(|s| Self::is_destructive(s)).collect()
+    }
+
+    /// Check if a statement drops or erases data.
+    ///
+    /// Flags DROP, TRUNCATE, ALTER ... DROP, and DELETE or UPDATE without a
+    /// top-level WHERE clause.
+    pub fn is_destructive(statement: &str) -> bool {
+        let words = Self::top_level_words(statement);
+        let has = |keyword: &str| words.iter().any(|word| word == keyword);
+        match words.first().map(String::as_str) {
+            Some("DROP" | "TRUNCATE") => true,
+            Some("ALTER") => has("DROP"),
+            _ => match Self::main_keyword(&words) {
+                Some("DELETE" | "UPDATE") => !has("WHERE"),
+                _ => false,
+            },
+        }
+    }
+
+    /// Classify a statement that opens or closes a transaction.
+    ///
+    /// Returns None for every other statement, including `COMMIT PREPARED`
+    /// and `ROLLBACK PREPARED`, which finish a different, prepared
+    /// transaction.
+    pub fn transaction_control(statement: &str) -> Option<TransactionControl> {
+        let words = Self::top_level_words(statement);
+        let second = words.get(1).map(String::as_str);
+        match words.first().map(String::as_str)? {
+            "BEGIN" => Some(TransactionControl::Begin),
+            "START" if second == Some("TRANSACTION") => Some(TransactionControl::Begin),
+            "COMMIT" | "ROLLBACK" if second == Some("PREPARED") => None,
+            "COMMIT" | "END" | "ROLLBACK" | "ABORT"
+                if words.windows(2).any(|pair| pair[0] == "AND" && pair[1] == "CHAIN") =>
+            {
+                Some(TransactionControl::Chain)
+            }
+            "COMMIT" | "END" => Some(TransactionControl::Commit),
+            "PREPARE" if second == Some("TRANSACTION") => Some(TransactionControl::Commit),
+            "ROLLBACK" | "ABORT" if words.iter().any(|word| word == "TO") => {
+                Some(TransactionControl::RollbackToSavepoint)
+            }
+            "ROLLBACK" | "ABORT" => Some(TransactionControl::Rollback),
+            _ => None,
+        }
+    }
+
+    /// Check if a statement only reads data.
+    ///
+    /// SELECT (without INTO), VALUES, TABLE, SHOW, and EXPLAIN without
+    /// ANALYZE qualify. A WITH query qualifies only if none of its CTEs
+    /// modify data.
+    pub fn is_read_only_statement(statement: &str) -> bool {
+        let words = Self::top_level_words(statement);
+        let has = |keyword: &str| words.iter().any(|word| word == keyword);
+        match Self::main_keyword(&words) {
+            Some("SELECT") => {
+                !has("INTO")
+                    && !Self::words(statement, true).iter().any(|word| {
+                        matches!(word.as_str(), "INSERT" | "UPDATE" | "DELETE" | "MERGE")
+                    })
+            }
+            Some("VALUES" | "TABLE" | "SHOW") => true,
+            Some("EXPLAIN") => !Self::is_explain_analyze(statement),
+            _ => false,
+        }
+    }
+
+    /// Check if a statement is an EXPLAIN that runs the explained statement.
+    ///
+    /// Recognizes `EXPLAIN ANALYZE ...` and the option list form
+    /// `EXPLAIN (ANALYZE, ...) ...`. An explicit `ANALYZE false` (or `off`,
+    /// `0`) only plans the statement.
+    pub fn is_explain_analyze(statement: &str) -> bool {
+        let is_analyze = |word: &str| {
+            word.eq_ignore_ascii_case("ANALYZE") || word.eq_ignore_ascii_case("ANALYSE")
+        };
+
+        let trimmed = Self::skip_leading_comments(statement);
+        let Some(rest) = trimmed
+            .get(..7)
+            .filter(|keyword| keyword.eq_ignore_ascii_case("EXPLAIN"))
+            .map(|_| &trimmed[7..])
+            .filter(|rest| !rest.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_'))
+        else {
+            return false;
+        };
+        let rest = rest.trim_start();
+
+        match rest.strip_prefix('(') {
+            Some(options) => {
+                let options = &options[..options.find(')').unwrap_or(options.len())];
+                options.split(',').any(|option| {
+                    let mut parts = option.split_whitespace();
+                    parts.next().is_some_and(is_analyze)
+                        && !matches!(
+                            parts.next().map(str::to_ascii_lowercase).as_deref(),
+                            Some("false" | "off" | "0")
+                        )
+                })
+            }
+            None => {
+                let end = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+                is_analyze(&rest[..end])
+            }
+        }
+    }
+
+    /// Uppercased words outside parentheses, literals, quoted identifiers,
+    /// and comments.
+    fn top_level_words(sql: &str) -> Vec<String> {
+        Self::words(sql, false)
+    }
+
+    /// Uppercased words outside literals, quoted identifiers, and comments.
+    ///
+    /// Words inside parentheses are included only when `nested` is set.
+    fn words(sql: &str, nested: bool) -> Vec<String> {
+        let bytes = sql.as_bytes();
+        let mut words = Vec::new();
+        let mut depth = 0usize;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            match bytes[i] {
+                b'\'' => {
+                    let escapes = sql_lex::has_escape_prefix(bytes, i);
+                    i = sql_lex::skip_quoted(bytes, i, b'\'', escapes).unwrap_or(bytes.len());
+                }
+                b'"' => i = sql_lex::skip_quoted(bytes, i, b'"', false).unwrap_or(bytes.len()),
+                b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                    i = sql_lex::skip_line_comment(bytes, i);
+                }
+                b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                    i = sql_lex::skip_block_comment(bytes, i).unwrap_or(bytes.len())
+                }
+                b'$' => {
+                    i = match sql_lex::dollar_tag(bytes, i) {
+                        Some(tag) => {
+                            sql_lex::skip_dollar_quoted(bytes, i, tag).unwrap_or(bytes.len())
+                        }
+                        None => i + 1,
+                    };
+                }
+                b'(' | b'[' => {
+                    depth += 1;
+                    i += 1;
+                }
+                b')' | b']' => {
+                    depth = depth.saturating_sub(1);
+                    i += 1;
+                }
+                b if b.is_ascii_alphabetic() || b == b'_' => {
+                    let start = i;
+                    while i < bytes.len()
+                        && (bytes[i].is_ascii_alphanumeric() || matches!(bytes[i], b'_' | b'$'))
+                    {
+                        i += 1;
+                    }
+                    if depth == 0 || nested {
+                        words.push(sql[start..i].to_ascii_uppercase());
+                    }
+                }
+                b if b.is_ascii_digit() => {
+                    while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.')
+                    {
+                        i += 1;
+                    }
+                }
+                _ => i += 1,
+            }
+        }
+
+        words
+    }
+
+    /// Detect the type of SQL query from its first keyword.
+    pub fn detect_query_type(sql: &str) -> QueryType {
+        let trimmed = Self::skip_leading_comments(sql);
+        let end = trimmed.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(trimmed.len());
+        let keyword = trimmed[..end].to_ascii_uppercase();
+
+        match keyword.as_str() {
+            "SELECT" | "WITH" => QueryType::Select,
+            "INSERT" => QueryType::Insert,
+            "UPDATE" => QueryType::Update,
+            "DELETE" => QueryType::Delete,
+            "EXPLAIN" => QueryType::Explain,
+            "CREATE" | "ALTER" | "DROP" | "TRUNCATE" | "COMMENT" => QueryType::Ddl,
+            "SET" | "RESET" | "SHOW" | "VACUUM" | "ANALYZE" | "ANALYSE" | "CLUSTER" | "REINDEX"
+            | "CHECKPOINT" | "DISCARD" | "LOAD" => QueryType::Utility,
+            _ => QueryType::Other,
+        }
+    }
+
+    /// Check if any statement in a script is DDL, so cached schema may be stale
+    /// once it runs.
+    pub fn changes_schema(sql: &str) -> bool {
+        Self::split_statements(sql)
+            .into_iter()
+            .any(|statement| Self::detect_query_type(statement) == QueryType::Ddl)
+    }
+
+    /// Execute a query with streaming results via channel (FR-011, FR-012, FR-014).
+    ///
+    /// Sends QueryEvent messages through the provided channel as results arrive.
+    /// Events are sent in order: Columns, Rows (batches), then Complete or Error.
+    ///
+    /// # Arguments
+    /// * `conn` - Pooled database connection
+    /// * `sql` - SQL query to execute
+    /// * `handle` - Query handle for tracking and cancellation
+    /// * `tx` - Channel sender for QueryEvent stream
+    ///
+    /// # Event Ordering
+    /// 1. `Columns` - Sent first with column metadata
+    /// 2. `FirstRow` - Once, with the time until the first row arrived
+    /// 3. `Rows` - Sent in batches of the connection's stream batch size
+    /// 4. `Progress` - Sent every 10,000 rows (optional)
+    /// 5. `Complete` or `Error` - Exactly one, as final event
+    ///
+    /// `Notice` events for anything the server raises while the query runs
+    /// (`RAISE NOTICE`, warnings) are interleaved in the order they arrived,
+    /// always ahead of the final event.
+    ///
+    /// If the connection has a cursor fetch size, SELECTs are streamed
+    /// through a server-side cursor instead (see
+    /// [`Self::execute_streaming_cursor`]).
+    pub async fn execute_streaming(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<QueryEvent>,
+    ) -> Result<(), TuskError> {
+        let mut notices = match conn.capture_notices().await {
+            Ok(notices) => notices,
+            Err(e) => {
+                tracing::debug!(
+                    query_id = %handle.id(),
+                    error = %e,
+                    "Streaming without notice capture"
+                );
+                return Self::stream_events(conn, sql, handle, tx).await;
+            }
+        };
+
+        // Events pass through here so notices can be merged into the stream
+        let (events_tx, mut events) = mpsc::channel(SCRIPT_EVENT_BUFFER);
+        let forward = async move {
+            loop {
+                let event = select! {
+                    biased;
+                    Some(notice) = notices.recv() => notice,
+                    event = events.recv() => {
+                        let Some(event) = event else { break };
+                        // A notice raised before the event was read goes first
+                        while let Some(notice) = notices.try_recv() {
+                            if tx.send(notice).await.is_err() {
+                                return;
+                            }
+                        }
+                        event
+                    }
+                };
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+        };
+        let (result, ()) = tokio::join!(Self::stream_events(conn, sql, handle, events_tx), forward);
+        result
+    }
+
+    /// Execute a query with streaming results like [`Self::execute_streaming`]
+    /// on a pool connection, keeping explicit transactions on one connection.
+    ///
+    /// After `BEGIN`, the pool pins a connection and every statement runs
+    /// on it until `COMMIT` or `ROLLBACK`; otherwise each statement would
+    /// land on whichever pooled connection is free and the transaction
+    /// would silently break. The pinned transaction's status is updated
+    /// from each statement's outcome (see [`ConnectionPool::transaction_status`]).
+    /// SELECTs in the transaction stream from a single query rather than a
+    /// cursor, whose own transaction would end the user's.
+    pub async fn execute_streaming_pooled(
+        pool: &ConnectionPool,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<QueryEvent>,
+    ) -> Result<(), TuskError> {
+        let control = Self::transaction_control(sql);
+        let txn = match pool.transaction() {
+            Some(txn) => txn,
+            None if control == Some(TransactionControl::Begin) => pool.pin_transaction().await?,
+            None => return Self::execute_streaming(&pool.get().await?, sql, handle, tx).await,
+        };
+
+        // Events pass through here to see whether the statement failed
+        let (events_tx, mut events) = mpsc::channel(SCRIPT_EVENT_BUFFER);
+        let watch = async move {
+            let mut failed = false;
+            while let Some(event) = events.recv().await {
+                failed |= matches!(event, QueryEvent::Error(_));
+                let _ = tx.send(event).await;
+            }
+            failed
+        };
+        let (result, failed) =
+            tokio::join!(Self::execute_streaming(txn.connection(), sql, handle, events_tx), watch);
+        let status = pool.record_transaction(&txn, control, !failed && result.is_ok());
+        tracing::debug!(
+            query_id = %handle.id(),
+            status = status.label(),
+            "Ran statement in transaction"
+        );
+        result
+    }
+
+    /// Stream a query's events, choosing a cursor or a single query.
+    async fn stream_events(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<QueryEvent>,
+    ) -> Result<(), TuskError> {
+        match conn.cursor_fetch_size() {
+            Some(fetch_size) if Self::declare_cursor_sql(sql).is_some() => {
+                Self::execute_streaming_cursor(conn, sql, handle, tx, fetch_size).await
+            }
+            _ => {
+                let batch_size = conn.stream_batch_size();
+                Self::execute_streaming_with_batch_size(conn, sql, handle, tx, batch_size).await
+            }
+        }
+    }
+
+    /// Stream a SELECT through a server-side cursor.
+    ///
+    /// Declares a cursor for the query inside a transaction and `FETCH`es
+    /// `fetch_size` rows at a time, so memory stays bounded by one fetch and
+    /// the first rows arrive before the whole result is computed. Each fetch
+    /// is sent as one Rows batch, in the same event order as
+    /// [`Self::execute_streaming`]. The transaction is committed once the
+    /// rows are exhausted and rolled back on error or cancellation; either
+    /// way the cursor is closed. Statements that can't back a cursor (see
+    /// [`Self::declare_cursor_sql`]) are streamed from a single query.
+    pub async fn execute_streaming_cursor(
+        conn: &PooledConnection,
+        sql: &str,
+        handle: &QueryHandle,
+        tx: mpsc::Sender<QueryEvent>,
+        fetch_size: usize,
+    ) -> Result<(), TuskError> {
+        let fetch_size = fetch_size.max(1);
+        let Some(declare) = Self::declare_cursor_sql(sql) else {
+            return Self::execute_streaming_with_batch_size(conn, sql, handle, tx, fetch_size)
+                .await;
+        };
+        let start = Instant::now();
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+
+        tracing::debug!(query_id = %handle.id(), fetch_size, "Executing query through a cursor");
+
+        if let Err(e) = conn.simple_execute("BEGIN").await {
+            let _ = tx.send(QueryEvent::error(e)).await;
+            return Ok(());
+        }
+        let result = Self::fetch_cursor(conn, &declare, handle, &tx, fetch_size, start).await;
+
+        // The connection goes back to the pool, so the transaction (and with
+        // it the cursor) must end even if the query failed or was cancelled
+        let end = if matches!(result, Ok(Some(_))) { "COMMIT" } else { "ROLLBACK" };
+        if let Err(e) = conn.simple_execute(end).await {
+            tracing::warn!(
+                query_id = %handle.id(),
+                error = %e,
+                "Failed to end cursor transaction"
+            );
+        }
+
+        match result {
+            Ok(Some(total_rows)) => {
+                let elapsed = start.elapsed();
+                tracing::debug!(
+                    query_id = %handle.id(),
+                    execution_time_us = duration_us(elapsed),
+                    total_rows,
+                    "Cursor query completed"
+                );
+                let _ = tx.send(QueryEvent::complete(total_rows, elapsed, None)).await;
+            }
+            // Receiver dropped, nobody is waiting for the end
+            Ok(None) => {}
+            Err(e) => {
+                let _ = tx.send(QueryEvent::error(e)).await;
+            }
+        }
+        Ok(())
+    }
 
-        let execution_time_ms = start.elapsed().as_millis() as u64;
+    /// Declare the stream cursor and send its rows a fetch at a time.
+    ///
+    /// Returns the number of rows sent, or None if the receiver was dropped.
+    async fn fetch_cursor(
+        conn: &PooledConnection,
+        declare: &str,
+        handle: &QueryHandle,
+        tx: &mpsc::Sender<QueryEvent>,
+        fetch_size: usize,
+        start: Instant,
+    ) -> Result<Option<usize>, TuskError> {
+        select! {
+            result = conn.simple_execute(declare) => {
+                result?;
+            }
+            _ = handle.cancelled() => return Err(TuskError::query_cancelled(handle.id())),
+        }
 
-        // Handle query completed before cancellation could propagate
-        // Per spec: return results normally if query completed (FR race handling)
-        let rows = result?;
+        let fetch = format!("FETCH FORWARD {fetch_size} FROM {STREAM_CURSOR}");
+        let mut columns_sent = false;
+        let mut total_rows: usize = 0;
+        let mut last_progress_at: usize = 0;
+        let mut first_row = FirstRowTimer::new(start);
 
-        // Extract column information
-        let columns = if rows.is_empty() {
-            Vec::new()
-        } else {
-            rows.first()
-                .map(|row| {
-                    row.columns()
-                        .iter()
-                        .map(|col| ColumnInfo {
-                            name: col.name().to_string(),
-                            type_oid: col.type_().oid(),
-                            type_name: col.type_().name().to_string(),
-                        })
-                        .collect()
-                })
-                .unwrap_or_default()
-        };
+        loop {
+            let rows = select! {
+                result = conn.query(fetch.as_str(), &[]) => result?,
+                _ = handle.cancelled() => {
+                    tracing::debug!(
+                        query_id = %handle.id(),
+                        rows_received = total_rows,
+                        "Cursor query cancelled during streaming"
+                    );
+                    return Err(TuskError::query_cancelled(handle.id()));
+                }
+            };
 
-        // Determine rows affected (for non-SELECT queries)
-        let rows_affected = match query_type {
-            QueryType::Select => None,
-            _ => Some(rows.len() as u64),
-        };
+            // Column metadata comes with the first fetch (FR-014)
+            if !columns_sent {
+                let columns: Vec<ColumnInfo> = rows
+                    .first()
+                    .map(|row| row.columns().iter().map(ColumnInfo::from).collect())
+                    .unwrap_or_default();
+                if tx.send(QueryEvent::columns(columns)).await.is_err() {
+                    return Ok(None);
+                }
+                columns_sent = true;
+            }
 
-        tracing::debug!(
-            query_id = %handle.id(),
-            execution_time_ms,
-            row_count = rows.len(),
-            "Query completed"
-        );
+            let fetched = rows.len();
+            if fetched == 0 {
+                break;
+            }
+            if let Some(event) = first_row.record_row() {
+                if tx.send(event).await.is_err() {
+                    return Ok(None);
+                }
+            }
 
-        Ok(QueryResult {
-            query_id: handle.id(),
-            columns,
-            rows,
-            rows_affected,
-            execution_time_ms,
-            query_type,
-        })
-    }
+            total_rows += fetched;
+            if tx.send(QueryEvent::rows(rows, total_rows)).await.is_err() {
+                return Ok(None);
+            }
+            if total_rows - last_progress_at >= PROGRESS_INTERVAL {
+                last_progress_at = total_rows;
+                let _ = tx.send(QueryEvent::progress(total_rows)).await;
+            }
 
-    /// Detect the type of SQL query.
-    pub fn detect_query_type(sql: &str) -> QueryType {
-        let trimmed = sql.trim_start().to_uppercase();
-
-        if trimmed.starts_with("SELECT") || trimmed.starts_with("WITH") {
-            QueryType::Select
-        } else if trimmed.starts_with("INSERT") {
-            QueryType::Insert
-        } else if trimmed.starts_with("UPDATE") {
-            QueryType::Update
-        } else if trimmed.starts_with("DELETE") {
-            QueryType::Delete
-        } else {
-            QueryType::Other
+            // A short fetch means the cursor is exhausted
+            if fetched < fetch_size {
+                break;
+            }
         }
+
+        Ok(Some(total_rows))
     }
 
-    /// Execute a query with streaming results via channel (FR-011, FR-012, FR-014).
+    /// Build the `DECLARE` that streams a statement through a cursor.
     ///
-    /// Sends QueryEvent messages through the provided channel as results arrive.
-    /// Events are sent in order: Columns, Rows (batches), then Complete or Error.
-    ///
-    /// # Arguments
-    /// * `conn` - Pooled database connection
-    /// * `sql` - SQL query to execute
-    /// * `handle` - Query handle for tracking and cancellation
-    /// * `tx` - Channel sender for QueryEvent stream
-    ///
-    /// # Event Ordering
-    /// 1. `Columns` - Sent first with column metadata
-    /// 2. `Rows` - Sent in batches of 1000 rows
-    /// 3. `Progress` - Sent every 10,000 rows (optional)
-    /// 4. `Complete` or `Error` - Exactly one, as final event
-    pub async fn execute_streaming(
-        conn: &PooledConnection,
-        sql: &str,
-        handle: &QueryHandle,
-        tx: mpsc::Sender<QueryEvent>,
-    ) -> Result<(), TuskError> {
-        Self::execute_streaming_with_batch_size(conn, sql, handle, tx, DEFAULT_BATCH_SIZE).await
+    /// Only a single read-only SELECT, VALUES or TABLE query can back a
+    /// cursor (a WITH query counts as its main statement). Returns None for
+    /// anything else.
+    pub fn declare_cursor_sql(sql: &str) -> Option<String> {
+        let statement = match Self::split_statements(sql).as_slice() {
+            [statement] => statement.trim(),
+            _ => return None,
+        };
+        let words = Self::top_level_words(statement);
+        let is_query = matches!(Self::main_keyword(&words), Some("SELECT" | "VALUES" | "TABLE"));
+        (is_query && Self::is_read_only_statement(statement))
+            .then(|| format!("DECLARE {STREAM_CURSOR} NO SCROLL CURSOR FOR {statement}"))
     }
 
     /// Execute a streaming query with custom batch size.
+    ///
+    /// Overrides the connection's stream batch size for a single query.
     pub async fn execute_streaming_with_batch_size(
         conn: &PooledConnection,
         sql: &str,
@@ -179,7 +1513,7 @@ impl QueryService {
         let query_type = Self::detect_query_type(sql);
 
         // Store the PostgreSQL cancel token for server-side cancellation (T031)
-        handle.set_pg_cancel_token(conn.cancel_token());
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
 
         tracing::debug!(
             query_id = %handle.id(),
@@ -188,9 +1522,41 @@ impl QueryService {
             "Executing streaming query"
         );
 
+        // VACUUM and friends can't run inside a transaction block, so they
+        // aren't prepared; everything else is prepared so statements without
+        // a result set can be told apart
+        let statement = if Self::uses_simple_protocol(sql, &[]) {
+            None
+        } else {
+            let prepared = select! {
+                result = conn.prepare(sql) => result,
+                _ = handle.cancelled() => {
+                    tracing::debug!(query_id = %handle.id(), "Query cancelled before execution");
+                    let _ = tx.send(QueryEvent::error(TuskError::query_cancelled(handle.id()))).await;
+                    return Ok(());
+                }
+            };
+            match prepared {
+                Ok(statement) => Some(statement),
+                Err(e) => {
+                    let _ = tx.send(QueryEvent::error(e)).await;
+                    return Ok(());
+                }
+            }
+        };
+
+        // SET, CALL, VACUUM and the like only report a command status
+        let statement = match statement {
+            Some(statement) if !statement.columns().is_empty() => statement,
+            statement => {
+                Self::stream_command(conn, sql, statement.as_ref(), handle, start, &tx).await;
+                return Ok(());
+            }
+        };
+
         // Execute query and get row stream
         let row_stream = select! {
-            result = conn.query_raw(sql, &[] as &[&(dyn tokio_postgres::types::ToSql + Sync)]) => {
+            result = conn.query_raw(&statement, &[] as &[&(dyn tokio_postgres::types::ToSql + Sync)]) => {
                 result
             }
             _ = handle.cancelled() => {
@@ -215,9 +1581,10 @@ impl QueryService {
 
         // Track if we've sent columns yet
         let mut columns_sent = false;
-        let mut batch: Vec<tokio_postgres::Row> = Vec::with_capacity(batch_size);
+        let mut batcher = RowBatcher::new(batch_size);
         let mut total_rows: usize = 0;
         let mut last_progress_at: usize = 0;
+        let mut first_row = FirstRowTimer::new(start);
 
         loop {
             // Check for cancellation before each batch
@@ -249,15 +1616,8 @@ impl QueryService {
                 Some(Ok(row)) => {
                     // Send column metadata on first row (FR-014)
                     if !columns_sent {
-                        let columns: Vec<ColumnInfo> = row
-                            .columns()
-                            .iter()
-                            .map(|col| ColumnInfo {
-                                name: col.name().to_string(),
-                                type_oid: col.type_().oid(),
-                                type_name: col.type_().name().to_string(),
-                            })
-                            .collect();
+                        let columns: Vec<ColumnInfo> =
+                            row.columns().iter().map(ColumnInfo::from).collect();
 
                         if tx.send(QueryEvent::columns(columns)).await.is_err() {
                             // Receiver dropped, stop streaming
@@ -266,13 +1626,17 @@ impl QueryService {
                         columns_sent = true;
                     }
 
-                    batch.push(row);
+                    // Time to first row, ahead of the batch that contains it (SC-002)
+                    if let Some(event) = first_row.record_row() {
+                        if tx.send(event).await.is_err() {
+                            return Ok(());
+                        }
+                    }
+
                     total_rows += 1;
 
                     // Send batch when full (FR-012)
-                    if batch.len() >= batch_size {
-                        let rows_to_send =
-                            std::mem::replace(&mut batch, Vec::with_capacity(batch_size));
+                    if let Some(rows_to_send) = batcher.push(row) {
                         if tx.send(QueryEvent::rows(rows_to_send, total_rows)).await.is_err() {
                             return Ok(());
                         }
@@ -304,7 +1668,7 @@ impl QueryService {
         }
 
         // Send any remaining rows in the final batch
-        if !batch.is_empty() {
+        if let Some(batch) = batcher.finish() {
             let _ = tx.send(QueryEvent::rows(batch, total_rows)).await;
         }
 
@@ -313,21 +1677,679 @@ impl QueryService {
             let _ = tx.send(QueryEvent::columns(Vec::new())).await;
         }
 
-        let execution_time_ms = start.elapsed().as_millis() as u64;
+        let elapsed = start.elapsed();
         let rows_affected = match query_type {
-            QueryType::Select => None,
+            QueryType::Select | QueryType::Explain => None,
             _ => Some(total_rows as u64),
         };
 
         tracing::debug!(
             query_id = %handle.id(),
-            execution_time_ms,
+            execution_time_us = duration_us(elapsed),
             total_rows,
             "Streaming query completed"
         );
 
-        let _ = tx.send(QueryEvent::complete(total_rows, execution_time_ms, rows_affected)).await;
+        let _ = tx.send(QueryEvent::complete(total_rows, elapsed, rows_affected)).await;
 
         Ok(())
     }
+
+    /// Run a statement without a result set and stream its command status.
+    async fn stream_command(
+        conn: &PooledConnection,
+        sql: &str,
+        statement: Option<&tokio_postgres::Statement>,
+        handle: &QueryHandle,
+        start: Instant,
+        tx: &mpsc::Sender<QueryEvent>,
+    ) {
+        match Self::execute_command(conn, sql, statement, &[], handle, start).await {
+            Ok(result) => {
+                if tx.send(QueryEvent::columns(Vec::new())).await.is_ok() {
+                    let event = QueryEvent::command_complete(
+                        result.command_tag.unwrap_or_default(),
+                        Duration::from_micros(result.execution_time_us),
+                        result.rows_affected,
+                    );
+                    let _ = tx.send(event).await;
+                }
+            }
+            Err(e) => {
+                let _ = tx.send(QueryEvent::error(e)).await;
+            }
+        }
+    }
+}
+
+/// Text of an `EXPLAIN (FORMAT JSON)` result, which has type `json`.
+struct PlanJson(String);
+
+impl<'a> FromSql<'a> for PlanJson {
+    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+        Ok(Self(std::str::from_utf8(raw)?.to_string()))
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        matches!(*ty, Type::JSON | Type::TEXT)
+    }
+}
+
+/// Convert an error from a `COPY ... FROM STDIN`, keeping the server's
+/// context, which names the line of data that failed.
+fn copy_error(error: tokio_postgres::Error) -> TuskError {
+    let Some(db) = error.as_db_error() else {
+        return TuskError::from(error);
+    };
+    let detail = match (db.detail(), db.where_()) {
+        (Some(detail), Some(context)) => Some(format!("{detail}\n{context}")),
+        (detail, context) => detail.or(context).map(String::from),
+    };
+    TuskError::query(
+        db.message(),
+        detail,
+        db.hint().map(String::from),
+        None,
+        Some(db.code().code().to_string()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_hides_literals() {
+        assert_eq!(
+            QueryService::fingerprint(
+                "SELECT *\n  FROM users -- active only\n WHERE email = 'a@b.c' AND id IN (1, 2.5)"
+            ),
+            "SELECT * FROM users WHERE email = ? AND id IN (?, ?)"
+        );
+        assert_eq!(
+            QueryService::fingerprint("UPDATE t1 SET note = E'it\\'s', \"Col 2\" = $1 /* c */;"),
+            "UPDATE t1 SET note = ?, \"Col 2\" = $1 ;"
+        );
+        assert_eq!(QueryService::fingerprint("SELECT $body$ secret $body$, 1e5"), "SELECT ?, ?");
+
+        let long = format!("SELECT {} FROM wide", vec!["column_name"; 20].join(", "));
+        let fingerprint = QueryService::fingerprint(&long);
+        assert_eq!(fingerprint.chars().count(), FINGERPRINT_MAX_CHARS + 1);
+        assert!(fingerprint.ends_with('…'));
+    }
+
+    #[test]
+    fn test_split_two_selects() {
+        let statements = QueryService::split_statements("SELECT 1 AS a; SELECT 'x' AS b, 2 AS c;");
+        assert_eq!(statements, vec!["SELECT 1 AS a", "SELECT 'x' AS b, 2 AS c"]);
+    }
+
+    #[test]
+    fn test_split_ignores_semicolons_in_literals_and_comments() {
+        let sql =
+            "SELECT 'a;b', \"c;d\", E'it\\'s;' -- trailing; comment\n; /* x; /* y; */ */ SELECT 2";
+        let statements = QueryService::split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].starts_with("SELECT 'a;b'"));
+        assert!(statements[1].ends_with("SELECT 2"));
+    }
+
+    #[test]
+    fn test_split_respects_dollar_quoted_bodies() {
+        let sql = "CREATE FUNCTION f() RETURNS int AS $body$ SELECT 1; $body$ LANGUAGE sql; \
+                   SELECT $1::int";
+        let statements = QueryService::split_statements(sql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("LANGUAGE sql"));
+        assert_eq!(statements[1], "SELECT $1::int");
+    }
+
+    #[test]
+    fn test_split_reports_statement_offsets() {
+        let sql = "SELECT 'a;b';\n  UPDATE t SET x = 1 ;\n\n-- done\nSELECT $$;$$";
+        let statements = QueryService::split_statements_with_offsets(sql);
+        assert_eq!(statements.len(), 3);
+        for (offset, statement) in &statements {
+            assert_eq!(&sql[*offset..*offset + statement.len()], *statement);
+        }
+        assert_eq!(statements[0], (0, "SELECT 'a;b'"));
+        assert_eq!(statements[1], (16, "UPDATE t SET x = 1"));
+        assert!(statements[2].1.ends_with("SELECT $$;$$"));
+        assert_eq!(
+            QueryService::split_statements(sql),
+            statements.iter().map(|(_, s)| *s).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_split_drops_empty_and_comment_only_fragments() {
+        let statements = QueryService::split_statements(";; SELECT 1;\n-- done\n");
+        assert_eq!(statements, vec!["SELECT 1"]);
+    }
+
+    #[test]
+    fn test_row_limit_detects_bare_selects() {
+        assert!(QueryService::needs_row_limit("SELECT * FROM orders"));
+        assert!(QueryService::needs_row_limit("select id from orders order by id offset 10"));
+        assert!(QueryService::needs_row_limit("SELECT 1 UNION ALL SELECT 2"));
+        assert!(QueryService::needs_row_limit("WITH recent AS (SELECT 1) SELECT * FROM recent"));
+
+        assert!(!QueryService::needs_row_limit("SELECT * FROM orders LIMIT 10"));
+        assert!(!QueryService::needs_row_limit("select * from orders limit all"));
+        assert!(!QueryService::needs_row_limit("SELECT * FROM orders FETCH FIRST 5 ROWS ONLY"));
+        assert!(!QueryService::needs_row_limit("SELECT * INTO archive FROM orders"));
+    }
+
+    #[test]
+    fn test_row_limit_ignores_nested_limits() {
+        assert!(QueryService::needs_row_limit(
+            "SELECT * FROM (SELECT * FROM orders LIMIT 5) o JOIN items i ON i.order_id = o.id"
+        ));
+        assert!(QueryService::needs_row_limit(
+            "WITH top AS (SELECT * FROM orders ORDER BY total DESC LIMIT 5) SELECT * FROM top"
+        ));
+        assert!(QueryService::needs_row_limit(
+            "SELECT id, (SELECT name FROM users u WHERE u.id = o.user_id LIMIT 1) FROM orders o"
+        ));
+        // Keywords in literals and comments don't count
+        assert!(QueryService::needs_row_limit("SELECT 'LIMIT 5', \"limit\" FROM t /* LIMIT */"));
+        assert!(QueryService::needs_row_limit("SELECT $$ LIMIT 1 $$ -- limit 1"));
+    }
+
+    #[test]
+    fn test_row_limit_skips_other_statements() {
+        for sql in [
+            "INSERT INTO t SELECT * FROM s",
+            "UPDATE t SET a = 1",
+            "DELETE FROM t",
+            "EXPLAIN SELECT * FROM t",
+            "WITH moved AS (DELETE FROM t RETURNING *) INSERT INTO archive SELECT * FROM moved",
+            "CREATE TABLE t2 AS SELECT * FROM t",
+            "VALUES (1), (2)",
+        ] {
+            assert!(!QueryService::needs_row_limit(sql), "{}", sql);
+        }
+        assert_eq!(QueryService::apply_row_limit("UPDATE t SET a = 1", 100), None);
+        assert_eq!(QueryService::apply_row_limit("SELECT 1 LIMIT 1;", 100), None);
+    }
+
+    #[test]
+    fn test_command_tags_become_status_messages() {
+        let status = |sql, rows_affected| {
+            crate::models::command_status(&QueryService::command_tag(sql), rows_affected)
+        };
+
+        assert_eq!(status("SET work_mem = '64MB'", None), "SET");
+        assert_eq!(status("begin", None), "BEGIN");
+        assert_eq!(
+            status("START TRANSACTION ISOLATION LEVEL SERIALIZABLE", None),
+            "START TRANSACTION"
+        );
+        assert_eq!(status("END", None), "COMMIT");
+        assert_eq!(status("CALL archive_orders(30)", None), "CALL");
+        assert_eq!(status("VACUUM (ANALYZE) orders", None), "VACUUM");
+        assert_eq!(status("-- tidy up\nRESET ALL", None), "RESET");
+        assert_eq!(status("UPDATE orders SET total = 0", Some(3)), "UPDATE 3");
+        assert_eq!(status("INSERT INTO orders VALUES (1)", Some(1)), "INSERT 0 1");
+    }
+
+    #[test]
+    fn test_command_tag_names_ddl_objects() {
+        assert_eq!(QueryService::command_tag("CREATE OR REPLACE FUNCTION f()"), "CREATE FUNCTION");
+        assert_eq!(QueryService::command_tag("create unique index i on t (a)"), "CREATE INDEX");
+        assert_eq!(QueryService::command_tag("CREATE TEMP TABLE scratch (a int)"), "CREATE TABLE");
+        assert_eq!(
+            QueryService::command_tag("DROP MATERIALIZED VIEW IF EXISTS totals"),
+            "DROP MATERIALIZED VIEW"
+        );
+        assert_eq!(QueryService::command_tag("ALTER TABLE t ADD COLUMN b int"), "ALTER TABLE");
+        assert_eq!(
+            QueryService::command_tag("WITH old AS (SELECT 1) DELETE FROM t USING old"),
+            "DELETE"
+        );
+
+        assert!(QueryService::command_counts_rows("UPDATE"));
+        assert!(QueryService::command_counts_rows("COPY"));
+        assert!(!QueryService::command_counts_rows("CALL"));
+        assert!(!QueryService::command_counts_rows("CREATE TABLE"));
+    }
+
+    #[test]
+    fn test_non_transactional_commands_are_detected() {
+        for sql in [
+            "VACUUM",
+            "vacuum (analyze, verbose) orders",
+            "CREATE INDEX CONCURRENTLY idx ON orders (id)",
+            "CREATE UNIQUE INDEX CONCURRENTLY idx ON orders (id)",
+            "DROP INDEX CONCURRENTLY idx",
+            "REINDEX (VERBOSE) TABLE CONCURRENTLY orders",
+            "REINDEX DATABASE shop",
+            "CREATE DATABASE shop",
+            "DROP DATABASE IF EXISTS shop",
+            "CREATE TABLESPACE fast LOCATION '/ssd'",
+            "ALTER SYSTEM SET work_mem = '64MB'",
+            "ALTER DATABASE shop SET TABLESPACE fast",
+            "CREATE SUBSCRIPTION sub CONNECTION 'host=a' PUBLICATION pub",
+            "DISCARD ALL",
+            "COMMIT PREPARED 'tx1'",
+            "CLUSTER",
+        ] {
+            assert!(QueryService::is_non_transactional(sql), "{}", sql);
+        }
+
+        for sql in [
+            "SELECT 'VACUUM'",
+            "-- VACUUM\nSELECT 1",
+            "ANALYZE orders",
+            "CREATE INDEX idx ON orders (id)",
+            "REINDEX TABLE orders",
+            "ALTER DATABASE shop SET work_mem = '64MB'",
+            "CLUSTER orders USING orders_pkey",
+            "DISCARD PLANS",
+        ] {
+            assert!(!QueryService::is_non_transactional(sql), "{}", sql);
+        }
+    }
+
+    #[test]
+    fn test_vacuum_is_sent_outside_a_transaction() {
+        // The simple protocol sends the statement alone, without the
+        // implicit transaction of a prepared statement
+        assert!(QueryService::uses_simple_protocol("VACUUM orders", &[]));
+        assert!(QueryService::uses_simple_protocol("CREATE INDEX CONCURRENTLY i ON t (a)", &[]));
+        assert!(!QueryService::uses_simple_protocol("SELECT 1", &[]));
+        assert!(!QueryService::uses_simple_protocol("SET work_mem = '64MB'", &[]));
+
+        // Scripts run each statement separately, so VACUUM stays on its own
+        assert_eq!(
+            QueryService::split_statements("UPDATE t SET a = 1; VACUUM t"),
+            vec!["UPDATE t SET a = 1", "VACUUM t"]
+        );
+        assert!(QueryService::uses_simple_protocol("VACUUM t", &[]));
+    }
+
+    #[test]
+    fn test_search_path_change_reads_last_set() {
+        assert_eq!(
+            QueryService::search_path_change("SET search_path TO sales, public").as_deref(),
+            Some("sales, public")
+        );
+        assert_eq!(
+            QueryService::search_path_change(
+                "-- switch schema\nSET SESSION search_path = \"Sales\"; SELECT 1"
+            )
+            .as_deref(),
+            Some("\"Sales\"")
+        );
+        // The last change in a script wins
+        assert_eq!(
+            QueryService::search_path_change("SET search_path TO a; SET search_path TO b")
+                .as_deref(),
+            Some("b")
+        );
+        assert_eq!(
+            QueryService::search_path_change("SET search_path TO DEFAULT").as_deref(),
+            Some("")
+        );
+        assert_eq!(QueryService::search_path_change("RESET search_path").as_deref(), Some(""));
+        // SET LOCAL only lasts until the transaction ends
+        assert_eq!(QueryService::search_path_change("SET LOCAL search_path TO a"), None);
+        assert_eq!(QueryService::search_path_change("SET statement_timeout = 0"), None);
+        assert_eq!(QueryService::search_path_change("SELECT 'SET search_path TO a'"), None);
+    }
+
+    #[test]
+    fn test_destructive_statements_are_flagged() {
+        assert!(QueryService::is_destructive("DROP TABLE users"));
+        assert!(QueryService::is_destructive("truncate users"));
+        assert!(QueryService::is_destructive("DELETE FROM users"));
+        assert!(QueryService::is_destructive("UPDATE users SET active = false"));
+        assert!(QueryService::is_destructive("ALTER TABLE users DROP COLUMN email"));
+        // A WHERE inside a subquery doesn't filter the outer DELETE
+        assert!(QueryService::is_destructive(
+            "DELETE FROM users USING (SELECT id FROM old WHERE x) o"
+        ));
+
+        assert!(!QueryService::is_destructive("DELETE FROM users WHERE id = 1"));
+        assert!(!QueryService::is_destructive("UPDATE users SET a = 1 WHERE id = 1"));
+        assert!(!QueryService::is_destructive("ALTER TABLE users ADD COLUMN email text"));
+        assert!(!QueryService::is_destructive("SELECT 'DROP TABLE users'"));
+
+        assert_eq!(
+            QueryService::destructive_statements("SELECT 1; DROP TABLE t; SELECT 2"),
+            vec!["DROP TABLE t"]
+        );
+    }
+
+    #[test]
+    fn test_prod_forces_confirmation_for_writes() {
+        let update = "UPDATE users SET active = false WHERE id = 1";
+        // The heuristic lets a filtered UPDATE through...
+        assert!(!QueryService::is_destructive(update));
+        assert_eq!(QueryService::confirmation_reason(update, ConnectionEnvironment::Dev), None);
+        assert_eq!(QueryService::confirmation_reason(update, ConnectionEnvironment::Staging), None);
+        // ...but production always asks
+        assert_eq!(
+            QueryService::confirmation_reason(update, ConnectionEnvironment::Prod),
+            Some(ConfirmationReason::Production)
+        );
+        assert_eq!(
+            QueryService::confirmation_reason(
+                "INSERT INTO t VALUES (1); CREATE INDEX i ON t (a)",
+                ConnectionEnvironment::Prod
+            ),
+            Some(ConfirmationReason::Production)
+        );
+
+        // Destructive statements need confirmation everywhere
+        assert_eq!(
+            QueryService::confirmation_reason("DROP TABLE t", ConnectionEnvironment::Dev),
+            Some(ConfirmationReason::Destructive(vec!["DROP TABLE t".to_string()]))
+        );
+    }
+
+    #[test]
+    fn test_prod_reads_run_without_confirmation() {
+        for sql in [
+            "SELECT * FROM users",
+            "WITH recent AS (SELECT * FROM t) SELECT * FROM recent",
+            "EXPLAIN SELECT 1",
+            "SHOW search_path",
+        ] {
+            assert_eq!(QueryService::confirmation_reason(sql, ConnectionEnvironment::Prod), None);
+        }
+
+        for sql in [
+            "SELECT * INTO backup FROM users",
+            "WITH gone AS (DELETE FROM t WHERE a RETURNING *) SELECT * FROM gone",
+            "EXPLAIN ANALYZE DELETE FROM t WHERE a",
+        ] {
+            assert_eq!(
+                QueryService::confirmation_reason(sql, ConnectionEnvironment::Prod),
+                Some(ConfirmationReason::Production),
+                "{sql}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_explain_is_its_own_query_type() {
+        for sql in [
+            "EXPLAIN SELECT 1",
+            "explain (ANALYZE) SELECT * FROM users",
+            "  EXPLAIN ANALYZE VERBOSE DELETE FROM t",
+        ] {
+            assert_eq!(QueryService::detect_query_type(sql), QueryType::Explain, "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_ddl_and_utility_query_types() {
+        for sql in [
+            "CREATE TABLE t (id int)",
+            "create index on t (id)",
+            "ALTER TABLE t ADD COLUMN name text",
+            "DROP VIEW IF EXISTS v",
+            "TRUNCATE t",
+            "COMMENT ON TABLE t IS 'notes'",
+        ] {
+            assert_eq!(QueryService::detect_query_type(sql), QueryType::Ddl, "{sql}");
+        }
+        for sql in [
+            "SET search_path TO app",
+            "show work_mem",
+            "RESET ALL",
+            "VACUUM (ANALYZE) t",
+            "ANALYZE t",
+            "REINDEX TABLE t",
+        ] {
+            assert_eq!(QueryService::detect_query_type(sql), QueryType::Utility, "{sql}");
+        }
+        for sql in ["COPY t TO STDOUT", "BEGIN", "COMMIT", "SETTINGS"] {
+            assert_eq!(QueryService::detect_query_type(sql), QueryType::Other, "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_changes_schema_checks_every_statement() {
+        assert!(QueryService::changes_schema("SELECT 1; CREATE TABLE t (id int);"));
+        assert!(QueryService::changes_schema("  drop table t"));
+        assert!(QueryService::changes_schema("/* setup */ CREATE TABLE t (id int)"));
+        assert!(!QueryService::changes_schema("SELECT 'CREATE TABLE t'; SET work_mem = '64MB'"));
+        assert!(!QueryService::changes_schema("-- DROP TABLE t\nSELECT 1"));
+    }
+
+    #[test]
+    fn test_estimate_sql_only_plans_single_statements() {
+        assert_eq!(
+            QueryService::estimate_sql("SELECT * FROM orders WHERE id = 1;").unwrap(),
+            "EXPLAIN (FORMAT JSON) SELECT * FROM orders WHERE id = 1"
+        );
+        assert!(QueryService::estimate_sql("WITH x AS (SELECT 1) DELETE FROM t").is_ok());
+
+        for sql in [
+            "",
+            "-- nothing",
+            "SELECT 1; DELETE FROM t",
+            "EXPLAIN ANALYZE DELETE FROM t",
+            "DROP TABLE t",
+            "VACUUM t",
+        ] {
+            assert!(QueryService::estimate_sql(sql).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_declare_cursor_only_for_read_only_queries() {
+        assert_eq!(
+            QueryService::declare_cursor_sql("SELECT * FROM events;").as_deref(),
+            Some("DECLARE tusk_stream NO SCROLL CURSOR FOR SELECT * FROM events")
+        );
+        for sql in
+            ["VALUES (1), (2)", "TABLE events", "WITH recent AS (SELECT 1) SELECT * FROM recent"]
+        {
+            assert!(QueryService::declare_cursor_sql(sql).is_some(), "{sql}");
+        }
+        for sql in [
+            "",
+            "SELECT 1; SELECT 2",
+            "SELECT * INTO copy FROM events",
+            "WITH gone AS (DELETE FROM events RETURNING *) SELECT * FROM gone",
+            "DELETE FROM events RETURNING *",
+            "SHOW work_mem",
+            "EXPLAIN SELECT 1",
+        ] {
+            assert!(QueryService::declare_cursor_sql(sql).is_none(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_copy_out_sql_exports_read_only_queries() {
+        let options = CopyOptions::default();
+        assert_eq!(
+            QueryService::copy_out_sql("SELECT * FROM orders;", &options).unwrap(),
+            "COPY (SELECT * FROM orders) TO STDOUT WITH (FORMAT csv, HEADER true)"
+        );
+        assert!(QueryService::copy_out_sql("TABLE orders", &options).is_ok());
+
+        for refused in ["", "SELECT 1; SELECT 2", "DELETE FROM orders RETURNING *"] {
+            let err = QueryService::copy_out_sql(refused, &options).unwrap_err();
+            assert!(err.hint().is_some(), "{refused}");
+        }
+        assert!(QueryService::copy_out_sql(
+            "WITH gone AS (DELETE FROM orders RETURNING id) SELECT * FROM gone",
+            &options
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_copy_in_sql_quotes_target() {
+        let options = CopyOptions::default();
+        assert_eq!(
+            QueryService::copy_in_sql("public", "orders", &[], &options).unwrap(),
+            "COPY public.orders FROM STDIN WITH (FORMAT csv, HEADER true)"
+        );
+        assert_eq!(
+            QueryService::copy_in_sql("Sales", "order", &["id", "Total"], &options).unwrap(),
+            "COPY \"Sales\".\"order\" (id, \"Total\") FROM STDIN WITH (FORMAT csv, HEADER true)"
+        );
+    }
+
+    #[test]
+    fn test_transaction_control_classifies_statements() {
+        use TransactionControl::*;
+
+        for (sql, expected) in [
+            ("BEGIN", Some(Begin)),
+            ("begin isolation level serializable;", Some(Begin)),
+            ("START TRANSACTION READ ONLY", Some(Begin)),
+            ("-- done\nCOMMIT", Some(Commit)),
+            ("END", Some(Commit)),
+            ("PREPARE TRANSACTION 'tx1'", Some(Commit)),
+            ("ROLLBACK", Some(Rollback)),
+            ("abort", Some(Rollback)),
+            ("ROLLBACK TO SAVEPOINT before_update", Some(RollbackToSavepoint)),
+            ("ROLLBACK TO before_update", Some(RollbackToSavepoint)),
+            ("COMMIT AND CHAIN", Some(Chain)),
+            ("ROLLBACK AND NO CHAIN", Some(Rollback)),
+            ("COMMIT PREPARED 'tx1'", None),
+            ("SELECT 'BEGIN'", None),
+            ("DO $$ BEGIN PERFORM 1; END $$", None),
+        ] {
+            assert_eq!(QueryService::transaction_control(sql), expected, "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_explain_plan_refuses_analyze_of_writes() {
+        assert_eq!(
+            QueryService::explain_plan_sql("SELECT * FROM orders;", false, false).unwrap(),
+            "EXPLAIN (FORMAT JSON) SELECT * FROM orders"
+        );
+        assert_eq!(
+            QueryService::explain_plan_sql("SELECT * FROM orders", true, false).unwrap(),
+            "EXPLAIN (FORMAT JSON, ANALYZE true) SELECT * FROM orders"
+        );
+        assert!(QueryService::explain_plan_sql("DELETE FROM orders", false, false).is_ok());
+
+        let refused = QueryService::explain_plan_sql("DELETE FROM orders", true, false);
+        assert!(refused.unwrap_err().hint().is_some());
+        assert!(QueryService::explain_plan_sql(
+            "WITH gone AS (DELETE FROM orders RETURNING id) SELECT * FROM gone",
+            true,
+            false
+        )
+        .is_err());
+        assert_eq!(
+            QueryService::explain_plan_sql("DELETE FROM orders", true, true).unwrap(),
+            "EXPLAIN (FORMAT JSON, ANALYZE true) DELETE FROM orders"
+        );
+
+        for sql in ["", "SELECT 1; SELECT 2", "EXPLAIN SELECT 1", "VACUUM t"] {
+            assert!(QueryService::explain_plan_sql(sql, false, true).is_err(), "{sql}");
+        }
+    }
+
+    #[test]
+    fn test_explain_analyze_is_detected() {
+        for sql in [
+            "EXPLAIN ANALYZE SELECT 1",
+            "explain analyse select 1",
+            "EXPLAIN (ANALYZE) SELECT 1",
+            "EXPLAIN (FORMAT JSON, ANALYZE true, BUFFERS) SELECT 1",
+            "EXPLAIN ( analyze on ) SELECT 1",
+        ] {
+            assert!(QueryService::is_explain_analyze(sql), "{sql}");
+        }
+        for sql in [
+            "EXPLAIN SELECT 1",
+            "EXPLAIN VERBOSE SELECT 1",
+            "EXPLAIN (ANALYZE false) DELETE FROM t",
+            "EXPLAIN (COSTS off) SELECT analyze FROM t",
+            "ANALYZE users",
+            "EXPLAINANALYZE",
+        ] {
+            assert!(!QueryService::is_explain_analyze(sql), "{sql}");
+        }
+
+        // The option list form runs the statement too, so it isn't read-only
+        assert!(!QueryService::is_read_only_statement("EXPLAIN (ANALYZE) DELETE FROM t"));
+        assert!(QueryService::is_read_only_statement("EXPLAIN (ANALYZE off) DELETE FROM t"));
+    }
+
+    #[test]
+    fn test_apply_row_limit_appends_limit() {
+        assert_eq!(
+            QueryService::apply_row_limit("SELECT * FROM t;", 100).as_deref(),
+            Some("SELECT * FROM t\nLIMIT 100")
+        );
+        // A trailing line comment can't comment out the limit
+        assert_eq!(
+            QueryService::apply_row_limit("SELECT * FROM t -- all rows", 100).as_deref(),
+            Some("SELECT * FROM t -- all rows\nLIMIT 100")
+        );
+        assert_eq!(
+            QueryService::apply_row_limit("SELECT 1; UPDATE t SET a = 1; SELECT 2 LIMIT 3", 10)
+                .as_deref(),
+            Some("SELECT 1\nLIMIT 10;\nUPDATE t SET a = 1;\nSELECT 2 LIMIT 3")
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_script_tags_events_and_stops_at_first_error() {
+        let pool = crate::test_support::local_pool(1).await;
+        let sql = "SELECT 1 AS a;\n  SELECT 1 / 0;\nSELECT 3";
+        let handle = QueryHandle::new(pool.id(), sql);
+        let (tx, mut rx) = mpsc::channel(64);
+        let summary =
+            QueryService::execute_script_pooled(&pool, sql, &handle, tx).await.expect("script");
+
+        let mut terminal = Vec::new();
+        while let Some(ScriptEvent { statement, offset, event }) = rx.recv().await {
+            if event.is_terminal() {
+                terminal.push((statement, offset, matches!(event, QueryEvent::Error(_))));
+            }
+        }
+        assert_eq!(terminal, [(1, 0, false), (2, 17, true)]);
+        assert_eq!((summary.succeeded, summary.failed, summary.not_run), (1, 1, 1));
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_script_runs_on_pinned_transaction() {
+        use crate::models::TransactionStatus;
+
+        let pool = crate::test_support::local_pool(2).await;
+        let run = |sql: &'static str| {
+            let pool = &pool;
+            async move {
+                let handle = QueryHandle::new(pool.id(), sql);
+                let (tx, mut rx) = mpsc::channel(64);
+                let summary = QueryService::execute_script_pooled(pool, sql, &handle, tx)
+                    .await
+                    .expect("script");
+                let mut pids = Vec::new();
+                while let Some(ScriptEvent { event, .. }) = rx.recv().await {
+                    if let QueryEvent::Rows { rows, .. } = event {
+                        pids.extend(rows.iter().map(|row| row.get::<_, i32>(0)));
+                    }
+                }
+                (summary, pids)
+            }
+        };
+
+        let (_, pids) = run("BEGIN; SELECT pg_backend_pid()").await;
+        assert_eq!(pool.transaction_status(), TransactionStatus::InTransaction);
+        let (_, more) = run("SELECT pg_backend_pid(); SELECT pg_backend_pid()").await;
+        assert!(more.iter().all(|pid| *pid == pids[0]), "{pids:?} {more:?}");
+
+        let (summary, _) = run("SELECT 1 / 0; SELECT 1").await;
+        assert_eq!(summary.not_run, 1);
+        assert_eq!(pool.transaction_status(), TransactionStatus::Failed);
+
+        run("ROLLBACK; SELECT 1").await;
+        assert!(pool.transaction().is_none());
+    }
 }
@@ -4,12 +4,39 @@
 //! including schemas, tables, views, functions, and columns.
 
 use std::collections::HashMap;
+use std::future::Future;
+use std::pin::pin;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tokio::select;
 
 use crate::error::TuskError;
+use crate::models::connection::ConnectionConfig;
+use crate::models::query::QueryHandle;
+use crate::models::query_builder;
 use crate::models::schema::{
-    ColumnDetail, DatabaseSchema, FunctionInfo, SchemaInfo, TableInfo, ViewInfo,
+    ColumnDetail, ColumnSample, ConstraintInfo, ConstraintKind, DatabaseSchema, FunctionInfo,
+    IndexInfo, SchemaInfo, SchemaObjectKind, SearchHit, SearchMatchKind, TableInfo, ViewInfo,
 };
-use crate::services::connection::PooledConnection;
+use crate::services::connection::{ConnectionPool, PooledConnection};
+use crate::services::ddl::quote_ident;
+
+/// How long a cancelled introspection query gets to stop on the server
+/// before its connection is returned to the pool anyway.
+const CANCEL_SETTLE_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Number of most frequent values returned by a column sample by default.
+pub const DEFAULT_SAMPLE_LIMIT: usize = 10;
+
+/// Upper bound on the number of values a column sample returns.
+pub const MAX_SAMPLE_LIMIT: usize = 100;
+
+/// Most tables and views whose columns are loaded with the schema.
+///
+/// Beyond this, columns are left out and loaded per object with
+/// [`SchemaService::load_table_columns`], keeping the initial load fast.
+pub const EAGER_COLUMN_LIMIT: usize = 500;
 
 /// Schema introspection service.
 ///
@@ -20,34 +47,102 @@ pub struct SchemaService;
 impl SchemaService {
     /// Load complete schema information for the connected database.
     ///
-    /// This loads schemas, tables, views, functions, and all columns.
-    pub async fn load_schema(conn: &PooledConnection) -> Result<DatabaseSchema, TuskError> {
-        let schemas = Self::load_schemas(conn).await?;
-        let tables = Self::load_tables(conn).await?;
-        let views = Self::load_views(conn).await?;
-        let functions = Self::load_functions(conn).await?;
-
-        // Load columns for all tables and views
+    /// This loads schemas, tables, views, functions, indexes, constraints, and
+    /// all columns. When there are more than [`EAGER_COLUMN_LIMIT`] tables and
+    /// views, columns are left to be loaded per object as they're needed.
+    /// Cancelling `handle` stops the load between queries and cancels the
+    /// query in flight on the server, returning a query_cancelled error.
+    pub async fn load_schema(
+        conn: &PooledConnection,
+        handle: &QueryHandle,
+    ) -> Result<DatabaseSchema, TuskError> {
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+        tracing::debug!(query_id = %handle.id(), "Loading schema");
+
+        let schemas = Self::cancellable(handle, Self::load_schemas(conn)).await?;
+        let tables = Self::cancellable(handle, Self::load_tables(conn)).await?;
+        let views = Self::cancellable(handle, Self::load_views(conn)).await?;
+        let functions = Self::cancellable(handle, Self::load_functions(conn)).await?;
+        let indexes = Self::cancellable(handle, Self::load_indexes(conn)).await?;
+        let constraints = Self::cancellable(handle, Self::load_constraints(conn)).await?;
+
+        // Load columns for all tables and views, unless there are too many
         let mut table_columns: HashMap<(String, String), Vec<ColumnDetail>> = HashMap::new();
         let mut view_columns: HashMap<(String, String), Vec<ColumnDetail>> = HashMap::new();
+        let relations = tables.len() + views.len();
+        if relations > EAGER_COLUMN_LIMIT {
+            tracing::debug!(
+                query_id = %handle.id(),
+                relations,
+                "Deferring column loads until objects are expanded"
+            );
+        } else {
+            for table in &tables {
+                let columns =
+                    Self::cancellable(handle, Self::load_columns(conn, &table.schema, &table.name))
+                        .await?;
+                table_columns.insert((table.schema.clone(), table.name.clone()), columns);
+            }
+
+            for view in &views {
+                let columns =
+                    Self::cancellable(handle, Self::load_columns(conn, &view.schema, &view.name))
+                        .await?;
+                view_columns.insert((view.schema.clone(), view.name.clone()), columns);
+            }
+        }
+
+        tracing::debug!(
+            query_id = %handle.id(),
+            tables = tables.len(),
+            views = views.len(),
+            indexes = indexes.len(),
+            constraints = constraints.len(),
+            "Schema loaded"
+        );
+        Ok(DatabaseSchema {
+            schemas,
+            tables,
+            views,
+            functions,
+            table_columns,
+            view_columns,
+            indexes,
+            constraints,
+        })
+    }
 
-        for table in &tables {
-            let columns = Self::load_columns(conn, &table.schema, &table.name).await?;
-            table_columns.insert((table.schema.clone(), table.name.clone()), columns);
+    /// Run one introspection query, stopping it if `handle` is cancelled.
+    ///
+    /// Cancelling the handle also cancels the query on the server (see
+    /// [`QueryHandle::cancel`]); the query is then given a moment to stop so
+    /// the connection is idle again when it goes back to the pool.
+    async fn cancellable<T>(
+        handle: &QueryHandle,
+        query: impl Future<Output = Result<T, TuskError>>,
+    ) -> Result<T, TuskError> {
+        if handle.is_cancelled() {
+            return Err(TuskError::query_cancelled(handle.id()));
         }
 
-        for view in &views {
-            let columns = Self::load_columns(conn, &view.schema, &view.name).await?;
-            view_columns.insert((view.schema.clone(), view.name.clone()), columns);
+        let mut query = pin!(query);
+        select! {
+            result = &mut query => return result,
+            _ = handle.cancelled() => {}
         }
+        tracing::debug!(query_id = %handle.id(), "Schema load cancelled");
 
-        Ok(DatabaseSchema { schemas, tables, views, functions, table_columns, view_columns })
+        // Only a query on a connection was sent the server cancel
+        if handle.get_pg_cancel_token().is_some() {
+            let _ = tokio::time::timeout(CANCEL_SETTLE_TIMEOUT, query).await;
+        }
+        Err(TuskError::query_cancelled(handle.id()))
     }
 
     /// Load all schemas (excluding system schemas by default).
     pub async fn load_schemas(conn: &PooledConnection) -> Result<Vec<SchemaInfo>, TuskError> {
         let rows = conn
-            .query(
+            .query_cached(
                 r#"
                 SELECT
                     n.nspname AS name,
@@ -70,16 +165,23 @@ impl SchemaService {
     /// Load all tables in the database.
     pub async fn load_tables(conn: &PooledConnection) -> Result<Vec<TableInfo>, TuskError> {
         let rows = conn
-            .query(
+            .query_cached(
                 r#"
                 SELECT
                     n.nspname AS schema,
                     c.relname AS name,
                     pg_get_userbyid(c.relowner) AS owner,
                     c.reltuples::bigint AS estimated_rows,
-                    pg_table_size(c.oid) AS size_bytes
+                    pg_table_size(c.oid) AS size_bytes,
+                    EXTRACT(EPOCH FROM s.last_vacuum)::float8 AS last_vacuum,
+                    EXTRACT(EPOCH FROM s.last_autovacuum)::float8 AS last_autovacuum,
+                    EXTRACT(EPOCH FROM s.last_analyze)::float8 AS last_analyze,
+                    EXTRACT(EPOCH FROM s.last_autoanalyze)::float8 AS last_autoanalyze,
+                    COALESCE(s.n_dead_tup, 0) AS dead_tuples,
+                    obj_description(c.oid, 'pg_class') AS comment
                 FROM pg_catalog.pg_class c
                 JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
+                LEFT JOIN pg_catalog.pg_stat_user_tables s ON s.relid = c.oid
                 WHERE c.relkind = 'r'
                   AND n.nspname NOT LIKE 'pg_%'
                   AND n.nspname != 'information_schema'
@@ -97,6 +199,12 @@ impl SchemaService {
                 owner: row.get("owner"),
                 estimated_rows: row.get("estimated_rows"),
                 size_bytes: row.get("size_bytes"),
+                last_vacuum: epoch_to_datetime(row.get("last_vacuum")),
+                last_autovacuum: epoch_to_datetime(row.get("last_autovacuum")),
+                last_analyze: epoch_to_datetime(row.get("last_analyze")),
+                last_autoanalyze: epoch_to_datetime(row.get("last_autoanalyze")),
+                dead_tuples: row.get("dead_tuples"),
+                comment: row.get("comment"),
             })
             .collect())
     }
@@ -104,13 +212,14 @@ impl SchemaService {
     /// Load all views in the database.
     pub async fn load_views(conn: &PooledConnection) -> Result<Vec<ViewInfo>, TuskError> {
         let rows = conn
-            .query(
+            .query_cached(
                 r#"
                 SELECT
                     n.nspname AS schema,
                     c.relname AS name,
                     pg_get_userbyid(c.relowner) AS owner,
-                    c.relkind = 'm' AS is_materialized
+                    c.relkind = 'm' AS is_materialized,
+                    obj_description(c.oid, 'pg_class') AS comment
                 FROM pg_catalog.pg_class c
                 JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
                 WHERE c.relkind IN ('v', 'm')
@@ -129,6 +238,7 @@ impl SchemaService {
                 name: row.get("name"),
                 owner: row.get("owner"),
                 is_materialized: row.get("is_materialized"),
+                comment: row.get("comment"),
             })
             .collect())
     }
@@ -136,7 +246,7 @@ impl SchemaService {
     /// Load all functions in the database.
     pub async fn load_functions(conn: &PooledConnection) -> Result<Vec<FunctionInfo>, TuskError> {
         let rows = conn
-            .query(
+            .query_cached(
                 r#"
                 SELECT
                     n.nspname AS schema,
@@ -147,7 +257,8 @@ impl SchemaService {
                         WHEN 'i' THEN 'IMMUTABLE'
                         WHEN 's' THEN 'STABLE'
                         WHEN 'v' THEN 'VOLATILE'
-                    END AS volatility
+                    END AS volatility,
+                    obj_description(p.oid, 'pg_proc') AS comment
                 FROM pg_catalog.pg_proc p
                 JOIN pg_catalog.pg_namespace n ON n.oid = p.pronamespace
                 WHERE n.nspname NOT LIKE 'pg_%'
@@ -167,6 +278,126 @@ impl SchemaService {
                 return_type: row.get("return_type"),
                 arguments: row.get("arguments"),
                 volatility: row.get("volatility"),
+                comment: row.get("comment"),
+            })
+            .collect())
+    }
+
+    /// Load all indexes on tables in the database.
+    ///
+    /// Key columns come from `pg_get_indexdef`, so expression keys are
+    /// rendered as their SQL text. Included (non-key) columns are omitted.
+    pub async fn load_indexes(conn: &PooledConnection) -> Result<Vec<IndexInfo>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT
+                    n.nspname AS schema,
+                    t.relname AS table_name,
+                    i.relname AS name,
+                    ARRAY(
+                        SELECT pg_get_indexdef(x.indexrelid, k, true)
+                        FROM generate_series(1, x.indnkeyatts::integer) AS k
+                        ORDER BY k
+                    ) AS columns,
+                    x.indisunique AS is_unique,
+                    x.indisprimary AS is_primary,
+                    am.amname::text AS method,
+                    pg_get_expr(x.indpred, x.indrelid, true) AS predicate
+                FROM pg_catalog.pg_index x
+                JOIN pg_catalog.pg_class i ON i.oid = x.indexrelid
+                JOIN pg_catalog.pg_class t ON t.oid = x.indrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+                JOIN pg_catalog.pg_am am ON am.oid = i.relam
+                WHERE t.relkind = 'r'
+                  AND n.nspname NOT LIKE 'pg_%'
+                  AND n.nspname != 'information_schema'
+                ORDER BY n.nspname, t.relname, i.relname
+                "#,
+                &[],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| IndexInfo {
+                schema: row.get("schema"),
+                table: row.get("table_name"),
+                name: row.get("name"),
+                columns: row.get("columns"),
+                is_unique: row.get("is_unique"),
+                is_primary: row.get("is_primary"),
+                method: row.get("method"),
+                predicate: row.get("predicate"),
+            })
+            .collect())
+    }
+
+    /// Load all primary key, foreign key, unique, check, and exclusion
+    /// constraints on tables in the database.
+    pub async fn load_constraints(
+        conn: &PooledConnection,
+    ) -> Result<Vec<ConstraintInfo>, TuskError> {
+        let rows = conn
+            .query_cached(
+                r#"
+                SELECT
+                    n.nspname AS schema,
+                    t.relname AS table_name,
+                    c.conname AS name,
+                    c.contype::text AS kind,
+                    ARRAY(
+                        SELECT a.attname::text
+                        FROM unnest(c.conkey) WITH ORDINALITY AS k(attnum, ord)
+                        JOIN pg_catalog.pg_attribute a
+                          ON a.attrelid = c.conrelid AND a.attnum = k.attnum
+                        ORDER BY k.ord
+                    ) AS columns,
+                    pg_get_constraintdef(c.oid, true) AS definition,
+                    rn.nspname AS referenced_schema,
+                    rt.relname AS referenced_table,
+                    ARRAY(
+                        SELECT a.attname::text
+                        FROM unnest(c.confkey) WITH ORDINALITY AS k(attnum, ord)
+                        JOIN pg_catalog.pg_attribute a
+                          ON a.attrelid = c.confrelid AND a.attnum = k.attnum
+                        ORDER BY k.ord
+                    ) AS referenced_columns,
+                    c.condeferrable AS is_deferrable,
+                    c.condeferred AS is_deferred,
+                    c.convalidated AS is_validated
+                FROM pg_catalog.pg_constraint c
+                JOIN pg_catalog.pg_class t ON t.oid = c.conrelid
+                JOIN pg_catalog.pg_namespace n ON n.oid = t.relnamespace
+                LEFT JOIN pg_catalog.pg_class rt ON rt.oid = c.confrelid
+                LEFT JOIN pg_catalog.pg_namespace rn ON rn.oid = rt.relnamespace
+                WHERE t.relkind IN ('r', 'p')
+                  AND c.contype IN ('p', 'f', 'u', 'c', 'x')
+                  AND n.nspname NOT LIKE 'pg_%'
+                  AND n.nspname != 'information_schema'
+                ORDER BY n.nspname, t.relname, c.conname
+                "#,
+                &[],
+            )
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|row| {
+                Some(ConstraintInfo {
+                    kind: ConstraintKind::from_code(row.get("kind"))?,
+                    schema: row.get("schema"),
+                    table: row.get("table_name"),
+                    name: row.get("name"),
+                    columns: row.get("columns"),
+                    definition: row.get("definition"),
+                    referenced_schema: row.get("referenced_schema"),
+                    referenced_table: row.get("referenced_table"),
+                    referenced_columns: row.get("referenced_columns"),
+                    is_deferrable: row.get("is_deferrable"),
+                    is_deferred: row.get("is_deferred"),
+                    is_validated: row.get("is_validated"),
+                })
             })
             .collect())
     }
@@ -178,7 +409,7 @@ impl SchemaService {
         table: &str,
     ) -> Result<Vec<ColumnDetail>, TuskError> {
         let rows = conn
-            .query(
+            .query_cached(
                 r#"
                 SELECT
                     a.attname AS name,
@@ -192,7 +423,8 @@ impl SchemaService {
                         FALSE
                     ) AS is_primary_key,
                     pg_get_expr(d.adbin, d.adrelid) AS default_value,
-                    a.attnum::integer AS ordinal_position
+                    a.attnum::integer AS ordinal_position,
+                    col_description(a.attrelid, a.attnum) AS comment
                 FROM pg_catalog.pg_attribute a
                 JOIN pg_catalog.pg_class c ON c.oid = a.attrelid
                 JOIN pg_catalog.pg_namespace n ON n.oid = c.relnamespace
@@ -216,7 +448,785 @@ impl SchemaService {
                 is_primary_key: row.get("is_primary_key"),
                 default_value: row.get("default_value"),
                 ordinal_position: row.get("ordinal_position"),
+                comment: row.get("comment"),
             })
             .collect())
     }
+
+    /// Load the columns of one table or view without reloading the schema.
+    ///
+    /// Used to fill in a single object on demand, such as when it's expanded
+    /// or its details are shown. Returns no columns if the object doesn't exist.
+    pub async fn load_table_columns(
+        pool: &ConnectionPool,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ColumnDetail>, TuskError> {
+        let conn = pool.get().await?;
+        Self::load_columns(&conn, schema, table).await
+    }
+
+    /// Sample the value distribution of one column.
+    ///
+    /// Returns the `limit` most frequent non-null values (clamped to
+    /// 1..=[`MAX_SAMPLE_LIMIT`]) with their counts, plus the row and NULL
+    /// totals. Values are compared as text, so types without an equality
+    /// operator (such as json) can be sampled too. This scans the whole
+    /// table; cancelling `handle` also cancels the query on the server.
+    pub async fn sample_column(
+        pool: &ConnectionPool,
+        schema: &str,
+        table: &str,
+        column: &str,
+        limit: usize,
+        handle: &QueryHandle,
+    ) -> Result<ColumnSample, TuskError> {
+        let limit = limit.clamp(1, MAX_SAMPLE_LIMIT);
+        let sql = column_sample_sql(schema, table, column);
+        tracing::debug!(
+            query_id = %handle.id(),
+            schema = %schema,
+            table = %table,
+            column = %column,
+            limit,
+            "Sampling column values"
+        );
+
+        let conn = Self::cancellable(handle, pool.get()).await?;
+        let _pg_cancel = handle.track_pg_cancel(conn.cancel_token());
+        // One extra row leaves room for the NULL group, which sorts last
+        let fetch = (limit + 1) as i64;
+        let rows = Self::cancellable(handle, conn.query(sql.as_str(), &[&fetch])).await?;
+
+        let (total_rows, null_rows) =
+            rows.first().map_or((0, 0), |row| (row.get("total_rows"), row.get("null_rows")));
+        let counts = rows.iter().map(|row| (row.get("value"), row.get("frequency")));
+        Ok(ColumnSample::from_counts(counts, total_rows, null_rows, limit))
+    }
+
+    /// Search the cached schema for objects, columns, and comments containing `query`.
+    ///
+    /// Matching is case-insensitive. `kinds` limits the object types searched
+    /// (empty = all). Exact name matches sort first, then name, column, and
+    /// comment matches, each ordered by path.
+    pub fn search_objects(
+        schema: &DatabaseSchema,
+        query: &str,
+        kinds: &[SchemaObjectKind],
+    ) -> Vec<SearchHit> {
+        let needle = query.trim().to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let wants = |kind| kinds.is_empty() || kinds.contains(&kind);
+        let matches = |text: &str| text.to_lowercase().contains(&needle);
+
+        let mut hits = Vec::new();
+        let mut search_relation =
+            |kind: SchemaObjectKind,
+             schema_name: &str,
+             name: &str,
+             comment: Option<&str>,
+             columns: Option<&Vec<ColumnDetail>>| {
+                let hit = |column: Option<&str>, match_kind, text: &str| SearchHit {
+                    object_kind: kind,
+                    schema: schema_name.to_string(),
+                    object: name.to_string(),
+                    column: column.map(String::from),
+                    match_kind,
+                    matched_text: text.to_string(),
+                };
+                if matches(name) {
+                    hits.push(hit(None, SearchMatchKind::Name, name));
+                }
+                if let Some(comment) = comment.filter(|c| matches(c)) {
+                    hits.push(hit(None, SearchMatchKind::Comment, comment));
+                }
+                for column in columns.into_iter().flatten() {
+                    if matches(&column.name) {
+                        hits.push(hit(Some(&column.name), SearchMatchKind::Column, &column.name));
+                    }
+                    if let Some(comment) = column.comment.as_deref().filter(|c| matches(c)) {
+                        hits.push(hit(Some(&column.name), SearchMatchKind::Comment, comment));
+                    }
+                }
+            };
+
+        if wants(SchemaObjectKind::Table) {
+            for table in &schema.tables {
+                let key = (table.schema.clone(), table.name.clone());
+                search_relation(
+                    SchemaObjectKind::Table,
+                    &table.schema,
+                    &table.name,
+                    table.comment.as_deref(),
+                    schema.table_columns.get(&key),
+                );
+            }
+        }
+        if wants(SchemaObjectKind::View) {
+            for view in &schema.views {
+                let key = (view.schema.clone(), view.name.clone());
+                search_relation(
+                    SchemaObjectKind::View,
+                    &view.schema,
+                    &view.name,
+                    view.comment.as_deref(),
+                    schema.view_columns.get(&key),
+                );
+            }
+        }
+        if wants(SchemaObjectKind::Function) {
+            for func in &schema.functions {
+                let signature = format!("{}({})", func.name, func.arguments);
+                let hit = |match_kind, text: &str| SearchHit {
+                    object_kind: SchemaObjectKind::Function,
+                    schema: func.schema.clone(),
+                    object: signature.clone(),
+                    column: None,
+                    match_kind,
+                    matched_text: text.to_string(),
+                };
+                if matches(&func.name) {
+                    hits.push(hit(SearchMatchKind::Name, &func.name));
+                }
+                if let Some(comment) = func.comment.as_deref().filter(|c| matches(c)) {
+                    hits.push(hit(SearchMatchKind::Comment, comment));
+                }
+            }
+        }
+
+        hits.sort_by_cached_key(|hit| {
+            let exact = hit.match_kind != SearchMatchKind::Comment
+                && hit.matched_text.to_lowercase() == needle;
+            (!exact, hit.match_kind, hit.path())
+        });
+        hits
+    }
+
+    /// Refresh a materialized view.
+    ///
+    /// Refused on read-only connections. A concurrent refresh keeps the view
+    /// readable while it runs but requires a unique index on the view.
+    pub async fn refresh_materialized_view(
+        pool: &ConnectionPool,
+        schema: &str,
+        name: &str,
+        concurrently: bool,
+    ) -> Result<(), TuskError> {
+        ensure_writable(pool.config(), "refresh", schema, name)?;
+
+        let sql = refresh_materialized_view_sql(schema, name, concurrently);
+        tracing::debug!(schema = %schema, view = %name, concurrently, "Refreshing materialized view");
+
+        let conn = pool.get().await?;
+        match conn.execute(sql.as_str(), &[]).await {
+            Ok(_) => Ok(()),
+            // 55000: object_not_in_prerequisite_state, raised when no unique index exists
+            Err(TuskError::Query { message, detail, position, code, .. })
+                if concurrently && code.as_deref() == Some("55000") =>
+            {
+                Err(TuskError::query(
+                    message,
+                    detail,
+                    Some(format!(
+                        "REFRESH CONCURRENTLY needs a unique index on {}.{} covering all rows; \
+                         create one or refresh without CONCURRENTLY",
+                        schema, name
+                    )),
+                    position,
+                    code,
+                ))
+            }
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A destructive maintenance operation on a table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableOperation {
+    /// Remove every row, keeping the table.
+    Truncate,
+    /// Remove the table itself.
+    Drop,
+}
+
+impl TableOperation {
+    /// Build the statement for a table.
+    pub fn sql(self, schema: &str, name: &str) -> String {
+        match self {
+            Self::Truncate => query_builder::truncate(schema, name),
+            Self::Drop => query_builder::drop_table(schema, name),
+        }
+    }
+
+    /// Refuse the operation on a read-only connection.
+    pub fn ensure_allowed(
+        self,
+        config: &ConnectionConfig,
+        schema: &str,
+        name: &str,
+    ) -> Result<(), TuskError> {
+        ensure_writable(config, self.verb(), schema, name)
+    }
+
+    /// Lowercase verb for messages, e.g. "truncate".
+    pub fn verb(self) -> &'static str {
+        match self {
+            Self::Truncate => "truncate",
+            Self::Drop => "drop",
+        }
+    }
+}
+
+/// Refuse to change `schema.name` on a read-only connection.
+pub(crate) fn ensure_writable(
+    config: &ConnectionConfig,
+    verb: &str,
+    schema: &str,
+    name: &str,
+) -> Result<(), TuskError> {
+    if !config.options.read_only {
+        return Ok(());
+    }
+    Err(TuskError::query(
+        format!("Cannot {} {}.{}: connection is read-only", verb, schema, name),
+        None,
+        Some(format!("Disable read-only mode in the connection settings to {} it", verb)),
+        None,
+        None,
+    ))
+}
+
+/// Convert a `pg_stat` timestamp, selected as epoch seconds, to a UTC datetime.
+fn epoch_to_datetime(epoch: Option<f64>) -> Option<DateTime<Utc>> {
+    epoch.and_then(|secs| DateTime::from_timestamp_micros((secs * 1_000_000.0).round() as i64))
+}
+
+/// Build the query behind [`SchemaService::sample_column`].
+///
+/// Groups the column's text values, most frequent first with NULL last, and
+/// carries the row and NULL totals on every row. `$1` is the row limit.
+pub fn column_sample_sql(schema: &str, table: &str, column: &str) -> String {
+    format!(
+        "SELECT value, frequency, \
+         sum(frequency) OVER ()::bigint AS total_rows, \
+         COALESCE(sum(frequency) FILTER (WHERE value IS NULL) OVER (), 0)::bigint AS null_rows \
+         FROM (SELECT {}::text AS value, count(*) AS frequency FROM {}.{} GROUP BY 1) AS counts \
+         ORDER BY value IS NULL, frequency DESC, value \
+         LIMIT $1",
+        quote_ident(column),
+        quote_ident(schema),
+        quote_ident(table)
+    )
+}
+
+/// Build the `REFRESH MATERIALIZED VIEW` statement for a view.
+pub fn refresh_materialized_view_sql(schema: &str, name: &str, concurrently: bool) -> String {
+    format!(
+        "REFRESH MATERIALIZED VIEW {}{}.{}",
+        if concurrently { "CONCURRENTLY " } else { "" },
+        quote_ident(schema),
+        quote_ident(name)
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::schema::fixtures;
+    use crate::test_support::local_pool;
+
+    fn table(
+        last_analyze: Option<DateTime<Utc>>,
+        last_autoanalyze: Option<DateTime<Utc>>,
+    ) -> TableInfo {
+        TableInfo {
+            estimated_rows: 100,
+            size_bytes: 8192,
+            last_analyze,
+            last_autoanalyze,
+            dead_tuples: 12,
+            ..fixtures::table("public", "orders")
+        }
+    }
+
+    fn column(name: &str, comment: Option<&str>) -> ColumnDetail {
+        ColumnDetail {
+            name: name.to_string(),
+            data_type: "text".to_string(),
+            is_nullable: true,
+            is_primary_key: false,
+            default_value: None,
+            ordinal_position: 1,
+            comment: comment.map(String::from),
+        }
+    }
+
+    fn search_schema() -> DatabaseSchema {
+        let mut users = table(None, None);
+        users.name = "users".to_string();
+        let mut customers = table(None, None);
+        customers.name = "customers".to_string();
+        customers.comment = Some("Billing contacts, keyed by email".to_string());
+        let mut emails = table(None, None);
+        emails.name = "email".to_string();
+
+        let mut schema = DatabaseSchema {
+            tables: vec![users, customers, emails],
+            views: vec![ViewInfo {
+                schema: "public".to_string(),
+                name: "active_users".to_string(),
+                owner: "postgres".to_string(),
+                is_materialized: false,
+                comment: None,
+            }],
+            functions: vec![FunctionInfo {
+                schema: "public".to_string(),
+                name: "normalize_email".to_string(),
+                return_type: "text".to_string(),
+                arguments: "text".to_string(),
+                volatility: "IMMUTABLE".to_string(),
+                comment: None,
+            }],
+            ..Default::default()
+        };
+        let key = |name: &str| ("public".to_string(), name.to_string());
+        schema.table_columns.insert(key("users"), vec![column("id", None), column("email", None)]);
+        schema.table_columns.insert(
+            key("customers"),
+            vec![column("contact_email", None), column("phone", Some("Primary email fallback"))],
+        );
+        schema.view_columns.insert(key("active_users"), vec![column("email", None)]);
+        schema
+    }
+
+    #[test]
+    fn test_search_finds_columns_in_multiple_tables() {
+        let hits = SchemaService::search_objects(&search_schema(), "EMAIL", &[]);
+        let paths: Vec<String> = hits.iter().map(|h| h.path()).collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                // Exact name matches first
+                "public.email",
+                "public.active_users.email",
+                "public.users.email",
+                "public.normalize_email(text)",
+                "public.customers.contact_email",
+                "public.customers",
+                "public.customers.phone",
+            ]
+        );
+        assert_eq!(hits[0].match_kind, SearchMatchKind::Name);
+        assert_eq!(hits[1].match_kind, SearchMatchKind::Column);
+        assert_eq!(hits[5].match_kind, SearchMatchKind::Comment);
+        assert_eq!(hits[6].matched_text, "Primary email fallback");
+    }
+
+    #[test]
+    fn test_search_kinds_limit_object_types() {
+        let schema = search_schema();
+
+        let views = SchemaService::search_objects(&schema, "email", &[SchemaObjectKind::View]);
+        assert_eq!(views.len(), 1);
+        assert!(views.iter().all(|h| h.object_kind == SchemaObjectKind::View));
+
+        let functions =
+            SchemaService::search_objects(&schema, "email", &[SchemaObjectKind::Function]);
+        assert_eq!(functions.len(), 1);
+        assert_eq!(functions[0].object, "normalize_email(text)");
+
+        assert!(SchemaService::search_objects(&schema, "  ", &[]).is_empty());
+    }
+
+    #[test]
+    fn test_epoch_to_datetime() {
+        let analyzed = epoch_to_datetime(Some(1_700_000_000.25)).expect("timestamp");
+        assert_eq!(analyzed.to_rfc3339(), "2023-11-14T22:13:20.250+00:00");
+        assert_eq!(epoch_to_datetime(None), None);
+    }
+
+    #[test]
+    fn test_last_analyzed_prefers_most_recent_run() {
+        let manual = epoch_to_datetime(Some(1_700_000_000.0));
+        let auto = epoch_to_datetime(Some(1_700_003_600.0));
+
+        assert_eq!(table(manual, auto).last_analyzed(), auto);
+        assert_eq!(table(manual, None).last_analyzed(), manual);
+        assert_eq!(table(None, None).last_analyzed(), None);
+        assert_eq!(table(None, None).last_vacuumed(), None);
+    }
+
+    #[test]
+    fn test_refresh_materialized_view_sql() {
+        assert_eq!(
+            refresh_materialized_view_sql("public", "daily_sales", false),
+            "REFRESH MATERIALIZED VIEW public.daily_sales"
+        );
+        assert_eq!(
+            refresh_materialized_view_sql("public", "daily_sales", true),
+            "REFRESH MATERIALIZED VIEW CONCURRENTLY public.daily_sales"
+        );
+    }
+
+    #[test]
+    fn test_refresh_materialized_view_sql_quotes_identifiers() {
+        assert_eq!(
+            refresh_materialized_view_sql("Reporting", "sales \"summary\"", true),
+            "REFRESH MATERIALIZED VIEW CONCURRENTLY \"Reporting\".\"sales \"\"summary\"\"\""
+        );
+        assert_eq!(
+            refresh_materialized_view_sql("public", "user", false),
+            "REFRESH MATERIALIZED VIEW public.\"user\""
+        );
+    }
+
+    #[test]
+    fn test_table_operation_sql() {
+        assert_eq!(TableOperation::Truncate.sql("s", "t"), "TRUNCATE TABLE s.t;");
+        assert_eq!(TableOperation::Drop.sql("s", "t"), "DROP TABLE s.t;");
+        assert_eq!(
+            TableOperation::Truncate.sql("Sales", "order lines"),
+            "TRUNCATE TABLE \"Sales\".\"order lines\";"
+        );
+        assert_eq!(TableOperation::Drop.sql("Sales", "user"), "DROP TABLE \"Sales\".\"user\";");
+    }
+
+    #[test]
+    fn test_read_only_connections_refuse_table_changes() {
+        use crate::models::ConnectionConfig;
+
+        let mut config = ConnectionConfig::new("local", "localhost", "app", "postgres");
+        assert!(ensure_writable(&config, "drop", "public", "orders").is_ok());
+
+        config.options.read_only = true;
+        for operation in [TableOperation::Truncate, TableOperation::Drop] {
+            let err = operation.ensure_allowed(&config, "public", "orders").unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                format!("Cannot {} public.orders: connection is read-only", operation.verb())
+            );
+            assert!(err.hint().unwrap().contains("read-only mode"));
+        }
+    }
+
+    #[test]
+    fn test_column_sample_sql_quotes_identifiers() {
+        let sql = column_sample_sql("Sales", "order lines", "status");
+        assert!(sql.contains("SELECT status::text AS value, count(*) AS frequency"));
+        assert!(sql.contains("FROM \"Sales\".\"order lines\" GROUP BY 1"));
+        assert!(sql.contains("ORDER BY value IS NULL, frequency DESC, value"));
+        assert!(sql.ends_with("LIMIT $1"));
+
+        let sql = column_sample_sql("public", "users", "select");
+        assert!(sql.contains("SELECT \"select\"::text AS value"));
+    }
+
+    #[test]
+    fn test_column_sample_from_counts() {
+        let counts = vec![
+            (Some("open".to_string()), 5),
+            (Some("closed".to_string()), 3),
+            (Some("void".to_string()), 1),
+            (None, 1),
+        ];
+        let sample = ColumnSample::from_counts(counts, 10, 1, 2);
+
+        let values: Vec<_> =
+            sample.top_values.iter().map(|v| (v.value.as_str(), v.count)).collect();
+        assert_eq!(values, [("open", 5), ("closed", 3)]);
+        assert_eq!(sample.null_fraction(), 0.1);
+        assert_eq!(sample.fraction(sample.top_values[0].count), 0.5);
+
+        let empty = ColumnSample::from_counts(Vec::new(), 0, 0, DEFAULT_SAMPLE_LIMIT);
+        assert!(empty.top_values.is_empty());
+        assert_eq!(empty.null_fraction(), 0.0);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_load_tables_reports_vacuum_and_analyze() {
+        let pool = local_pool(1).await;
+        let conn = pool.get().await.unwrap();
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let table = format!("{}.events", quote_ident(&schema));
+        for sql in [
+            format!("CREATE SCHEMA {}", quote_ident(&schema)),
+            format!("CREATE TABLE {table} (id int)"),
+            format!("INSERT INTO {table} SELECT generate_series(1, 100)"),
+            format!("DELETE FROM {table} WHERE id <= 40"),
+            format!("ANALYZE {table}"),
+        ] {
+            conn.execute(sql.as_str(), &[]).await.unwrap();
+        }
+
+        // Older servers publish statistics with a short delay
+        let (conn, schema) = (&conn, schema.as_str());
+        let stats = move || async move {
+            SchemaService::load_tables(conn)
+                .await
+                .unwrap()
+                .into_iter()
+                .find(|t| t.schema == schema && t.name == "events")
+                .unwrap()
+        };
+        let mut analyzed = stats().await;
+        for _ in 0..50 {
+            if analyzed.last_analyze.is_some() && analyzed.dead_tuples > 0 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            analyzed = stats().await;
+        }
+
+        conn.execute(format!("VACUUM {table}").as_str(), &[]).await.unwrap();
+        let mut vacuumed = stats().await;
+        for _ in 0..50 {
+            if vacuumed.last_vacuum.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            vacuumed = stats().await;
+        }
+        conn.execute(format!("DROP SCHEMA {} CASCADE", quote_ident(&schema)).as_str(), &[])
+            .await
+            .unwrap();
+
+        assert!(analyzed.last_analyzed().is_some(), "{analyzed:?}");
+        assert!(analyzed.dead_tuples > 0, "{analyzed:?}");
+        assert!(vacuumed.last_vacuumed().is_some(), "{vacuumed:?}");
+        assert!(vacuumed.last_vacuum >= analyzed.last_analyze, "{vacuumed:?}");
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_sample_column_counts_values_and_nulls() {
+        let pool = local_pool(4).await;
+
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let conn = pool.get().await.unwrap();
+        conn.execute(format!("CREATE SCHEMA {}", quote_ident(&schema)).as_str(), &[])
+            .await
+            .unwrap();
+        conn.execute(
+            format!("CREATE TABLE {}.tickets (status text)", quote_ident(&schema)).as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+        conn.execute(
+            format!(
+                "INSERT INTO {}.tickets VALUES \
+                 ('open'), ('open'), ('open'), ('closed'), ('closed'), ('void'), (NULL), (NULL)",
+                quote_ident(&schema)
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        let handle = QueryHandle::new(uuid::Uuid::new_v4(), "sample column");
+        let sample =
+            SchemaService::sample_column(&pool, &schema, "tickets", "status", 2, &handle).await;
+        let conn = pool.get().await.unwrap();
+        conn.execute(format!("DROP SCHEMA {} CASCADE", quote_ident(&schema)).as_str(), &[])
+            .await
+            .unwrap();
+
+        let sample = sample.unwrap();
+        let values: Vec<_> =
+            sample.top_values.iter().map(|v| (v.value.as_str(), v.count)).collect();
+        assert_eq!(values, [("open", 3), ("closed", 2)]);
+        assert_eq!(sample.total_rows, 8);
+        assert_eq!(sample.null_rows, 2);
+        assert_eq!(sample.null_fraction(), 0.25);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_load_table_columns_flags_keys_and_defaults() {
+        let pool = local_pool(4).await;
+
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let conn = pool.get().await.unwrap();
+        conn.execute(format!("CREATE SCHEMA {}", quote_ident(&schema)).as_str(), &[])
+            .await
+            .unwrap();
+        conn.execute(
+            format!(
+                "CREATE TABLE {}.order_lines (
+                    order_id integer,
+                    line_no smallint,
+                    status text NOT NULL DEFAULT 'open',
+                    note varchar(200),
+                    PRIMARY KEY (order_id, line_no)
+                )",
+                quote_ident(&schema)
+            )
+            .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+        conn.execute(
+            format!("COMMENT ON COLUMN {}.order_lines.note IS 'Free text'", quote_ident(&schema))
+                .as_str(),
+            &[],
+        )
+        .await
+        .unwrap();
+        drop(conn);
+
+        let columns = SchemaService::load_table_columns(&pool, &schema, "order_lines").await;
+        let missing = SchemaService::load_table_columns(&pool, &schema, "missing").await;
+        let conn = pool.get().await.unwrap();
+        conn.execute(format!("DROP SCHEMA {} CASCADE", quote_ident(&schema)).as_str(), &[])
+            .await
+            .unwrap();
+
+        let columns = columns.unwrap();
+        let names: Vec<_> = columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["order_id", "line_no", "status", "note"]);
+        assert!(columns[0].is_primary_key && columns[1].is_primary_key);
+        assert!(!columns[2].is_primary_key && !columns[3].is_primary_key);
+        assert!(!columns[0].is_nullable && !columns[2].is_nullable && columns[3].is_nullable);
+        assert_eq!(columns[2].default_value.as_deref(), Some("'open'::text"));
+        assert_eq!(columns[0].default_value, None);
+        assert_eq!(columns[3].data_type, "character varying(200)");
+        assert_eq!(columns[3].comment.as_deref(), Some("Free text"));
+        assert!(missing.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_load_indexes_includes_partial_and_expression_keys() {
+        let pool = local_pool(4).await;
+
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let qualified = quote_ident(&schema);
+        let conn = pool.get().await.unwrap();
+        conn.simple_execute(&format!(
+            "CREATE SCHEMA {qualified};
+             CREATE TABLE {qualified}.users (id integer PRIMARY KEY, email text, active boolean);
+             CREATE UNIQUE INDEX users_email_key ON {qualified}.users (lower(email))
+                 WHERE active;
+             CREATE INDEX users_email_hash ON {qualified}.users USING hash (email);"
+        ))
+        .await
+        .unwrap();
+
+        let indexes = SchemaService::load_indexes(&conn).await;
+        conn.execute(format!("DROP SCHEMA {qualified} CASCADE").as_str(), &[]).await.unwrap();
+
+        let indexes: Vec<_> = indexes.unwrap().into_iter().filter(|i| i.schema == schema).collect();
+        let names: Vec<_> = indexes.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, ["users_email_hash", "users_email_key", "users_pkey"]);
+        assert_eq!(indexes[0].method, "hash");
+        assert_eq!(indexes[1].columns, ["lower(email)"]);
+        assert_eq!(indexes[1].predicate.as_deref(), Some("active"));
+        assert!(indexes[1].is_unique && !indexes[1].is_primary);
+        assert!(indexes[2].is_primary);
+        assert_eq!(indexes[2].columns, ["id"]);
+        assert_eq!(indexes[2].predicate, None);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires a running PostgreSQL server"]
+    async fn test_load_constraints_captures_references_and_status() {
+        let pool = local_pool(4).await;
+
+        let schema = format!("tusk_test_{}", uuid::Uuid::new_v4().simple());
+        let qualified = quote_ident(&schema);
+        let conn = pool.get().await.unwrap();
+        conn.simple_execute(&format!(
+            "CREATE SCHEMA {qualified};
+             CREATE TABLE {qualified}.customers (id integer PRIMARY KEY, email text UNIQUE);
+             CREATE TABLE {qualified}.orders (
+                 id integer PRIMARY KEY,
+                 customer_id integer,
+                 qty integer
+             );
+             ALTER TABLE {qualified}.orders ADD CONSTRAINT orders_customer_fk
+                 FOREIGN KEY (customer_id) REFERENCES {qualified}.customers (id)
+                 DEFERRABLE INITIALLY DEFERRED;
+             ALTER TABLE {qualified}.orders ADD CONSTRAINT orders_qty_check
+                 CHECK (qty > 0) NOT VALID;
+             CREATE TABLE {qualified}.visits (at date PRIMARY KEY) PARTITION BY RANGE (at);"
+        ))
+        .await
+        .unwrap();
+
+        let constraints = SchemaService::load_constraints(&conn).await;
+        conn.execute(format!("DROP SCHEMA {qualified} CASCADE").as_str(), &[]).await.unwrap();
+
+        let constraints: Vec<_> =
+            constraints.unwrap().into_iter().filter(|c| c.schema == schema).collect();
+        let names: Vec<_> = constraints.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "customers_email_key",
+                "customers_pkey",
+                "orders_customer_fk",
+                "orders_pkey",
+                "orders_qty_check",
+                "visits_pkey"
+            ]
+        );
+        assert_eq!(constraints[0].kind, ConstraintKind::Unique);
+        assert_eq!(constraints[1].kind, ConstraintKind::PrimaryKey);
+
+        let fk = &constraints[2];
+        assert_eq!(fk.kind, ConstraintKind::ForeignKey);
+        assert_eq!(fk.columns, ["customer_id"]);
+        assert_eq!(fk.references(), Some((schema.as_str(), "customers")));
+        assert_eq!(fk.referenced_columns, ["id"]);
+        assert!(fk.is_deferrable && fk.is_deferred && fk.is_validated);
+
+        let check = &constraints[4];
+        assert_eq!(check.kind, ConstraintKind::Check);
+        assert!(check.definition.starts_with("CHECK"));
+        assert!(!check.is_validated && !check.is_deferrable);
+        assert_eq!(check.references(), None);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_stops_introspection_promptly() {
+        let handle = std::sync::Arc::new(QueryHandle::new(uuid::Uuid::new_v4(), "load schema"));
+        let canceller = handle.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        // Stands in for a catalog query that would otherwise never finish
+        let started = std::time::Instant::now();
+        let result =
+            SchemaService::cancellable(&handle, std::future::pending::<Result<(), TuskError>>())
+                .await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(
+            matches!(result, Err(TuskError::QueryCancelled { query_id }) if query_id == handle.id())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_load_skips_remaining_queries() {
+        let handle = QueryHandle::new(uuid::Uuid::new_v4(), "load schema");
+        handle.cancel();
+
+        let started = std::cell::Cell::new(false);
+        let result = SchemaService::cancellable(&handle, async {
+            started.set(true);
+            Ok(())
+        })
+        .await;
+        assert!(!started.get());
+        assert!(result.unwrap_err().is_cancelled());
+    }
 }
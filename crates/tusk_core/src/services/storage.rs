@@ -1,6 +1,6 @@
 //! Local SQLite storage for application metadata.
 //!
-//! Stores saved connections, query history, saved queries, and UI state.
+//! Stores saved connections, query history, saved queries, snippets, and UI state.
 //! Credentials are NOT stored here—they use the OS keychain via CredentialService.
 //!
 //! # Data Directory Locations
@@ -12,15 +12,23 @@
 
 use crate::error::TuskError;
 use crate::models::{
-    ConnectionConfig, ConnectionOptions, QueryHistoryEntry, SshAuthMethod, SshTunnelConfig, SslMode,
+    psql_files, snippet, ConnectionConfig, ConnectionEnvironment, ConnectionOptions,
+    QueryHistoryEntry, Snippet, SshAuthMethod, SshTunnelConfig, SslMode,
 };
 
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use rusqlite::{params, Connection, OptionalExtension};
-use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
+/// Version of the connection bundle written by [`LocalStorage::export_connections`].
+///
+/// Bump when the document changes in a way older versions cannot read.
+pub const CONNECTION_BUNDLE_VERSION: u32 = 1;
+
 /// Get the default data directory for the application.
 ///
 /// # Paths by Platform (FR-026)
@@ -86,6 +94,16 @@ pub fn init_data_dir(path: &PathBuf) -> Result<(), TuskError> {
     Ok(())
 }
 
+/// Read a connection file chosen for import.
+fn read_import_file(path: &Path) -> Result<String, TuskError> {
+    std::fs::read_to_string(path).map_err(|e| {
+        TuskError::storage(
+            format!("Failed to read '{}': {}", path.display(), e),
+            Some("Check that the file exists and is readable"),
+        )
+    })
+}
+
 /// SQLite-based local storage for application data.
 ///
 /// Thread-safe via internal Mutex. Uses WAL mode for concurrent reads.
@@ -264,6 +282,206 @@ impl LocalStorage {
             tracing::info!("Applied migration 1: initial_schema");
         }
 
+        // Migration 2: Query snippets
+        if current_step < 2 {
+            conn.execute_batch(
+                "
+                CREATE TABLE snippets (
+                    snippet_id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    body TEXT NOT NULL,
+                    created_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                    updated_at TEXT NOT NULL DEFAULT CURRENT_TIMESTAMP
+                ) STRICT;
+
+                CREATE INDEX idx_snippets_name ON snippets(name);
+                ",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 2 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 2, 'snippets')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 2: snippets");
+        }
+
+        // Migration 3: Microsecond query timing
+        if current_step < 3 {
+            conn.execute_batch("ALTER TABLE query_history ADD COLUMN execution_time_us INTEGER;")
+                .map_err(|e| TuskError::storage(format!("Migration 3 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 3, 'history_execution_time_us')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 3: history_execution_time_us");
+        }
+
+        // Migration 4: Per-connection row limit
+        if current_step < 4 {
+            conn.execute_batch(
+                "ALTER TABLE connections ADD COLUMN row_limit INTEGER DEFAULT 1000;",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 4 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 4, 'connection_row_limit')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 4: connection_row_limit");
+        }
+
+        // Migration 5: Per-connection default schema
+        if current_step < 5 {
+            conn.execute_batch("ALTER TABLE connections ADD COLUMN default_schema TEXT;")
+                .map_err(|e| TuskError::storage(format!("Migration 5 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 5, 'connection_default_schema')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 5: connection_default_schema");
+        }
+
+        // Migration 6: Connection environment tag
+        if current_step < 6 {
+            conn.execute_batch(
+                "ALTER TABLE connections ADD COLUMN environment TEXT NOT NULL DEFAULT 'dev';",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 6 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 6, 'connection_environment')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 6: connection_environment");
+        }
+
+        // Migration 7: Prepared statement caching toggle
+        if current_step < 7 {
+            conn.execute_batch(
+                "ALTER TABLE connections ADD COLUMN cache_prepared_statements INTEGER NOT NULL DEFAULT 1;",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 7 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 7, 'connection_statement_cache')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 7: connection_statement_cache");
+        }
+
+        // Migration 8: Stream batch size
+        if current_step < 8 {
+            conn.execute_batch(
+                "ALTER TABLE connections ADD COLUMN stream_batch_size INTEGER NOT NULL DEFAULT 1000;",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 8 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 8, 'connection_stream_batch_size')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 8: connection_stream_batch_size");
+        }
+
+        // Migration 9: Connect on startup
+        if current_step < 9 {
+            conn.execute_batch(
+                "ALTER TABLE connections ADD COLUMN connect_on_startup INTEGER NOT NULL DEFAULT 0;",
+            )
+            .map_err(|e| TuskError::storage(format!("Migration 9 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 9, 'connection_connect_on_startup')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 9: connection_connect_on_startup");
+        }
+
+        // Migration 10: Full-text index over history SQL
+        if current_step < 10 {
+            let fts5_available: bool = conn
+                .query_row("SELECT sqlite_compileoption_used('ENABLE_FTS5')", [], |row| row.get(0))
+                .unwrap_or(false);
+
+            if fts5_available {
+                conn.execute_batch(
+                    "
+                    CREATE VIRTUAL TABLE query_history_fts USING fts5(
+                        sql_text,
+                        content = 'query_history',
+                        content_rowid = 'history_id'
+                    );
+
+                    CREATE TRIGGER query_history_fts_insert AFTER INSERT ON query_history BEGIN
+                        INSERT INTO query_history_fts(rowid, sql_text)
+                        VALUES (new.history_id, new.sql_text);
+                    END;
+
+                    CREATE TRIGGER query_history_fts_delete AFTER DELETE ON query_history BEGIN
+                        INSERT INTO query_history_fts(query_history_fts, rowid, sql_text)
+                        VALUES ('delete', old.history_id, old.sql_text);
+                    END;
+
+                    CREATE TRIGGER query_history_fts_update AFTER UPDATE OF sql_text ON query_history BEGIN
+                        INSERT INTO query_history_fts(query_history_fts, rowid, sql_text)
+                        VALUES ('delete', old.history_id, old.sql_text);
+                        INSERT INTO query_history_fts(rowid, sql_text)
+                        VALUES (new.history_id, new.sql_text);
+                    END;
+
+                    -- Index the history recorded before this migration
+                    INSERT INTO query_history_fts(query_history_fts) VALUES ('rebuild');
+                    ",
+                )
+                .map_err(|e| TuskError::storage(format!("Migration 10 failed: {e}"), None))?;
+            } else {
+                // Recorded anyway so later migrations keep running; full-text
+                // search reports itself unavailable instead.
+                tracing::warn!("SQLite lacks FTS5, history full-text search is unavailable");
+            }
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 10, 'history_fts')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 10: history_fts");
+        }
+
+        // Migration 11: Server-side cursor fetch size
+        if current_step < 11 {
+            conn.execute_batch("ALTER TABLE connections ADD COLUMN cursor_fetch_size INTEGER;")
+                .map_err(|e| TuskError::storage(format!("Migration 11 failed: {e}"), None))?;
+
+            conn.execute(
+                "INSERT INTO migrations (domain, step, migration) VALUES (?, 11, 'connection_cursor_fetch_size')",
+                [DOMAIN],
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to record migration: {e}"), None))?;
+
+            tracing::info!("Applied migration 11: connection_cursor_fetch_size");
+        }
+
         Ok(())
     }
 
@@ -272,11 +490,30 @@ impl LocalStorage {
         &self.data_dir
     }
 
+    /// Get the latest applied schema migration step.
+    pub fn schema_version(&self) -> Result<i32, TuskError> {
+        let conn = self.connection.lock();
+        conn.query_row(
+            "SELECT COALESCE(MAX(step), 0) FROM migrations WHERE domain = 'core'",
+            [],
+            |row| row.get(0),
+        )
+        .map_err(|e| TuskError::storage(format!("Failed to read schema version: {e}"), None))
+    }
+
     // ========== Connection Operations ==========
 
     /// Save a connection configuration.
     pub fn save_connection(&self, config: &ConnectionConfig) -> Result<(), TuskError> {
         let conn = self.connection.lock();
+        self.save_connection_internal(&conn, config)
+    }
+
+    fn save_connection_internal(
+        &self,
+        conn: &Connection,
+        config: &ConnectionConfig,
+    ) -> Result<(), TuskError> {
         let now = Utc::now().to_rfc3339();
 
         conn.execute(
@@ -284,8 +521,10 @@ impl LocalStorage {
                 connection_id, name, host, port, database_name, username,
                 ssl_mode, ssh_tunnel_id, color, read_only,
                 connect_timeout_secs, statement_timeout_secs, application_name,
-                created_at, updated_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?14)
+                created_at, updated_at, row_limit, default_schema, environment,
+                cache_prepared_statements, stream_batch_size, connect_on_startup,
+                cursor_fetch_size
+            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?14, ?15, ?16, ?17, ?18, ?19, ?20, ?21)
             ON CONFLICT(connection_id) DO UPDATE SET
                 name = excluded.name,
                 host = excluded.host,
@@ -299,7 +538,14 @@ impl LocalStorage {
                 connect_timeout_secs = excluded.connect_timeout_secs,
                 statement_timeout_secs = excluded.statement_timeout_secs,
                 application_name = excluded.application_name,
-                updated_at = excluded.updated_at",
+                updated_at = excluded.updated_at,
+                row_limit = excluded.row_limit,
+                default_schema = excluded.default_schema,
+                environment = excluded.environment,
+                cache_prepared_statements = excluded.cache_prepared_statements,
+                stream_batch_size = excluded.stream_batch_size,
+                connect_on_startup = excluded.connect_on_startup,
+                cursor_fetch_size = excluded.cursor_fetch_size",
             params![
                 config.id.to_string(),
                 config.name,
@@ -315,6 +561,13 @@ impl LocalStorage {
                 config.options.statement_timeout_secs,
                 config.options.application_name,
                 now,
+                config.options.row_limit,
+                config.options.default_schema,
+                config.environment.as_str(),
+                config.options.cache_prepared_statements,
+                config.options.stream_batch_size,
+                config.options.connect_on_startup,
+                config.options.cursor_fetch_size,
             ],
         )
         .map_err(|e| TuskError::storage(format!("Failed to save connection: {e}"), None))?;
@@ -331,7 +584,9 @@ impl LocalStorage {
             .query_row(
                 "SELECT connection_id, name, host, port, database_name, username,
                         ssl_mode, ssh_tunnel_id, color, read_only,
-                        connect_timeout_secs, statement_timeout_secs, application_name
+                        connect_timeout_secs, statement_timeout_secs, application_name,
+                        row_limit, default_schema, environment, cache_prepared_statements,
+                        stream_batch_size, connect_on_startup, cursor_fetch_size
                  FROM connections WHERE connection_id = ?",
                 [id.to_string()],
                 |row| {
@@ -349,6 +604,13 @@ impl LocalStorage {
                         connect_timeout_secs: row.get(10)?,
                         statement_timeout_secs: row.get(11)?,
                         application_name: row.get(12)?,
+                        row_limit: row.get(13)?,
+                        default_schema: row.get(14)?,
+                        environment: row.get(15)?,
+                        cache_prepared_statements: row.get(16)?,
+                        stream_batch_size: row.get(17)?,
+                        connect_on_startup: row.get(18)?,
+                        cursor_fetch_size: row.get(19)?,
                     })
                 },
             )
@@ -380,7 +642,9 @@ impl LocalStorage {
             .prepare(
                 "SELECT connection_id, name, host, port, database_name, username,
                         ssl_mode, ssh_tunnel_id, color, read_only,
-                        connect_timeout_secs, statement_timeout_secs, application_name
+                        connect_timeout_secs, statement_timeout_secs, application_name,
+                        row_limit, default_schema, environment, cache_prepared_statements,
+                        stream_batch_size, connect_on_startup, cursor_fetch_size
                  FROM connections ORDER BY last_connected_at DESC NULLS LAST, name",
             )
             .map_err(|e| TuskError::storage(format!("Failed to prepare query: {e}"), None))?;
@@ -401,6 +665,13 @@ impl LocalStorage {
                     connect_timeout_secs: row.get(10)?,
                     statement_timeout_secs: row.get(11)?,
                     application_name: row.get(12)?,
+                    row_limit: row.get(13)?,
+                    default_schema: row.get(14)?,
+                    environment: row.get(15)?,
+                    cache_prepared_statements: row.get(16)?,
+                    stream_batch_size: row.get(17)?,
+                    connect_on_startup: row.get(18)?,
+                    cursor_fetch_size: row.get(19)?,
                 })
             })
             .map_err(|e| TuskError::storage(format!("Failed to query connections: {e}"), None))?;
@@ -435,14 +706,211 @@ impl LocalStorage {
         Ok(())
     }
 
+    /// Import the services of a `.pg_service.conf` file as saved connections.
+    ///
+    /// Services reaching the same host, port, database and user as a saved
+    /// connection (or an earlier service) are skipped. Returns the imported
+    /// connections with any password from the file, for the credential service.
+    pub fn import_from_pg_service(
+        &self,
+        path: &Path,
+    ) -> Result<Vec<(ConnectionConfig, Option<String>)>, TuskError> {
+        let contents = read_import_file(path)?;
+        self.import_new_connections(psql_files::parse_pg_service(&contents)?)
+    }
+
+    /// Import the entries of a `.pgpass` file as saved connections.
+    ///
+    /// Entries with a wildcard user are skipped, as are entries reaching the
+    /// same host, port, database and user as a saved connection. Returns the
+    /// imported connections with their passwords, for the credential service.
+    pub fn import_from_pgpass(
+        &self,
+        path: &Path,
+    ) -> Result<Vec<(ConnectionConfig, String)>, TuskError> {
+        let contents = read_import_file(path)?;
+        let entries = psql_files::parse_pgpass(&contents);
+        self.import_new_connections(entries.iter().filter_map(|entry| entry.to_config()).collect())
+    }
+
+    /// Export every saved connection as a versioned JSON bundle.
+    ///
+    /// Passwords are not part of the bundle. SSH tunnels used by the
+    /// connections are included once and referenced by tunnel ID.
+    pub fn export_connections(&self) -> Result<String, TuskError> {
+        let mut ssh_tunnels: Vec<SshTunnelConfig> = Vec::new();
+        let connections = self
+            .load_all_connections()?
+            .into_iter()
+            .map(|mut config| {
+                let ssh_tunnel_id = config.ssh_tunnel.take().map(|tunnel| {
+                    let id = tunnel.id;
+                    if !ssh_tunnels.iter().any(|t| t.id == id) {
+                        ssh_tunnels.push(tunnel);
+                    }
+                    id
+                });
+                BundledConnection { config, ssh_tunnel_id }
+            })
+            .collect();
+
+        let bundle = ConnectionBundle {
+            version: CONNECTION_BUNDLE_VERSION,
+            exported_at: Utc::now(),
+            ssh_tunnels,
+            connections,
+        };
+        serde_json::to_string_pretty(&bundle)
+            .map_err(|e| TuskError::storage(format!("Failed to serialize connections: {e}"), None))
+    }
+
+    /// Import a bundle written by [`LocalStorage::export_connections`].
+    ///
+    /// With `merge`, connections are upserted by ID and other saved
+    /// connections are kept. Without it, saved connections missing from the
+    /// bundle are deleted so the table matches the bundle; connections kept
+    /// by ID keep their history. SSH tunnels are saved first and connections
+    /// re-linked to them by tunnel ID. Returns the number of connections
+    /// written. Nothing is written if the bundle is invalid.
+    pub fn import_connections(&self, json: &str, merge: bool) -> Result<usize, TuskError> {
+        let bundle = ConnectionBundle::parse(json)?;
+
+        let mut conn = self.connection.lock();
+        let tx = conn.transaction().map_err(|e| {
+            TuskError::storage(format!("Failed to start import transaction: {e}"), None)
+        })?;
+
+        for tunnel in &bundle.ssh_tunnels {
+            self.save_ssh_tunnel_internal(&tx, tunnel)?;
+        }
+        let tunnels: HashMap<Uuid, &SshTunnelConfig> =
+            bundle.ssh_tunnels.iter().map(|tunnel| (tunnel.id, tunnel)).collect();
+
+        if !merge {
+            let keep: Vec<String> =
+                bundle.connections.iter().map(|c| c.config.id.to_string()).collect();
+            let saved: Vec<String> = tx
+                .prepare("SELECT connection_id FROM connections")
+                .and_then(|mut stmt| {
+                    let ids = stmt.query_map([], |row| row.get(0))?.collect();
+                    ids
+                })
+                .map_err(|e| {
+                    TuskError::storage(format!("Failed to read saved connections: {e}"), None)
+                })?;
+            for id in saved.iter().filter(|id| !keep.contains(id)) {
+                tx.execute("DELETE FROM connections WHERE connection_id = ?", [id]).map_err(
+                    |e| TuskError::storage(format!("Failed to delete connection: {e}"), None),
+                )?;
+            }
+        }
+
+        let written = bundle.connections.len();
+        for BundledConnection { mut config, ssh_tunnel_id } in bundle.connections {
+            config.ssh_tunnel = match ssh_tunnel_id {
+                Some(id) => match tunnels.get(&id) {
+                    Some(tunnel) => Some((*tunnel).clone()),
+                    None => self.load_ssh_tunnel_internal(&tx, id)?.or_else(|| {
+                        tracing::warn!(
+                            connection_id = %config.id,
+                            tunnel_id = %id,
+                            "Dropping reference to an SSH tunnel missing from the bundle"
+                        );
+                        None
+                    }),
+                },
+                None => None,
+            };
+            self.save_connection_internal(&tx, &config)?;
+        }
+
+        tx.commit().map_err(|e| {
+            TuskError::storage(format!("Failed to commit imported connections: {e}"), None)
+        })?;
+        tracing::info!(count = written, merge, "Imported connection bundle");
+        Ok(written)
+    }
+
+    /// Save the candidates that do not duplicate a saved connection.
+    fn import_new_connections<P>(
+        &self,
+        candidates: Vec<(ConnectionConfig, P)>,
+    ) -> Result<Vec<(ConnectionConfig, P)>, TuskError> {
+        let mut known = self.load_all_connections()?;
+        let mut imported = Vec::new();
+        for (config, password) in candidates {
+            if known.iter().any(|saved| saved.same_target(&config)) {
+                tracing::debug!(name = %config.name, "Skipping already saved connection");
+                continue;
+            }
+            self.save_connection(&config)?;
+            known.push(config.clone());
+            imported.push((config, password));
+        }
+        tracing::info!(count = imported.len(), "Imported connections");
+        Ok(imported)
+    }
+
+    /// Load the most recently used connections, newest first.
+    ///
+    /// Only connections that have been connected to at least once are included.
+    pub fn load_recent_connections(
+        &self,
+        limit: usize,
+    ) -> Result<Vec<RecentConnection>, TuskError> {
+        let conn = self.connection.lock();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT connection_id, last_connected_at FROM connections
+                 WHERE last_connected_at IS NOT NULL
+                 ORDER BY last_connected_at DESC, name
+                 LIMIT ?",
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to prepare query: {e}"), None))?;
+
+        let rows = stmt
+            .query_map([limit as i64], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })
+            .map_err(|e| {
+                TuskError::storage(format!("Failed to query recent connections: {e}"), None)
+            })?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TuskError::storage(format!("Failed to read row: {e}"), None))?;
+        drop(stmt);
+        drop(conn);
+
+        let mut recent = Vec::with_capacity(rows.len());
+        for (id, last_connected_at) in rows {
+            let id = Uuid::parse_str(&id)
+                .map_err(|e| TuskError::storage(format!("Invalid connection ID: {e}"), None))?;
+            let Some(config) = self.load_connection(id)? else {
+                continue;
+            };
+            recent.push(RecentConnection {
+                config,
+                last_connected_at: DateTime::parse_from_rfc3339(&last_connected_at)
+                    .map(|dt| dt.with_timezone(&Utc))
+                    .unwrap_or_else(|_| Utc::now()),
+            });
+        }
+
+        Ok(recent)
+    }
+
     /// Update the last connected timestamp.
     pub fn update_last_connected(&self, id: Uuid) -> Result<(), TuskError> {
+        self.set_last_connected(id, Utc::now())
+    }
+
+    /// Set the last connected timestamp to a specific time.
+    fn set_last_connected(&self, id: Uuid, at: DateTime<Utc>) -> Result<(), TuskError> {
         let conn = self.connection.lock();
-        let now = Utc::now().to_rfc3339();
 
         conn.execute(
             "UPDATE connections SET last_connected_at = ? WHERE connection_id = ?",
-            params![now, id.to_string()],
+            params![at.to_rfc3339(), id.to_string()],
         )
         .map_err(|e| TuskError::storage(format!("Failed to update last_connected: {e}"), None))?;
 
@@ -454,7 +922,14 @@ impl LocalStorage {
     /// Save an SSH tunnel configuration.
     pub fn save_ssh_tunnel(&self, tunnel: &SshTunnelConfig) -> Result<(), TuskError> {
         let conn = self.connection.lock();
+        self.save_ssh_tunnel_internal(&conn, tunnel)
+    }
 
+    fn save_ssh_tunnel_internal(
+        &self,
+        conn: &Connection,
+        tunnel: &SshTunnelConfig,
+    ) -> Result<(), TuskError> {
         conn.execute(
             "INSERT INTO ssh_tunnels (tunnel_id, name, host, port, username, auth_method, key_path)
              VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
@@ -567,8 +1042,8 @@ impl LocalStorage {
         let conn = self.connection.lock();
 
         conn.execute(
-            "INSERT INTO query_history (connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "INSERT INTO query_history (connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at, execution_time_us)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
             params![
                 entry.connection_id.to_string(),
                 entry.sql,
@@ -576,6 +1051,7 @@ impl LocalStorage {
                 entry.row_count,
                 entry.error_message,
                 entry.executed_at.to_rfc3339(),
+                entry.execution_time_us,
             ],
         )
         .map_err(|e| TuskError::storage(format!("Failed to add to history: {e}"), None))?;
@@ -595,7 +1071,7 @@ impl LocalStorage {
 
         let mut stmt = conn
             .prepare(
-                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at
+                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at, execution_time_us
                  FROM query_history
                  WHERE connection_id = ?
                  ORDER BY executed_at DESC
@@ -612,7 +1088,7 @@ impl LocalStorage {
 
         let mut stmt = conn
             .prepare(
-                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at
+                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at, execution_time_us
                  FROM query_history
                  ORDER BY executed_at DESC
                  LIMIT ?",
@@ -634,7 +1110,7 @@ impl LocalStorage {
 
         let mut stmt = if connection_id.is_some() {
             conn.prepare(
-                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at
+                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at, execution_time_us
                  FROM query_history
                  WHERE sql_text LIKE ? AND connection_id = ?
                  ORDER BY executed_at DESC
@@ -643,7 +1119,7 @@ impl LocalStorage {
             .map_err(|e| TuskError::storage(format!("Failed to prepare query: {e}"), None))?
         } else {
             conn.prepare(
-                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at
+                "SELECT history_id, connection_id, sql_text, execution_time_ms, row_count, error_message, executed_at, execution_time_us
                  FROM query_history
                  WHERE sql_text LIKE ?
                  ORDER BY executed_at DESC
@@ -662,6 +1138,56 @@ impl LocalStorage {
         }
     }
 
+    /// Search history with the full-text index, most relevant first.
+    ///
+    /// Each whitespace-separated term must appear in the SQL; a term ending
+    /// in `*` matches as a prefix (`ord*` finds `orders`). Punctuation splits
+    /// words, so `users.email` matches those two words in sequence. An empty
+    /// query returns the most recent entries, like [`LocalStorage::search_history`].
+    ///
+    /// Fails with a storage error if SQLite was built without FTS5.
+    pub fn search_history_fts(
+        &self,
+        query: &str,
+        connection_id: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<QueryHistoryEntry>, TuskError> {
+        let Some(match_query) = fts_match_query(query) else {
+            return self.search_history("", connection_id, limit);
+        };
+
+        let conn = self.connection.lock();
+        let indexed: bool = conn
+            .query_row(
+                "SELECT EXISTS (SELECT 1 FROM sqlite_master WHERE name = 'query_history_fts')",
+                [],
+                |row| row.get(0),
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to read schema: {e}"), None))?;
+        if !indexed {
+            return Err(TuskError::storage(
+                "Full-text history search is unavailable",
+                Some("This SQLite build lacks FTS5; use the regular history search instead"),
+            ));
+        }
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT h.history_id, h.connection_id, h.sql_text, h.execution_time_ms, h.row_count, h.error_message, h.executed_at, h.execution_time_us
+                 FROM query_history_fts
+                 JOIN query_history h ON h.history_id = query_history_fts.rowid
+                 WHERE query_history_fts MATCH ?1 AND (?2 IS NULL OR h.connection_id = ?2)
+                 ORDER BY query_history_fts.rank, h.executed_at DESC
+                 LIMIT ?3",
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to prepare query: {e}"), None))?;
+
+        self.query_history_entries(
+            &mut stmt,
+            params![match_query, connection_id.map(|id| id.to_string()), limit as i64],
+        )
+    }
+
     fn query_history_entries(
         &self,
         stmt: &mut rusqlite::Statement,
@@ -678,6 +1204,7 @@ impl LocalStorage {
                     connection_id: Uuid::parse_str(&connection_id_str).unwrap_or_default(),
                     sql: row.get(2)?,
                     execution_time_ms: row.get(3)?,
+                    execution_time_us: row.get(7)?,
                     row_count: row.get(4)?,
                     error_message: row.get(5)?,
                     executed_at: DateTime::parse_from_rfc3339(&executed_at_str)
@@ -837,6 +1364,78 @@ impl LocalStorage {
         Ok(())
     }
 
+    // ========== Snippet Operations ==========
+
+    /// Save a snippet.
+    pub fn save_snippet(&self, snippet: &Snippet) -> Result<(), TuskError> {
+        let conn = self.connection.lock();
+        let now = Utc::now().to_rfc3339();
+
+        conn.execute(
+            "INSERT INTO snippets (snippet_id, name, body, created_at, updated_at)
+             VALUES (?1, ?2, ?3, ?4, ?4)
+             ON CONFLICT(snippet_id) DO UPDATE SET
+                name = excluded.name,
+                body = excluded.body,
+                updated_at = excluded.updated_at",
+            params![snippet.id.to_string(), snippet.name, snippet.body, now],
+        )
+        .map_err(|e| TuskError::storage(format!("Failed to save snippet: {e}"), None))?;
+
+        tracing::debug!(snippet_id = %snippet.id, name = %snippet.name, "Snippet saved");
+        Ok(())
+    }
+
+    /// Load all snippets ordered by name.
+    pub fn load_all_snippets(&self) -> Result<Vec<Snippet>, TuskError> {
+        let conn = self.connection.lock();
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT snippet_id, name, body, created_at, updated_at
+                 FROM snippets ORDER BY name",
+            )
+            .map_err(|e| TuskError::storage(format!("Failed to prepare query: {e}"), None))?;
+
+        let rows = stmt
+            .query_map([], |row| self.row_to_snippet(row))
+            .map_err(|e| TuskError::storage(format!("Failed to query snippets: {e}"), None))?;
+
+        rows.collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TuskError::storage(format!("Failed to read snippets: {e}"), None))
+    }
+
+    fn row_to_snippet(&self, row: &rusqlite::Row) -> rusqlite::Result<Snippet> {
+        let id_str: String = row.get(0)?;
+        let body: String = row.get(2)?;
+        let created_at_str: String = row.get(3)?;
+        let updated_at_str: String = row.get(4)?;
+
+        Ok(Snippet {
+            id: Uuid::parse_str(&id_str).unwrap_or_default(),
+            name: row.get(1)?,
+            placeholders: snippet::placeholder_names(&body),
+            body,
+            created_at: DateTime::parse_from_rfc3339(&created_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+            updated_at: DateTime::parse_from_rfc3339(&updated_at_str)
+                .map(|dt| dt.with_timezone(&Utc))
+                .unwrap_or_else(|_| Utc::now()),
+        })
+    }
+
+    /// Delete a snippet.
+    pub fn delete_snippet(&self, id: Uuid) -> Result<(), TuskError> {
+        let conn = self.connection.lock();
+
+        conn.execute("DELETE FROM snippets WHERE snippet_id = ?", [id.to_string()])
+            .map_err(|e| TuskError::storage(format!("Failed to delete snippet: {e}"), None))?;
+
+        tracing::debug!(snippet_id = %id, "Snippet deleted");
+        Ok(())
+    }
+
     // ========== UI State Operations ==========
 
     /// Save UI state.
@@ -886,6 +1485,30 @@ impl LocalStorage {
         Ok(())
     }
 
+    /// Save the tables pinned in the schema browser for a connection.
+    ///
+    /// Tables are identified as `schema.name`, in display order.
+    pub fn save_pinned_tables(
+        &self,
+        connection_id: Uuid,
+        tables: &[String],
+    ) -> Result<(), TuskError> {
+        let key = pinned_tables_key(connection_id);
+        if tables.is_empty() {
+            return self.delete_ui_state(&key);
+        }
+        self.save_ui_state(&key, &serde_json::json!(tables))
+    }
+
+    /// Load the tables pinned in the schema browser for a connection.
+    pub fn load_pinned_tables(&self, connection_id: Uuid) -> Result<Vec<String>, TuskError> {
+        let Some(value) = self.load_ui_state(&pinned_tables_key(connection_id))? else {
+            return Ok(Vec::new());
+        };
+        serde_json::from_value(value)
+            .map_err(|e| TuskError::storage(format!("Invalid pinned tables: {e}"), None))
+    }
+
     // ========== Helper Methods ==========
 
     fn row_to_connection_config(
@@ -910,8 +1533,15 @@ impl LocalStorage {
                 statement_timeout_secs: row.statement_timeout_secs,
                 read_only: row.read_only,
                 application_name: row.application_name,
+                row_limit: row.row_limit,
+                default_schema: row.default_schema,
+                cache_prepared_statements: row.cache_prepared_statements,
+                stream_batch_size: row.stream_batch_size,
+                connect_on_startup: row.connect_on_startup,
+                cursor_fetch_size: row.cursor_fetch_size,
             },
             color: row.color,
+            environment: ConnectionEnvironment::parse(&row.environment),
         })
     }
 }
@@ -931,6 +1561,77 @@ struct ConnectionConfigRow {
     connect_timeout_secs: u32,
     statement_timeout_secs: Option<u32>,
     application_name: String,
+    row_limit: Option<u32>,
+    default_schema: Option<String>,
+    environment: String,
+    cache_prepared_statements: bool,
+    stream_batch_size: u32,
+    connect_on_startup: bool,
+    cursor_fetch_size: Option<u32>,
+}
+
+/// Saved connections exported as JSON, without passwords.
+#[derive(Debug, Serialize, Deserialize)]
+struct ConnectionBundle {
+    /// Bundle format version, see [`CONNECTION_BUNDLE_VERSION`]
+    version: u32,
+    /// When the bundle was written
+    exported_at: DateTime<Utc>,
+    /// SSH tunnels referenced by the connections
+    #[serde(default)]
+    ssh_tunnels: Vec<SshTunnelConfig>,
+    /// The saved connections
+    connections: Vec<BundledConnection>,
+}
+
+impl ConnectionBundle {
+    /// Parse a bundle, checking its version before its contents.
+    fn parse(json: &str) -> Result<Self, TuskError> {
+        let invalid = |e: serde_json::Error| {
+            TuskError::storage(
+                format!("Invalid connection bundle: {e}"),
+                Some("Choose a file written by Export Connections"),
+            )
+        };
+        let value: serde_json::Value = serde_json::from_str(json).map_err(invalid)?;
+        let version =
+            value.get("version").and_then(serde_json::Value::as_u64).ok_or_else(|| {
+                TuskError::storage(
+                    "Not a connection bundle: missing version",
+                    Some("Choose a file written by Export Connections"),
+                )
+            })?;
+        if version > u64::from(CONNECTION_BUNDLE_VERSION) {
+            return Err(TuskError::storage(
+                format!(
+                    "Connection bundle version {version} is newer than the supported version {}",
+                    CONNECTION_BUNDLE_VERSION
+                ),
+                Some("Update Tusk to import connections exported by a newer version"),
+            ));
+        }
+        serde_json::from_value(value).map_err(invalid)
+    }
+}
+
+/// A connection in a bundle, its SSH tunnel replaced by a reference.
+#[derive(Debug, Serialize, Deserialize)]
+struct BundledConnection {
+    /// The connection, without its SSH tunnel
+    #[serde(flatten)]
+    config: ConnectionConfig,
+    /// ID of the connection's SSH tunnel in the bundle
+    #[serde(default)]
+    ssh_tunnel_id: Option<Uuid>,
+}
+
+/// A saved connection with the time it was last used.
+#[derive(Debug, Clone)]
+pub struct RecentConnection {
+    /// The connection configuration
+    pub config: ConnectionConfig,
+    /// When the connection last succeeded
+    pub last_connected_at: DateTime<Utc>,
 }
 
 /// A saved query in the user's query library.
@@ -970,3 +1671,389 @@ impl SavedQuery {
         }
     }
 }
+
+/// Turn a history search into an FTS5 query, or None if it has no terms.
+///
+/// Terms are quoted so SQL punctuation and FTS5 operators are matched as
+/// text; a trailing `*` is kept outside the quotes as a prefix match.
+fn fts_match_query(query: &str) -> Option<String> {
+    let terms: Vec<String> = query
+        .split_whitespace()
+        .filter_map(|term| {
+            let (word, prefix) = match term.strip_suffix('*') {
+                Some(word) => (word.trim_end_matches('*'), true),
+                None => (term, false),
+            };
+            if word.is_empty() {
+                return None;
+            }
+            let quoted = format!("\"{}\"", word.replace('"', "\"\""));
+            Some(if prefix { quoted + "*" } else { quoted })
+        })
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+/// UI state key for a connection's pinned tables.
+fn pinned_tables_key(connection_id: Uuid) -> String {
+    format!("pinned_tables:{connection_id}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn open_storage() -> (tempfile::TempDir, LocalStorage) {
+        let dir = tempfile::tempdir().unwrap();
+        let storage =
+            LocalStorage::open_with_path(dir.path().join("tusk.db"), dir.path().to_path_buf())
+                .unwrap();
+        (dir, storage)
+    }
+
+    fn saved(storage: &LocalStorage, name: &str) -> ConnectionConfig {
+        let config = ConnectionConfig::new(name, "localhost", "postgres", "postgres");
+        storage.save_connection(&config).unwrap();
+        config
+    }
+
+    #[test]
+    fn test_recent_connections_newest_first_with_limit() {
+        let (_dir, storage) = open_storage();
+        let base = Utc::now() - Duration::days(1);
+        let oldest = saved(&storage, "oldest");
+        let newest = saved(&storage, "newest");
+        let middle = saved(&storage, "middle");
+        let _never = saved(&storage, "never connected");
+
+        storage.set_last_connected(oldest.id, base).unwrap();
+        storage.set_last_connected(newest.id, base + Duration::hours(2)).unwrap();
+        storage.set_last_connected(middle.id, base + Duration::hours(1)).unwrap();
+
+        let names = |limit| {
+            storage
+                .load_recent_connections(limit)
+                .unwrap()
+                .into_iter()
+                .map(|recent| recent.config.name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(names(10), vec!["newest", "middle", "oldest"]);
+        assert_eq!(names(2), vec!["newest", "middle"]);
+
+        let recent = storage.load_recent_connections(1).unwrap();
+        assert_eq!(recent[0].config.id, newest.id);
+        assert_eq!(recent[0].last_connected_at, base + Duration::hours(2));
+    }
+
+    #[test]
+    fn test_connect_moves_connection_to_front() {
+        let (_dir, storage) = open_storage();
+        let alpha = saved(&storage, "alpha");
+        let beta = saved(&storage, "beta");
+
+        let names = |storage: &LocalStorage| {
+            storage
+                .load_all_connections()
+                .unwrap()
+                .into_iter()
+                .map(|config| config.name)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&storage), vec!["alpha", "beta"]);
+
+        // Simulate a successful connect to the second connection
+        storage.update_last_connected(beta.id).unwrap();
+
+        let recent = storage.load_recent_connections(10).unwrap();
+        assert_eq!(recent.len(), 1);
+        assert_eq!(recent[0].config.id, beta.id);
+        assert!(Utc::now() - recent[0].last_connected_at < Duration::minutes(1));
+        assert_eq!(names(&storage), vec!["beta", "alpha"]);
+
+        storage.update_last_connected(alpha.id).unwrap();
+        assert_eq!(names(&storage), vec!["alpha", "beta"]);
+    }
+
+    #[test]
+    fn test_history_keeps_sub_millisecond_timing() {
+        let (_dir, storage) = open_storage();
+        let config = saved(&storage, "timed");
+        let mut entry = QueryHistoryEntry::new(config.id, "SELECT 1");
+        entry.execution_time_ms = Some(1);
+        entry.execution_time_us = Some(300);
+        storage.add_to_history(&entry).unwrap();
+
+        let history = storage.load_history(config.id, 10).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].execution_time_ms, Some(1));
+        assert_eq!(history[0].execution_time_us, Some(300));
+    }
+
+    #[test]
+    fn test_fts_match_query_quotes_terms() {
+        assert_eq!(fts_match_query("  "), None);
+        assert_eq!(fts_match_query("*"), None);
+        assert_eq!(fts_match_query("users email"), Some(r#""users" "email""#.to_string()));
+        assert_eq!(fts_match_query("ord*"), Some(r#""ord"*"#.to_string()));
+        assert_eq!(fts_match_query(r#"say"hi OR"#), Some(r#""say""hi" "OR""#.to_string()));
+    }
+
+    #[test]
+    fn test_search_history_fts_ranks_and_filters() {
+        let (_dir, storage) = open_storage();
+        let app = saved(&storage, "app");
+        let other = saved(&storage, "other");
+        for (connection, sql) in [
+            (app.id, "SELECT email FROM users WHERE users.active"),
+            (
+                app.id,
+                "SELECT id, name, status, total, created_at FROM orders JOIN users USING (id)",
+            ),
+            (app.id, "DELETE FROM sessions"),
+            (other.id, "SELECT * FROM users"),
+        ] {
+            storage.add_to_history(&QueryHistoryEntry::new(connection, sql)).unwrap();
+        }
+
+        let found = storage.search_history_fts("users", Some(app.id), 10).unwrap();
+        let sql: Vec<&str> = found.iter().map(|entry| entry.sql.as_str()).collect();
+        // The short query mentioning users twice outranks the newer, longer one
+        assert_eq!(
+            sql,
+            vec![
+                "SELECT email FROM users WHERE users.active",
+                "SELECT id, name, status, total, created_at FROM orders JOIN users USING (id)",
+            ]
+        );
+
+        assert_eq!(storage.search_history_fts("users", None, 10).unwrap().len(), 3);
+        assert_eq!(storage.search_history_fts("ord*", None, 10).unwrap().len(), 1);
+        assert_eq!(storage.search_history_fts("users.active", None, 10).unwrap().len(), 1);
+        assert!(storage.search_history_fts("ord", None, 10).unwrap().is_empty());
+        assert_eq!(storage.search_history_fts("", None, 10).unwrap().len(), 4);
+
+        // Deleting history removes it from the index
+        storage.clear_history(app.id).unwrap();
+        assert_eq!(storage.search_history_fts("users", None, 10).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_row_limit_round_trips() {
+        let (_dir, storage) = open_storage();
+        let limited = saved(&storage, "limited");
+        assert_eq!(limited.options.row_limit, Some(1000));
+
+        let mut unlimited = ConnectionConfig::new("unlimited", "localhost", "postgres", "postgres");
+        unlimited.options.row_limit = None;
+        storage.save_connection(&unlimited).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().options.row_limit;
+        assert_eq!(load(limited.id), Some(1000));
+        assert_eq!(load(unlimited.id), None);
+    }
+
+    #[test]
+    fn test_default_schema_round_trips() {
+        let (_dir, storage) = open_storage();
+        let unset = saved(&storage, "unset");
+
+        let mut sales = ConnectionConfig::new("sales", "localhost", "postgres", "postgres");
+        sales.options.default_schema = Some("Sales".to_string());
+        storage.save_connection(&sales).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().options.default_schema;
+        assert_eq!(load(unset.id), None);
+        assert_eq!(load(sales.id).as_deref(), Some("Sales"));
+    }
+
+    #[test]
+    fn test_environment_round_trips() {
+        let (_dir, storage) = open_storage();
+        let dev = saved(&storage, "dev");
+
+        let mut prod = ConnectionConfig::new("prod", "db.example.com", "app", "postgres");
+        prod.environment = ConnectionEnvironment::Prod;
+        storage.save_connection(&prod).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().environment;
+        assert_eq!(load(dev.id), ConnectionEnvironment::Dev);
+        assert_eq!(load(prod.id), ConnectionEnvironment::Prod);
+    }
+
+    #[test]
+    fn test_statement_cache_toggle_round_trips() {
+        let (_dir, storage) = open_storage();
+        let cached = saved(&storage, "cached");
+
+        let mut uncached = ConnectionConfig::new("uncached", "localhost", "postgres", "postgres");
+        uncached.options.cache_prepared_statements = false;
+        storage.save_connection(&uncached).unwrap();
+
+        let load =
+            |id| storage.load_connection(id).unwrap().unwrap().options.cache_prepared_statements;
+        assert!(load(cached.id));
+        assert!(!load(uncached.id));
+    }
+
+    #[test]
+    fn test_stream_batch_size_round_trips() {
+        let (_dir, storage) = open_storage();
+        let default = saved(&storage, "default");
+
+        let mut small = ConnectionConfig::new("small", "localhost", "postgres", "postgres");
+        small.options.stream_batch_size = 50;
+        storage.save_connection(&small).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().options.stream_batch_size;
+        assert_eq!(load(default.id), 1000);
+        assert_eq!(load(small.id), 50);
+    }
+
+    #[test]
+    fn test_cursor_fetch_size_round_trips() {
+        let (_dir, storage) = open_storage();
+        let default = saved(&storage, "default");
+
+        let mut cursor = ConnectionConfig::new("cursor", "localhost", "postgres", "postgres");
+        cursor.options.cursor_fetch_size = Some(5000);
+        storage.save_connection(&cursor).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().options.cursor_fetch_size;
+        assert_eq!(load(default.id), None);
+        assert_eq!(load(cursor.id), Some(5000));
+    }
+
+    #[test]
+    fn test_connect_on_startup_round_trips() {
+        let (_dir, storage) = open_storage();
+        let default = saved(&storage, "default");
+
+        let mut startup = ConnectionConfig::new("startup", "localhost", "postgres", "postgres");
+        startup.options.connect_on_startup = true;
+        storage.save_connection(&startup).unwrap();
+
+        let load = |id| storage.load_connection(id).unwrap().unwrap().options.connect_on_startup;
+        assert!(!load(default.id));
+        assert!(load(startup.id));
+    }
+
+    #[test]
+    fn test_pinned_tables_round_trip() {
+        let (_dir, storage) = open_storage();
+        let first = saved(&storage, "first");
+        let second = saved(&storage, "second");
+        assert!(storage.load_pinned_tables(first.id).unwrap().is_empty());
+
+        let pins = vec!["public.users".to_string(), "sales.orders".to_string()];
+        storage.save_pinned_tables(first.id, &pins).unwrap();
+        assert_eq!(storage.load_pinned_tables(first.id).unwrap(), pins);
+        assert!(storage.load_pinned_tables(second.id).unwrap().is_empty());
+
+        // Unpinning the last table removes the entry
+        storage.save_pinned_tables(first.id, &[]).unwrap();
+        assert!(storage.load_pinned_tables(first.id).unwrap().is_empty());
+        assert!(storage.load_ui_state(&pinned_tables_key(first.id)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_import_skips_saved_and_duplicate_targets() {
+        let (dir, storage) = open_storage();
+        saved(&storage, "existing");
+
+        let service = dir.path().join("pg_service.conf");
+        std::fs::write(
+            &service,
+            "[local]\nhost=localhost\ndbname=postgres\nuser=postgres\n\n\
+             [app]\nhost=db.example.com\ndbname=app\nuser=app\npassword=secret\n",
+        )
+        .unwrap();
+        let imported = storage.import_from_pg_service(&service).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].0.name, "app");
+        assert_eq!(imported[0].1.as_deref(), Some("secret"));
+
+        let pgpass = dir.path().join("pgpass");
+        std::fs::write(&pgpass, "db.example.com:5432:app:app:secret\nreplica:*:app:app:other\n")
+            .unwrap();
+        let imported = storage.import_from_pgpass(&pgpass).unwrap();
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].0.host, "replica");
+        assert_eq!(storage.load_all_connections().unwrap().len(), 3);
+    }
+
+    #[test]
+    fn test_connection_bundle_round_trips_with_tunnels() {
+        let (_dir, source) = open_storage();
+        let tunnel = SshTunnelConfig::new("bastion", "bastion.example.com", "deploy");
+        source.save_ssh_tunnel(&tunnel).unwrap();
+        let mut tunneled = ConnectionConfig::new("prod", "10.0.0.5", "app", "app");
+        tunneled.ssh_tunnel = Some(tunnel.clone());
+        tunneled.options.read_only = true;
+        source.save_connection(&tunneled).unwrap();
+        let plain = saved(&source, "local");
+
+        let json = source.export_connections().unwrap();
+        assert!(!json.contains("password"));
+
+        let (_dir, target) = open_storage();
+        assert_eq!(target.import_connections(&json, true).unwrap(), 2);
+
+        let imported = target.load_connection(tunneled.id).unwrap().unwrap();
+        assert_eq!(imported.name, "prod");
+        assert!(imported.options.read_only);
+        assert_eq!(imported.ssh_tunnel.map(|t| t.id), Some(tunnel.id));
+        assert!(target.load_ssh_tunnel(tunnel.id).unwrap().is_some());
+        assert!(target.load_connection(plain.id).unwrap().unwrap().ssh_tunnel.is_none());
+    }
+
+    #[test]
+    fn test_connection_bundle_merge_or_replace() {
+        let (_dir, storage) = open_storage();
+        let kept = saved(&storage, "kept");
+        let json = storage.export_connections().unwrap();
+
+        let extra = saved(&storage, "extra");
+        let mut renamed = kept.clone();
+        renamed.name = "renamed".to_string();
+        storage.save_connection(&renamed).unwrap();
+
+        // Merging upserts by ID and keeps the other connections
+        assert_eq!(storage.import_connections(&json, true).unwrap(), 1);
+        assert_eq!(storage.load_connection(kept.id).unwrap().unwrap().name, "kept");
+        assert!(storage.load_connection(extra.id).unwrap().is_some());
+
+        // Replacing leaves exactly the bundle's connections
+        assert_eq!(storage.import_connections(&json, false).unwrap(), 1);
+        let ids: Vec<Uuid> = storage.load_all_connections().unwrap().iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![kept.id]);
+    }
+
+    #[test]
+    fn test_connection_bundle_rejects_future_and_invalid_versions() {
+        let (_dir, storage) = open_storage();
+        saved(&storage, "existing");
+        let future = format!(
+            r#"{{"version": {}, "exported_at": "2030-01-01T00:00:00Z", "connections": []}}"#,
+            CONNECTION_BUNDLE_VERSION + 1
+        );
+
+        let err = storage.import_connections(&future, false).unwrap_err();
+        assert!(err.to_string().contains("newer than the supported version"), "{err}");
+        assert!(err.hint().is_some_and(|hint| hint.contains("Update Tusk")));
+        assert!(storage.import_connections(r#"{"connections": []}"#, false).is_err());
+        assert!(storage.import_connections("not json", false).is_err());
+
+        // A rejected bundle leaves saved connections alone
+        assert_eq!(storage.load_all_connections().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_recent_connections_empty_before_first_connect() {
+        let (_dir, storage) = open_storage();
+        saved(&storage, "fresh");
+        assert!(storage.load_recent_connections(5).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+//! Byte-level helpers for skipping over SQL literals and comments.
+//!
+//! These are the shared building blocks for code that scans SQL text
+//! without a full parser: statement splitting and fingerprinting in
+//! [`QueryService`](crate::QueryService), bracket matching in the editor,
+//! and snippet placeholder scanning. Each helper takes the index of the
+//! opening delimiter and returns the index just past the closing one, or
+//! `None` when the text ends before it is closed.
+
+/// Index just past a quoted token starting at `start`, if it is closed.
+///
+/// Doubled quote characters are treated as escaped quotes. Backslash
+/// escapes apply to `E'...'` strings.
+pub fn skip_quoted(
+    bytes: &[u8],
+    start: usize,
+    quote: u8,
+    backslash_escapes: bool,
+) -> Option<usize> {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        if backslash_escapes && bytes[i] == b'\\' {
+            i += 2;
+        } else if bytes[i] == quote {
+            if bytes.get(i + 1) == Some(&quote) {
+                i += 2;
+            } else {
+                return Some(i + 1);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Whether the `'` at `start` opens an `E'...'` string.
+pub fn has_escape_prefix(bytes: &[u8], start: usize) -> bool {
+    start > 0 && matches!(bytes[start - 1], b'E' | b'e')
+}
+
+/// Index of the newline ending a `--` comment starting at `start`, or the
+/// end of the text.
+pub fn skip_line_comment(bytes: &[u8], start: usize) -> usize {
+    bytes[start..].iter().position(|&b| b == b'\n').map_or(bytes.len(), |n| start + n)
+}
+
+/// Index just past a block comment starting at `start`, if it is closed.
+///
+/// Block comments nest in PostgreSQL.
+pub fn skip_block_comment(bytes: &[u8], start: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut i = start;
+    while i < bytes.len() {
+        if bytes[i] == b'/' && bytes.get(i + 1) == Some(&b'*') {
+            depth += 1;
+            i += 2;
+        } else if bytes[i] == b'*' && bytes.get(i + 1) == Some(&b'/') {
+            depth -= 1;
+            i += 2;
+            if depth == 0 {
+                return Some(i);
+            }
+        } else {
+            i += 1;
+        }
+    }
+    None
+}
+
+/// Parse a dollar-quote tag (e.g. `$$` or `$body$`) starting at `start`.
+pub fn dollar_tag(bytes: &[u8], start: usize) -> Option<&[u8]> {
+    // `$1` is a parameter placeholder, not a quote
+    if start > 0 && (bytes[start - 1].is_ascii_alphanumeric() || bytes[start - 1] == b'_') {
+        return None;
+    }
+    let mut end = start + 1;
+    while end < bytes.len() && (bytes[end].is_ascii_alphanumeric() || bytes[end] == b'_') {
+        end += 1;
+    }
+    (end < bytes.len() && bytes[end] == b'$' && !bytes[start + 1].is_ascii_digit())
+        .then(|| &bytes[start..=end])
+}
+
+/// Index just past the dollar-quoted body opened by `tag`, if it is closed.
+pub fn skip_dollar_quoted(bytes: &[u8], start: usize, tag: &[u8]) -> Option<usize> {
+    let body_start = start + tag.len();
+    bytes[body_start..]
+        .windows(tag.len())
+        .position(|window| window == tag)
+        .map(|pos| body_start + pos + tag.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_skip_quoted() {
+        let sql = b"'it''s' x";
+        assert_eq!(skip_quoted(sql, 0, b'\'', false), Some(7));
+        assert_eq!(skip_quoted(b"E'it\\'s' x", 1, b'\'', true), Some(8));
+        assert!(has_escape_prefix(b"E'x'", 1));
+        assert!(!has_escape_prefix(b"'x'", 0));
+        assert_eq!(skip_quoted(b"\"open", 0, b'"', false), None);
+    }
+
+    #[test]
+    fn test_skip_comments() {
+        assert_eq!(skip_line_comment(b"-- a\nb", 0), 4);
+        assert_eq!(skip_line_comment(b"-- a", 0), 4);
+        assert_eq!(skip_block_comment(b"/* a /* b */ c */ d", 0), Some(17));
+        assert_eq!(skip_block_comment(b"/* a /* b */", 0), None);
+    }
+
+    #[test]
+    fn test_dollar_quotes() {
+        let sql = b"$body$ a $$ b $body$ c";
+        let tag = dollar_tag(sql, 0).unwrap();
+        assert_eq!(tag, b"$body$");
+        assert_eq!(skip_dollar_quoted(sql, 0, tag), Some(20));
+        assert_eq!(skip_dollar_quoted(b"$$ open", 0, b"$$"), None);
+
+        // Parameters and identifiers containing `$` are not quotes
+        assert_eq!(dollar_tag(b"$1$", 0), None);
+        assert_eq!(dollar_tag(b"a$b$", 1), None);
+    }
+}
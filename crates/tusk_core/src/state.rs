@@ -3,20 +3,59 @@
 //! Provides centralized state accessible from any component (FR-005 through FR-009).
 //! Implements `gpui::Global` for use with GPUI's context system.
 
+use crate::diagnostics::{
+    redacted_log_tail, DiagnosticsConnection, DiagnosticsReport, LOG_TAIL_LINES,
+};
 use crate::error::TuskError;
 use crate::models::{
-    ConnectionConfig, ConnectionStatus, PoolStatus, QueryEvent, QueryHandle, SchemaCache,
+    ConnectionConfig, ConnectionStatus, PoolStatus, QueryEvent, QueryHandle, QueryType, SchemaCache,
 };
 use crate::services::{ConnectionPool, CredentialService, LocalStorage, QueryService};
 
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+/// Idle connections opened when a connection is established.
+const WARMUP_CONNECTIONS: usize = 2;
+
+/// How long shutdown waits for the server to acknowledge query cancellation.
+const SHUTDOWN_CANCEL_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Running queries by query ID, shared with the tasks executing them.
+type QueryRegistry = Arc<RwLock<HashMap<Uuid, Arc<QueryHandle>>>>;
+
+/// A query registered with [`TuskState::track_query`], unregistered when
+/// this is dropped.
+///
+/// Moving it into the task that runs the query keeps the query registered
+/// for exactly as long as it runs, however that task ends.
+pub struct QueryRegistration {
+    handle: Arc<QueryHandle>,
+    registry: QueryRegistry,
+}
+
+impl QueryRegistration {
+    /// Get the registered query's handle.
+    pub fn handle(&self) -> &Arc<QueryHandle> {
+        &self.handle
+    }
+}
+
+impl Drop for QueryRegistration {
+    fn drop(&mut self) {
+        let id = self.handle.id();
+        if self.registry.write().remove(&id).is_some() {
+            tracing::trace!(query_id = %id, "Query unregistered");
+        }
+    }
+}
+
 /// Wrapper for connection pool with status tracking (FR-006).
 ///
 /// Stores a connection pool along with its current status and metadata.
@@ -84,14 +123,14 @@ pub struct TuskState {
     connections: RwLock<HashMap<Uuid, ConnectionEntry>>,
     /// Schema caches per connection (FR-007)
     schema_caches: RwLock<HashMap<Uuid, SchemaCache>>,
-    /// Active queries with cancellation support (FR-008)
-    active_queries: RwLock<HashMap<Uuid, Arc<QueryHandle>>>,
+    /// Running queries with cancellation support (FR-008)
+    running_queries: QueryRegistry,
     /// Local SQLite storage
     storage: LocalStorage,
     /// Application data directory
     data_dir: PathBuf,
     /// Credential service for OS keychain
-    credential_service: CredentialService,
+    credential_service: Arc<CredentialService>,
     /// Tokio runtime for async database operations
     tokio_runtime: tokio::runtime::Runtime,
 }
@@ -121,14 +160,14 @@ impl TuskState {
         let storage = LocalStorage::open(data_dir.clone())?;
 
         // Initialize credential service
-        let credential_service = CredentialService::new();
+        let credential_service = Arc::new(CredentialService::with_data_dir(&data_dir));
 
         tracing::info!(data_dir = %data_dir.display(), "TuskState initialized");
 
         Ok(Self {
             connections: RwLock::new(HashMap::new()),
             schema_caches: RwLock::new(HashMap::new()),
-            active_queries: RwLock::new(HashMap::new()),
+            running_queries: QueryRegistry::default(),
             storage,
             data_dir,
             credential_service,
@@ -259,6 +298,35 @@ impl TuskState {
         cache
     }
 
+    /// Invalidate a connection's schema cache after a statement of `query_type`
+    /// succeeded on it.
+    ///
+    /// Only DDL invalidates. DDL inside an open transaction is deferred until
+    /// the transaction commits, since the schema is reloaded on another
+    /// connection. Returns true when the schema browser should be refreshed,
+    /// whether or not a cache was loaded.
+    pub fn invalidate_schema_after(&self, connection_id: &Uuid, query_type: QueryType) -> bool {
+        let pool = self.get_connection(connection_id);
+        let mut changed = pool.as_ref().is_some_and(|pool| pool.take_committed_schema_change());
+        if query_type == QueryType::Ddl {
+            match pool.as_ref().and_then(|pool| pool.transaction()) {
+                Some(txn) => {
+                    tracing::debug!(connection_id = %connection_id, "Schema change deferred to commit");
+                    txn.defer_schema_change();
+                }
+                None => changed = true,
+            }
+        }
+        if !changed {
+            return false;
+        }
+        if let Some(cache) = self.schema_caches.write().get_mut(connection_id) {
+            cache.invalidate();
+            tracing::debug!(connection_id = %connection_id, "Schema cache invalidated by DDL");
+        }
+        true
+    }
+
     /// Check if schema cache exists and is valid for a connection.
     pub fn has_valid_schema_cache(&self, connection_id: &Uuid) -> bool {
         self.schema_caches.read().get(connection_id).map(|cache| cache.is_valid()).unwrap_or(false)
@@ -267,22 +335,30 @@ impl TuskState {
     // ========== Query Tracking (FR-008) ==========
 
     /// Register a query for tracking.
+    ///
+    /// Whoever runs the query unregisters it when it completes or is cancelled.
     pub fn register_query(&self, handle: QueryHandle) -> Arc<QueryHandle> {
         let id = handle.id();
         let handle = Arc::new(handle);
-        self.active_queries.write().insert(id, handle.clone());
+        self.running_queries.write().insert(id, handle.clone());
         tracing::trace!(query_id = %id, "Query registered");
         handle
     }
 
+    /// Register a query for as long as the returned registration is held.
+    pub fn track_query(&self, handle: QueryHandle) -> QueryRegistration {
+        let handle = self.register_query(handle);
+        QueryRegistration { handle, registry: self.running_queries.clone() }
+    }
+
     /// Get a query handle by ID.
     pub fn get_query(&self, id: &Uuid) -> Option<Arc<QueryHandle>> {
-        self.active_queries.read().get(id).cloned()
+        self.running_queries.read().get(id).cloned()
     }
 
     /// Unregister a completed or cancelled query (FR-016).
     pub fn unregister_query(&self, id: &Uuid) -> Option<Arc<QueryHandle>> {
-        let handle = self.active_queries.write().remove(id);
+        let handle = self.running_queries.write().remove(id);
         if handle.is_some() {
             tracing::trace!(query_id = %id, "Query unregistered");
         }
@@ -291,46 +367,63 @@ impl TuskState {
 
     /// Cancel a running query (FR-013, T030, T031).
     ///
-    /// This method:
-    /// 1. Signals the CancellationToken (for cooperative cancellation)
-    /// 2. Sends a PostgreSQL cancel request to the server (to interrupt the query)
+    /// Signals the CancellationToken (for cooperative cancellation) and sends
+    /// a PostgreSQL cancel request to the server to interrupt the statement,
+    /// see [`QueryHandle::cancel`].
     ///
     /// Returns true if the query was found and cancellation was requested.
     pub fn cancel_query(&self, id: &Uuid) -> bool {
-        if let Some(handle) = self.active_queries.read().get(id) {
-            // Signal cooperative cancellation
-            handle.cancel();
-
-            // Send PostgreSQL cancel request to server (T031)
-            if let Some(pg_cancel_token) = handle.get_pg_cancel_token() {
-                let query_id = *id;
-                self.spawn(async move {
-                    // Use NoTls for cancel request (same as connection)
-                    match pg_cancel_token.cancel_query(tokio_postgres::NoTls).await {
-                        Ok(()) => {
-                            tracing::debug!(query_id = %query_id, "PostgreSQL cancel request sent");
-                        }
-                        Err(e) => {
-                            // Cancel request failed, but cooperative cancellation still works
-                            tracing::warn!(
-                                query_id = %query_id,
-                                error = %e,
-                                "Failed to send PostgreSQL cancel request"
-                            );
-                        }
-                    }
-                });
+        match self.running_queries.read().get(id) {
+            Some(handle) => {
+                handle.cancel();
+                true
             }
-
-            true
-        } else {
-            false
+            None => false,
         }
     }
 
-    /// Get all active query IDs.
-    pub fn active_query_ids(&self) -> Vec<Uuid> {
-        self.active_queries.read().keys().copied().collect()
+    /// Get the IDs of all running queries.
+    pub fn running_query_ids(&self) -> Vec<Uuid> {
+        self.running_queries.read().keys().copied().collect()
+    }
+
+    /// Cancel every running query and close all connection pools.
+    ///
+    /// Called when the app quits so queries don't keep running on the server
+    /// after Tusk exits. Blocks until the server acknowledges the cancel
+    /// requests, for at most a couple of seconds.
+    pub fn shutdown(&self) {
+        let handles: Vec<_> = self.running_queries.write().drain().map(|(_, h)| h).collect();
+        let cancel_tokens: Vec<_> = handles
+            .iter()
+            .filter_map(|handle| {
+                // Taken first so the requests below are the only ones sent
+                let token = handle.take_pg_cancel_token();
+                handle.cancel();
+                token
+            })
+            .collect();
+
+        if !cancel_tokens.is_empty() {
+            tracing::info!(queries = cancel_tokens.len(), "Cancelling running queries on shutdown");
+            let requests = futures_util::future::join_all(
+                cancel_tokens
+                    .into_iter()
+                    .map(|token| async move { token.cancel_query(tokio_postgres::NoTls).await }),
+            );
+            match self.block_on(tokio::time::timeout(SHUTDOWN_CANCEL_TIMEOUT, requests)) {
+                Ok(results) => {
+                    for e in results.into_iter().filter_map(Result::err) {
+                        tracing::warn!(error = %e, "Failed to cancel query on shutdown");
+                    }
+                }
+                Err(_) => tracing::warn!("Timed out cancelling queries on shutdown"),
+            }
+        }
+
+        for entry in self.connections.read().values() {
+            entry.pool().close();
+        }
     }
 
     // ========== Service Accessors ==========
@@ -345,6 +438,11 @@ impl TuskState {
         &self.credential_service
     }
 
+    /// Get a shared handle to the credential service for use in runtime tasks.
+    pub fn shared_credentials(&self) -> Arc<CredentialService> {
+        Arc::clone(&self.credential_service)
+    }
+
     /// Get the data directory path.
     pub fn data_dir(&self) -> &PathBuf {
         &self.data_dir
@@ -377,6 +475,56 @@ impl TuskState {
         self.tokio_runtime.block_on(future)
     }
 
+    // ========== Diagnostics ==========
+
+    /// Write a diagnostics bundle for bug reports to `path`.
+    ///
+    /// The zip holds the app version, platform, storage schema version,
+    /// connection names with pool statuses, and a redacted tail of the
+    /// current log. Passwords, hosts, usernames and SQL are left out.
+    pub fn export_diagnostics(&self, path: &Path) -> Result<(), TuskError> {
+        let saved = self.storage.load_all_connections()?;
+        let mut connections: Vec<DiagnosticsConnection> = {
+            let open = self.connections.read();
+            let mut connections: Vec<_> = saved
+                .iter()
+                .map(|config| DiagnosticsConnection {
+                    name: config.name.clone(),
+                    status: open.get(&config.id).map(|entry| entry.status().clone()),
+                    pool: open.get(&config.id).map(ConnectionEntry::pool_status),
+                })
+                .collect();
+            // Open connections that were never saved
+            connections.extend(
+                open.values().filter(|entry| saved.iter().all(|c| c.id != entry.id())).map(
+                    |entry| DiagnosticsConnection {
+                        name: entry.config().name.clone(),
+                        status: Some(entry.status().clone()),
+                        pool: Some(entry.pool_status()),
+                    },
+                ),
+            );
+            connections
+        };
+        connections.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let log_tail = crate::logging::latest_log_file(&crate::logging::log_dir_in(&self.data_dir))
+            .and_then(|file| std::fs::read_to_string(file).ok())
+            .map(|text| redacted_log_tail(&text, LOG_TAIL_LINES))
+            .unwrap_or_default();
+
+        let report = DiagnosticsReport {
+            app_version: env!("CARGO_PKG_VERSION").to_string(),
+            schema_version: self.storage.schema_version()?,
+            connections,
+            log_tail,
+        };
+        report.write_bundle(path)?;
+
+        tracing::info!(path = %path.display(), "Exported diagnostics bundle");
+        Ok(())
+    }
+
     // ========== Convenience Methods ==========
 
     /// Load all saved connections from storage.
@@ -389,6 +537,88 @@ impl TuskState {
         self.storage.save_connection(config)
     }
 
+    /// Import the services of a `.pg_service.conf` file as saved connections.
+    ///
+    /// Passwords given in the file go to the credential service when
+    /// `store_passwords` is set. Returns the imported connections.
+    pub fn import_from_pg_service(
+        &self,
+        path: &Path,
+        store_passwords: bool,
+    ) -> Result<Vec<ConnectionConfig>, TuskError> {
+        let imported = self.storage.import_from_pg_service(path)?;
+        self.store_imported_passwords(imported, store_passwords)
+    }
+
+    /// Import the entries of a `.pgpass` file as saved connections.
+    ///
+    /// Their passwords go to the credential service when `store_passwords`
+    /// is set. Returns the imported connections.
+    pub fn import_from_pgpass(
+        &self,
+        path: &Path,
+        store_passwords: bool,
+    ) -> Result<Vec<ConnectionConfig>, TuskError> {
+        let imported = self.storage.import_from_pgpass(path)?;
+        let imported = imported.into_iter().map(|(config, password)| (config, Some(password)));
+        self.store_imported_passwords(imported.collect(), store_passwords)
+    }
+
+    /// Store the passwords of imported connections, if requested.
+    ///
+    /// If a password can't be stored the import is undone, so no imported
+    /// connection is left saved without its password.
+    fn store_imported_passwords(
+        &self,
+        imported: Vec<(ConnectionConfig, Option<String>)>,
+        store_passwords: bool,
+    ) -> Result<Vec<ConnectionConfig>, TuskError> {
+        let mut stored = Vec::new();
+        for (config, password) in &imported {
+            let Some(password) = password.as_deref().filter(|p| store_passwords && !p.is_empty())
+            else {
+                continue;
+            };
+            if let Err(e) = self.credential_service.store_password(config.id, password) {
+                self.undo_import(imported.iter().map(|(config, _)| config.id), &stored);
+                return Err(e);
+            }
+            stored.push(config.id);
+        }
+        Ok(imported.into_iter().map(|(config, _)| config).collect())
+    }
+
+    /// Delete imported connections and the passwords stored for them.
+    ///
+    /// Failures are logged; the error that caused the rollback is the one
+    /// reported.
+    fn undo_import(&self, connection_ids: impl Iterator<Item = Uuid>, stored_passwords: &[Uuid]) {
+        for connection_id in connection_ids {
+            if let Err(e) = self.storage.delete_connection(connection_id) {
+                tracing::warn!(%connection_id, error = %e, "Failed to roll back imported connection");
+            }
+        }
+        for &connection_id in stored_passwords {
+            if let Err(e) = self.credential_service.delete_password(connection_id) {
+                tracing::warn!(%connection_id, error = %e, "Failed to roll back imported password");
+            }
+        }
+    }
+
+    /// Record a successful connect so recently used connections sort first.
+    ///
+    /// Has no effect for connections that are not saved. Failures are logged
+    /// rather than returned, since the connection itself succeeded.
+    pub fn record_connected(&self, connection_id: Uuid) {
+        if let Err(e) = self.storage.update_last_connected(connection_id) {
+            tracing::warn!(
+                connection_id = %connection_id,
+                error = %e,
+                "Failed to record last connected time"
+            );
+        }
+    }
+
     // ========== High-Level Connection API (FR-005, FR-006, FR-007, FR-008) ==========
 
     /// Establish a new database connection (FR-005, FR-006, FR-007).
@@ -419,6 +649,15 @@ impl TuskState {
         // Create and validate connection pool
         let pool = ConnectionPool::new(config.clone(), password).await?;
 
+        // Open a few idle connections up front so the first queries start fast
+        if let Err(e) = pool.warmup(WARMUP_CONNECTIONS).await {
+            tracing::warn!(
+                connection_id = %connection_id,
+                error = %e,
+                "Failed to warm up connection pool"
+            );
+        }
+
         // Store password in credential service (FR-009, FR-028)
         // Note: password is intentionally NOT logged (FR-026)
         if let Err(e) = self.credential_service.store_password(connection_id, password) {
@@ -433,6 +672,7 @@ impl TuskState {
         // Add connection entry with Connected status
         let entry = ConnectionEntry::new(config.clone(), Arc::new(pool));
         self.connections.write().insert(connection_id, entry);
+        self.record_connected(connection_id);
 
         tracing::info!(
             connection_id = %connection_id,
@@ -452,21 +692,17 @@ impl TuskState {
     /// # Arguments
     /// * `connection_id` - ID of connection to close
     pub async fn disconnect(&self, connection_id: Uuid) -> Result<(), TuskError> {
+        self.close_connection(connection_id)
+    }
+
+    /// Close a database connection without awaiting, for callers on the UI thread.
+    ///
+    /// Does the same as [`TuskState::disconnect`], which never has to wait.
+    pub fn close_connection(&self, connection_id: Uuid) -> Result<(), TuskError> {
         tracing::debug!(connection_id = %connection_id, "Disconnecting");
 
         // Cancel all active queries on this connection
-        let query_ids: Vec<Uuid> = self
-            .active_queries
-            .read()
-            .iter()
-            .filter(|(_, handle)| handle.connection_id() == connection_id)
-            .map(|(id, _)| *id)
-            .collect();
-
-        for query_id in query_ids {
-            self.cancel_query(&query_id);
-            self.unregister_query(&query_id);
-        }
+        self.cancel_connection_queries(connection_id);
 
         // Remove schema cache
         self.schema_caches.write().remove(&connection_id);
@@ -482,6 +718,44 @@ impl TuskState {
         }
     }
 
+    /// Close every open connection.
+    ///
+    /// Cancels the queries running on them, closes their pools and removes
+    /// them and their schema caches from state. Returns the IDs of the
+    /// connections that were closed.
+    pub fn disconnect_all(&self) -> Vec<Uuid> {
+        let entries: Vec<ConnectionEntry> =
+            self.connections.write().drain().map(|(_, entry)| entry).collect();
+
+        let mut closed = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let connection_id = entry.id();
+            self.cancel_connection_queries(connection_id);
+            self.schema_caches.write().remove(&connection_id);
+            entry.pool().close();
+            closed.push(connection_id);
+        }
+
+        tracing::info!(connections = closed.len(), "Closed all connections");
+        closed
+    }
+
+    /// Cancel and unregister the running queries of a connection.
+    fn cancel_connection_queries(&self, connection_id: Uuid) {
+        let query_ids: Vec<Uuid> = self
+            .running_queries
+            .read()
+            .iter()
+            .filter(|(_, handle)| handle.connection_id() == connection_id)
+            .map(|(id, _)| *id)
+            .collect();
+
+        for query_id in query_ids {
+            self.cancel_query(&query_id);
+            self.unregister_query(&query_id);
+        }
+    }
+
     /// Get the current status of a connection (FR-006).
     ///
     /// Returns ConnectionStatus for a connection ID.
@@ -583,21 +857,23 @@ impl TuskState {
             .get_connection(&connection_id)
             .ok_or_else(|| TuskError::internal(format!("No active connection: {connection_id}")))?;
 
-        // Create and register query handle
-        let handle = QueryHandle::new(connection_id, sql.to_string());
-        let handle = self.register_query(handle);
+        // Create and register query handle; it's unregistered when the
+        // registration is dropped, including when no connection is available
+        let registration = self.track_query(QueryHandle::new(connection_id, sql.to_string()));
+        let handle = registration.handle().clone();
 
         // Get a connection from the pool
         let conn = pool.get().await?;
 
         // Execute streaming query
         let sql_owned = sql.to_string();
-        let handle_ref = handle.clone();
 
-        // Spawn the streaming execution
+        // Spawn the streaming execution, unregistering the query once it ends
         let query_id = handle.id();
         self.spawn(async move {
-            let result = QueryService::execute_streaming(&conn, &sql_owned, &handle_ref, tx).await;
+            let result =
+                QueryService::execute_streaming(&conn, &sql_owned, registration.handle(), tx).await;
+            drop(registration);
 
             if let Err(e) = result {
                 tracing::warn!(
@@ -615,3 +891,232 @@ impl TuskState {
 // Implement GPUI's Global trait for application-wide state access (FR-005)
 #[cfg(feature = "gpui")]
 impl gpui::Global for TuskState {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> (tempfile::TempDir, TuskState) {
+        let dir = tempfile::tempdir().unwrap();
+        let state = TuskState::with_data_dir(dir.path().to_path_buf()).unwrap();
+        (dir, state)
+    }
+
+    #[test]
+    fn test_query_registry_lifecycle() {
+        let (_dir, state) = state();
+        let connection_id = Uuid::new_v4();
+
+        let first = state.register_query(QueryHandle::new(connection_id, "SELECT 1"));
+        let second = state.register_query(QueryHandle::new(connection_id, "SELECT 2"));
+        let mut running = state.running_query_ids();
+        running.sort();
+        let mut expected = vec![first.id(), second.id()];
+        expected.sort();
+        assert_eq!(running, expected);
+
+        let removed = state.unregister_query(&first.id()).expect("registered");
+        assert_eq!(removed.id(), first.id());
+        assert_eq!(state.running_query_ids(), vec![second.id()]);
+
+        // Unregistering twice is harmless
+        assert!(state.unregister_query(&first.id()).is_none());
+        assert!(state.get_query(&first.id()).is_none());
+    }
+
+    #[test]
+    fn test_query_registration_unregisters_on_drop() {
+        let (_dir, state) = state();
+        let registration = state.track_query(QueryHandle::new(Uuid::new_v4(), "SELECT 1"));
+        let id = registration.handle().id();
+        assert_eq!(state.running_query_ids(), vec![id]);
+
+        drop(registration);
+        assert!(state.running_query_ids().is_empty());
+        assert!(state.get_query(&id).is_none());
+    }
+
+    #[test]
+    fn test_cancel_marks_handle_cancelled() {
+        let (_dir, state) = state();
+        let handle = state.register_query(QueryHandle::new(Uuid::new_v4(), "SELECT pg_sleep(10)"));
+        assert!(!handle.is_cancelled());
+
+        assert!(state.cancel_query(&handle.id()));
+        assert!(handle.is_cancelled());
+        // Cancelling leaves the query registered until its runner finishes
+        assert_eq!(state.running_query_ids(), vec![handle.id()]);
+
+        state.unregister_query(&handle.id());
+        assert!(!state.cancel_query(&handle.id()));
+    }
+
+    #[test]
+    fn test_disconnect_all_closes_every_pool() {
+        let (_dir, state) = state();
+        let pools: Vec<Arc<ConnectionPool>> = ["a", "b", "c"]
+            .into_iter()
+            .map(|name| {
+                let config = ConnectionConfig::new(name, "localhost", "postgres", "postgres");
+                let pool = Arc::new(ConnectionPool::unvalidated(config));
+                state.add_connection_arc(pool.config().clone(), pool.clone());
+                pool
+            })
+            .collect();
+        let query = state.register_query(QueryHandle::new(pools[0].id(), "SELECT pg_sleep(10)"));
+        let other = state.register_query(QueryHandle::new(Uuid::new_v4(), "SELECT 1"));
+
+        let mut closed = state.disconnect_all();
+        closed.sort();
+        let mut expected: Vec<Uuid> = pools.iter().map(|pool| pool.id()).collect();
+        expected.sort();
+        assert_eq!(closed, expected);
+
+        assert!(pools.iter().all(|pool| pool.is_closed()));
+        assert!(state.connection_ids().is_empty());
+        assert!(query.is_cancelled());
+        // Queries of connections that were not open are left alone
+        assert!(!other.is_cancelled());
+        assert_eq!(state.running_query_ids(), vec![other.id()]);
+        assert!(state.disconnect_all().is_empty());
+    }
+
+    #[test]
+    fn test_shutdown_cancels_running_queries() {
+        let (_dir, state) = state();
+        let handles: Vec<_> = (0..3)
+            .map(|i| state.register_query(QueryHandle::new(Uuid::new_v4(), format!("SELECT {i}"))))
+            .collect();
+
+        state.shutdown();
+
+        assert!(handles.iter().all(|handle| handle.is_cancelled()));
+        assert!(state.running_query_ids().is_empty());
+    }
+
+    #[test]
+    fn test_ddl_invalidates_schema_cache() {
+        use crate::models::DatabaseSchema;
+
+        let (_dir, state) = state();
+        let connection_id = Uuid::new_v4();
+        state.set_schema_cache(SchemaCache::new(connection_id, DatabaseSchema::default()));
+
+        assert!(!state.invalidate_schema_after(&connection_id, QueryType::Select));
+        assert!(!state.invalidate_schema_after(&connection_id, QueryType::Utility));
+        assert!(state.has_valid_schema_cache(&connection_id));
+
+        assert!(state.invalidate_schema_after(&connection_id, QueryType::Ddl));
+        assert!(!state.has_valid_schema_cache(&connection_id));
+        assert!(state.get_schema_cache(&connection_id).is_none());
+        // The stale schema stays available for display until the refresh lands
+        assert!(state.get_schema_cache_any(&connection_id).is_some());
+
+        // A connection without a loaded cache still asks for a refresh
+        assert!(state.invalidate_schema_after(&Uuid::new_v4(), QueryType::Ddl));
+    }
+
+    #[test]
+    fn test_export_diagnostics_bundle() {
+        use std::io::Read;
+
+        let (dir, state) = state();
+        let config = ConnectionConfig::new("Billing Replica", "db.internal", "billing", "app_user");
+        state.save_connection(&config).unwrap();
+        // The keychain may be unavailable in CI; the bundle must not depend on it
+        let _ = state.store_password(config.id, "hunter2-secret");
+
+        let log_dir = crate::logging::log_dir_in(dir.path());
+        std::fs::create_dir_all(&log_dir).unwrap();
+        std::fs::write(
+            log_dir.join("tusk.2024-01-02.log"),
+            "INFO tusk: Connected name=Billing\n\
+             DEBUG tusk: Retry password=hunter2-secret attempt=2\n\
+             DEBUG tusk: Executing sql=SELECT card_number FROM payments rows=1\n",
+        )
+        .unwrap();
+
+        let bundle = dir.path().join("diagnostics.zip");
+        state.export_diagnostics(&bundle).unwrap();
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&bundle).unwrap()).unwrap();
+        let mut names: Vec<_> = archive.file_names().map(String::from).collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec![
+                crate::diagnostics::CONNECTIONS_ENTRY,
+                crate::diagnostics::INFO_ENTRY,
+                crate::diagnostics::LOG_ENTRY
+            ]
+        );
+
+        let mut contents = String::new();
+        for i in 0..archive.len() {
+            archive.by_index(i).unwrap().read_to_string(&mut contents).unwrap();
+        }
+        assert!(contents.contains("Billing Replica"));
+        assert!(contents.contains(env!("CARGO_PKG_VERSION")));
+        assert!(contents.contains("Connected name=Billing"));
+        for secret in ["hunter2-secret", "card_number", "db.internal", "app_user"] {
+            assert!(!contents.contains(secret), "bundle leaked {secret}");
+        }
+    }
+
+    /// Stores one credential, then fails like a full or locked keychain.
+    struct OneSlotProvider(Arc<parking_lot::Mutex<HashMap<String, String>>>);
+
+    impl crate::services::credentials::CredentialsProvider for OneSlotProvider {
+        fn store(&self, key: &str, value: &str) -> Result<(), TuskError> {
+            let mut values = self.0.lock();
+            if !values.is_empty() {
+                return Err(TuskError::keyring("Keychain is locked", None));
+            }
+            values.insert(key.to_string(), value.to_string());
+            Ok(())
+        }
+
+        fn get(&self, key: &str) -> Result<Option<String>, TuskError> {
+            Ok(self.0.lock().get(key).cloned())
+        }
+
+        fn delete(&self, key: &str) -> Result<(), TuskError> {
+            self.0.lock().remove(key);
+            Ok(())
+        }
+
+        fn name(&self) -> &'static str {
+            "one-slot"
+        }
+
+        fn backend(&self) -> crate::services::CredentialBackend {
+            crate::services::CredentialBackend::Session
+        }
+    }
+
+    #[test]
+    fn test_failed_password_store_rolls_back_import() {
+        let (dir, mut state) = state();
+        let stored = Arc::new(parking_lot::Mutex::new(HashMap::new()));
+        state.credential_service =
+            Arc::new(CredentialService::with_provider(Box::new(OneSlotProvider(stored.clone()))));
+
+        let pgpass = dir.path().join("pgpass");
+        std::fs::write(
+            &pgpass,
+            "db1:5432:app:app:first
+db2:5432:app:app:second
+",
+        )
+        .unwrap();
+        assert!(state.import_from_pgpass(&pgpass, true).is_err());
+
+        // Neither connection stays saved, and the stored password is removed
+        assert!(state.load_saved_connections().unwrap().is_empty());
+        assert!(stored.lock().is_empty());
+
+        // Without storing passwords the same import succeeds
+        assert_eq!(state.import_from_pgpass(&pgpass, false).unwrap().len(), 2);
+        assert_eq!(state.load_saved_connections().unwrap().len(), 2);
+    }
+}
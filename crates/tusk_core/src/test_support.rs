@@ -0,0 +1,34 @@
+//! Helpers shared by tests that run against a PostgreSQL server.
+//!
+//! Those tests are `#[ignore]`d and connect using the libpq environment
+//! variables (`PGHOST`, `PGPORT`, `PGDATABASE`, `PGUSER`, `PGPASSWORD`).
+
+use std::time::Duration;
+
+use crate::models::ConnectionConfig;
+use crate::services::connection::ConnectionPool;
+
+/// Connect using the libpq environment variables, defaulting to a local server.
+pub(crate) async fn local_pool(max_size: usize) -> ConnectionPool {
+    local_pool_with(max_size, |_| {}).await
+}
+
+/// Connect like [`local_pool`], adjusting the connection config first.
+pub(crate) async fn local_pool_with(
+    max_size: usize,
+    configure: impl FnOnce(&mut ConnectionConfig),
+) -> ConnectionPool {
+    let var = |name: &str, default: &str| std::env::var(name).unwrap_or(default.to_string());
+    let mut config = ConnectionConfig::new(
+        "test",
+        var("PGHOST", "localhost"),
+        var("PGDATABASE", "postgres"),
+        var("PGUSER", "postgres"),
+    );
+    config.port = var("PGPORT", "5432").parse().expect("PGPORT");
+    configure(&mut config);
+    let password = var("PGPASSWORD", "postgres");
+    ConnectionPool::with_pool_config(config, &password, max_size, Duration::from_secs(5))
+        .await
+        .expect("connect to local PostgreSQL")
+}
@@ -138,7 +138,8 @@ mod tests {
         );
         assert_eq!(QueryService::detect_query_type("UPDATE users SET x = 1"), QueryType::Update);
         assert_eq!(QueryService::detect_query_type("DELETE FROM users"), QueryType::Delete);
-        assert_eq!(QueryService::detect_query_type("CREATE TABLE test (id INT)"), QueryType::Other);
+        assert_eq!(QueryService::detect_query_type("CREATE TABLE test (id INT)"), QueryType::Ddl);
+        assert_eq!(QueryService::detect_query_type("COPY test FROM STDIN"), QueryType::Other);
     }
 
     // =========================================================================
@@ -649,12 +650,20 @@ mod tests {
     fn test_e21_zero_rows_is_informational() {
         // E21 is not an error - it's just an informational display in results panel
         // Verify that QueryEvent::complete with 0 rows doesn't create an error
-        let event = QueryEvent::complete(0, 50, None);
+        let event = QueryEvent::complete(0, std::time::Duration::from_millis(50), None);
         match event {
-            QueryEvent::Complete { total_rows, execution_time_ms, rows_affected } => {
+            QueryEvent::Complete {
+                total_rows,
+                execution_time_ms,
+                execution_time_us,
+                rows_affected,
+                command_tag,
+            } => {
                 assert_eq!(total_rows, 0);
                 assert_eq!(execution_time_ms, 50);
+                assert_eq!(execution_time_us, 50_000);
                 assert!(rows_affected.is_none());
+                assert!(command_tag.is_none());
             }
             _ => panic!("Expected Complete variant"),
         }
@@ -0,0 +1,36 @@
+//! Embedded application assets.
+//!
+//! SVG icons are compiled into the binary and served to GPUI through the
+//! [`Assets`] source, so icons render without files next to the executable.
+
+use std::borrow::Cow;
+
+use gpui::{AssetSource, Result, SharedString};
+
+/// SVG icons by asset path.
+const ICONS: &[(&str, &[u8])] = &[
+    ("icons/constraint.svg", include_bytes!("../../../assets/icons/constraint.svg")),
+    ("icons/enum.svg", include_bytes!("../../../assets/icons/enum.svg")),
+    ("icons/foreign_key.svg", include_bytes!("../../../assets/icons/foreign_key.svg")),
+    ("icons/index.svg", include_bytes!("../../../assets/icons/index.svg")),
+    ("icons/sequence.svg", include_bytes!("../../../assets/icons/sequence.svg")),
+    ("icons/trigger.svg", include_bytes!("../../../assets/icons/trigger.svg")),
+];
+
+/// Asset source for embedded icons, registered with `Application::with_assets`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Assets;
+
+impl AssetSource for Assets {
+    fn load(&self, path: &str) -> Result<Option<Cow<'static, [u8]>>> {
+        Ok(ICONS.iter().find(|(name, _)| *name == path).map(|(_, bytes)| Cow::Borrowed(*bytes)))
+    }
+
+    fn list(&self, path: &str) -> Result<Vec<SharedString>> {
+        Ok(ICONS
+            .iter()
+            .filter(|(name, _)| name.starts_with(path))
+            .map(|(name, _)| SharedString::from(*name))
+            .collect())
+    }
+}
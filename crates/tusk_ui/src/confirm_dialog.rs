@@ -4,14 +4,15 @@
 //! destructive operations like closing unsaved tabs, dropping tables, etc.
 
 use gpui::{
-    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, MouseButton, Render,
-    SharedString, Window,
+    div, prelude::*, px, App, Context, Entity, EventEmitter, FocusHandle, MouseButton, Render,
+    SharedString, Subscription, Window,
 };
 
 use crate::button::{Button, ButtonVariant};
 use crate::icon::IconName;
 use crate::key_bindings::modal;
 use crate::panel::Focusable;
+use crate::text_input::{TextInput, TextInputEvent};
 use crate::TuskTheme;
 
 /// Events emitted by the confirm dialog.
@@ -24,7 +25,7 @@ pub enum ConfirmDialogEvent {
 }
 
 /// The type of confirmation dialog (affects styling).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ConfirmDialogKind {
     /// Standard confirmation (blue accent).
     #[default]
@@ -33,6 +34,24 @@ pub enum ConfirmDialogKind {
     Warning,
     /// Destructive action confirmation (red accent).
     Destructive,
+    /// Destructive action that stays disabled until `expected` is typed (red accent).
+    TypeToConfirm {
+        /// Text the user must type, such as the name of a table to drop.
+        expected: String,
+    },
+}
+
+impl ConfirmDialogKind {
+    /// Check if `typed` enables the confirm button for this kind.
+    ///
+    /// Only [`Self::TypeToConfirm`] checks the text: it must equal `expected`
+    /// after trimming surrounding whitespace, with matching case.
+    pub fn accepts(&self, typed: &str) -> bool {
+        match self {
+            Self::TypeToConfirm { expected } => typed.trim() == expected,
+            Self::Standard | Self::Warning | Self::Destructive => true,
+        }
+    }
 }
 
 /// A modal confirmation dialog.
@@ -54,6 +73,12 @@ pub struct ConfirmDialog {
     kind: ConfirmDialogKind,
     /// Focus handle for keyboard navigation.
     focus_handle: FocusHandle,
+    /// Input for the text a type-to-confirm dialog requires.
+    confirm_input: Option<Entity<TextInput>>,
+    /// Text typed into `confirm_input`.
+    typed: String,
+    /// Subscription to `confirm_input` events.
+    _input_subscription: Option<Subscription>,
 }
 
 impl ConfirmDialog {
@@ -70,6 +95,9 @@ impl ConfirmDialog {
             cancel_label: "Cancel".into(),
             kind: ConfirmDialogKind::default(),
             focus_handle: cx.focus_handle(),
+            confirm_input: None,
+            typed: String::new(),
+            _input_subscription: None,
         }
     }
 
@@ -86,6 +114,35 @@ impl ConfirmDialog {
             cancel_label: "Cancel".into(),
             kind: ConfirmDialogKind::Destructive,
             focus_handle: cx.focus_handle(),
+            confirm_input: None,
+            typed: String::new(),
+            _input_subscription: None,
+        }
+    }
+
+    /// Create a destructive dialog confirmed by typing `expected`, such as a table name.
+    ///
+    /// The confirm button stays disabled until the typed text matches.
+    pub fn type_to_confirm(
+        title: impl Into<SharedString>,
+        message: impl Into<SharedString>,
+        expected: impl Into<String>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let expected = expected.into();
+        let input = cx.new(|cx| TextInput::new(expected.clone(), cx));
+        let subscription = cx.subscribe(&input, Self::handle_input_event);
+
+        Self {
+            title: title.into(),
+            message: message.into(),
+            confirm_label: "Delete".into(),
+            cancel_label: "Cancel".into(),
+            kind: ConfirmDialogKind::TypeToConfirm { expected },
+            focus_handle: cx.focus_handle(),
+            confirm_input: Some(input),
+            typed: String::new(),
+            _input_subscription: Some(subscription),
         }
     }
 
@@ -102,6 +159,9 @@ impl ConfirmDialog {
             cancel_label: "Cancel".into(),
             kind: ConfirmDialogKind::Warning,
             focus_handle: cx.focus_handle(),
+            confirm_input: None,
+            typed: String::new(),
+            _input_subscription: None,
         }
     }
 
@@ -123,11 +183,39 @@ impl ConfirmDialog {
         self
     }
 
-    /// Confirm the action.
+    /// Check if the confirm button is enabled.
+    pub fn is_confirm_enabled(&self) -> bool {
+        self.kind.accepts(&self.typed)
+    }
+
+    /// Confirm the action, unless the required text hasn't been typed.
     pub fn confirm(&mut self, cx: &mut Context<Self>) {
+        if !self.is_confirm_enabled() {
+            return;
+        }
         cx.emit(ConfirmDialogEvent::Confirmed);
     }
 
+    /// Track the typed confirmation text; Enter confirms once it matches.
+    fn handle_input_event(
+        &mut self,
+        _input: Entity<TextInput>,
+        event: &TextInputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            TextInputEvent::Changed(text) => {
+                self.typed = text.clone();
+                cx.notify();
+            }
+            TextInputEvent::Submitted(text) => {
+                self.typed = text.clone();
+                self.confirm(cx);
+            }
+            TextInputEvent::Focus | TextInputEvent::Blur => {}
+        }
+    }
+
     /// Dismiss the dialog.
     pub fn dismiss(&mut self, cx: &mut Context<Self>) {
         cx.emit(ConfirmDialogEvent::Dismissed);
@@ -138,7 +226,9 @@ impl ConfirmDialog {
         match self.kind {
             ConfirmDialogKind::Standard => IconName::Info,
             ConfirmDialogKind::Warning => IconName::Warning,
-            ConfirmDialogKind::Destructive => IconName::Trash,
+            ConfirmDialogKind::Destructive | ConfirmDialogKind::TypeToConfirm { .. } => {
+                IconName::Trash
+            }
         }
     }
 
@@ -147,7 +237,9 @@ impl ConfirmDialog {
         match self.kind {
             ConfirmDialogKind::Standard => ButtonVariant::Primary,
             ConfirmDialogKind::Warning => ButtonVariant::Primary,
-            ConfirmDialogKind::Destructive => ButtonVariant::Danger,
+            ConfirmDialogKind::Destructive | ConfirmDialogKind::TypeToConfirm { .. } => {
+                ButtonVariant::Danger
+            }
         }
     }
 }
@@ -155,8 +247,12 @@ impl ConfirmDialog {
 impl EventEmitter<ConfirmDialogEvent> for ConfirmDialog {}
 
 impl Focusable for ConfirmDialog {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
+    /// Type-to-confirm dialogs focus their input so typing starts right away.
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        match &self.confirm_input {
+            Some(input) => gpui::Focusable::focus_handle(input.read(cx), cx),
+            None => self.focus_handle.clone(),
+        }
     }
 }
 
@@ -167,7 +263,13 @@ impl Render for ConfirmDialog {
         let icon_color = match self.kind {
             ConfirmDialogKind::Standard => theme.colors.accent,
             ConfirmDialogKind::Warning => theme.colors.warning,
-            ConfirmDialogKind::Destructive => theme.colors.error,
+            ConfirmDialogKind::Destructive | ConfirmDialogKind::TypeToConfirm { .. } => {
+                theme.colors.error
+            }
+        };
+        let expected = match &self.kind {
+            ConfirmDialogKind::TypeToConfirm { expected } => Some(expected.clone()),
+            _ => None,
         };
 
         // Modal backdrop
@@ -233,6 +335,22 @@ impl Render for ConfirmDialog {
                             .text_color(theme.colors.text_muted)
                             .child(self.message.clone()),
                     )
+                    // Text the user must type to enable the confirm button
+                    .when_some(self.confirm_input.clone().zip(expected), |d, (input, expected)| {
+                        d.child(
+                            div()
+                                .flex()
+                                .flex_col()
+                                .gap(px(6.0))
+                                .child(
+                                    div()
+                                        .text_size(px(13.0))
+                                        .text_color(theme.colors.text)
+                                        .child(format!("Type \"{}\" to confirm", expected)),
+                                )
+                                .child(input),
+                        )
+                    })
                     // Action buttons
                     .child(
                         div()
@@ -251,6 +369,7 @@ impl Render for ConfirmDialog {
                                 Button::new("confirm-button")
                                     .label(self.confirm_label.clone())
                                     .variant(self.confirm_button_variant())
+                                    .disabled(!self.is_confirm_enabled())
                                     .on_click(cx.listener(|this, _, _window, cx| {
                                         this.confirm(cx);
                                     })),
@@ -266,9 +385,27 @@ mod tests {
 
     #[test]
     fn test_confirm_dialog_kinds() {
-        // Verify icon mapping works
-        assert_eq!(ConfirmDialogKind::Standard as i32, ConfirmDialogKind::Standard as i32);
-        assert_eq!(ConfirmDialogKind::Warning as i32, ConfirmDialogKind::Warning as i32);
-        assert_eq!(ConfirmDialogKind::Destructive as i32, ConfirmDialogKind::Destructive as i32);
+        assert_eq!(ConfirmDialogKind::default(), ConfirmDialogKind::Standard);
+        assert_ne!(ConfirmDialogKind::Warning, ConfirmDialogKind::Destructive);
+        for kind in [
+            ConfirmDialogKind::Standard,
+            ConfirmDialogKind::Warning,
+            ConfirmDialogKind::Destructive,
+        ] {
+            assert!(kind.accepts(""));
+        }
+    }
+
+    #[test]
+    fn test_type_to_confirm_requires_exact_text() {
+        let kind = ConfirmDialogKind::TypeToConfirm { expected: "orders".to_string() };
+
+        assert!(kind.accepts("orders"));
+        assert!(kind.accepts("  orders\n"));
+        assert!(!kind.accepts(""));
+        assert!(!kind.accepts("order"));
+        assert!(!kind.accepts("Orders"));
+        assert!(!kind.accepts("orders_archive"));
+        assert!(!kind.accepts("or ders"));
     }
 }
@@ -0,0 +1,175 @@
+//! Debouncing for filter and search inputs.
+//!
+//! Rapid calls are coalesced so only the latest value is delivered, once no
+//! new call has arrived for the debounce delay. [`DebounceState`] holds the
+//! timing logic against an explicit clock; [`Debouncer`] drives it with GPUI
+//! timers.
+
+use std::time::{Duration, Instant};
+
+use gpui::{Context, Task};
+
+/// Debounce timing logic, independent of any executor.
+#[derive(Debug)]
+pub struct DebounceState<T> {
+    delay: Duration,
+    pending: Option<(Instant, T)>,
+}
+
+impl<T> DebounceState<T> {
+    /// Create a state that fires after `delay` of quiet.
+    pub fn new(delay: Duration) -> Self {
+        Self { delay, pending: None }
+    }
+
+    /// The debounce delay.
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Record a call at `now`, replacing any pending value and restarting the window.
+    pub fn push(&mut self, value: T, now: Instant) {
+        self.pending = Some((now, value));
+    }
+
+    /// When the pending value becomes due, if any.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.pending.as_ref().map(|(at, _)| *at + self.delay)
+    }
+
+    /// Take the pending value if the quiet interval has elapsed by `now`.
+    pub fn poll(&mut self, now: Instant) -> Option<T> {
+        match self.deadline() {
+            Some(deadline) if now >= deadline => self.flush(),
+            _ => None,
+        }
+    }
+
+    /// Take the pending value immediately, regardless of timing.
+    pub fn flush(&mut self) -> Option<T> {
+        self.pending.take().map(|(_, value)| value)
+    }
+
+    /// Check if a value is waiting to fire.
+    pub fn is_pending(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+/// Coalesces rapid calls on a view and fires the latest after a quiet interval.
+///
+/// Each call restarts a GPUI timer; dropping the previous timer task cancels it.
+pub struct Debouncer<T> {
+    state: DebounceState<T>,
+    _timer: Option<Task<()>>,
+}
+
+impl<T: 'static> Debouncer<T> {
+    /// Create a debouncer with the given delay.
+    pub fn new(delay: Duration) -> Self {
+        Self { state: DebounceState::new(delay), _timer: None }
+    }
+
+    /// Schedule `fire` with `value` once no further call arrives within the delay.
+    ///
+    /// `debouncer` locates this debouncer on the view when the timer elapses.
+    pub fn call<V: 'static>(
+        &mut self,
+        value: T,
+        cx: &mut Context<V>,
+        debouncer: fn(&mut V) -> &mut Debouncer<T>,
+        fire: fn(&mut V, T, &mut Context<V>),
+    ) {
+        self.state.push(value, Instant::now());
+        let delay = self.state.delay();
+
+        self._timer = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(delay).await;
+            let _ = this.update(cx, |view, cx| {
+                if let Some(value) = debouncer(view).state.poll(Instant::now()) {
+                    fire(view, value, cx);
+                }
+            });
+        }));
+    }
+
+    /// Take the pending value now and cancel the timer.
+    pub fn flush(&mut self) -> Option<T> {
+        self._timer = None;
+        self.state.flush()
+    }
+
+    /// Check if a call is waiting to fire.
+    pub fn is_pending(&self) -> bool {
+        self.state.is_pending()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELAY: Duration = Duration::from_millis(300);
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_fires_only_after_quiet_interval() {
+        let start = Instant::now();
+        let mut state = DebounceState::new(DELAY);
+
+        state.push("u", start);
+        assert_eq!(state.poll(start + ms(299)), None);
+        assert_eq!(state.poll(start + ms(300)), Some("u"));
+        assert_eq!(state.poll(start + ms(900)), None, "fires once per burst");
+    }
+
+    #[test]
+    fn test_coalesces_rapid_events_into_latest_value() {
+        let start = Instant::now();
+        let mut state = DebounceState::new(DELAY);
+        let mut fired = Vec::new();
+
+        // Keystrokes 100ms apart, then a pause, then one more keystroke
+        let events = [(0, "u"), (100, "us"), (200, "use"), (300, "user"), (1000, "users")];
+        let mut events = events.iter().peekable();
+        for tick in (0..=1500).step_by(50) {
+            let now = start + ms(tick);
+            while let Some((_, value)) = events.next_if(|(at, _)| *at <= tick) {
+                state.push(*value, now);
+            }
+            if let Some(value) = state.poll(now) {
+                fired.push((tick, value));
+            }
+        }
+
+        assert_eq!(fired, vec![(600, "user"), (1300, "users")]);
+    }
+
+    #[test]
+    fn test_new_event_restarts_window() {
+        let start = Instant::now();
+        let mut state = DebounceState::new(DELAY);
+
+        state.push(1, start);
+        state.push(2, start + ms(250));
+        assert_eq!(state.deadline(), Some(start + ms(550)));
+        assert_eq!(state.poll(start + ms(400)), None);
+        assert_eq!(state.poll(start + ms(550)), Some(2));
+    }
+
+    #[test]
+    fn test_flush_returns_pending_immediately() {
+        let start = Instant::now();
+        let mut state = DebounceState::new(DELAY);
+        assert_eq!(state.flush(), None::<&str>);
+
+        state.push("orders", start);
+        assert!(state.is_pending());
+        assert_eq!(state.flush(), Some("orders"));
+        assert!(!state.is_pending());
+        assert_eq!(state.poll(start + ms(1000)), None);
+    }
+}
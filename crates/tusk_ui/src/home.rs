@@ -0,0 +1,222 @@
+//! Home view shown in an empty pane.
+//!
+//! Lists the most recently used connections for one-click connect, plus a
+//! button to open the connection dialog for a new connection.
+
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use gpui::{
+    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, Render, SharedString, Window,
+};
+use uuid::Uuid;
+
+use crate::button::{Button, ButtonVariant};
+use crate::icon::{Icon, IconName, IconSize};
+use crate::panel::Focusable;
+use crate::panels::result_history::format_age;
+use crate::TuskTheme;
+
+#[cfg(feature = "persistence")]
+use tusk_core::TuskState;
+
+/// Maximum number of recent connections listed.
+const MAX_RECENT_CONNECTIONS: usize = 8;
+
+/// Events emitted by the home view.
+#[derive(Debug, Clone)]
+pub enum HomeViewEvent {
+    /// Connect to a saved connection.
+    Connect { connection_id: Uuid },
+    /// Open the connection dialog for a new connection.
+    NewConnection,
+}
+
+/// A recently used connection shown on the home view.
+#[derive(Debug, Clone)]
+struct RecentConnectionEntry {
+    id: Uuid,
+    name: SharedString,
+    host: SharedString,
+    database: SharedString,
+    /// Unix time of the last successful connect.
+    last_connected_secs: i64,
+}
+
+/// Home view listing recent connections.
+pub struct HomeView {
+    focus_handle: FocusHandle,
+    recent: Vec<RecentConnectionEntry>,
+}
+
+impl HomeView {
+    /// Create the home view and load recent connections.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self { focus_handle: cx.focus_handle(), recent: Self::load_recent(cx) }
+    }
+
+    /// Reload recent connections from storage.
+    pub fn reload(&mut self, cx: &mut Context<Self>) {
+        self.recent = Self::load_recent(cx);
+        cx.notify();
+    }
+
+    /// Load recent connections from storage.
+    #[cfg(feature = "persistence")]
+    fn load_recent(cx: &App) -> Vec<RecentConnectionEntry> {
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return Vec::new();
+        };
+        match state.storage().load_recent_connections(MAX_RECENT_CONNECTIONS) {
+            Ok(recent) => recent
+                .into_iter()
+                .map(|recent| RecentConnectionEntry {
+                    id: recent.config.id,
+                    name: recent.config.name.into(),
+                    host: recent.config.host.into(),
+                    database: recent.config.database.into(),
+                    last_connected_secs: recent.last_connected_at.timestamp(),
+                })
+                .collect(),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load recent connections");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Load recent connections placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn load_recent(_cx: &App) -> Vec<RecentConnectionEntry> {
+        Vec::new()
+    }
+
+    /// Render the list of recent connections.
+    fn render_recent(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
+        let now_secs =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as i64).unwrap_or(0);
+
+        if self.recent.is_empty() {
+            return div()
+                .text_size(px(12.0))
+                .text_color(theme.colors.text_muted)
+                .child("No recent connections")
+                .into_any_element();
+        }
+
+        div()
+            .flex()
+            .flex_col()
+            .w_full()
+            .border_1()
+            .border_color(theme.colors.border)
+            .rounded(px(6.0))
+            .overflow_hidden()
+            .children(self.recent.iter().map(|entry| {
+                let connection_id = entry.id;
+                let age = Duration::from_secs((now_secs - entry.last_connected_secs).max(0) as u64);
+
+                div()
+                    .id(entry.id)
+                    .flex()
+                    .items_center()
+                    .gap(px(10.0))
+                    .px(px(12.0))
+                    .py(px(8.0))
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.colors.element_hover))
+                    .on_click(cx.listener(move |_this, _, _window, cx| {
+                        cx.emit(HomeViewEvent::Connect { connection_id });
+                    }))
+                    .child(
+                        Icon::new(IconName::Database)
+                            .size(IconSize::Small)
+                            .color(theme.colors.text_muted),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .gap(px(2.0))
+                            .child(
+                                div()
+                                    .text_size(px(13.0))
+                                    .text_color(theme.colors.text)
+                                    .child(entry.name.clone()),
+                            )
+                            .child(
+                                div()
+                                    .text_size(px(11.0))
+                                    .text_color(theme.colors.text_muted)
+                                    .child(format!("{} / {}", entry.host, entry.database)),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.text_muted)
+                            .child(format_age(age)),
+                    )
+            }))
+            .into_any_element()
+    }
+}
+
+impl EventEmitter<HomeViewEvent> for HomeView {}
+
+impl Focusable for HomeView {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for HomeView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .bg(theme.colors.editor_background)
+            .child(
+                div()
+                    .w(px(420.0))
+                    .flex()
+                    .flex_col()
+                    .gap(px(16.0))
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .justify_between()
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .font_weight(gpui::FontWeight::SEMIBOLD)
+                                    .text_color(theme.colors.text)
+                                    .child("Recent Connections"),
+                            )
+                            .child(
+                                Button::new("home-new-connection")
+                                    .label("New Connection")
+                                    .icon(IconName::Plus)
+                                    .variant(ButtonVariant::Primary)
+                                    .on_click(cx.listener(|_this, _, _window, cx| {
+                                        cx.emit(HomeViewEvent::NewConnection);
+                                    })),
+                            ),
+                    )
+                    .child(self.render_recent(&theme, cx))
+                    .child(div().text_size(px(12.0)).text_color(theme.colors.text_muted).child(
+                        if cfg!(target_os = "macos") {
+                            "Press Cmd+N to create a new query"
+                        } else {
+                            "Press Ctrl+N to create a new query"
+                        },
+                    )),
+            )
+    }
+}
@@ -1,9 +1,12 @@
 //! Icon system for Tusk application.
 //!
 //! Provides the IconName enum with all available icons and an Icon component
-//! for rendering them at various sizes.
+//! for rendering them at various sizes. Icons with an embedded SVG asset
+//! render as SVG; the rest fall back to a unicode character.
 
-use gpui::{div, prelude::*, px, App, Hsla, IntoElement, Pixels, RenderOnce, SharedString, Window};
+use gpui::{
+    div, prelude::*, px, svg, App, Hsla, IntoElement, Pixels, RenderOnce, SharedString, Window,
+};
 
 use crate::TuskTheme;
 
@@ -77,6 +80,10 @@ pub enum IconName {
     Sequence,
     /// Constraint
     Constraint,
+    /// Foreign key
+    ForeignKey,
+    /// Enum type
+    Enum,
 
     // Connection status
     /// Connected indicator
@@ -109,6 +116,10 @@ pub enum IconName {
     Pin,
     /// Unpin
     Unpin,
+    /// Eye, for revealing hidden text
+    Eye,
+    /// Crossed-out eye, for hiding revealed text
+    EyeOff,
     /// Maximize window
     Maximize,
     /// Minimize window
@@ -177,6 +188,8 @@ impl IconName {
             Self::Trigger => "trigger",
             Self::Sequence => "sequence",
             Self::Constraint => "constraint",
+            Self::ForeignKey => "foreign_key",
+            Self::Enum => "enum",
 
             // Connection status
             Self::Connected => "connected",
@@ -196,6 +209,8 @@ impl IconName {
             Self::HorizontalDots => "horizontal_dots",
             Self::Pin => "pin",
             Self::Unpin => "unpin",
+            Self::Eye => "eye",
+            Self::EyeOff => "eye_off",
             Self::Maximize => "maximize",
             Self::Minimize => "minimize",
             Self::SplitHorizontal => "split_horizontal",
@@ -254,6 +269,8 @@ impl IconName {
             Self::Trigger => "⚡",
             Self::Sequence => "#",
             Self::Constraint => "⧫",
+            Self::ForeignKey => "⚿",
+            Self::Enum => "≔",
 
             // Connection status
             Self::Connected => "●",
@@ -273,6 +290,8 @@ impl IconName {
             Self::HorizontalDots => "⋯",
             Self::Pin => "📌",
             Self::Unpin => "📌",
+            Self::Eye => "👁",
+            Self::EyeOff => "◌",
             Self::Maximize => "⤢",
             Self::Minimize => "⤡",
             Self::SplitHorizontal => "⫿",
@@ -292,6 +311,21 @@ impl IconName {
     }
 }
 
+impl IconName {
+    /// Get the embedded SVG asset path, for icons that have one.
+    pub fn svg_path(&self) -> Option<&'static str> {
+        Some(match self {
+            Self::Index => "icons/index.svg",
+            Self::Sequence => "icons/sequence.svg",
+            Self::Enum => "icons/enum.svg",
+            Self::Trigger => "icons/trigger.svg",
+            Self::ForeignKey => "icons/foreign_key.svg",
+            Self::Constraint => "icons/constraint.svg",
+            _ => return None,
+        })
+    }
+}
+
 /// Size variants for icons.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum IconSize {
@@ -354,8 +388,11 @@ impl RenderOnce for Icon {
         let size = self.size.pixels();
         let color = self.color.unwrap_or(theme.colors.text);
 
-        // For now, render the unicode character representation
-        // TODO: Load SVG icons from assets when available
+        if let Some(path) = self.name.svg_path() {
+            return svg().path(path).size(size).flex_none().text_color(color).into_any_element();
+        }
+
+        // Icons without an SVG asset render their unicode character
         div()
             .size(size)
             .flex()
@@ -365,6 +402,7 @@ impl RenderOnce for Icon {
             .text_size(size)
             .line_height(size)
             .child(SharedString::from(self.name.as_char()))
+            .into_any_element()
     }
 }
 
@@ -388,6 +426,29 @@ mod tests {
         assert_eq!(IconName::ChevronRight.name(), "chevron_right");
     }
 
+    #[test]
+    fn test_schema_object_icons_resolve() {
+        use crate::assets::Assets;
+        use gpui::AssetSource;
+
+        let icons = [
+            IconName::Index,
+            IconName::Sequence,
+            IconName::Enum,
+            IconName::Trigger,
+            IconName::ForeignKey,
+            IconName::Constraint,
+        ];
+        for icon in icons {
+            assert!(!icon.name().is_empty());
+            assert!(!icon.as_char().is_empty());
+            let path = icon.svg_path().unwrap_or_else(|| panic!("{:?} has no SVG", icon));
+            let svg = Assets.load(path).unwrap().unwrap_or_else(|| panic!("{} missing", path));
+            assert!(svg.starts_with(b"<svg"), "{} is not an SVG", path);
+        }
+        assert_eq!(IconName::Table.svg_path(), None);
+    }
+
     #[test]
     fn test_icon_chars() {
         assert_eq!(IconName::ChevronRight.as_char(), "›");
@@ -1,9 +1,15 @@
 //! Keyboard shortcuts and action definitions for Tusk.
 //!
 //! This module defines all global actions and registers key bindings.
+//! Every binding is listed once in [`all_bindings`], which drives both
+//! registration and the keyboard shortcuts help.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use gpui::{actions, App, KeyBinding};
 
+use crate::text_input;
+
 // ============================================================================
 // Workspace Actions
 // ============================================================================
@@ -37,13 +43,25 @@ actions!(
         SplitDown,
         FocusNextPane,
         FocusPreviousPane,
+        FocusPaneLeft,
+        FocusPaneRight,
+        FocusPaneUp,
+        FocusPaneDown,
+        ZoomPane,
         ClosePane,
         // Panel focus
         FocusSchemaBrowser,
         FocusResults,
         FocusMessages,
+        // Files
+        OpenSqlFile,
+        SaveSqlFile,
+        SaveSqlFileAs,
+        // Results
+        ExportResults,
         // Global
         CommandPalette,
+        GoToObject,
         Settings,
         // Application
         Quit,
@@ -59,7 +77,20 @@ actions!(
 // Query Actions
 // ============================================================================
 
-actions!(query, [RunQuery, ExplainQuery, FormatQuery, CancelQuery,]);
+actions!(
+    query,
+    [
+        RunQuery,
+        ExplainQuery,
+        EstimateQuery,
+        FormatQuery,
+        CancelQuery,
+        ToggleLineComment,
+        DuplicateLine,
+        MoveLineUp,
+        MoveLineDown,
+    ]
+);
 
 // ============================================================================
 // Tree Navigation Actions
@@ -123,167 +154,837 @@ pub mod context_menu {
 }
 
 // ============================================================================
-// Key Binding Registration
+// Results Grid Actions
 // ============================================================================
 
-/// Register all global key bindings.
+/// Results grid actions module (cell navigation and copy).
+pub mod results_grid {
+    use gpui::actions;
+    actions!(
+        results_grid,
+        [
+            MoveUp,
+            MoveDown,
+            MoveLeft,
+            MoveRight,
+            ExtendUp,
+            ExtendDown,
+            ExtendLeft,
+            ExtendRight,
+            CopySelection,
+        ]
+    );
+}
+
+// ============================================================================
+// Binding Table
+// ============================================================================
+
+/// Category a binding is listed under in the keyboard shortcuts help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BindingCategory {
+    /// Tabs, panes, docks, and application commands.
+    Workspace,
+    /// Query editor commands.
+    Editor,
+    /// Text editing in input fields.
+    Editing,
+    /// Results and messages panels.
+    Results,
+    /// Trees, dropdowns, menus, dialogs, and forms.
+    Navigation,
+}
+
+impl BindingCategory {
+    /// All categories in display order.
+    pub const ALL: [BindingCategory; 5] =
+        [Self::Workspace, Self::Editor, Self::Editing, Self::Results, Self::Navigation];
+
+    /// Display name of the category.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Workspace => "Workspace",
+            Self::Editor => "Editor",
+            Self::Editing => "Editing",
+            Self::Results => "Results",
+            Self::Navigation => "Navigation",
+        }
+    }
+}
+
+/// A default key binding and the help text shown for it.
+#[derive(Clone)]
+pub struct BindingInfo {
+    /// Keystrokes in GPUI syntax (e.g., "cmd-shift-p").
+    pub keystrokes: &'static str,
+    /// Registered action name (e.g., "workspace::CommandPalette").
+    pub action_name: &'static str,
+    /// Key context the binding applies in (None = everywhere).
+    pub context: Option<&'static str>,
+    /// What the action does.
+    pub description: &'static str,
+    /// Help category.
+    pub category: BindingCategory,
+    /// Build the GPUI binding for the given keystrokes.
+    bind: fn(&str, Option<&str>) -> KeyBinding,
+}
+
+impl BindingInfo {
+    /// Create the GPUI key binding for these keystrokes.
+    pub fn key_binding(&self) -> KeyBinding {
+        (self.bind)(self.keystrokes, self.context)
+    }
+
+    /// Create the GPUI key binding with different keystrokes.
+    pub fn key_binding_with(&self, keystrokes: &str) -> KeyBinding {
+        (self.bind)(keystrokes, self.context)
+    }
+
+    /// Keystrokes formatted for display (e.g., "Cmd+Shift+P").
+    pub fn display_keys(&self) -> String {
+        format_keystrokes(self.keystrokes)
+    }
+}
+
+impl std::fmt::Debug for BindingInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BindingInfo")
+            .field("keystrokes", &self.keystrokes)
+            .field("action_name", &self.action_name)
+            .field("context", &self.context)
+            .field("description", &self.description)
+            .field("category", &self.category)
+            .finish()
+    }
+}
+
+/// Declare a [`BindingInfo`] entry.
+macro_rules! binding {
+    ($keys:expr, $action:expr, $context:expr, $category:ident, $description:expr) => {
+        BindingInfo {
+            keystrokes: $keys,
+            action_name: gpui::Action::name(&$action),
+            context: $context,
+            description: $description,
+            category: BindingCategory::$category,
+            bind: |keystrokes, context| KeyBinding::new(keystrokes, $action, context),
+        }
+    };
+}
+
+/// Every default key binding for the current platform.
 ///
-/// This should be called once during application initialization.
-pub fn register_key_bindings(cx: &mut App) {
-    // macOS bindings (using Cmd)
-    #[cfg(target_os = "macos")]
-    cx.bind_keys([
+/// Registration and the keyboard shortcuts help both read this list, so
+/// new bindings appear in the help automatically.
+pub fn all_bindings() -> Vec<BindingInfo> {
+    let mut bindings = platform_bindings();
+    bindings.extend(common_bindings());
+    bindings
+}
+
+/// Bindings that use the platform modifier (macOS: Cmd).
+#[cfg(target_os = "macos")]
+fn platform_bindings() -> Vec<BindingInfo> {
+    vec![
         // Connection
-        KeyBinding::new("cmd-shift-n", NewConnection, Some("Workspace")),
+        binding!("cmd-shift-n", NewConnection, Some("Workspace"), Workspace, "New Connection"),
         // Tab management
-        KeyBinding::new("cmd-n", NewQueryTab, Some("Workspace")),
-        KeyBinding::new("cmd-w", CloseActiveTab, Some("Workspace")),
-        KeyBinding::new("cmd-shift-w", CloseAllTabs, Some("Workspace")),
-        KeyBinding::new("cmd-}", NextTab, Some("Workspace")),
-        KeyBinding::new("cmd-{", PreviousTab, Some("Workspace")),
-        KeyBinding::new("cmd-1", ActivateTab1, Some("Workspace")),
-        KeyBinding::new("cmd-2", ActivateTab2, Some("Workspace")),
-        KeyBinding::new("cmd-3", ActivateTab3, Some("Workspace")),
-        KeyBinding::new("cmd-4", ActivateTab4, Some("Workspace")),
-        KeyBinding::new("cmd-5", ActivateTab5, Some("Workspace")),
-        KeyBinding::new("cmd-6", ActivateTab6, Some("Workspace")),
-        KeyBinding::new("cmd-7", ActivateTab7, Some("Workspace")),
-        KeyBinding::new("cmd-8", ActivateTab8, Some("Workspace")),
-        KeyBinding::new("cmd-9", ActivateTab9, Some("Workspace")),
+        binding!("cmd-n", NewQueryTab, Some("Workspace"), Workspace, "New Query Tab"),
+        binding!("cmd-o", OpenSqlFile, Some("Workspace"), Workspace, "Open SQL File"),
+        binding!("cmd-s", SaveSqlFile, Some("Workspace"), Workspace, "Save SQL File"),
+        binding!("cmd-shift-s", SaveSqlFileAs, Some("Workspace"), Workspace, "Save SQL File As"),
+        binding!("cmd-w", CloseActiveTab, Some("Workspace"), Workspace, "Close Tab"),
+        binding!("cmd-shift-w", CloseAllTabs, Some("Workspace"), Workspace, "Close All Tabs"),
+        binding!("cmd-}", NextTab, Some("Workspace"), Workspace, "Next Tab"),
+        binding!("cmd-{", PreviousTab, Some("Workspace"), Workspace, "Previous Tab"),
+        binding!("cmd-1", ActivateTab1, Some("Workspace"), Workspace, "Activate Tab 1"),
+        binding!("cmd-2", ActivateTab2, Some("Workspace"), Workspace, "Activate Tab 2"),
+        binding!("cmd-3", ActivateTab3, Some("Workspace"), Workspace, "Activate Tab 3"),
+        binding!("cmd-4", ActivateTab4, Some("Workspace"), Workspace, "Activate Tab 4"),
+        binding!("cmd-5", ActivateTab5, Some("Workspace"), Workspace, "Activate Tab 5"),
+        binding!("cmd-6", ActivateTab6, Some("Workspace"), Workspace, "Activate Tab 6"),
+        binding!("cmd-7", ActivateTab7, Some("Workspace"), Workspace, "Activate Tab 7"),
+        binding!("cmd-8", ActivateTab8, Some("Workspace"), Workspace, "Activate Tab 8"),
+        binding!("cmd-9", ActivateTab9, Some("Workspace"), Workspace, "Activate Tab 9"),
         // Dock toggles
-        KeyBinding::new("cmd-b", ToggleLeftDock, Some("Workspace")),
-        KeyBinding::new("cmd-shift-b", ToggleRightDock, Some("Workspace")),
-        KeyBinding::new("cmd-j", ToggleBottomDock, Some("Workspace")),
+        binding!("cmd-b", ToggleLeftDock, Some("Workspace"), Workspace, "Toggle Schema Browser"),
+        binding!("cmd-shift-b", ToggleRightDock, Some("Workspace"), Workspace, "Toggle Right Dock"),
+        binding!("cmd-j", ToggleBottomDock, Some("Workspace"), Results, "Toggle Results Panel"),
         // Pane management
-        KeyBinding::new("cmd-\\", SplitRight, Some("Workspace")),
-        KeyBinding::new("cmd-|", SplitDown, Some("Workspace")),
-        KeyBinding::new("cmd-k cmd-right", FocusNextPane, Some("Workspace")),
-        KeyBinding::new("cmd-k cmd-left", FocusPreviousPane, Some("Workspace")),
-        KeyBinding::new("cmd-k cmd-w", ClosePane, Some("Workspace")),
+        binding!("cmd-\\", SplitRight, Some("Workspace"), Workspace, "Split Right"),
+        binding!("cmd-|", SplitDown, Some("Workspace"), Workspace, "Split Down"),
+        binding!("cmd-k cmd-right", FocusNextPane, Some("Workspace"), Workspace, "Focus Next Pane"),
+        binding!(
+            "cmd-k cmd-left",
+            FocusPreviousPane,
+            Some("Workspace"),
+            Workspace,
+            "Focus Previous Pane"
+        ),
+        binding!("cmd-k left", FocusPaneLeft, Some("Workspace"), Workspace, "Focus Pane Left"),
+        binding!("cmd-k right", FocusPaneRight, Some("Workspace"), Workspace, "Focus Pane Right"),
+        binding!("cmd-k up", FocusPaneUp, Some("Workspace"), Workspace, "Focus Pane Up"),
+        binding!("cmd-k down", FocusPaneDown, Some("Workspace"), Workspace, "Focus Pane Down"),
+        binding!("cmd-k cmd-w", ClosePane, Some("Workspace"), Workspace, "Close Pane"),
         // Panel focus
-        KeyBinding::new("cmd-shift-e", FocusSchemaBrowser, Some("Workspace")),
-        KeyBinding::new("cmd-shift-r", FocusResults, Some("Workspace")),
-        KeyBinding::new("cmd-shift-m", FocusMessages, Some("Workspace")),
+        binding!(
+            "cmd-shift-e",
+            FocusSchemaBrowser,
+            Some("Workspace"),
+            Workspace,
+            "Focus Schema Browser"
+        ),
+        binding!("cmd-shift-r", FocusResults, Some("Workspace"), Results, "Focus Results"),
+        binding!("cmd-shift-m", FocusMessages, Some("Workspace"), Results, "Focus Messages"),
+        binding!("cmd-alt-e", ExportResults, Some("Workspace"), Results, "Export Results"),
+        binding!(
+            "cmd-c",
+            results_grid::CopySelection,
+            Some("ResultsGrid"),
+            Results,
+            "Copy Selected Cells"
+        ),
         // Global
-        KeyBinding::new("cmd-shift-p", CommandPalette, Some("Workspace")),
-        KeyBinding::new("cmd-,", Settings, Some("Workspace")),
-        KeyBinding::new("cmd-/", ShowKeyboardShortcuts, None),
-        KeyBinding::new("cmd-q", Quit, None),
-    ]);
-
-    // Windows/Linux bindings (using Ctrl)
-    #[cfg(not(target_os = "macos"))]
-    cx.bind_keys([
+        binding!("cmd-shift-p", CommandPalette, Some("Workspace"), Workspace, "Command Palette"),
+        binding!("cmd-p", GoToObject, Some("Workspace"), Workspace, "Go to Object"),
+        binding!("cmd-,", Settings, Some("Workspace"), Workspace, "Open Settings"),
+        binding!("cmd-/", ShowKeyboardShortcuts, None, Workspace, "Show Keyboard Shortcuts"),
+        binding!("cmd-q", Quit, None, Workspace, "Quit Tusk"),
+        // Query
+        binding!("cmd-enter", RunQuery, Some("QueryEditor"), Editor, "Run Query"),
+        binding!("cmd-shift-e", ExplainQuery, Some("QueryEditor"), Editor, "Explain Query"),
+        binding!("cmd-alt-enter", EstimateQuery, Some("QueryEditor"), Editor, "Estimate Query"),
+        binding!("cmd-shift-f", FormatQuery, Some("QueryEditor"), Editor, "Format Query"),
+        binding!("cmd-/", ToggleLineComment, Some("QueryEditor"), Editor, "Toggle Line Comment"),
+        binding!("cmd-shift-d", DuplicateLine, Some("QueryEditor"), Editor, "Duplicate Line"),
+        // Text input
+        binding!("cmd-a", text_input::SelectAll, Some("TextInput"), Editing, "Select All"),
+        binding!("cmd-c", text_input::Copy, Some("TextInput"), Editing, "Copy"),
+        binding!("cmd-x", text_input::Cut, Some("TextInput"), Editing, "Cut"),
+        binding!("cmd-v", text_input::Paste, Some("TextInput"), Editing, "Paste"),
+        // Tree expand/collapse all
+        binding!("cmd-shift-right", tree::ExpandAll, Some("Tree"), Navigation, "Expand All"),
+        binding!("cmd-shift-left", tree::CollapseAll, Some("Tree"), Navigation, "Collapse All"),
+    ]
+}
+
+/// Bindings that use the platform modifier (Windows/Linux: Ctrl).
+#[cfg(not(target_os = "macos"))]
+fn platform_bindings() -> Vec<BindingInfo> {
+    vec![
         // Connection
-        KeyBinding::new("ctrl-shift-n", NewConnection, Some("Workspace")),
+        binding!("ctrl-shift-n", NewConnection, Some("Workspace"), Workspace, "New Connection"),
         // Tab management
-        KeyBinding::new("ctrl-n", NewQueryTab, Some("Workspace")),
-        KeyBinding::new("ctrl-w", CloseActiveTab, Some("Workspace")),
-        KeyBinding::new("ctrl-shift-w", CloseAllTabs, Some("Workspace")),
-        KeyBinding::new("ctrl-tab", NextTab, Some("Workspace")),
-        KeyBinding::new("ctrl-shift-tab", PreviousTab, Some("Workspace")),
-        KeyBinding::new("ctrl-1", ActivateTab1, Some("Workspace")),
-        KeyBinding::new("ctrl-2", ActivateTab2, Some("Workspace")),
-        KeyBinding::new("ctrl-3", ActivateTab3, Some("Workspace")),
-        KeyBinding::new("ctrl-4", ActivateTab4, Some("Workspace")),
-        KeyBinding::new("ctrl-5", ActivateTab5, Some("Workspace")),
-        KeyBinding::new("ctrl-6", ActivateTab6, Some("Workspace")),
-        KeyBinding::new("ctrl-7", ActivateTab7, Some("Workspace")),
-        KeyBinding::new("ctrl-8", ActivateTab8, Some("Workspace")),
-        KeyBinding::new("ctrl-9", ActivateTab9, Some("Workspace")),
+        binding!("ctrl-n", NewQueryTab, Some("Workspace"), Workspace, "New Query Tab"),
+        binding!("ctrl-o", OpenSqlFile, Some("Workspace"), Workspace, "Open SQL File"),
+        binding!("ctrl-s", SaveSqlFile, Some("Workspace"), Workspace, "Save SQL File"),
+        binding!("ctrl-shift-s", SaveSqlFileAs, Some("Workspace"), Workspace, "Save SQL File As"),
+        binding!("ctrl-w", CloseActiveTab, Some("Workspace"), Workspace, "Close Tab"),
+        binding!("ctrl-shift-w", CloseAllTabs, Some("Workspace"), Workspace, "Close All Tabs"),
+        binding!("ctrl-tab", NextTab, Some("Workspace"), Workspace, "Next Tab"),
+        binding!("ctrl-shift-tab", PreviousTab, Some("Workspace"), Workspace, "Previous Tab"),
+        binding!("ctrl-1", ActivateTab1, Some("Workspace"), Workspace, "Activate Tab 1"),
+        binding!("ctrl-2", ActivateTab2, Some("Workspace"), Workspace, "Activate Tab 2"),
+        binding!("ctrl-3", ActivateTab3, Some("Workspace"), Workspace, "Activate Tab 3"),
+        binding!("ctrl-4", ActivateTab4, Some("Workspace"), Workspace, "Activate Tab 4"),
+        binding!("ctrl-5", ActivateTab5, Some("Workspace"), Workspace, "Activate Tab 5"),
+        binding!("ctrl-6", ActivateTab6, Some("Workspace"), Workspace, "Activate Tab 6"),
+        binding!("ctrl-7", ActivateTab7, Some("Workspace"), Workspace, "Activate Tab 7"),
+        binding!("ctrl-8", ActivateTab8, Some("Workspace"), Workspace, "Activate Tab 8"),
+        binding!("ctrl-9", ActivateTab9, Some("Workspace"), Workspace, "Activate Tab 9"),
         // Dock toggles
-        KeyBinding::new("ctrl-b", ToggleLeftDock, Some("Workspace")),
-        KeyBinding::new("ctrl-shift-b", ToggleRightDock, Some("Workspace")),
-        KeyBinding::new("ctrl-j", ToggleBottomDock, Some("Workspace")),
+        binding!("ctrl-b", ToggleLeftDock, Some("Workspace"), Workspace, "Toggle Schema Browser"),
+        binding!(
+            "ctrl-shift-b",
+            ToggleRightDock,
+            Some("Workspace"),
+            Workspace,
+            "Toggle Right Dock"
+        ),
+        binding!("ctrl-j", ToggleBottomDock, Some("Workspace"), Results, "Toggle Results Panel"),
         // Pane management
-        KeyBinding::new("ctrl-\\", SplitRight, Some("Workspace")),
-        KeyBinding::new("ctrl-|", SplitDown, Some("Workspace")),
-        KeyBinding::new("ctrl-k ctrl-right", FocusNextPane, Some("Workspace")),
-        KeyBinding::new("ctrl-k ctrl-left", FocusPreviousPane, Some("Workspace")),
-        KeyBinding::new("ctrl-k ctrl-w", ClosePane, Some("Workspace")),
+        binding!("ctrl-\\", SplitRight, Some("Workspace"), Workspace, "Split Right"),
+        binding!("ctrl-|", SplitDown, Some("Workspace"), Workspace, "Split Down"),
+        binding!(
+            "ctrl-k ctrl-right",
+            FocusNextPane,
+            Some("Workspace"),
+            Workspace,
+            "Focus Next Pane"
+        ),
+        binding!(
+            "ctrl-k ctrl-left",
+            FocusPreviousPane,
+            Some("Workspace"),
+            Workspace,
+            "Focus Previous Pane"
+        ),
+        binding!("ctrl-k left", FocusPaneLeft, Some("Workspace"), Workspace, "Focus Pane Left"),
+        binding!("ctrl-k right", FocusPaneRight, Some("Workspace"), Workspace, "Focus Pane Right"),
+        binding!("ctrl-k up", FocusPaneUp, Some("Workspace"), Workspace, "Focus Pane Up"),
+        binding!("ctrl-k down", FocusPaneDown, Some("Workspace"), Workspace, "Focus Pane Down"),
+        binding!("ctrl-k ctrl-w", ClosePane, Some("Workspace"), Workspace, "Close Pane"),
         // Panel focus
-        KeyBinding::new("ctrl-shift-e", FocusSchemaBrowser, Some("Workspace")),
-        KeyBinding::new("ctrl-shift-r", FocusResults, Some("Workspace")),
-        KeyBinding::new("ctrl-shift-m", FocusMessages, Some("Workspace")),
+        binding!(
+            "ctrl-shift-e",
+            FocusSchemaBrowser,
+            Some("Workspace"),
+            Workspace,
+            "Focus Schema Browser"
+        ),
+        binding!("ctrl-shift-r", FocusResults, Some("Workspace"), Results, "Focus Results"),
+        binding!("ctrl-shift-m", FocusMessages, Some("Workspace"), Results, "Focus Messages"),
+        binding!("ctrl-alt-e", ExportResults, Some("Workspace"), Results, "Export Results"),
+        binding!(
+            "ctrl-c",
+            results_grid::CopySelection,
+            Some("ResultsGrid"),
+            Results,
+            "Copy Selected Cells"
+        ),
         // Global
-        KeyBinding::new("ctrl-shift-p", CommandPalette, Some("Workspace")),
-        KeyBinding::new("ctrl-,", Settings, Some("Workspace")),
-        KeyBinding::new("ctrl-/", ShowKeyboardShortcuts, None),
-        KeyBinding::new("alt-f4", Quit, None),
-    ]);
-
-    // Query bindings - macOS
-    #[cfg(target_os = "macos")]
-    cx.bind_keys([
-        KeyBinding::new("cmd-enter", RunQuery, Some("QueryEditor")),
-        KeyBinding::new("cmd-shift-e", ExplainQuery, Some("QueryEditor")),
-        KeyBinding::new("cmd-shift-f", FormatQuery, Some("QueryEditor")),
-        KeyBinding::new("escape", CancelQuery, Some("QueryEditor")),
-    ]);
-
-    // Query bindings - Windows/Linux
-    #[cfg(not(target_os = "macos"))]
-    cx.bind_keys([
-        KeyBinding::new("ctrl-enter", RunQuery, Some("QueryEditor")),
-        KeyBinding::new("ctrl-shift-e", ExplainQuery, Some("QueryEditor")),
-        KeyBinding::new("ctrl-shift-f", FormatQuery, Some("QueryEditor")),
-        KeyBinding::new("escape", CancelQuery, Some("QueryEditor")),
-    ]);
-
-    // Tree navigation bindings (platform-independent)
-    cx.bind_keys([
-        KeyBinding::new("up", tree::SelectPrevious, Some("Tree")),
-        KeyBinding::new("down", tree::SelectNext, Some("Tree")),
-        KeyBinding::new("right", tree::ExpandSelected, Some("Tree")),
-        KeyBinding::new("left", tree::CollapseSelected, Some("Tree")),
-        KeyBinding::new("enter", tree::ActivateSelected, Some("Tree")),
-    ]);
-
-    // Tree expand/collapse all - macOS
-    #[cfg(target_os = "macos")]
-    cx.bind_keys([
-        KeyBinding::new("cmd-shift-right", tree::ExpandAll, Some("Tree")),
-        KeyBinding::new("cmd-shift-left", tree::CollapseAll, Some("Tree")),
-    ]);
-
-    // Tree expand/collapse all - Windows/Linux
-    #[cfg(not(target_os = "macos"))]
-    cx.bind_keys([
-        KeyBinding::new("ctrl-shift-right", tree::ExpandAll, Some("Tree")),
-        KeyBinding::new("ctrl-shift-left", tree::CollapseAll, Some("Tree")),
-    ]);
-
-    // Select/dropdown bindings
-    cx.bind_keys([
-        KeyBinding::new("space", select::Open, Some("Select")),
-        KeyBinding::new("enter", select::Open, Some("Select")),
-        KeyBinding::new("down", select::Open, Some("Select")),
-        KeyBinding::new("escape", select::Close, Some("SelectPopover")),
-        KeyBinding::new("down", select::SelectNextOption, Some("SelectPopover")),
-        KeyBinding::new("up", select::SelectPreviousOption, Some("SelectPopover")),
-        KeyBinding::new("enter", select::Confirm, Some("SelectPopover")),
-    ]);
-
-    // Modal bindings
-    cx.bind_keys([
-        KeyBinding::new("escape", self::modal::Dismiss, Some("Modal")),
-        KeyBinding::new("enter", self::modal::ConfirmAction, Some("Modal")),
-    ]);
-
-    // Context menu bindings
-    cx.bind_keys([
-        KeyBinding::new("down", context_menu::SelectNextItem, Some("ContextMenu")),
-        KeyBinding::new("up", context_menu::SelectPreviousItem, Some("ContextMenu")),
-        KeyBinding::new("enter", context_menu::ConfirmItem, Some("ContextMenu")),
-        KeyBinding::new("escape", context_menu::DismissMenu, Some("ContextMenu")),
-        KeyBinding::new("right", context_menu::OpenSubmenu, Some("ContextMenu")),
-        KeyBinding::new("left", context_menu::CloseSubmenu, Some("ContextMenu")),
-    ]);
-
-    // Form navigation bindings (Tab to cycle fields)
-    // Note: Using None for context so Tab works when focus is on child elements
-    cx.bind_keys([
-        KeyBinding::new("tab", form::Tab, None),
-        KeyBinding::new("shift-tab", form::TabPrev, None),
-    ]);
+        binding!("ctrl-shift-p", CommandPalette, Some("Workspace"), Workspace, "Command Palette"),
+        binding!("ctrl-p", GoToObject, Some("Workspace"), Workspace, "Go to Object"),
+        binding!("ctrl-,", Settings, Some("Workspace"), Workspace, "Open Settings"),
+        binding!("ctrl-/", ShowKeyboardShortcuts, None, Workspace, "Show Keyboard Shortcuts"),
+        binding!("alt-f4", Quit, None, Workspace, "Quit Tusk"),
+        // Query
+        binding!("ctrl-enter", RunQuery, Some("QueryEditor"), Editor, "Run Query"),
+        binding!("ctrl-shift-e", ExplainQuery, Some("QueryEditor"), Editor, "Explain Query"),
+        binding!("ctrl-alt-enter", EstimateQuery, Some("QueryEditor"), Editor, "Estimate Query"),
+        binding!("ctrl-shift-f", FormatQuery, Some("QueryEditor"), Editor, "Format Query"),
+        binding!("ctrl-/", ToggleLineComment, Some("QueryEditor"), Editor, "Toggle Line Comment"),
+        binding!("ctrl-shift-d", DuplicateLine, Some("QueryEditor"), Editor, "Duplicate Line"),
+        // Text input
+        binding!("ctrl-a", text_input::SelectAll, Some("TextInput"), Editing, "Select All"),
+        binding!("ctrl-c", text_input::Copy, Some("TextInput"), Editing, "Copy"),
+        binding!("ctrl-x", text_input::Cut, Some("TextInput"), Editing, "Cut"),
+        binding!("ctrl-v", text_input::Paste, Some("TextInput"), Editing, "Paste"),
+        // Tree expand/collapse all
+        binding!("ctrl-shift-right", tree::ExpandAll, Some("Tree"), Navigation, "Expand All"),
+        binding!("ctrl-shift-left", tree::CollapseAll, Some("Tree"), Navigation, "Collapse All"),
+    ]
+}
+
+/// Platform-independent bindings.
+fn common_bindings() -> Vec<BindingInfo> {
+    vec![
+        // Query
+        binding!("escape", CancelQuery, Some("QueryEditor"), Editor, "Cancel Query"),
+        binding!("alt-up", MoveLineUp, Some("QueryEditor"), Editor, "Move Line Up"),
+        binding!("alt-down", MoveLineDown, Some("QueryEditor"), Editor, "Move Line Down"),
+        // Text input; Tab/Shift-Tab are left to form::Tab/form::TabPrev so
+        // parent components control field ordering
+        binding!("backspace", text_input::Backspace, Some("TextInput"), Editing, "Delete Backward"),
+        binding!("delete", text_input::Delete, Some("TextInput"), Editing, "Delete Forward"),
+        binding!("left", text_input::Left, Some("TextInput"), Editing, "Move Left"),
+        binding!("right", text_input::Right, Some("TextInput"), Editing, "Move Right"),
+        binding!("shift-left", text_input::SelectLeft, Some("TextInput"), Editing, "Select Left"),
+        binding!(
+            "shift-right",
+            text_input::SelectRight,
+            Some("TextInput"),
+            Editing,
+            "Select Right"
+        ),
+        binding!("home", text_input::Home, Some("TextInput"), Editing, "Move to Start"),
+        binding!("end", text_input::End, Some("TextInput"), Editing, "Move to End"),
+        binding!("up", text_input::Up, Some("TextInput"), Editing, "Previous History Entry"),
+        binding!("down", text_input::Down, Some("TextInput"), Editing, "Next History Entry"),
+        binding!("enter", text_input::Submit, Some("TextInput"), Editing, "Submit Input"),
+        // Pane management
+        binding!("shift-escape", ZoomPane, Some("Workspace"), Workspace, "Toggle Pane Zoom"),
+        // Tree navigation
+        binding!("up", tree::SelectPrevious, Some("Tree"), Navigation, "Previous Tree Item"),
+        binding!("down", tree::SelectNext, Some("Tree"), Navigation, "Next Tree Item"),
+        binding!("right", tree::ExpandSelected, Some("Tree"), Navigation, "Expand Tree Item"),
+        binding!("left", tree::CollapseSelected, Some("Tree"), Navigation, "Collapse Tree Item"),
+        binding!("enter", tree::ActivateSelected, Some("Tree"), Navigation, "Activate Tree Item"),
+        // Results grid
+        binding!("up", results_grid::MoveUp, Some("ResultsGrid"), Results, "Previous Row"),
+        binding!("down", results_grid::MoveDown, Some("ResultsGrid"), Results, "Next Row"),
+        binding!("left", results_grid::MoveLeft, Some("ResultsGrid"), Results, "Previous Column"),
+        binding!("right", results_grid::MoveRight, Some("ResultsGrid"), Results, "Next Column"),
+        binding!(
+            "shift-up",
+            results_grid::ExtendUp,
+            Some("ResultsGrid"),
+            Results,
+            "Extend Selection Up"
+        ),
+        binding!(
+            "shift-down",
+            results_grid::ExtendDown,
+            Some("ResultsGrid"),
+            Results,
+            "Extend Selection Down"
+        ),
+        binding!(
+            "shift-left",
+            results_grid::ExtendLeft,
+            Some("ResultsGrid"),
+            Results,
+            "Extend Selection Left"
+        ),
+        binding!(
+            "shift-right",
+            results_grid::ExtendRight,
+            Some("ResultsGrid"),
+            Results,
+            "Extend Selection Right"
+        ),
+        // Select/dropdown
+        binding!("space", select::Open, Some("Select"), Navigation, "Open Dropdown"),
+        binding!("enter", select::Open, Some("Select"), Navigation, "Open Dropdown"),
+        binding!("down", select::Open, Some("Select"), Navigation, "Open Dropdown"),
+        binding!("escape", select::Close, Some("SelectPopover"), Navigation, "Close Dropdown"),
+        binding!(
+            "down",
+            select::SelectNextOption,
+            Some("SelectPopover"),
+            Navigation,
+            "Next Option"
+        ),
+        binding!(
+            "up",
+            select::SelectPreviousOption,
+            Some("SelectPopover"),
+            Navigation,
+            "Previous Option"
+        ),
+        binding!("enter", select::Confirm, Some("SelectPopover"), Navigation, "Choose Option"),
+        // Modal
+        binding!("escape", self::modal::Dismiss, Some("Modal"), Navigation, "Dismiss Dialog"),
+        binding!("enter", self::modal::ConfirmAction, Some("Modal"), Navigation, "Confirm Dialog"),
+        // Context menu
+        binding!(
+            "down",
+            context_menu::SelectNextItem,
+            Some("ContextMenu"),
+            Navigation,
+            "Next Menu Item"
+        ),
+        binding!(
+            "up",
+            context_menu::SelectPreviousItem,
+            Some("ContextMenu"),
+            Navigation,
+            "Previous Menu Item"
+        ),
+        binding!(
+            "enter",
+            context_menu::ConfirmItem,
+            Some("ContextMenu"),
+            Navigation,
+            "Choose Menu Item"
+        ),
+        binding!(
+            "escape",
+            context_menu::DismissMenu,
+            Some("ContextMenu"),
+            Navigation,
+            "Close Menu"
+        ),
+        binding!(
+            "right",
+            context_menu::OpenSubmenu,
+            Some("ContextMenu"),
+            Navigation,
+            "Open Submenu"
+        ),
+        binding!(
+            "left",
+            context_menu::CloseSubmenu,
+            Some("ContextMenu"),
+            Navigation,
+            "Close Submenu"
+        ),
+        // Form navigation; no context so Tab works when focus is on child elements
+        binding!("tab", form::Tab, None, Navigation, "Next Field"),
+        binding!("shift-tab", form::TabPrev, None, Navigation, "Previous Field"),
+    ]
+}
+
+/// Format GPUI keystrokes for display ("cmd-k cmd-w" becomes "Cmd+K Cmd+W").
+pub fn format_keystrokes(keystrokes: &str) -> String {
+    keystrokes
+        .split_whitespace()
+        .map(|chord| {
+            // A trailing "-" is the minus key rather than a separator
+            let (modifiers, key) = match chord.strip_suffix("--") {
+                Some(modifiers) => (modifiers, "-"),
+                None => chord.rsplit_once('-').unwrap_or(("", chord)),
+            };
+            modifiers
+                .split('-')
+                .filter(|modifier| !modifier.is_empty())
+                .chain(std::iter::once(key))
+                .map(capitalize_key)
+                .collect::<Vec<_>>()
+                .join("+")
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Capitalize a single key name ("shift" -> "Shift", "k" -> "K").
+fn capitalize_key(key: &str) -> String {
+    let mut chars = key.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// ============================================================================
+// User Keymap
+// ============================================================================
+
+/// UI state key under which keymap overrides are persisted.
+pub const KEYMAP_STATE_KEY: &str = "keymap";
+
+/// User keymap overrides: action name to keystrokes (e.g.,
+/// "workspace::CommandPalette" to "cmd-k cmd-p"). An empty keystroke
+/// string unbinds the action.
+pub type KeymapOverrides = BTreeMap<String, String>;
+
+/// A binding with its effective keystrokes after applying overrides.
+#[derive(Debug, Clone)]
+pub struct KeymapEntry {
+    /// The default binding this entry is based on.
+    pub binding: BindingInfo,
+    /// Effective keystrokes in GPUI syntax.
+    pub keystrokes: String,
+}
+
+impl KeymapEntry {
+    /// Whether the user has changed this binding's keystrokes.
+    pub fn is_overridden(&self) -> bool {
+        self.keystrokes != self.binding.keystrokes
+    }
+
+    /// Create the GPUI key binding for the effective keystrokes.
+    pub fn key_binding(&self) -> KeyBinding {
+        self.binding.key_binding_with(&self.keystrokes)
+    }
+
+    /// Effective keystrokes formatted for display.
+    pub fn display_keys(&self) -> String {
+        format_keystrokes(&self.keystrokes)
+    }
+}
+
+/// An override that could not be applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeymapIssue {
+    /// The override names an action that has no default binding.
+    UnknownAction(String),
+    /// The override's keystrokes could not be parsed.
+    InvalidKeystrokes { action: String, keystrokes: String },
+}
+
+impl std::fmt::Display for KeymapIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::UnknownAction(action) => write!(f, "unknown action '{}'", action),
+            Self::InvalidKeystrokes { action, keystrokes } => {
+                write!(f, "invalid keystrokes '{}' for '{}'", keystrokes, action)
+            }
+        }
+    }
+}
+
+/// Several actions bound to the same keystrokes in the same context.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeymapConflict {
+    /// Key context shared by the bindings (None = everywhere).
+    pub context: Option<&'static str>,
+    /// The conflicting keystrokes.
+    pub keystrokes: String,
+    /// Names of the actions bound to them.
+    pub actions: Vec<&'static str>,
+}
+
+impl std::fmt::Display for KeymapConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "'{}' is bound to {} in {}",
+            self.keystrokes,
+            self.actions.join(", "),
+            self.context.unwrap_or("every context")
+        )
+    }
+}
+
+/// Apply user overrides on top of the default bindings.
+///
+/// An override replaces the keystrokes of every default binding for its
+/// action, once per key context. Overrides for unknown actions or with
+/// unparseable keystrokes are skipped and returned as issues.
+pub fn merge_overrides(
+    defaults: &[BindingInfo],
+    overrides: &KeymapOverrides,
+) -> (Vec<KeymapEntry>, Vec<KeymapIssue>) {
+    let mut issues = Vec::new();
+    let mut applied: HashMap<&str, String> = HashMap::new();
+
+    for (action, keystrokes) in overrides {
+        if !defaults.iter().any(|b| b.action_name == *action) {
+            issues.push(KeymapIssue::UnknownAction(action.clone()));
+            continue;
+        }
+        let keystrokes = normalize_keystrokes(keystrokes);
+        if !keystrokes.is_empty() && !is_valid_keystrokes(&keystrokes) {
+            issues.push(KeymapIssue::InvalidKeystrokes { action: action.clone(), keystrokes });
+            continue;
+        }
+        applied.insert(action.as_str(), keystrokes);
+    }
+
+    let mut overridden_contexts = HashSet::new();
+    let entries = defaults
+        .iter()
+        .filter_map(|binding| match applied.get(binding.action_name) {
+            None => Some(KeymapEntry {
+                binding: binding.clone(),
+                keystrokes: binding.keystrokes.into(),
+            }),
+            // Alternative default keystrokes collapse into the single override
+            Some(keystrokes) => {
+                let first = overridden_contexts.insert((binding.action_name, binding.context));
+                (first && !keystrokes.is_empty()).then(|| KeymapEntry {
+                    binding: binding.clone(),
+                    keystrokes: keystrokes.clone(),
+                })
+            }
+        })
+        .collect();
+
+    (entries, issues)
+}
+
+/// Find keystrokes bound to more than one action in the same context.
+pub fn find_conflicts(entries: &[KeymapEntry]) -> Vec<KeymapConflict> {
+    let mut conflicts: Vec<KeymapConflict> = Vec::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let action = entry.binding.action_name;
+        let context = entry.binding.context;
+        if conflicts.iter().any(|c| c.context == context && c.keystrokes == entry.keystrokes) {
+            continue;
+        }
+
+        let mut actions = vec![action];
+        for other in &entries[index + 1..] {
+            if other.binding.context == context
+                && other.keystrokes == entry.keystrokes
+                && !actions.contains(&other.binding.action_name)
+            {
+                actions.push(other.binding.action_name);
+            }
+        }
+
+        if actions.len() > 1 {
+            conflicts.push(KeymapConflict {
+                context,
+                keystrokes: entry.keystrokes.clone(),
+                actions,
+            });
+        }
+    }
+
+    conflicts
+}
+
+/// Collapse whitespace between chords ("  cmd-k   cmd-p " becomes "cmd-k cmd-p").
+fn normalize_keystrokes(keystrokes: &str) -> String {
+    keystrokes.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Whether every chord parses as a GPUI keystroke.
+fn is_valid_keystrokes(keystrokes: &str) -> bool {
+    keystrokes.split_whitespace().all(|chord| gpui::Keystroke::parse(chord).is_ok())
+}
+
+/// Load keymap overrides from storage.
+#[cfg(feature = "persistence")]
+pub fn load_keymap_overrides(cx: &App) -> KeymapOverrides {
+    use tusk_core::TuskState;
+
+    let Some(state) = cx.try_global::<TuskState>() else {
+        return KeymapOverrides::new();
+    };
+    match state.storage().load_ui_state(KEYMAP_STATE_KEY) {
+        Ok(Some(value)) => serde_json::from_value(value).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Ignoring malformed keymap overrides");
+            KeymapOverrides::new()
+        }),
+        Ok(None) => KeymapOverrides::new(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load keymap overrides");
+            KeymapOverrides::new()
+        }
+    }
+}
+
+/// Load keymap overrides placeholder for non-persistence builds.
+#[cfg(not(feature = "persistence"))]
+pub fn load_keymap_overrides(_cx: &App) -> KeymapOverrides {
+    KeymapOverrides::new()
+}
+
+/// The effective keymap: default bindings with the user's overrides applied.
+///
+/// Overrides that cannot be applied are skipped. If the overrides leave
+/// conflicting bindings, they are ignored entirely and the defaults are used.
+pub fn active_keymap(cx: &App) -> Vec<KeymapEntry> {
+    let defaults = all_bindings();
+    let overrides = load_keymap_overrides(cx);
+    let (entries, issues) = merge_overrides(&defaults, &overrides);
+    for issue in &issues {
+        tracing::warn!(%issue, "Skipping keymap override");
+    }
+
+    let conflicts = find_conflicts(&entries);
+    if conflicts.is_empty() {
+        return entries;
+    }
+    for conflict in &conflicts {
+        tracing::warn!(%conflict, "Conflicting keymap override");
+    }
+    tracing::warn!("Keymap overrides ignored; using default key bindings");
+    merge_overrides(&defaults, &KeymapOverrides::new()).0
+}
+
+// ============================================================================
+// Key Binding Registration
+// ============================================================================
+
+/// Register all global key bindings.
+///
+/// Applies the user's keymap overrides on top of the defaults. This should
+/// be called once during application initialization, after `TuskState` is
+/// available.
+pub fn register_key_bindings(cx: &mut App) {
+    let keymap = active_keymap(cx);
+    cx.bind_keys(keymap.iter().map(KeymapEntry::key_binding));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_binding_has_description_and_category() {
+        let bindings = all_bindings();
+        assert!(!bindings.is_empty());
+
+        for binding in &bindings {
+            assert!(!binding.description.trim().is_empty(), "{:?}", binding);
+            assert!(!binding.action_name.is_empty(), "{:?}", binding);
+            assert!(BindingCategory::ALL.contains(&binding.category), "{:?}", binding);
+        }
+
+        // Every category shows up in the help
+        let categories: HashSet<_> = bindings.iter().map(|b| b.category).collect();
+        assert_eq!(categories.len(), BindingCategory::ALL.len());
+    }
+
+    #[test]
+    fn test_bindings_are_unique_per_context() {
+        let mut seen = HashSet::new();
+        for binding in all_bindings() {
+            assert!(
+                seen.insert((binding.keystrokes, binding.context, binding.action_name)),
+                "duplicate binding {:?}",
+                binding
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_keystrokes() {
+        assert_eq!(format_keystrokes("cmd-shift-p"), "Cmd+Shift+P");
+        assert_eq!(format_keystrokes("ctrl-k ctrl-right"), "Ctrl+K Ctrl+Right");
+        assert_eq!(format_keystrokes("escape"), "Escape");
+        assert_eq!(format_keystrokes("cmd-\\"), "Cmd+\\");
+        assert_eq!(format_keystrokes("ctrl--"), "Ctrl+-");
+    }
+
+    fn overrides(pairs: &[(&str, &str)]) -> KeymapOverrides {
+        pairs.iter().map(|(a, k)| (a.to_string(), k.to_string())).collect()
+    }
+
+    fn keys_for(entries: &[KeymapEntry], action: &str) -> Vec<String> {
+        entries
+            .iter()
+            .filter(|e| e.binding.action_name == action)
+            .map(|e| e.keystrokes.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_overrides() {
+        let defaults = all_bindings();
+        let palette = gpui::Action::name(&CommandPalette);
+        let open = gpui::Action::name(&select::Open);
+        let settings = gpui::Action::name(&Settings);
+
+        let (entries, issues) = merge_overrides(
+            &defaults,
+            &overrides(&[
+                (palette, "  alt-p   alt-p "),
+                (open, "alt-down"),
+                (settings, ""),
+                ("workspace::DoesNotExist", "alt-x"),
+                (gpui::Action::name(&Quit), "ctrl-banana-x"),
+            ]),
+        );
+
+        // Overridden keystrokes replace the defaults, whitespace normalized
+        assert_eq!(keys_for(&entries, palette), ["alt-p alt-p"]);
+        assert!(entries.iter().find(|e| e.binding.action_name == palette).unwrap().is_overridden());
+        // Alternative default bindings collapse into the one override
+        assert_eq!(keys_for(&entries, open), ["alt-down"]);
+        // An empty override unbinds the action
+        assert!(keys_for(&entries, settings).is_empty());
+        // Bad overrides are reported and the defaults are kept
+        assert_eq!(issues.len(), 2);
+        assert!(issues.contains(&KeymapIssue::UnknownAction("workspace::DoesNotExist".into())));
+        let quit = entries.iter().find(|e| e.binding.action_name == gpui::Action::name(&Quit));
+        assert!(!quit.unwrap().is_overridden());
+
+        // Untouched bindings keep their defaults
+        let (defaults_only, issues) = merge_overrides(&defaults, &KeymapOverrides::new());
+        assert!(issues.is_empty());
+        assert_eq!(defaults_only.len(), defaults.len());
+        assert!(defaults_only.iter().all(|e| !e.is_overridden()));
+    }
+
+    #[test]
+    fn test_find_conflicts_within_context() {
+        let defaults = all_bindings();
+        let (entries, _) = merge_overrides(&defaults, &KeymapOverrides::new());
+        assert!(find_conflicts(&entries).is_empty());
+
+        // Two workspace actions on the same keystrokes conflict
+        let new_tab = gpui::Action::name(&NewQueryTab);
+        let palette = gpui::Action::name(&CommandPalette);
+        let (entries, _) =
+            merge_overrides(&defaults, &overrides(&[(new_tab, "alt-t"), (palette, "alt-t")]));
+        let conflicts = find_conflicts(&entries);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].context, Some("Workspace"));
+        assert_eq!(conflicts[0].keystrokes, "alt-t");
+        assert_eq!(conflicts[0].actions.len(), 2);
+        assert!(conflicts[0].actions.contains(&new_tab));
+        assert!(conflicts[0].actions.contains(&palette));
+
+        // The same keystrokes in different contexts do not conflict
+        let run = gpui::Action::name(&RunQuery);
+        let (entries, _) =
+            merge_overrides(&defaults, &overrides(&[(new_tab, "alt-r"), (run, "alt-r")]));
+        assert!(find_conflicts(&entries).is_empty());
+    }
 }
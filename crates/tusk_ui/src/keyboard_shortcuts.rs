@@ -2,19 +2,22 @@
 //!
 //! This module provides a modal dialog that shows all keyboard shortcuts
 //! organized by category, helping users discover and learn the available
-//! key bindings in Tusk.
+//! key bindings in Tusk. The list is generated from the active keymap
+//! ([`key_bindings::active_keymap`](crate::key_bindings::active_keymap)), so
+//! new bindings and user overrides appear automatically.
 
-use gpui::{div, prelude::*, px, App, Context, Render, Window};
+use gpui::{div, prelude::*, px, App, Context, Hsla, Render, Window};
 
+use crate::key_bindings::{active_keymap, BindingCategory, KeymapEntry};
 use crate::modal::{Modal, ModalAction, ModalLayer};
 use crate::TuskTheme;
 
 /// A single keyboard shortcut entry.
 struct ShortcutEntry {
-    /// The key combination (e.g., "Cmd+N").
-    keys: &'static str,
     /// Description of what the shortcut does.
     description: &'static str,
+    /// Alternative key combinations (e.g., "Cmd+N").
+    keys: Vec<String>,
 }
 
 /// A category of shortcuts.
@@ -22,170 +25,45 @@ struct ShortcutCategory {
     /// Category name.
     name: &'static str,
     /// Shortcuts in this category.
-    shortcuts: &'static [ShortcutEntry],
+    shortcuts: Vec<ShortcutEntry>,
 }
 
-/// macOS keyboard shortcuts organized by category.
-#[cfg(target_os = "macos")]
-const SHORTCUTS: &[ShortcutCategory] = &[
-    ShortcutCategory {
-        name: "General",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+Q", description: "Quit Tusk" },
-            ShortcutEntry { keys: "Cmd+,", description: "Open Settings" },
-            ShortcutEntry { keys: "Cmd+/", description: "Show Keyboard Shortcuts" },
-            ShortcutEntry { keys: "Cmd+Shift+P", description: "Command Palette" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Tabs",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+N", description: "New Query Tab" },
-            ShortcutEntry { keys: "Cmd+W", description: "Close Tab" },
-            ShortcutEntry { keys: "Cmd+Shift+W", description: "Close All Tabs" },
-            ShortcutEntry { keys: "Cmd+}", description: "Next Tab" },
-            ShortcutEntry { keys: "Cmd+{", description: "Previous Tab" },
-            ShortcutEntry { keys: "Cmd+1-9", description: "Activate Tab 1-9" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Panels",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+B", description: "Toggle Schema Browser" },
-            ShortcutEntry { keys: "Cmd+Shift+B", description: "Toggle Right Dock" },
-            ShortcutEntry { keys: "Cmd+J", description: "Toggle Results Panel" },
-            ShortcutEntry { keys: "Cmd+Shift+E", description: "Focus Schema Browser" },
-            ShortcutEntry { keys: "Cmd+Shift+R", description: "Focus Results" },
-            ShortcutEntry { keys: "Cmd+Shift+M", description: "Focus Messages" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Editor Layout",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+\\", description: "Split Right" },
-            ShortcutEntry { keys: "Cmd+|", description: "Split Down" },
-            ShortcutEntry { keys: "Cmd+K Cmd+Right", description: "Focus Next Pane" },
-            ShortcutEntry { keys: "Cmd+K Cmd+Left", description: "Focus Previous Pane" },
-            ShortcutEntry { keys: "Cmd+K Cmd+W", description: "Close Pane" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Query Editor",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+Enter", description: "Run Query" },
-            ShortcutEntry { keys: "Cmd+Shift+E", description: "Explain Query" },
-            ShortcutEntry { keys: "Cmd+Shift+F", description: "Format Query" },
-            ShortcutEntry { keys: "Escape", description: "Cancel Query" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Editing",
-        shortcuts: &[
-            ShortcutEntry { keys: "Cmd+Z", description: "Undo" },
-            ShortcutEntry { keys: "Cmd+Shift+Z", description: "Redo" },
-            ShortcutEntry { keys: "Cmd+X", description: "Cut" },
-            ShortcutEntry { keys: "Cmd+C", description: "Copy" },
-            ShortcutEntry { keys: "Cmd+V", description: "Paste" },
-            ShortcutEntry { keys: "Cmd+A", description: "Select All" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Tree Navigation",
-        shortcuts: &[
-            ShortcutEntry { keys: "Up/Down", description: "Navigate Items" },
-            ShortcutEntry { keys: "Right", description: "Expand Item" },
-            ShortcutEntry { keys: "Left", description: "Collapse Item" },
-            ShortcutEntry { keys: "Enter", description: "Activate Item" },
-            ShortcutEntry { keys: "Cmd+Shift+Right", description: "Expand All" },
-            ShortcutEntry { keys: "Cmd+Shift+Left", description: "Collapse All" },
-        ],
-    },
-];
-
-/// Windows/Linux keyboard shortcuts organized by category.
-#[cfg(not(target_os = "macos"))]
-const SHORTCUTS: &[ShortcutCategory] = &[
-    ShortcutCategory {
-        name: "General",
-        shortcuts: &[
-            ShortcutEntry { keys: "Alt+F4", description: "Quit Tusk" },
-            ShortcutEntry { keys: "Ctrl+,", description: "Open Settings" },
-            ShortcutEntry { keys: "Ctrl+/", description: "Show Keyboard Shortcuts" },
-            ShortcutEntry { keys: "Ctrl+Shift+P", description: "Command Palette" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Tabs",
-        shortcuts: &[
-            ShortcutEntry { keys: "Ctrl+N", description: "New Query Tab" },
-            ShortcutEntry { keys: "Ctrl+W", description: "Close Tab" },
-            ShortcutEntry { keys: "Ctrl+Shift+W", description: "Close All Tabs" },
-            ShortcutEntry { keys: "Ctrl+Tab", description: "Next Tab" },
-            ShortcutEntry { keys: "Ctrl+Shift+Tab", description: "Previous Tab" },
-            ShortcutEntry { keys: "Ctrl+1-9", description: "Activate Tab 1-9" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Panels",
-        shortcuts: &[
-            ShortcutEntry { keys: "Ctrl+B", description: "Toggle Schema Browser" },
-            ShortcutEntry { keys: "Ctrl+Shift+B", description: "Toggle Right Dock" },
-            ShortcutEntry { keys: "Ctrl+J", description: "Toggle Results Panel" },
-            ShortcutEntry { keys: "Ctrl+Shift+E", description: "Focus Schema Browser" },
-            ShortcutEntry { keys: "Ctrl+Shift+R", description: "Focus Results" },
-            ShortcutEntry { keys: "Ctrl+Shift+M", description: "Focus Messages" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Editor Layout",
-        shortcuts: &[
-            ShortcutEntry { keys: "Ctrl+\\", description: "Split Right" },
-            ShortcutEntry { keys: "Ctrl+|", description: "Split Down" },
-            ShortcutEntry { keys: "Ctrl+K Ctrl+Right", description: "Focus Next Pane" },
-            ShortcutEntry { keys: "Ctrl+K Ctrl+Left", description: "Focus Previous Pane" },
-            ShortcutEntry { keys: "Ctrl+K Ctrl+W", description: "Close Pane" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Query Editor",
-        shortcuts: &[
-            ShortcutEntry { keys: "Ctrl+Enter", description: "Run Query" },
-            ShortcutEntry { keys: "Ctrl+Shift+E", description: "Explain Query" },
-            ShortcutEntry { keys: "Ctrl+Shift+F", description: "Format Query" },
-            ShortcutEntry { keys: "Escape", description: "Cancel Query" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Editing",
-        shortcuts: &[
-            ShortcutEntry { keys: "Ctrl+Z", description: "Undo" },
-            ShortcutEntry { keys: "Ctrl+Y", description: "Redo" },
-            ShortcutEntry { keys: "Ctrl+X", description: "Cut" },
-            ShortcutEntry { keys: "Ctrl+C", description: "Copy" },
-            ShortcutEntry { keys: "Ctrl+V", description: "Paste" },
-            ShortcutEntry { keys: "Ctrl+A", description: "Select All" },
-        ],
-    },
-    ShortcutCategory {
-        name: "Tree Navigation",
-        shortcuts: &[
-            ShortcutEntry { keys: "Up/Down", description: "Navigate Items" },
-            ShortcutEntry { keys: "Right", description: "Expand Item" },
-            ShortcutEntry { keys: "Left", description: "Collapse Item" },
-            ShortcutEntry { keys: "Enter", description: "Activate Item" },
-            ShortcutEntry { keys: "Ctrl+Shift+Right", description: "Expand All" },
-            ShortcutEntry { keys: "Ctrl+Shift+Left", description: "Collapse All" },
-        ],
-    },
-];
+/// Group bindings by category, merging bindings with the same description.
+///
+/// Categories keep [`BindingCategory::ALL`] order and entries keep the
+/// order they were registered in. Empty categories are omitted.
+fn group_bindings(entries: &[KeymapEntry]) -> Vec<ShortcutCategory> {
+    BindingCategory::ALL
+        .iter()
+        .filter_map(|category| {
+            let mut shortcuts: Vec<ShortcutEntry> = Vec::new();
+            for entry in entries.iter().filter(|e| e.binding.category == *category) {
+                let binding = &entry.binding;
+                let keys = entry.display_keys();
+                match shortcuts.iter_mut().find(|s| s.description == binding.description) {
+                    Some(shortcut) => {
+                        if !shortcut.keys.contains(&keys) {
+                            shortcut.keys.push(keys);
+                        }
+                    }
+                    None => shortcuts
+                        .push(ShortcutEntry { description: binding.description, keys: vec![keys] }),
+                }
+            }
+            (!shortcuts.is_empty()).then(|| ShortcutCategory { name: category.label(), shortcuts })
+        })
+        .collect()
+}
 
 /// View that renders the keyboard shortcuts content.
-pub struct KeyboardShortcutsContent;
+pub struct KeyboardShortcutsContent {
+    categories: Vec<ShortcutCategory>,
+}
 
 impl KeyboardShortcutsContent {
-    /// Create a new keyboard shortcuts content view.
-    pub fn new(_cx: &mut Context<Self>) -> Self {
-        Self
+    /// Create a new keyboard shortcuts content view from the active keymap.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        Self { categories: group_bindings(&active_keymap(cx)) }
     }
 }
 
@@ -204,7 +82,7 @@ impl Render for KeyboardShortcutsContent {
             .gap(px(16.0))
             .overflow_y_scroll()
             .max_h(px(400.0))
-            .children(SHORTCUTS.iter().map(|category| {
+            .children(self.categories.iter().map(|category| {
                 div()
                     .flex()
                     .flex_col()
@@ -244,20 +122,24 @@ impl Render for KeyboardShortcutsContent {
                                                 .text_color(text_muted)
                                                 .child(shortcut.description),
                                         )
-                                        // Key combination
-                                        .child(div().flex().gap(px(4.0)).children(
-                                            shortcut.keys.split('+').map(|key| {
-                                                div()
-                                                    .px(px(6.0))
-                                                    .py(px(2.0))
-                                                    .bg(surface_bg)
-                                                    .border_1()
-                                                    .border_color(border_color)
-                                                    .rounded(px(4.0))
-                                                    .text_size(px(11.0))
-                                                    .font_weight(gpui::FontWeight::MEDIUM)
-                                                    .text_color(text_color)
-                                                    .child(key)
+                                        // Key combinations; chords and alternatives
+                                        // are separated by wider gaps
+                                        .child(div().flex().gap(px(12.0)).children(
+                                            shortcut.keys.iter().map(|keys| {
+                                                div().flex().gap(px(8.0)).children(
+                                                    keys.split(' ').map(|chord| {
+                                                        div().flex().gap(px(4.0)).children(
+                                                            chord.split('+').map(|key| {
+                                                                key_cap(
+                                                                    key.to_string(),
+                                                                    text_color,
+                                                                    surface_bg,
+                                                                    border_color,
+                                                                )
+                                                            }),
+                                                        )
+                                                    }),
+                                                )
                                             }),
                                         ))
                                 },
@@ -267,6 +149,26 @@ impl Render for KeyboardShortcutsContent {
     }
 }
 
+/// Render a single key cap.
+fn key_cap(
+    key: String,
+    text_color: Hsla,
+    background: Hsla,
+    border_color: Hsla,
+) -> impl IntoElement {
+    div()
+        .px(px(6.0))
+        .py(px(2.0))
+        .bg(background)
+        .border_1()
+        .border_color(border_color)
+        .rounded(px(4.0))
+        .text_size(px(11.0))
+        .font_weight(gpui::FontWeight::MEDIUM)
+        .text_color(text_color)
+        .child(key)
+}
+
 /// Show the keyboard shortcuts modal.
 ///
 /// This function creates and displays a modal containing all keyboard shortcuts.
@@ -285,3 +187,26 @@ pub fn show_keyboard_shortcuts(cx: &mut App) {
         layer.show(modal, cx);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_bindings::{all_bindings, merge_overrides, KeymapOverrides};
+
+    #[test]
+    fn test_group_bindings_covers_every_binding() {
+        let (entries, _) = merge_overrides(&all_bindings(), &KeymapOverrides::new());
+        let categories = group_bindings(&entries);
+
+        let names: Vec<_> = categories.iter().map(|c| c.name).collect();
+        assert_eq!(names, ["Workspace", "Editor", "Editing", "Results", "Navigation"]);
+
+        for entry in &entries {
+            let binding = &entry.binding;
+            let category = categories.iter().find(|c| c.name == binding.category.label()).unwrap();
+            let shortcut =
+                category.shortcuts.iter().find(|s| s.description == binding.description).unwrap();
+            assert!(shortcut.keys.contains(&entry.display_keys()));
+        }
+    }
+}
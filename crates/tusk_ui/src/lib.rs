@@ -9,12 +9,15 @@
 
 // Core modules
 pub mod application_menu;
+pub mod assets;
 pub mod button;
 pub mod confirm_dialog;
 pub mod connection_dialog;
 pub mod context_menu;
+pub mod debounce;
 pub mod dock;
 pub mod error_panel;
+pub mod home;
 pub mod icon;
 pub mod key_bindings;
 pub mod keyboard_shortcuts;
@@ -25,9 +28,13 @@ pub mod panel;
 pub mod panels;
 pub mod popover_menu;
 pub mod query_editor;
+pub mod quick_open;
 pub mod resizer;
 pub mod select;
 pub mod spinner;
+pub mod sql_editing;
+pub mod sql_file;
+pub mod sql_reference;
 pub mod status_bar;
 pub mod text_input;
 pub mod theme;
@@ -38,35 +45,45 @@ pub mod workspace;
 
 // Re-exports for convenience
 pub use application_menu::ApplicationMenu;
+pub use assets::Assets;
 pub use button::{Button, ButtonSize, ButtonStyle, ButtonVariant, IconPosition};
 pub use confirm_dialog::{ConfirmDialog, ConfirmDialogEvent, ConfirmDialogKind};
-pub use connection_dialog::{ConnectionDialog, ConnectionDialogEvent, ConnectionDialogState};
+pub use connection_dialog::{
+    ConnectionDialog, ConnectionDialogEvent, ConnectionDialogState, SavedConnectionStatus,
+};
 pub use context_menu::{ContextMenu, ContextMenuEvent, ContextMenuItem, ContextMenuLayer};
 pub use dock::{Dock, DockEvent};
 pub use error_panel::{ErrorPanel, ErrorPanelContent};
+pub use home::{HomeView, HomeViewEvent};
 pub use icon::{Icon, IconName, IconSize};
 pub use key_bindings::register_key_bindings;
 pub use keyboard_shortcuts::show_keyboard_shortcuts;
 pub use layout::{radius, sizes, spacing};
 pub use modal::{Modal, ModalAction, ModalEvent, ModalLayer};
 pub use pane::{
-    Pane, PaneEvent, PaneGroup, PaneGroupEvent, PaneLayout, PaneNode, SerializedAxis, TabItem,
+    FocusDirection, Pane, PaneEvent, PaneGroup, PaneGroupEvent, PaneLayout, PaneNode, PaneRect,
+    PaneZoom, SerializedAxis, TabItem,
 };
 pub use panel::{DockPosition, Focusable, Panel, PanelEntry, PanelEvent, PanelHandle};
 pub use panels::{
     database_schema_to_tree, DisplayColumn, DisplayError, DisplayRow, Message, MessageSeverity,
     MessagesPanel, ResultsPanel, ResultsPanelState, ResultsState, ResultsStatus,
-    SchemaBrowserPanel, SchemaItem,
+    SchemaBrowserPanel, SchemaItem, SelectionSummary, SeverityCounts, SeverityFilter,
 };
 pub use popover_menu::{PopoverMenu, PopoverMenuHandle};
-pub use query_editor::{QueryEditor, QueryEditorState, QueryEditorStatus};
+pub use query_editor::{QueryEditor, QueryEditorEvent, QueryEditorState, QueryEditorStatus};
+pub use quick_open::{QuickOpen, QuickOpenEvent};
 pub use resizer::Resizer;
 pub use select::{Select, SelectEvent, SelectOption};
 pub use spinner::{Spinner, SpinnerSize};
-pub use status_bar::{ConnectionStatus, ExecutionState, StatusBar};
+pub use sql_file::SqlFileState;
+pub use sql_reference::{DocEntry, DocKind};
+pub use status_bar::{
+    ConnectionStatus, EnvironmentLabel, ExecutionEvent, ExecutionState, StatusBar,
+    TransactionIndicator,
+};
 pub use text_input::{
-    register_text_input_bindings, Copy, Cut, Paste, Redo, SelectAll, TextInput, TextInputEvent,
-    Undo,
+    Copy, Cut, InputHistory, InputKind, Paste, Redo, SelectAll, TextInput, TextInputEvent, Undo,
 };
 pub use theme::{ThemeColors, TuskTheme};
 pub use toast::{Toast, ToastLayer, ToastSeverity};
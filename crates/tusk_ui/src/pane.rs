@@ -5,8 +5,8 @@
 
 use gpui::{
     canvas, deferred, div, prelude::*, px, AnyElement, AnyView, App, Axis, Bounds, ClickEvent,
-    Context, CursorStyle, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle, IntoElement,
-    Pixels, Point, Render, SharedString, Subscription, Window,
+    Context, CursorStyle, DragMoveEvent, Entity, EntityId, EventEmitter, FocusHandle, Hsla,
+    IntoElement, Pixels, Point, Render, SharedString, Subscription, Window,
 };
 use smallvec::SmallVec;
 use uuid::Uuid;
@@ -62,6 +62,8 @@ pub struct TabItem {
     pub dirty: bool,
     /// Whether the tab can be closed.
     pub closable: bool,
+    /// Accent stripe along the top of the tab (e.g. for production connections).
+    pub accent: Option<Hsla>,
     /// The content view for this tab.
     pub view: AnyView,
 }
@@ -75,6 +77,7 @@ impl TabItem {
             icon: None,
             dirty: false,
             closable: true,
+            accent: None,
             view: view.into(),
         }
     }
@@ -96,6 +99,12 @@ impl TabItem {
         self.closable = closable;
         self
     }
+
+    /// Set the tab's accent stripe color.
+    pub fn with_accent(mut self, accent: Option<Hsla>) -> Self {
+        self.accent = accent;
+        self
+    }
 }
 
 // ============================================================================
@@ -137,6 +146,8 @@ pub struct Pane {
     _dialog_subscription: Option<Subscription>,
     /// Index being dragged over for visual feedback.
     drag_over_index: Option<usize>,
+    /// View shown when no tabs are open (falls back to a hint when unset).
+    empty_view: Option<AnyView>,
 }
 
 impl Pane {
@@ -150,9 +161,16 @@ impl Pane {
             pending_close_index: None,
             _dialog_subscription: None,
             drag_over_index: None,
+            empty_view: None,
         }
     }
 
+    /// Set the view shown when no tabs are open.
+    pub fn set_empty_view(&mut self, view: Option<AnyView>, cx: &mut Context<Self>) {
+        self.empty_view = view;
+        cx.notify();
+    }
+
     /// Get all tabs.
     pub fn tabs(&self) -> &[TabItem] {
         &self.tabs
@@ -329,6 +347,22 @@ impl Pane {
         self.activate_tab(prev, cx);
     }
 
+    /// Update the title and dirty marker of the tab showing `view_id`.
+    pub fn update_tab(
+        &mut self,
+        view_id: EntityId,
+        title: SharedString,
+        dirty: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(tab) = self.tabs.iter_mut().find(|tab| tab.view.entity_id() == view_id) else {
+            return;
+        };
+        tab.title = title;
+        tab.dirty = dirty;
+        cx.notify();
+    }
+
     /// Move a tab from one index to another.
     pub fn move_tab(&mut self, from: usize, to: usize, cx: &mut Context<Self>) {
         if from >= self.tabs.len() || to >= self.tabs.len() || from == to {
@@ -492,6 +526,11 @@ impl Pane {
             tab_div = tab_div.bg(drop_target_bg);
         }
 
+        // Environment accent stripe
+        if let Some(tab_accent) = tab.accent {
+            tab_div = tab_div.border_t_2().border_color(tab_accent);
+        }
+
         // Icon
         if let Some(icon) = tab_icon {
             tab_div = tab_div.child(Icon::new(icon).size(IconSize::Small).color(text_color));
@@ -535,6 +574,8 @@ impl Pane {
 
         if let Some(tab) = self.active_tab() {
             div().flex_1().w_full().bg(theme.colors.editor_background).child(tab.view.clone())
+        } else if let Some(view) = &self.empty_view {
+            div().flex_1().w_full().bg(theme.colors.editor_background).child(view.clone())
         } else {
             // Empty state
             div()
@@ -689,11 +730,61 @@ impl PaneNode {
     }
 }
 
+/// Which pane, if any, is zoomed to fill its pane group.
+///
+/// Kept beside the pane tree rather than in it, so unzooming restores the
+/// split exactly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PaneZoom<T> {
+    zoomed: Option<T>,
+}
+
+impl<T> Default for PaneZoom<T> {
+    fn default() -> Self {
+        Self { zoomed: None }
+    }
+}
+
+impl<T: Copy + PartialEq> PaneZoom<T> {
+    /// The zoomed pane.
+    pub fn zoomed(&self) -> Option<T> {
+        self.zoomed
+    }
+
+    /// Zoom `active` if nothing is zoomed, otherwise unzoom.
+    pub fn toggle(&mut self, active: T) {
+        self.zoomed = match self.zoomed {
+            Some(_) => None,
+            None => Some(active),
+        };
+    }
+
+    /// Unzoom. Returns true if a pane was zoomed.
+    pub fn clear(&mut self) -> bool {
+        self.zoomed.take().is_some()
+    }
+
+    /// The pane adjacent to `from` in `direction`, as in [`adjacent_pane`].
+    ///
+    /// While a pane is zoomed the others are hidden, so there is none.
+    pub fn adjacent_pane(
+        &self,
+        rects: &[PaneRect],
+        from: usize,
+        direction: FocusDirection,
+    ) -> Option<usize> {
+        match self.zoomed {
+            Some(_) => None,
+            None => adjacent_pane(rects, from, direction),
+        }
+    }
+}
+
 /// Serializable pane layout for persistence.
 ///
 /// This mirrors PaneNode but without Entity references, allowing
 /// serialization to/from JSON for workspace state persistence.
-#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum PaneLayout {
     /// A single pane (leaf node).
     #[default]
@@ -709,6 +800,129 @@ pub enum PaneLayout {
     },
 }
 
+impl PaneLayout {
+    /// Compute the rectangle each pane occupies within a `width` x `height` area.
+    ///
+    /// Rectangles are returned in the same order as [`PaneNode::panes`].
+    /// Split handles are ignored.
+    pub fn pane_rects(&self, width: f32, height: f32) -> Vec<PaneRect> {
+        let mut rects = Vec::new();
+        self.collect_rects(PaneRect { left: 0.0, top: 0.0, width, height }, &mut rects);
+        rects
+    }
+
+    fn collect_rects(&self, area: PaneRect, rects: &mut Vec<PaneRect>) {
+        match self {
+            PaneLayout::Single => rects.push(area),
+            PaneLayout::Split { axis, children, ratios } => {
+                let mut offset = 0.0;
+                for (child, ratio) in children.iter().zip(ratios.iter()) {
+                    let child_area = match axis {
+                        SerializedAxis::Horizontal => PaneRect {
+                            left: area.left + offset * area.width,
+                            width: ratio * area.width,
+                            ..area
+                        },
+                        SerializedAxis::Vertical => PaneRect {
+                            top: area.top + offset * area.height,
+                            height: ratio * area.height,
+                            ..area
+                        },
+                    };
+                    child.collect_rects(child_area, rects);
+                    offset += ratio;
+                }
+            }
+        }
+    }
+}
+
+/// Rectangle occupied by a pane, relative to the pane group's origin.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PaneRect {
+    /// Left edge.
+    pub left: f32,
+    /// Top edge.
+    pub top: f32,
+    /// Width.
+    pub width: f32,
+    /// Height.
+    pub height: f32,
+}
+
+impl PaneRect {
+    /// Right edge.
+    pub fn right(&self) -> f32 {
+        self.left + self.width
+    }
+
+    /// Bottom edge.
+    pub fn bottom(&self) -> f32 {
+        self.top + self.height
+    }
+}
+
+/// Direction for spatial pane focus movement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FocusDirection {
+    /// Toward the left edge.
+    Left,
+    /// Toward the right edge.
+    Right,
+    /// Toward the top edge.
+    Up,
+    /// Toward the bottom edge.
+    Down,
+}
+
+/// Find the pane spatially adjacent to `rects[from]` in `direction`.
+///
+/// Candidates must lie beyond the current pane's edge and overlap it on the
+/// other axis. The nearest one wins; ties go to the largest overlap, then
+/// to the pane closest to the current pane's top/left edge.
+pub fn adjacent_pane(rects: &[PaneRect], from: usize, direction: FocusDirection) -> Option<usize> {
+    // Tolerance for rounding in ratio arithmetic
+    const EPSILON: f32 = 0.5;
+
+    let current = rects.get(from)?;
+    rects
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != from)
+        .filter_map(|(index, rect)| {
+            let (gap, overlap, offset) = match direction {
+                FocusDirection::Left => (
+                    current.left - rect.right(),
+                    span_overlap(current.top, current.bottom(), rect.top, rect.bottom()),
+                    (rect.top - current.top).abs(),
+                ),
+                FocusDirection::Right => (
+                    rect.left - current.right(),
+                    span_overlap(current.top, current.bottom(), rect.top, rect.bottom()),
+                    (rect.top - current.top).abs(),
+                ),
+                FocusDirection::Up => (
+                    current.top - rect.bottom(),
+                    span_overlap(current.left, current.right(), rect.left, rect.right()),
+                    (rect.left - current.left).abs(),
+                ),
+                FocusDirection::Down => (
+                    rect.top - current.bottom(),
+                    span_overlap(current.left, current.right(), rect.left, rect.right()),
+                    (rect.left - current.left).abs(),
+                ),
+            };
+            (gap > -EPSILON && overlap > EPSILON).then_some((index, gap, overlap, offset))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1).then(b.2.total_cmp(&a.2)).then(a.3.total_cmp(&b.3)))
+        .map(|(index, ..)| index)
+}
+
+/// Length of the overlap between two spans (zero or negative if disjoint).
+fn span_overlap(start_a: f32, end_a: f32, start_b: f32, end_b: f32) -> f32 {
+    end_a.min(end_b) - start_a.max(start_b)
+}
+
 /// Serializable axis enum.
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
 pub enum SerializedAxis {
@@ -751,6 +965,8 @@ pub enum PaneGroupEvent {
     ActivePaneChanged { pane: Entity<Pane> },
     /// Layout ratios changed.
     RatiosChanged,
+    /// The active pane was zoomed or unzoomed.
+    ZoomChanged { zoomed: bool },
 }
 
 // ============================================================================
@@ -769,6 +985,10 @@ pub struct PaneGroup {
     bounds: Bounds<Pixels>,
     /// Previous drag coordinates (to avoid duplicate processing).
     previous_drag_coordinates: Option<Point<Pixels>>,
+    /// View shown by panes with no open tabs.
+    empty_view: Option<AnyView>,
+    /// Pane zoomed to fill the group, if any.
+    zoom: PaneZoom<EntityId>,
 }
 
 impl PaneGroup {
@@ -781,7 +1001,17 @@ impl PaneGroup {
             focus_handle: cx.focus_handle(),
             bounds: Bounds::default(),
             previous_drag_coordinates: None,
+            empty_view: None,
+            zoom: PaneZoom::default(),
+        }
+    }
+
+    /// Set the view shown by panes with no open tabs, including panes created by splits.
+    pub fn set_empty_view(&mut self, view: Option<AnyView>, cx: &mut Context<Self>) {
+        for pane in self.root.panes() {
+            pane.update(cx, |pane, cx| pane.set_empty_view(view.clone(), cx));
         }
+        self.empty_view = view;
     }
 
     /// Get the active pane.
@@ -793,6 +1023,7 @@ impl PaneGroup {
     pub fn set_active_pane(&mut self, pane: Entity<Pane>, cx: &mut Context<Self>) {
         if self.active_pane.entity_id() != pane.entity_id() {
             self.active_pane = pane.clone();
+            self.zoom.clear();
             cx.emit(PaneGroupEvent::ActivePaneChanged { pane });
             cx.notify();
         }
@@ -803,6 +1034,21 @@ impl PaneGroup {
         self.root.panes()
     }
 
+    /// Whether a pane is zoomed to fill the group.
+    pub fn is_zoomed(&self) -> bool {
+        self.zoom.zoomed().is_some()
+    }
+
+    /// Toggle zoom on the active pane.
+    ///
+    /// Zooming only changes what is rendered; the pane tree and its ratios
+    /// are left untouched, so unzooming restores the split exactly.
+    pub fn toggle_zoom(&mut self, cx: &mut Context<Self>) {
+        self.zoom.toggle(self.active_pane.entity_id());
+        cx.emit(PaneGroupEvent::ZoomChanged { zoomed: self.is_zoomed() });
+        cx.notify();
+    }
+
     /// Get the current layout for serialization/persistence.
     pub fn layout(&self) -> PaneLayout {
         self.root.to_layout()
@@ -815,7 +1061,13 @@ impl PaneGroup {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) -> Entity<Pane> {
+        if self.zoom.clear() {
+            cx.emit(PaneGroupEvent::ZoomChanged { zoomed: false });
+        }
+
         let new_pane = cx.new(Pane::new);
+        let empty_view = self.empty_view.clone();
+        new_pane.update(cx, |pane, cx| pane.set_empty_view(empty_view, cx));
 
         // For simplicity, we replace the root with a split
         // A full implementation would find the active pane in the tree
@@ -849,6 +1101,9 @@ impl PaneGroup {
                     children.remove(0)
                 };
                 self.root = *remaining;
+                if self.zoom.clear() {
+                    cx.emit(PaneGroupEvent::ZoomChanged { zoomed: false });
+                }
 
                 // Update active pane
                 self.active_pane = self.root.panes().first().unwrap().clone();
@@ -948,6 +1203,40 @@ impl PaneGroup {
         }
     }
 
+    /// Focus the pane spatially adjacent to the active pane.
+    ///
+    /// Does nothing if there is no pane in that direction, or while a pane is
+    /// zoomed and the others are hidden.
+    pub fn focus_pane_in_direction(
+        &mut self,
+        direction: FocusDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let panes = self.panes();
+        let Some(pos) = panes.iter().position(|p| p.entity_id() == self.active_pane.entity_id())
+        else {
+            return;
+        };
+
+        // Before the first layout pass the bounds are empty; ratios alone
+        // still give the right adjacency
+        let size = self.bounds.size;
+        let (width, height) = if size.width > Pixels::ZERO && size.height > Pixels::ZERO {
+            (size.width / px(1.0), size.height / px(1.0))
+        } else {
+            (1000.0, 1000.0)
+        };
+
+        let rects = self.layout().pane_rects(width, height);
+        if let Some(target) = self.zoom.adjacent_pane(&rects, pos, direction) {
+            let target_pane = panes[target].clone();
+            self.set_active_pane(target_pane.clone(), cx);
+            let focus_handle = target_pane.read(cx).focus_handle.clone();
+            focus_handle.focus(window, cx);
+        }
+    }
+
     /// Render a pane node.
     fn render_node(&self, node: &PaneNode, cx: &mut Context<Self>) -> AnyElement {
         match node {
@@ -1076,7 +1365,10 @@ impl Render for PaneGroup {
                     this.resize_split(drag.split_index, drag.axis, e.event.position, cx);
                 }
             }))
-            .child(self.render_node(&self.root, cx))
+            .child(match self.zoom.zoomed().and_then(|id| self.root.find_pane(id)) {
+                Some(pane) => self.render_node(&PaneNode::Single(pane), cx),
+                None => self.render_node(&self.root, cx),
+            })
     }
 }
 
@@ -1090,4 +1382,120 @@ mod tests {
         // Note: We can't test view creation without GPUI context
         // This is a placeholder for the structure test
     }
+
+    /// Two columns, each split into two rows: top-left, bottom-left,
+    /// top-right, bottom-right.
+    fn grid_2x2() -> PaneLayout {
+        let column = || PaneLayout::Split {
+            axis: SerializedAxis::Vertical,
+            children: vec![PaneLayout::Single, PaneLayout::Single],
+            ratios: vec![0.5, 0.5],
+        };
+        PaneLayout::Split {
+            axis: SerializedAxis::Horizontal,
+            children: vec![column(), column()],
+            ratios: vec![0.5, 0.5],
+        }
+    }
+
+    #[test]
+    fn test_pane_rects_2x2() {
+        let rects = grid_2x2().pane_rects(800.0, 600.0);
+        assert_eq!(rects.len(), 4);
+        assert_eq!(rects[0], PaneRect { left: 0.0, top: 0.0, width: 400.0, height: 300.0 });
+        assert_eq!(rects[1], PaneRect { left: 0.0, top: 300.0, width: 400.0, height: 300.0 });
+        assert_eq!(rects[2], PaneRect { left: 400.0, top: 0.0, width: 400.0, height: 300.0 });
+        assert_eq!(rects[3], PaneRect { left: 400.0, top: 300.0, width: 400.0, height: 300.0 });
+    }
+
+    #[test]
+    fn test_adjacent_pane_2x2() {
+        const TOP_LEFT: usize = 0;
+        const BOTTOM_LEFT: usize = 1;
+        const TOP_RIGHT: usize = 2;
+        const BOTTOM_RIGHT: usize = 3;
+        let rects = grid_2x2().pane_rects(800.0, 600.0);
+
+        assert_eq!(adjacent_pane(&rects, TOP_LEFT, FocusDirection::Right), Some(TOP_RIGHT));
+        assert_eq!(adjacent_pane(&rects, TOP_LEFT, FocusDirection::Down), Some(BOTTOM_LEFT));
+        assert_eq!(adjacent_pane(&rects, TOP_LEFT, FocusDirection::Left), None);
+        assert_eq!(adjacent_pane(&rects, TOP_LEFT, FocusDirection::Up), None);
+        assert_eq!(adjacent_pane(&rects, BOTTOM_RIGHT, FocusDirection::Left), Some(BOTTOM_LEFT));
+        assert_eq!(adjacent_pane(&rects, BOTTOM_RIGHT, FocusDirection::Up), Some(TOP_RIGHT));
+        assert_eq!(adjacent_pane(&rects, BOTTOM_LEFT, FocusDirection::Right), Some(BOTTOM_RIGHT));
+        assert_eq!(adjacent_pane(&rects, TOP_RIGHT, FocusDirection::Right), None);
+    }
+
+    #[test]
+    fn test_toggle_zoom_twice_restores_layout() {
+        let layout = PaneLayout::Split {
+            axis: SerializedAxis::Horizontal,
+            children: vec![
+                PaneLayout::Single,
+                PaneLayout::Split {
+                    axis: SerializedAxis::Vertical,
+                    children: vec![PaneLayout::Single, PaneLayout::Single],
+                    ratios: vec![0.3, 0.7],
+                },
+            ],
+            ratios: vec![0.6, 0.4],
+        };
+        let before = layout.clone();
+        let rects = layout.pane_rects(800.0, 600.0);
+
+        let mut zoom = PaneZoom::default();
+        assert_eq!(zoom.adjacent_pane(&rects, 2, FocusDirection::Left), Some(0));
+
+        // Zoomed, the other panes are hidden and can't take focus
+        zoom.toggle(2);
+        assert_eq!(zoom.zoomed(), Some(2));
+        assert_eq!(zoom.adjacent_pane(&rects, 2, FocusDirection::Left), None);
+        assert_eq!(zoom.adjacent_pane(&rects, 2, FocusDirection::Up), None);
+
+        // Unzooming brings back the same split and neighbors
+        zoom.toggle(2);
+        assert_eq!(zoom.zoomed(), None);
+        assert_eq!(layout, before);
+        assert_eq!(layout.pane_rects(800.0, 600.0), rects);
+        assert_eq!(zoom.adjacent_pane(&rects, 2, FocusDirection::Left), Some(0));
+        assert_eq!(zoom.adjacent_pane(&rects, 2, FocusDirection::Up), Some(1));
+    }
+
+    #[test]
+    fn test_zoom_clear() {
+        let mut zoom = PaneZoom::default();
+        assert!(!zoom.clear());
+
+        zoom.toggle(1);
+        // Toggling while zoomed unzooms regardless of which pane is active
+        let mut other = zoom;
+        other.toggle(0);
+        assert_eq!(other.zoomed(), None);
+
+        assert!(zoom.clear());
+        assert_eq!(zoom.zoomed(), None);
+    }
+
+    #[test]
+    fn test_adjacent_pane_prefers_aligned_neighbor() {
+        // A full-height pane on the left, two stacked panes on the right
+        let layout = PaneLayout::Split {
+            axis: SerializedAxis::Horizontal,
+            children: vec![
+                PaneLayout::Single,
+                PaneLayout::Split {
+                    axis: SerializedAxis::Vertical,
+                    children: vec![PaneLayout::Single, PaneLayout::Single],
+                    ratios: vec![0.5, 0.5],
+                },
+            ],
+            ratios: vec![0.5, 0.5],
+        };
+        let rects = layout.pane_rects(800.0, 600.0);
+
+        assert_eq!(adjacent_pane(&rects, 0, FocusDirection::Right), Some(1));
+        assert_eq!(adjacent_pane(&rects, 2, FocusDirection::Left), Some(0));
+        assert_eq!(adjacent_pane(&rects, 1, FocusDirection::Down), Some(2));
+        assert_eq!(adjacent_pane(&rects, 3, FocusDirection::Up), None);
+    }
 }
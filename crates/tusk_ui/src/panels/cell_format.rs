@@ -0,0 +1,409 @@
+//! Cell value formatting for the results grid.
+//!
+//! Arrays and composite (row) types are rendered using PostgreSQL's own text
+//! output conventions so values look the same as they would in psql:
+//! - Arrays as `{1,2,3}`, with multi-dimensional arrays nested as `{{1,2},{3,4}}`
+//! - Composites as `(a,b)`, with NULL fields left empty
+//! - Elements quoted and escaped whenever the literal would otherwise be ambiguous
+//!
+//! Element values (numeric, timestamps, uuid, json, inet, ...) are decoded to
+//! text by `tusk_core::models::value::scalar_text`.
+
+#[cfg(feature = "persistence")]
+use tokio_postgres::types::{FromSql, Kind, Type};
+#[cfg(feature = "persistence")]
+use tusk_core::models::value::scalar_text;
+
+/// A decoded cell value that preserves array and composite structure.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// SQL NULL.
+    Null,
+    /// A scalar value already rendered as text.
+    Scalar(String),
+    /// An array; multi-dimensional arrays nest one level per dimension.
+    Array(Vec<CellValue>),
+    /// A composite (row) value with one entry per field.
+    Composite(Vec<CellValue>),
+}
+
+impl CellValue {
+    /// Create a scalar value.
+    pub fn scalar(value: impl Into<String>) -> Self {
+        Self::Scalar(value.into())
+    }
+
+    /// Check if this value is NULL.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Self::Null)
+    }
+
+    /// Render the value using PostgreSQL's text output format.
+    ///
+    /// A top-level NULL is rendered as `NULL` to match the rest of the grid.
+    pub fn to_pg_literal(&self) -> String {
+        match self {
+            Self::Null => "NULL".to_string(),
+            Self::Scalar(value) => value.clone(),
+            Self::Array(elements) => format_array(elements),
+            Self::Composite(fields) => format_composite(fields),
+        }
+    }
+}
+
+/// Format array elements as `{...}`, recursing into nested dimensions.
+fn format_array(elements: &[CellValue]) -> String {
+    let mut out = String::from("{");
+    for (i, element) in elements.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        match element {
+            CellValue::Null => out.push_str("NULL"),
+            // Sub-arrays of a multi-dimensional array are never quoted
+            CellValue::Array(inner) => out.push_str(&format_array(inner)),
+            other => out.push_str(&quote_array_element(&other.to_pg_literal())),
+        }
+    }
+    out.push('}');
+    out
+}
+
+/// Format composite fields as `(...)`.
+fn format_composite(fields: &[CellValue]) -> String {
+    let mut out = String::from("(");
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        // NULL fields are represented by nothing at all between the delimiters
+        if !field.is_null() {
+            out.push_str(&quote_composite_field(&field.to_pg_literal()));
+        }
+    }
+    out.push(')');
+    out
+}
+
+/// Quote an array element if needed, escaping `"` and `\` with a backslash.
+///
+/// Matches `array_out`: elements are quoted when empty, when they spell
+/// `NULL`, or when they contain delimiters, quotes, backslashes or whitespace.
+pub fn quote_array_element(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.eq_ignore_ascii_case("NULL")
+        || value
+            .chars()
+            .any(|c| matches!(c, '{' | '}' | ',' | '"' | '\\') || c.is_ascii_whitespace());
+
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+/// Quote a composite field if needed, doubling embedded `"` and `\`.
+///
+/// Matches `record_out`: fields are quoted when empty or when they contain
+/// parentheses, commas, quotes, backslashes or whitespace.
+pub fn quote_composite_field(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value
+            .chars()
+            .any(|c| matches!(c, '(' | ')' | ',' | '"' | '\\') || c.is_ascii_whitespace());
+
+    if !needs_quotes {
+        return value.to_string();
+    }
+
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        if c == '"' || c == '\\' {
+            out.push(c);
+        }
+        out.push(c);
+    }
+    out.push('"');
+    out
+}
+
+/// Check whether a column type needs structured decoding.
+#[cfg(feature = "persistence")]
+pub fn is_structured_type(ty: &Type) -> bool {
+    match ty.kind() {
+        Kind::Array(_) | Kind::Composite(_) => true,
+        Kind::Domain(inner) => is_structured_type(inner),
+        _ => false,
+    }
+}
+
+#[cfg(feature = "persistence")]
+use tusk_core::models::value::DecodeError;
+
+#[cfg(feature = "persistence")]
+impl<'a> FromSql<'a> for CellValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Self, DecodeError> {
+        decode_value(ty, raw)
+    }
+
+    fn from_sql_null(_ty: &Type) -> Result<Self, DecodeError> {
+        Ok(Self::Null)
+    }
+
+    fn accepts(_ty: &Type) -> bool {
+        true
+    }
+}
+
+/// Decode a binary value, dispatching on the type's kind.
+#[cfg(feature = "persistence")]
+fn decode_value(ty: &Type, raw: &[u8]) -> Result<CellValue, DecodeError> {
+    match ty.kind() {
+        Kind::Array(member) => decode_array(member, raw),
+        Kind::Composite(fields) => {
+            let field_types: Vec<Type> = fields.iter().map(|f| f.type_().clone()).collect();
+            decode_composite(&field_types, raw)
+        }
+        Kind::Domain(inner) => decode_value(inner, raw),
+        _ => decode_scalar(ty, raw),
+    }
+}
+
+/// Decode a scalar value into its display text.
+///
+/// Booleans use psql's `t`/`f` inside arrays and composites. Types without a
+/// text decoder are shown as `<type>`.
+#[cfg(feature = "persistence")]
+fn decode_scalar(ty: &Type, raw: &[u8]) -> Result<CellValue, DecodeError> {
+    let text = match *ty {
+        Type::BOOL => if bool::from_sql(ty, raw)? { "t" } else { "f" }.to_string(),
+        _ => scalar_text(ty, raw)?.unwrap_or_else(|| format!("<{}>", ty.name())),
+    };
+    Ok(CellValue::Scalar(text))
+}
+
+/// Decode a binary array: header, dimensions, then elements in row-major order.
+#[cfg(feature = "persistence")]
+fn decode_array(member: &Type, raw: &[u8]) -> Result<CellValue, DecodeError> {
+    let mut reader = BinaryReader::new(raw);
+    let ndim = reader.read_i32()?;
+    let _has_nulls = reader.read_i32()?;
+    let _element_oid = reader.read_u32()?;
+
+    if ndim <= 0 {
+        return Ok(CellValue::Array(Vec::new()));
+    }
+
+    let mut dims = Vec::with_capacity(ndim as usize);
+    for _ in 0..ndim {
+        let len = reader.read_i32()?;
+        let _lower_bound = reader.read_i32()?;
+        dims.push(usize::try_from(len).map_err(|_| "invalid array dimension")?);
+    }
+
+    let total: usize = dims.iter().product();
+    let mut elements = Vec::with_capacity(total);
+    for _ in 0..total {
+        elements.push(match reader.read_value()? {
+            Some(bytes) => decode_value(member, bytes)?,
+            None => CellValue::Null,
+        });
+    }
+
+    let mut elements = elements.into_iter();
+    Ok(nest_dimensions(&dims, &mut elements))
+}
+
+/// Split a flat element list into nested arrays, one level per dimension.
+#[cfg(feature = "persistence")]
+fn nest_dimensions(dims: &[usize], elements: &mut impl Iterator<Item = CellValue>) -> CellValue {
+    match dims {
+        [] => CellValue::Array(Vec::new()),
+        [len] => CellValue::Array(elements.take(*len).collect()),
+        [len, rest @ ..] => {
+            CellValue::Array((0..*len).map(|_| nest_dimensions(rest, elements)).collect())
+        }
+    }
+}
+
+/// Decode a binary composite: field count, then (oid, length, bytes) per field.
+#[cfg(feature = "persistence")]
+fn decode_composite(field_types: &[Type], raw: &[u8]) -> Result<CellValue, DecodeError> {
+    let mut reader = BinaryReader::new(raw);
+    let count = reader.read_i32()?;
+
+    let mut fields = Vec::with_capacity(count.max(0) as usize);
+    for i in 0..count.max(0) as usize {
+        let oid = reader.read_u32()?;
+        let value = reader.read_value()?;
+        let field = match value {
+            None => CellValue::Null,
+            Some(bytes) => match field_types.get(i).cloned().or_else(|| Type::from_oid(oid)) {
+                Some(ty) => decode_value(&ty, bytes)?,
+                None => CellValue::Scalar(format!("<oid {oid}>")),
+            },
+        };
+        fields.push(field);
+    }
+
+    Ok(CellValue::Composite(fields))
+}
+
+/// Minimal cursor over PostgreSQL binary wire data.
+#[cfg(feature = "persistence")]
+struct BinaryReader<'a> {
+    buf: &'a [u8],
+}
+
+#[cfg(feature = "persistence")]
+impl<'a> BinaryReader<'a> {
+    fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.buf.len() < len {
+            return Err("unexpected end of binary value".into());
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn read_i32(&mut self) -> Result<i32, DecodeError> {
+        let bytes = self.take(4)?;
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn read_u32(&mut self) -> Result<u32, DecodeError> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Read a length-prefixed value; a length of -1 denotes NULL.
+    fn read_value(&mut self) -> Result<Option<&'a [u8]>, DecodeError> {
+        let len = self.read_i32()?;
+        if len < 0 {
+            return Ok(None);
+        }
+        self.take(len as usize).map(Some)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_array(values: &[Option<&str>]) -> CellValue {
+        CellValue::Array(
+            values.iter().map(|v| v.map(CellValue::scalar).unwrap_or(CellValue::Null)).collect(),
+        )
+    }
+
+    #[test]
+    fn test_format_simple_array() {
+        let value = CellValue::Array(vec![
+            CellValue::scalar("1"),
+            CellValue::scalar("2"),
+            CellValue::scalar("3"),
+        ]);
+        assert_eq!(value.to_pg_literal(), "{1,2,3}");
+        assert_eq!(CellValue::Array(Vec::new()).to_pg_literal(), "{}");
+    }
+
+    #[test]
+    fn test_format_text_array_escaping() {
+        let value = text_array(&[
+            Some("plain"),
+            Some("a,b"),
+            Some("say \"hi\""),
+            Some("back\\slash"),
+            Some("two words"),
+            Some(""),
+            Some("null"),
+            None,
+        ]);
+        assert_eq!(
+            value.to_pg_literal(),
+            r#"{plain,"a,b","say \"hi\"","back\\slash","two words","","null",NULL}"#
+        );
+    }
+
+    #[test]
+    fn test_format_nested_array() {
+        let value = CellValue::Array(vec![
+            text_array(&[Some("1"), Some("2")]),
+            text_array(&[Some("{x}"), None]),
+        ]);
+        assert_eq!(value.to_pg_literal(), r#"{{1,2},{"{x}",NULL}}"#);
+    }
+
+    #[test]
+    fn test_format_composite() {
+        let value = CellValue::Composite(vec![
+            CellValue::scalar("42"),
+            CellValue::scalar("hello world"),
+            CellValue::Null,
+            CellValue::scalar("a\"b"),
+            CellValue::scalar(""),
+        ]);
+        assert_eq!(value.to_pg_literal(), r#"(42,"hello world",,"a""b","")"#);
+    }
+
+    #[test]
+    fn test_format_composite_inside_array() {
+        let value = CellValue::Array(vec![
+            CellValue::Composite(vec![CellValue::scalar("1"), CellValue::scalar("x")]),
+            CellValue::Composite(vec![CellValue::scalar("2"), CellValue::Null]),
+        ]);
+        assert_eq!(value.to_pg_literal(), r#"{"(1,x)","(2,)"}"#);
+    }
+
+    #[test]
+    fn test_format_array_inside_composite() {
+        let value = CellValue::Composite(vec![
+            CellValue::scalar("1"),
+            text_array(&[Some("a"), Some("b c")]),
+        ]);
+        assert_eq!(value.to_pg_literal(), r#"(1,"{a,""b c""}")"#);
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_decode_typed_array_elements() {
+        // uuid[] with one element and a NULL: header, one dimension, elements
+        let mut raw = Vec::new();
+        for word in [1i32, 1, Type::UUID.oid() as i32, 2, 1] {
+            raw.extend_from_slice(&word.to_be_bytes());
+        }
+        raw.extend_from_slice(&16i32.to_be_bytes());
+        raw.extend(0u8..16);
+        raw.extend_from_slice(&(-1i32).to_be_bytes());
+
+        let value = CellValue::from_sql(&Type::UUID_ARRAY, &raw).unwrap();
+        assert_eq!(value.to_pg_literal(), "{00010203-0405-0607-0809-0a0b0c0d0e0f,NULL}");
+
+        // jsonb[] elements lose their version byte and are quoted like text
+        let mut raw = Vec::new();
+        for word in [1i32, 0, Type::JSONB.oid() as i32, 1, 1] {
+            raw.extend_from_slice(&word.to_be_bytes());
+        }
+        let element = b"\x01{\"a\": 1}";
+        raw.extend_from_slice(&(element.len() as i32).to_be_bytes());
+        raw.extend_from_slice(element);
+
+        let value = CellValue::from_sql(&Type::JSONB_ARRAY, &raw).unwrap();
+        assert_eq!(value.to_pg_literal(), r#"{"{\"a\": 1}"}"#);
+    }
+}
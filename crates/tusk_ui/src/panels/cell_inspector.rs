@@ -0,0 +1,505 @@
+//! Popover for reading one result cell in full.
+//!
+//! Grid cells show a single truncated line, which hides most of a wide JSON
+//! document or a long text value. The inspector shows the whole value:
+//! `json`/`jsonb` values pretty-printed with syntax colors, anything else as
+//! wrapped monospace text. Values are laid out into fixed-width rows in the
+//! background and only the rows in view are rendered, so multi-megabyte
+//! values open without stalling the UI.
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use gpui::{
+    div, prelude::*, px, uniform_list, App, Context, EventEmitter, FocusHandle, Hsla, MouseButton,
+    MouseDownEvent, Render, SharedString, Task, UniformListScrollHandle, Window,
+};
+
+use super::json_view::{pretty_json, INDENT};
+use super::schema_browser::format_bytes;
+use crate::icon::{Icon, IconName, IconSize};
+use crate::key_bindings::modal;
+use crate::layout::spacing;
+use crate::panel::Focusable;
+use crate::spinner::{Spinner, SpinnerSize};
+use crate::theme::ThemeColors;
+use crate::tooltip::Tooltip;
+use crate::TuskTheme;
+
+/// Characters per display row before a line wraps.
+const WRAP_COLUMNS: usize = 100;
+
+/// Syntax class of a span of inspected text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenKind {
+    /// Object member name
+    Key,
+    /// String value
+    String,
+    /// Number
+    Number,
+    /// `true`, `false`, or `null`
+    Literal,
+    /// Brackets, commas, and colons
+    Punctuation,
+    /// Text that isn't JSON
+    Plain,
+}
+
+impl TokenKind {
+    fn color(&self, colors: &ThemeColors) -> Hsla {
+        match self {
+            Self::Key => colors.text_accent,
+            Self::String => colors.status_success,
+            Self::Number => colors.status_warning,
+            Self::Literal => colors.status_info,
+            Self::Punctuation => colors.text_muted,
+            Self::Plain => colors.text,
+        }
+    }
+}
+
+/// A run of text with one syntax class.
+type Span = (TokenKind, String);
+
+/// One display row of the inspected value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct InspectorRow {
+    /// JSON nesting depth (0 for plain text).
+    depth: usize,
+    spans: Vec<Span>,
+}
+
+/// Events emitted by the cell inspector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CellInspectorEvent {
+    /// The inspector was closed.
+    Dismissed,
+}
+
+/// Popover showing the full value of one cell.
+pub struct CellInspector {
+    focus_handle: FocusHandle,
+    /// Column name
+    column: SharedString,
+    /// PostgreSQL type name of the column
+    type_name: String,
+    /// The cell value as shown in the grid
+    value: Arc<str>,
+    /// Display rows; None while the value is laid out.
+    rows: Option<Vec<InspectorRow>>,
+    /// Whether the rows are highlighted JSON.
+    is_json: bool,
+    scroll_handle: UniformListScrollHandle,
+    _layout_task: Task<()>,
+}
+
+impl CellInspector {
+    /// Create an inspector for a cell of a column with the given type.
+    ///
+    /// Values of `json`/`jsonb` columns are shown as JSON when they parse.
+    pub fn new(
+        column: impl Into<SharedString>,
+        type_name: impl Into<String>,
+        value: impl Into<Arc<str>>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let type_name = type_name.into();
+        let value: Arc<str> = value.into();
+        let json = matches!(type_name.as_str(), "json" | "jsonb");
+
+        let text = value.clone();
+        let layout_task = cx.spawn(async move |this, cx| {
+            let (rows, is_json) =
+                cx.background_executor().spawn(async move { layout_rows(&text, json) }).await;
+            let _ = this.update(cx, |inspector, cx| {
+                inspector.rows = Some(rows);
+                inspector.is_json = is_json;
+                cx.notify();
+            });
+        });
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            column: column.into(),
+            type_name,
+            value,
+            rows: None,
+            is_json: false,
+            scroll_handle: UniformListScrollHandle::new(),
+            _layout_task: layout_task,
+        }
+    }
+
+    /// Copy the value to the clipboard as it appears in the grid.
+    pub fn copy_value(&self, cx: &mut App) {
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(self.value.to_string()));
+    }
+
+    /// Close the inspector.
+    pub fn dismiss(&mut self, cx: &mut Context<Self>) {
+        cx.emit(CellInspectorEvent::Dismissed);
+    }
+
+    /// Type, size, and format shown under the column name.
+    fn subtitle(&self) -> String {
+        let mut subtitle =
+            format!("{} • {}", self.type_name, format_bytes(self.value.len() as i64));
+        if let Some(rows) = &self.rows {
+            subtitle.push_str(&format!(
+                " • {} line{}",
+                rows.len(),
+                if rows.len() == 1 { "" } else { "s" }
+            ));
+        }
+        subtitle
+    }
+
+    fn render_rows(
+        &self,
+        range: Range<usize>,
+        colors: &ThemeColors,
+    ) -> Vec<gpui::Stateful<gpui::Div>> {
+        let Some(rows) = &self.rows else {
+            return Vec::new();
+        };
+        range
+            .filter_map(|index| Some((index, rows.get(index)?)))
+            .map(|(index, row)| {
+                div()
+                    .id(("cell-inspector-row", index))
+                    .flex()
+                    .pl(px((row.depth * 16) as f32))
+                    .children(row.spans.iter().map(|(kind, text)| {
+                        div().text_color(kind.color(colors)).child(text.clone())
+                    }))
+            })
+            .collect()
+    }
+}
+
+/// Lay out a value as display rows, returning whether it was shown as JSON.
+///
+/// JSON is pretty-printed and highlighted when `json` is set and the value
+/// parses; everything else is split into plain lines. Rows longer than
+/// [`WRAP_COLUMNS`] characters wrap.
+fn layout_rows(text: &str, json: bool) -> (Vec<InspectorRow>, bool) {
+    let pretty = if json {
+        pretty_json(text).or_else(|| {
+            // Scalars (`42`, `"text"`) are valid JSON that pretty_json leaves alone
+            serde_json::from_str::<serde::de::IgnoredAny>(text)
+                .is_ok()
+                .then(|| text.trim().to_string())
+        })
+    } else {
+        None
+    };
+
+    let mut rows = Vec::new();
+    match &pretty {
+        Some(pretty) => {
+            for line in pretty.lines() {
+                let content = line.trim_start();
+                let depth = (line.len() - content.len()) / INDENT;
+                for spans in wrap_spans(highlight_json(content), WRAP_COLUMNS) {
+                    rows.push(InspectorRow { depth, spans });
+                }
+            }
+        }
+        None => {
+            for line in text.lines() {
+                let line = line.replace('\t', "    ");
+                for spans in wrap_spans(vec![(TokenKind::Plain, line)], WRAP_COLUMNS) {
+                    rows.push(InspectorRow { depth: 0, spans });
+                }
+            }
+        }
+    }
+    (rows, pretty.is_some())
+}
+
+/// Split one line of pretty-printed JSON into highlighted spans.
+fn highlight_json(line: &str) -> Vec<Span> {
+    let mut spans: Vec<Span> = Vec::new();
+    let mut rest = line;
+    while let Some(c) = rest.chars().next() {
+        let (kind, len) = match c {
+            '"' => {
+                let len = string_len(rest);
+                // A string followed by a colon names an object member
+                let kind = if rest[len..].trim_start().starts_with(':') {
+                    TokenKind::Key
+                } else {
+                    TokenKind::String
+                };
+                (kind, len)
+            }
+            '-' | '0'..='9' => {
+                let len = rest
+                    .find(|c: char| {
+                        !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'))
+                    })
+                    .unwrap_or(rest.len());
+                (TokenKind::Number, len)
+            }
+            c if c.is_ascii_alphabetic() => {
+                let len = rest.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(rest.len());
+                (TokenKind::Literal, len)
+            }
+            c => (TokenKind::Punctuation, c.len_utf8()),
+        };
+        match spans.last_mut() {
+            Some((last, text)) if *last == kind => text.push_str(&rest[..len]),
+            _ => spans.push((kind, rest[..len].to_string())),
+        }
+        rest = &rest[len..];
+    }
+    spans
+}
+
+/// Byte length of the JSON string starting at the beginning of `text`,
+/// including both quotes (or the rest of the text if it is unterminated).
+fn string_len(text: &str) -> usize {
+    let mut escaped = false;
+    for (index, c) in text.char_indices().skip(1) {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return index + 1,
+            _ => {}
+        }
+    }
+    text.len()
+}
+
+/// Break spans into rows of at most `width` characters.
+fn wrap_spans(spans: Vec<Span>, width: usize) -> Vec<Vec<Span>> {
+    let mut rows: Vec<Vec<Span>> = vec![Vec::new()];
+    let mut used = 0;
+    for (kind, text) in spans {
+        let mut rest = text.as_str();
+        while !rest.is_empty() {
+            if used == width {
+                rows.push(Vec::new());
+                used = 0;
+            }
+            let split = rest.char_indices().nth(width - used).map_or(rest.len(), |(i, _)| i);
+            let (head, tail) = rest.split_at(split);
+            used += head.chars().count();
+            if let Some(row) = rows.last_mut() {
+                row.push((kind, head.to_string()));
+            }
+            rest = tail;
+        }
+    }
+    rows
+}
+
+impl EventEmitter<CellInspectorEvent> for CellInspector {}
+
+impl Focusable for CellInspector {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for CellInspector {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+        let hover_bg = theme.colors.element_hover;
+        let row_count = self.rows.as_ref().map_or(0, Vec::len);
+
+        let header_button = |id: &'static str, icon: IconName, tooltip: &'static str| {
+            div()
+                .id(id)
+                .p(px(2.0))
+                .rounded(px(4.0))
+                .cursor_pointer()
+                .hover(move |style| style.bg(hover_bg))
+                .tooltip(Tooltip::text(tooltip))
+                .child(Icon::new(icon).size(IconSize::Small).color(theme.colors.text_muted))
+        };
+
+        let body = match &self.rows {
+            None => div()
+                .flex_1()
+                .flex()
+                .items_center()
+                .justify_center()
+                .gap(spacing::SM)
+                .child(Spinner::new().size(SpinnerSize::Small))
+                .child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(theme.colors.text_muted)
+                        .child("Formatting value…"),
+                )
+                .into_any_element(),
+            Some(_) => {
+                let colors = theme.colors.clone();
+                uniform_list(
+                    "cell-inspector-rows",
+                    row_count,
+                    cx.processor(move |this, range: Range<usize>, _window, _cx| {
+                        this.render_rows(range, &colors)
+                    }),
+                )
+                .flex_1()
+                .px(spacing::SM)
+                .py(spacing::XS)
+                .font_family("monospace")
+                .text_size(px(12.0))
+                .track_scroll(&self.scroll_handle)
+                .into_any_element()
+            }
+        };
+
+        div()
+            .id("cell-inspector")
+            .key_context("Modal")
+            .track_focus(&self.focus_handle)
+            .w(px(800.0))
+            .h(px(420.0))
+            .flex()
+            .flex_col()
+            .overflow_hidden()
+            .bg(theme.colors.elevated_surface_background)
+            .border_1()
+            .border_color(theme.colors.border)
+            .rounded(px(8.0))
+            .shadow_lg()
+            .on_mouse_down_out(cx.listener(|this, _: &MouseDownEvent, _window, cx| {
+                this.dismiss(cx);
+            }))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _: &MouseDownEvent, window, cx| {
+                    window.focus(&this.focus_handle, cx);
+                }),
+            )
+            .on_action(cx.listener(|this, _: &modal::Dismiss, _window, cx| {
+                this.dismiss(cx);
+            }))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(spacing::SM)
+                    .px(spacing::MD)
+                    .py(spacing::SM)
+                    .border_b_1()
+                    .border_color(theme.colors.border)
+                    .child(
+                        div()
+                            .flex_1()
+                            .flex()
+                            .flex_col()
+                            .overflow_hidden()
+                            .child(
+                                div()
+                                    .text_size(px(13.0))
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(theme.colors.text)
+                                    .truncate()
+                                    .child(self.column.clone()),
+                            )
+                            .child(
+                                div()
+                                    .text_size(px(11.0))
+                                    .text_color(theme.colors.text_muted)
+                                    .child(self.subtitle()),
+                            ),
+                    )
+                    .child(
+                        header_button("cell-inspector-copy", IconName::Copy, "Copy value")
+                            .on_click(cx.listener(|this, _, _window, cx| {
+                                this.copy_value(cx);
+                            })),
+                    )
+                    .child(
+                        header_button("cell-inspector-close", IconName::Close, "Close").on_click(
+                            cx.listener(|this, _, _window, cx| {
+                                this.dismiss(cx);
+                            }),
+                        ),
+                    ),
+            )
+            .child(body)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(rows: &[InspectorRow]) -> Vec<String> {
+        rows.iter()
+            .map(|row| {
+                let text: String = row.spans.iter().map(|(_, text)| text.as_str()).collect();
+                format!("{}{}", " ".repeat(row.depth * INDENT), text)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_highlight_json_classifies_tokens() {
+        use TokenKind::*;
+        assert_eq!(
+            highlight_json(r#""na\"me": "x:y","#),
+            [
+                (Key, r#""na\"me""#.to_string()),
+                (Punctuation, ": ".to_string()),
+                (String, r#""x:y""#.to_string()),
+                (Punctuation, ",".to_string()),
+            ]
+        );
+        assert_eq!(
+            highlight_json(r#""n": -1.5e3, "ok": null"#),
+            [
+                (Key, r#""n""#.to_string()),
+                (Punctuation, ": ".to_string()),
+                (Number, "-1.5e3".to_string()),
+                (Punctuation, ", ".to_string()),
+                (Key, r#""ok""#.to_string()),
+                (Punctuation, ": ".to_string()),
+                (Literal, "null".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_json_values_are_pretty_printed() {
+        let (rows, is_json) = layout_rows(r#"{"a":[1,true],"b":{}}"#, true);
+        assert!(is_json);
+        assert_eq!(
+            texts(&rows),
+            ["{", "  \"a\": [", "    1,", "    true", "  ],", "  \"b\": {}", "}"]
+        );
+
+        // Scalars are JSON too; text columns are never highlighted
+        assert!(layout_rows(r#""just text""#, true).1);
+        let (rows, is_json) = layout_rows(r#"{"a":1}"#, false);
+        assert!(!is_json);
+        assert_eq!(rows[0].spans, [(TokenKind::Plain, r#"{"a":1}"#.to_string())]);
+        // Invalid JSON falls back to text
+        assert!(!layout_rows("{oops", true).1);
+    }
+
+    #[test]
+    fn test_long_lines_wrap() {
+        let text = format!("{}\n\tshort", "é".repeat(WRAP_COLUMNS * 2 + 5));
+        let (rows, _) = layout_rows(&text, false);
+        let lengths: Vec<usize> = texts(&rows).iter().map(|t| t.chars().count()).collect();
+        assert_eq!(lengths, [WRAP_COLUMNS, WRAP_COLUMNS, 5, 9]);
+
+        let spans =
+            vec![(TokenKind::Key, "abc".to_string()), (TokenKind::String, "defg".to_string())];
+        assert_eq!(
+            wrap_spans(spans, 5),
+            [
+                vec![(TokenKind::Key, "abc".to_string()), (TokenKind::String, "de".to_string())],
+                vec![(TokenKind::String, "fg".to_string())],
+            ]
+        );
+    }
+}
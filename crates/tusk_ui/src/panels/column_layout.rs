@@ -0,0 +1,182 @@
+//! Column order and visibility of a result grid.
+//!
+//! A layout is built for one set of column names and maps display positions
+//! to source column indices. Hidden columns keep their place in the order so
+//! showing them again restores the arrangement. A layout whose column names
+//! no longer match the displayed result is discarded in favour of a fresh one.
+
+/// Order and visibility of the columns of one result.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ColumnLayout {
+    /// Column names the layout was built for
+    names: Vec<String>,
+    /// Source column indices in display order, hidden ones included
+    order: Vec<usize>,
+    /// Whether each source column is hidden
+    hidden: Vec<bool>,
+}
+
+impl ColumnLayout {
+    /// Create the default layout: every column shown in source order.
+    pub fn new<S: AsRef<str>>(names: &[S]) -> Self {
+        Self {
+            names: names.iter().map(|n| n.as_ref().to_string()).collect(),
+            order: (0..names.len()).collect(),
+            hidden: vec![false; names.len()],
+        }
+    }
+
+    /// Check if the layout was built for these column names.
+    pub fn matches<S: AsRef<str>>(&self, names: &[S]) -> bool {
+        self.names.len() == names.len()
+            && self.names.iter().zip(names).all(|(a, b)| a.as_str() == b.as_ref())
+    }
+
+    /// Check if the layout shows every column in source order.
+    pub fn is_default(&self) -> bool {
+        self.hidden_count() == 0 && self.order.iter().enumerate().all(|(i, &c)| i == c)
+    }
+
+    /// Source indices of the visible columns, in display order.
+    pub fn visible(&self) -> Vec<usize> {
+        self.order.iter().copied().filter(|&c| !self.hidden[c]).collect()
+    }
+
+    /// Number of hidden columns.
+    pub fn hidden_count(&self) -> usize {
+        self.hidden.iter().filter(|&&h| h).count()
+    }
+
+    /// Hide a source column.
+    ///
+    /// The last visible column cannot be hidden. Returns whether the layout changed.
+    pub fn hide(&mut self, col: usize) -> bool {
+        if col >= self.hidden.len() || self.hidden[col] || self.visible().len() <= 1 {
+            return false;
+        }
+        self.hidden[col] = true;
+        true
+    }
+
+    /// Show every hidden column in its place in the order.
+    pub fn show_all(&mut self) {
+        self.hidden.iter_mut().for_each(|h| *h = false);
+    }
+
+    /// Restore source order with every column shown.
+    ///
+    /// Returns whether the layout changed.
+    pub fn reset(&mut self) -> bool {
+        if self.is_default() {
+            return false;
+        }
+        self.order = (0..self.names.len()).collect();
+        self.show_all();
+        true
+    }
+
+    /// Move a source column to a position among the visible columns.
+    ///
+    /// `to` is clamped to the last visible position. Returns whether the
+    /// layout changed.
+    pub fn move_to(&mut self, col: usize, to: usize) -> bool {
+        let visible = self.visible();
+        let Some(from) = visible.iter().position(|&c| c == col) else {
+            return false;
+        };
+        let to = to.min(visible.len() - 1);
+        if from == to {
+            return false;
+        }
+        let target = visible[to];
+        self.order.retain(|&c| c != col);
+        let at = self.order.iter().position(|&c| c == target).unwrap_or(self.order.len());
+        // Land after the target when moving right so it ends up at `to`.
+        let at = if to > from { at + 1 } else { at };
+        self.order.insert(at, col);
+        true
+    }
+
+    /// Move a source column one visible position left (`-1`) or right (`1`).
+    pub fn shift(&mut self, col: usize, delta: isize) -> bool {
+        let Some(from) = self.visible().iter().position(|&c| c == col) else {
+            return false;
+        };
+        match from.checked_add_signed(delta) {
+            Some(to) => self.move_to(col, to),
+            None => false,
+        }
+    }
+
+    /// Pick the visible cells of a row, in display order.
+    pub fn project<T: Clone>(&self, cells: &[T]) -> Vec<T> {
+        self.visible().into_iter().filter_map(|c| cells.get(c).cloned()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_layout_keeps_source_order() {
+        let layout = ColumnLayout::new(&["id", "name", "email"]);
+        assert!(layout.is_default());
+        assert_eq!(layout.visible(), vec![0, 1, 2]);
+        assert_eq!(
+            layout.project(&["1", "ada", "ada@example.com"]),
+            ["1", "ada", "ada@example.com"]
+        );
+    }
+
+    #[test]
+    fn test_hide_and_move_apply_to_rows() {
+        let mut layout = ColumnLayout::new(&["id", "name", "email", "created"]);
+        assert!(layout.hide(1));
+        assert!(layout.move_to(3, 0));
+        assert_eq!(layout.visible(), vec![3, 0, 2]);
+        assert_eq!(layout.project(&["1", "ada", "a@x", "2024"]), ["2024", "1", "a@x"]);
+
+        // Moving right lands on the requested visible position.
+        assert!(layout.move_to(3, 2));
+        assert_eq!(layout.visible(), vec![0, 2, 3]);
+
+        // Hidden columns come back in their place in the order.
+        layout.show_all();
+        assert_eq!(layout.visible(), vec![0, 1, 2, 3]);
+        assert!(layout.is_default());
+
+        assert!(layout.move_to(0, 3));
+        assert_eq!(layout.visible(), vec![1, 2, 3, 0]);
+        assert!(layout.reset());
+        assert!(layout.is_default());
+        assert!(!layout.reset());
+    }
+
+    #[test]
+    fn test_shift_stops_at_edges() {
+        let mut layout = ColumnLayout::new(&["a", "b", "c"]);
+        assert!(!layout.shift(0, -1));
+        assert!(!layout.shift(2, 1));
+        assert!(layout.shift(0, 1));
+        assert_eq!(layout.visible(), vec![1, 0, 2]);
+    }
+
+    #[test]
+    fn test_last_visible_column_cannot_be_hidden() {
+        let mut layout = ColumnLayout::new(&["a", "b"]);
+        assert!(layout.hide(0));
+        assert!(!layout.hide(1));
+        assert!(!layout.hide(0));
+        assert_eq!(layout.visible(), vec![1]);
+        assert_eq!(layout.hidden_count(), 1);
+    }
+
+    #[test]
+    fn test_matches_column_names() {
+        let layout = ColumnLayout::new(&["id", "name"]);
+        assert!(layout.matches(&["id", "name"]));
+        assert!(!layout.matches(&["id"]));
+        assert!(!layout.matches(&["name", "id"]));
+    }
+}
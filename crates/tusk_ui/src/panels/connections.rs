@@ -0,0 +1,419 @@
+//! Connections panel listing the open database connections.
+//!
+//! Each row shows a connection's status and its pool's health and in-use
+//! count, with a button to disconnect it. The header closes every
+//! connection at once. Rows are re-read from `TuskState` periodically so
+//! pool counts stay current.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use gpui::{
+    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, Hsla, Render, SharedString, Task,
+    Window,
+};
+use tusk_core::{ConnectionStatus, PoolStatus, TuskState};
+use uuid::Uuid;
+
+use crate::icon::{Icon, IconName, IconSize};
+use crate::panel::{DockPosition, Focusable, Panel, PanelEvent};
+use crate::tooltip::Tooltip;
+use crate::TuskTheme;
+
+/// How often rows are re-read from state.
+const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// One open connection as shown in the panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionRow {
+    /// Connection ID
+    pub id: Uuid,
+    /// Connection name
+    pub name: String,
+    /// Connection status
+    pub status: ConnectionStatus,
+    /// Pool status, if the connection has a pool
+    pub pool: Option<PoolStatus>,
+}
+
+impl ConnectionRow {
+    /// Build rows from active connections and their pool statuses.
+    ///
+    /// Rows are sorted by name, case-insensitively, so the list does not
+    /// reshuffle as the underlying map changes.
+    pub fn collect(
+        connections: Vec<(Uuid, String, ConnectionStatus)>,
+        pool_statuses: &HashMap<Uuid, PoolStatus>,
+    ) -> Vec<Self> {
+        let mut rows: Vec<Self> = connections
+            .into_iter()
+            .map(|(id, name, status)| Self {
+                id,
+                name,
+                status,
+                pool: pool_statuses.get(&id).copied(),
+            })
+            .collect();
+        rows.sort_by(|a, b| {
+            a.name.to_lowercase().cmp(&b.name.to_lowercase()).then_with(|| a.id.cmp(&b.id))
+        });
+        rows
+    }
+
+    /// Check if the connection is usable without contention.
+    pub fn is_healthy(&self) -> bool {
+        self.status.is_connected() && self.pool.is_some_and(|pool| pool.is_healthy())
+    }
+
+    /// Short status: the connection's state, or its pool's health once connected.
+    pub fn health_label(&self) -> String {
+        match (&self.status, self.pool) {
+            (ConnectionStatus::Connected, Some(pool)) if pool.waiting > 0 => {
+                format!("Busy, {} waiting", pool.waiting)
+            }
+            (ConnectionStatus::Connected, Some(_)) => "Healthy".to_string(),
+            (ConnectionStatus::Connected, None) => "Connected".to_string(),
+            (ConnectionStatus::Connecting, _) => "Connecting".to_string(),
+            (ConnectionStatus::Disconnected, _) => "Disconnected".to_string(),
+            (ConnectionStatus::Error { message, .. }, _) => format!("Error: {message}"),
+        }
+    }
+
+    /// Pool usage such as "1 of 4 in use", if the connection has a pool.
+    pub fn usage_label(&self) -> Option<String> {
+        self.pool.map(|pool| format!("{} of {} in use", pool.active(), pool.max_size))
+    }
+
+    /// Theme color for the status dot.
+    fn health_color(&self, theme: &TuskTheme) -> Hsla {
+        if self.status.is_error() {
+            theme.colors.error
+        } else if self.is_healthy() {
+            theme.colors.success
+        } else if self.status.is_connected() || self.status.is_connecting() {
+            theme.colors.warning
+        } else {
+            theme.colors.text_muted
+        }
+    }
+}
+
+/// Events emitted by the connections panel.
+#[derive(Debug, Clone)]
+pub enum ConnectionsPanelEvent {
+    /// Connections were closed from the panel.
+    Disconnected {
+        /// IDs of the closed connections
+        connection_ids: Vec<Uuid>,
+    },
+}
+
+/// Panel listing open connections with per-connection and global disconnect.
+pub struct ConnectionsPanel {
+    focus_handle: FocusHandle,
+    /// Open connections, sorted by name.
+    rows: Vec<ConnectionRow>,
+    /// Periodic refresh of the rows.
+    _refresh: Task<()>,
+}
+
+impl ConnectionsPanel {
+    /// Create a new connections panel.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let refresh = cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(REFRESH_INTERVAL).await;
+            if this.update(cx, |panel, cx| panel.refresh(cx)).is_err() {
+                break;
+            }
+        });
+        let mut panel =
+            Self { focus_handle: cx.focus_handle(), rows: Vec::new(), _refresh: refresh };
+        panel.refresh(cx);
+        panel
+    }
+
+    /// Get the rows currently shown.
+    pub fn rows(&self) -> &[ConnectionRow] {
+        &self.rows
+    }
+
+    /// Re-read the open connections from state.
+    pub fn refresh(&mut self, cx: &mut Context<Self>) {
+        let rows = match cx.try_global::<TuskState>() {
+            Some(state) => {
+                ConnectionRow::collect(state.all_connections(), &state.all_pool_statuses())
+            }
+            None => Vec::new(),
+        };
+        if rows != self.rows {
+            self.rows = rows;
+            cx.notify();
+        }
+    }
+
+    /// Close one connection.
+    pub fn disconnect(&mut self, connection_id: Uuid, cx: &mut Context<Self>) {
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        match state.close_connection(connection_id) {
+            Ok(()) => {
+                cx.emit(ConnectionsPanelEvent::Disconnected {
+                    connection_ids: vec![connection_id],
+                });
+            }
+            Err(e) => {
+                tracing::warn!(connection_id = %connection_id, error = %e, "Failed to disconnect");
+            }
+        }
+        self.refresh(cx);
+    }
+
+    /// Close every open connection.
+    pub fn disconnect_all(&mut self, cx: &mut Context<Self>) {
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let connection_ids = state.disconnect_all();
+        if !connection_ids.is_empty() {
+            cx.emit(ConnectionsPanelEvent::Disconnected { connection_ids });
+        }
+        self.refresh(cx);
+    }
+
+    /// Render one connection row.
+    fn render_row(
+        &self,
+        row: &ConnectionRow,
+        theme: &TuskTheme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let id = row.id;
+        let hover_bg = theme.colors.element_hover;
+        let detail = match row.usage_label() {
+            Some(usage) => format!("{} · {usage}", row.health_label()),
+            None => row.health_label(),
+        };
+
+        div()
+            .id(SharedString::from(format!("connection-row-{id}")))
+            .flex()
+            .items_center()
+            .gap(px(8.0))
+            .px(px(12.0))
+            .py(px(6.0))
+            .border_b_1()
+            .border_color(theme.colors.border_variant)
+            .hover(|style| style.bg(hover_bg))
+            .child(div().size(px(8.0)).rounded_full().bg(row.health_color(theme)))
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .flex()
+                    .flex_col()
+                    .gap(px(2.0))
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(theme.colors.text)
+                            .truncate()
+                            .child(row.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.text_muted)
+                            .truncate()
+                            .child(detail),
+                    ),
+            )
+            .child(
+                div()
+                    .id(SharedString::from(format!("connection-disconnect-{id}")))
+                    .p(px(2.0))
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .hover(|style| style.bg(hover_bg))
+                    .tooltip(Tooltip::text("Disconnect"))
+                    .on_click(cx.listener(move |panel, _, _, cx| panel.disconnect(id, cx)))
+                    .child(
+                        Icon::new(IconName::Disconnected)
+                            .size(IconSize::XSmall)
+                            .color(theme.colors.text_muted),
+                    ),
+            )
+    }
+
+    /// Render the empty state.
+    fn render_empty_state(&self, theme: &TuskTheme) -> impl IntoElement {
+        div()
+            .size_full()
+            .flex()
+            .items_center()
+            .justify_center()
+            .p(px(16.0))
+            .text_size(px(12.0))
+            .text_color(theme.colors.text_muted)
+            .child("No open connections")
+    }
+}
+
+impl EventEmitter<PanelEvent> for ConnectionsPanel {}
+impl EventEmitter<ConnectionsPanelEvent> for ConnectionsPanel {}
+
+impl Focusable for ConnectionsPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for ConnectionsPanel {
+    fn panel_id(&self) -> &'static str {
+        "connections"
+    }
+
+    fn title(&self, _cx: &App) -> SharedString {
+        "Connections".into()
+    }
+
+    fn icon(&self, _cx: &App) -> IconName {
+        IconName::Database
+    }
+
+    fn focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn position(&self, _cx: &App) -> DockPosition {
+        DockPosition::Left
+    }
+}
+
+impl Render for ConnectionsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+        let theme = &theme;
+        let hover_bg = theme.colors.element_hover;
+
+        let content = if self.rows.is_empty() {
+            self.render_empty_state(theme).into_any_element()
+        } else {
+            let rows: Vec<_> =
+                self.rows.iter().map(|row| self.render_row(row, theme, cx)).collect();
+            div()
+                .id("connections-list")
+                .size_full()
+                .overflow_y_scroll()
+                .children(rows)
+                .into_any_element()
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(theme.colors.panel_background)
+            .child(
+                // Panel header
+                div()
+                    .h(px(32.0))
+                    .w_full()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px(px(12.0))
+                    .border_b_1()
+                    .border_color(theme.colors.border)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(Icon::new(IconName::Database).size(IconSize::Small))
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(theme.colors.text)
+                                    .child("Connections"),
+                            )
+                            .when(!self.rows.is_empty(), |d| {
+                                d.child(
+                                    div()
+                                        .text_size(px(10.0))
+                                        .text_color(theme.colors.text_muted)
+                                        .child(format!("({})", self.rows.len())),
+                                )
+                            }),
+                    )
+                    .when(!self.rows.is_empty(), |d| {
+                        d.child(
+                            div()
+                                .id("connections-disconnect-all")
+                                .flex()
+                                .items_center()
+                                .gap(px(4.0))
+                                .px(px(6.0))
+                                .py(px(2.0))
+                                .rounded(px(4.0))
+                                .cursor_pointer()
+                                .text_size(px(11.0))
+                                .text_color(theme.colors.text_muted)
+                                .hover(|style| style.bg(hover_bg))
+                                .on_click(cx.listener(|panel, _, _, cx| panel.disconnect_all(cx)))
+                                .child(
+                                    Icon::new(IconName::Disconnected)
+                                        .size(IconSize::XSmall)
+                                        .color(theme.colors.text_muted),
+                                )
+                                .child("Disconnect All"),
+                        )
+                    }),
+            )
+            .child(div().flex_1().overflow_hidden().child(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool(size: usize, available: isize, waiting: usize) -> PoolStatus {
+        PoolStatus { max_size: 4, size, available, waiting }
+    }
+
+    #[test]
+    fn test_rows_join_connections_with_pool_statuses() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let connections = vec![
+            (b, "staging".to_string(), ConnectionStatus::Connected),
+            (a, "Analytics".to_string(), ConnectionStatus::Connected),
+            (c, "prod".to_string(), ConnectionStatus::error("server closed the connection", true)),
+        ];
+        let statuses = HashMap::from([(a, pool(3, 1, 0)), (b, pool(4, -1, 2))]);
+
+        let rows = ConnectionRow::collect(connections, &statuses);
+        let names: Vec<&str> = rows.iter().map(|row| row.name.as_str()).collect();
+        assert_eq!(names, vec!["Analytics", "prod", "staging"]);
+
+        assert!(rows[0].is_healthy());
+        assert_eq!(rows[0].health_label(), "Healthy");
+        assert_eq!(rows[0].usage_label().as_deref(), Some("2 of 4 in use"));
+
+        assert!(!rows[1].is_healthy());
+        assert_eq!(rows[1].pool, None);
+        assert_eq!(rows[1].health_label(), "Error: server closed the connection");
+        assert_eq!(rows[1].usage_label(), None);
+
+        assert!(!rows[2].is_healthy());
+        assert_eq!(rows[2].health_label(), "Busy, 2 waiting");
+        assert_eq!(rows[2].usage_label().as_deref(), Some("4 of 4 in use"));
+    }
+
+    #[test]
+    fn test_rows_empty_without_connections() {
+        assert!(ConnectionRow::collect(Vec::new(), &HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,314 @@
+//! Pretty-printed, collapsible view of JSON cell values.
+//!
+//! `json` and `jsonb` values arrive in the grid as a single line of text.
+//! [`show_json_view`] opens them in a modal with one line per member and
+//! foldable objects and arrays. Keys keep the order they had in the cell.
+//! Text that does not parse is shown as-is.
+
+use std::collections::HashSet;
+
+use gpui::{div, prelude::*, px, App, Context, Render, SharedString, Window};
+
+use crate::icon::{Icon, IconName, IconSize};
+use crate::modal::{Modal, ModalAction, ModalLayer};
+use crate::TuskTheme;
+
+/// Spaces per nesting level in pretty-printed output.
+pub(crate) const INDENT: usize = 2;
+
+/// Check whether cell text is a JSON object or array.
+///
+/// Bare scalars are valid JSON too, but a cell like `42` or `true` is far
+/// more likely a number or boolean column, so they are not treated as JSON.
+pub fn looks_like_json(text: &str) -> bool {
+    let text = text.trim();
+    (text.starts_with('{') || text.starts_with('['))
+        && serde_json::from_str::<serde::de::IgnoredAny>(text).is_ok()
+}
+
+/// Pretty-print a JSON object or array, keeping key order and number text.
+///
+/// Returns None when the text is not a JSON object or array.
+pub fn pretty_json(text: &str) -> Option<String> {
+    if !looks_like_json(text) {
+        return None;
+    }
+
+    let mut out = String::with_capacity(text.len() * 2);
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = text.trim().chars().peekable();
+
+    let newline = |out: &mut String, depth: usize| {
+        out.push('\n');
+        out.push_str(&" ".repeat(depth * INDENT));
+    };
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '{' | '[' => {
+                out.push(c);
+                while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                // Keep empty containers on one line
+                if let Some(close) = chars.next_if(|&next| next == '}' || next == ']') {
+                    out.push(close);
+                } else {
+                    depth += 1;
+                    newline(&mut out, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                newline(&mut out, depth);
+                out.push(c);
+            }
+            ',' => {
+                out.push(c);
+                newline(&mut out, depth);
+            }
+            ':' => out.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => out.push(c),
+        }
+    }
+    Some(out)
+}
+
+/// A line of pretty-printed JSON.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct JsonLine {
+    /// Line text without indentation.
+    text: String,
+    /// Nesting depth.
+    depth: usize,
+    /// For lines that open an object or array, the index of the closing line.
+    close: Option<usize>,
+}
+
+/// Split pretty-printed JSON into lines, pairing openers with their closers.
+fn json_lines(pretty: &str) -> Vec<JsonLine> {
+    let mut lines: Vec<JsonLine> = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+
+    for (index, raw) in pretty.lines().enumerate() {
+        let text = raw.trim_start();
+        let depth = (raw.len() - text.len()) / INDENT;
+        // Member lines start with a quote or a scalar, so only closers start
+        // with a bracket; strings end with a quote, so only openers end with one
+        if text.starts_with('}') || text.starts_with(']') {
+            if let Some(opener) = open.pop() {
+                lines[opener].close = Some(index);
+            }
+        }
+        if text.ends_with('{') || text.ends_with('[') {
+            open.push(index);
+        }
+        lines.push(JsonLine { text: text.to_string(), depth, close: None });
+    }
+    lines
+}
+
+/// Modal body showing a JSON value as a collapsible tree.
+pub struct JsonView {
+    /// Pretty-printed lines, empty when the value did not parse.
+    lines: Vec<JsonLine>,
+    /// Original text, shown when the value did not parse.
+    plain: SharedString,
+    /// Indexes of collapsed opener lines.
+    collapsed: HashSet<usize>,
+}
+
+impl JsonView {
+    /// Create a view of the given cell text.
+    pub fn new(text: impl Into<SharedString>, _cx: &mut Context<Self>) -> Self {
+        let plain = text.into();
+        let lines = pretty_json(&plain).map(|pretty| json_lines(&pretty)).unwrap_or_default();
+        Self { lines, plain, collapsed: HashSet::new() }
+    }
+
+    /// Whether the value parsed as JSON.
+    pub fn is_json(&self) -> bool {
+        !self.lines.is_empty()
+    }
+
+    /// Collapse or expand the object or array opened on `index`.
+    pub fn toggle(&mut self, index: usize, cx: &mut Context<Self>) {
+        if !self.collapsed.remove(&index) {
+            self.collapsed.insert(index);
+        }
+        cx.notify();
+    }
+
+    /// Lines to display as (line index, text), with collapsed ranges folded
+    /// onto their opening line.
+    fn visible_lines(&self) -> Vec<(usize, String)> {
+        let mut visible = Vec::new();
+        let mut index = 0;
+        while let Some(line) = self.lines.get(index) {
+            match line.close {
+                Some(close) if self.collapsed.contains(&index) => {
+                    let closer = self.lines[close].text.as_str();
+                    visible.push((index, format!("{} … {}", line.text, closer)));
+                    index = close + 1;
+                }
+                _ => {
+                    visible.push((index, line.text.clone()));
+                    index += 1;
+                }
+            }
+        }
+        visible
+    }
+}
+
+impl Render for JsonView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>();
+        let text_color = theme.colors.text;
+        let text_muted = theme.colors.text_muted;
+        let hover_bg = theme.colors.element_hover;
+
+        let container = div()
+            .id("json-view-content")
+            .flex()
+            .flex_col()
+            .max_h(px(400.0))
+            .overflow_y_scroll()
+            .font_family("monospace")
+            .text_size(px(12.0))
+            .text_color(text_color);
+
+        if !self.is_json() {
+            return container.child(div().child(self.plain.clone()));
+        }
+
+        container.children(self.visible_lines().into_iter().map(|(index, text)| {
+            let line = &self.lines[index];
+            let foldable = line.close.is_some();
+            let icon = if self.collapsed.contains(&index) {
+                IconName::ChevronRight
+            } else {
+                IconName::ChevronDown
+            };
+
+            div()
+                .id(("json-view-line", index))
+                .flex()
+                .items_center()
+                .pl(px((line.depth * 16) as f32))
+                .child(div().w(px(16.0)).flex_shrink_0().when(foldable, |d| {
+                    d.child(Icon::new(icon).size(IconSize::XSmall).color(text_muted))
+                }))
+                .when(foldable, |d| {
+                    d.cursor_pointer().hover(|style| style.bg(hover_bg)).on_click(cx.listener(
+                        move |this, _, _window, cx| {
+                            this.toggle(index, cx);
+                        },
+                    ))
+                })
+                .child(text)
+        }))
+    }
+}
+
+/// Show a cell value in the JSON view modal.
+pub fn show_json_view(
+    column: impl Into<SharedString>,
+    text: impl Into<SharedString>,
+    cx: &mut App,
+) {
+    let text = text.into();
+    let content = cx.new(|cx| JsonView::new(text, cx));
+    let subtitle =
+        if content.read(cx).is_json() { "JSON" } else { "Not valid JSON; shown as text" };
+
+    let modal = cx.new(|cx| {
+        Modal::new(column, cx)
+            .subtitle(subtitle)
+            .width(600.0)
+            .body(content.into())
+            .action(ModalAction::confirm("Close"))
+    });
+
+    cx.update_global::<ModalLayer, _>(|layer, cx| {
+        layer.show(modal, cx);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_json_requires_object_or_array() {
+        assert!(looks_like_json(r#"{"a": 1}"#));
+        assert!(looks_like_json("  [1, 2, 3]  "));
+        assert!(looks_like_json("{}"));
+
+        // Valid JSON scalars are left alone
+        assert!(!looks_like_json("42"));
+        assert!(!looks_like_json("-1.5e3"));
+        assert!(!looks_like_json("true"));
+        assert!(!looks_like_json("null"));
+        assert!(!looks_like_json(r#""text""#));
+
+        // Brackets that do not parse
+        assert!(!looks_like_json("{1,2,3}"));
+        assert!(!looks_like_json("[1, 2"));
+        assert!(!looks_like_json("NULL"));
+        assert!(!looks_like_json(""));
+    }
+
+    #[test]
+    fn test_pretty_json_keeps_order_and_strings() {
+        let pretty = pretty_json(r#"{"b":1,"a":[true,{"x":"{,:}\" ["}],"e":{},"n":1.50}"#);
+        assert_eq!(
+            pretty.as_deref(),
+            Some(
+                "{\n  \"b\": 1,\n  \"a\": [\n    true,\n    {\n      \"x\": \"{,:}\\\" [\"\n    }\n  ],\n  \"e\": {},\n  \"n\": 1.50\n}"
+            )
+        );
+
+        // Existing whitespace is normalized
+        assert_eq!(pretty_json("[ 1 ,\n 2 ]").as_deref(), Some("[\n  1,\n  2\n]"));
+        assert_eq!(pretty_json("[ ]").as_deref(), Some("[]"));
+        assert_eq!(pretty_json("42"), None);
+        assert_eq!(pretty_json("{oops}"), None);
+    }
+
+    #[test]
+    fn test_json_lines_pair_openers_with_closers() {
+        let pretty = pretty_json(r#"{"a":[1,2],"b":{"c":"]"}}"#).unwrap();
+        let lines = json_lines(&pretty);
+        let texts: Vec<_> = lines.iter().map(|l| (l.depth, l.text.as_str(), l.close)).collect();
+        assert_eq!(
+            texts,
+            [
+                (0, "{", Some(8)),
+                (1, "\"a\": [", Some(4)),
+                (2, "1,", None),
+                (2, "2", None),
+                (1, "],", None),
+                (1, "\"b\": {", Some(7)),
+                (2, "\"c\": \"]\"", None),
+                (1, "}", None),
+                (0, "}", None),
+            ]
+        );
+    }
+}
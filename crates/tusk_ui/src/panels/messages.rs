@@ -4,13 +4,19 @@
 //! - Query execution messages (info, warning, error)
 //! - PostgreSQL notices and warnings
 //! - System messages
+//! - A session log of executed queries, showing literal-free SQL fingerprints
+//!   unless full SQL is revealed
+//! - Severity filter toggles with a count per severity, colored from the theme
 
 use gpui::{
-    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, Render, SharedString, Window,
+    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, Hsla, Render, SharedString,
+    Window,
 };
 
 use crate::icon::{Icon, IconName, IconSize};
 use crate::panel::{DockPosition, Focusable, Panel, PanelEvent};
+use crate::status_bar::{format_elapsed, ExecutionEvent};
+use crate::tooltip::Tooltip;
 use crate::TuskTheme;
 
 /// Severity level of a message.
@@ -36,6 +42,120 @@ impl MessageSeverity {
             MessageSeverity::Success => IconName::Check,
         }
     }
+
+    /// Get the theme color for this severity.
+    pub fn color(&self, theme: &TuskTheme) -> Hsla {
+        match self {
+            MessageSeverity::Info => theme.colors.accent,
+            MessageSeverity::Warning => theme.colors.warning,
+            MessageSeverity::Error => theme.colors.error,
+            MessageSeverity::Success => theme.colors.success,
+        }
+    }
+
+    /// Severity whose filter toggle covers this one; success counts as info.
+    fn filter_level(&self) -> MessageSeverity {
+        match self {
+            MessageSeverity::Success => MessageSeverity::Info,
+            severity => *severity,
+        }
+    }
+}
+
+/// Severities shown in the messages panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeverityFilter {
+    /// Show info and success messages.
+    pub info: bool,
+    /// Show warnings.
+    pub warning: bool,
+    /// Show errors.
+    pub error: bool,
+}
+
+impl Default for SeverityFilter {
+    fn default() -> Self {
+        Self { info: true, warning: true, error: true }
+    }
+}
+
+impl SeverityFilter {
+    /// Check if messages of a severity are shown.
+    pub fn shows(&self, severity: MessageSeverity) -> bool {
+        match severity.filter_level() {
+            MessageSeverity::Warning => self.warning,
+            MessageSeverity::Error => self.error,
+            _ => self.info,
+        }
+    }
+
+    /// Show or hide messages of a severity.
+    pub fn toggle(&mut self, severity: MessageSeverity) {
+        match severity.filter_level() {
+            MessageSeverity::Warning => self.warning = !self.warning,
+            MessageSeverity::Error => self.error = !self.error,
+            _ => self.info = !self.info,
+        }
+    }
+
+    /// Check if every severity is shown.
+    pub fn shows_all(&self) -> bool {
+        self.info && self.warning && self.error
+    }
+}
+
+/// Number of messages per severity, success counted as info.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SeverityCounts {
+    /// Info and success messages.
+    pub info: usize,
+    /// Warnings.
+    pub warning: usize,
+    /// Errors.
+    pub error: usize,
+}
+
+impl SeverityCounts {
+    /// Count messages by severity.
+    pub fn count(messages: &[Message]) -> Self {
+        let mut counts = Self::default();
+        for message in messages {
+            match message.severity.filter_level() {
+                MessageSeverity::Warning => counts.warning += 1,
+                MessageSeverity::Error => counts.error += 1,
+                _ => counts.info += 1,
+            }
+        }
+        counts
+    }
+
+    /// Count for the filter toggle covering a severity.
+    pub fn get(&self, severity: MessageSeverity) -> usize {
+        match severity.filter_level() {
+            MessageSeverity::Warning => self.warning,
+            MessageSeverity::Error => self.error,
+            _ => self.info,
+        }
+    }
+
+    /// Summary in a fixed order (errors, warnings, info), skipping zero counts.
+    pub fn summary(&self) -> String {
+        let plural =
+            |n: usize, one: &str, many: &str| format!("{} {}", n, if n == 1 { one } else { many });
+        let parts: Vec<String> = [
+            (self.error > 0).then(|| plural(self.error, "error", "errors")),
+            (self.warning > 0).then(|| plural(self.warning, "warning", "warnings")),
+            (self.info > 0).then(|| format!("{} info", self.info)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        if parts.is_empty() {
+            "No messages".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
 }
 
 /// A message entry in the messages panel.
@@ -47,27 +167,81 @@ pub struct Message {
     pub text: String,
     /// Optional timestamp (as formatted string).
     pub timestamp: Option<String>,
+    /// SQL of a logged query.
+    pub sql: Option<LoggedSql>,
+}
+
+/// SQL attached to a query log message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoggedSql {
+    /// Fingerprint with literals replaced, shown by default.
+    pub fingerprint: String,
+    /// Full statement text, shown when the panel reveals full SQL.
+    pub text: String,
 }
 
 impl Message {
+    /// Create a message with the given severity.
+    fn new(severity: MessageSeverity, text: impl Into<String>) -> Self {
+        Self { severity, text: text.into(), timestamp: None, sql: None }
+    }
+
     /// Create a new info message.
     pub fn info(text: impl Into<String>) -> Self {
-        Self { severity: MessageSeverity::Info, text: text.into(), timestamp: None }
+        Self::new(MessageSeverity::Info, text)
     }
 
     /// Create a new warning message.
     pub fn warning(text: impl Into<String>) -> Self {
-        Self { severity: MessageSeverity::Warning, text: text.into(), timestamp: None }
+        Self::new(MessageSeverity::Warning, text)
     }
 
     /// Create a new error message.
     pub fn error(text: impl Into<String>) -> Self {
-        Self { severity: MessageSeverity::Error, text: text.into(), timestamp: None }
+        Self::new(MessageSeverity::Error, text)
     }
 
     /// Create a new success message.
     pub fn success(text: impl Into<String>) -> Self {
-        Self { severity: MessageSeverity::Success, text: text.into(), timestamp: None }
+        Self::new(MessageSeverity::Success, text)
+    }
+
+    /// Create a message for a notice the server raised, prefixed with its
+    /// severity like psql prints it. WARNINGs are warnings; NOTICE, INFO
+    /// and the rest are informational.
+    #[cfg(feature = "persistence")]
+    pub fn server_notice(severity: tusk_core::NoticeSeverity, text: &str) -> Self {
+        let message_severity = match severity {
+            tusk_core::NoticeSeverity::Warning => MessageSeverity::Warning,
+            _ => MessageSeverity::Info,
+        };
+        Self::new(message_severity, format!("{}: {}", severity.as_str(), text))
+    }
+
+    /// Create a query log entry for a finished query.
+    ///
+    /// `command` names what ran ("SELECT", "3 statements") and `rows` is the
+    /// count to report, if any. Returns None for events that don't end a query.
+    pub fn query_log(command: &str, event: &ExecutionEvent, rows: Option<u64>) -> Option<Self> {
+        let text = match event {
+            ExecutionEvent::Completed { elapsed_us, .. } => {
+                let mut text = format!("Executed {} in {}", command, format_elapsed(*elapsed_us));
+                if let Some(rows) = rows {
+                    text.push_str(&format!(", {} row{}", rows, if rows == 1 { "" } else { "s" }));
+                }
+                text
+            }
+            ExecutionEvent::Failed(message) => format!("Failed: {}", message),
+            ExecutionEvent::Cancelled => format!("Cancelled {}", command),
+            _ => return None,
+        };
+        Some(Self::info(text))
+    }
+
+    /// Attach the SQL of a logged query.
+    pub fn with_sql(mut self, fingerprint: impl Into<String>, text: impl Into<String>) -> Self {
+        self.sql = Some(LoggedSql { fingerprint: fingerprint.into(), text: text.into() });
+        self
     }
 
     /// Add a timestamp to the message.
@@ -86,12 +260,21 @@ pub struct MessagesPanel {
     focus_handle: FocusHandle,
     /// List of messages to display.
     messages: Vec<Message>,
+    /// Show logged queries' full SQL instead of their fingerprints.
+    show_full_sql: bool,
+    /// Severities shown in the list.
+    filter: SeverityFilter,
 }
 
 impl MessagesPanel {
     /// Create a new messages panel.
     pub fn new(cx: &mut Context<Self>) -> Self {
-        Self { focus_handle: cx.focus_handle(), messages: Vec::new() }
+        Self {
+            focus_handle: cx.focus_handle(),
+            messages: Vec::new(),
+            show_full_sql: false,
+            filter: SeverityFilter::default(),
+        }
     }
 
     /// Get the messages.
@@ -117,6 +300,33 @@ impl MessagesPanel {
         cx.notify();
     }
 
+    /// Toggle between fingerprints and full SQL for logged queries.
+    pub fn toggle_full_sql(&mut self, cx: &mut Context<Self>) {
+        self.show_full_sql = !self.show_full_sql;
+        cx.notify();
+    }
+
+    /// Get whether logged queries show their full SQL.
+    pub fn shows_full_sql(&self) -> bool {
+        self.show_full_sql
+    }
+
+    /// Get the severities shown in the list.
+    pub fn filter(&self) -> SeverityFilter {
+        self.filter
+    }
+
+    /// Show or hide messages of a severity.
+    pub fn toggle_severity(&mut self, severity: MessageSeverity, cx: &mut Context<Self>) {
+        self.filter.toggle(severity);
+        cx.notify();
+    }
+
+    /// Messages passing the severity filter.
+    pub fn visible_messages(&self) -> impl Iterator<Item = &Message> {
+        self.messages.iter().filter(|message| self.filter.shows(message.severity))
+    }
+
     /// Render the empty state.
     fn render_empty_state(&self, theme: &TuskTheme) -> impl IntoElement {
         div()
@@ -153,11 +363,10 @@ impl MessagesPanel {
 
     /// Render a single message.
     fn render_message(&self, message: &Message, theme: &TuskTheme) -> impl IntoElement {
-        let icon_color = match message.severity {
-            MessageSeverity::Info => theme.colors.accent,
-            MessageSeverity::Warning => theme.colors.warning,
-            MessageSeverity::Error => theme.colors.error,
-            MessageSeverity::Success => theme.colors.success,
+        let icon_color = message.severity.color(theme);
+        let text_color = match message.severity {
+            MessageSeverity::Info => theme.colors.text,
+            _ => icon_color,
         };
 
         div()
@@ -179,9 +388,22 @@ impl MessagesPanel {
                     .child(
                         div()
                             .text_size(px(12.0))
-                            .text_color(theme.colors.text)
+                            .text_color(text_color)
                             .child(message.text.clone()),
                     )
+                    .when_some(message.sql.as_ref(), |d, sql| {
+                        d.child(
+                            div()
+                                .text_size(px(11.0))
+                                .font_family("monospace")
+                                .text_color(theme.colors.text_muted)
+                                .child(if self.show_full_sql {
+                                    sql.text.clone()
+                                } else {
+                                    sql.fingerprint.clone()
+                                }),
+                        )
+                    })
                     .when_some(message.timestamp.as_ref(), |d, timestamp| {
                         d.child(
                             div()
@@ -193,13 +415,80 @@ impl MessagesPanel {
             )
     }
 
+    /// Render a small labelled button for the panel header.
+    fn render_header_button(
+        &self,
+        id: &'static str,
+        icon: IconName,
+        label: &'static str,
+        theme: &TuskTheme,
+        on_click: impl Fn(&gpui::ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> impl IntoElement {
+        let hover_bg = theme.colors.element_hover;
+        div()
+            .id(id)
+            .flex()
+            .items_center()
+            .gap(px(4.0))
+            .px(px(6.0))
+            .py(px(2.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .text_size(px(11.0))
+            .text_color(theme.colors.text_muted)
+            .hover(|style| style.bg(hover_bg))
+            .on_click(on_click)
+            .child(Icon::new(icon).size(IconSize::XSmall).color(theme.colors.text_muted))
+            .child(label)
+    }
+
+    /// Render the toggle that shows or hides a severity, with its count.
+    fn render_severity_toggle(
+        &self,
+        severity: MessageSeverity,
+        count: usize,
+        theme: &TuskTheme,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let shown = self.filter.shows(severity);
+        let color = if shown { severity.color(theme) } else { theme.colors.text_muted };
+        let hover_bg = theme.colors.element_hover;
+        let id = match severity {
+            MessageSeverity::Warning => "messages-filter-warning",
+            MessageSeverity::Error => "messages-filter-error",
+            _ => "messages-filter-info",
+        };
+
+        div()
+            .id(id)
+            .flex()
+            .items_center()
+            .gap(px(3.0))
+            .px(px(4.0))
+            .py(px(2.0))
+            .rounded(px(4.0))
+            .cursor_pointer()
+            .when(shown, |d| d.bg(theme.colors.element_background))
+            .when(!shown, |d| d.opacity(0.6))
+            .hover(|style| style.bg(hover_bg))
+            .on_click(cx.listener(move |panel, _, _, cx| panel.toggle_severity(severity, cx)))
+            .child(Icon::new(severity.icon()).size(IconSize::XSmall).color(color))
+            .child(div().text_size(px(10.0)).text_color(color).child(count.to_string()))
+    }
+
     /// Render the messages list.
     fn render_messages_list(&self, theme: &TuskTheme) -> impl IntoElement {
-        div()
-            .id("messages-list")
-            .size_full()
-            .overflow_y_scroll()
-            .children(self.messages.iter().map(|msg| self.render_message(msg, theme)))
+        let list = div().id("messages-list").size_full().overflow_y_scroll();
+        if self.visible_messages().next().is_none() {
+            return list.child(
+                div()
+                    .p(px(12.0))
+                    .text_size(px(12.0))
+                    .text_color(theme.colors.text_muted)
+                    .child("No messages match the severity filter"),
+            );
+        }
+        list.children(self.visible_messages().map(|msg| self.render_message(msg, theme)))
     }
 }
 
@@ -239,20 +528,25 @@ impl Panel for MessagesPanel {
 
 impl Render for MessagesPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = cx.global::<TuskTheme>();
+        let theme = cx.global::<TuskTheme>().clone();
+        let theme = &theme;
 
         let content = if self.messages.is_empty() {
             self.render_empty_state(theme).into_any_element()
         } else {
             self.render_messages_list(theme).into_any_element()
         };
+        let has_sql = self.messages.iter().any(|message| message.sql.is_some());
+        let counts = SeverityCounts::count(&self.messages);
 
         // Badge showing message count if there are messages
         let badge = if !self.messages.is_empty() {
             Some(
                 div()
+                    .id("messages-count")
                     .text_size(px(10.0))
                     .text_color(theme.colors.text_muted)
+                    .tooltip(Tooltip::text(counts.summary()))
                     .child(format!("({})", self.messages.len())),
             )
         } else {
@@ -272,6 +566,7 @@ impl Render for MessagesPanel {
                     .w_full()
                     .flex()
                     .items_center()
+                    .justify_between()
                     .px(px(12.0))
                     .border_b_1()
                     .border_color(theme.colors.border)
@@ -289,6 +584,43 @@ impl Render for MessagesPanel {
                                     .child("Messages"),
                             )
                             .children(badge),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(4.0))
+                            .when(!self.messages.is_empty(), |d| {
+                                d.children(
+                                    [
+                                        MessageSeverity::Error,
+                                        MessageSeverity::Warning,
+                                        MessageSeverity::Info,
+                                    ]
+                                    .map(|severity| {
+                                        let count = counts.get(severity);
+                                        self.render_severity_toggle(severity, count, theme, cx)
+                                    }),
+                                )
+                            })
+                            .when(has_sql, |d| {
+                                d.child(self.render_header_button(
+                                    "messages-full-sql",
+                                    IconName::Code,
+                                    if self.show_full_sql { "Fingerprints" } else { "Full SQL" },
+                                    theme,
+                                    cx.listener(|panel, _, _, cx| panel.toggle_full_sql(cx)),
+                                ))
+                            })
+                            .when(!self.messages.is_empty(), |d| {
+                                d.child(self.render_header_button(
+                                    "messages-clear",
+                                    IconName::Trash,
+                                    "Clear",
+                                    theme,
+                                    cx.listener(|panel, _, _, cx| panel.clear(cx)),
+                                ))
+                            }),
                     ),
             )
             .child(
@@ -314,6 +646,20 @@ mod tests {
         assert_eq!(error.timestamp, Some("12:34:56".to_string()));
     }
 
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_server_notice_maps_severity() {
+        use tusk_core::NoticeSeverity;
+
+        let warning = Message::server_notice(NoticeSeverity::Warning, "disk nearly full");
+        assert_eq!(warning.severity, MessageSeverity::Warning);
+        assert_eq!(warning.text, "WARNING: disk nearly full");
+
+        let notice = Message::server_notice(NoticeSeverity::Notice, "relation exists, skipping");
+        assert_eq!(notice.severity, MessageSeverity::Info);
+        assert_eq!(Message::server_notice(NoticeSeverity::Info, "x").text, "INFO: x");
+    }
+
     #[test]
     fn test_message_severity_icon() {
         assert_eq!(MessageSeverity::Info.icon(), IconName::Info);
@@ -321,4 +667,85 @@ mod tests {
         assert_eq!(MessageSeverity::Error.icon(), IconName::Error);
         assert_eq!(MessageSeverity::Success.icon(), IconName::Check);
     }
+
+    #[test]
+    fn test_severity_filter_shows_selected_severities() {
+        let messages = vec![
+            Message::info("Connected"),
+            Message::success("Query complete"),
+            Message::warning("Row limit applied"),
+            Message::error("syntax error"),
+        ];
+        let visible = |filter: &SeverityFilter| -> Vec<&str> {
+            messages.iter().filter(|m| filter.shows(m.severity)).map(|m| m.text.as_str()).collect()
+        };
+
+        let mut filter = SeverityFilter::default();
+        assert!(filter.shows_all());
+        assert_eq!(visible(&filter).len(), 4);
+
+        // Success follows the info toggle
+        filter.toggle(MessageSeverity::Info);
+        assert_eq!(visible(&filter), vec!["Row limit applied", "syntax error"]);
+
+        filter.toggle(MessageSeverity::Warning);
+        assert_eq!(visible(&filter), vec!["syntax error"]);
+        assert!(!filter.shows_all());
+
+        filter.toggle(MessageSeverity::Error);
+        assert!(visible(&filter).is_empty());
+    }
+
+    #[test]
+    fn test_severity_counts_summary_is_stable() {
+        assert_eq!(SeverityCounts::count(&[]).summary(), "No messages");
+
+        let messages = vec![
+            Message::info("a"),
+            Message::error("b"),
+            Message::success("c"),
+            Message::warning("d"),
+            Message::error("e"),
+        ];
+        let counts = SeverityCounts::count(&messages);
+        assert_eq!(counts, SeverityCounts { info: 2, warning: 1, error: 2 });
+        assert_eq!(counts.get(MessageSeverity::Success), 2);
+        assert_eq!(counts.summary(), "2 errors, 1 warning, 2 info");
+
+        let reordered: Vec<Message> = messages.into_iter().rev().collect();
+        assert_eq!(SeverityCounts::count(&reordered).summary(), counts.summary());
+        assert_eq!(SeverityCounts { info: 0, warning: 2, error: 0 }.summary(), "2 warnings");
+    }
+
+    #[test]
+    fn test_query_log_for_success() {
+        let completed = ExecutionEvent::Completed { rows: 12, elapsed_us: 42_000 };
+        let message = Message::query_log("SELECT", &completed, Some(12)).unwrap();
+        assert_eq!(message.severity, MessageSeverity::Info);
+        assert_eq!(message.text, "Executed SELECT in 42ms, 12 rows");
+
+        let message = Message::query_log("UPDATE", &completed, Some(1)).unwrap();
+        assert_eq!(message.text, "Executed UPDATE in 42ms, 1 row");
+
+        // Commands without a row count
+        let fast = ExecutionEvent::Completed { rows: 0, elapsed_us: 300 };
+        let message = Message::query_log("CREATE TABLE", &fast, None)
+            .unwrap()
+            .with_sql("CREATE TABLE t (id int)", "CREATE TABLE t (id int)");
+        assert_eq!(message.text, "Executed CREATE TABLE in 0.3ms");
+        assert!(message.sql.is_some());
+    }
+
+    #[test]
+    fn test_query_log_for_failure() {
+        let failed = ExecutionEvent::Failed("syntax error at or near \"SELEC\"".into());
+        let message = Message::query_log("SELEC", &failed, None).unwrap();
+        assert_eq!(message.text, "Failed: syntax error at or near \"SELEC\"");
+
+        let message = Message::query_log("SELECT", &ExecutionEvent::Cancelled, None).unwrap();
+        assert_eq!(message.text, "Cancelled SELECT");
+
+        // Events that don't end a query aren't logged
+        assert!(Message::query_log("SELECT", &ExecutionEvent::Started, None).is_none());
+    }
 }
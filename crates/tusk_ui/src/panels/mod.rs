@@ -2,18 +2,57 @@
 //!
 //! This module contains concrete panel implementations that live inside docks:
 //! - Schema browser panel (left dock)
-//! - Results panel (bottom dock)
+//! - Results panel (bottom dock), with per-tab result history and a JSON cell view
+//! - Inspector popover for reading a long or JSON cell value in full
+//! - Comparison of a result with the tab's previous run
+//! - Column hiding and reordering for each query tab
+//! - Type-aware sorting of result rows by a column
 //! - Messages panel (bottom dock)
+//! - Connections panel (left dock) with per-connection and global disconnect
+//! - Notifications panel (bottom dock) for LISTEN subscriptions
 
+pub mod cell_format;
+pub mod cell_inspector;
+pub mod column_layout;
+#[cfg(feature = "persistence")]
+pub mod connections;
+pub mod json_view;
 pub mod messages;
+#[cfg(feature = "persistence")]
+pub mod notifications;
+#[cfg(feature = "persistence")]
+pub mod result_diff;
+pub mod result_history;
+pub mod result_sort;
 pub mod results;
 pub mod schema_browser;
+pub mod selection;
 
-pub use messages::{Message, MessageSeverity, MessagesPanel};
+pub use cell_format::CellValue;
+pub use cell_inspector::{CellInspector, CellInspectorEvent};
+pub use column_layout::ColumnLayout;
+#[cfg(feature = "persistence")]
+pub use connections::{ConnectionRow, ConnectionsPanel, ConnectionsPanelEvent};
+pub use json_view::{looks_like_json, pretty_json, show_json_view, JsonView};
+pub use messages::{
+    LoggedSql, Message, MessageSeverity, MessagesPanel, SeverityCounts, SeverityFilter,
+};
+#[cfg(feature = "persistence")]
+pub use notifications::{
+    NotificationLog, NotificationsPanel, ReceivedNotification, SubscriptionStatus,
+};
+#[cfg(feature = "persistence")]
+pub use result_diff::{show_result_diff, ResultDiffView};
+pub use result_history::{ResultHistory, ResultSnapshot};
+pub use result_sort::{ColumnSort, SortDirection, SortKind};
+#[cfg(feature = "persistence")]
+pub use results::ResultData;
 pub use results::{
-    DisplayColumn, DisplayError, DisplayRow, ResultsPanel, ResultsPanelState, ResultsState,
-    ResultsStatus,
+    CellCopy, CommandStatus, DisplayColumn, DisplayError, DisplayRow, DraggedColumn, EditMode,
+    LargeResult, ResultsEditEvent, ResultsPanel, ResultsPanelState, ResultsState, ResultsStatus,
+    ServerNotice,
 };
 pub use schema_browser::{
-    database_schema_to_tree, SchemaBrowserEvent, SchemaBrowserPanel, SchemaItem,
+    database_schema_to_tree, DefinitionTarget, SchemaBrowserEvent, SchemaBrowserPanel, SchemaItem,
 };
+pub use selection::{CellPosition, CellSelection, NumericSummary, SelectionSummary};
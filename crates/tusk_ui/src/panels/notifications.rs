@@ -0,0 +1,563 @@
+//! Notifications panel for channels subscribed with LISTEN.
+//!
+//! Entering a channel subscribes the active connection to it. Each
+//! subscription holds its own connection (see `ConnectionPool::listen`) and
+//! removing it unsubscribes. Received notifications are listed as they
+//! arrive, oldest dropped first once the log is full.
+
+use std::collections::VecDeque;
+
+use gpui::{
+    div, prelude::*, px, App, Context, Entity, EventEmitter, FocusHandle, Hsla, Render,
+    SharedString, Subscription, Task, Window,
+};
+use tusk_core::{Notification, NotificationEvent, TuskState};
+use uuid::Uuid;
+
+use crate::icon::{Icon, IconName, IconSize};
+use crate::panel::{DockPosition, Focusable, Panel, PanelEvent};
+use crate::text_input::{TextInput, TextInputEvent};
+use crate::tooltip::Tooltip;
+use crate::TuskTheme;
+
+/// Maximum notifications kept in the log.
+const MAX_NOTIFICATIONS: usize = 500;
+
+/// A notification as shown in the panel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReceivedNotification {
+    /// The notification
+    pub notification: Notification,
+    /// Local time it arrived, formatted as HH:MM:SS
+    pub received_at: String,
+}
+
+/// Received notifications, oldest first, with a fixed capacity.
+#[derive(Debug, Clone)]
+pub struct NotificationLog {
+    entries: VecDeque<ReceivedNotification>,
+    capacity: usize,
+    /// Notifications dropped to stay within capacity.
+    dropped: usize,
+}
+
+impl NotificationLog {
+    /// Create an empty log holding at most `capacity` notifications.
+    pub fn new(capacity: usize) -> Self {
+        Self { entries: VecDeque::new(), capacity: capacity.max(1), dropped: 0 }
+    }
+
+    /// Append a notification, dropping the oldest if the log is full.
+    pub fn push(&mut self, entry: ReceivedNotification) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Get the notifications, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &ReceivedNotification> {
+        self.entries.iter()
+    }
+
+    /// Get the number of notifications kept.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if the log is empty.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the number of notifications dropped to stay within capacity.
+    pub fn dropped(&self) -> usize {
+        self.dropped
+    }
+
+    /// Remove all notifications.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.dropped = 0;
+    }
+}
+
+/// State of a channel subscription.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubscriptionStatus {
+    /// Opening the listening connection.
+    Connecting,
+    /// Receiving notifications.
+    Listening,
+    /// The subscription failed or its connection dropped.
+    Failed(String),
+}
+
+/// A channel subscription owned by the panel.
+struct ChannelSubscription {
+    id: Uuid,
+    connection_id: Uuid,
+    channel: String,
+    status: SubscriptionStatus,
+    /// Holds the listener; dropping it unsubscribes.
+    _task: Task<()>,
+}
+
+/// Panel listing LISTEN subscriptions and the notifications they receive.
+pub struct NotificationsPanel {
+    focus_handle: FocusHandle,
+    channel_input: Entity<TextInput>,
+    _input_subscription: Subscription,
+    /// Connection new subscriptions are opened on.
+    connection_id: Option<Uuid>,
+    subscriptions: Vec<ChannelSubscription>,
+    log: NotificationLog,
+}
+
+impl NotificationsPanel {
+    /// Create a new notifications panel.
+    pub fn new(cx: &mut Context<Self>) -> Self {
+        let channel_input = cx.new(|cx| TextInput::new("Channel to listen on...", cx));
+        let input_subscription = cx.subscribe(&channel_input, Self::handle_input_event);
+        Self {
+            focus_handle: cx.focus_handle(),
+            channel_input,
+            _input_subscription: input_subscription,
+            connection_id: None,
+            subscriptions: Vec::new(),
+            log: NotificationLog::new(MAX_NOTIFICATIONS),
+        }
+    }
+
+    /// Get the received notifications.
+    pub fn log(&self) -> &NotificationLog {
+        &self.log
+    }
+
+    /// Set the connection new subscriptions are opened on.
+    ///
+    /// Existing subscriptions keep listening on their own connection.
+    pub fn set_connection(&mut self, connection_id: Option<Uuid>, cx: &mut Context<Self>) {
+        self.connection_id = connection_id;
+        cx.notify();
+    }
+
+    /// Drop the subscriptions of closed connections.
+    pub fn connections_closed(&mut self, connection_ids: &[Uuid], cx: &mut Context<Self>) {
+        self.subscriptions
+            .retain(|subscription| !connection_ids.contains(&subscription.connection_id));
+        if self.connection_id.is_some_and(|id| connection_ids.contains(&id)) {
+            self.connection_id = None;
+        }
+        cx.notify();
+    }
+
+    /// Handle events from the channel input.
+    fn handle_input_event(
+        &mut self,
+        _input: Entity<TextInput>,
+        event: &TextInputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        if let TextInputEvent::Submitted(channel) = event {
+            self.listen(channel.trim().to_string(), cx);
+        }
+    }
+
+    /// Subscribe the current connection to a channel.
+    ///
+    /// Does nothing without a connection, for an empty channel, or if the
+    /// connection already listens on the channel.
+    pub fn listen(&mut self, channel: String, cx: &mut Context<Self>) {
+        let Some(connection_id) = self.connection_id else {
+            return;
+        };
+        if channel.is_empty()
+            || self.subscriptions.iter().any(|subscription| {
+                subscription.connection_id == connection_id && subscription.channel == channel
+            })
+        {
+            return;
+        }
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+
+        let id = Uuid::new_v4();
+        let listened = channel.clone();
+        let task = cx.spawn(async move |this, cx| {
+            let result = runtime_handle.spawn(async move { pool.listen(&listened).await }).await;
+            let mut listener = match result {
+                Ok(Ok(listener)) => listener,
+                Ok(Err(e)) => {
+                    let _ = this
+                        .update(cx, |panel, cx| panel.subscription_failed(id, e.to_string(), cx));
+                    return;
+                }
+                Err(e) => {
+                    let message = format!("Listen task failed: {e}");
+                    let _ = this.update(cx, |panel, cx| panel.subscription_failed(id, message, cx));
+                    return;
+                }
+            };
+            if this
+                .update(cx, |panel, cx| panel.set_status(id, SubscriptionStatus::Listening, cx))
+                .is_err()
+            {
+                return;
+            }
+
+            while let Some(event) = listener.recv().await {
+                let is_terminal = event.is_terminal();
+                let result = this.update(cx, |panel, cx| panel.handle_event(id, event, cx));
+                if result.is_err() || is_terminal {
+                    break;
+                }
+            }
+        });
+
+        self.subscriptions.push(ChannelSubscription {
+            id,
+            connection_id,
+            channel,
+            status: SubscriptionStatus::Connecting,
+            _task: task,
+        });
+        self.channel_input.update(cx, |input, cx| input.clear(cx));
+        cx.notify();
+    }
+
+    /// Remove a subscription, which unsubscribes its channel.
+    pub fn unlisten(&mut self, subscription_id: Uuid, cx: &mut Context<Self>) {
+        self.subscriptions.retain(|subscription| subscription.id != subscription_id);
+        cx.notify();
+    }
+
+    /// Remove all received notifications.
+    pub fn clear(&mut self, cx: &mut Context<Self>) {
+        self.log.clear();
+        cx.notify();
+    }
+
+    /// Handle an event from a subscription's listener.
+    fn handle_event(
+        &mut self,
+        subscription_id: Uuid,
+        event: NotificationEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            NotificationEvent::Notification(notification) => {
+                self.log.push(ReceivedNotification {
+                    notification,
+                    received_at: chrono::Local::now().format("%H:%M:%S").to_string(),
+                });
+                cx.notify();
+            }
+            NotificationEvent::Error(e) => {
+                self.subscription_failed(subscription_id, e.to_string(), cx)
+            }
+        }
+    }
+
+    /// Mark a subscription as failed, keeping it listed with the error.
+    fn subscription_failed(
+        &mut self,
+        subscription_id: Uuid,
+        error: String,
+        cx: &mut Context<Self>,
+    ) {
+        tracing::warn!(subscription_id = %subscription_id, error = %error, "LISTEN subscription failed");
+        self.set_status(subscription_id, SubscriptionStatus::Failed(error), cx);
+    }
+
+    /// Update a subscription's status.
+    fn set_status(
+        &mut self,
+        subscription_id: Uuid,
+        status: SubscriptionStatus,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(subscription) =
+            self.subscriptions.iter_mut().find(|subscription| subscription.id == subscription_id)
+        {
+            subscription.status = status;
+            cx.notify();
+        }
+    }
+
+    /// Theme color for a subscription's status dot.
+    fn status_color(status: &SubscriptionStatus, theme: &TuskTheme) -> Hsla {
+        match status {
+            SubscriptionStatus::Connecting => theme.colors.warning,
+            SubscriptionStatus::Listening => theme.colors.success,
+            SubscriptionStatus::Failed(_) => theme.colors.error,
+        }
+    }
+
+    /// Render the channel input and the subscribed channels.
+    fn render_subscriptions(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
+        let hover_bg = theme.colors.element_hover;
+        let chips: Vec<_> = self
+            .subscriptions
+            .iter()
+            .map(|subscription| {
+                let id = subscription.id;
+                let tooltip = match &subscription.status {
+                    SubscriptionStatus::Connecting => "Connecting".to_string(),
+                    SubscriptionStatus::Listening => "Listening".to_string(),
+                    SubscriptionStatus::Failed(error) => error.clone(),
+                };
+                div()
+                    .id(SharedString::from(format!("notification-channel-{id}")))
+                    .flex()
+                    .items_center()
+                    .gap(px(4.0))
+                    .px(px(6.0))
+                    .py(px(2.0))
+                    .rounded(px(4.0))
+                    .border_1()
+                    .border_color(theme.colors.border)
+                    .tooltip(Tooltip::text(tooltip))
+                    .child(
+                        div()
+                            .size(px(6.0))
+                            .rounded_full()
+                            .bg(Self::status_color(&subscription.status, theme)),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.text)
+                            .child(subscription.channel.clone()),
+                    )
+                    .child(
+                        div()
+                            .id(SharedString::from(format!("notification-unlisten-{id}")))
+                            .rounded(px(2.0))
+                            .cursor_pointer()
+                            .hover(|style| style.bg(hover_bg))
+                            .on_click(cx.listener(move |panel, _, _, cx| panel.unlisten(id, cx)))
+                            .child(
+                                Icon::new(IconName::Close)
+                                    .size(IconSize::XSmall)
+                                    .color(theme.colors.text_muted),
+                            ),
+                    )
+            })
+            .collect();
+
+        div()
+            .w_full()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap(px(6.0))
+            .px(px(12.0))
+            .py(px(4.0))
+            .border_b_1()
+            .border_color(theme.colors.border_variant)
+            .child(div().w(px(200.0)).child(self.channel_input.clone()))
+            .when(self.connection_id.is_none(), |d| {
+                d.child(
+                    div()
+                        .text_size(px(11.0))
+                        .text_color(theme.colors.text_muted)
+                        .child("Connect to a database to listen"),
+                )
+            })
+            .children(chips)
+    }
+
+    /// Render one received notification.
+    fn render_notification(entry: &ReceivedNotification, theme: &TuskTheme) -> impl IntoElement {
+        let notification = &entry.notification;
+        div()
+            .flex()
+            .items_start()
+            .gap(px(8.0))
+            .px(px(12.0))
+            .py(px(2.0))
+            .text_size(px(12.0))
+            .font_family("monospace")
+            .child(div().text_color(theme.colors.text_muted).child(entry.received_at.clone()))
+            .child(div().text_color(theme.colors.accent).child(notification.channel.clone()))
+            .child(
+                div()
+                    .text_color(theme.colors.text_muted)
+                    .child(format!("pid {}", notification.pid)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .min_w_0()
+                    .text_color(theme.colors.text)
+                    .child(notification.payload.clone()),
+            )
+    }
+}
+
+impl EventEmitter<PanelEvent> for NotificationsPanel {}
+
+impl Focusable for NotificationsPanel {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Panel for NotificationsPanel {
+    fn panel_id(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn title(&self, _cx: &App) -> SharedString {
+        "Notifications".into()
+    }
+
+    fn icon(&self, _cx: &App) -> IconName {
+        IconName::Connected
+    }
+
+    fn focus(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        window.focus(&self.focus_handle, cx);
+    }
+
+    fn position(&self, _cx: &App) -> DockPosition {
+        DockPosition::Bottom
+    }
+}
+
+impl Render for NotificationsPanel {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+        let theme = &theme;
+        let hover_bg = theme.colors.element_hover;
+
+        let content = if self.log.is_empty() {
+            div()
+                .size_full()
+                .flex()
+                .items_center()
+                .justify_center()
+                .text_size(px(12.0))
+                .text_color(theme.colors.text_muted)
+                .child(if self.subscriptions.is_empty() {
+                    "Enter a channel to listen for notifications"
+                } else {
+                    "No notifications received"
+                })
+                .into_any_element()
+        } else {
+            let rows: Vec<_> =
+                self.log.entries().map(|entry| Self::render_notification(entry, theme)).collect();
+            div()
+                .id("notifications-list")
+                .size_full()
+                .overflow_y_scroll()
+                .py(px(4.0))
+                .children(rows)
+                .into_any_element()
+        };
+
+        div()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .flex()
+            .flex_col()
+            .bg(theme.colors.panel_background)
+            .child(
+                // Panel header
+                div()
+                    .h(px(32.0))
+                    .w_full()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .px(px(12.0))
+                    .border_b_1()
+                    .border_color(theme.colors.border)
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .child(Icon::new(IconName::Connected).size(IconSize::Small))
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .font_weight(gpui::FontWeight::MEDIUM)
+                                    .text_color(theme.colors.text)
+                                    .child("Notifications"),
+                            )
+                            .when(!self.log.is_empty(), |d| {
+                                let count = match self.log.dropped() {
+                                    0 => format!("({})", self.log.len()),
+                                    dropped => {
+                                        format!("({}, {dropped} older dropped)", self.log.len())
+                                    }
+                                };
+                                d.child(
+                                    div()
+                                        .text_size(px(10.0))
+                                        .text_color(theme.colors.text_muted)
+                                        .child(count),
+                                )
+                            }),
+                    )
+                    .when(!self.log.is_empty(), |d| {
+                        d.child(
+                            div()
+                                .id("notifications-clear")
+                                .px(px(6.0))
+                                .py(px(2.0))
+                                .rounded(px(4.0))
+                                .cursor_pointer()
+                                .text_size(px(11.0))
+                                .text_color(theme.colors.text_muted)
+                                .hover(|style| style.bg(hover_bg))
+                                .on_click(cx.listener(|panel, _, _, cx| panel.clear(cx)))
+                                .child("Clear"),
+                        )
+                    }),
+            )
+            .child(self.render_subscriptions(theme, cx))
+            .child(div().flex_1().overflow_hidden().child(content))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(payload: &str) -> ReceivedNotification {
+        ReceivedNotification {
+            notification: Notification {
+                channel: "jobs".to_string(),
+                payload: payload.to_string(),
+                pid: 4242,
+            },
+            received_at: "12:00:00".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_log_drops_oldest_past_capacity() {
+        let mut log = NotificationLog::new(2);
+        for payload in ["a", "b", "c"] {
+            log.push(received(payload));
+        }
+        let payloads: Vec<&str> =
+            log.entries().map(|entry| entry.notification.payload.as_str()).collect();
+        assert_eq!(payloads, vec!["b", "c"]);
+        assert_eq!(log.dropped(), 1);
+
+        log.clear();
+        assert!(log.is_empty());
+        assert_eq!(log.dropped(), 0);
+    }
+}
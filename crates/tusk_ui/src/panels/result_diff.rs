@@ -0,0 +1,246 @@
+//! Side-by-side comparison of two results of the same query tab.
+//!
+//! [`show_result_diff`] opens a modal comparing the displayed result with the
+//! previous run. Rows are paired by position or by a chosen key column and
+//! colored as added, removed, or changed; changed cells show the old value
+//! struck through next to the new one.
+
+use gpui::{div, prelude::*, px, App, Context, Hsla, Render, SharedString, Window};
+use tusk_core::{ColumnInfo, DiffKey, ResultDiff, RowChange};
+
+use crate::modal::{Modal, ModalAction, ModalLayer};
+use crate::TuskTheme;
+
+/// Rows rendered before the list is cut off.
+const MAX_DIFF_ROWS: usize = 200;
+
+/// Columns and decoded rows (None for NULL) of one side of the comparison.
+pub type DiffSide = (Vec<ColumnInfo>, Vec<Vec<Option<String>>>);
+
+/// Modal body rendering a [`ResultDiff`].
+pub struct ResultDiffView {
+    /// The earlier result.
+    before: DiffSide,
+    /// The later result.
+    after: DiffSide,
+    /// Comparison under the current key.
+    diff: ResultDiff,
+}
+
+impl ResultDiffView {
+    /// Compare two results, pairing rows by position.
+    pub fn new(before: DiffSide, after: DiffSide, _cx: &mut Context<Self>) -> Self {
+        let diff =
+            ResultDiff::compute(&before.0, &before.1, &after.0, &after.1, &DiffKey::Position);
+        Self { before, after, diff }
+    }
+
+    /// Get the current comparison.
+    pub fn diff(&self) -> &ResultDiff {
+        &self.diff
+    }
+
+    /// Re-pair rows with a different key.
+    pub fn set_key(&mut self, key: DiffKey, cx: &mut Context<Self>) {
+        self.diff =
+            ResultDiff::compute(&self.before.0, &self.before.1, &self.after.0, &self.after.1, &key);
+        cx.notify();
+    }
+
+    /// Columns both results share, offered as keys.
+    fn key_options(&self) -> Vec<String> {
+        self.diff
+            .columns
+            .iter()
+            .filter(|c| c.in_before && c.in_after)
+            .map(|c| c.name.clone())
+            .collect()
+    }
+
+    /// Render the key selector chips.
+    fn render_key_selector(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
+        let hover_bg = theme.colors.element_hover;
+        let options = std::iter::once(DiffKey::Position)
+            .chain(self.key_options().into_iter().map(|name| DiffKey::Columns(vec![name])));
+
+        div()
+            .flex()
+            .flex_wrap()
+            .items_center()
+            .gap(px(4.0))
+            .text_size(px(11.0))
+            .child(div().text_color(theme.colors.text_muted).child("Match rows by"))
+            .children(options.enumerate().map(|(index, key)| {
+                let label = match &key {
+                    DiffKey::Position => "Row position".to_string(),
+                    DiffKey::Columns(names) => names.join(", "),
+                };
+                let active = key == self.diff.key;
+                div()
+                    .id(("result-diff-key", index))
+                    .px(px(8.0))
+                    .py(px(2.0))
+                    .rounded(px(4.0))
+                    .cursor_pointer()
+                    .when(active, |d| {
+                        d.bg(theme.colors.element_background).text_color(theme.colors.text)
+                    })
+                    .when(!active, |d| {
+                        d.text_color(theme.colors.text_muted).hover(|style| style.bg(hover_bg))
+                    })
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.set_key(key.clone(), cx);
+                    }))
+                    .child(label)
+            }))
+    }
+}
+
+impl Render for ResultDiffView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+        let colors = &theme.colors;
+        let diff = &self.diff;
+        let tint = |color: Hsla| Hsla { a: 0.15, ..color };
+
+        let summary = if diff.is_identical() {
+            "Results are identical".to_string()
+        } else {
+            format!(
+                "{} added, {} removed, {} changed",
+                diff.added(),
+                diff.removed(),
+                diff.changed()
+            )
+        };
+        let hidden = diff.rows.len().saturating_sub(MAX_DIFF_ROWS);
+
+        let header = div()
+            .flex()
+            .h(px(24.0))
+            .items_center()
+            .bg(colors.element_background)
+            .border_b_1()
+            .border_color(colors.border)
+            .child(div().w(px(20.0)).flex_shrink_0())
+            .children(diff.columns.iter().map(|column| {
+                let side = match (column.in_before, column.in_after) {
+                    (true, false) => " (removed)",
+                    (false, true) => " (added)",
+                    _ => "",
+                };
+                div()
+                    .flex_1()
+                    .min_w(px(100.0))
+                    .px(px(8.0))
+                    .truncate()
+                    .font_weight(gpui::FontWeight::MEDIUM)
+                    .text_color(if side.is_empty() { colors.text_muted } else { colors.warning })
+                    .child(format!("{}{}", column.name, side))
+            }));
+
+        let rows = diff.rows.iter().take(MAX_DIFF_ROWS).map(|row| {
+            let (marker, background) = match row.change {
+                RowChange::Unchanged => (" ", None),
+                RowChange::Changed => ("~", Some(tint(colors.warning))),
+                RowChange::Added => ("+", Some(tint(colors.success))),
+                RowChange::Removed => ("-", Some(tint(colors.error))),
+            };
+            // Added and changed rows show their new values, removed rows their old
+            let shows_after = row.after.is_some();
+            let values = row.after.as_ref().or(row.before.as_ref());
+
+            div()
+                .flex()
+                .h(px(24.0))
+                .items_center()
+                .when_some(background, |d, bg| d.bg(bg))
+                .child(div().w(px(20.0)).flex_shrink_0().px(px(6.0)).child(marker))
+                .children((0..diff.columns.len()).map(|index| {
+                    let column = &diff.columns[index];
+                    let present = if shows_after { column.in_after } else { column.in_before };
+                    let value = values.and_then(|v| v[index].clone());
+                    let cell = div().flex_1().min_w(px(100.0)).px(px(8.0)).truncate();
+                    if row.changed_cells.contains(&index) {
+                        let old = row.before.as_ref().and_then(|v| v[index].clone());
+                        cell.flex()
+                            .gap(px(4.0))
+                            .child(
+                                div()
+                                    .line_through()
+                                    .text_color(colors.error)
+                                    .child(old.unwrap_or_else(|| "NULL".to_string())),
+                            )
+                            .child(
+                                div()
+                                    .text_color(colors.success)
+                                    .child(value.unwrap_or_else(|| "NULL".to_string())),
+                            )
+                    } else if present {
+                        cell.text_color(colors.text)
+                            .child(value.unwrap_or_else(|| "NULL".to_string()))
+                    } else {
+                        cell
+                    }
+                }))
+        });
+
+        div()
+            .flex()
+            .flex_col()
+            .gap(px(8.0))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .justify_between()
+                    .gap(px(8.0))
+                    .child(self.render_key_selector(&theme, cx))
+                    .child(div().text_size(px(11.0)).text_color(colors.text_muted).child(summary)),
+            )
+            .child(
+                div()
+                    .id("result-diff-rows")
+                    .flex()
+                    .flex_col()
+                    .max_h(px(400.0))
+                    .overflow_y_scroll()
+                    .border_1()
+                    .border_color(colors.border)
+                    .rounded(px(6.0))
+                    .text_size(px(12.0))
+                    .child(header)
+                    .children(rows),
+            )
+            .when(hidden > 0, |d| {
+                d.child(
+                    div()
+                        .text_size(px(11.0))
+                        .text_color(colors.text_muted)
+                        .child(format!("{} more rows not shown", hidden)),
+                )
+            })
+    }
+}
+
+/// Show the comparison of two results in a modal.
+pub fn show_result_diff(
+    subtitle: impl Into<SharedString>,
+    before: DiffSide,
+    after: DiffSide,
+    cx: &mut App,
+) {
+    let content = cx.new(|cx| ResultDiffView::new(before, after, cx));
+
+    let modal = cx.new(|cx| {
+        Modal::new("Compare Results", cx)
+            .subtitle(subtitle)
+            .width(800.0)
+            .body(content.into())
+            .action(ModalAction::confirm("Close"))
+    });
+
+    cx.update_global::<ModalLayer, _>(|layer, cx| {
+        layer.show(modal, cx);
+    });
+}
@@ -0,0 +1,244 @@
+//! Per-tab history of recent query results.
+//!
+//! Each query tab keeps a bounded ring buffer of result snapshots so a
+//! previous run can be brought back and compared with the latest one. When
+//! the buffer is full the oldest snapshot is evicted.
+
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+use tusk_core::QueryService;
+
+use super::results::ResultsPanelState;
+
+/// Default number of results kept per query tab.
+pub const DEFAULT_RESULT_HISTORY: usize = 10;
+
+/// A completed run captured for the result history.
+#[derive(Debug, Clone)]
+pub struct ResultSnapshot {
+    /// SQL that produced the results
+    pub sql: String,
+    /// When the run completed
+    pub captured_at: SystemTime,
+    /// Result sets in statement order (one for a single query)
+    pub result_sets: Vec<ResultsPanelState>,
+}
+
+impl ResultSnapshot {
+    /// Capture result sets for the given SQL, stamped with the current time.
+    pub fn new(sql: impl Into<String>, result_sets: Vec<ResultsPanelState>) -> Self {
+        Self { sql: sql.into(), captured_at: SystemTime::now(), result_sets }
+    }
+
+    /// Time since the snapshot was captured.
+    pub fn age(&self) -> Duration {
+        self.captured_at.elapsed().unwrap_or_default()
+    }
+
+    /// Check if the run was an EXPLAIN ANALYZE.
+    ///
+    /// Unlike a plain EXPLAIN, it ran the explained statement, including
+    /// any writes it makes.
+    pub fn is_explain_analyze(&self) -> bool {
+        QueryService::is_explain_analyze(&self.sql)
+    }
+}
+
+/// Bounded ring buffer with a cursor for previous/next navigation.
+///
+/// Pushing moves the cursor to the newest entry. The cursor always points at
+/// a stored entry while the buffer is non-empty.
+#[derive(Debug, Clone)]
+pub struct ResultHistory<T> {
+    entries: VecDeque<T>,
+    capacity: usize,
+    cursor: usize,
+}
+
+impl<T> ResultHistory<T> {
+    /// Create a history holding at most `capacity` entries (at least one).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self { entries: VecDeque::with_capacity(capacity), capacity, cursor: 0 }
+    }
+
+    /// Maximum number of entries kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of stored entries.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if nothing has been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Record a new entry, evicting the oldest when full, and select it.
+    pub fn push(&mut self, entry: T) {
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+        self.cursor = self.entries.len() - 1;
+    }
+
+    /// Index of the selected entry, oldest first.
+    pub fn cursor(&self) -> Option<usize> {
+        (!self.entries.is_empty()).then_some(self.cursor)
+    }
+
+    /// The selected entry.
+    pub fn current(&self) -> Option<&T> {
+        self.entries.get(self.cursor)
+    }
+
+    /// The entry just before the selected one.
+    pub fn previous(&self) -> Option<&T> {
+        self.entries.get(self.cursor.checked_sub(1)?)
+    }
+
+    /// The most recent entry.
+    pub fn latest(&self) -> Option<&T> {
+        self.entries.back()
+    }
+
+    /// Check if an older entry exists before the cursor.
+    pub fn has_previous(&self) -> bool {
+        self.cursor > 0
+    }
+
+    /// Check if a newer entry exists after the cursor.
+    pub fn has_next(&self) -> bool {
+        self.cursor + 1 < self.entries.len()
+    }
+
+    /// Move to the previous (older) entry.
+    pub fn select_previous(&mut self) -> Option<&T> {
+        if !self.has_previous() {
+            return None;
+        }
+        self.cursor -= 1;
+        self.current()
+    }
+
+    /// Move to the next (newer) entry.
+    pub fn select_next(&mut self) -> Option<&T> {
+        if !self.has_next() {
+            return None;
+        }
+        self.cursor += 1;
+        self.current()
+    }
+
+    /// Iterate over entries, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.entries.iter()
+    }
+}
+
+impl<T> Default for ResultHistory<T> {
+    fn default() -> Self {
+        Self::new(DEFAULT_RESULT_HISTORY)
+    }
+}
+
+/// Format a snapshot age compactly (e.g. "just now", "5m ago").
+pub fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+    match secs {
+        0..=4 => "just now".to_string(),
+        5..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_evicts_oldest_at_capacity() {
+        let mut history = ResultHistory::new(3);
+        for run in 1..=5 {
+            history.push(run);
+        }
+
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.iter().copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+        assert_eq!(history.current(), Some(&5));
+        assert_eq!(history.cursor(), Some(2));
+    }
+
+    #[test]
+    fn test_navigation_stays_in_bounds() {
+        let mut history = ResultHistory::new(3);
+        assert_eq!(history.cursor(), None);
+        assert_eq!(history.select_previous(), None);
+        assert_eq!(history.select_next(), None);
+
+        history.push("a");
+        history.push("b");
+        history.push("c");
+        history.push("d");
+
+        assert_eq!(history.previous(), Some(&"c"));
+        assert_eq!(history.select_previous(), Some(&"c"));
+        assert_eq!(history.select_previous(), Some(&"b"));
+        assert_eq!(history.previous(), None);
+        assert_eq!(history.select_previous(), None);
+        assert_eq!(history.cursor(), Some(0));
+        assert!(!history.has_previous());
+
+        assert_eq!(history.select_next(), Some(&"c"));
+        assert_eq!(history.select_next(), Some(&"d"));
+        assert_eq!(history.select_next(), None);
+        assert_eq!(history.cursor(), Some(2));
+        assert!(!history.has_next());
+    }
+
+    #[test]
+    fn test_push_after_navigating_selects_newest() {
+        let mut history = ResultHistory::new(2);
+        history.push(1);
+        history.push(2);
+        history.select_previous();
+        assert_eq!(history.current(), Some(&1));
+
+        history.push(3);
+        assert_eq!(history.current(), Some(&3));
+        assert_eq!(history.cursor(), Some(1));
+        assert_eq!(history.select_previous(), Some(&2));
+    }
+
+    #[test]
+    fn test_zero_capacity_keeps_one_entry() {
+        let mut history = ResultHistory::new(0);
+        history.push("x");
+        history.push("y");
+        assert_eq!(history.capacity(), 1);
+        assert_eq!(history.iter().collect::<Vec<_>>(), vec![&"y"]);
+    }
+
+    #[test]
+    fn test_snapshot_flags_explain_analyze() {
+        assert!(ResultSnapshot::new("EXPLAIN ANALYZE DELETE FROM t", vec![]).is_explain_analyze());
+        assert!(!ResultSnapshot::new("EXPLAIN SELECT 1", vec![]).is_explain_analyze());
+        assert!(!ResultSnapshot::new("SELECT 'analyze'", vec![]).is_explain_analyze());
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(2)), "just now");
+        assert_eq!(format_age(Duration::from_secs(42)), "42s ago");
+        assert_eq!(format_age(Duration::from_secs(185)), "3m ago");
+        assert_eq!(format_age(Duration::from_secs(7200)), "2h ago");
+        assert_eq!(format_age(Duration::from_secs(3 * 86_400)), "3d ago");
+    }
+}
@@ -0,0 +1,603 @@
+//! Client-side sorting of a result grid by one column.
+//!
+//! Cells are compared according to the column's PostgreSQL type rather than
+//! as text: numbers by value, dates and times chronologically (applying time
+//! zone offsets), intervals by length, and booleans false before true. NULL
+//! cells sort last in both directions, after any cells that don't parse as
+//! the column's type. Rows that compare equal keep their previous order.
+//!
+//! Sorting reorders the rows in place and records where each row arrived,
+//! so clearing the sort restores the order the server returned.
+
+use std::cmp::Ordering;
+
+use super::results::{DisplayColumn, DisplayRow};
+
+/// Microseconds in a day, as used for interval and timestamp keys.
+const DAY_MICROS: i128 = 86_400_000_000;
+
+/// Direction of a column sort.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortDirection {
+    /// Smallest values first.
+    Ascending,
+    /// Largest values first.
+    Descending,
+}
+
+/// The column a result is sorted by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ColumnSort {
+    /// Source column index
+    pub col: usize,
+    /// Sort direction
+    pub direction: SortDirection,
+}
+
+impl ColumnSort {
+    /// The sort after clicking the header of `col`.
+    ///
+    /// Clicking cycles a column through ascending, descending, and unsorted;
+    /// clicking a different column starts it ascending.
+    pub fn cycle(current: Option<Self>, col: usize) -> Option<Self> {
+        match current {
+            Some(sort) if sort.col == col => match sort.direction {
+                SortDirection::Ascending => {
+                    Some(Self { col, direction: SortDirection::Descending })
+                }
+                SortDirection::Descending => None,
+            },
+            _ => Some(Self { col, direction: SortDirection::Ascending }),
+        }
+    }
+}
+
+/// How the cells of a column are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKind {
+    /// Integers, floating point, and `numeric`.
+    Numeric,
+    /// Dates, times, timestamps, and intervals.
+    Temporal,
+    /// `true` and `false`.
+    Boolean,
+    /// Everything else, compared case-insensitively.
+    Text,
+}
+
+impl SortKind {
+    /// Choose the comparison for a PostgreSQL type name (e.g. `int4`, `timestamptz`).
+    pub fn for_type(type_name: &str) -> Self {
+        match type_name {
+            "int2" | "int4" | "int8" | "float4" | "float8" | "numeric" | "oid" => Self::Numeric,
+            "date" | "time" | "timetz" | "timestamp" | "timestamptz" | "interval" => Self::Temporal,
+            "bool" => Self::Boolean,
+            _ => Self::Text,
+        }
+    }
+}
+
+/// Comparable form of one cell.
+#[derive(Debug, Clone, PartialEq)]
+enum CellKey {
+    /// A number, with its exact integer value when it has one.
+    Number(f64, Option<i128>),
+    /// Microseconds from an origin shared by the column's values.
+    Time(i128),
+    /// A boolean.
+    Bool(bool),
+    /// Text, lowercased for comparison, with the original to break ties.
+    Text(String, String),
+}
+
+impl CellKey {
+    /// Parse a non-NULL cell, or None if it isn't a valid value of `kind`.
+    fn parse(kind: SortKind, text: &str) -> Option<Self> {
+        match kind {
+            SortKind::Numeric => {
+                let number = text.parse::<f64>().ok()?;
+                Some(Self::Number(number, text.parse::<i128>().ok()))
+            }
+            SortKind::Temporal => temporal_key(text).map(Self::Time),
+            SortKind::Boolean => match text {
+                "true" | "t" => Some(Self::Bool(true)),
+                "false" | "f" => Some(Self::Bool(false)),
+                _ => None,
+            },
+            SortKind::Text => Some(Self::text(text)),
+        }
+    }
+
+    fn text(text: &str) -> Self {
+        Self::Text(text.to_lowercase(), text.to_string())
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::Number(a, a_int), Self::Number(b, b_int)) => {
+                a.total_cmp(b).then_with(|| match (a_int, b_int) {
+                    // Large integers can round to the same float
+                    (Some(a), Some(b)) => a.cmp(b),
+                    _ => Ordering::Equal,
+                })
+            }
+            (Self::Time(a), Self::Time(b)) => a.cmp(b),
+            (Self::Bool(a), Self::Bool(b)) => a.cmp(b),
+            (Self::Text(a, a_raw), Self::Text(b, b_raw)) => a.cmp(b).then_with(|| a_raw.cmp(b_raw)),
+            // Keys of one column share a variant
+            _ => Ordering::Equal,
+        }
+    }
+}
+
+/// Sort key of a cell: parsed values first, then unparsed text, then NULL.
+#[derive(Debug, Clone, PartialEq)]
+enum SortKey {
+    Value(CellKey),
+    Unparsed(CellKey),
+    Null,
+}
+
+impl SortKey {
+    /// Key of a row's cell in column `col`.
+    fn of(kind: SortKind, row: &DisplayRow, col: usize) -> Self {
+        Self::new(kind, row.cells.get(col).and_then(Option::as_deref))
+    }
+
+    fn new(kind: SortKind, cell: Option<&str>) -> Self {
+        match cell {
+            None => Self::Null,
+            Some(text) => match CellKey::parse(kind, text) {
+                Some(key) => Self::Value(key),
+                None => Self::Unparsed(CellKey::text(text)),
+            },
+        }
+    }
+
+    /// Compare two keys; NULLs and unparsed cells stay last in either direction.
+    fn compare(&self, other: &Self, direction: SortDirection) -> Ordering {
+        let directed = |ordering: Ordering| match direction {
+            SortDirection::Ascending => ordering,
+            SortDirection::Descending => ordering.reverse(),
+        };
+        match (self, other) {
+            (Self::Value(a), Self::Value(b)) | (Self::Unparsed(a), Self::Unparsed(b)) => {
+                directed(a.compare(b))
+            }
+            (Self::Null, Self::Null) => Ordering::Equal,
+            (a, b) => a.rank().cmp(&b.rank()),
+        }
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Value(_) => 0,
+            Self::Unparsed(_) => 1,
+            Self::Null => 2,
+        }
+    }
+}
+
+/// Sort `rows` by `sort`, or restore their arrival order when it's None.
+///
+/// `arrival` holds the arrival position of each row while sorted and is
+/// empty when the rows are in arrival order. Rows appended since the last
+/// sort are taken to have arrived last.
+pub fn sort_rows(
+    columns: &[DisplayColumn],
+    rows: &mut Vec<DisplayRow>,
+    arrival: &mut Vec<usize>,
+    sort: Option<ColumnSort>,
+) {
+    let start = arrival.len();
+    arrival.extend(start..rows.len());
+
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    match sort {
+        Some(ColumnSort { col, direction }) => {
+            let kind = column_kind(columns, col);
+            let keys: Vec<SortKey> = rows.iter().map(|row| SortKey::of(kind, row, col)).collect();
+            order.sort_by(|&a, &b| keys[a].compare(&keys[b], direction));
+        }
+        None => order.sort_by_key(|&i| arrival[i]),
+    }
+
+    *rows = permute(std::mem::take(rows), &order);
+    *arrival = match sort {
+        Some(_) => order.iter().map(|&i| arrival[i]).collect(),
+        None => Vec::new(),
+    };
+}
+
+/// Merge rows appended since the last sort into the sorted rows before them.
+///
+/// Only the new rows are sorted; each is then placed after the sorted rows
+/// it doesn't compare before, as a full stable sort would, so a streamed
+/// result stays sorted without re-sorting every row per batch.
+pub fn merge_new_rows(
+    columns: &[DisplayColumn],
+    rows: &mut Vec<DisplayRow>,
+    arrival: &mut Vec<usize>,
+    sort: ColumnSort,
+) {
+    let start = arrival.len();
+    if start >= rows.len() {
+        return;
+    }
+    let ColumnSort { col, direction } = sort;
+    let kind = column_kind(columns, col);
+
+    let mut new: Vec<(SortKey, usize, DisplayRow)> = rows
+        .drain(start..)
+        .enumerate()
+        .map(|(i, row)| (SortKey::of(kind, &row, col), start + i, row))
+        .collect();
+    new.sort_by(|a, b| a.0.compare(&b.0, direction));
+    let positions: Vec<usize> = new
+        .iter()
+        .map(|(key, ..)| {
+            rows.partition_point(|row| {
+                SortKey::of(kind, row, col).compare(key, direction) != Ordering::Greater
+            })
+        })
+        .collect();
+
+    let mut sorted = std::mem::take(rows).into_iter().zip(std::mem::take(arrival));
+    let mut taken = 0;
+    for ((_, arrived, row), position) in new.into_iter().zip(positions) {
+        for (row, arrived) in sorted.by_ref().take(position - taken) {
+            rows.push(row);
+            arrival.push(arrived);
+        }
+        taken = position;
+        rows.push(row);
+        arrival.push(arrived);
+    }
+    for (row, arrived) in sorted {
+        rows.push(row);
+        arrival.push(arrived);
+    }
+}
+
+/// Comparison used for a column.
+fn column_kind(columns: &[DisplayColumn], col: usize) -> SortKind {
+    columns.get(col).map_or(SortKind::Text, |column| SortKind::for_type(&column.type_name))
+}
+
+/// Reorder `items` so that position `n` holds `items[order[n]]`.
+fn permute<T>(items: Vec<T>, order: &[usize]) -> Vec<T> {
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    order.iter().filter_map(|&i| slots.get_mut(i).and_then(Option::take)).collect()
+}
+
+/// Microsecond key for a date, time, timestamp, or interval in PostgreSQL's
+/// default (ISO) output style.
+///
+/// Dates and timestamps count from 1970-01-01 UTC, times from midnight, and
+/// intervals use PostgreSQL's own comparison rules (30-day months, 24-hour
+/// days). `infinity` and `-infinity` sort after and before every other value.
+fn temporal_key(text: &str) -> Option<i128> {
+    match text {
+        "infinity" => return Some(i128::MAX),
+        "-infinity" => return Some(i128::MIN),
+        _ => {}
+    }
+    if let Some(key) = timestamp_key(text) {
+        return Some(key);
+    }
+    if let Some((micros, offset)) = time_with_offset(text) {
+        return Some(micros - offset);
+    }
+    interval_key(text)
+}
+
+/// Key for `YYYY-MM-DD[ HH:MM:SS[.ffffff][+HH[:MM]]][ BC]`.
+fn timestamp_key(text: &str) -> Option<i128> {
+    let (text, bc) = match text.strip_suffix(" BC") {
+        Some(text) => (text, true),
+        None => (text, false),
+    };
+    let (date, time) = match text.split_once([' ', 'T']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (text, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let year: i64 = digits(parts.next()?)?;
+    let month: i64 = digits(parts.next()?)?;
+    let day: i64 = digits(parts.next()?)?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    // 1 BC is year 0 in the proleptic Gregorian calendar
+    let year = if bc { 1 - year } else { year };
+
+    let (micros, offset) = match time {
+        Some(time) => time_with_offset(time)?,
+        None => (0, 0),
+    };
+    Some(days_from_civil(year, month, day) as i128 * DAY_MICROS + micros - offset)
+}
+
+/// Parse `HH:MM:SS[.ffffff]` with an optional `+HH[:MM[:SS]]` or `-HH...`
+/// offset, returning microseconds past midnight and the offset in microseconds.
+fn time_with_offset(text: &str) -> Option<(i128, i128)> {
+    let (time, offset) = match text.find(['+', '-']) {
+        Some(at) => {
+            let sign = if text[at..].starts_with('-') { -1 } else { 1 };
+            (&text[..at], sign * clock_micros(&text[at + 1..])?)
+        }
+        None => (text, 0),
+    };
+    Some((clock_micros(time)?, offset))
+}
+
+/// Parse `HH[:MM[:SS[.ffffff]]]` into microseconds; hours may exceed 24.
+fn clock_micros(text: &str) -> Option<i128> {
+    let mut parts = text.splitn(3, ':');
+    let hours: i128 = digits(parts.next()?)?;
+    let minutes: i128 = parts.next().map_or(Some(0), digits)?;
+    let (seconds, fraction) = match parts.next() {
+        Some(seconds) => match seconds.split_once('.') {
+            Some((whole, fraction)) => (digits(whole)?, fraction_micros(fraction)?),
+            None => (digits(seconds)?, 0),
+        },
+        None => (0, 0),
+    };
+    if minutes >= 60 || seconds >= 61 {
+        return None;
+    }
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1_000_000 + fraction)
+}
+
+/// Parse up to six fractional-second digits into microseconds.
+fn fraction_micros(text: &str) -> Option<i128> {
+    if text.is_empty() || text.len() > 6 {
+        return None;
+    }
+    let value: i128 = digits(text)?;
+    Some(value * 10_i128.pow(6 - text.len() as u32))
+}
+
+/// Key for an interval such as `1 year 2 mons -3 days +04:05:06.5`.
+fn interval_key(text: &str) -> Option<i128> {
+    let mut total: i128 = 0;
+    let mut tokens = text.split_whitespace().peekable();
+    tokens.peek()?;
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            let (sign, clock) = match token.as_bytes()[0] {
+                b'-' => (-1, &token[1..]),
+                b'+' => (1, &token[1..]),
+                _ => (1, token),
+            };
+            total += sign * clock_micros(clock)?;
+            continue;
+        }
+        let amount: i128 = token.parse().ok()?;
+        let unit_days = match tokens.next()? {
+            "year" | "years" => 360,
+            "mon" | "mons" => 30,
+            "day" | "days" => 1,
+            _ => return None,
+        };
+        total += amount * unit_days * DAY_MICROS;
+    }
+    Some(total)
+}
+
+/// Parse a run of ASCII digits.
+fn digits<T: std::str::FromStr>(text: &str) -> Option<T> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
+
+/// Days from 1970-01-01 to a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month_index = (month + 9) % 12;
+    let day_of_year = (153 * month_index + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(type_name: &str) -> DisplayColumn {
+        DisplayColumn {
+            name: type_name.to_string(),
+            type_oid: 0,
+            type_name: type_name.to_string(),
+            table_oid: None,
+            column_id: None,
+        }
+    }
+
+    fn rows(values: &[&str]) -> Vec<DisplayRow> {
+        values
+            .iter()
+            .map(|v| DisplayRow { cells: vec![(*v != "NULL").then(|| v.to_string())] })
+            .collect()
+    }
+
+    fn cells(rows: &[DisplayRow]) -> Vec<&str> {
+        rows.iter().filter_map(|row| row.display(0)).collect()
+    }
+
+    fn sorted(type_name: &str, values: &[&str], direction: SortDirection) -> Vec<String> {
+        let mut rows = rows(values);
+        let mut arrival = Vec::new();
+        let sort = ColumnSort { col: 0, direction };
+        sort_rows(&[column(type_name)], &mut rows, &mut arrival, Some(sort));
+        cells(&rows).into_iter().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_sort_cycles_through_directions() {
+        let ascending = ColumnSort::cycle(None, 2);
+        assert_eq!(ascending, Some(ColumnSort { col: 2, direction: SortDirection::Ascending }));
+        let descending = ColumnSort::cycle(ascending, 2);
+        assert_eq!(descending, Some(ColumnSort { col: 2, direction: SortDirection::Descending }));
+        assert_eq!(ColumnSort::cycle(descending, 2), None);
+        assert_eq!(ColumnSort::cycle(descending, 0), ColumnSort::cycle(None, 0));
+    }
+
+    #[test]
+    fn test_numbers_sort_by_value_with_nulls_last() {
+        let values = ["10", "NULL", "9", "-2.5", "NaN", "9007199254740993", "9007199254740992"];
+        assert_eq!(
+            sorted("numeric", &values, SortDirection::Ascending),
+            ["-2.5", "9", "10", "9007199254740992", "9007199254740993", "NaN", "NULL"]
+        );
+        assert_eq!(
+            sorted("int8", &values, SortDirection::Descending),
+            ["NaN", "9007199254740993", "9007199254740992", "10", "9", "-2.5", "NULL"]
+        );
+        // Text columns compare case-insensitively, not by value
+        assert_eq!(
+            sorted("text", &["b", "10", "A", "9"], SortDirection::Ascending),
+            ["10", "9", "A", "b"]
+        );
+    }
+
+    #[test]
+    fn test_unparsed_cells_sort_after_values() {
+        assert_eq!(
+            sorted("bool", &["NULL", "maybe", "true", "false"], SortDirection::Descending),
+            ["true", "false", "maybe", "NULL"]
+        );
+    }
+
+    #[test]
+    fn test_timestamps_sort_chronologically() {
+        let values = [
+            "2024-03-01 09:00:00+02",
+            "2024-03-01 08:30:00+00",
+            "infinity",
+            "0044-03-15 BC",
+            "2024-02-29",
+            "-infinity",
+            "10000-01-01",
+        ];
+        assert_eq!(
+            sorted("timestamptz", &values, SortDirection::Ascending),
+            [
+                "-infinity",
+                "0044-03-15 BC",
+                "2024-02-29",
+                // 07:00 UTC comes before 08:30 UTC
+                "2024-03-01 09:00:00+02",
+                "2024-03-01 08:30:00+00",
+                "10000-01-01",
+                "infinity",
+            ]
+        );
+        assert_eq!(
+            sorted("time", &["23:59:59.5", "09:00:00", "23:59:59.25"], SortDirection::Ascending),
+            ["09:00:00", "23:59:59.25", "23:59:59.5"]
+        );
+    }
+
+    #[test]
+    fn test_intervals_sort_by_length() {
+        let values = ["1 mon", "29 days 23:00:00", "1 year", "-1 days +02:00:00", "00:30:00"];
+        assert_eq!(
+            sorted("interval", &values, SortDirection::Ascending),
+            ["-1 days +02:00:00", "00:30:00", "29 days 23:00:00", "1 mon", "1 year"]
+        );
+    }
+
+    #[test]
+    fn test_clearing_sort_restores_arrival_order() {
+        let columns = [column("int4")];
+        let mut rows = rows(&["3", "1", "2"]);
+        let mut arrival = Vec::new();
+        let ascending = ColumnSort { col: 0, direction: SortDirection::Ascending };
+        sort_rows(&columns, &mut rows, &mut arrival, Some(ascending));
+        assert_eq!(cells(&rows), ["1", "2", "3"]);
+        assert_eq!(arrival, [1, 2, 0]);
+
+        // Streamed rows are merged into the sorted ones
+        rows.extend(self::rows(&["0", "5"]));
+        merge_new_rows(&columns, &mut rows, &mut arrival, ascending);
+        assert_eq!(cells(&rows), ["0", "1", "2", "3", "5"]);
+        assert_eq!(arrival, [3, 1, 2, 0, 4]);
+
+        sort_rows(&columns, &mut rows, &mut arrival, None);
+        assert_eq!(cells(&rows), ["3", "1", "2", "0", "5"]);
+        assert!(arrival.is_empty());
+    }
+
+    #[test]
+    fn test_merged_batches_match_a_full_sort() {
+        let columns = [column("numeric")];
+        let descending = ColumnSort { col: 0, direction: SortDirection::Descending };
+        let batches: [&[&str]; 3] =
+            [&["2", "NULL", "10"], &["2.0", "x", "11", "NULL"], &["-1", "10", "2"]];
+
+        let mut merged = Vec::new();
+        let mut arrival = Vec::new();
+        sort_rows(&columns, &mut merged, &mut arrival, Some(descending));
+        for batch in batches {
+            merged.extend(rows(batch));
+            merge_new_rows(&columns, &mut merged, &mut arrival, descending);
+        }
+
+        let mut full = rows(&batches.concat());
+        let mut full_arrival = Vec::new();
+        sort_rows(&columns, &mut full, &mut full_arrival, Some(descending));
+        assert_eq!(cells(&merged), cells(&full));
+        assert_eq!(arrival, full_arrival);
+        assert_eq!(cells(&merged), ["11", "10", "10", "2", "2.0", "2", "-1", "x", "NULL", "NULL"]);
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_decoded_temporal_cells_sort_chronologically() {
+        use tokio_postgres::types::Type;
+        use tusk_core::models::value::scalar_text;
+
+        const DAY: i64 = 86_400_000_000;
+        let decode = |ty: &Type, raw: &[u8]| scalar_text(ty, raw).unwrap().unwrap();
+        let stamps: Vec<String> = [DAY * 400, -DAY * 800_000, 5_000_000, i64::MAX, -DAY]
+            .iter()
+            .map(|usecs| decode(&Type::TIMESTAMPTZ, &usecs.to_be_bytes()))
+            .collect();
+        let stamps: Vec<&str> = stamps.iter().map(String::as_str).collect();
+        assert_eq!(
+            sorted("timestamptz", &stamps, SortDirection::Ascending),
+            [
+                "0192-09-05 00:00:00+00 BC",
+                "1999-12-31 00:00:00+00",
+                "2000-01-01 00:00:05+00",
+                "2001-02-04 00:00:00+00",
+                "infinity",
+            ]
+        );
+
+        let interval = |usecs: i64, days: i32, months: i32| {
+            let mut raw = usecs.to_be_bytes().to_vec();
+            raw.extend(days.to_be_bytes());
+            raw.extend(months.to_be_bytes());
+            decode(&Type::INTERVAL, &raw)
+        };
+        let intervals = [interval(0, 0, 14), interval(-90_000_000, -2, 0), interval(0, 31, 0)];
+        let intervals: Vec<&str> = intervals.iter().map(String::as_str).collect();
+        assert_eq!(
+            sorted("interval", &intervals, SortDirection::Descending),
+            ["1 year 2 mons", "31 days", "-2 days -00:01:30"]
+        );
+    }
+
+    #[test]
+    fn test_days_from_civil() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        assert_eq!(days_from_civil(2000, 3, 1), 11_017);
+        assert_eq!(days_from_civil(1969, 12, 31), -1);
+    }
+}
@@ -8,24 +8,60 @@
 //! - Column metadata display (FR-014)
 //! - Execution time and row count (FR-015)
 //! - Error display with details
+//! - Array and composite values rendered in PostgreSQL text form
+//! - Result selector for batches that return several result sets
+//! - Cell selection with a numeric summary for the status bar
+//! - Keyboard selection (Shift+arrows) and copying cells as TSV, CSV,
+//!   Markdown, or `INSERT` statements
+//! - Per-tab history of recent results with previous/next navigation
+//! - Copy as a Markdown table and export to CSV/JSON files
+//! - Command status (e.g. "SET") for statements that return no columns
+//! - Pretty-printed JSON view for cells holding JSON objects or arrays
+//! - Cell inspector popover (double-click) for long text and JSON values
+//! - Edit mode that turns a changed cell into an `UPDATE` for review
+//! - Comparison of the displayed result with the tab's previous run
+//! - Per-tab column hiding and reordering, applied to exports
+//! - Type-aware sorting by clicking a column header, NULLs last
+
+use std::collections::HashMap;
 
 use gpui::{
-    div, prelude::*, px, App, Context, EventEmitter, FocusHandle, Render, SharedString, Task,
-    Window,
+    anchored, deferred, div, prelude::*, px, App, Context, Entity, EntityId, EventEmitter,
+    FocusHandle, MouseButton, MouseDownEvent, Pixels, Point, Render, SharedString, Subscription,
+    Task, Window,
 };
 
+use super::cell_inspector::{CellInspector, CellInspectorEvent};
+use super::column_layout::ColumnLayout;
+use super::json_view::{looks_like_json, show_json_view};
+use super::messages::Message;
+use super::result_history::{format_age, ResultHistory, ResultSnapshot};
+use super::result_sort::{merge_new_rows, sort_rows, ColumnSort, SortDirection};
+use super::schema_browser::format_bytes;
+use super::selection::{CellPosition, CellSelection, SelectionSummary};
+use crate::context_menu::{ContextMenu, ContextMenuItem, ContextMenuLayer};
 use crate::icon::{Icon, IconName, IconSize};
+use crate::key_bindings::{results_grid, ExportResults};
+use crate::modal::{Modal, ModalAction, ModalEvent, ModalLayer};
 use crate::panel::{DockPosition, Focusable, Panel, PanelEvent};
 use crate::spinner::{Spinner, SpinnerSize};
+use crate::status_bar::{format_elapsed, ExecutionEvent};
+use crate::text_input::TextInput;
 use crate::tooltip::Tooltip;
 use crate::TuskTheme;
 
 #[cfg(feature = "persistence")]
-use tusk_core::{ColumnInfo, QueryEvent, TuskError};
+use tusk_core::{
+    models::{command_status, estimate_result_bytes, query::LARGE_RESULT_BYTES},
+    ColumnInfo, EditTarget, QueryEvent, ScriptEvent, TuskError,
+};
 
 #[cfg(feature = "persistence")]
 use tokio::sync::mpsc;
 
+/// Most rows rendered in the grid.
+const MAX_DISPLAYED_ROWS: usize = 100;
+
 /// Status of the results panel (FR-014, FR-015).
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum ResultsStatus {
@@ -79,17 +115,45 @@ impl ResultsStatus {
 pub struct DisplayColumn {
     /// Column name
     pub name: String,
+    /// PostgreSQL type OID
+    pub type_oid: u32,
     /// PostgreSQL type name
     pub type_name: String,
+    /// OID of the source table (None for computed columns)
+    pub table_oid: Option<u32>,
+    /// Attribute number within the source table
+    pub column_id: Option<i16>,
 }
 
 #[cfg(feature = "persistence")]
 impl From<ColumnInfo> for DisplayColumn {
     fn from(col: ColumnInfo) -> Self {
-        Self { name: col.name, type_name: col.type_name }
+        Self {
+            name: col.name,
+            type_oid: col.type_oid,
+            type_name: col.type_name,
+            table_oid: col.table_oid,
+            column_id: col.column_id,
+        }
     }
 }
 
+/// Result sets of a script streaming one statement at a time.
+#[cfg(feature = "persistence")]
+#[derive(Debug, Default)]
+struct ScriptRun {
+    /// Statement whose result is streaming into the displayed state, 0 before the first
+    statement: usize,
+    /// Results of the statements that already ended
+    finished: Vec<ResultsPanelState>,
+    /// Rows returned by the statements that completed
+    rows: usize,
+    /// Time taken by the statements that completed, in microseconds
+    elapsed_us: u64,
+    /// Whether a statement failed or was cancelled, stopping the script
+    failed: bool,
+}
+
 /// Error information for display.
 #[derive(Debug, Clone)]
 pub struct DisplayError {
@@ -119,14 +183,139 @@ impl From<TuskError> for DisplayError {
 }
 
 /// Represents a row of data for display.
-/// Each cell is pre-converted to a String for rendering.
+/// Each cell is pre-converted to its text form for rendering.
 #[derive(Debug, Clone)]
 pub struct DisplayRow {
-    /// Cell values as strings
-    pub cells: Vec<String>,
+    /// Cell values as text, None for NULL
+    pub cells: Vec<Option<String>>,
+}
+
+impl DisplayRow {
+    /// Text of a cell as shown in the grid, with NULL as `NULL`.
+    pub fn display(&self, col: usize) -> Option<&str> {
+        self.cells.get(col).map(|cell| cell.as_deref().unwrap_or("NULL"))
+    }
+}
+
+/// Decoded copy of a displayed result, for exports.
+#[cfg(feature = "persistence")]
+#[derive(Debug, Clone)]
+pub struct ResultData {
+    /// Column metadata
+    pub columns: Vec<ColumnInfo>,
+    /// Cell text per row, None for NULL
+    pub rows: Vec<Vec<Option<String>>>,
+}
+
+#[cfg(feature = "persistence")]
+impl ResultData {
+    /// Copy a displayed result, with NULL cells as None.
+    fn from_state(state: &ResultsPanelState) -> Self {
+        Self::from_rows(&state.columns, &state.rows)
+    }
+
+    /// Copy displayed rows, with NULL cells as None.
+    fn from_rows(columns: &[DisplayColumn], rows: &[DisplayRow]) -> Self {
+        let columns: Vec<ColumnInfo> = columns
+            .iter()
+            .map(|c| ColumnInfo {
+                name: c.name.clone(),
+                type_oid: c.type_oid,
+                type_name: c.type_name.clone(),
+                table_oid: c.table_oid,
+                column_id: c.column_id,
+            })
+            .collect();
+        let rows = rows.iter().map(|row| row.cells.clone()).collect();
+        Self { columns, rows }
+    }
+
+    /// Keep only the given source columns, in the given order.
+    pub fn select_columns(self, columns: &[usize]) -> Self {
+        Self {
+            columns: columns.iter().filter_map(|&c| self.columns.get(c).cloned()).collect(),
+            rows: self
+                .rows
+                .iter()
+                .map(|row| columns.iter().filter_map(|&c| row.get(c).cloned()).collect())
+                .collect(),
+        }
+    }
+}
+
+/// Marker for dragging a results header to reorder columns.
+#[derive(Clone)]
+pub struct DraggedColumn {
+    /// Source index of the dragged column
+    pub col: usize,
+}
+
+impl Render for DraggedColumn {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        // Feedback comes from the drag_over styling of the target header
+        gpui::Empty
+    }
+}
+
+/// Cell editing state of the displayed result.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum EditMode {
+    /// Cells are read-only.
+    #[default]
+    Off,
+    /// Looking up the result's source table and primary key.
+    Loading,
+    /// Double-clicking a cell from the source table edits it.
+    On,
+    /// The result can't be edited, with the reason.
+    Refused(String),
+}
+
+/// Requests that need the result's source table, handled by the workspace.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ResultsEditEvent {
+    /// Edit mode was turned on; resolve the source table of the result.
+    TargetRequested,
+    /// Copy the selected rows as `INSERT` statements into the source table.
+    InsertsRequested,
+    /// A cell edit produced an `UPDATE` to review before running it.
+    UpdateGenerated {
+        /// Schema-qualified table name
+        table: String,
+        /// The generated statement
+        sql: String,
+    },
+    /// A cell edit could not be turned into an `UPDATE`.
+    Failed(String),
+}
+
+/// What the selected cells are copied to the clipboard as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellCopy {
+    /// Tab-separated values for spreadsheets, NULL as an empty cell
+    Tsv,
+    /// Comma-separated values with a header row
+    Csv,
+    /// GitHub-flavored Markdown table
+    Markdown,
+    /// `INSERT` statements into the result's source table, NULL as `NULL`
+    Inserts,
+}
+
+impl CellCopy {
+    /// Label in the "Copy As" menu.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Tsv => "TSV",
+            Self::Csv => "CSV",
+            Self::Markdown => "Markdown Table",
+            Self::Inserts => "INSERT Statements",
+        }
+    }
 }
 
 /// State for the results panel (FR-011, FR-012, FR-014, FR-015).
+#[derive(Debug, Clone)]
 pub struct ResultsPanelState {
     /// Column metadata from the query
     pub columns: Vec<DisplayColumn>,
@@ -134,14 +323,20 @@ pub struct ResultsPanelState {
     pub rows: Vec<DisplayRow>,
     /// Total rows received so far
     pub total_rows: usize,
-    /// Query execution time in milliseconds
-    pub execution_time_ms: Option<u64>,
+    /// Query execution time in microseconds
+    pub execution_time_us: Option<u64>,
     /// Rows affected (for INSERT/UPDATE/DELETE)
     pub rows_affected: Option<u64>,
+    /// Status of a statement that returned no columns (e.g. "SET", "UPDATE 3")
+    pub command_status: Option<String>,
     /// Current status
     pub status: ResultsStatus,
     /// Error information if status is Error
     pub error: Option<DisplayError>,
+    /// Column the rows are sorted by, if any
+    pub sort: Option<ColumnSort>,
+    /// Arrival position of each row while sorted; empty when unsorted
+    pub arrival: Vec<usize>,
 }
 
 impl Default for ResultsPanelState {
@@ -150,10 +345,13 @@ impl Default for ResultsPanelState {
             columns: Vec::new(),
             rows: Vec::new(),
             total_rows: 0,
-            execution_time_ms: None,
+            execution_time_us: None,
             rows_affected: None,
+            command_status: None,
             status: ResultsStatus::Empty,
             error: None,
+            sort: None,
+            arrival: Vec::new(),
         }
     }
 }
@@ -164,10 +362,36 @@ impl ResultsPanelState {
         self.columns.clear();
         self.rows.clear();
         self.total_rows = 0;
-        self.execution_time_ms = None;
+        self.execution_time_us = None;
         self.rows_affected = None;
+        self.command_status = None;
         self.status = ResultsStatus::Empty;
         self.error = None;
+        self.sort = None;
+        self.arrival.clear();
+    }
+
+    /// Sort the rows by a column, or restore the order they arrived in.
+    pub fn sort_by(&mut self, sort: Option<ColumnSort>) {
+        self.sort = sort;
+        sort_rows(&self.columns, &mut self.rows, &mut self.arrival, sort);
+    }
+
+    /// Merge rows appended since the last sort into a sorted result.
+    pub fn merge_rows(&mut self) {
+        if let Some(sort) = self.sort {
+            merge_new_rows(&self.columns, &mut self.rows, &mut self.arrival, sort);
+        }
+    }
+
+    /// Arrival position of the displayed row `row`.
+    fn arrival_of(&self, row: usize) -> usize {
+        self.arrival.get(row).copied().unwrap_or(row)
+    }
+
+    /// Displayed position of the row that arrived at position `arrived`.
+    fn position_of(&self, arrived: usize) -> usize {
+        self.arrival.iter().position(|&a| a == arrived).unwrap_or(arrived)
     }
 
     /// Set to loading state (clear previous results).
@@ -190,6 +414,43 @@ pub struct ResultsPanel {
     focus_handle: FocusHandle,
     /// Panel state with columns, rows, status, and error.
     state: ResultsPanelState,
+    /// Result sets from a multi-statement batch. The slot at
+    /// `active_result` is empty while its contents are shown in `state`.
+    result_sets: Vec<ResultsPanelState>,
+    /// Index of the batch result currently shown.
+    active_result: usize,
+    /// Selected cell range in the displayed result.
+    selection: Option<CellSelection>,
+    /// Recent results for each query tab, keyed by editor entity.
+    histories: HashMap<EntityId, ResultHistory<ResultSnapshot>>,
+    /// Tab and SQL of the run in progress, recorded once it completes.
+    pending_run: Option<(EntityId, String)>,
+    /// SQL of the most recent run.
+    last_run_sql: Option<String>,
+    /// Tab whose history is shown.
+    history_source: Option<EntityId>,
+    /// Column order and visibility for each query tab, keyed by editor entity.
+    layouts: HashMap<EntityId, ColumnLayout>,
+    /// Whether cells of the displayed result can be edited.
+    edit_mode: EditMode,
+    /// Source table and primary key of the displayed result, in edit mode.
+    #[cfg(feature = "persistence")]
+    edit_target: Option<EditTarget>,
+    /// Subscription to the open cell edit modal.
+    _edit_subscription: Option<Subscription>,
+    /// Open cell inspector and the point it is anchored to.
+    inspector: Option<(Point<Pixels>, Entity<CellInspector>)>,
+    /// Subscription to `inspector` events.
+    _inspector_subscription: Option<Subscription>,
+    /// Columns of the streaming result, for estimating its size.
+    #[cfg(feature = "persistence")]
+    streamed_columns: Vec<ColumnInfo>,
+    /// Whether the current run was reported as a large result.
+    #[cfg(feature = "persistence")]
+    large_result_warned: bool,
+    /// Script streaming one result set per statement, while it runs.
+    #[cfg(feature = "persistence")]
+    script: Option<ScriptRun>,
     /// Background task for receiving streaming events.
     _stream_task: Option<Task<()>>,
 }
@@ -200,6 +461,26 @@ impl ResultsPanel {
         Self {
             focus_handle: cx.focus_handle(),
             state: ResultsPanelState::default(),
+            result_sets: Vec::new(),
+            active_result: 0,
+            selection: None,
+            histories: HashMap::new(),
+            pending_run: None,
+            last_run_sql: None,
+            history_source: None,
+            layouts: HashMap::new(),
+            edit_mode: EditMode::Off,
+            #[cfg(feature = "persistence")]
+            edit_target: None,
+            _edit_subscription: None,
+            inspector: None,
+            _inspector_subscription: None,
+            #[cfg(feature = "persistence")]
+            streamed_columns: Vec::new(),
+            #[cfg(feature = "persistence")]
+            large_result_warned: false,
+            #[cfg(feature = "persistence")]
+            script: None,
             _stream_task: None,
         }
     }
@@ -224,8 +505,617 @@ impl ResultsPanel {
         self.state.rows.len()
     }
 
+    /// Get the number of result sets from the last batch (0 for a single query).
+    pub fn result_count(&self) -> usize {
+        self.result_sets.len()
+    }
+
+    /// Get the index of the batch result currently shown.
+    pub fn active_result(&self) -> usize {
+        self.active_result
+    }
+
+    /// Show a different result set from the last batch.
+    pub fn select_result(&mut self, index: usize, cx: &mut Context<Self>) {
+        if index >= self.result_sets.len() || index == self.active_result {
+            return;
+        }
+        std::mem::swap(&mut self.state, &mut self.result_sets[self.active_result]);
+        std::mem::swap(&mut self.state, &mut self.result_sets[index]);
+        self.active_result = index;
+        self.reset_edit_mode();
+        self.set_selection(None, cx);
+        cx.notify();
+    }
+
+    /// Get the column the displayed result is sorted by.
+    pub fn sort(&self) -> Option<ColumnSort> {
+        self.state.sort
+    }
+
+    /// Advance a column's sort from ascending to descending to unsorted.
+    pub fn cycle_sort(&mut self, col: usize, cx: &mut Context<Self>) {
+        if col >= self.state.columns.len() {
+            return;
+        }
+        self.state.sort_by(ColumnSort::cycle(self.state.sort, col));
+        self.set_selection(None, cx);
+        cx.notify();
+    }
+
+    /// Get the selected cell range.
+    pub fn selection(&self) -> Option<&CellSelection> {
+        self.selection.as_ref()
+    }
+
+    /// Select a cell, or extend the selection to it when `extend` is set.
+    ///
+    /// `col` is a source column index; hidden columns can't be selected.
+    pub fn select_cell(&mut self, row: usize, col: usize, extend: bool, cx: &mut Context<Self>) {
+        let Some(col) = self.visible_columns().iter().position(|&c| c == col) else {
+            return;
+        };
+        let position = CellPosition::new(row, col);
+        let selection = match self.selection {
+            Some(selection) if extend => selection.extend_to(position),
+            _ => CellSelection::single(position),
+        };
+        self.set_selection(Some(selection), cx);
+    }
+
+    /// Move the selected cell, or extend the selection, by whole cells.
+    ///
+    /// Starts at the first shown cell when nothing is selected.
+    pub fn move_selection(
+        &mut self,
+        row_delta: isize,
+        col_delta: isize,
+        extend: bool,
+        cx: &mut Context<Self>,
+    ) {
+        let cols = self.visible_columns().len();
+        let rows = self.state.rows.len().min(MAX_DISPLAYED_ROWS);
+        if rows == 0 || cols == 0 {
+            return;
+        }
+        let selection = match self.selection {
+            Some(selection) => selection.step(rows, cols, row_delta, col_delta, extend),
+            None => CellSelection::single(CellPosition::new(0, 0)),
+        };
+        self.set_selection(Some(selection), cx);
+    }
+
+    /// Selected cells in display column order, with NULL cells as None.
+    #[cfg(feature = "persistence")]
+    pub fn selection_data(&self) -> Option<ResultData> {
+        let selection = self.selection?;
+        let rows = self.state.rows.get(selection.rows())?;
+        let columns = selection.source_cols(&self.visible_columns());
+        Some(ResultData::from_rows(&self.state.columns, rows).select_columns(&columns))
+    }
+
+    /// Copy the selected cells to the clipboard.
+    ///
+    /// `INSERT` statements need the source table, so the workspace is asked
+    /// to resolve it and copy them.
+    #[cfg(feature = "persistence")]
+    pub fn copy_selection(&mut self, copy: CellCopy, cx: &mut Context<Self>) {
+        use tusk_core::models::export::ClipboardFormat;
+
+        let format = match copy {
+            CellCopy::Tsv => ClipboardFormat::Tsv,
+            CellCopy::Csv => ClipboardFormat::Csv,
+            CellCopy::Markdown => ClipboardFormat::Markdown,
+            CellCopy::Inserts => {
+                if self.selection.is_some() {
+                    cx.emit(ResultsEditEvent::InsertsRequested);
+                }
+                return;
+            }
+        };
+        let Some(data) = self.selection_data() else {
+            return;
+        };
+        let text = format.format(&data.columns, &data.rows);
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(text));
+    }
+
+    /// Copy placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn copy_selection(&mut self, _copy: CellCopy, _cx: &mut Context<Self>) {}
+
+    /// Summarize the current selection for the status bar.
+    pub fn selection_summary(&self) -> SelectionSummary {
+        SelectionSummary::compute(
+            &self.state.columns,
+            &self.state.rows,
+            &self.visible_columns(),
+            self.state.total_rows,
+            self.selection.as_ref(),
+        )
+    }
+
+    /// Open the inspector popover for a cell at a window position.
+    ///
+    /// NULL cells have no value to inspect.
+    pub fn inspect_cell(
+        &mut self,
+        row: usize,
+        col: usize,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(column) = self.state.columns.get(col) else {
+            return;
+        };
+        let Some(value) = self.state.rows.get(row).and_then(|r| r.cells.get(col)?.clone()) else {
+            return;
+        };
+        let (name, type_name) = (column.name.clone(), column.type_name.clone());
+
+        let inspector = cx.new(|cx| CellInspector::new(name, type_name, value, cx));
+        let subscription = cx.subscribe_in(
+            &inspector,
+            window,
+            |this, _, event: &CellInspectorEvent, window, cx| match event {
+                CellInspectorEvent::Dismissed => {
+                    this.inspector = None;
+                    this._inspector_subscription = None;
+                    window.focus(&this.focus_handle, cx);
+                    cx.notify();
+                }
+            },
+        );
+        let focus_handle = gpui::Focusable::focus_handle(inspector.read(cx), cx);
+        window.focus(&focus_handle, cx);
+
+        self.inspector = Some((position, inspector));
+        self._inspector_subscription = Some(subscription);
+        cx.notify();
+    }
+
+    /// Show the context menu for a cell.
+    ///
+    /// Offers copying the selection in each [`CellCopy`] format and opening
+    /// the cell in the inspector. Cells holding a JSON object or array, which
+    /// includes decoded `json`/`jsonb` values, also offer a collapsible JSON
+    /// view; JSON scalars have nothing to fold and are left to the inspector.
+    fn show_cell_menu(
+        &mut self,
+        row: usize,
+        col: usize,
+        position: Point<Pixels>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(column) = self.state.columns.get(col) else {
+            return;
+        };
+        let Some(value) = self.state.rows.get(row).and_then(|r| r.cells.get(col)) else {
+            return;
+        };
+        let json_value = value.clone().filter(|value| looks_like_json(value));
+
+        let panel = cx.entity().downgrade();
+        let copy_item = |label: &str, copy: CellCopy| {
+            let panel = panel.clone();
+            ContextMenuItem::action(label.to_string(), move |cx| {
+                let _ = panel.update(cx, |panel, cx| panel.copy_selection(copy, cx));
+            })
+        };
+        let mut items = vec![
+            copy_item("Copy", CellCopy::Tsv).icon(IconName::Copy).shortcut("Cmd+C"),
+            ContextMenuItem::submenu(
+                "Copy As",
+                [CellCopy::Tsv, CellCopy::Csv, CellCopy::Markdown, CellCopy::Inserts]
+                    .into_iter()
+                    .map(|copy| copy_item(copy.label(), copy))
+                    .collect(),
+            ),
+            ContextMenuItem::separator(),
+            {
+                let panel = panel.clone();
+                let window_handle = window.window_handle();
+                ContextMenuItem::action("Inspect Value", move |cx| {
+                    let _ = window_handle.update(cx, |_, window, cx| {
+                        panel.update(cx, |panel, cx| {
+                            panel.inspect_cell(row, col, position, window, cx);
+                        })
+                    });
+                })
+                .icon(IconName::Search)
+            },
+        ];
+        if let Some(value) = json_value {
+            let name = column.name.clone();
+            items.push(
+                ContextMenuItem::action("View JSON", move |cx| {
+                    show_json_view(name.clone(), value.clone(), cx);
+                })
+                .icon(IconName::Code),
+            );
+        }
+
+        let shown_at = self.visible_columns().iter().position(|&c| c == col);
+        if !self.selection.zip(shown_at).is_some_and(|(s, at)| s.contains(row, at)) {
+            self.select_cell(row, col, false, cx);
+        }
+        let menu = cx.new(|cx| ContextMenu::new(position, cx).items(items));
+        cx.update_global::<ContextMenuLayer, _>(|layer, cx| {
+            layer.show_deferred(menu, cx);
+        });
+    }
+
+    /// Column layout of the shown tab, if it was built for the displayed columns.
+    pub fn column_layout(&self) -> Option<&ColumnLayout> {
+        let names: Vec<&str> = self.state.columns.iter().map(|c| c.name.as_str()).collect();
+        self.layouts.get(&self.history_source?).filter(|layout| layout.matches(&names))
+    }
+
+    /// Source indices of the displayed columns, in display order.
+    pub fn visible_columns(&self) -> Vec<usize> {
+        match self.column_layout() {
+            Some(layout) => layout.visible(),
+            None => (0..self.state.columns.len()).collect(),
+        }
+    }
+
+    /// Hide a column of the displayed result.
+    pub fn hide_column(&mut self, col: usize, cx: &mut Context<Self>) {
+        self.update_column_layout(|layout| layout.hide(col), cx);
+    }
+
+    /// Move a column of the displayed result to a visible position.
+    pub fn move_column(&mut self, col: usize, to: usize, cx: &mut Context<Self>) {
+        self.update_column_layout(|layout| layout.move_to(col, to), cx);
+    }
+
+    /// Move a column of the displayed result one position left (`-1`) or right (`1`).
+    pub fn shift_column(&mut self, col: usize, delta: isize, cx: &mut Context<Self>) {
+        self.update_column_layout(|layout| layout.shift(col, delta), cx);
+    }
+
+    /// Show all columns of the displayed result in source order.
+    pub fn reset_columns(&mut self, cx: &mut Context<Self>) {
+        self.update_column_layout(ColumnLayout::reset, cx);
+    }
+
+    /// Change the shown tab's layout, starting over if the columns changed.
+    fn update_column_layout(
+        &mut self,
+        change: impl FnOnce(&mut ColumnLayout) -> bool,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(id) = self.history_source else {
+            return;
+        };
+        let names: Vec<&str> = self.state.columns.iter().map(|c| c.name.as_str()).collect();
+        let layout = self.layouts.entry(id).or_insert_with(|| ColumnLayout::new(&names));
+        if !layout.matches(&names) {
+            *layout = ColumnLayout::new(&names);
+        }
+        if change(layout) {
+            self.set_selection(None, cx);
+            cx.notify();
+        }
+    }
+
+    /// Show the context menu for a column header.
+    fn show_header_menu(&mut self, col: usize, position: Point<Pixels>, cx: &mut Context<Self>) {
+        if self.history_source.is_none() {
+            return;
+        }
+        let visible = self.visible_columns();
+        let Some(index) = visible.iter().position(|&c| c == col) else {
+            return;
+        };
+        let hidden = self.column_layout().map(|layout| layout.hidden_count()).unwrap_or(0);
+        let customized = self.column_layout().is_some_and(|layout| !layout.is_default());
+        let panel = cx.entity().downgrade();
+        let action = |label: &str, change: fn(&mut Self, usize, &mut Context<Self>)| {
+            let panel = panel.clone();
+            ContextMenuItem::action(label.to_string(), move |cx| {
+                let _ = panel.update(cx, |panel, cx| change(panel, col, cx));
+            })
+        };
+
+        let items = vec![
+            action("Hide Column", |panel, col, cx| panel.hide_column(col, cx))
+                .icon(IconName::EyeOff)
+                .disabled(visible.len() <= 1),
+            action("Move Left", |panel, col, cx| panel.shift_column(col, -1, cx))
+                .icon(IconName::ChevronLeft)
+                .disabled(index == 0),
+            action("Move Right", |panel, col, cx| panel.shift_column(col, 1, cx))
+                .icon(IconName::ChevronRight)
+                .disabled(index + 1 >= visible.len()),
+            ContextMenuItem::separator(),
+            action(
+                &if hidden > 0 {
+                    format!("Show All Columns ({} hidden)", hidden)
+                } else {
+                    "Reset Column Order".to_string()
+                },
+                |panel, _, cx| panel.reset_columns(cx),
+            )
+            .icon(IconName::Eye)
+            .disabled(!customized),
+        ];
+
+        let menu = cx.new(|cx| ContextMenu::new(position, cx).items(items));
+        cx.update_global::<ContextMenuLayer, _>(|layer, cx| {
+            layer.show_deferred(menu, cx);
+        });
+    }
+
+    /// Replace the selection and publish the new summary.
+    fn set_selection(&mut self, selection: Option<CellSelection>, cx: &mut Context<Self>) {
+        if self.selection == selection {
+            return;
+        }
+        self.selection = selection;
+        cx.emit(self.selection_summary());
+        cx.notify();
+    }
+
+    /// Note the tab and SQL of a run about to start.
+    ///
+    /// Its results are added to that tab's history when the run completes.
+    /// The history is dropped when the tab's editor is released.
+    pub fn begin_run<V: 'static>(
+        &mut self,
+        source: &Entity<V>,
+        sql: impl Into<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let id = source.entity_id();
+        if !self.histories.contains_key(&id) {
+            self.histories.insert(id, ResultHistory::default());
+            cx.observe_release(source, move |panel, _, cx| {
+                panel.histories.remove(&id);
+                panel.layouts.remove(&id);
+                if panel.history_source == Some(id) {
+                    panel.history_source = None;
+                    cx.notify();
+                }
+            })
+            .detach();
+        }
+        let sql = sql.into();
+        self.last_run_sql = Some(sql.clone());
+        self.pending_run = Some((id, sql));
+        self.history_source = Some(id);
+    }
+
+    /// Get the SQL of the most recent run, as written in the editor.
+    pub fn last_run_sql(&self) -> Option<&str> {
+        self.last_run_sql.as_deref()
+    }
+
+    /// Get the result history of the tab that ran last.
+    pub fn history(&self) -> Option<&ResultHistory<ResultSnapshot>> {
+        self.histories.get(&self.history_source?)
+    }
+
+    /// Show the previous (older) result from the tab's history.
+    pub fn show_previous_result(&mut self, cx: &mut Context<Self>) {
+        if self.state.status.is_active() {
+            return;
+        }
+        let snapshot = self
+            .history_source
+            .and_then(|id| self.histories.get_mut(&id))
+            .and_then(|history| history.select_previous().cloned());
+        if let Some(snapshot) = snapshot {
+            self.show_snapshot(snapshot, cx);
+        }
+    }
+
+    /// Show the next (newer) result from the tab's history.
+    pub fn show_next_result(&mut self, cx: &mut Context<Self>) {
+        if self.state.status.is_active() {
+            return;
+        }
+        let snapshot = self
+            .history_source
+            .and_then(|id| self.histories.get_mut(&id))
+            .and_then(|history| history.select_next().cloned());
+        if let Some(snapshot) = snapshot {
+            self.show_snapshot(snapshot, cx);
+        }
+    }
+
+    /// Display the result sets of a history snapshot.
+    fn show_snapshot(&mut self, snapshot: ResultSnapshot, cx: &mut Context<Self>) {
+        self.clear_result_sets();
+        let mut result_sets = snapshot.result_sets;
+        if result_sets.len() > 1 {
+            self.state = std::mem::take(&mut result_sets[0]);
+            self.result_sets = result_sets;
+        } else {
+            self.state = result_sets.pop().unwrap_or_else(|| ResultsPanelState {
+                status: ResultsStatus::Complete,
+                ..Default::default()
+            });
+        }
+        cx.emit(self.selection_summary());
+        cx.notify();
+    }
+
+    /// Add the displayed results to the history of the tab that ran them.
+    fn record_run(&mut self) {
+        let Some((id, sql)) = self.pending_run.take() else {
+            return;
+        };
+        let result_sets = if self.result_sets.is_empty() {
+            vec![self.state.clone()]
+        } else {
+            let mut sets = self.result_sets.clone();
+            sets[self.active_result] = self.state.clone();
+            sets
+        };
+        self.histories.entry(id).or_default().push(ResultSnapshot::new(sql, result_sets));
+    }
+
+    /// Drop any result sets kept from a previous batch.
+    fn clear_result_sets(&mut self) {
+        self.result_sets.clear();
+        self.active_result = 0;
+        #[cfg(feature = "persistence")]
+        {
+            self.script = None;
+        }
+        self.selection = None;
+        self.reset_edit_mode();
+    }
+
+    /// Get the cell editing state.
+    pub fn edit_mode(&self) -> &EditMode {
+        &self.edit_mode
+    }
+
+    /// Turn edit mode on, or off if it is already on.
+    ///
+    /// Turning it on asks the workspace to resolve the result's source table
+    /// and primary key; editing starts once [`Self::set_edit_target`] succeeds.
+    pub fn toggle_edit_mode(&mut self, cx: &mut Context<Self>) {
+        match self.edit_mode {
+            EditMode::Off | EditMode::Refused(_) => {
+                self.edit_mode = EditMode::Loading;
+                cx.emit(ResultsEditEvent::TargetRequested);
+            }
+            EditMode::Loading | EditMode::On => self.reset_edit_mode(),
+        }
+        cx.notify();
+    }
+
+    /// Refuse edit mode for the displayed result, showing `reason`.
+    pub fn refuse_edit(&mut self, reason: impl Into<String>, cx: &mut Context<Self>) {
+        if self.edit_mode == EditMode::Loading {
+            self.edit_mode = EditMode::Refused(reason.into());
+            cx.notify();
+        }
+    }
+
+    /// Start editing with the resolved source table, or refuse with the reason.
+    #[cfg(feature = "persistence")]
+    pub fn set_edit_target(
+        &mut self,
+        target: Result<EditTarget, TuskError>,
+        cx: &mut Context<Self>,
+    ) {
+        // The result changed while the lookup ran
+        if self.edit_mode != EditMode::Loading {
+            return;
+        }
+        match target {
+            Ok(target) => {
+                self.edit_target = Some(target);
+                self.edit_mode = EditMode::On;
+                cx.notify();
+            }
+            Err(e) => self.refuse_edit(e.to_string(), cx),
+        }
+    }
+
+    /// Leave edit mode.
+    fn reset_edit_mode(&mut self) {
+        self.edit_mode = EditMode::Off;
+        #[cfg(feature = "persistence")]
+        {
+            self.edit_target = None;
+        }
+        self._edit_subscription = None;
+    }
+
+    /// Open the editor for a cell, if edit mode is on and the cell's column
+    /// comes from the source table.
+    #[cfg(feature = "persistence")]
+    fn edit_cell(&mut self, row: usize, col: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(target) = self.edit_target.as_ref().filter(|t| t.is_editable(col)) else {
+            return;
+        };
+        let (Some(column), Some(cells)) = (self.state.columns.get(col), self.state.rows.get(row))
+        else {
+            return;
+        };
+        let value = cells.cells.get(col).cloned().flatten();
+        let subtitle = format!("{}.{}, row {}", target.schema, target.table, row + 1);
+        let title = format!("Edit {}", column.name);
+
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new("Value", cx);
+            if let Some(value) = value {
+                input.set_text(value, cx);
+            }
+            input
+        });
+        let modal = cx.new(|cx| {
+            Modal::new(title, cx)
+                .subtitle(subtitle)
+                .body(input.clone().into())
+                .action(ModalAction::cancel())
+                .action(ModalAction::new("set-null", "Set NULL").dismisses(true))
+                .action(ModalAction::primary("generate", "Generate UPDATE").dismisses(true))
+        });
+        self._edit_subscription =
+            Some(cx.subscribe(&modal, move |this, _modal, event: &ModalEvent, cx| {
+                let ModalEvent::ActionTriggered { action_id } = event else {
+                    return;
+                };
+                let value = match action_id.as_ref() {
+                    "generate" => Some(input.read(cx).text().to_string()),
+                    "set-null" => None,
+                    _ => return,
+                };
+                this.generate_update(row, col, value, cx);
+            }));
+
+        cx.update_global::<ModalLayer, _>(|layer, cx| {
+            layer.show(modal, cx);
+        });
+        let focus_handle = gpui::Focusable::focus_handle(input.read(cx), cx);
+        window.focus(&focus_handle, cx);
+    }
+
+    /// Cell editor placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn edit_cell(
+        &mut self,
+        _row: usize,
+        _col: usize,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let _ = cx;
+    }
+
+    /// Build the `UPDATE` for an edited cell and hand it on for review.
+    #[cfg(feature = "persistence")]
+    fn generate_update(
+        &mut self,
+        row: usize,
+        col: usize,
+        value: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
+        let (Some(target), Some(cells)) = (self.edit_target.as_ref(), self.state.rows.get(row))
+        else {
+            return;
+        };
+        cx.emit(match target.update_sql(&cells.cells, col, value.as_deref()) {
+            Ok(sql) => ResultsEditEvent::UpdateGenerated {
+                table: format!("{}.{}", target.schema, target.table),
+                sql,
+            },
+            Err(e) => ResultsEditEvent::Failed(e.to_string()),
+        });
+    }
+
     /// Set the panel to loading state.
     pub fn set_loading(&mut self, cx: &mut Context<Self>) {
+        self.clear_result_sets();
         self.state.set_loading();
         cx.notify();
     }
@@ -246,6 +1136,7 @@ impl ResultsPanel {
     /// Clear the panel back to empty state.
     pub fn clear(&mut self, cx: &mut Context<Self>) {
         self.state.clear();
+        self.clear_result_sets();
         self._stream_task = None;
         cx.notify();
     }
@@ -260,7 +1151,10 @@ impl ResultsPanel {
     #[cfg(feature = "persistence")]
     pub fn start_streaming(&mut self, mut rx: mpsc::Receiver<QueryEvent>, cx: &mut Context<Self>) {
         // Clear and set to loading
+        self.clear_result_sets();
         self.state.set_loading();
+        self.streamed_columns.clear();
+        self.large_result_warned = false;
 
         // Spawn background task to receive events
         self._stream_task = Some(cx.spawn(async move |this, cx| {
@@ -281,6 +1175,84 @@ impl ResultsPanel {
         cx.notify();
     }
 
+    /// Start receiving a script's events, one result set per statement.
+    ///
+    /// Each statement streams into the displayed result like
+    /// [`Self::start_streaming`]; when the next statement starts, the
+    /// finished result is set aside. Once the script ends its results are
+    /// reachable through the result selector, showing the first one, or the
+    /// statement that stopped the script if one failed.
+    #[cfg(feature = "persistence")]
+    pub fn start_script(&mut self, mut rx: mpsc::Receiver<ScriptEvent>, cx: &mut Context<Self>) {
+        self.clear_result_sets();
+        self.state.set_loading();
+        self.streamed_columns.clear();
+        self.large_result_warned = false;
+        self.script = Some(ScriptRun::default());
+
+        self._stream_task = Some(cx.spawn(async move |this, cx| {
+            while let Some(event) = rx.recv().await {
+                let result = this.update(cx, |panel: &mut ResultsPanel, cx| {
+                    panel.handle_script_event(event, cx);
+                });
+                if result.is_err() {
+                    return;
+                }
+            }
+            // The sender is dropped once the script has ended
+            let _ = this.update(cx, |panel: &mut ResultsPanel, cx| panel.finish_script(cx));
+        }));
+
+        cx.notify();
+    }
+
+    /// Handle an event of a streaming script's statement.
+    #[cfg(feature = "persistence")]
+    fn handle_script_event(&mut self, event: ScriptEvent, cx: &mut Context<Self>) {
+        let ScriptEvent { statement, offset, event } = event;
+        let Some(script) = &mut self.script else {
+            return;
+        };
+        if statement != script.statement {
+            if script.statement > 0 {
+                script.finished.push(std::mem::take(&mut self.state));
+            }
+            script.statement = statement;
+            self.state.set_loading();
+            self.streamed_columns.clear();
+            self.selection = None;
+        }
+        if matches!(event, QueryEvent::Error(_)) {
+            tracing::debug!(statement, offset, "Script stopped at failed statement");
+        }
+        self.handle_event(event, cx);
+    }
+
+    /// Show the result sets of a script that has ended.
+    #[cfg(feature = "persistence")]
+    fn finish_script(&mut self, cx: &mut Context<Self>) {
+        let Some(mut script) = self.script.take() else {
+            return;
+        };
+        if !script.finished.is_empty() {
+            script.finished.push(std::mem::take(&mut self.state));
+            self.active_result = if script.failed { script.finished.len() - 1 } else { 0 };
+            self.state = std::mem::take(&mut script.finished[self.active_result]);
+            self.result_sets = script.finished;
+            self.reset_edit_mode();
+            self.selection = None;
+        }
+        // A failed statement already reported the script's end, and a script
+        // that failed before its first statement is reported by the caller
+        if !script.failed && script.statement > 0 {
+            self.record_run();
+            cx.emit(ExecutionEvent::Completed { rows: script.rows, elapsed_us: script.elapsed_us });
+        }
+        tracing::debug!(result_count = self.result_sets.len(), "Script completed");
+        cx.emit(self.selection_summary());
+        cx.notify();
+    }
+
     /// Start streaming placeholder for non-persistence builds.
     #[cfg(not(feature = "persistence"))]
     pub fn start_streaming<T>(&mut self, _rx: T, cx: &mut Context<Self>) {
@@ -299,37 +1271,96 @@ impl ResultsPanel {
     pub fn handle_event(&mut self, event: QueryEvent, cx: &mut Context<Self>) {
         match event {
             QueryEvent::Columns(columns) => {
+                self.streamed_columns = columns.clone();
                 self.state.columns = columns.into_iter().map(DisplayColumn::from).collect();
                 self.state.status = ResultsStatus::Streaming;
                 tracing::debug!(column_count = self.state.columns.len(), "Received columns");
             }
+            QueryEvent::FirstRow { elapsed_ms } => {
+                tracing::debug!(elapsed_ms, "Received first row");
+                cx.emit(ExecutionEvent::FirstRow { elapsed_ms });
+            }
             QueryEvent::Rows { rows, total_so_far } => {
                 // Convert tokio_postgres::Row to DisplayRow
                 for row in rows {
-                    let cells: Vec<String> =
+                    let cells: Vec<Option<String>> =
                         (0..self.state.columns.len()).map(|i| Self::format_cell(&row, i)).collect();
                     self.state.rows.push(DisplayRow { cells });
                 }
+                // Merge the batch into a sorted result, keeping the selected rows selected
+                if self.state.sort.is_some() {
+                    let selected = self.selection.map(|s| {
+                        (s, self.state.arrival_of(s.anchor.row), self.state.arrival_of(s.head.row))
+                    });
+                    self.state.merge_rows();
+                    if let Some((mut selection, anchor, head)) = selected {
+                        selection.anchor.row = self.state.position_of(anchor);
+                        selection.head.row = self.state.position_of(head);
+                        self.set_selection(Some(selection), cx);
+                    }
+                }
                 self.state.total_rows = total_so_far;
                 tracing::trace!(total_rows = total_so_far, "Received rows batch");
+                self.check_result_size(
+                    estimate_result_bytes(&self.streamed_columns, total_so_far),
+                    total_so_far,
+                    cx,
+                );
             }
             QueryEvent::Progress { rows_so_far } => {
                 self.state.total_rows = rows_so_far;
             }
-            QueryEvent::Complete { total_rows, execution_time_ms, rows_affected } => {
+            QueryEvent::Complete {
+                total_rows,
+                execution_time_us,
+                rows_affected,
+                command_tag,
+                ..
+            } => {
                 self.state.total_rows = total_rows;
-                self.state.execution_time_ms = Some(execution_time_ms);
+                self.state.execution_time_us = Some(execution_time_us);
                 self.state.rows_affected = rows_affected;
+                self.state.command_status =
+                    command_tag.map(|tag| command_status(&tag, rows_affected));
                 self.state.status = ResultsStatus::Complete;
+                // A script's statements are recorded and reported together when it ends
+                let in_script = match &mut self.script {
+                    Some(script) => {
+                        script.rows += total_rows;
+                        script.elapsed_us += execution_time_us;
+                        true
+                    }
+                    None => {
+                        self.record_run();
+                        false
+                    }
+                };
+                // Commands have no grid to show; their status goes to the Messages panel
+                if let Some(status) = &self.state.command_status {
+                    cx.emit(CommandStatus(format!(
+                        "{} ({})",
+                        status,
+                        format_elapsed(execution_time_us)
+                    )));
+                }
                 tracing::debug!(
                     total_rows,
-                    execution_time_ms,
+                    execution_time_us,
                     rows_affected = ?rows_affected,
                     "Query completed"
                 );
+                if !in_script {
+                    cx.emit(ExecutionEvent::Completed {
+                        rows: total_rows,
+                        elapsed_us: execution_time_us,
+                    });
+                }
             }
             QueryEvent::Error(err) => {
                 let display_error = DisplayError::from(err);
+                if let Some(script) = &mut self.script {
+                    script.failed = true;
+                }
 
                 // T035: Preserve already-received results when query is cancelled
                 if display_error.is_cancelled {
@@ -341,73 +1372,368 @@ impl ResultsPanel {
                         rows_preserved = self.state.rows.len(),
                         "Query cancelled, preserving received results"
                     );
+                    cx.emit(ExecutionEvent::Cancelled);
                 } else {
                     // Regular error - show error state
+                    let message = display_error.message.clone();
                     self.state.status = ResultsStatus::Error;
                     self.state.error = Some(display_error);
                     tracing::debug!("Query error received");
+                    cx.emit(ExecutionEvent::Failed(message.into()));
                 }
             }
+            QueryEvent::Notice { severity, message } => {
+                cx.emit(ServerNotice(Message::server_notice(severity, &message)));
+            }
         }
         cx.notify();
     }
 
-    /// Format a cell value from a tokio_postgres::Row.
+    /// Warn once per run when a result's estimated size passes [`LARGE_RESULT_BYTES`].
     #[cfg(feature = "persistence")]
-    fn format_cell(row: &tokio_postgres::Row, index: usize) -> String {
-        use tokio_postgres::types::Type;
+    fn check_result_size(&mut self, estimated_bytes: u64, rows: usize, cx: &mut Context<Self>) {
+        if self.large_result_warned || estimated_bytes < LARGE_RESULT_BYTES {
+            return;
+        }
+        self.large_result_warned = true;
+        tracing::warn!(estimated_bytes, rows, "Large query result");
+        cx.emit(LargeResult { estimated_bytes, rows });
+    }
+
+    /// Format a cell value from a tokio_postgres::Row, or None for NULL.
+    ///
+    /// Arrays and composites are rendered like psql; scalars are decoded to
+    /// their text form by type.
+    #[cfg(feature = "persistence")]
+    fn format_cell(row: &tokio_postgres::Row, index: usize) -> Option<String> {
+        use super::cell_format::{is_structured_type, CellValue};
+        use tusk_core::models::export::cell_text;
 
         let column = &row.columns()[index];
         let type_ = column.type_();
 
-        // Handle NULL values
-        if row.try_get::<_, Option<String>>(index).ok().flatten().is_none() {
-            // Try to detect if it's actually NULL vs a type mismatch
-            // For simplicity, try common types
-            match *type_ {
-                Type::BOOL => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<bool>>(index) {
-                        return v.to_string();
-                    }
-                }
-                Type::INT2 => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<i16>>(index) {
-                        return v.to_string();
-                    }
-                }
-                Type::INT4 => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<i32>>(index) {
-                        return v.to_string();
-                    }
-                }
-                Type::INT8 => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<i64>>(index) {
-                        return v.to_string();
-                    }
-                }
-                Type::FLOAT4 => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<f32>>(index) {
-                        return v.to_string();
-                    }
-                }
-                Type::FLOAT8 => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<f64>>(index) {
-                        return v.to_string();
-                    }
+        // Arrays and composites are decoded structurally and rendered like psql
+        if is_structured_type(type_) {
+            return match row.try_get::<_, CellValue>(index) {
+                Ok(CellValue::Null) => None,
+                Ok(value) => Some(value.to_pg_literal()),
+                Err(e) => {
+                    tracing::trace!(column = %column.name(), error = %e, "Failed to decode cell");
+                    Some(format!("<{}>", type_.name()))
                 }
-                Type::TEXT | Type::VARCHAR | Type::CHAR | Type::NAME => {
-                    if let Ok(Some(v)) = row.try_get::<_, Option<String>>(index) {
-                        return v;
-                    }
-                }
-                _ => {}
-            }
-            // If we couldn't get a value, it's likely NULL
-            return "NULL".to_string();
+            };
         }
+        cell_text(row, index)
+    }
+
+    /// Columns and rows of the displayed result, with NULL cells as None.
+    ///
+    /// Returns None when no completed result with columns is shown.
+    #[cfg(feature = "persistence")]
+    pub fn export_data(&self) -> Option<ResultData> {
+        if !self.state.status.is_complete() || self.state.columns.is_empty() {
+            return None;
+        }
+        let data = ResultData::from_state(&self.state);
+        match self.column_layout() {
+            Some(layout) if !layout.is_default() => Some(data.select_columns(&layout.visible())),
+            _ => Some(data),
+        }
+    }
+
+    /// Compare the displayed result with the same result set of the tab's
+    /// previous run.
+    #[cfg(feature = "persistence")]
+    pub fn compare_with_previous(&self, cx: &mut App) {
+        use super::result_diff::show_result_diff;
+
+        let Some((previous, before)) = self.previous_result() else {
+            return;
+        };
+        let before = ResultData::from_state(before);
+        let after = ResultData::from_state(&self.state);
+        let subtitle =
+            format!("Previous run ({}) vs. displayed result", format_age(previous.age()));
+        show_result_diff(subtitle, (before.columns, before.rows), (after.columns, after.rows), cx);
+    }
+
+    /// Compare placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn compare_with_previous(&self, _cx: &mut App) {}
+
+    /// The previous run of the shown tab and its result set matching the
+    /// displayed one, if both runs returned rows.
+    fn previous_result(&self) -> Option<(&ResultSnapshot, &ResultsPanelState)> {
+        if !self.state.status.is_complete() || self.state.columns.is_empty() {
+            return None;
+        }
+        let previous = self.history()?.previous()?;
+        let state = previous
+            .result_sets
+            .get(self.active_result)
+            .or_else(|| previous.result_sets.first())
+            .filter(|state| !state.columns.is_empty())?;
+        Some((previous, state))
+    }
+
+    /// Render the button that compares the displayed result with the previous run.
+    fn render_compare_button(
+        &self,
+        theme: &TuskTheme,
+        cx: &mut Context<Self>,
+    ) -> Option<gpui::Stateful<gpui::Div>> {
+        if !cfg!(feature = "persistence") {
+            return None;
+        }
+        self.previous_result()?;
+        let hover_bg = theme.colors.element_hover;
+
+        Some(
+            div()
+                .id("results-compare")
+                .p(px(2.0))
+                .rounded(px(4.0))
+                .cursor_pointer()
+                .hover(|style| style.bg(hover_bg))
+                .tooltip(Tooltip::text("Compare with previous run"))
+                .on_click(cx.listener(|this, _, _window, cx| {
+                    this.compare_with_previous(cx);
+                }))
+                .child(
+                    Icon::new(IconName::SplitHorizontal)
+                        .size(IconSize::Small)
+                        .color(theme.colors.text_muted),
+                ),
+        )
+    }
+    /// Copy the displayed result as a GitHub-flavored Markdown table.
+    #[cfg(feature = "persistence")]
+    pub fn copy_as_markdown(&self, cx: &mut App) {
+        use tusk_core::models::export::{markdown_table, MarkdownOptions};
+
+        let Some(data) = self.export_data() else {
+            return;
+        };
+        let markdown = markdown_table(
+            &data.columns,
+            &data.rows,
+            self.state.total_rows,
+            &MarkdownOptions::default(),
+        );
+        cx.write_to_clipboard(gpui::ClipboardItem::new_string(markdown));
+    }
+
+    /// Copy as Markdown placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn copy_as_markdown(&self, _cx: &mut App) {}
+
+    /// Render the button that copies the displayed result as Markdown.
+    fn render_copy_markdown(
+        &self,
+        theme: &TuskTheme,
+        cx: &mut Context<Self>,
+    ) -> Option<gpui::Stateful<gpui::Div>> {
+        if !self.state.status.is_complete() || self.state.columns.is_empty() {
+            return None;
+        }
+        let hover_bg = theme.colors.element_hover;
+
+        Some(
+            div()
+                .id("results-copy-markdown")
+                .p(px(2.0))
+                .rounded(px(4.0))
+                .cursor_pointer()
+                .hover(|style| style.bg(hover_bg))
+                .tooltip(Tooltip::text("Copy as Markdown table"))
+                .on_click(cx.listener(|this, _, _window, cx| {
+                    this.copy_as_markdown(cx);
+                }))
+                .child(
+                    Icon::new(IconName::Copy).size(IconSize::Small).color(theme.colors.text_muted),
+                ),
+        )
+    }
+
+    /// Render the button that exports the displayed result to a file.
+    fn render_export_button(&self, theme: &TuskTheme) -> Option<gpui::Stateful<gpui::Div>> {
+        if !self.state.status.is_complete() || self.state.columns.is_empty() {
+            return None;
+        }
+        let hover_bg = theme.colors.element_hover;
+
+        Some(
+            div()
+                .id("results-export")
+                .p(px(2.0))
+                .rounded(px(4.0))
+                .cursor_pointer()
+                .hover(|style| style.bg(hover_bg))
+                .tooltip(Tooltip::text("Export to CSV or JSON file"))
+                .on_click(|_, window, cx| {
+                    window.dispatch_action(Box::new(ExportResults), cx);
+                })
+                .child(
+                    Icon::new(IconName::Export)
+                        .size(IconSize::Small)
+                        .color(theme.colors.text_muted),
+                ),
+        )
+    }
+
+    /// Render the edit mode toggle, with the reason when editing is refused.
+    fn render_edit_toggle(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> Option<gpui::Div> {
+        if !self.state.status.is_complete() || self.state.columns.is_empty() {
+            return None;
+        }
+        let hover_bg = theme.colors.element_hover;
+        let (tooltip, icon_color) = match &self.edit_mode {
+            EditMode::Off => {
+                ("Edit cells (double-click a cell to change it)", theme.colors.text_muted)
+            }
+            EditMode::Loading => ("Checking the source table...", theme.colors.text_muted),
+            EditMode::On => ("Stop editing", theme.colors.accent),
+            EditMode::Refused(_) => ("Editing unavailable; click to retry", theme.colors.warning),
+        };
+        let refused = match &self.edit_mode {
+            EditMode::Refused(reason) => Some(reason.clone()),
+            _ => None,
+        };
+
+        Some(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(4.0))
+                .when_some(refused, |d, reason| {
+                    d.child(
+                        div()
+                            .id("results-edit-refused")
+                            .max_w(px(320.0))
+                            .truncate()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.warning)
+                            .tooltip(Tooltip::text(reason.clone()))
+                            .child(reason),
+                    )
+                })
+                .child(
+                    div()
+                        .id("results-edit-toggle")
+                        .p(px(2.0))
+                        .rounded(px(4.0))
+                        .cursor_pointer()
+                        .hover(|style| style.bg(hover_bg))
+                        .when(self.edit_mode == EditMode::On, |d| {
+                            d.bg(theme.colors.element_background)
+                        })
+                        .tooltip(Tooltip::text(tooltip))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.toggle_edit_mode(cx);
+                        }))
+                        .child(Icon::new(IconName::Edit).size(IconSize::Small).color(icon_color)),
+                ),
+        )
+    }
+
+    /// Render previous/next navigation through the tab's result history.
+    fn render_history_nav(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> Option<gpui::Div> {
+        let history = self.history().filter(|history| history.len() > 1)?;
+        let position = history.cursor()? + 1;
+        let snapshot = history.current()?;
+        let has_previous = history.has_previous() && !self.state.status.is_active();
+        let has_next = history.has_next() && !self.state.status.is_active();
+        let hover_bg = theme.colors.element_hover;
+        let sql_preview: String = snapshot.sql.split_whitespace().collect::<Vec<_>>().join(" ");
+
+        let nav_button = |id: &'static str, icon: IconName, enabled: bool| {
+            div()
+                .id(id)
+                .p(px(2.0))
+                .rounded(px(4.0))
+                .when(enabled, |d| d.cursor_pointer().hover(|style| style.bg(hover_bg)))
+                .when(!enabled, |d| d.opacity(0.4))
+                .child(Icon::new(icon).size(IconSize::Small).color(theme.colors.text_muted))
+        };
 
-        // Non-NULL string value
-        row.try_get::<_, Option<String>>(index).ok().flatten().unwrap_or_else(|| "NULL".to_string())
+        Some(
+            div()
+                .flex()
+                .items_center()
+                .gap(px(4.0))
+                .text_size(px(11.0))
+                .text_color(theme.colors.text_muted)
+                .child(
+                    nav_button("results-history-previous", IconName::ChevronLeft, has_previous)
+                        .tooltip(Tooltip::text("Previous result"))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.show_previous_result(cx);
+                        })),
+                )
+                .child(
+                    div().id("results-history-position").tooltip(Tooltip::text(sql_preview)).child(
+                        format!(
+                            "Run {} of {} · {}",
+                            position,
+                            history.len(),
+                            format_age(snapshot.age())
+                        ),
+                    ),
+                )
+                .when(snapshot.is_explain_analyze(), |d| {
+                    d.child(
+                        div()
+                            .id("results-history-analyze")
+                            .text_color(theme.colors.warning)
+                            .tooltip(Tooltip::text(
+                                "EXPLAIN ANALYZE ran the statement, including any writes",
+                            ))
+                            .child("Executed"),
+                    )
+                })
+                .child(
+                    nav_button("results-history-next", IconName::ChevronRight, has_next)
+                        .tooltip(Tooltip::text("Next result"))
+                        .on_click(cx.listener(|this, _, _window, cx| {
+                            this.show_next_result(cx);
+                        })),
+                ),
+        )
+    }
+
+    /// Render the selector for switching between batch result sets.
+    fn render_result_selector(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>().clone();
+        let hover_bg = theme.colors.element_hover;
+
+        div()
+            .flex()
+            .items_center()
+            .h(px(28.0))
+            .px(px(8.0))
+            .gap(px(2.0))
+            .border_b_1()
+            .border_color(theme.colors.border)
+            .children((0..self.result_sets.len()).map(|index| {
+                let is_active = index == self.active_result;
+                div()
+                    .id(("results-set-tab", index))
+                    .px(px(8.0))
+                    .py(px(2.0))
+                    .rounded(px(4.0))
+                    .text_size(px(11.0))
+                    .cursor_pointer()
+                    .when(is_active, |d| {
+                        d.bg(theme.colors.element_background).text_color(theme.colors.text)
+                    })
+                    .when(!is_active, |d| {
+                        d.text_color(theme.colors.text_muted).hover(|style| style.bg(hover_bg))
+                    })
+                    .on_click(cx.listener(move |this, _, _window, cx| {
+                        this.select_result(index, cx);
+                    }))
+                    .child(format!("Result {}", index + 1))
+            }))
     }
 
     /// Render the empty state.
@@ -463,11 +1789,15 @@ impl ResultsPanel {
     }
 
     /// Render the streaming/complete state with results.
-    fn render_results_state(&self, theme: &TuskTheme) -> impl IntoElement {
+    fn render_results_state(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
         let is_streaming = self.state.status.is_streaming();
         let row_count = self.state.rows.len();
         let total_rows = self.state.total_rows;
         let was_cancelled = self.state.error.as_ref().map(|e| e.is_cancelled).unwrap_or(false);
+        let visible = self.visible_columns();
+        let displayed_rows = self.state.rows.iter().take(MAX_DISPLAYED_ROWS);
+        let drop_target_bg = theme.colors.drop_target_background;
+        let accent = theme.colors.accent;
 
         div()
             .flex()
@@ -483,46 +1813,121 @@ impl ResultsPanel {
                     .bg(theme.colors.element_background)
                     .border_b_1()
                     .border_color(theme.colors.border)
-                    .children(self.state.columns.iter().enumerate().map(|(col_idx, col)| {
+                    .children(visible.iter().enumerate().map(|(position, &col_idx)| {
+                        let col = &self.state.columns[col_idx];
                         let col_name = col.name.clone();
                         let col_name_for_tooltip = col.name.clone();
+                        let sort_icon = match self.state.sort {
+                            Some(sort) if sort.col == col_idx => Some(match sort.direction {
+                                SortDirection::Ascending => IconName::ChevronUp,
+                                SortDirection::Descending => IconName::ChevronDown,
+                            }),
+                            _ => None,
+                        };
                         div()
                             .id(("results-header-col", col_idx))
+                            .on_drag(DraggedColumn { col: col_idx }, |dragged, _, _, cx| {
+                                cx.stop_propagation();
+                                cx.new(|_| dragged.clone())
+                            })
+                            .drag_over::<DraggedColumn>(move |header, dragged, _, _cx| {
+                                if dragged.col != col_idx {
+                                    header.bg(drop_target_bg).border_l_2().border_color(accent)
+                                } else {
+                                    header
+                                }
+                            })
+                            .on_drop(cx.listener(move |this, dragged: &DraggedColumn, _, cx| {
+                                this.move_column(dragged.col, position, cx);
+                            }))
+                            .on_mouse_down(
+                                MouseButton::Right,
+                                cx.listener(move |this, e: &MouseDownEvent, _window, cx| {
+                                    this.show_header_menu(col_idx, e.position, cx);
+                                }),
+                            )
+                            .on_click(cx.listener(move |this, _, _window, cx| {
+                                this.cycle_sort(col_idx, cx);
+                            }))
                             .flex_1()
+                            .flex()
+                            .items_center()
+                            .gap(px(2.0))
                             .min_w(px(100.0))
                             .px(px(8.0))
+                            .cursor_pointer()
                             .text_size(px(11.0))
                             .font_weight(gpui::FontWeight::MEDIUM)
                             .text_color(theme.colors.text_muted)
-                            .truncate()
                             .tooltip(Tooltip::text(col_name_for_tooltip))
-                            .child(col_name)
+                            .child(div().flex_1().truncate().child(col_name))
+                            .when_some(sort_icon, |d, icon| {
+                                d.child(Icon::new(icon).size(IconSize::XSmall).color(accent))
+                            })
                     })),
             )
             // Results body with rows (simplified - no virtualization yet)
             .child(div().id("results-body").flex_1().overflow_y_scroll().children(
-                self.state.rows.iter().take(100).enumerate().map(|(row_idx, row)| {
+                displayed_rows.enumerate().map(|(row_idx, row)| {
+                    let selection = self.selection;
                     let bg = if row_idx % 2 == 0 {
                         theme.colors.panel_background
                     } else {
                         theme.colors.element_background
                     };
                     div().flex().items_center().h(px(24.0)).px(px(8.0)).bg(bg).children(
-                        row.cells.iter().enumerate().map(|(col_idx, cell)| {
-                            let cell_text = cell.clone();
-                            let cell_text_for_tooltip = cell.clone();
+                        visible.iter().enumerate().filter_map(|(position, &col_idx)| {
+                            let cell = row.display(col_idx)?;
+                            let cell_text = cell.to_string();
+                            let cell_text_for_tooltip = cell.to_string();
                             // Combine row and col into a unique ID (row * 1000 + col allows up to 1000 columns)
                             let cell_id = row_idx * 1000 + col_idx;
-                            div()
-                                .id(("results-cell", cell_id))
-                                .flex_1()
-                                .min_w(px(100.0))
-                                .px(px(8.0))
-                                .text_size(px(12.0))
-                                .text_color(theme.colors.text)
-                                .truncate()
-                                .tooltip(Tooltip::text(cell_text_for_tooltip))
-                                .child(cell_text)
+                            let is_selected =
+                                selection.is_some_and(|s| s.contains(row_idx, position));
+                            Some(
+                                div()
+                                    .id(("results-cell", cell_id))
+                                    .flex_1()
+                                    .min_w(px(100.0))
+                                    .px(px(8.0))
+                                    .when(is_selected, |d| {
+                                        d.bg(theme.colors.list_active_selection_background)
+                                    })
+                                    .on_mouse_down(
+                                        MouseButton::Left,
+                                        cx.listener(move |this, e: &MouseDownEvent, window, cx| {
+                                            window.focus(&this.focus_handle, cx);
+                                            this.select_cell(
+                                                row_idx,
+                                                col_idx,
+                                                e.modifiers.shift,
+                                                cx,
+                                            );
+                                            if e.click_count == 2 {
+                                                if this.edit_mode == EditMode::On {
+                                                    this.edit_cell(row_idx, col_idx, window, cx);
+                                                } else {
+                                                    this.inspect_cell(
+                                                        row_idx, col_idx, e.position, window, cx,
+                                                    );
+                                                }
+                                            }
+                                        }),
+                                    )
+                                    .on_mouse_down(
+                                        MouseButton::Right,
+                                        cx.listener(move |this, e: &MouseDownEvent, window, cx| {
+                                            this.show_cell_menu(
+                                                row_idx, col_idx, e.position, window, cx,
+                                            );
+                                        }),
+                                    )
+                                    .text_size(px(12.0))
+                                    .text_color(theme.colors.text)
+                                    .truncate()
+                                    .tooltip(Tooltip::text(cell_text_for_tooltip))
+                                    .child(cell_text),
+                            )
                         }),
                     )
                 }),
@@ -560,8 +1965,11 @@ impl ResultsPanel {
                                             row_count,
                                             if row_count == 1 { "" } else { "s" }
                                         )
-                                    } else if row_count > 100 {
-                                        format!("{} rows (showing first 100)", total_rows)
+                                    } else if row_count > MAX_DISPLAYED_ROWS {
+                                        format!(
+                                            "{} rows (showing first {})",
+                                            total_rows, MAX_DISPLAYED_ROWS
+                                        )
                                     } else {
                                         format!(
                                             "{} row{}",
@@ -571,12 +1979,12 @@ impl ResultsPanel {
                                     }),
                             ),
                     )
-                    .when(self.state.execution_time_ms.is_some(), |s| {
+                    .when_some(self.state.execution_time_us, |s, us| {
                         s.child(
                             div()
                                 .text_size(px(11.0))
                                 .text_color(theme.colors.text_muted)
-                                .child(format!("{}ms", self.state.execution_time_ms.unwrap_or(0))),
+                                .child(format_elapsed(us)),
                         )
                     })
                     .when(self.state.rows_affected.is_some(), |s| {
@@ -589,6 +1997,45 @@ impl ResultsPanel {
             )
     }
 
+    /// Render the status of a statement that returned no columns.
+    fn render_command_state(&self, theme: &TuskTheme) -> impl IntoElement {
+        let status = self.state.command_status.clone().unwrap_or_default();
+
+        div()
+            .flex()
+            .flex_col()
+            .items_center()
+            .justify_center()
+            .size_full()
+            .gap(px(8.0))
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .child(
+                        Icon::new(IconName::Check)
+                            .size(IconSize::Medium)
+                            .color(theme.colors.success),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(13.0))
+                            .font_weight(gpui::FontWeight::MEDIUM)
+                            .text_color(theme.colors.text)
+                            .child(status),
+                    ),
+            )
+            .when_some(self.state.execution_time_us, |el, us| {
+                el.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(theme.colors.text_muted)
+                        .child(format!("Completed in {}", format_elapsed(us))),
+                )
+            })
+    }
+
     /// Render the error state.
     fn render_error_state(&self, theme: &TuskTheme) -> impl IntoElement {
         let error = self.state.error.as_ref();
@@ -649,6 +2096,48 @@ impl ResultsPanel {
 
 impl EventEmitter<PanelEvent> for ResultsPanel {}
 
+/// Terminal query events, used to drive the status bar.
+impl EventEmitter<ExecutionEvent> for ResultsPanel {}
+
+/// Status of a completed statement that returned no columns (e.g. "SET (0.3ms)").
+#[derive(Debug, Clone, PartialEq)]
+pub struct CommandStatus(pub String);
+
+impl EventEmitter<CommandStatus> for ResultsPanel {}
+
+/// A notice the server raised while a query ran (e.g. `RAISE NOTICE`).
+#[derive(Debug, Clone)]
+pub struct ServerNotice(pub Message);
+
+impl EventEmitter<ServerNotice> for ResultsPanel {}
+
+/// A result large enough that exporting it beats rendering it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LargeResult {
+    /// Estimated in-memory size of the rows received so far
+    pub estimated_bytes: u64,
+    /// Rows received so far
+    pub rows: usize,
+}
+
+impl LargeResult {
+    /// Warning shown to the user.
+    pub fn message(&self) -> String {
+        format!(
+            "Large result: {} rows, about {} in memory",
+            self.rows,
+            format_bytes(i64::try_from(self.estimated_bytes).unwrap_or(i64::MAX))
+        )
+    }
+}
+
+impl EventEmitter<LargeResult> for ResultsPanel {}
+
+/// Selection changes, used for the status bar summary.
+impl EventEmitter<SelectionSummary> for ResultsPanel {}
+
+impl EventEmitter<ResultsEditEvent> for ResultsPanel {}
+
 impl Focusable for ResultsPanel {
     fn focus_handle(&self, _cx: &App) -> FocusHandle {
         self.focus_handle.clone()
@@ -683,19 +2172,56 @@ impl Panel for ResultsPanel {
 
 impl Render for ResultsPanel {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = cx.global::<TuskTheme>();
+        let result_selector = (self.result_sets.len() > 1).then(|| self.render_result_selector(cx));
+        let theme = cx.global::<TuskTheme>().clone();
+        let edit_toggle = self.render_edit_toggle(&theme, cx);
+        let compare_button = self.render_compare_button(&theme, cx);
+        let copy_markdown = self.render_copy_markdown(&theme, cx);
+        let export_button = self.render_export_button(&theme);
+        let history_nav = self.render_history_nav(&theme, cx);
 
         let content = match &self.state.status {
-            ResultsStatus::Empty => self.render_empty_state(theme).into_any_element(),
-            ResultsStatus::Loading => self.render_loading_state(theme).into_any_element(),
+            ResultsStatus::Empty => self.render_empty_state(&theme).into_any_element(),
+            ResultsStatus::Loading => self.render_loading_state(&theme).into_any_element(),
+            ResultsStatus::Complete if self.state.command_status.is_some() => {
+                self.render_command_state(&theme).into_any_element()
+            }
             ResultsStatus::Streaming | ResultsStatus::Complete => {
-                self.render_results_state(theme).into_any_element()
+                self.render_results_state(&theme, cx).into_any_element()
             }
-            ResultsStatus::Error => self.render_error_state(theme).into_any_element(),
+            ResultsStatus::Error => self.render_error_state(&theme).into_any_element(),
         };
 
         div()
             .track_focus(&self.focus_handle)
+            .key_context("ResultsGrid")
+            .on_action(cx.listener(|this, _: &results_grid::MoveUp, _window, cx| {
+                this.move_selection(-1, 0, false, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::MoveDown, _window, cx| {
+                this.move_selection(1, 0, false, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::MoveLeft, _window, cx| {
+                this.move_selection(0, -1, false, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::MoveRight, _window, cx| {
+                this.move_selection(0, 1, false, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::ExtendUp, _window, cx| {
+                this.move_selection(-1, 0, true, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::ExtendDown, _window, cx| {
+                this.move_selection(1, 0, true, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::ExtendLeft, _window, cx| {
+                this.move_selection(0, -1, true, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::ExtendRight, _window, cx| {
+                this.move_selection(0, 1, true, cx);
+            }))
+            .on_action(cx.listener(|this, _: &results_grid::CopySelection, _window, cx| {
+                this.copy_selection(CellCopy::Tsv, cx);
+            }))
             .size_full()
             .flex()
             .flex_col()
@@ -707,6 +2233,7 @@ impl Render for ResultsPanel {
                     .w_full()
                     .flex()
                     .items_center()
+                    .justify_between()
                     .px(px(12.0))
                     .border_b_1()
                     .border_color(theme.colors.border)
@@ -723,12 +2250,35 @@ impl Render for ResultsPanel {
                                     .text_color(theme.colors.text)
                                     .child("Results"),
                             ),
+                    )
+                    .child(
+                        div()
+                            .flex()
+                            .items_center()
+                            .gap(px(8.0))
+                            .children(edit_toggle)
+                            .children(compare_button)
+                            .children(copy_markdown)
+                            .children(export_button)
+                            .children(history_nav),
                     ),
             )
+            .children(result_selector)
             .child(
                 // Panel content
                 div().flex_1().overflow_hidden().child(content),
             )
+            .when_some(self.inspector.clone(), |el, (position, inspector)| {
+                el.child(
+                    deferred(
+                        anchored()
+                            .position(position)
+                            .snap_to_window_with_margin(px(8.))
+                            .child(div().occlude().child(inspector)),
+                    )
+                    .with_priority(1),
+                )
+            })
     }
 }
 
@@ -751,14 +2301,20 @@ mod tests {
         assert!(state.columns.is_empty());
         assert!(state.rows.is_empty());
         assert_eq!(state.total_rows, 0);
-        assert!(state.execution_time_ms.is_none());
+        assert!(state.execution_time_us.is_none());
         assert!(state.status.is_empty());
     }
 
     #[test]
     fn test_results_panel_state_clear() {
         let mut state = ResultsPanelState::default();
-        state.columns.push(DisplayColumn { name: "id".to_string(), type_name: "int4".to_string() });
+        state.columns.push(DisplayColumn {
+            name: "id".to_string(),
+            type_oid: 23,
+            type_name: "int4".to_string(),
+            table_oid: None,
+            column_id: None,
+        });
         state.total_rows = 100;
         state.status = ResultsStatus::Complete;
 
@@ -768,4 +2324,162 @@ mod tests {
         assert_eq!(state.total_rows, 0);
         assert!(state.status.is_empty());
     }
+
+    fn sample_state() -> ResultsPanelState {
+        let column = |name: &str| DisplayColumn {
+            name: name.to_string(),
+            type_oid: 25,
+            type_name: "text".to_string(),
+            table_oid: None,
+            column_id: None,
+        };
+        ResultsPanelState {
+            columns: vec![column("id"), column("name"), column("email")],
+            rows: vec![
+                DisplayRow { cells: vec![Some("1".into()), Some("ada".into()), None] },
+                DisplayRow {
+                    cells: vec![
+                        Some("2".into()),
+                        Some("alan".into()),
+                        Some("alan@example.com".into()),
+                    ],
+                },
+            ],
+            total_rows: 2,
+            status: ResultsStatus::Complete,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_column_layout_applies_to_rows() {
+        let state = sample_state();
+        let names: Vec<&str> = state.columns.iter().map(|c| c.name.as_str()).collect();
+        let mut layout = ColumnLayout::new(&names);
+        layout.hide(0);
+        layout.move_to(2, 0);
+
+        let cells: Vec<Vec<Option<String>>> =
+            state.rows.iter().map(|row| layout.project(&row.cells)).collect();
+        assert_eq!(
+            cells,
+            vec![
+                vec![None, Some("ada".to_string())],
+                vec![Some("alan@example.com".to_string()), Some("alan".to_string())],
+            ]
+        );
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_export_keeps_visible_columns_in_order() {
+        let state = sample_state();
+        let names: Vec<&str> = state.columns.iter().map(|c| c.name.as_str()).collect();
+        let mut layout = ColumnLayout::new(&names);
+        layout.hide(1);
+        layout.move_to(2, 0);
+
+        let data = ResultData::from_state(&state).select_columns(&layout.visible());
+        let columns: Vec<&str> = data.columns.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(columns, vec!["email", "id"]);
+        assert_eq!(
+            data.rows,
+            vec![
+                vec![None, Some("1".to_string())],
+                vec![Some("alan@example.com".to_string()), Some("2".to_string())],
+            ]
+        );
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_selected_cells_copy_as_tsv() {
+        use tusk_core::models::export::ClipboardFormat;
+
+        let state = sample_state();
+        let selection =
+            CellSelection::single(CellPosition::new(0, 1)).extend_to(CellPosition::new(1, 2));
+        let rows = &state.rows[selection.rows()];
+        let columns = selection.source_cols(&[0, 1, 2]);
+
+        let data = ResultData::from_rows(&state.columns, rows).select_columns(&columns);
+        assert_eq!(
+            ClipboardFormat::Tsv.format(&data.columns, &data.rows),
+            "ada\t\nalan\talan@example.com"
+        );
+
+        // With email moved to the front, the first two shown columns are email and id
+        let names: Vec<&str> = state.columns.iter().map(|c| c.name.as_str()).collect();
+        let mut layout = ColumnLayout::new(&names);
+        layout.move_to(2, 0);
+        let selection =
+            CellSelection::single(CellPosition::new(1, 0)).extend_to(CellPosition::new(1, 1));
+        let columns = selection.source_cols(&layout.visible());
+        let data = ResultData::from_rows(&state.columns, &state.rows[selection.rows()])
+            .select_columns(&columns);
+        assert_eq!(ClipboardFormat::Tsv.format(&data.columns, &data.rows), "alan@example.com\t2");
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_null_text_is_not_null() {
+        let mut state = sample_state();
+        state.rows[1].cells[2] = Some("NULL".to_string());
+
+        let data = ResultData::from_state(&state).select_columns(&[2]);
+        assert_eq!(data.rows, vec![vec![None], vec![Some("NULL".to_string())]]);
+        assert_eq!(state.rows[0].display(2), Some("NULL"));
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_export_writes_decoded_typed_values() {
+        use tokio_postgres::types::Type;
+        use tusk_core::models::export::{write_export, ExportFormat};
+        use tusk_core::models::value::scalar_text;
+
+        let types = [Type::NUMERIC, Type::TIMESTAMPTZ, Type::UUID, Type::JSONB];
+        let columns: Vec<DisplayColumn> = types
+            .iter()
+            .map(|ty| DisplayColumn {
+                name: ty.name().to_string(),
+                type_oid: ty.oid(),
+                type_name: ty.name().to_string(),
+                table_oid: None,
+                column_id: None,
+            })
+            .collect();
+        // 12.50: two base-10000 digits, weight 0, scale 2
+        let numeric: Vec<u8> =
+            [2i16, 0, 0, 2, 12, 5000].iter().flat_map(|n| n.to_be_bytes()).collect();
+        let stamp = (86_400_000_000i64 + 1_500_000).to_be_bytes();
+        let uuid: Vec<u8> = (1..=16).collect();
+        let jsonb = [&[1u8][..], br#"{"a": [1, 2]}"#].concat();
+        let raws: [&[u8]; 4] = [&numeric, &stamp, &uuid, &jsonb];
+        let cells = types.iter().zip(raws).map(|(ty, raw)| scalar_text(ty, raw).unwrap()).collect();
+        let rows = [DisplayRow { cells }, DisplayRow { cells: vec![None; 4] }];
+
+        let data = ResultData::from_rows(&columns, &rows);
+        assert_eq!(data.columns[3].type_oid, Type::JSONB.oid());
+
+        let mut out = Vec::new();
+        write_export(ExportFormat::Csv, &data.columns, &data.rows, &mut out, |_| {}).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "numeric,timestamptz,uuid,jsonb\n\
+             12.50,2000-01-02 00:00:01.5+00,01020304-0506-0708-090a-0b0c0d0e0f10,\
+             \"{\"\"a\"\": [1, 2]}\"\n\
+             ,,,\n"
+        );
+
+        let mut out = Vec::new();
+        let json = ExportFormat::Json { lines: true };
+        write_export(json, &data.columns, &data.rows, &mut out, |_| {}).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"numeric\":\"12.50\",\"timestamptz\":\"2000-01-02 00:00:01.5+00\",\
+             \"uuid\":\"01020304-0506-0708-090a-0b0c0d0e0f10\",\"jsonb\":{\"a\":[1,2]}}\n\
+             {\"numeric\":null,\"timestamptz\":null,\"uuid\":null,\"jsonb\":null}\n"
+        );
+    }
 }
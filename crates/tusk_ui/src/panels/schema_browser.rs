@@ -2,18 +2,29 @@
 //!
 //! The schema browser lives in the left dock and provides a tree view of:
 //! - Schemas
-//! - Tables (with columns)
+//! - Tables (with columns, constraints and indexes)
 //! - Views (with columns)
 //! - Functions
 
 use gpui::{
     div, prelude::*, px, App, ClipboardItem, Context, Entity, EventEmitter, FocusHandle,
-    FontWeight, Point, Render, SharedString, Subscription, Window,
+    FontWeight, Point, Render, SharedString, Subscription, Task, WeakEntity, Window,
 };
 
-use tusk_core::models::schema::DatabaseSchema;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tusk_core::models::query_builder;
+use tusk_core::models::schema::{
+    ColumnDetail, ColumnSample, ConstraintKind, DatabaseSchema, SchemaObjectKind, SearchHit,
+    SearchMatchKind, TableInfo,
+};
+use tusk_core::services::schema::TableOperation;
+use tusk_core::services::SchemaService;
 
 use crate::context_menu::{ContextMenu, ContextMenuItem, ContextMenuLayer};
+use crate::debounce::Debouncer;
 use crate::icon::{Icon, IconName, IconSize};
 use crate::layout::spacing;
 use crate::panel::{DockPosition, Focusable, Panel, PanelEvent};
@@ -22,11 +33,86 @@ use crate::text_input::{TextInput, TextInputEvent};
 use crate::tree::{Tree, TreeEvent, TreeItem};
 use crate::TuskTheme;
 
+/// Quiet interval before filter keystrokes are applied to the tree.
+const FILTER_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// Minimum filter length before columns and comments are searched.
+const MIN_SEARCH_LEN: usize = 2;
+
+/// Maximum number of search hits listed under the filter.
+const MAX_SEARCH_HITS: usize = 50;
+
+/// ID of the "Pinned" section at the top of the tree.
+const PINNED_FOLDER_ID: &str = "@pinned";
+
+/// Prefix keeping the IDs of pinned copies distinct from their originals.
+const PINNED_ID_PREFIX: &str = "@pinned/";
+
+/// Longest sampled value shown before it's truncated.
+const MAX_SAMPLE_VALUE_CHARS: usize = 40;
+
 /// Events emitted by the schema browser panel (T056).
 #[derive(Debug, Clone)]
 pub enum SchemaBrowserEvent {
     /// User requested a schema refresh.
     RefreshRequested,
+    /// User requested the DDL or definition of an object.
+    ShowDefinition(DefinitionTarget),
+    /// User requested a materialized view refresh.
+    RefreshMaterializedView { schema: String, name: String, concurrently: bool },
+    /// User requested a query to be opened in a new tab and run.
+    RunQuery { title: String, sql: String },
+    /// User cancelled the schema load in progress.
+    CancelLoad,
+    /// User pinned or unpinned a table; carries the full pin list (`schema.name`).
+    PinnedTablesChanged(Vec<String>),
+    /// User requested a table be truncated or dropped; the workspace confirms first.
+    TableOperation { operation: TableOperation, schema: String, name: String },
+    /// User requested the value distribution of a column.
+    SampleColumn { column_id: String, schema: String, table: String, column: String },
+    /// User expanded a table or view whose columns haven't been loaded.
+    LoadColumns { schema: String, name: String },
+}
+
+/// A schema object whose definition can be shown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DefinitionTarget {
+    /// A table (shown as `CREATE TABLE` with its indexes).
+    Table { schema: String, name: String },
+    /// A view or materialized view.
+    View { schema: String, name: String },
+    /// A function, identified by its identity arguments.
+    Function { schema: String, name: String, arguments: String },
+}
+
+impl DefinitionTarget {
+    /// Resolve the target for a tree item, using the schema prefix of its ID.
+    pub fn for_item(item: &SchemaItem) -> Option<Self> {
+        match item {
+            SchemaItem::Table { id, name, .. } => {
+                let schema = unpinned_id(id).strip_suffix(&format!(".{}", name))?.to_string();
+                Some(Self::Table { schema, name: name.clone() })
+            }
+            SchemaItem::View { id, name, .. } => {
+                let schema = unpinned_id(id).strip_suffix(&format!(".{}", name))?.to_string();
+                Some(Self::View { schema, name: name.clone() })
+            }
+            SchemaItem::Function { id, name, arguments, .. } => {
+                let schema =
+                    unpinned_id(id).strip_suffix(&format!(".{}({})", name, arguments))?.to_string();
+                Some(Self::Function { schema, name: name.clone(), arguments: arguments.clone() })
+            }
+            _ => None,
+        }
+    }
+
+    /// Tab title for the definition.
+    pub fn title(&self) -> String {
+        match self {
+            Self::Table { name, .. } | Self::View { name, .. } => format!("{} (DDL)", name),
+            Self::Function { name, arguments, .. } => format!("{}({}) (DDL)", name, arguments),
+        }
+    }
 }
 
 /// Schema item types for the tree view.
@@ -40,14 +126,36 @@ pub enum SchemaItem {
     ViewsFolder { id: String, children: Vec<SchemaItem> },
     /// Folder for functions within a schema.
     FunctionsFolder { id: String, children: Vec<SchemaItem> },
-    /// A table within a schema.
-    Table { id: String, name: String, children: Vec<SchemaItem> },
-    /// A view within a schema.
-    View { id: String, name: String, is_materialized: bool, children: Vec<SchemaItem> },
+    /// Section listing pinned tables above the schemas.
+    PinnedFolder { id: String, children: Vec<SchemaItem> },
+    /// Folder for constraints within a table.
+    ConstraintsFolder { id: String, children: Vec<SchemaItem> },
+    /// Folder for indexes within a table.
+    IndexesFolder { id: String, children: Vec<SchemaItem> },
+    /// A table within a schema. Without `columns_loaded`, columns are loaded on first expand.
+    Table { id: String, name: String, columns_loaded: bool, children: Vec<SchemaItem> },
+    /// A view within a schema, with columns loaded like a table's.
+    View {
+        id: String,
+        name: String,
+        is_materialized: bool,
+        columns_loaded: bool,
+        children: Vec<SchemaItem>,
+    },
     /// A function within a schema.
     Function { id: String, name: String, arguments: String, return_type: String },
     /// A column within a table or view.
     Column { id: String, name: String, data_type: String, is_nullable: bool, is_primary_key: bool },
+    /// A constraint on a table. Foreign keys carry the (schema, table) they reference.
+    Constraint {
+        id: String,
+        name: String,
+        kind: ConstraintKind,
+        definition: String,
+        references: Option<(String, String)>,
+    },
+    /// An index on a table.
+    Index { id: String, name: String, summary: String, is_primary: bool },
 }
 
 impl TreeItem for SchemaItem {
@@ -59,10 +167,15 @@ impl TreeItem for SchemaItem {
             SchemaItem::TablesFolder { id, .. } => id.clone(),
             SchemaItem::ViewsFolder { id, .. } => id.clone(),
             SchemaItem::FunctionsFolder { id, .. } => id.clone(),
+            SchemaItem::PinnedFolder { id, .. } => id.clone(),
+            SchemaItem::ConstraintsFolder { id, .. } => id.clone(),
+            SchemaItem::IndexesFolder { id, .. } => id.clone(),
             SchemaItem::Table { id, .. } => id.clone(),
             SchemaItem::View { id, .. } => id.clone(),
             SchemaItem::Function { id, .. } => id.clone(),
             SchemaItem::Column { id, .. } => id.clone(),
+            SchemaItem::Constraint { id, .. } => id.clone(),
+            SchemaItem::Index { id, .. } => id.clone(),
         }
     }
 
@@ -78,6 +191,15 @@ impl TreeItem for SchemaItem {
             SchemaItem::FunctionsFolder { children, .. } => {
                 format!("Functions ({})", children.len()).into()
             }
+            SchemaItem::PinnedFolder { children, .. } => {
+                format!("Pinned ({})", children.len()).into()
+            }
+            SchemaItem::ConstraintsFolder { children, .. } => {
+                format!("Constraints ({})", children.len()).into()
+            }
+            SchemaItem::IndexesFolder { children, .. } => {
+                format!("Indexes ({})", children.len()).into()
+            }
             SchemaItem::Table { name, .. } => name.clone().into(),
             SchemaItem::View { name, is_materialized, .. } => {
                 if *is_materialized {
@@ -103,6 +225,10 @@ impl TreeItem for SchemaItem {
                 }
                 label.into()
             }
+            SchemaItem::Constraint { name, definition, .. } => {
+                format!("{} {}", name, definition).into()
+            }
+            SchemaItem::Index { name, summary, .. } => format!("{} {}", name, summary).into(),
         }
     }
 
@@ -112,6 +238,9 @@ impl TreeItem for SchemaItem {
             SchemaItem::TablesFolder { .. } => IconName::Folder,
             SchemaItem::ViewsFolder { .. } => IconName::Folder,
             SchemaItem::FunctionsFolder { .. } => IconName::Folder,
+            SchemaItem::PinnedFolder { .. } => IconName::Pin,
+            SchemaItem::ConstraintsFolder { .. } => IconName::Folder,
+            SchemaItem::IndexesFolder { .. } => IconName::Folder,
             SchemaItem::Table { .. } => IconName::Table,
             SchemaItem::View { is_materialized, .. } => {
                 if *is_materialized {
@@ -128,6 +257,18 @@ impl TreeItem for SchemaItem {
                     IconName::Column
                 }
             }
+            SchemaItem::Constraint { kind, .. } => match kind {
+                ConstraintKind::PrimaryKey => IconName::Key,
+                ConstraintKind::ForeignKey => IconName::ForeignKey,
+                _ => IconName::Constraint,
+            },
+            SchemaItem::Index { is_primary, .. } => {
+                if *is_primary {
+                    IconName::Key
+                } else {
+                    IconName::Index
+                }
+            }
         })
     }
 
@@ -137,10 +278,24 @@ impl TreeItem for SchemaItem {
             SchemaItem::TablesFolder { children, .. } => Some(children),
             SchemaItem::ViewsFolder { children, .. } => Some(children),
             SchemaItem::FunctionsFolder { children, .. } => Some(children),
+            SchemaItem::PinnedFolder { children, .. } => Some(children),
+            SchemaItem::ConstraintsFolder { children, .. } => Some(children),
+            SchemaItem::IndexesFolder { children, .. } => Some(children),
             SchemaItem::Table { children, .. } => Some(children),
             SchemaItem::View { children, .. } => Some(children),
             SchemaItem::Function { .. } => None,
             SchemaItem::Column { .. } => None,
+            SchemaItem::Constraint { .. } => None,
+            SchemaItem::Index { .. } => None,
+        }
+    }
+
+    fn is_loaded(&self) -> bool {
+        match self {
+            SchemaItem::Table { columns_loaded, .. } | SchemaItem::View { columns_loaded, .. } => {
+                *columns_loaded
+            }
+            _ => true,
         }
     }
 }
@@ -152,11 +307,18 @@ impl TreeItem for SchemaItem {
 ///   - Tables (folder)
 ///     - Table
 ///       - Column
+///       - Constraints (folder)
+///         - Constraint
+///       - Indexes (folder)
+///         - Index
 ///   - Views (folder)
 ///     - View
 ///       - Column
 ///   - Functions (folder)
 ///     - Function
+///
+/// Tables and views whose columns aren't loaded yet are marked so their
+/// columns are requested when they're first expanded.
 pub fn database_schema_to_tree(schema: &DatabaseSchema) -> Vec<SchemaItem> {
     schema
         .schemas
@@ -170,25 +332,54 @@ pub fn database_schema_to_tree(schema: &DatabaseSchema) -> Vec<SchemaItem> {
                 .iter()
                 .filter(|t| &t.schema == schema_name)
                 .map(|table| {
+                    let table_id = format!("{}.{}", schema_name, table.name);
+
                     // Get columns for this table
-                    let columns: Vec<SchemaItem> = schema
+                    let mut columns: Vec<SchemaItem> = schema
                         .table_columns
                         .get(&(schema_name.clone(), table.name.clone()))
-                        .map(|cols| {
-                            cols.iter()
-                                .map(|col| SchemaItem::Column {
-                                    id: format!("{}.{}.{}", schema_name, table.name, col.name),
-                                    name: col.name.clone(),
-                                    data_type: col.data_type.clone(),
-                                    is_nullable: col.is_nullable,
-                                    is_primary_key: col.is_primary_key,
-                                })
-                                .collect()
-                        })
+                        .map(|cols| column_items(&table_id, cols))
                         .unwrap_or_default();
 
+                    // Column ids continue the table id with `.`, so a `/#` suffix never collides
+                    let constraints_id = format!("{}/#constraints", table_id);
+                    let constraints: Vec<SchemaItem> = schema
+                        .table_constraints(schema_name, &table.name)
+                        .map(|constraint| SchemaItem::Constraint {
+                            id: format!("{}/{}", constraints_id, constraint.name),
+                            name: constraint.name.clone(),
+                            kind: constraint.kind,
+                            definition: constraint.definition.clone(),
+                            references: constraint
+                                .references()
+                                .map(|(schema, table)| (schema.to_string(), table.to_string())),
+                        })
+                        .collect();
+                    if !constraints.is_empty() {
+                        columns.push(SchemaItem::ConstraintsFolder {
+                            id: constraints_id,
+                            children: constraints,
+                        });
+                    }
+
+                    let indexes_id = format!("{}/#indexes", table_id);
+                    let indexes: Vec<SchemaItem> = schema
+                        .table_indexes(schema_name, &table.name)
+                        .map(|index| SchemaItem::Index {
+                            id: format!("{}/{}", indexes_id, index.name),
+                            name: index.name.clone(),
+                            summary: index.summary(),
+                            is_primary: index.is_primary,
+                        })
+                        .collect();
+                    if !indexes.is_empty() {
+                        columns
+                            .push(SchemaItem::IndexesFolder { id: indexes_id, children: indexes });
+                    }
+
                     SchemaItem::Table {
-                        id: format!("{}.{}", schema_name, table.name),
+                        columns_loaded: schema.columns_loaded(schema_name, &table.name),
+                        id: table_id,
                         name: table.name.clone(),
                         children: columns,
                     }
@@ -201,25 +392,18 @@ pub fn database_schema_to_tree(schema: &DatabaseSchema) -> Vec<SchemaItem> {
                 .iter()
                 .filter(|v| &v.schema == schema_name)
                 .map(|view| {
+                    let view_id = format!("{}.{}", schema_name, view.name);
+
                     // Get columns for this view
                     let columns: Vec<SchemaItem> = schema
                         .view_columns
                         .get(&(schema_name.clone(), view.name.clone()))
-                        .map(|cols| {
-                            cols.iter()
-                                .map(|col| SchemaItem::Column {
-                                    id: format!("{}.{}.{}", schema_name, view.name, col.name),
-                                    name: col.name.clone(),
-                                    data_type: col.data_type.clone(),
-                                    is_nullable: col.is_nullable,
-                                    is_primary_key: col.is_primary_key,
-                                })
-                                .collect()
-                        })
+                        .map(|cols| column_items(&view_id, cols))
                         .unwrap_or_default();
 
                     SchemaItem::View {
-                        id: format!("{}.{}", schema_name, view.name),
+                        columns_loaded: schema.columns_loaded(schema_name, &view.name),
+                        id: view_id,
                         name: view.name.clone(),
                         is_materialized: view.is_materialized,
                         children: columns,
@@ -269,6 +453,131 @@ pub fn database_schema_to_tree(schema: &DatabaseSchema) -> Vec<SchemaItem> {
         .collect()
 }
 
+/// Column items for a table or view, with IDs under `relation_id` (`schema.name`).
+fn column_items(relation_id: &str, columns: &[ColumnDetail]) -> Vec<SchemaItem> {
+    columns
+        .iter()
+        .map(|col| SchemaItem::Column {
+            id: format!("{}.{}", relation_id, col.name),
+            name: col.name.clone(),
+            data_type: col.data_type.clone(),
+            is_nullable: col.is_nullable,
+            is_primary_key: col.is_primary_key,
+        })
+        .collect()
+}
+
+/// Fill in the columns of an unloaded table or view and of its pinned copy.
+///
+/// `columns` go ahead of the constraint and index folders, as when the tree
+/// is built with columns.
+fn fill_columns(items: &mut [SchemaItem], relation_id: &str, columns: &[SchemaItem]) {
+    for item in items {
+        match item {
+            SchemaItem::Table { id, columns_loaded, children, .. }
+            | SchemaItem::View { id, columns_loaded, children, .. } => {
+                if !*columns_loaded && unpinned_id(id) == relation_id {
+                    let pinned = id.starts_with(PINNED_ID_PREFIX);
+                    let filled =
+                        columns
+                            .iter()
+                            .map(|col| if pinned { pinned_copy(col) } else { col.clone() });
+                    children.splice(0..0, filled);
+                    *columns_loaded = true;
+                }
+            }
+            SchemaItem::Schema { children, .. }
+            | SchemaItem::TablesFolder { children, .. }
+            | SchemaItem::ViewsFolder { children, .. }
+            | SchemaItem::PinnedFolder { children, .. } => {
+                fill_columns(children, relation_id, columns);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Build the schema tree with a "Pinned" section first.
+///
+/// `pinned` holds table IDs (`schema.name`) in display order; tables that no
+/// longer exist are skipped, and the section is omitted when none remain.
+/// Pinned entries are copies whose IDs carry a prefix so they can be
+/// expanded and selected independently of the originals.
+pub fn schema_tree_with_pins(schema: &DatabaseSchema, pinned: &[String]) -> Vec<SchemaItem> {
+    let mut tree = database_schema_to_tree(schema);
+    let pinned_items: Vec<SchemaItem> =
+        pinned.iter().filter_map(|id| find_table(&tree, id)).map(pinned_copy).collect();
+
+    if !pinned_items.is_empty() {
+        tree.insert(
+            0,
+            SchemaItem::PinnedFolder { id: PINNED_FOLDER_ID.to_string(), children: pinned_items },
+        );
+    }
+    tree
+}
+
+/// The ID of the original item for a pinned copy, or `id` unchanged.
+pub fn unpinned_id(id: &str) -> &str {
+    id.strip_prefix(PINNED_ID_PREFIX).unwrap_or(id)
+}
+
+/// Find a table item by ID anywhere in the tree.
+fn find_table<'a>(items: &'a [SchemaItem], id: &str) -> Option<&'a SchemaItem> {
+    items.iter().find_map(|item| match item {
+        SchemaItem::Table { id: table_id, .. } if table_id == id => Some(item),
+        SchemaItem::Schema { children, .. } | SchemaItem::TablesFolder { children, .. } => {
+            find_table(children, id)
+        }
+        _ => None,
+    })
+}
+
+/// Copy a table and everything under it under prefixed IDs.
+fn pinned_copy(item: &SchemaItem) -> SchemaItem {
+    match item {
+        SchemaItem::Table { id, name, columns_loaded, children } => SchemaItem::Table {
+            id: format!("{PINNED_ID_PREFIX}{id}"),
+            name: name.clone(),
+            columns_loaded: *columns_loaded,
+            children: children.iter().map(pinned_copy).collect(),
+        },
+        SchemaItem::Column { id, name, data_type, is_nullable, is_primary_key } => {
+            SchemaItem::Column {
+                id: format!("{PINNED_ID_PREFIX}{id}"),
+                name: name.clone(),
+                data_type: data_type.clone(),
+                is_nullable: *is_nullable,
+                is_primary_key: *is_primary_key,
+            }
+        }
+        SchemaItem::ConstraintsFolder { id, children } => SchemaItem::ConstraintsFolder {
+            id: format!("{PINNED_ID_PREFIX}{id}"),
+            children: children.iter().map(pinned_copy).collect(),
+        },
+        SchemaItem::Constraint { id, name, kind, definition, references } => {
+            SchemaItem::Constraint {
+                id: format!("{PINNED_ID_PREFIX}{id}"),
+                name: name.clone(),
+                kind: *kind,
+                definition: definition.clone(),
+                references: references.clone(),
+            }
+        }
+        SchemaItem::IndexesFolder { id, children } => SchemaItem::IndexesFolder {
+            id: format!("{PINNED_ID_PREFIX}{id}"),
+            children: children.iter().map(pinned_copy).collect(),
+        },
+        SchemaItem::Index { id, name, summary, is_primary } => SchemaItem::Index {
+            id: format!("{PINNED_ID_PREFIX}{id}"),
+            name: name.clone(),
+            summary: summary.clone(),
+            is_primary: *is_primary,
+        },
+        other => other.clone(),
+    }
+}
+
 /// Schema browser panel for navigating database objects.
 pub struct SchemaBrowserPanel {
     /// Focus handle for keyboard navigation.
@@ -281,10 +590,26 @@ pub struct SchemaBrowserPanel {
     filter_input: Entity<TextInput>,
     /// Subscription to filter input events.
     _filter_subscription: Subscription,
+    /// Coalesces filter keystrokes before re-filtering the tree.
+    filter_debouncer: Debouncer<String>,
     /// Whether the panel is currently loading schema data.
     is_loading: bool,
     /// Optional error message if schema loading failed.
     error: Option<SharedString>,
+    /// Size and maintenance statistics keyed by table item ID.
+    table_details: HashMap<String, TableInfo>,
+    /// ID of the selected tree item.
+    selected_id: Option<String>,
+    /// Loaded schema, searched for column and comment matches.
+    database_schema: Option<Arc<DatabaseSchema>>,
+    /// Matches for the current filter text.
+    search_hits: Vec<SearchHit>,
+    /// Running search; replacing it cancels the previous search.
+    _search_task: Option<Task<()>>,
+    /// IDs (`schema.name`) of tables pinned for the current connection.
+    pinned_tables: Vec<String>,
+    /// Value distribution for a column item ID; None while it's being sampled.
+    column_sample: Option<(String, Option<ColumnSample>)>,
 }
 
 impl SchemaBrowserPanel {
@@ -308,8 +633,16 @@ impl SchemaBrowserPanel {
             _tree_subscription: Some(tree_subscription),
             filter_input,
             _filter_subscription: filter_subscription,
+            filter_debouncer: Debouncer::new(FILTER_DEBOUNCE),
             is_loading: false,
             error: None,
+            table_details: HashMap::new(),
+            selected_id: None,
+            database_schema: None,
+            search_hits: Vec::new(),
+            _search_task: None,
+            pinned_tables: Vec::new(),
+            column_sample: None,
         }
     }
 
@@ -322,14 +655,18 @@ impl SchemaBrowserPanel {
     ) {
         match event {
             TextInputEvent::Changed(text) => {
-                if let Some(tree) = &self.tree {
-                    tree.update(cx, |tree, cx| {
-                        tree.set_filter(text.clone(), cx);
-                    });
-                }
+                self.filter_debouncer.call(
+                    text.clone(),
+                    cx,
+                    |this| &mut this.filter_debouncer,
+                    Self::apply_filter,
+                );
             }
             TextInputEvent::Submitted(_) => {
-                // Could focus the tree on submit
+                // Apply a pending filter immediately
+                if let Some(text) = self.filter_debouncer.flush() {
+                    self.apply_filter(text, cx);
+                }
             }
             TextInputEvent::Focus | TextInputEvent::Blur => {
                 // Focus/blur events - no action needed for filter input
@@ -337,6 +674,81 @@ impl SchemaBrowserPanel {
         }
     }
 
+    /// Filter the tree to items matching the text and search columns and comments.
+    fn apply_filter(&mut self, text: String, cx: &mut Context<Self>) {
+        if let Some(tree) = &self.tree {
+            tree.update(cx, |tree, cx| {
+                tree.set_filter(text.clone(), cx);
+            });
+        }
+        self.search(text, cx);
+    }
+
+    /// Search the loaded schema in the background.
+    ///
+    /// Starting a new search drops the previous task, cancelling it.
+    fn search(&mut self, query: String, cx: &mut Context<Self>) {
+        let schema = match &self.database_schema {
+            Some(schema) if query.trim().len() >= MIN_SEARCH_LEN => schema.clone(),
+            _ => {
+                self._search_task = None;
+                self.search_hits.clear();
+                cx.notify();
+                return;
+            }
+        };
+
+        self._search_task = Some(cx.spawn(async move |this, cx| {
+            let hits = cx
+                .background_executor()
+                .spawn(async move { SchemaService::search_objects(&schema, &query, &[]) })
+                .await;
+            let _ = this.update(cx, |panel, cx| {
+                panel.search_hits = hits;
+                cx.notify();
+            });
+        }));
+    }
+
+    /// Reveal a search hit in the tree: clear the filter, expand its parents, and select it.
+    pub fn reveal_hit(&mut self, hit: &SearchHit, cx: &mut Context<Self>) {
+        let folder = match hit.object_kind {
+            SchemaObjectKind::Table => "tables",
+            SchemaObjectKind::View => "views",
+            SchemaObjectKind::Function => "functions",
+        };
+        let mut ancestors = vec![hit.schema.clone(), format!("{}.{}", hit.schema, folder)];
+        if hit.column.is_some() {
+            ancestors.push(format!("{}.{}", hit.schema, hit.object));
+        }
+        self.reveal(ancestors, hit.path(), cx);
+    }
+
+    /// Reveal a table in the tree, such as the one a foreign key references.
+    fn reveal_table(&mut self, schema: &str, table: &str, cx: &mut Context<Self>) {
+        tracing::debug!(schema = %schema, table = %table, "Revealing table");
+        let ancestors = vec![schema.to_string(), format!("{}.tables", schema)];
+        self.reveal(ancestors, format!("{}.{}", schema, table), cx);
+    }
+
+    /// Clear the filter, expand `ancestors`, and select `selected`.
+    fn reveal(&mut self, ancestors: Vec<String>, selected: String, cx: &mut Context<Self>) {
+        self.filter_debouncer.flush();
+        self.filter_input.update(cx, |input, cx| input.clear(cx));
+        self.apply_filter(String::new(), cx);
+
+        if let Some(tree) = &self.tree {
+            tree.update(cx, |tree, cx| {
+                for id in ancestors {
+                    tree.expand(id, cx);
+                }
+                tree.set_selected(Some(selected.clone()), cx);
+            });
+        }
+        self.selected_id = Some(selected);
+        cx.notify();
+    }
+
     /// Handle events from the tree component.
     fn handle_tree_event(
         &mut self,
@@ -345,12 +757,25 @@ impl SchemaBrowserPanel {
         cx: &mut Context<Self>,
     ) {
         match event {
-            TreeEvent::Selected { id: _ } => {
-                // Item selected - could update details panel
+            TreeEvent::Selected { id } => {
+                self.selected_id = Some(id.clone());
+                cx.notify();
             }
-            TreeEvent::Activated { id: _ } => {
-                // Item activated (double-click or Enter)
-                // Future: Open table data, show view definition, etc.
+            TreeEvent::Activated { id } => {
+                // Item activated (double-click or Enter): foreign keys jump to
+                // the table they reference
+                let references =
+                    tree.read(cx).visible_entries().iter().find_map(|entry| match &entry.item {
+                        SchemaItem::Constraint { id: item_id, references, .. } if item_id == id => {
+                            references.clone()
+                        }
+                        _ => None,
+                    });
+                if let Some((schema, table)) = references {
+                    if self.has_table(&schema, &table) {
+                        self.reveal_table(&schema, &table, cx);
+                    }
+                }
             }
             TreeEvent::Expanded { id: _ } => {
                 // Item expanded
@@ -358,6 +783,18 @@ impl SchemaBrowserPanel {
             TreeEvent::Collapsed { id: _ } => {
                 // Item collapsed
             }
+            TreeEvent::LoadChildren { id } => {
+                // A table or view loaded without columns was expanded
+                let target = tree.read(cx).find(id).and_then(DefinitionTarget::for_item);
+                if let Some(
+                    DefinitionTarget::Table { schema, name }
+                    | DefinitionTarget::View { schema, name },
+                ) = target
+                {
+                    tracing::debug!(schema = %schema, name = %name, "Column load requested");
+                    cx.emit(SchemaBrowserEvent::LoadColumns { schema, name });
+                }
+            }
             TreeEvent::ContextMenu { id, position } => {
                 // Find the item by ID and show appropriate context menu
                 self.show_context_menu(tree.clone(), id.clone(), *position, cx);
@@ -387,7 +824,7 @@ impl SchemaBrowserPanel {
         };
 
         // Create menu items based on the item type
-        let menu_items = self.create_menu_items_for_item(&item, &id);
+        let menu_items = self.create_menu_items_for_item(&item, cx.entity().downgrade());
 
         if menu_items.is_empty() {
             return;
@@ -402,83 +839,156 @@ impl SchemaBrowserPanel {
     }
 
     /// Create context menu items based on the schema item type.
-    fn create_menu_items_for_item(&self, item: &SchemaItem, id: &str) -> Vec<ContextMenuItem> {
+    fn create_menu_items_for_item(
+        &self,
+        item: &SchemaItem,
+        browser: WeakEntity<Self>,
+    ) -> Vec<ContextMenuItem> {
+        // Definition actions emit an event the workspace turns into a read-only tab
+        let definition_action = |label: &'static str| {
+            let browser = browser.clone();
+            let target = DefinitionTarget::for_item(item);
+            let disabled = target.is_none();
+            ContextMenuItem::action(label, move |cx| {
+                let Some(target) = target.clone() else {
+                    return;
+                };
+                tracing::info!(target = ?target, "Definition requested");
+                let _ = browser.update(cx, |_, cx| {
+                    cx.emit(SchemaBrowserEvent::ShowDefinition(target));
+                });
+            })
+            .icon(IconName::File)
+            .disabled(disabled)
+        };
+
+        let refresh_action = |label: &'static str, concurrently: bool| {
+            let browser = browser.clone();
+            let target = DefinitionTarget::for_item(item);
+            ContextMenuItem::action(label, move |cx| {
+                let Some(DefinitionTarget::View { schema, name }) = target.clone() else {
+                    return;
+                };
+                let _ = browser.update(cx, |_, cx| {
+                    cx.emit(SchemaBrowserEvent::RefreshMaterializedView {
+                        schema,
+                        name,
+                        concurrently,
+                    });
+                });
+            })
+            .icon(IconName::Refresh)
+        };
+
+        // Query actions emit SQL from the query builder for the workspace to run in a new tab
+        let query_action = |label: &'static str, build: fn(&str, &str) -> String| {
+            let browser = browser.clone();
+            let target = DefinitionTarget::for_item(item);
+            ContextMenuItem::action(label, move |cx| {
+                let Some(
+                    DefinitionTarget::Table { schema, name }
+                    | DefinitionTarget::View { schema, name },
+                ) = target.clone()
+                else {
+                    return;
+                };
+                tracing::info!(schema = %schema, name = %name, action = label, "Query requested");
+                let sql = build(&schema, &name);
+                let _ = browser.update(cx, |_, cx| {
+                    cx.emit(SchemaBrowserEvent::RunQuery { title: name, sql });
+                });
+            })
+            .icon(IconName::Play)
+        };
+        // Destructive table actions are confirmed and run by the workspace
+        let table_operation = |label: &'static str, operation: TableOperation| {
+            let browser = browser.clone();
+            let target = DefinitionTarget::for_item(item);
+            ContextMenuItem::action(label, move |cx| {
+                let Some(DefinitionTarget::Table { schema, name }) = target.clone() else {
+                    return;
+                };
+                tracing::info!(schema = %schema, name = %name, ?operation, "Table operation requested");
+                let _ = browser.update(cx, |_, cx| {
+                    cx.emit(SchemaBrowserEvent::TableOperation { operation, schema, name });
+                });
+            })
+            .icon(IconName::Trash)
+        };
+        let select_top = || {
+            query_action("Select Top 100", |schema, name| {
+                query_builder::select_all(schema, name, Some(100))
+            })
+            .shortcut("Cmd+Return")
+        };
+
         match item {
-            SchemaItem::Table { name, .. } => {
-                let table_name = name.clone();
-                let table_id = id.to_string();
+            SchemaItem::Table { id, name, .. } => {
                 let copy_name = name.clone();
+                let table_id = unpinned_id(id).to_string();
+                let (pin_label, pin_icon) = if self.pinned_tables.contains(&table_id) {
+                    ("Unpin", IconName::Unpin)
+                } else {
+                    ("Pin to Top", IconName::Pin)
+                };
+                let pin_browser = browser.clone();
 
                 vec![
-                    ContextMenuItem::action("Select Top 100", move |_cx| {
-                        // Future: Execute SELECT * FROM table LIMIT 100
-                        tracing::info!(table = %table_name, "Select Top 100 requested");
-                    })
-                    .icon(IconName::Play)
-                    .shortcut("Cmd+Return"),
+                    select_top(),
+                    query_action("Count Rows", query_builder::count),
                     ContextMenuItem::separator(),
-                    ContextMenuItem::action("View DDL", move |_cx| {
-                        // Future: Show CREATE TABLE statement
-                        tracing::info!(table = %table_id, "View DDL requested");
-                    })
-                    .icon(IconName::File),
+                    definition_action("View DDL"),
                     ContextMenuItem::separator(),
+                    ContextMenuItem::action(pin_label, move |cx| {
+                        let _ = pin_browser.update(cx, |panel, cx| {
+                            panel.toggle_pin(table_id.clone(), cx);
+                        });
+                    })
+                    .icon(pin_icon),
                     ContextMenuItem::action("Copy Name", move |cx| {
                         cx.write_to_clipboard(ClipboardItem::new_string(copy_name.clone()));
                         tracing::info!(name = %copy_name, "Copied table name to clipboard");
                     })
                     .icon(IconName::Copy)
                     .shortcut("Cmd+C"),
+                    ContextMenuItem::separator(),
+                    table_operation("Truncate...", TableOperation::Truncate),
+                    table_operation("Drop Table...", TableOperation::Drop),
                 ]
             }
             SchemaItem::View { name, is_materialized, .. } => {
-                let view_name = name.clone();
-                let view_id = id.to_string();
                 let copy_name = name.clone();
-                let is_mat = *is_materialized;
 
-                vec![
-                    ContextMenuItem::action("Select Top 100", move |_cx| {
-                        tracing::info!(view = %view_name, "Select Top 100 requested");
-                    })
-                    .icon(IconName::Play)
-                    .shortcut("Cmd+Return"),
-                    ContextMenuItem::separator(),
-                    ContextMenuItem::action("View DDL", move |_cx| {
-                        tracing::info!(view = %view_id, "View DDL requested");
-                    })
-                    .icon(IconName::File),
-                    ContextMenuItem::action(
-                        if is_mat { "Refresh Materialized View" } else { "View Definition" },
-                        move |_cx| {
-                            if is_mat {
-                                tracing::info!("Refresh materialized view requested");
-                            } else {
-                                tracing::info!("View definition requested");
-                            }
-                        },
-                    )
-                    .icon(IconName::Refresh),
+                let mut items = vec![
+                    select_top(),
+                    query_action("Count Rows", query_builder::count),
                     ContextMenuItem::separator(),
+                    definition_action("View DDL"),
+                ];
+                if *is_materialized {
+                    items.push(refresh_action("Refresh Materialized View", false));
+                    items.push(refresh_action("Refresh Concurrently", true));
+                } else {
+                    items.push(definition_action("View Definition"));
+                }
+                items.push(ContextMenuItem::separator());
+                items.push(
                     ContextMenuItem::action("Copy Name", move |cx| {
                         cx.write_to_clipboard(ClipboardItem::new_string(copy_name.clone()));
                         tracing::info!(name = %copy_name, "Copied view name to clipboard");
                     })
                     .icon(IconName::Copy)
                     .shortcut("Cmd+C"),
-                ]
+                );
+                items
             }
             SchemaItem::Function { name, arguments, return_type, .. } => {
                 let func_name = name.clone();
-                let func_id = id.to_string();
                 let func_sig = format!("{}({})", name, arguments);
                 let _return_type = return_type.clone();
 
                 vec![
-                    ContextMenuItem::action("View DDL", move |_cx| {
-                        tracing::info!(function = %func_id, "View DDL requested");
-                    })
-                    .icon(IconName::File),
+                    definition_action("View DDL"),
                     ContextMenuItem::separator(),
                     ContextMenuItem::action("Copy Name", move |cx| {
                         cx.write_to_clipboard(ClipboardItem::new_string(func_name.clone()));
@@ -493,11 +1003,35 @@ impl SchemaBrowserPanel {
                     .icon(IconName::Copy),
                 ]
             }
-            SchemaItem::Column { name, data_type, .. } => {
+            SchemaItem::Column { id, name, data_type, .. } => {
                 let col_name = name.clone();
                 let col_type = data_type.clone();
+                let column_id = unpinned_id(id).to_string();
+                let target = self.column_target(&column_id);
+                let disabled = target.is_none();
+                let sample_browser = browser.clone();
 
                 vec![
+                    ContextMenuItem::action("Sample Values", move |cx| {
+                        let Some((schema, table, column)) = target.clone() else {
+                            return;
+                        };
+                        tracing::info!(schema = %schema, table = %table, column = %column, "Column sample requested");
+                        let column_id = column_id.clone();
+                        let _ = sample_browser.update(cx, |panel, cx| {
+                            panel.column_sample = Some((column_id.clone(), None));
+                            cx.emit(SchemaBrowserEvent::SampleColumn {
+                                column_id,
+                                schema,
+                                table,
+                                column,
+                            });
+                            cx.notify();
+                        });
+                    })
+                    .icon(IconName::Search)
+                    .disabled(disabled),
+                    ContextMenuItem::separator(),
                     ContextMenuItem::action("Copy Name", move |cx| {
                         cx.write_to_clipboard(ClipboardItem::new_string(col_name.clone()));
                         tracing::info!(name = %col_name, "Copied column name to clipboard");
@@ -521,15 +1055,156 @@ impl SchemaBrowserPanel {
                 .icon(IconName::Copy)
                 .shortcut("Cmd+C")]
             }
+            SchemaItem::Constraint { name, definition, references, .. } => {
+                let constraint_name = name.clone();
+                let constraint_def = definition.clone();
+                let mut items = Vec::new();
+                if let Some((schema, table)) = references {
+                    let disabled = !self.has_table(schema, table);
+                    let (schema, table) = (schema.clone(), table.clone());
+                    let reveal_browser = browser.clone();
+                    items.push(
+                        ContextMenuItem::action("Go to Referenced Table", move |cx| {
+                            let _ = reveal_browser.update(cx, |panel, cx| {
+                                panel.reveal_table(&schema, &table, cx);
+                            });
+                        })
+                        .icon(IconName::Table)
+                        .disabled(disabled),
+                    );
+                    items.push(ContextMenuItem::separator());
+                }
+                items.push(
+                    ContextMenuItem::action("Copy Name", move |cx| {
+                        cx.write_to_clipboard(ClipboardItem::new_string(constraint_name.clone()));
+                        tracing::info!(
+                            name = %constraint_name,
+                            "Copied constraint name to clipboard"
+                        );
+                    })
+                    .icon(IconName::Copy)
+                    .shortcut("Cmd+C"),
+                );
+                items.push(
+                    ContextMenuItem::action("Copy Definition", move |cx| {
+                        cx.write_to_clipboard(ClipboardItem::new_string(constraint_def.clone()));
+                        tracing::info!("Copied constraint definition to clipboard");
+                    })
+                    .icon(IconName::Copy),
+                );
+                items
+            }
+            SchemaItem::Index { name, .. } => {
+                let index_name = name.clone();
+
+                vec![ContextMenuItem::action("Copy Name", move |cx| {
+                    cx.write_to_clipboard(ClipboardItem::new_string(index_name.clone()));
+                    tracing::info!(name = %index_name, "Copied index name to clipboard");
+                })
+                .icon(IconName::Copy)
+                .shortcut("Cmd+C")]
+            }
             // Folder items don't have context menu actions
             SchemaItem::TablesFolder { .. }
             | SchemaItem::ViewsFolder { .. }
-            | SchemaItem::FunctionsFolder { .. } => {
+            | SchemaItem::FunctionsFolder { .. }
+            | SchemaItem::PinnedFolder { .. }
+            | SchemaItem::ConstraintsFolder { .. }
+            | SchemaItem::IndexesFolder { .. } => {
                 vec![]
             }
         }
     }
 
+    /// The loaded schema, if any.
+    pub fn database_schema(&self) -> Option<Arc<DatabaseSchema>> {
+        self.database_schema.clone()
+    }
+
+    /// Check if a table is in the loaded schema.
+    fn has_table(&self, schema: &str, table: &str) -> bool {
+        self.database_schema
+            .as_ref()
+            .is_some_and(|s| s.tables.iter().any(|t| t.schema == schema && t.name == table))
+    }
+
+    /// Resolve a column item ID to its schema, table, and column names.
+    ///
+    /// Looked up in the loaded schema, since names may themselves contain dots.
+    fn column_target(&self, column_id: &str) -> Option<(String, String, String)> {
+        let schema = self.database_schema.as_ref()?;
+        schema.table_columns.iter().chain(&schema.view_columns).find_map(
+            |((schema, table), columns)| {
+                columns
+                    .iter()
+                    .find(|column| format!("{}.{}.{}", schema, table, column.name) == column_id)
+                    .map(|column| (schema.clone(), table.clone(), column.name.clone()))
+            },
+        )
+    }
+
+    /// Fill in the columns of a table or view loaded on demand.
+    ///
+    /// Ignored if the object isn't in the loaded schema or already has columns,
+    /// e.g. when the schema was reloaded while they were loading.
+    pub fn set_columns(
+        &mut self,
+        schema: &str,
+        name: &str,
+        columns: Vec<ColumnDetail>,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(database_schema) = &mut self.database_schema else {
+            return;
+        };
+        let exists = database_schema.tables.iter().any(|t| t.schema == schema && t.name == name)
+            || database_schema.views.iter().any(|v| v.schema == schema && v.name == name);
+        if !exists || database_schema.columns_loaded(schema, name) {
+            return;
+        }
+
+        let relation_id = format!("{}.{}", schema, name);
+        let items = column_items(&relation_id, &columns);
+        Arc::make_mut(database_schema).set_columns(schema, name, columns);
+        if let Some(tree) = &self.tree {
+            tree.update(cx, |tree, cx| {
+                let mut tree_items = tree.items().to_vec();
+                fill_columns(&mut tree_items, &relation_id, &items);
+                tree.set_items(tree_items, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    /// Collapse a table or view whose columns failed to load, so expanding
+    /// it again retries.
+    pub fn cancel_column_load(&mut self, schema: &str, name: &str, cx: &mut Context<Self>) {
+        let relation_id = format!("{}.{}", schema, name);
+        if let Some(tree) = &self.tree {
+            tree.update(cx, |tree, cx| {
+                tree.collapse(format!("{PINNED_ID_PREFIX}{relation_id}"), cx);
+                tree.collapse(relation_id, cx);
+            });
+        }
+    }
+
+    /// Show the sampled value distribution of a column.
+    pub fn set_column_sample(
+        &mut self,
+        column_id: String,
+        sample: ColumnSample,
+        cx: &mut Context<Self>,
+    ) {
+        self.column_sample = Some((column_id, Some(sample)));
+        cx.notify();
+    }
+
+    /// Hide the column sample, e.g. after sampling failed.
+    pub fn clear_column_sample(&mut self, cx: &mut Context<Self>) {
+        self.column_sample = None;
+        cx.notify();
+    }
+
     /// Set the loading state.
     pub fn set_loading(&mut self, loading: bool, cx: &mut Context<Self>) {
         self.is_loading = loading;
@@ -542,6 +1217,58 @@ impl SchemaBrowserPanel {
         cx.notify();
     }
 
+    /// Set table statistics shown in the details section for the selected table.
+    pub fn set_table_details(&mut self, tables: &[TableInfo], cx: &mut Context<Self>) {
+        self.table_details = tables
+            .iter()
+            .map(|table| (format!("{}.{}", table.schema, table.name), table.clone()))
+            .collect();
+        cx.notify();
+    }
+
+    /// Set the loaded schema: builds the tree, table statistics, and search index.
+    pub fn set_database_schema(&mut self, schema: DatabaseSchema, cx: &mut Context<Self>) {
+        self.set_schema(schema_tree_with_pins(&schema, &self.pinned_tables), cx);
+        self.set_table_details(&schema.tables, cx);
+        self.database_schema = Some(Arc::new(schema));
+        self.search_hits.clear();
+        self.column_sample = None;
+    }
+
+    /// Tables pinned to the top of the tree, as `schema.name` IDs.
+    pub fn pinned_tables(&self) -> &[String] {
+        &self.pinned_tables
+    }
+
+    /// Set the pinned tables for the current connection, rebuilding the tree if loaded.
+    pub fn set_pinned_tables(&mut self, pinned: Vec<String>, cx: &mut Context<Self>) {
+        if self.pinned_tables == pinned {
+            return;
+        }
+        self.pinned_tables = pinned;
+        self.rebuild_tree(cx);
+    }
+
+    /// Pin a table to the top of the tree, or unpin it if already pinned.
+    fn toggle_pin(&mut self, table_id: String, cx: &mut Context<Self>) {
+        if let Some(index) = self.pinned_tables.iter().position(|id| *id == table_id) {
+            self.pinned_tables.remove(index);
+            tracing::info!(table = %table_id, "Table unpinned");
+        } else {
+            self.pinned_tables.push(table_id.clone());
+            tracing::info!(table = %table_id, "Table pinned");
+        }
+        self.rebuild_tree(cx);
+        cx.emit(SchemaBrowserEvent::PinnedTablesChanged(self.pinned_tables.clone()));
+    }
+
+    /// Rebuild the tree from the loaded schema, e.g. after the pins change.
+    fn rebuild_tree(&mut self, cx: &mut Context<Self>) {
+        if let Some(schema) = self.database_schema.clone() {
+            self.set_schema(schema_tree_with_pins(&schema, &self.pinned_tables), cx);
+        }
+    }
+
     /// Set the schema items to display.
     pub fn set_schema(&mut self, items: Vec<SchemaItem>, cx: &mut Context<Self>) {
         if let Some(tree) = &self.tree {
@@ -629,6 +1356,23 @@ impl SchemaBrowserPanel {
             })
     }
 
+    /// Render the object counts for the loaded schema.
+    fn render_summary(&self, theme: &TuskTheme) -> Option<impl IntoElement> {
+        let counts = self.database_schema.as_ref()?.counts();
+
+        Some(
+            div()
+                .w_full()
+                .px(px(12.0))
+                .py(spacing::XS)
+                .border_b_1()
+                .border_color(theme.colors.border)
+                .text_size(px(11.0))
+                .text_color(theme.colors.text_muted)
+                .child(counts.to_string()),
+        )
+    }
+
     /// Render the filter input.
     fn render_filter_input(&self, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.global::<TuskTheme>();
@@ -646,6 +1390,74 @@ impl SchemaBrowserPanel {
             .child(div().flex_1().child(self.filter_input.clone()))
     }
 
+    /// Render matches for the filter text; clicking one reveals it in the tree.
+    fn render_search_hits(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
+        let total = self.search_hits.len();
+
+        div()
+            .id("schema-search-hits")
+            .max_h(px(180.0))
+            .overflow_y_scroll()
+            .flex()
+            .flex_col()
+            .py(spacing::XS)
+            .border_b_1()
+            .border_color(theme.colors.border)
+            .child(
+                div()
+                    .px(spacing::SM)
+                    .text_size(px(11.0))
+                    .text_color(theme.colors.text_muted)
+                    .child(if total > MAX_SEARCH_HITS {
+                        format!("{} matches (showing {})", total, MAX_SEARCH_HITS)
+                    } else {
+                        format!("{} match{}", total, if total == 1 { "" } else { "es" })
+                    }),
+            )
+            .children(self.search_hits.iter().take(MAX_SEARCH_HITS).enumerate().map(|(ix, hit)| {
+                let icon = match (hit.match_kind, hit.object_kind) {
+                    (SearchMatchKind::Column, _) => IconName::Column,
+                    (_, SchemaObjectKind::Table) => IconName::Table,
+                    (_, SchemaObjectKind::View) => IconName::View,
+                    (_, SchemaObjectKind::Function) => IconName::Function,
+                };
+                let detail = match hit.match_kind {
+                    SearchMatchKind::Comment => format!("comment: {}", hit.matched_text),
+                    SearchMatchKind::Column => "column".to_string(),
+                    SearchMatchKind::Name => "name".to_string(),
+                };
+                let target = hit.clone();
+
+                div()
+                    .id(("schema-search-hit", ix))
+                    .flex()
+                    .items_center()
+                    .gap(spacing::XS)
+                    .px(spacing::SM)
+                    .py(px(2.0))
+                    .cursor_pointer()
+                    .hover(|s| s.bg(theme.colors.element_hover))
+                    .on_click(cx.listener(move |this, _, _, cx| this.reveal_hit(&target, cx)))
+                    .child(Icon::new(icon).size(IconSize::Small).color(theme.colors.text_muted))
+                    .child(
+                        div()
+                            .flex_1()
+                            .overflow_hidden()
+                            .text_size(px(12.0))
+                            .text_color(theme.colors.text)
+                            .child(hit.path()),
+                    )
+                    .child(
+                        div()
+                            .max_w(px(120.0))
+                            .overflow_hidden()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.text_muted)
+                            .child(detail),
+                    )
+            }))
+    }
+
     /// Render the empty state when not connected.
     fn render_empty_state(&self, theme: &TuskTheme) -> impl IntoElement {
         div()
@@ -684,7 +1496,7 @@ impl SchemaBrowserPanel {
     }
 
     /// Render the loading state.
-    fn render_loading_state(&self, theme: &TuskTheme) -> impl IntoElement {
+    fn render_loading_state(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .flex_col()
@@ -699,6 +1511,107 @@ impl SchemaBrowserPanel {
                     .text_size(px(13.0))
                     .child("Loading schema..."),
             )
+            .child(
+                div()
+                    .id("cancel-schema-load")
+                    .text_size(px(12.0))
+                    .text_color(theme.colors.accent)
+                    .cursor_pointer()
+                    .hover(|s| s.underline())
+                    .on_click(cx.listener(|_, _, _, cx| cx.emit(SchemaBrowserEvent::CancelLoad)))
+                    .child("Cancel"),
+            )
+    }
+
+    /// Render size and vacuum statistics for the selected table.
+    fn render_table_details(&self, theme: &TuskTheme) -> Option<impl IntoElement> {
+        let table = self.table_details.get(unpinned_id(self.selected_id.as_ref()?))?;
+        let last_vacuum = table.last_vacuumed().map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string());
+        let last_analyze =
+            table.last_analyzed().map(|t| t.format("%Y-%m-%d %H:%M UTC").to_string());
+        let rows = [
+            ("Rows (est.)", table.estimated_rows.max(0).to_string()),
+            ("Size", format_bytes(table.size_bytes)),
+            ("Dead tuples", table.dead_tuples.to_string()),
+            ("Last vacuum", last_vacuum.unwrap_or_else(|| "Never".to_string())),
+            ("Last analyze", last_analyze.unwrap_or_else(|| "Never".to_string())),
+        ];
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap(spacing::XS)
+                .p(spacing::SM)
+                .border_t_1()
+                .border_color(theme.colors.border)
+                .child(
+                    div()
+                        .text_size(px(12.0))
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(theme.colors.text)
+                        .child(format!("{}.{}", table.schema, table.name)),
+                )
+                .children(rows.into_iter().map(|(label, value)| {
+                    div()
+                        .flex()
+                        .justify_between()
+                        .text_size(px(11.0))
+                        .child(div().text_color(theme.colors.text_muted).child(label))
+                        .child(div().text_color(theme.colors.text).child(value))
+                })),
+        )
+    }
+
+    /// Render the sampled value distribution when its column is selected.
+    fn render_column_sample(&self, theme: &TuskTheme) -> Option<impl IntoElement> {
+        let (column_id, sample) = self.column_sample.as_ref()?;
+        if unpinned_id(self.selected_id.as_ref()?) != column_id {
+            return None;
+        }
+
+        let row = |label: String, value: String| {
+            div()
+                .flex()
+                .justify_between()
+                .gap(spacing::SM)
+                .text_size(px(11.0))
+                .child(div().text_color(theme.colors.text_muted).overflow_hidden().child(label))
+                .child(div().flex_none().text_color(theme.colors.text).child(value))
+        };
+        let rows: Vec<_> = match sample {
+            None => vec![row("Sampling values...".to_string(), String::new())],
+            Some(sample) => {
+                let share =
+                    |count: i64| format!("{} ({:.1}%)", count, sample.fraction(count) * 100.0);
+                let mut rows: Vec<_> = sample
+                    .top_values
+                    .iter()
+                    .map(|value| row(truncate_value(&value.value), share(value.count)))
+                    .collect();
+                rows.push(row("NULL".to_string(), share(sample.null_rows)));
+                rows.push(row("Rows".to_string(), sample.total_rows.to_string()));
+                rows
+            }
+        };
+
+        Some(
+            div()
+                .flex()
+                .flex_col()
+                .gap(spacing::XS)
+                .p(spacing::SM)
+                .border_t_1()
+                .border_color(theme.colors.border)
+                .child(
+                    div()
+                        .text_size(px(12.0))
+                        .font_weight(FontWeight::SEMIBOLD)
+                        .text_color(theme.colors.text)
+                        .child(format!("Values of {}", column_id)),
+                )
+                .children(rows),
+        )
     }
 
     /// Render an error state.
@@ -762,7 +1675,7 @@ impl Render for SchemaBrowserPanel {
         let theme = cx.global::<TuskTheme>().clone();
 
         let content = if self.is_loading {
-            self.render_loading_state(&theme).into_any_element()
+            self.render_loading_state(&theme, cx).into_any_element()
         } else if let Some(error) = &self.error {
             self.render_error_state(error, &theme).into_any_element()
         } else if let Some(tree) = &self.tree {
@@ -786,24 +1699,62 @@ impl Render for SchemaBrowserPanel {
             .bg(theme.colors.panel_background)
             // Panel header with refresh button (T056)
             .child(self.render_header(&theme, cx))
+            // Object counts for the loaded schema
+            .when(!self.is_loading && self.error.is_none(), |d| {
+                d.children(self.render_summary(&theme))
+            })
             // Filter input (only show when there's data)
             .when(has_data, |d| d.child(self.render_filter_input(cx)))
+            // Column and comment matches for the filter text
+            .when(has_data && !self.search_hits.is_empty(), |d| {
+                d.child(self.render_search_hits(&theme, cx))
+            })
             .child(
                 // Panel content
                 div().flex_1().overflow_hidden().child(content),
             )
+            // Statistics for the selected table
+            .children(self.render_table_details(&theme))
+            // Value distribution for the selected column
+            .children(self.render_column_sample(&theme))
+    }
+}
+
+/// Shorten a sampled value to fit the details section.
+fn truncate_value(value: &str) -> String {
+    match value.char_indices().nth(MAX_SAMPLE_VALUE_CHARS) {
+        Some((end, _)) => format!("{}…", &value[..end]),
+        None => value.to_string(),
+    }
+}
+
+/// Format a byte count with binary units (e.g. `8.0 KB`).
+pub(crate) fn format_bytes(bytes: i64) -> String {
+    const UNITS: [&str; 5] = ["B", "KB", "MB", "GB", "TB"];
+    let mut value = bytes.max(0) as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes.max(0))
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use tusk_core::models::schema::fixtures::table;
 
     #[test]
     fn test_schema_item_id() {
         let item = SchemaItem::Table {
             id: "test-table".to_string(),
             name: "users".to_string(),
+            columns_loaded: true,
             children: vec![],
         };
         assert_eq!(item.id(), "test-table");
@@ -814,6 +1765,7 @@ mod tests {
         let item = SchemaItem::Table {
             id: "test-table".to_string(),
             name: "users".to_string(),
+            columns_loaded: true,
             children: vec![],
         };
         assert_eq!(item.label().as_ref(), "users");
@@ -821,14 +1773,19 @@ mod tests {
 
     #[test]
     fn test_schema_item_icon() {
-        let table =
-            SchemaItem::Table { id: "t".to_string(), name: "users".to_string(), children: vec![] };
+        let table = SchemaItem::Table {
+            id: "t".to_string(),
+            name: "users".to_string(),
+            columns_loaded: true,
+            children: vec![],
+        };
         assert_eq!(table.icon(), Some(IconName::Table));
 
         let view = SchemaItem::View {
             id: "v".to_string(),
             name: "active_users".to_string(),
             is_materialized: false,
+            columns_loaded: true,
             children: vec![],
         };
         assert_eq!(view.icon(), Some(IconName::View));
@@ -836,8 +1793,12 @@ mod tests {
 
     #[test]
     fn test_schema_item_expandable() {
-        let table =
-            SchemaItem::Table { id: "t".to_string(), name: "users".to_string(), children: vec![] };
+        let table = SchemaItem::Table {
+            id: "t".to_string(),
+            name: "users".to_string(),
+            columns_loaded: true,
+            children: vec![],
+        };
         assert!(table.is_expandable()); // Tables can have children (columns)
 
         let column = SchemaItem::Column {
@@ -889,4 +1850,281 @@ mod tests {
         };
         assert_eq!(func_with_args.label().as_ref(), "get_user(id bigint) -> users");
     }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(8192), "8.0 KB");
+        assert_eq!(format_bytes(5 * 1024 * 1024 + 512 * 1024), "5.5 MB");
+    }
+
+    #[test]
+    fn test_truncate_value() {
+        assert_eq!(truncate_value("open"), "open");
+        let long = "é".repeat(MAX_SAMPLE_VALUE_CHARS + 5);
+        let truncated = truncate_value(&long);
+        assert_eq!(truncated.chars().count(), MAX_SAMPLE_VALUE_CHARS + 1);
+        assert!(truncated.ends_with('…'));
+    }
+
+    #[test]
+    fn test_definition_target_uses_schema_from_id() {
+        let table = SchemaItem::Table {
+            id: "sales.v2.orders".to_string(),
+            name: "orders".to_string(),
+            columns_loaded: true,
+            children: vec![],
+        };
+        assert_eq!(
+            DefinitionTarget::for_item(&table),
+            Some(DefinitionTarget::Table {
+                schema: "sales.v2".to_string(),
+                name: "orders".to_string()
+            })
+        );
+
+        let func = SchemaItem::Function {
+            id: "public.add(integer, integer)".to_string(),
+            name: "add".to_string(),
+            arguments: "integer, integer".to_string(),
+            return_type: "integer".to_string(),
+        };
+        let target = DefinitionTarget::for_item(&func).expect("function target");
+        assert_eq!(target.title(), "add(integer, integer) (DDL)");
+        assert!(
+            matches!(target, DefinitionTarget::Function { ref schema, .. } if schema == "public")
+        );
+
+        let schema = SchemaItem::Schema {
+            id: "public".to_string(),
+            name: "public".to_string(),
+            children: vec![],
+        };
+        assert_eq!(DefinitionTarget::for_item(&schema), None);
+    }
+
+    #[test]
+    fn test_tables_list_indexes_after_columns() {
+        use tusk_core::models::schema::{ColumnDetail, IndexInfo, SchemaInfo};
+
+        let index =
+            |name: &str, columns: &[&str], is_primary: bool, predicate: Option<&str>| IndexInfo {
+                schema: "public".to_string(),
+                table: "users".to_string(),
+                name: name.to_string(),
+                columns: columns.iter().map(|c| c.to_string()).collect(),
+                is_unique: true,
+                is_primary,
+                method: "btree".to_string(),
+                predicate: predicate.map(String::from),
+            };
+        let mut schema = DatabaseSchema {
+            schemas: vec![SchemaInfo { name: "public".to_string(), owner: "postgres".to_string() }],
+            tables: ["users", "events"].map(|name| table("public", name)).to_vec(),
+            indexes: vec![
+                index("users_email_key", &["lower(email)"], false, Some("active")),
+                index("users_pkey", &["id"], true, None),
+            ],
+            ..Default::default()
+        };
+        schema.table_columns.insert(
+            ("public".to_string(), "users".to_string()),
+            vec![ColumnDetail {
+                name: "id".to_string(),
+                data_type: "integer".to_string(),
+                is_nullable: false,
+                is_primary_key: true,
+                default_value: None,
+                ordinal_position: 1,
+                comment: None,
+            }],
+        );
+
+        let tree = database_schema_to_tree(&schema);
+        let tables = tree[0].children().unwrap()[0].children().unwrap();
+        let users = tables.iter().find(|t| t.id() == "public.users").unwrap();
+        let children = users.children().unwrap();
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].id(), "public.users.id");
+        let folder = &children[1];
+        assert_eq!(folder.id(), "public.users/#indexes");
+        assert_eq!(folder.label().as_ref(), "Indexes (2)");
+
+        let indexes = folder.children().unwrap();
+        assert_eq!(
+            indexes[0].label().as_ref(),
+            "users_email_key (lower(email)) UNIQUE WHERE active"
+        );
+        assert_eq!(indexes[0].icon(), Some(IconName::Index));
+        assert_eq!(indexes[1].label().as_ref(), "users_pkey (id) PRIMARY KEY");
+        assert_eq!(indexes[1].icon(), Some(IconName::Key));
+
+        // Tables without indexes get no empty folder
+        let events = tables.iter().find(|t| t.id() == "public.events").unwrap();
+        assert!(events.children().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_foreign_keys_carry_referenced_table() {
+        use tusk_core::models::schema::{ConstraintInfo, SchemaInfo};
+
+        let constraint = |name: &str, kind: ConstraintKind, definition: &str| ConstraintInfo {
+            schema: "sales".to_string(),
+            table: "orders".to_string(),
+            name: name.to_string(),
+            kind,
+            columns: vec![],
+            definition: definition.to_string(),
+            referenced_schema: None,
+            referenced_table: None,
+            referenced_columns: vec![],
+            is_deferrable: false,
+            is_deferred: false,
+            is_validated: true,
+        };
+        let mut fk = constraint(
+            "orders_customer_fk",
+            ConstraintKind::ForeignKey,
+            "FOREIGN KEY (customer_id) REFERENCES customers(id)",
+        );
+        fk.referenced_schema = Some("public".to_string());
+        fk.referenced_table = Some("customers".to_string());
+        let schema = DatabaseSchema {
+            schemas: vec![SchemaInfo { name: "sales".to_string(), owner: "postgres".to_string() }],
+            tables: vec![table("sales", "orders")],
+            constraints: vec![
+                fk,
+                constraint("orders_pkey", ConstraintKind::PrimaryKey, "PRIMARY KEY (id)"),
+                constraint("orders_qty_check", ConstraintKind::Check, "CHECK (qty > 0) NOT VALID"),
+            ],
+            ..Default::default()
+        };
+
+        let tree = database_schema_to_tree(&schema);
+        let orders = &tree[0].children().unwrap()[0].children().unwrap()[0];
+        let folder = &orders.children().unwrap()[0];
+        assert_eq!(folder.id(), "sales.orders/#constraints");
+        assert_eq!(folder.label().as_ref(), "Constraints (3)");
+
+        let constraints = folder.children().unwrap();
+        assert_eq!(constraints[0].id(), "sales.orders/#constraints/orders_customer_fk");
+        assert_eq!(constraints[0].icon(), Some(IconName::ForeignKey));
+        assert!(matches!(
+            &constraints[0],
+            SchemaItem::Constraint { references: Some((schema, table)), .. }
+                if schema == "public" && table == "customers"
+        ));
+        assert_eq!(constraints[1].icon(), Some(IconName::Key));
+        assert_eq!(constraints[2].label().as_ref(), "orders_qty_check CHECK (qty > 0) NOT VALID");
+        assert_eq!(constraints[2].icon(), Some(IconName::Constraint));
+        assert!(matches!(&constraints[2], SchemaItem::Constraint { references: None, .. }));
+    }
+
+    #[test]
+    fn test_schema_tree_with_pins_lists_pinned_first() {
+        use tusk_core::models::schema::{ColumnDetail, SchemaInfo};
+
+        let mut schema = DatabaseSchema {
+            schemas: ["public", "sales"]
+                .map(|name| SchemaInfo { name: name.to_string(), owner: "postgres".to_string() })
+                .to_vec(),
+            tables: vec![table("public", "users"), table("sales", "orders")],
+            ..Default::default()
+        };
+        schema.table_columns.insert(
+            ("sales".to_string(), "orders".to_string()),
+            vec![ColumnDetail {
+                name: "id".to_string(),
+                data_type: "bigint".to_string(),
+                is_nullable: false,
+                is_primary_key: true,
+                default_value: None,
+                ordinal_position: 1,
+                comment: None,
+            }],
+        );
+
+        // Without pins the tree is unchanged
+        let plain = schema_tree_with_pins(&schema, &[]);
+        assert_eq!(plain.iter().map(|item| item.id()).collect::<Vec<_>>(), ["public", "sales"]);
+
+        let pinned = ["sales.orders", "public.missing", "public.users"].map(String::from);
+        let tree = schema_tree_with_pins(&schema, &pinned);
+        assert_eq!(tree.len(), 3);
+        let SchemaItem::PinnedFolder { children, .. } = &tree[0] else {
+            panic!("expected pinned section first, got {:?}", tree[0]);
+        };
+        assert_eq!(tree[0].label().as_ref(), "Pinned (2)");
+        let ids: Vec<_> = children.iter().map(|item| item.id()).collect();
+        assert_eq!(ids, ["@pinned/sales.orders", "@pinned/public.users"]);
+        assert_eq!(children[0].children().unwrap()[0].id(), "@pinned/sales.orders.id");
+
+        // Pinned copies resolve to the original table
+        assert_eq!(unpinned_id(&ids[0]), "sales.orders");
+        assert_eq!(
+            DefinitionTarget::for_item(&children[0]),
+            Some(DefinitionTarget::Table {
+                schema: "sales".to_string(),
+                name: "orders".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_unloaded_columns_fill_in_before_folders() {
+        use tusk_core::models::schema::{ColumnDetail, IndexInfo, SchemaInfo};
+
+        let schema = DatabaseSchema {
+            schemas: vec![SchemaInfo { name: "public".to_string(), owner: "postgres".to_string() }],
+            tables: vec![table("public", "users")],
+            indexes: vec![IndexInfo {
+                schema: "public".to_string(),
+                table: "users".to_string(),
+                name: "users_pkey".to_string(),
+                columns: vec!["id".to_string()],
+                is_unique: true,
+                is_primary: true,
+                method: "btree".to_string(),
+                predicate: None,
+            }],
+            ..Default::default()
+        };
+        let mut tree = schema_tree_with_pins(&schema, &["public.users".to_string()]);
+        let tables = |tree: &[SchemaItem]| {
+            let pinned = tree[0].children().unwrap()[0].clone();
+            let users = tree[1].children().unwrap()[0].children().unwrap()[0].clone();
+            [users, pinned]
+        };
+        for table in tables(&tree) {
+            assert!(!table.is_loaded(), "{} should load columns on expand", table.id());
+            assert_eq!(table.children().unwrap().len(), 1);
+        }
+
+        let columns = column_items(
+            "public.users",
+            &[ColumnDetail {
+                name: "id".to_string(),
+                data_type: "integer".to_string(),
+                is_nullable: false,
+                is_primary_key: true,
+                default_value: None,
+                ordinal_position: 1,
+                comment: None,
+            }],
+        );
+        fill_columns(&mut tree, "public.users", &columns);
+
+        let [users, pinned] = tables(&tree);
+        assert!(users.is_loaded() && pinned.is_loaded());
+        let ids = |item: &SchemaItem| -> Vec<String> {
+            item.children().unwrap().iter().map(|child| child.id()).collect()
+        };
+        assert_eq!(ids(&users), ["public.users.id", "public.users/#indexes"]);
+        assert_eq!(ids(&pinned), ["@pinned/public.users.id", "@pinned/public.users/#indexes"]);
+
+        // Filling again doesn't duplicate the columns
+        fill_columns(&mut tree, "public.users", &columns);
+        assert_eq!(ids(&tables(&tree)[0]).len(), 2);
+    }
 }
@@ -0,0 +1,363 @@
+//! Cell selection in the results grid and its status bar summary.
+//!
+//! A selection is a rectangle spanned by an anchor cell and a head cell.
+//! Columns are display positions, so the rectangle covers what the user sees
+//! after columns are reordered or hidden; the grid's visible column list maps
+//! them back to source column indices.
+//! The summary reports the row count and, when numeric cells are selected,
+//! spreadsheet-style aggregates (count, sum, average, min, max).
+
+use std::ops::RangeInclusive;
+
+use super::results::{DisplayColumn, DisplayRow};
+
+/// Position of a cell in the results grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellPosition {
+    /// Row index
+    pub row: usize,
+    /// Column position in display order
+    pub col: usize,
+}
+
+impl CellPosition {
+    /// Create a cell position.
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+}
+
+/// Rectangular range of selected cells.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellSelection {
+    /// Cell where the selection started
+    pub anchor: CellPosition,
+    /// Cell the selection was extended to
+    pub head: CellPosition,
+}
+
+impl CellSelection {
+    /// Select a single cell.
+    pub fn single(position: CellPosition) -> Self {
+        Self { anchor: position, head: position }
+    }
+
+    /// Extend the selection to a new head, keeping the anchor.
+    pub fn extend_to(self, head: CellPosition) -> Self {
+        Self { anchor: self.anchor, head }
+    }
+
+    /// Move the head by whole cells within a grid of `rows` rows and `cols`
+    /// visible columns, extending the selection when `extend` is set and
+    /// collapsing it onto the new cell otherwise.
+    pub fn step(
+        self,
+        rows: usize,
+        cols: usize,
+        row_delta: isize,
+        col_delta: isize,
+        extend: bool,
+    ) -> Self {
+        if rows == 0 || cols == 0 {
+            return self;
+        }
+        let row = self.head.row.saturating_add_signed(row_delta).min(rows - 1);
+        let col = self.head.col.saturating_add_signed(col_delta).min(cols - 1);
+        let head = CellPosition::new(row, col);
+        if extend {
+            self.extend_to(head)
+        } else {
+            Self::single(head)
+        }
+    }
+
+    /// Rows covered by the selection.
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.row.min(self.head.row)..=self.anchor.row.max(self.head.row)
+    }
+
+    /// Display positions of the columns covered by the selection.
+    pub fn cols(&self) -> RangeInclusive<usize> {
+        self.anchor.col.min(self.head.col)..=self.anchor.col.max(self.head.col)
+    }
+
+    /// Source indices of the selected columns, in display order.
+    ///
+    /// `visible` lists the source indices of the shown columns in display
+    /// order, as from [`ColumnLayout::visible`](super::ColumnLayout::visible).
+    pub fn source_cols(&self, visible: &[usize]) -> Vec<usize> {
+        self.cols().filter_map(|col| visible.get(col).copied()).collect()
+    }
+
+    /// Check if a cell, given by its row and display position, is inside the selection.
+    pub fn contains(&self, row: usize, col: usize) -> bool {
+        self.rows().contains(&row) && self.cols().contains(&col)
+    }
+
+    /// Number of cells in the selection.
+    pub fn cell_count(&self) -> usize {
+        self.rows().count() * self.cols().count()
+    }
+}
+
+/// Aggregates over the numeric cells in a selection.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericSummary {
+    /// Number of numeric values
+    pub count: usize,
+    /// Sum of values
+    pub sum: f64,
+    /// Smallest value
+    pub min: f64,
+    /// Largest value
+    pub max: f64,
+}
+
+impl NumericSummary {
+    /// Mean of the values.
+    pub fn avg(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+/// Summary of the results grid shown in the status bar.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectionSummary {
+    /// Total rows in the current result
+    pub total_rows: usize,
+    /// Number of selected cells
+    pub selected_cells: usize,
+    /// Aggregates when numeric cells are selected
+    pub numeric: Option<NumericSummary>,
+}
+
+impl SelectionSummary {
+    /// Compute the summary for a selection over the given rows.
+    ///
+    /// `visible` maps the selection's display positions to source columns.
+    /// Only cells in numeric columns contribute to the aggregates; NULLs and
+    /// values that fail to parse are skipped.
+    pub fn compute(
+        columns: &[DisplayColumn],
+        rows: &[DisplayRow],
+        visible: &[usize],
+        total_rows: usize,
+        selection: Option<&CellSelection>,
+    ) -> Self {
+        let Some(selection) = selection else {
+            return Self { total_rows, selected_cells: 0, numeric: None };
+        };
+
+        let source_cols = selection.source_cols(visible);
+        let mut numeric: Option<NumericSummary> = None;
+        for row in selection.rows().filter_map(|r| rows.get(r)) {
+            for &col in &source_cols {
+                let is_numeric = columns.get(col).is_some_and(|c| is_numeric_type(&c.type_name));
+                if !is_numeric {
+                    continue;
+                }
+                let Some(value) =
+                    row.cells.get(col).and_then(Option::as_deref).and_then(parse_numeric)
+                else {
+                    continue;
+                };
+                numeric = Some(match numeric {
+                    None => NumericSummary { count: 1, sum: value, min: value, max: value },
+                    Some(acc) => NumericSummary {
+                        count: acc.count + 1,
+                        sum: acc.sum + value,
+                        min: acc.min.min(value),
+                        max: acc.max.max(value),
+                    },
+                });
+            }
+        }
+
+        let selected_cells = selection.rows().count() * source_cols.len();
+        Self { total_rows, selected_cells, numeric }
+    }
+
+    /// Render the summary as status bar text.
+    pub fn display(&self) -> String {
+        let mut parts = vec![format!(
+            "{} row{}",
+            format_count(self.total_rows),
+            if self.total_rows == 1 { "" } else { "s" }
+        )];
+        if self.selected_cells > 0 {
+            parts.push(format!("{} selected", format_count(self.selected_cells)));
+        }
+        if let Some(numeric) = &self.numeric {
+            parts.push(format!("COUNT={}", format_count(numeric.count)));
+            parts.push(format!("SUM={}", format_number(numeric.sum)));
+            parts.push(format!("AVG={}", format_number(numeric.avg())));
+            parts.push(format!("MIN={}", format_number(numeric.min)));
+            parts.push(format!("MAX={}", format_number(numeric.max)));
+        }
+        parts.join(" • ")
+    }
+}
+
+/// Check if a PostgreSQL type name is numeric.
+pub fn is_numeric_type(type_name: &str) -> bool {
+    matches!(type_name, "int2" | "int4" | "int8" | "float4" | "float8" | "numeric" | "oid")
+}
+
+/// Parse a displayed cell as a number, treating non-finite values as absent.
+fn parse_numeric(cell: &str) -> Option<f64> {
+    cell.trim().parse::<f64>().ok().filter(|v| v.is_finite())
+}
+
+/// Format a count with thousands separators.
+fn format_count(n: usize) -> String {
+    let digits = n.to_string();
+    let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, ch) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            out.push(',');
+        }
+        out.push(ch);
+    }
+    out
+}
+
+/// Format an aggregate value, dropping trailing zeros.
+fn format_number(value: f64) -> String {
+    let formatted = format!("{:.4}", value);
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    if trimmed == "-0" {
+        "0".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(name: &str, type_name: &str) -> DisplayColumn {
+        DisplayColumn {
+            name: name.to_string(),
+            type_oid: 0,
+            type_name: type_name.to_string(),
+            table_oid: None,
+            column_id: None,
+        }
+    }
+
+    fn row(cells: &[&str]) -> DisplayRow {
+        DisplayRow { cells: cells.iter().map(|c| (*c != "NULL").then(|| c.to_string())).collect() }
+    }
+
+    #[test]
+    fn test_step_clamps_to_grid() {
+        let start = CellSelection::single(CellPosition::new(1, 1));
+
+        let moved = start.step(5, 3, 0, 1, false);
+        assert_eq!(moved, CellSelection::single(CellPosition::new(1, 2)));
+        assert_eq!(moved.step(5, 3, 0, 1, false), moved);
+        assert_eq!(start.step(5, 3, -3, -2, false), CellSelection::single(CellPosition::new(0, 0)));
+
+        let extended = start.step(5, 3, 1, 0, true).step(5, 3, 10, 0, true);
+        assert_eq!(extended.anchor, CellPosition::new(1, 1));
+        assert_eq!(extended.head, CellPosition::new(4, 1));
+        assert_eq!(extended.cell_count(), 4);
+    }
+
+    #[test]
+    fn test_selection_follows_reordered_columns() {
+        // Column 1 is hidden and column 3 was moved to the front
+        let visible = [3, 0, 2];
+        let columns = vec![
+            column("a", "int4"),
+            column("b", "int4"),
+            column("c", "text"),
+            column("d", "int4"),
+        ];
+        let rows = vec![row(&["1", "100", "x", "10"]), row(&["2", "200", "y", "20"])];
+
+        // The first two shown columns, d and a, are adjacent on screen only
+        let selection =
+            CellSelection::single(CellPosition::new(0, 0)).extend_to(CellPosition::new(1, 1));
+        assert_eq!(selection.source_cols(&visible), vec![3, 0]);
+
+        let summary = SelectionSummary::compute(&columns, &rows, &visible, 2, Some(&selection));
+        let numeric = summary.numeric.expect("numeric summary");
+        // Hidden column b is not summed even though its source index lies between
+        assert_eq!(summary.selected_cells, 4);
+        assert_eq!(numeric.sum, 33.0);
+
+        let moved = selection.step(2, visible.len(), 0, 1, true);
+        assert_eq!(moved.source_cols(&visible), vec![3, 0, 2]);
+    }
+
+    #[test]
+    fn test_summary_aggregates_numeric_cells() {
+        let columns = vec![column("id", "int4"), column("price", "numeric")];
+        let rows = vec![row(&["1", "2.5"]), row(&["2", "7.5"]), row(&["3", "5"])];
+        let selection =
+            CellSelection::single(CellPosition::new(0, 1)).extend_to(CellPosition::new(2, 1));
+
+        let summary = SelectionSummary::compute(&columns, &rows, &[0, 1], 3, Some(&selection));
+        let numeric = summary.numeric.expect("numeric summary");
+
+        assert_eq!(summary.selected_cells, 3);
+        assert_eq!(numeric.count, 3);
+        assert_eq!(numeric.sum, 15.0);
+        assert_eq!(numeric.avg(), 5.0);
+        assert_eq!(numeric.min, 2.5);
+        assert_eq!(numeric.max, 7.5);
+    }
+
+    #[test]
+    fn test_summary_ignores_nulls_and_non_numeric_cells() {
+        let columns = vec![column("name", "text"), column("qty", "int8")];
+        let rows = vec![row(&["42", "10"]), row(&["7", "NULL"]), row(&["x", "20"])];
+        let selection =
+            CellSelection::single(CellPosition::new(2, 1)).extend_to(CellPosition::new(0, 0));
+
+        let summary = SelectionSummary::compute(&columns, &rows, &[0, 1], 3, Some(&selection));
+        let numeric = summary.numeric.expect("numeric summary");
+
+        // Text column values are skipped even when they look numeric
+        assert_eq!(summary.selected_cells, 6);
+        assert_eq!(numeric.count, 2);
+        assert_eq!(numeric.sum, 30.0);
+    }
+
+    #[test]
+    fn test_summary_without_numeric_cells() {
+        let columns = vec![column("name", "text")];
+        let rows = vec![row(&["a"])];
+        let selection = CellSelection::single(CellPosition::new(0, 0));
+
+        let summary = SelectionSummary::compute(&columns, &rows, &[0], 1234, Some(&selection));
+
+        assert!(summary.numeric.is_none());
+        assert_eq!(summary.display(), "1,234 rows • 1 selected");
+    }
+
+    #[test]
+    fn test_summary_display_with_aggregates() {
+        let summary = SelectionSummary {
+            total_rows: 1,
+            selected_cells: 2,
+            numeric: Some(NumericSummary { count: 2, sum: 3.0, min: 1.0, max: 2.0 }),
+        };
+        assert_eq!(
+            summary.display(),
+            "1 row • 2 selected • COUNT=2 • SUM=3 • AVG=1.5 • MIN=1 • MAX=2"
+        );
+    }
+
+    #[test]
+    fn test_selection_contains() {
+        let selection =
+            CellSelection::single(CellPosition::new(3, 2)).extend_to(CellPosition::new(1, 4));
+        assert!(selection.contains(2, 3));
+        assert!(!selection.contains(0, 3));
+        assert!(!selection.contains(2, 5));
+        assert_eq!(selection.cell_count(), 9);
+    }
+}
@@ -5,26 +5,58 @@
 //! - Query execution via TuskState (FR-010)
 //! - Streaming results to ResultsPanel (FR-011, FR-012)
 //! - Query cancellation support (FR-013)
+//! - Multi-statement scripts with one result set per statement
+//! - Snippet insertion with `${name}` placeholders
+//! - Hover docs for SQL keywords and built-in functions
+//! - Bracket matching and auto-closing of `(` and `'`
+//! - Toggling `--` comments on the selected lines
+//! - Duplicating and moving the selected lines
+//! - Opening and saving `.sql` files with dirty tracking
+//! - Confirmation before destructive queries and writes on production connections
 
 use gpui::{
-    div, prelude::*, px, App, Context, Entity, FocusHandle, Focusable, Render, Task, Window,
+    deferred, div, prelude::*, px, App, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    MouseButton, Pixels, Point, Render, SharedString, Subscription, Task, Window,
 };
+use std::path::PathBuf;
 use std::sync::Arc;
 use uuid::Uuid;
 
+use crate::confirm_dialog::ConfirmDialog;
 use crate::icon::{Icon, IconName, IconSize};
-use crate::key_bindings::{CancelQuery, RunQuery};
+use crate::key_bindings::{
+    CancelQuery, DuplicateLine, MoveLineDown, MoveLineUp, RunQuery, ToggleLineComment,
+};
 use crate::panels::{Message, MessagesPanel, ResultsPanel};
 use crate::spinner::{Spinner, SpinnerSize};
+use crate::sql_editing;
+use crate::sql_file::SqlFileState;
+use crate::sql_reference;
 use crate::text_input::{TextInput, TextInputEvent};
 use crate::TuskTheme;
 
 #[cfg(feature = "persistence")]
-use tusk_core::{QueryHandle, TuskState};
+use crate::confirm_dialog::ConfirmDialogEvent;
+#[cfg(feature = "persistence")]
+use crate::context_menu::{ContextMenu, ContextMenuItem, ContextMenuLayer};
+#[cfg(feature = "persistence")]
+use tusk_core::{ConfirmationReason, QueryHandle, QueryRegistration, TuskState};
 
 #[cfg(feature = "persistence")]
 use tokio::sync::mpsc;
 
+/// Number of submitted queries recalled with Up and Down in the SQL input.
+const SQL_INPUT_HISTORY: usize = 100;
+
+/// History entries scanned for suggested snippets.
+#[cfg(feature = "persistence")]
+const SNIPPET_SUGGESTION_HISTORY: usize = 200;
+
+/// Hover docs for a word in the SQL input.
+fn sql_hover_docs(word: &str) -> Option<SharedString> {
+    sql_reference::lookup(word).map(|entry| entry.tooltip_text().into())
+}
+
 /// Status of the query editor.
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum QueryEditorStatus {
@@ -54,6 +86,48 @@ impl QueryEditorStatus {
     }
 }
 
+/// Events emitted by the query editor.
+#[derive(Debug, Clone)]
+pub enum QueryEditorEvent {
+    /// A query started executing; the handle allows cancelling it elsewhere.
+    #[cfg(feature = "persistence")]
+    Started(Arc<QueryHandle>),
+    /// Cancellation of the running query was requested from the editor.
+    CancelRequested,
+    /// The query failed before or during execution.
+    Failed(String),
+    /// The backing file or its dirty state changed.
+    FileStateChanged,
+    /// A successful run changed the session search_path (empty after RESET).
+    SearchPathChanged(String),
+    /// A successful run executed DDL, so cached schema metadata is stale.
+    SchemaChanged,
+    /// A run opened, failed, or ended a transaction on the connection.
+    TransactionStatusChanged,
+}
+
+/// Notices when a run changes its connection's transaction status.
+#[cfg(feature = "persistence")]
+struct TransactionWatch {
+    pool: Arc<tusk_core::ConnectionPool>,
+    before: tusk_core::TransactionStatus,
+}
+
+#[cfg(feature = "persistence")]
+impl TransactionWatch {
+    /// Remember the status before a run.
+    fn new(pool: &Arc<tusk_core::ConnectionPool>) -> Self {
+        Self { pool: pool.clone(), before: pool.transaction_status() }
+    }
+
+    /// Emit [`QueryEditorEvent::TransactionStatusChanged`] if the run changed the status.
+    fn emit_if_changed(&self, cx: &mut Context<QueryEditor>) {
+        if self.pool.transaction_status() != self.before {
+            cx.emit(QueryEditorEvent::TransactionStatusChanged);
+        }
+    }
+}
+
 /// State for the query editor component.
 pub struct QueryEditorState {
     /// Current connection ID for this editor.
@@ -112,6 +186,16 @@ pub struct QueryEditor {
     results_panel: Option<Entity<ResultsPanel>>,
     /// Messages panel for notifications (e.g., query cancelled).
     messages_panel: Option<Entity<MessagesPanel>>,
+    /// Whether the editor shows a definition that can't be edited or executed.
+    read_only: bool,
+    /// Row limit added to the last run's SELECTs, if any.
+    row_limit_applied: Option<u32>,
+    /// `.sql` file backing this editor, if any.
+    file: SqlFileState,
+    /// Confirmation shown before running a guarded query.
+    confirm_dialog: Option<Entity<ConfirmDialog>>,
+    /// Subscription to the confirmation dialog's events.
+    _dialog_subscription: Option<Subscription>,
     /// Background task for query execution (dropped on new query = automatic cancellation).
     _execution_task: Option<Task<()>>,
 }
@@ -119,8 +203,13 @@ pub struct QueryEditor {
 impl QueryEditor {
     /// Create a new query editor.
     pub fn new(cx: &mut Context<Self>) -> Self {
-        let sql_input =
-            cx.new(|cx| TextInput::new("Enter SQL query (e.g., SELECT * FROM users)", cx));
+        let sql_input = cx.new(|cx| {
+            let mut input = TextInput::new("Enter SQL query (e.g., SELECT * FROM users)", cx);
+            input.enable_history(SQL_INPUT_HISTORY);
+            input.set_hover_docs(sql_hover_docs);
+            input.enable_bracket_pairs();
+            input
+        });
 
         // Subscribe to text input changes
         cx.subscribe(&sql_input, Self::on_sql_input_event).detach();
@@ -132,14 +221,24 @@ impl QueryEditor {
             sql_input,
             results_panel: None,
             messages_panel: None,
+            read_only: false,
+            row_limit_applied: None,
+            file: SqlFileState::default(),
+            confirm_dialog: None,
+            _dialog_subscription: None,
             _execution_task: None,
         }
     }
 
     /// Create a new query editor with a connection.
     pub fn with_connection(connection_id: Uuid, cx: &mut Context<Self>) -> Self {
-        let sql_input =
-            cx.new(|cx| TextInput::new("Enter SQL query (e.g., SELECT * FROM users)", cx));
+        let sql_input = cx.new(|cx| {
+            let mut input = TextInput::new("Enter SQL query (e.g., SELECT * FROM users)", cx);
+            input.enable_history(SQL_INPUT_HISTORY);
+            input.set_hover_docs(sql_hover_docs);
+            input.enable_bracket_pairs();
+            input
+        });
 
         // Subscribe to text input changes
         cx.subscribe(&sql_input, Self::on_sql_input_event).detach();
@@ -151,6 +250,11 @@ impl QueryEditor {
             sql_input,
             results_panel: None,
             messages_panel: None,
+            read_only: false,
+            row_limit_applied: None,
+            file: SqlFileState::default(),
+            confirm_dialog: None,
+            _dialog_subscription: None,
             _execution_task: None,
         }
     }
@@ -165,6 +269,9 @@ impl QueryEditor {
         match event {
             TextInputEvent::Changed(text) => {
                 self.content = text.clone();
+                if self.file.edited(&self.content) {
+                    cx.emit(QueryEditorEvent::FileStateChanged);
+                }
                 cx.notify();
             }
             TextInputEvent::Submitted(_) => {
@@ -203,9 +310,132 @@ impl QueryEditor {
     /// Set the SQL content.
     pub fn set_content(&mut self, content: impl Into<String>, cx: &mut Context<Self>) {
         self.content = content.into();
+        let content = self.content.clone();
+        self.sql_input.update(cx, |input, cx| input.set_text(content, cx));
+        cx.notify();
+    }
+
+    /// Get the `.sql` file backing this editor.
+    pub fn file(&self) -> &SqlFileState {
+        &self.file
+    }
+
+    /// Load the contents of a `.sql` file into the editor.
+    pub fn open_file(&mut self, path: PathBuf, content: String, cx: &mut Context<Self>) {
+        self.file = SqlFileState::opened(path, content.clone());
+        self.set_content(content, cx);
+        cx.emit(QueryEditorEvent::FileStateChanged);
+    }
+
+    /// Record that `content` was written to `path`.
+    ///
+    /// The buffer stays dirty if it was edited while the write was in flight.
+    pub fn mark_saved(&mut self, path: PathBuf, content: String, cx: &mut Context<Self>) {
+        self.file.saved(path, content);
+        self.file.edited(&self.content);
+        cx.emit(QueryEditorEvent::FileStateChanged);
         cx.notify();
     }
 
+    /// Set whether the editor is read-only.
+    ///
+    /// Read-only editors display a definition: the text can be selected and
+    /// copied but not edited or executed.
+    pub fn set_read_only(&mut self, read_only: bool, cx: &mut Context<Self>) {
+        self.read_only = read_only;
+        self.sql_input.update(cx, |input, _| input.set_read_only(read_only));
+        cx.notify();
+    }
+
+    /// Check if the editor is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Insert a snippet body at the end of the editor content.
+    ///
+    /// Escapes are resolved and unfilled `${name}` placeholders are kept so the
+    /// user can replace them.
+    #[cfg(feature = "persistence")]
+    pub fn insert_snippet(&mut self, body: &str, cx: &mut Context<Self>) {
+        let expanded = tusk_core::models::snippet::expand(body, &Default::default());
+        let content = if self.content.trim().is_empty() {
+            expanded
+        } else {
+            format!("{}\n{}", self.content.trim_end(), expanded)
+        };
+        self.content = content.clone();
+        self.sql_input.update(cx, |input, cx| input.set_text(content, cx));
+        cx.notify();
+    }
+
+    /// Show the snippet menu at the given position.
+    #[cfg(feature = "persistence")]
+    fn show_snippet_menu(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        let (snippets, suggested) = match cx.try_global::<TuskState>() {
+            Some(state) => {
+                let snippets = state.storage().load_all_snippets().unwrap_or_else(|e| {
+                    tracing::warn!(error = %e, "Failed to load snippets");
+                    Vec::new()
+                });
+                (snippets, self.suggested_snippets(state))
+            }
+            None => (Vec::new(), Vec::new()),
+        };
+
+        let editor = cx.entity().downgrade();
+        let snippet_item = |snippet: tusk_core::Snippet, icon: IconName| {
+            let editor = editor.clone();
+            let body = snippet.body;
+            ContextMenuItem::action(snippet.name, move |cx| {
+                let _ = editor.update(cx, |editor, cx| editor.insert_snippet(&body, cx));
+            })
+            .icon(icon)
+        };
+
+        let mut items: Vec<ContextMenuItem> = if snippets.is_empty() {
+            vec![ContextMenuItem::action("No saved snippets", |_cx| {}).disabled(true)]
+        } else {
+            snippets.into_iter().map(|snippet| snippet_item(snippet, IconName::Code)).collect()
+        };
+        if !suggested.is_empty() {
+            items.push(ContextMenuItem::separator());
+            items.extend(
+                suggested.into_iter().map(|snippet| snippet_item(snippet, IconName::Table)),
+            );
+        }
+
+        let menu = cx.new(|cx| ContextMenu::new(position, cx).items(items));
+        cx.update_global::<ContextMenuLayer, _>(|layer, cx| {
+            layer.show_deferred(menu, cx);
+        });
+    }
+
+    /// Snippets for the tables this connection's recent queries used most.
+    #[cfg(feature = "persistence")]
+    fn suggested_snippets(&self, state: &TuskState) -> Vec<tusk_core::Snippet> {
+        let Some(connection_id) = self.state.connection_id else {
+            return Vec::new();
+        };
+        let Some(cache) = state.get_schema_cache_any(&connection_id) else {
+            return Vec::new();
+        };
+        let history = state
+            .storage()
+            .load_history(connection_id, SNIPPET_SUGGESTION_HISTORY)
+            .unwrap_or_else(|e| {
+                tracing::warn!(error = %e, "Failed to load history for snippet suggestions");
+                Vec::new()
+            });
+        tusk_core::models::suggest_snippets(connection_id, cache.schema(), &history)
+    }
+
+    /// Snippet menu placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn show_snippet_menu(&mut self, _position: Point<Pixels>, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
     /// Get the current status.
     pub fn status(&self) -> &QueryEditorStatus {
         &self.state.status
@@ -216,17 +446,48 @@ impl QueryEditor {
         self.state.is_executing()
     }
 
+    /// Row limit added to the last run's SELECTs, if any.
+    pub fn row_limit_applied(&self) -> Option<u32> {
+        self.row_limit_applied
+    }
+
     /// Execute the current SQL query (FR-010, FR-011, FR-012).
     ///
+    /// SELECTs without their own LIMIT get the connection's row limit.
+    #[cfg(feature = "persistence")]
+    pub fn execute_query(&mut self, cx: &mut Context<Self>) {
+        self.run_query(true, None, cx);
+    }
+
+    /// Execute the current SQL query without the connection's row limit.
+    #[cfg(feature = "persistence")]
+    pub fn execute_query_without_limit(&mut self, cx: &mut Context<Self>) {
+        self.run_query(false, None, cx);
+    }
+
+    /// Run the editor's SQL, optionally applying the row-limit guard.
+    ///
+    /// Scripts the destructive-query guard flags ask for confirmation first;
+    /// `approved_sql` is the text the user already confirmed.
+    ///
     /// This method:
     /// 1. Creates an mpsc channel for streaming results
     /// 2. Starts the results panel streaming
     /// 3. Spawns the query execution on the tokio runtime
     /// 4. Stores the query handle for cancellation support
     #[cfg(feature = "persistence")]
-    pub fn execute_query(&mut self, cx: &mut Context<Self>) {
+    fn run_query(
+        &mut self,
+        apply_row_limit: bool,
+        approved_sql: Option<String>,
+        cx: &mut Context<Self>,
+    ) {
         use tusk_core::services::QueryService;
 
+        if self.read_only || self.confirm_dialog.is_some() {
+            return;
+        }
+
         // Validate we have a connection
         let Some(connection_id) = self.state.connection_id else {
             tracing::warn!("Cannot execute query: no connection");
@@ -253,13 +514,85 @@ impl QueryEditor {
         };
         let runtime_handle = state.runtime().handle().clone();
 
-        // Create and register query handle
-        let handle = QueryHandle::new(connection_id, sql.clone());
-        let handle = state.register_query(handle);
+        // Destructive statements, and any write on a production connection, need confirmation
+        if approved_sql.as_deref() != Some(sql.as_str()) {
+            let config = state.get_connection_config(&connection_id);
+            let environment = config.as_ref().map(|config| config.environment).unwrap_or_default();
+            if let Some(reason) = QueryService::confirmation_reason(&sql, environment) {
+                let name = config.map(|config| config.name).unwrap_or_default();
+                tracing::debug!(connection_id = %connection_id, ?environment, "Query needs confirmation");
+                self.confirm_run(
+                    reason,
+                    &name,
+                    environment.is_production(),
+                    apply_row_limit,
+                    sql,
+                    cx,
+                );
+                return;
+            }
+        }
+
+        // Bare SELECTs get the connection's row limit unless the user opted out
+        let row_limit = if apply_row_limit {
+            state.get_connection_config(&connection_id).and_then(|config| config.options.row_limit)
+        } else {
+            None
+        };
+        let limited = row_limit.and_then(|limit| {
+            QueryService::apply_row_limit(&sql, limit).map(|limited| (limited, limit))
+        });
+        let source_sql = sql.clone();
+        let sql = match limited {
+            Some((limited, limit)) => {
+                self.row_limit_applied = Some(limit);
+                if let Some(messages_panel) = &self.messages_panel {
+                    messages_panel.update(cx, |panel, cx| {
+                        panel.add_message(
+                            Message::info(format!(
+                                "Results limited to {limit} rows; use Run without limit to fetch all rows"
+                            )),
+                            cx,
+                        );
+                    });
+                }
+                limited
+            }
+            None => {
+                self.row_limit_applied = None;
+                sql
+            }
+        };
+
+        // Create and register query handle; history keeps the SQL as written.
+        // The registration moves into the task running the query, which
+        // unregisters it however the run ends.
+        let handle =
+            QueryHandle::new(connection_id, sql.clone()).with_source_sql(source_sql.clone());
+        let registration = state.track_query(handle);
+        let handle = registration.handle().clone();
         self.state.active_query = Some(handle.clone());
 
         // Update status to executing
         self.state.status = QueryEditorStatus::Executing;
+        cx.emit(QueryEditorEvent::Started(handle.clone()));
+
+        // Results are kept in this tab's history once the run completes
+        if let Some(results_panel) = &self.results_panel {
+            let editor = cx.entity();
+            results_panel.update(cx, |panel, cx| {
+                panel.begin_run(&editor, source_sql, cx);
+            });
+        }
+
+        // Scripts with several statements keep one result set per statement
+        if QueryService::split_statements(&sql).len() > 1 {
+            self.execute_script(sql, pool, registration, runtime_handle, cx);
+            return;
+        }
+
+        let search_path = QueryService::search_path_change(&sql);
+        let query_type = QueryService::detect_query_type(&sql);
 
         // Create channel for streaming results (bounded with backpressure)
         let (tx, rx) = mpsc::channel(100);
@@ -273,22 +606,33 @@ impl QueryEditor {
 
         // Spawn the query execution task
         // Replacing _execution_task will drop the old task, automatically cancelling it
+        let transaction = TransactionWatch::new(&pool);
         self._execution_task = Some(cx.spawn(async move |this, cx| {
-            // Execute the query with streaming using QueryService directly
+            // Execute the query with streaming using QueryService directly;
+            // inside a transaction it runs on the transaction's connection
             let result = runtime_handle
                 .spawn(async move {
-                    // Get a connection from the pool
-                    let conn = pool.get().await?;
-                    QueryService::execute_streaming(&conn, &sql, &handle, tx).await
+                    let handle = registration.handle();
+                    QueryService::execute_streaming_pooled(&pool, &sql, handle, tx).await
                 })
                 .await;
 
             // Update the editor when query completes
             let _ = this.update(cx, |editor: &mut QueryEditor, cx| {
+                transaction.emit_if_changed(cx);
                 match result {
                     Ok(Ok(())) => {
                         tracing::debug!("Query execution completed");
                         editor.state.status = QueryEditorStatus::Idle;
+                        if let Some(search_path) = search_path {
+                            cx.emit(QueryEditorEvent::SearchPathChanged(search_path));
+                        }
+                        let invalidated = cx.try_global::<TuskState>().is_some_and(|state| {
+                            state.invalidate_schema_after(&connection_id, query_type)
+                        });
+                        if invalidated {
+                            cx.emit(QueryEditorEvent::SchemaChanged);
+                        }
                     }
                     Ok(Err(e)) => {
                         // Check if this is a connection lost error (T049)
@@ -310,10 +654,14 @@ impl QueryEditor {
                         } else {
                             tracing::warn!(error = %e, "Query execution failed");
                         }
+                        if !e.is_cancelled() {
+                            cx.emit(QueryEditorEvent::Failed(e.to_error_info().message));
+                        }
                         editor.state.status = QueryEditorStatus::Idle;
                     }
                     Err(e) => {
                         tracing::error!(error = %e, "Query task panicked");
+                        cx.emit(QueryEditorEvent::Failed("Query task failed".to_string()));
                         editor.state.status = QueryEditorStatus::Idle;
                     }
                 }
@@ -324,6 +672,155 @@ impl QueryEditor {
         cx.notify();
     }
 
+    /// Ask before running a script the destructive-query guard flagged.
+    #[cfg(feature = "persistence")]
+    fn confirm_run(
+        &mut self,
+        reason: ConfirmationReason,
+        connection_name: &str,
+        production: bool,
+        apply_row_limit: bool,
+        sql: String,
+        cx: &mut Context<Self>,
+    ) {
+        /// Flagged statements listed in the dialog before summarizing the rest.
+        const MAX_LISTED: usize = 3;
+
+        let (title, mut message) = match &reason {
+            ConfirmationReason::Destructive(statements) => {
+                let mut listed = statements[..statements.len().min(MAX_LISTED)].join(";\n");
+                if statements.len() > MAX_LISTED {
+                    listed.push_str(&format!("\n...and {} more", statements.len() - MAX_LISTED));
+                }
+                ("Run Destructive Query?", format!("This can drop or erase data:\n\n{listed}"))
+            }
+            ConfirmationReason::Production => {
+                ("Run on Production?", "This query may modify data.".to_string())
+            }
+        };
+        if production {
+            message.push_str(&format!("\n\n\"{connection_name}\" is a production connection."));
+        }
+
+        let dialog = cx.new(|cx| {
+            ConfirmDialog::destructive(title, message, cx).with_confirm_label("Run Anyway")
+        });
+        let subscription = cx.subscribe(&dialog, move |this, _, event: &ConfirmDialogEvent, cx| {
+            this.confirm_dialog = None;
+            this._dialog_subscription = None;
+            if matches!(event, ConfirmDialogEvent::Confirmed) {
+                this.run_query(apply_row_limit, Some(sql.clone()), cx);
+            }
+            cx.notify();
+        });
+
+        self.confirm_dialog = Some(dialog);
+        self._dialog_subscription = Some(subscription);
+        cx.notify();
+    }
+
+    /// Execute a multi-statement script, streaming one result set per statement.
+    ///
+    /// The script stops at the first statement that fails; its summary goes
+    /// to the Messages panel.
+    #[cfg(feature = "persistence")]
+    fn execute_script(
+        &mut self,
+        sql: String,
+        pool: Arc<tusk_core::ConnectionPool>,
+        registration: QueryRegistration,
+        runtime_handle: tokio::runtime::Handle,
+        cx: &mut Context<Self>,
+    ) {
+        use tusk_core::services::QueryService;
+        use tusk_core::{QueryEvent, QueryType};
+
+        let (tx, rx) = mpsc::channel(100);
+        if let Some(results_panel) = &self.results_panel {
+            results_panel.update(cx, |panel, cx| {
+                panel.start_script(rx, cx);
+            });
+        }
+
+        let search_path = QueryService::search_path_change(&sql);
+        let changes_schema = QueryService::changes_schema(&sql);
+        let connection_id = registration.handle().connection_id();
+
+        let transaction = TransactionWatch::new(&pool);
+        self._execution_task = Some(cx.spawn(async move |this, cx| {
+            // Inside a transaction the script runs on the transaction's connection
+            let result = runtime_handle
+                .spawn(async move {
+                    let handle = registration.handle();
+                    QueryService::execute_script_pooled(&pool, &sql, handle, tx).await
+                })
+                .await;
+
+            let _ = this.update(cx, |editor: &mut QueryEditor, cx| {
+                transaction.emit_if_changed(cx);
+                let outcome = match result {
+                    Ok(outcome) => outcome,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Script task panicked");
+                        cx.emit(QueryEditorEvent::Failed("Query task failed".to_string()));
+                        editor.state.status = QueryEditorStatus::Idle;
+                        cx.notify();
+                        return;
+                    }
+                };
+
+                match outcome {
+                    Ok(summary) => {
+                        tracing::debug!(
+                            succeeded = summary.succeeded,
+                            failed = summary.failed,
+                            not_run = summary.not_run,
+                            "Script execution completed"
+                        );
+                        if let Some(search_path) = search_path {
+                            cx.emit(QueryEditorEvent::SearchPathChanged(search_path));
+                        }
+                        // All DDL in the script shares one invalidation and refresh;
+                        // a script without DDL may still commit a transaction that ran some
+                        let query_type =
+                            if changes_schema { QueryType::Ddl } else { QueryType::Utility };
+                        let invalidated = cx.try_global::<TuskState>().is_some_and(|state| {
+                            state.invalidate_schema_after(&connection_id, query_type)
+                        });
+                        if invalidated {
+                            cx.emit(QueryEditorEvent::SchemaChanged);
+                        }
+                        if let Some(messages_panel) = &editor.messages_panel {
+                            messages_panel.update(cx, |panel, cx| {
+                                let message = if summary.failed > 0 {
+                                    Message::error(summary.message())
+                                } else {
+                                    Message::success(summary.message())
+                                };
+                                panel.add_message(message, cx);
+                            });
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Script execution failed");
+                        // The results panel reports the failure (or cancellation) to the status bar
+                        if let Some(results_panel) = &editor.results_panel {
+                            results_panel.update(cx, |panel, cx| {
+                                panel.handle_event(QueryEvent::error(e), cx);
+                            });
+                        } else if !e.is_cancelled() {
+                            cx.emit(QueryEditorEvent::Failed(e.to_error_info().message));
+                        }
+                    }
+                }
+                editor.state.status = QueryEditorStatus::Idle;
+                cx.notify();
+            });
+        }));
+
+        cx.notify();
+    }
+
     /// Execute query placeholder for non-persistence builds.
     #[cfg(not(feature = "persistence"))]
     pub fn execute_query(&mut self, cx: &mut Context<Self>) {
@@ -331,6 +828,12 @@ impl QueryEditor {
         cx.notify();
     }
 
+    /// Execute without limit placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn execute_query_without_limit(&mut self, cx: &mut Context<Self>) {
+        self.execute_query(cx);
+    }
+
     /// Cancel the currently executing query (FR-013).
     #[cfg(feature = "persistence")]
     pub fn cancel_query(&mut self, cx: &mut Context<Self>) {
@@ -342,6 +845,7 @@ impl QueryEditor {
 
             // Update status
             self.state.status = QueryEditorStatus::Cancelled;
+            cx.emit(QueryEditorEvent::CancelRequested);
 
             // Also cancel through TuskState to send PostgreSQL cancel
             let query_id = handle.id();
@@ -384,12 +888,68 @@ impl QueryEditor {
         self.cancel_query(cx);
     }
 
+    /// Handle the ToggleLineComment action (Cmd+/).
+    fn on_toggle_line_comment(
+        &mut self,
+        _: &ToggleLineComment,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sql_input.update(cx, |input, cx| {
+            let (text, selection) =
+                sql_editing::toggle_line_comment(input.text(), input.selected_range());
+            input.edit_text(text, selection, cx);
+        });
+    }
+
+    /// Handle the DuplicateLine action (Cmd+Shift+D).
+    fn on_duplicate_line(
+        &mut self,
+        _: &DuplicateLine,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.sql_input.update(cx, |input, cx| {
+            let (text, selection) =
+                sql_editing::duplicate_lines(input.text(), input.selected_range());
+            input.edit_text(text, selection, cx);
+        });
+    }
+
+    /// Handle the MoveLineUp action (Alt+Up).
+    fn on_move_line_up(&mut self, _: &MoveLineUp, _window: &mut Window, cx: &mut Context<Self>) {
+        self.move_lines(true, cx);
+    }
+
+    /// Handle the MoveLineDown action (Alt+Down).
+    fn on_move_line_down(
+        &mut self,
+        _: &MoveLineDown,
+        _window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.move_lines(false, cx);
+    }
+
+    /// Move the selected lines past the adjacent line, if there is one.
+    fn move_lines(&mut self, up: bool, cx: &mut Context<Self>) {
+        self.sql_input.update(cx, |input, cx| {
+            if let Some((text, selection)) =
+                sql_editing::move_lines(input.text(), input.selected_range(), up)
+            {
+                input.edit_text(text, selection, cx);
+            }
+        });
+    }
+
     /// Render the toolbar with execute/cancel button.
     fn render_toolbar(&self, theme: &TuskTheme, cx: &mut Context<Self>) -> impl IntoElement {
         let is_executing = self.state.status.is_executing();
         let has_connection = self.state.connection_id.is_some();
         let has_content = !self.content.trim().is_empty();
         let can_execute = has_connection && has_content && !is_executing;
+        let read_only = self.read_only;
+        let row_limit_applied = self.row_limit_applied.filter(|_| !is_executing);
 
         div()
             .h(px(36.0))
@@ -401,70 +961,140 @@ impl QueryEditor {
             .border_b_1()
             .border_color(theme.colors.border)
             .bg(theme.colors.panel_background)
-            .child(if is_executing {
-                // Cancel button while executing
-                div()
-                    .id("cancel-button")
-                    .flex()
-                    .items_center()
-                    .gap(px(6.0))
-                    .px(px(12.0))
-                    .py(px(4.0))
-                    .rounded(px(4.0))
-                    .bg(theme.colors.error.opacity(0.1))
-                    .hover(|s| s.bg(theme.colors.error.opacity(0.2)))
-                    .cursor_pointer()
-                    .on_click(cx.listener(|this, _, _, cx| {
-                        this.cancel_query(cx);
-                    }))
-                    .child(Spinner::new().size(SpinnerSize::Small))
-                    .child(div().text_size(px(12.0)).text_color(theme.colors.error).child("Cancel"))
-                    .into_any_element()
-            } else {
-                // Execute button when idle
-                div()
-                    .id("execute-button")
-                    .flex()
-                    .items_center()
-                    .gap(px(6.0))
-                    .px(px(12.0))
-                    .py(px(4.0))
-                    .rounded(px(4.0))
-                    .when(can_execute, |s| {
-                        s.bg(theme.colors.accent.opacity(0.1))
-                            .hover(|s| s.bg(theme.colors.accent.opacity(0.2)))
+            .when(!read_only, |toolbar| {
+                toolbar
+                    .child(if is_executing {
+                        // Cancel button while executing
+                        div()
+                            .id("cancel-button")
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .px(px(12.0))
+                            .py(px(4.0))
+                            .rounded(px(4.0))
+                            .bg(theme.colors.error.opacity(0.1))
+                            .hover(|s| s.bg(theme.colors.error.opacity(0.2)))
                             .cursor_pointer()
                             .on_click(cx.listener(|this, _, _, cx| {
-                                this.execute_query(cx);
+                                this.cancel_query(cx);
                             }))
-                    })
-                    .when(!can_execute, |s| s.opacity(0.5).cursor_not_allowed())
-                    .child(Icon::new(IconName::Play).size(IconSize::Small).color(if can_execute {
-                        theme.colors.accent
+                            .child(Spinner::new().size(SpinnerSize::Small))
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(theme.colors.error)
+                                    .child("Cancel"),
+                            )
+                            .into_any_element()
                     } else {
-                        theme.colors.text_muted
-                    }))
-                    .child(
+                        // Execute button when idle
                         div()
-                            .text_size(px(12.0))
-                            .text_color(if can_execute {
-                                theme.colors.accent
-                            } else {
-                                theme.colors.text_muted
+                            .id("execute-button")
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .px(px(12.0))
+                            .py(px(4.0))
+                            .rounded(px(4.0))
+                            .when(can_execute, |s| {
+                                s.bg(theme.colors.accent.opacity(0.1))
+                                    .hover(|s| s.bg(theme.colors.accent.opacity(0.2)))
+                                    .cursor_pointer()
+                                    .on_click(cx.listener(|this, _, _, cx| {
+                                        this.execute_query(cx);
+                                    }))
                             })
-                            .child("Execute"),
+                            .when(!can_execute, |s| s.opacity(0.5).cursor_not_allowed())
+                            .child(Icon::new(IconName::Play).size(IconSize::Small).color(
+                                if can_execute {
+                                    theme.colors.accent
+                                } else {
+                                    theme.colors.text_muted
+                                },
+                            ))
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(if can_execute {
+                                        theme.colors.accent
+                                    } else {
+                                        theme.colors.text_muted
+                                    })
+                                    .child("Execute"),
+                            )
+                            .into_any_element()
+                    })
+                    // Snippet menu
+                    .child(
+                        div()
+                            .id("snippets-button")
+                            .flex()
+                            .items_center()
+                            .gap(px(6.0))
+                            .px(px(8.0))
+                            .py(px(4.0))
+                            .rounded(px(4.0))
+                            .cursor_pointer()
+                            .hover(|s| s.bg(theme.colors.element_hover))
+                            .on_mouse_down(
+                                MouseButton::Left,
+                                cx.listener(|this, e: &gpui::MouseDownEvent, _window, cx| {
+                                    this.show_snippet_menu(e.position, cx);
+                                }),
+                            )
+                            .child(
+                                Icon::new(IconName::Code)
+                                    .size(IconSize::Small)
+                                    .color(theme.colors.text_muted),
+                            )
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(theme.colors.text_muted)
+                                    .child("Snippets"),
+                            ),
                     )
-                    .into_any_element()
+            })
+            // Row-limit notice with a way to fetch everything
+            .when_some(row_limit_applied.filter(|_| !read_only), |toolbar, limit| {
+                toolbar.child(
+                    div()
+                        .flex()
+                        .items_center()
+                        .gap(px(6.0))
+                        .child(
+                            div()
+                                .text_size(px(11.0))
+                                .text_color(theme.colors.warning)
+                                .child(format!("Limited to {limit} rows")),
+                        )
+                        .child(
+                            div()
+                                .id("run-without-limit-button")
+                                .px(px(8.0))
+                                .py(px(2.0))
+                                .rounded(px(4.0))
+                                .text_size(px(11.0))
+                                .text_color(theme.colors.accent)
+                                .cursor_pointer()
+                                .hover(|s| s.bg(theme.colors.element_hover))
+                                .on_click(cx.listener(|this, _, _, cx| {
+                                    this.execute_query_without_limit(cx);
+                                }))
+                                .child("Run without limit"),
+                        ),
+                )
             })
             // Connection status indicator
             .child(div().flex_1().flex().justify_end().child(
-                div().text_size(px(11.0)).text_color(theme.colors.text_muted).child(
-                    if has_connection {
-                        "Connected".to_string()
-                    } else {
-                        "Not connected".to_string()
-                    },
-                ),
+                div().text_size(px(11.0)).text_color(theme.colors.text_muted).child(if read_only {
+                    "Read-only".to_string()
+                } else if has_connection {
+                    "Connected".to_string()
+                } else {
+                    "Not connected".to_string()
+                }),
             ))
     }
 
@@ -480,7 +1110,9 @@ impl QueryEditor {
                 .child(self.sql_input.clone())
                 // Help text
                 .child(div().text_color(theme.colors.text_muted).text_size(px(11.0)).child(
-                    if cfg!(target_os = "macos") {
+                    if self.read_only {
+                        "Definition is read-only; copy it into a query tab to edit"
+                    } else if cfg!(target_os = "macos") {
                         "Press Cmd+Enter to execute, or click Execute button"
                     } else {
                         "Press Ctrl+Enter to execute, or click Execute button"
@@ -496,6 +1128,8 @@ impl Focusable for QueryEditor {
     }
 }
 
+impl EventEmitter<QueryEditorEvent> for QueryEditor {}
+
 impl Render for QueryEditor {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.global::<TuskTheme>().clone();
@@ -506,10 +1140,19 @@ impl Render for QueryEditor {
             .track_focus(&self.focus_handle)
             .on_action(cx.listener(Self::on_run_query))
             .on_action(cx.listener(Self::on_cancel_query))
+            .on_action(cx.listener(Self::on_toggle_line_comment))
+            .on_action(cx.listener(Self::on_duplicate_line))
+            .on_action(cx.listener(Self::on_move_line_up))
+            .on_action(cx.listener(Self::on_move_line_down))
+            .relative()
             .size_full()
             .flex()
             .flex_col()
             .child(self.render_toolbar(&theme, cx))
             .child(self.render_content(&theme))
+            // Confirmation for guarded queries
+            .when_some(self.confirm_dialog.clone(), |el, dialog| {
+                el.child(deferred(div().absolute().inset_0().child(dialog)).with_priority(1))
+            })
     }
 }
@@ -0,0 +1,328 @@
+//! Quick-open palette for jumping to a schema object.
+//!
+//! Lists the tables, views, functions, and columns of the loaded schema,
+//! fuzzy-matched against what's typed. Choosing a result emits
+//! [`QuickOpenEvent::Selected`], which the workspace reveals in the schema
+//! browser tree.
+
+use std::sync::Arc;
+
+use gpui::{
+    div, prelude::*, px, App, Context, Entity, EventEmitter, FocusHandle, MouseButton, Render,
+    Subscription, Task, Window,
+};
+use tusk_core::models::object_index::{ObjectEntry, ObjectIndex};
+use tusk_core::models::schema::{DatabaseSchema, SchemaObjectKind, SearchHit};
+
+use crate::icon::{Icon, IconName, IconSize};
+use crate::key_bindings::modal;
+use crate::layout::spacing;
+use crate::panel::Focusable;
+use crate::text_input::{self, TextInput, TextInputEvent};
+use crate::TuskTheme;
+
+/// Most results listed at once.
+const MAX_RESULTS: usize = 50;
+
+/// Events emitted by the quick-open palette.
+#[derive(Debug, Clone)]
+pub enum QuickOpenEvent {
+    /// User chose an object to reveal.
+    Selected(SearchHit),
+    /// User closed the palette without choosing.
+    Dismissed,
+}
+
+/// Overlay for finding a schema object by name.
+pub struct QuickOpen {
+    focus_handle: FocusHandle,
+    input: Entity<TextInput>,
+    /// Built in the background when the palette opens; None until ready.
+    index: Option<ObjectIndex>,
+    query: String,
+    results: Vec<ObjectEntry>,
+    /// Highlighted row in `results`.
+    selected: usize,
+    _build_task: Task<()>,
+    _input_subscription: Subscription,
+}
+
+impl QuickOpen {
+    /// Create a palette over `schema`, indexing it off the UI thread.
+    pub fn new(schema: Arc<DatabaseSchema>, cx: &mut Context<Self>) -> Self {
+        let input = cx.new(|cx| TextInput::new("Go to table, view, function, or column", cx));
+        let input_subscription = cx.subscribe(&input, Self::handle_input_event);
+
+        let build_task = cx.spawn(async move |this, cx| {
+            let index =
+                cx.background_executor().spawn(async move { ObjectIndex::build(&schema) }).await;
+            let _ = this.update(cx, |palette, cx| {
+                tracing::debug!(objects = index.len(), "Quick open index built");
+                palette.index = Some(index);
+                palette.refresh(cx);
+            });
+        });
+
+        Self {
+            focus_handle: cx.focus_handle(),
+            input,
+            index: None,
+            query: String::new(),
+            results: Vec::new(),
+            selected: 0,
+            _build_task: build_task,
+            _input_subscription: input_subscription,
+        }
+    }
+
+    fn handle_input_event(
+        &mut self,
+        _input: Entity<TextInput>,
+        event: &TextInputEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            TextInputEvent::Changed(text) => {
+                self.query = text.clone();
+                self.refresh(cx);
+            }
+            TextInputEvent::Submitted(_) => self.confirm(cx),
+            TextInputEvent::Focus | TextInputEvent::Blur => {}
+        }
+    }
+
+    /// Re-rank results for the current query.
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        if let Some(index) = &mut self.index {
+            self.results = index.search(&self.query, MAX_RESULTS).into_iter().cloned().collect();
+        }
+        self.selected = 0;
+        cx.notify();
+    }
+
+    /// Move the highlight down, wrapping to the top.
+    pub fn select_next(&mut self, cx: &mut Context<Self>) {
+        if !self.results.is_empty() {
+            self.selected = (self.selected + 1) % self.results.len();
+            cx.notify();
+        }
+    }
+
+    /// Move the highlight up, wrapping to the bottom.
+    pub fn select_previous(&mut self, cx: &mut Context<Self>) {
+        if !self.results.is_empty() {
+            self.selected = self.selected.checked_sub(1).unwrap_or(self.results.len() - 1);
+            cx.notify();
+        }
+    }
+
+    /// Choose the highlighted result.
+    pub fn confirm(&mut self, cx: &mut Context<Self>) {
+        if let Some(entry) = self.results.get(self.selected) {
+            cx.emit(QuickOpenEvent::Selected(entry.to_hit()));
+        }
+    }
+
+    /// Close without choosing.
+    pub fn dismiss(&mut self, cx: &mut Context<Self>) {
+        cx.emit(QuickOpenEvent::Dismissed);
+    }
+
+    fn status_text(&self) -> Option<String> {
+        match &self.index {
+            None => Some("Indexing schema…".to_string()),
+            Some(index) if index.is_empty() => Some("No objects in this schema".to_string()),
+            Some(_) if self.query.trim().is_empty() => None,
+            Some(_) if self.results.is_empty() => Some("No matching objects".to_string()),
+            Some(_) => None,
+        }
+    }
+}
+
+/// Icon for a result row.
+fn entry_icon(entry: &ObjectEntry) -> IconName {
+    match (&entry.column, entry.kind) {
+        (Some(_), _) => IconName::Column,
+        (None, SchemaObjectKind::Table) => IconName::Table,
+        (None, SchemaObjectKind::View) => IconName::View,
+        (None, SchemaObjectKind::Function) => IconName::Function,
+    }
+}
+
+/// Where a result lives, shown after its name (e.g. `public.users`).
+fn entry_location(entry: &ObjectEntry) -> String {
+    match &entry.column {
+        Some(_) => format!("{}.{}", entry.schema, entry.object),
+        None => entry.schema.clone(),
+    }
+}
+
+impl EventEmitter<QuickOpenEvent> for QuickOpen {}
+
+impl Focusable for QuickOpen {
+    /// Focus goes to the query input so typing starts right away.
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        gpui::Focusable::focus_handle(self.input.read(cx), cx)
+    }
+}
+
+impl Render for QuickOpen {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>();
+
+        div()
+            .id("quick-open-backdrop")
+            .size_full()
+            .absolute()
+            .inset_0()
+            .flex()
+            .justify_center()
+            .pt(px(80.0))
+            .bg(gpui::black().opacity(0.3))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _, _window, cx| {
+                    this.dismiss(cx);
+                }),
+            )
+            .child(
+                div()
+                    .id("quick-open")
+                    .key_context("Modal")
+                    .track_focus(&self.focus_handle)
+                    .w(px(520.0))
+                    .max_h(px(420.0))
+                    .bg(theme.colors.elevated_surface_background)
+                    .border_1()
+                    .border_color(theme.colors.border)
+                    .rounded(px(8.0))
+                    .shadow_lg()
+                    .flex()
+                    .flex_col()
+                    .overflow_hidden()
+                    .on_mouse_down(MouseButton::Left, |_, _, cx| {
+                        cx.stop_propagation();
+                    })
+                    .on_action(cx.listener(|this, _: &modal::Dismiss, _window, cx| {
+                        this.dismiss(cx);
+                    }))
+                    // Up and Down move through results instead of input history
+                    .capture_action(cx.listener(|this, _: &text_input::Up, _window, cx| {
+                        this.select_previous(cx);
+                    }))
+                    .capture_action(cx.listener(|this, _: &text_input::Down, _window, cx| {
+                        this.select_next(cx);
+                    }))
+                    .child(
+                        div()
+                            .p(spacing::SM)
+                            .border_b_1()
+                            .border_color(theme.colors.border)
+                            .child(self.input.clone()),
+                    )
+                    .when_some(self.status_text(), |d, status| {
+                        d.child(
+                            div()
+                                .px(spacing::SM)
+                                .py(spacing::XS)
+                                .text_size(px(12.0))
+                                .text_color(theme.colors.text_muted)
+                                .child(status),
+                        )
+                    })
+                    .child(
+                        div()
+                            .id("quick-open-results")
+                            .flex()
+                            .flex_col()
+                            .overflow_y_scroll()
+                            .py(spacing::XS)
+                            .children(self.results.iter().enumerate().map(|(ix, entry)| {
+                                let selected = ix == self.selected;
+                                div()
+                                    .id(("quick-open-result", ix))
+                                    .flex()
+                                    .items_center()
+                                    .gap(spacing::XS)
+                                    .px(spacing::SM)
+                                    .py(px(3.0))
+                                    .cursor_pointer()
+                                    .when(selected, |d| {
+                                        d.bg(theme.colors.list_active_selection_background)
+                                    })
+                                    .hover(|s| s.bg(theme.colors.list_hover_background))
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        this.selected = ix;
+                                        this.confirm(cx);
+                                    }))
+                                    .child(
+                                        Icon::new(entry_icon(entry))
+                                            .size(IconSize::Small)
+                                            .color(theme.colors.text_muted),
+                                    )
+                                    .child(
+                                        div()
+                                            .text_size(px(13.0))
+                                            .text_color(theme.colors.text)
+                                            .child(entry.name().to_string()),
+                                    )
+                                    .child(
+                                        div()
+                                            .flex_1()
+                                            .overflow_hidden()
+                                            .text_size(px(11.0))
+                                            .text_color(theme.colors.text_muted)
+                                            .child(entry_location(entry)),
+                                    )
+                            })),
+                    ),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tusk_core::models::schema::{ColumnDetail, FunctionInfo};
+
+    #[test]
+    fn test_result_rows_show_kind_and_location() {
+        let mut schema = DatabaseSchema {
+            functions: vec![FunctionInfo {
+                schema: "public".to_string(),
+                name: "order_total".to_string(),
+                return_type: "numeric".to_string(),
+                arguments: "integer".to_string(),
+                volatility: "STABLE".to_string(),
+                comment: None,
+            }],
+            ..Default::default()
+        };
+        schema.table_columns.insert(
+            ("sales".to_string(), "orders".to_string()),
+            vec![ColumnDetail {
+                name: "order_id".to_string(),
+                data_type: "bigint".to_string(),
+                is_nullable: false,
+                is_primary_key: true,
+                default_value: None,
+                ordinal_position: 1,
+                comment: None,
+            }],
+        );
+        let mut index = ObjectIndex::build(&schema);
+        let results = index.search("order", MAX_RESULTS);
+
+        let rows: Vec<_> = results
+            .iter()
+            .map(|entry| (entry_icon(entry), entry.name().to_string(), entry_location(entry)))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                (IconName::Function, "order_total(integer)".to_string(), "public".to_string()),
+                (IconName::Column, "order_id".to_string(), "sales.orders".to_string()),
+            ]
+        );
+    }
+}
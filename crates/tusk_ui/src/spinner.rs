@@ -1,7 +1,12 @@
 //! Spinner component for loading indicators.
+//!
+//! Spinners are indeterminate by default. [`Spinner::progress`] creates a
+//! determinate spinner that draws a progress arc for operations with known
+//! progress, optionally with a percentage label.
 
 use gpui::{
-    div, prelude::*, px, Animation, AnimationExt, App, IntoElement, Pixels, RenderOnce, Window,
+    canvas, div, point, prelude::*, px, Animation, AnimationExt, App, Bounds, Hsla, IntoElement,
+    PathBuilder, Pixels, RenderOnce, Window,
 };
 use std::time::Duration;
 
@@ -39,16 +44,36 @@ impl SpinnerSize {
     }
 }
 
-/// A loading spinner component with continuous rotation animation.
+/// A loading spinner: a rotating arc, or a progress arc when progress is known.
 #[derive(IntoElement)]
 pub struct Spinner {
     size: SpinnerSize,
+    /// Completed fraction (0–1) for a determinate spinner.
+    progress: Option<f32>,
+    /// Whether a determinate spinner shows its percentage.
+    show_percentage: bool,
 }
 
 impl Spinner {
-    /// Create a new spinner with default size.
+    /// Create a new indeterminate spinner with default size.
     pub fn new() -> Self {
-        Self { size: SpinnerSize::default() }
+        Self { size: SpinnerSize::default(), progress: None, show_percentage: false }
+    }
+
+    /// Create a determinate spinner showing `fraction` complete (clamped to 0–1).
+    pub fn progress(fraction: f32) -> Self {
+        Self { progress: Some(clamp_fraction(fraction)), ..Self::new() }
+    }
+
+    /// Show the percentage next to a determinate spinner.
+    pub fn show_percentage(mut self, show: bool) -> Self {
+        self.show_percentage = show;
+        self
+    }
+
+    /// Get the completed fraction, or None for an indeterminate spinner.
+    pub fn fraction(&self) -> Option<f32> {
+        self.progress
     }
 
     /// Set the spinner size.
@@ -79,12 +104,97 @@ impl Default for Spinner {
     }
 }
 
+/// Clamp a progress fraction to 0–1, treating NaN as no progress.
+pub fn clamp_fraction(fraction: f32) -> f32 {
+    if fraction.is_nan() {
+        0.0
+    } else {
+        fraction.clamp(0.0, 1.0)
+    }
+}
+
+/// Sweep angle of the progress arc in degrees.
+pub fn sweep_angle(fraction: f32) -> f32 {
+    clamp_fraction(fraction) * 360.0
+}
+
+/// Points along a clockwise arc starting at 12 o'clock, relative to its center.
+///
+/// Screen coordinates grow downward, so 12 o'clock is `(0, -radius)`.
+fn arc_points(radius: f32, sweep_degrees: f32) -> Vec<(f32, f32)> {
+    let segments = ((sweep_degrees / 10.0).ceil() as usize).max(1);
+    (0..=segments)
+        .map(|i| {
+            let angle = (sweep_degrees * i as f32 / segments as f32).to_radians();
+            (radius * angle.sin(), -radius * angle.cos())
+        })
+        .collect()
+}
+
+/// Stroke an arc centered in `bounds`, inset so the stroke stays inside.
+fn paint_arc(
+    bounds: Bounds<Pixels>,
+    stroke: f32,
+    sweep_degrees: f32,
+    color: Hsla,
+    window: &mut Window,
+) {
+    if sweep_degrees <= 0.0 {
+        return;
+    }
+    let center = bounds.center();
+    let radius = (f32::from(bounds.size.width) - stroke) / 2.0;
+
+    let mut builder = PathBuilder::stroke(px(stroke));
+    for (i, (x, y)) in arc_points(radius, sweep_degrees).into_iter().enumerate() {
+        let position = point(center.x + px(x), center.y + px(y));
+        if i == 0 {
+            builder.move_to(position);
+        } else {
+            builder.line_to(position);
+        }
+    }
+    if let Ok(path) = builder.build() {
+        window.paint_path(path, color);
+    }
+}
+
 impl RenderOnce for Spinner {
     fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let theme = cx.global::<TuskTheme>();
         let size = self.size.pixels();
         let border_width = self.size.border_width();
 
+        if let Some(fraction) = self.progress {
+            let stroke = f32::from(border_width);
+            let track = theme.colors.border;
+            let accent = theme.colors.accent;
+
+            return div()
+                .flex()
+                .items_center()
+                .gap(px(6.0))
+                .child(
+                    canvas(
+                        |_, _, _| {},
+                        move |bounds, _, window, _| {
+                            paint_arc(bounds, stroke, 360.0, track, window);
+                            paint_arc(bounds, stroke, sweep_angle(fraction), accent, window);
+                        },
+                    )
+                    .size(size),
+                )
+                .when(self.show_percentage, |d| {
+                    d.child(
+                        div()
+                            .text_size(px(11.0))
+                            .text_color(theme.colors.text_muted)
+                            .child(format!("{}%", (fraction * 100.0).round() as u32)),
+                    )
+                })
+                .into_any_element();
+        }
+
         div()
             .size(size)
             .rounded_full()
@@ -104,6 +214,7 @@ impl RenderOnce for Spinner {
                         move |element, _progress| element.occlude(),
                     ),
             )
+            .into_any_element()
     }
 }
 
@@ -128,5 +239,32 @@ mod tests {
 
         let large = Spinner::large();
         assert_eq!(large.size, SpinnerSize::Large);
+        assert_eq!(large.fraction(), None);
+    }
+
+    #[test]
+    fn test_progress_fraction_is_clamped() {
+        assert_eq!(Spinner::progress(0.25).fraction(), Some(0.25));
+        assert_eq!(Spinner::progress(-0.5).fraction(), Some(0.0));
+        assert_eq!(Spinner::progress(1.5).fraction(), Some(1.0));
+        assert_eq!(Spinner::progress(f32::NAN).fraction(), Some(0.0));
+        assert_eq!(Spinner::progress(0.5).size(SpinnerSize::Small).fraction(), Some(0.5));
+    }
+
+    #[test]
+    fn test_sweep_angle() {
+        assert_eq!(sweep_angle(0.0), 0.0);
+        assert_eq!(sweep_angle(0.25), 90.0);
+        assert_eq!(sweep_angle(1.0), 360.0);
+        assert_eq!(sweep_angle(2.0), 360.0);
+    }
+
+    #[test]
+    fn test_arc_points_run_clockwise_from_top() {
+        let points = arc_points(10.0, 90.0);
+        let (start, end) = (points[0], points[points.len() - 1]);
+        assert!(start.0.abs() < 1e-4 && (start.1 + 10.0).abs() < 1e-4, "{:?}", start);
+        assert!((end.0 - 10.0).abs() < 1e-4 && end.1.abs() < 1e-4, "{:?}", end);
+        assert!(points.iter().all(|(x, y)| ((x * x + y * y).sqrt() - 10.0).abs() < 1e-3));
     }
 }
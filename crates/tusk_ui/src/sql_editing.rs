@@ -0,0 +1,513 @@
+//! Editing helpers for SQL text in the query editor.
+//!
+//! Everything here works on plain strings and byte offsets so it can be
+//! tested without a window:
+//! - Literal and comment spans, so brackets inside them are ignored
+//! - Matching-bracket lookup for the bracket at the cursor
+//! - Auto-closing of `(` and `'`, and typing over the inserted closer
+//! - Toggling `--` comments on the selected lines
+//! - Duplicating and moving the selected lines
+
+use std::ops::Range;
+
+use tusk_core::sql_lex::{
+    dollar_tag, has_escape_prefix, skip_block_comment, skip_dollar_quoted, skip_line_comment,
+    skip_quoted,
+};
+
+/// A string, quoted identifier, dollar-quoted body, or comment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Span {
+    range: Range<usize>,
+    /// Whether the closing delimiter was found before the end of the text.
+    closed: bool,
+}
+
+/// Find the literal and comment spans in `text`, in order.
+fn literal_spans(text: &str) -> Vec<Span> {
+    let bytes = text.as_bytes();
+    let mut spans = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let end = match bytes[i] {
+            b'\'' => {
+                // E'...' strings allow backslash escapes
+                skip_quoted(bytes, i, b'\'', has_escape_prefix(bytes, i))
+            }
+            b'"' => skip_quoted(bytes, i, b'"', false),
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                let end = skip_line_comment(bytes, i);
+                spans.push(Span { range: start..end, closed: end < bytes.len() });
+                i = end;
+                continue;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => skip_block_comment(bytes, i),
+            b'$' => match dollar_tag(bytes, i) {
+                Some(tag) => skip_dollar_quoted(bytes, i, tag),
+                None => {
+                    i += 1;
+                    continue;
+                }
+            },
+            _ => {
+                i += 1;
+                continue;
+            }
+        };
+        spans.push(Span { range: start..end.unwrap_or(bytes.len()), closed: end.is_some() });
+        i = end.unwrap_or(bytes.len());
+    }
+
+    spans
+}
+
+/// Whether a cursor at `offset` is inside a string, quoted identifier,
+/// dollar-quoted body, or comment.
+///
+/// A cursor right after an unclosed opening quote counts as inside.
+pub fn in_literal(text: &str, offset: usize) -> bool {
+    literal_spans(text).iter().any(|span| {
+        span.range.start < offset
+            && (offset < span.range.end || (offset == span.range.end && !span.closed))
+    })
+}
+
+/// The partner of a bracket byte, and whether the bracket opens.
+fn bracket_partner(c: u8) -> Option<(u8, bool)> {
+    match c {
+        b'(' => Some((b')', true)),
+        b'[' => Some((b']', true)),
+        b')' => Some((b'(', false)),
+        b']' => Some((b'[', false)),
+        _ => None,
+    }
+}
+
+/// Find the bracket matching the one at byte `offset`.
+///
+/// Returns None if `offset` isn't a bracket outside literals and comments,
+/// or if the bracket is unbalanced. Brackets inside literals and comments
+/// are skipped while scanning.
+pub fn matching_bracket(text: &str, offset: usize) -> Option<usize> {
+    let bytes = text.as_bytes();
+    let bracket = *bytes.get(offset)?;
+    let (partner, forward) = bracket_partner(bracket)?;
+    let spans = literal_spans(text);
+    let in_span = |i: usize| spans.iter().any(|span| span.range.contains(&i));
+    if in_span(offset) {
+        return None;
+    }
+
+    let mut depth = 0usize;
+    let mut scan = |i: usize| -> Option<Option<usize>> {
+        if in_span(i) {
+            return None;
+        }
+        if bytes[i] == bracket {
+            depth += 1;
+        } else if bytes[i] == partner {
+            if depth == 0 {
+                return Some(Some(i));
+            }
+            depth -= 1;
+        }
+        None
+    };
+
+    if forward {
+        (offset + 1..bytes.len()).find_map(&mut scan).flatten()
+    } else {
+        (0..offset).rev().find_map(&mut scan).flatten()
+    }
+}
+
+/// The bracket pair to highlight for a cursor at `cursor`.
+///
+/// Checks the bracket after the cursor first, then the one before it.
+/// Returns the offsets of both brackets.
+pub fn bracket_pair_at(text: &str, cursor: usize) -> Option<(usize, usize)> {
+    [Some(cursor), cursor.checked_sub(1)]
+        .into_iter()
+        .flatten()
+        .find_map(|offset| matching_bracket(text, offset).map(|partner| (offset, partner)))
+}
+
+/// How a typed character should change the text when pairs are auto-closed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PairEdit {
+    /// Text inserted at the cursor in place of the typed character.
+    pub insert: &'static str,
+    /// Cursor offset after the edit.
+    pub cursor: usize,
+}
+
+/// Auto-closing of `(` and `'`, remembering which closers it inserted.
+///
+/// Typing a closer right before one that was auto-inserted moves over it
+/// instead of adding another. Closers typed by hand are never skipped.
+#[derive(Debug, Clone, Default)]
+pub struct AutoPairs {
+    /// Offsets of auto-inserted closers still in the text.
+    closers: Vec<usize>,
+}
+
+impl AutoPairs {
+    /// Decide how typing `typed` at `cursor` edits `text`.
+    ///
+    /// Returns None when the character should be inserted as typed. The
+    /// caller applies the returned edit and then must not call `edited`.
+    pub fn typed(&mut self, text: &str, cursor: usize, typed: &str) -> Option<PairEdit> {
+        if matches!(typed, ")" | "'") && text[cursor..].starts_with(typed) {
+            if let Some(index) = self.closers.iter().position(|&closer| closer == cursor) {
+                self.closers.remove(index);
+                return Some(PairEdit { insert: "", cursor: cursor + 1 });
+            }
+        }
+
+        let insert = match typed {
+            "(" => "()",
+            "'" => "''",
+            _ => return None,
+        };
+        if in_literal(text, cursor) || !Self::can_pair(text, cursor, typed) {
+            return None;
+        }
+
+        self.edited(cursor..cursor, insert.len());
+        self.closers.push(cursor + 1);
+        Some(PairEdit { insert, cursor: cursor + 1 })
+    }
+
+    /// Only pair before whitespace, closers, or the end, and never pair a
+    /// quote right after a word (as in `it's`).
+    fn can_pair(text: &str, cursor: usize, typed: &str) -> bool {
+        let next_ok = !matches!(
+            text[cursor..].chars().next(),
+            Some(c) if !(c.is_whitespace() || ",;)]".contains(c))
+        );
+        let prev_word =
+            text[..cursor].chars().next_back().is_some_and(|c| c.is_alphanumeric() || c == '_');
+        next_ok && !(typed == "'" && prev_word)
+    }
+
+    /// Record that `range` was replaced by `inserted_len` bytes.
+    pub fn edited(&mut self, range: Range<usize>, inserted_len: usize) {
+        self.closers.retain(|closer| !range.contains(closer));
+        for closer in &mut self.closers {
+            if *closer >= range.end {
+                *closer = *closer - range.len() + inserted_len;
+            }
+        }
+    }
+
+    /// Forget all auto-inserted closers, e.g. after the text is replaced.
+    pub fn clear(&mut self) {
+        self.closers.clear();
+    }
+}
+
+/// Prefix added by [`toggle_line_comment`].
+const LINE_COMMENT: &str = "-- ";
+
+/// Byte range of the whole lines touched by `selection`, without the
+/// trailing newline.
+///
+/// A selection ending at the very start of a line doesn't include that line.
+pub fn selected_lines(text: &str, selection: Range<usize>) -> Range<usize> {
+    let mut end = selection.end;
+    if selection.end > selection.start && text[..end].ends_with('\n') {
+        end -= 1;
+    }
+    let start = text[..selection.start].rfind('\n').map_or(0, |i| i + 1);
+    let end = text[end..].find('\n').map_or(text.len(), |i| end + i);
+    start..end.max(start)
+}
+
+/// Comment or uncomment the lines touched by `selection` with `-- `.
+///
+/// The first line decides: if it is commented, commented lines are
+/// uncommented; otherwise every non-blank line is commented. Comments go
+/// after each line's indentation. Returns the new text and selection.
+pub fn toggle_line_comment(text: &str, selection: Range<usize>) -> (String, Range<usize>) {
+    let lines = selected_lines(text, selection.clone());
+    let mut offset = lines.start;
+    let mut line_edits = Vec::new();
+    let mut uncomment = None;
+
+    for line in text[lines.clone()].split('\n') {
+        let indent = line.len() - line.trim_start().len();
+        let body = &line[indent..];
+        let commented = body.starts_with("--");
+        let uncomment = *uncomment.get_or_insert(commented);
+
+        if uncomment && commented {
+            let removed = if body.starts_with(LINE_COMMENT) { LINE_COMMENT.len() } else { 2 };
+            line_edits.push((offset + indent, removed, ""));
+        } else if !uncomment && !body.is_empty() {
+            line_edits.push((offset + indent, 0, LINE_COMMENT));
+        }
+        offset += line.len() + 1;
+    }
+
+    let mut result = String::with_capacity(text.len() + line_edits.len() * LINE_COMMENT.len());
+    let mut copied = 0;
+    for &(at, removed, inserted) in &line_edits {
+        result.push_str(&text[copied..at]);
+        result.push_str(inserted);
+        copied = at + removed;
+    }
+    result.push_str(&text[copied..]);
+
+    // Shift offsets past each edit; a selection's start stays before text
+    // inserted right at it, so the new comment is selected too
+    let map = |offset: usize, before_insert: bool| {
+        line_edits.iter().fold(offset, |mapped, &(at, removed, inserted)| {
+            if offset > at || (offset == at && !before_insert) {
+                let kept = offset.saturating_sub(at).min(removed);
+                mapped - kept + inserted.len()
+            } else {
+                mapped
+            }
+        })
+    };
+    let selection = if selection.is_empty() {
+        let cursor = map(selection.start, false);
+        cursor..cursor
+    } else {
+        map(selection.start, true)..map(selection.end, false)
+    };
+
+    (result, selection)
+}
+
+/// Insert a copy of the lines touched by `selection` below them.
+///
+/// The selection moves onto the copy. Returns the new text and selection.
+pub fn duplicate_lines(text: &str, selection: Range<usize>) -> (String, Range<usize>) {
+    let lines = selected_lines(text, selection.clone());
+    let block = &text[lines.clone()];
+    let result = format!("{}\n{}{}", &text[..lines.end], block, &text[lines.end..]);
+    let shift = block.len() + 1;
+    (result, selection.start + shift..selection.end + shift)
+}
+
+/// Swap the lines touched by `selection` with the line above or below.
+///
+/// The selection moves with the lines. Returns None when there is no line
+/// to swap with.
+pub fn move_lines(text: &str, selection: Range<usize>, up: bool) -> Option<(String, Range<usize>)> {
+    let lines = selected_lines(text, selection.clone());
+    let block = &text[lines.clone()];
+
+    if up {
+        let above_end = lines.start.checked_sub(1)?;
+        let above_start = text[..above_end].rfind('\n').map_or(0, |i| i + 1);
+        let above = &text[above_start..above_end];
+        let result = format!("{}{}\n{}{}", &text[..above_start], block, above, &text[lines.end..]);
+        let shift = above.len() + 1;
+        Some((result, selection.start - shift..selection.end - shift))
+    } else {
+        if lines.end >= text.len() {
+            return None;
+        }
+        let below_start = lines.end + 1;
+        let below_end = text[below_start..].find('\n').map_or(text.len(), |i| below_start + i);
+        let below = &text[below_start..below_end];
+        let result = format!("{}{}\n{}{}", &text[..lines.start], below, block, &text[below_end..]);
+        let shift = below.len() + 1;
+        Some((result, selection.start + shift..selection.end + shift))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Apply typed characters at the end of `text`, as the input would.
+    fn type_chars(pairs: &mut AutoPairs, text: &mut String, cursor: &mut usize, chars: &str) {
+        for c in chars.chars() {
+            let typed = c.to_string();
+            match pairs.typed(text, *cursor, &typed) {
+                Some(edit) => {
+                    text.insert_str(*cursor, edit.insert);
+                    *cursor = edit.cursor;
+                }
+                None => {
+                    pairs.edited(*cursor..*cursor, typed.len());
+                    text.insert_str(*cursor, &typed);
+                    *cursor += typed.len();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_matching_bracket_skips_literals() {
+        let sql = "SELECT coalesce(name, ')') FROM t";
+        assert_eq!(matching_bracket(sql, 15), Some(25));
+        assert_eq!(matching_bracket(sql, 25), Some(15));
+        // The paren inside the string isn't a bracket at all
+        assert_eq!(matching_bracket(sql, 23), None);
+
+        let sql = "f(a /* ( */, \"col)\", $$)$$, -- )\n b)";
+        assert_eq!(matching_bracket(sql, 1), Some(sql.len() - 1));
+
+        let sql = "count(a, (b + c))";
+        assert_eq!(matching_bracket(sql, 5), Some(16));
+        assert_eq!(matching_bracket(sql, 9), Some(15));
+
+        // Unbalanced and non-bracket offsets
+        assert_eq!(matching_bracket("max(a", 3), None);
+        assert_eq!(matching_bracket("max(a)", 0), None);
+        assert_eq!(matching_bracket("E'\\')' )", 7), None);
+    }
+
+    #[test]
+    fn test_bracket_pair_at_cursor() {
+        let sql = "sum(x)";
+        assert_eq!(bracket_pair_at(sql, 3), Some((3, 5)));
+        // Just after the closing bracket
+        assert_eq!(bracket_pair_at(sql, 6), Some((5, 3)));
+        assert_eq!(bracket_pair_at(sql, 1), None);
+    }
+
+    #[test]
+    fn test_in_literal() {
+        let sql = "SELECT 'a(b' -- c\n, 'open";
+        assert!(!in_literal(sql, 7));
+        assert!(in_literal(sql, 9));
+        assert!(!in_literal(sql, 12));
+        assert!(in_literal(sql, 16));
+        assert!(in_literal(sql, sql.len()));
+    }
+
+    #[test]
+    fn test_auto_close_and_type_over() {
+        let mut pairs = AutoPairs::default();
+        let mut text = String::from("SELECT ");
+        let mut cursor = text.len();
+
+        type_chars(&mut pairs, &mut text, &mut cursor, "count(");
+        assert_eq!(text, "SELECT count()");
+        assert_eq!(cursor, 13);
+
+        type_chars(&mut pairs, &mut text, &mut cursor, "*)");
+        assert_eq!(text, "SELECT count(*)");
+        assert_eq!(cursor, text.len());
+
+        // A hand-typed closer isn't skipped later
+        type_chars(&mut pairs, &mut text, &mut cursor, ")");
+        assert_eq!(text, "SELECT count(*))");
+    }
+
+    #[test]
+    fn test_auto_close_nested_and_quotes() {
+        let mut pairs = AutoPairs::default();
+        let mut text = String::new();
+        let mut cursor = 0;
+
+        type_chars(&mut pairs, &mut text, &mut cursor, "f(g(x), 'a')");
+        assert_eq!(text, "f(g(x), 'a')");
+        assert_eq!(cursor, text.len());
+
+        // No pairing inside strings, or for an apostrophe after a word
+        let mut text = String::from("'it");
+        let mut cursor = text.len();
+        type_chars(&mut pairs, &mut text, &mut cursor, "(");
+        assert_eq!(text, "'it(");
+        let mut text = String::from("it");
+        let mut cursor = text.len();
+        type_chars(&mut pairs, &mut text, &mut cursor, "'");
+        assert_eq!(text, "it'");
+    }
+
+    #[test]
+    fn test_edits_shift_and_drop_closers() {
+        let mut pairs = AutoPairs { closers: vec![4, 10] };
+        pairs.edited(2..2, 3);
+        assert_eq!(pairs.closers, vec![7, 13]);
+        pairs.edited(6..8, 0);
+        assert_eq!(pairs.closers, vec![11]);
+        pairs.clear();
+        assert!(pairs.closers.is_empty());
+    }
+
+    #[test]
+    fn test_selected_lines() {
+        let text = "one\ntwo\nthree";
+        assert_eq!(selected_lines(text, 5..5), 4..7);
+        assert_eq!(selected_lines(text, 1..9), 0..text.len());
+        // Ending at the start of a line leaves that line out
+        assert_eq!(selected_lines(text, 0..4), 0..3);
+        assert_eq!(selected_lines("", 0..0), 0..0);
+    }
+
+    #[test]
+    fn test_toggle_comment_mixed_selection_comments_all() {
+        let text = "SELECT a,\n  -- b,\n  c\n\nFROM t";
+        let (commented, selection) = toggle_line_comment(text, 0..text.len());
+        assert_eq!(commented, "-- SELECT a,\n  -- -- b,\n  -- c\n\n-- FROM t");
+        assert_eq!(selection, 0..commented.len());
+
+        // Toggling again takes back exactly what was added
+        let (uncommented, _) = toggle_line_comment(&commented, selection);
+        assert_eq!(uncommented, text);
+    }
+
+    #[test]
+    fn test_toggle_comment_uncomments_when_first_line_commented() {
+        let text = "  -- a\n    --b\nc";
+        let (result, _) = toggle_line_comment(text, 0..text.len());
+        assert_eq!(result, "  a\n    b\nc");
+    }
+
+    #[test]
+    fn test_toggle_comment_current_line_keeps_cursor() {
+        let text = "SELECT 1;\n    SELECT 2;";
+        let cursor = text.len() - 2;
+        let (result, selection) = toggle_line_comment(text, cursor..cursor);
+        assert_eq!(result, "SELECT 1;\n    -- SELECT 2;");
+        assert_eq!(&result[selection.start..], "2;");
+
+        let (result, selection) = toggle_line_comment(&result, selection);
+        assert_eq!(result, text);
+        assert_eq!(selection, cursor..cursor);
+    }
+
+    #[test]
+    fn test_duplicate_lines_inserts_copy_below() {
+        let text = "SELECT a\nFROM t\nWHERE b";
+        let (result, selection) = duplicate_lines(text, 11..11);
+        assert_eq!(result, "SELECT a\nFROM t\nFROM t\nWHERE b");
+        assert_eq!(selection, 18..18);
+
+        let (result, selection) = duplicate_lines(text, 2..12);
+        assert_eq!(result, "SELECT a\nFROM t\nSELECT a\nFROM t\nWHERE b");
+        assert_eq!(&result[selection], "LECT a\nFRO");
+
+        // The last line has no trailing newline to copy
+        assert_eq!(duplicate_lines("x", 1..1), ("x\nx".to_string(), 3..3));
+    }
+
+    #[test]
+    fn test_move_lines_swaps_adjacent_lines() {
+        let text = "one\ntwo\nthree";
+        let (result, selection) = move_lines(text, 5..5, true).unwrap();
+        assert_eq!(result, "two\none\nthree");
+        assert_eq!(&result[selection.start..], "wo\none\nthree");
+
+        let (result, selection) = move_lines(text, 5..5, false).unwrap();
+        assert_eq!(result, "one\nthree\ntwo");
+        assert_eq!(&result[selection.start..], "wo");
+
+        // A multi-line selection moves as a block
+        let (result, selection) = move_lines(text, 1..6, false).unwrap();
+        assert_eq!(result, "three\none\ntwo");
+        assert_eq!(&result[selection], "ne\ntw");
+
+        assert_eq!(move_lines(text, 1..1, true), None);
+        assert_eq!(move_lines(text, 10..10, false), None);
+    }
+}
@@ -0,0 +1,117 @@
+//! `.sql` files backing query editor tabs.
+//!
+//! [`SqlFileState`] tracks which file a tab was opened from or saved to and
+//! whether the buffer differs from what is on disk. The workspace mirrors it
+//! onto the tab title and dirty marker, so closing a dirty tab asks first.
+
+use std::path::{Path, PathBuf};
+
+/// File name offered when saving a tab that has no file yet.
+pub const DEFAULT_SQL_FILE_NAME: &str = "query.sql";
+
+/// Directory the save dialog starts in for tabs without a file.
+pub fn default_sql_dir() -> PathBuf {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .unwrap_or_else(std::env::temp_dir)
+}
+
+/// The file behind a query editor tab and its saved/dirty state.
+///
+/// Tabs without a file are scratch buffers and are never dirty.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SqlFileState {
+    /// File the buffer was loaded from or last saved to.
+    path: Option<PathBuf>,
+    /// Buffer contents as of the last load or save.
+    saved_content: String,
+    /// Whether the buffer differs from `saved_content`.
+    dirty: bool,
+}
+
+impl SqlFileState {
+    /// State for a buffer freshly loaded from `path`.
+    pub fn opened(path: PathBuf, content: impl Into<String>) -> Self {
+        Self { path: Some(path), saved_content: content.into(), dirty: false }
+    }
+
+    /// File backing the buffer, if any.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Whether the buffer has unsaved changes.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// Tab title: the file name, if the buffer has a file.
+    pub fn title(&self) -> Option<String> {
+        self.path.as_ref()?.file_name().map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// Record an edit. Returns true if the dirty state changed.
+    ///
+    /// Editing back to the saved contents makes the buffer clean again.
+    pub fn edited(&mut self, content: &str) -> bool {
+        let dirty = self.path.is_some() && content != self.saved_content;
+        let changed = dirty != self.dirty;
+        self.dirty = dirty;
+        changed
+    }
+
+    /// Record a successful save of `content` to `path`.
+    pub fn saved(&mut self, path: PathBuf, content: impl Into<String>) {
+        self.path = Some(path);
+        self.saved_content = content.into();
+        self.dirty = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_then_save_round_trip() {
+        let path = PathBuf::from("/tmp/report.sql");
+        let mut file = SqlFileState::opened(path.clone(), "SELECT 1;");
+        assert_eq!(file.path(), Some(path.as_path()));
+        assert_eq!(file.title().as_deref(), Some("report.sql"));
+        assert!(!file.is_dirty());
+
+        // edit -> dirty
+        assert!(file.edited("SELECT 2;"));
+        assert!(file.is_dirty());
+        // Further edits keep it dirty without reporting a change
+        assert!(!file.edited("SELECT 3;"));
+        assert!(file.is_dirty());
+
+        // save -> clean
+        file.saved(path.clone(), "SELECT 3;");
+        assert!(!file.is_dirty());
+        assert!(!file.edited("SELECT 3;"));
+
+        // Editing back to the saved text is clean again
+        assert!(file.edited("SELECT 4;"));
+        assert!(file.edited("SELECT 3;"));
+        assert!(!file.is_dirty());
+    }
+
+    #[test]
+    fn test_scratch_buffer_is_never_dirty() {
+        let mut file = SqlFileState::default();
+        assert_eq!(file.path(), None);
+        assert_eq!(file.title(), None);
+
+        assert!(!file.edited("SELECT 1;"));
+        assert!(!file.is_dirty());
+
+        // Save As gives the buffer a file and title
+        file.saved(PathBuf::from("/tmp/new.sql"), "SELECT 1;");
+        assert_eq!(file.title().as_deref(), Some("new.sql"));
+        assert!(file.edited("SELECT 1; SELECT 2;"));
+        assert!(file.is_dirty());
+    }
+}
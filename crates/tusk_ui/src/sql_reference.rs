@@ -0,0 +1,346 @@
+//! Built-in reference docs for SQL keywords and PostgreSQL functions.
+//!
+//! Backs the editor's hover tooltips. The data is a static table compiled
+//! into the crate, so lookups need neither a connection nor the network.
+
+use std::ops::Range;
+
+/// What a reference entry describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocKind {
+    /// SQL keyword or clause.
+    Keyword,
+    /// Built-in function.
+    Function,
+}
+
+/// Reference documentation for one keyword or function.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DocEntry {
+    /// Lowercase name as written in SQL.
+    pub name: &'static str,
+    /// Keyword or function.
+    pub kind: DocKind,
+    /// Usage synopsis, e.g. `coalesce(value [, ...])`.
+    pub signature: &'static str,
+    /// One-sentence description.
+    pub summary: &'static str,
+}
+
+impl DocEntry {
+    /// Text shown in the editor tooltip: the signature, then the summary.
+    pub fn tooltip_text(&self) -> String {
+        format!("{}\n{}", self.signature, self.summary)
+    }
+}
+
+const fn keyword(name: &'static str, signature: &'static str, summary: &'static str) -> DocEntry {
+    DocEntry { name, kind: DocKind::Keyword, signature, summary }
+}
+
+const fn function(name: &'static str, signature: &'static str, summary: &'static str) -> DocEntry {
+    DocEntry { name, kind: DocKind::Function, signature, summary }
+}
+
+/// Reference entries, sorted by name for binary search.
+static ENTRIES: &[DocEntry] = &[
+    function("abs", "abs(x)", "Absolute value of a number."),
+    function(
+        "age",
+        "age(timestamp [, timestamp])",
+        "Interval between two timestamps, or since midnight today.",
+    ),
+    function(
+        "array_agg",
+        "array_agg(expression [ORDER BY ...])",
+        "Collects input values, including nulls, into an array.",
+    ),
+    function("avg", "avg(expression)", "Average of all non-null input values."),
+    keyword(
+        "between",
+        "expr BETWEEN low AND high",
+        "True when the value lies in the inclusive range.",
+    ),
+    keyword(
+        "case",
+        "CASE WHEN condition THEN result [...] [ELSE result] END",
+        "Evaluates conditions in order and returns the first matching result.",
+    ),
+    function("cast", "CAST(expression AS type)", "Converts a value to another data type."),
+    function("ceil", "ceil(x)", "Smallest integer not less than the argument."),
+    function(
+        "coalesce",
+        "coalesce(value [, ...])",
+        "Returns the first of its arguments that is not null.",
+    ),
+    function(
+        "concat",
+        "concat(value [, ...])",
+        "Concatenates the text of all arguments, ignoring nulls.",
+    ),
+    function(
+        "count",
+        "count(*) | count(expression)",
+        "Number of input rows, or of rows where the expression is not null.",
+    ),
+    function("current_date", "current_date", "Current date at the start of the transaction."),
+    function(
+        "date_part",
+        "date_part(field, source)",
+        "Extracts a field such as year or hour as double precision.",
+    ),
+    function(
+        "date_trunc",
+        "date_trunc(field, source [, time_zone])",
+        "Truncates a timestamp or interval to the given precision, e.g. 'day'.",
+    ),
+    keyword(
+        "delete",
+        "DELETE FROM table [WHERE condition]",
+        "Removes rows from a table; without WHERE it removes all rows.",
+    ),
+    function("dense_rank", "dense_rank() OVER (...)", "Rank of the current row without gaps."),
+    keyword(
+        "distinct",
+        "SELECT DISTINCT [ON (expression)] ...",
+        "Removes duplicate rows from the result.",
+    ),
+    keyword("exists", "EXISTS (subquery)", "True when the subquery returns at least one row."),
+    keyword(
+        "explain",
+        "EXPLAIN [ANALYZE] statement",
+        "Shows the execution plan; ANALYZE also runs the statement.",
+    ),
+    function(
+        "extract",
+        "extract(field FROM source)",
+        "Extracts a field such as year or epoch from a date, time, or interval.",
+    ),
+    function("floor", "floor(x)", "Largest integer not greater than the argument."),
+    keyword(
+        "from",
+        "FROM from_item [, ...]",
+        "Lists the tables, views, or subqueries a query reads from.",
+    ),
+    function("gen_random_uuid", "gen_random_uuid()", "Generates a random version 4 UUID."),
+    function(
+        "generate_series",
+        "generate_series(start, stop [, step])",
+        "Set-returning function producing a series of values.",
+    ),
+    function("greatest", "greatest(value [, ...])", "Largest of its arguments, ignoring nulls."),
+    keyword(
+        "group",
+        "GROUP BY expression [, ...]",
+        "Combines rows with equal values so aggregates run per group.",
+    ),
+    keyword("having", "HAVING condition", "Filters groups produced by GROUP BY."),
+    keyword("ilike", "expr ILIKE pattern", "Case-insensitive pattern match using % and _."),
+    keyword(
+        "insert",
+        "INSERT INTO table [(columns)] VALUES (...) | query",
+        "Adds new rows to a table.",
+    ),
+    keyword(
+        "join",
+        "from_item [INNER | LEFT | RIGHT | FULL] JOIN from_item ON condition",
+        "Combines rows from two sources that satisfy the join condition.",
+    ),
+    function("json_agg", "json_agg(expression)", "Collects input values into a JSON array."),
+    function("jsonb_agg", "jsonb_agg(expression)", "Collects input values into a jsonb array."),
+    function(
+        "jsonb_build_object",
+        "jsonb_build_object(key, value [, ...])",
+        "Builds a jsonb object from alternating keys and values.",
+    ),
+    function(
+        "lag",
+        "lag(value [, offset [, default]]) OVER (...)",
+        "Value from a row before the current row in the window.",
+    ),
+    keyword(
+        "lateral",
+        "LATERAL (subquery)",
+        "Lets a subquery in FROM refer to columns of preceding items.",
+    ),
+    function(
+        "lead",
+        "lead(value [, offset [, default]]) OVER (...)",
+        "Value from a row after the current row in the window.",
+    ),
+    function("least", "least(value [, ...])", "Smallest of its arguments, ignoring nulls."),
+    function("length", "length(text)", "Number of characters in a string."),
+    keyword(
+        "like",
+        "expr LIKE pattern",
+        "Pattern match where % matches any text and _ one character.",
+    ),
+    keyword("limit", "LIMIT count", "Returns at most this many rows."),
+    function("lower", "lower(text)", "Converts a string to lower case."),
+    function("max", "max(expression)", "Maximum of all non-null input values."),
+    function("min", "min(expression)", "Minimum of all non-null input values."),
+    function("now", "now()", "Current date and time at the start of the transaction."),
+    function(
+        "nullif",
+        "nullif(value1, value2)",
+        "Returns null if the two values are equal, otherwise the first.",
+    ),
+    keyword("offset", "OFFSET start", "Skips this many rows before returning results."),
+    keyword(
+        "order",
+        "ORDER BY expression [ASC | DESC] [NULLS FIRST | LAST]",
+        "Sorts the result rows.",
+    ),
+    function(
+        "pg_sleep",
+        "pg_sleep(seconds)",
+        "Delays the current session for the given number of seconds.",
+    ),
+    function("random", "random()", "Random value in the range 0.0 <= x < 1.0."),
+    function("rank", "rank() OVER (...)", "Rank of the current row with gaps."),
+    function(
+        "regexp_replace",
+        "regexp_replace(source, pattern, replacement [, flags])",
+        "Replaces substrings matching a POSIX regular expression.",
+    ),
+    function("replace", "replace(text, from, to)", "Replaces all occurrences of a substring."),
+    keyword(
+        "returning",
+        "RETURNING expression [, ...]",
+        "Returns values from rows changed by INSERT, UPDATE, or DELETE.",
+    ),
+    function(
+        "round",
+        "round(x [, digits])",
+        "Rounds to the nearest integer or to the given decimal places.",
+    ),
+    function(
+        "row_number",
+        "row_number() OVER (...)",
+        "Number of the current row within its partition, counting from 1.",
+    ),
+    keyword(
+        "select",
+        "SELECT expression [, ...] FROM ...",
+        "Retrieves rows from tables, views, or expressions.",
+    ),
+    function(
+        "split_part",
+        "split_part(text, delimiter, n)",
+        "Splits a string on a delimiter and returns the nth field.",
+    ),
+    function(
+        "string_agg",
+        "string_agg(value, delimiter [ORDER BY ...])",
+        "Concatenates non-null values, separated by the delimiter.",
+    ),
+    function("substring", "substring(text [FROM start] [FOR count])", "Extracts part of a string."),
+    function("sum", "sum(expression)", "Sum of all non-null input values."),
+    function("to_char", "to_char(value, format)", "Formats a number or timestamp as text."),
+    function("to_date", "to_date(text, format)", "Parses text into a date using the format."),
+    function(
+        "to_timestamp",
+        "to_timestamp(text, format) | to_timestamp(epoch)",
+        "Parses text or Unix epoch seconds into a timestamp with time zone.",
+    ),
+    function(
+        "trim",
+        "trim([LEADING | TRAILING | BOTH] [characters] FROM text)",
+        "Removes characters, spaces by default, from the ends of a string.",
+    ),
+    keyword(
+        "truncate",
+        "TRUNCATE [TABLE] name [, ...] [CASCADE]",
+        "Quickly removes all rows from tables.",
+    ),
+    keyword(
+        "union",
+        "query UNION [ALL] query",
+        "Combines results of two queries; without ALL, duplicates are removed.",
+    ),
+    function("unnest", "unnest(array)", "Expands an array into a set of rows."),
+    keyword(
+        "update",
+        "UPDATE table SET column = value [, ...] [WHERE condition]",
+        "Changes values in existing rows; without WHERE it changes all rows.",
+    ),
+    function("upper", "upper(text)", "Converts a string to upper case."),
+    keyword(
+        "vacuum",
+        "VACUUM [FULL] [ANALYZE] [table]",
+        "Reclaims storage from dead rows and optionally updates statistics.",
+    ),
+    keyword("values", "VALUES (expression [, ...]) [, ...]", "Computes a set of literal rows."),
+    keyword("where", "WHERE condition", "Keeps only rows for which the condition is true."),
+    keyword(
+        "with",
+        "WITH name AS (query) [, ...] statement",
+        "Defines common table expressions usable in the main statement.",
+    ),
+];
+
+/// Look up reference docs for a keyword or function name, ignoring case.
+pub fn lookup(word: &str) -> Option<DocEntry> {
+    let word = word.to_ascii_lowercase();
+    ENTRIES.binary_search_by(|entry| entry.name.cmp(word.as_str())).ok().map(|index| ENTRIES[index])
+}
+
+/// Byte range of the identifier containing or ending at `offset` in `text`.
+pub fn word_at(text: &str, offset: usize) -> Option<Range<usize>> {
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let offset = offset.min(text.len());
+    if !text.is_char_boundary(offset) {
+        return None;
+    }
+
+    let start = text[..offset]
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_word(*c))
+        .last()
+        .map_or(offset, |(index, _)| index);
+    let end = text[offset..].find(|c: char| !is_word(c)).map_or(text.len(), |len| offset + len);
+
+    (start < end).then_some(start..end)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_functions_and_keywords() {
+        let coalesce = lookup("coalesce").expect("coalesce is documented");
+        assert_eq!(coalesce.kind, DocKind::Function);
+        assert!(!coalesce.summary.is_empty());
+        assert!(coalesce.signature.starts_with("coalesce("));
+
+        let date_trunc = lookup("DATE_TRUNC").expect("lookup ignores case");
+        assert!(!date_trunc.summary.is_empty());
+        assert!(date_trunc.tooltip_text().contains(date_trunc.summary));
+
+        assert_eq!(lookup("Select").map(|entry| entry.kind), Some(DocKind::Keyword));
+        assert_eq!(lookup("customers"), None);
+        assert_eq!(lookup(""), None);
+    }
+
+    #[test]
+    fn test_entries_are_sorted_and_lowercase() {
+        for pair in ENTRIES.windows(2) {
+            assert!(pair[0].name < pair[1].name, "{} before {}", pair[0].name, pair[1].name);
+        }
+        assert!(ENTRIES.iter().all(|entry| entry.name == entry.name.to_ascii_lowercase()));
+    }
+
+    #[test]
+    fn test_word_at() {
+        let sql = "SELECT coalesce(a, 0) FROM t_1";
+        assert_eq!(word_at(sql, 0), Some(0..6));
+        assert_eq!(word_at(sql, 9), Some(7..15));
+        // At the end of a word, still that word
+        assert_eq!(word_at(sql, 15), Some(7..15));
+        assert_eq!(word_at(sql, sql.len()), Some(27..30));
+        assert_eq!(word_at("a + b", 2), None);
+        assert_eq!(word_at("", 0), None);
+    }
+}
@@ -1,16 +1,28 @@
 //! Status bar component for displaying connection and execution state.
 //!
 //! The status bar sits at the bottom of the workspace and shows:
-//! - Connection status (left side)
+//! - Connection status (left side), with the database and search_path head
+//! - Environment badge for staging and production connections
+//! - Transaction badge while an explicit transaction is open or failed
 //! - Execution state and timing (right side)
+//! - Cancel button while a query is running
+//! - Live elapsed time while a query is running
+//! - Row count and numeric aggregates for the results selection
+
+use std::time::Instant;
 
 use gpui::{div, prelude::*, px, App, IntoElement, RenderOnce, SharedString, Window};
 
+use crate::button::ClickHandler;
 use crate::icon::{Icon, IconName, IconSize};
 use crate::layout::sizes::STATUS_BAR_HEIGHT;
+use crate::panels::SelectionSummary;
 use crate::spinner::{Spinner, SpinnerSize};
 use crate::TuskTheme;
 
+#[cfg(feature = "persistence")]
+use tusk_core::models::{ConnectionConfig, ConnectionEnvironment, TransactionStatus};
+
 /// Connection status for the status bar.
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
 pub enum ConnectionStatus {
@@ -23,6 +35,10 @@ pub enum ConnectionStatus {
         database: SharedString,
         /// Server host.
         host: SharedString,
+        /// First schema on the search_path, if known.
+        schema: Option<SharedString>,
+        /// Environment badge (None for development connections).
+        environment: Option<EnvironmentLabel>,
     },
     /// Currently connecting.
     Connecting,
@@ -30,6 +46,114 @@ pub enum ConnectionStatus {
     Error(SharedString),
 }
 
+/// Environment badge shown next to a connection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvironmentLabel {
+    /// Badge text (e.g. "Prod").
+    pub label: SharedString,
+    /// Whether the connection is production (shown in red).
+    pub production: bool,
+}
+
+impl EnvironmentLabel {
+    /// Badge for a connection's environment; development connections have none.
+    #[cfg(feature = "persistence")]
+    pub fn for_environment(environment: ConnectionEnvironment) -> Option<Self> {
+        match environment {
+            ConnectionEnvironment::Dev => None,
+            _ => Some(Self {
+                label: environment.label().into(),
+                production: environment.is_production(),
+            }),
+        }
+    }
+}
+
+/// Badge shown next to a connection with an open explicit transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionIndicator {
+    /// Statements run inside the transaction.
+    InTransaction,
+    /// A statement failed; the transaction must be rolled back.
+    Failed,
+}
+
+impl TransactionIndicator {
+    /// Badge for a connection's transaction status; none when idle.
+    #[cfg(feature = "persistence")]
+    pub fn for_status(status: TransactionStatus) -> Option<Self> {
+        match status {
+            TransactionStatus::Idle => None,
+            TransactionStatus::InTransaction => Some(Self::InTransaction),
+            TransactionStatus::Failed => Some(Self::Failed),
+        }
+    }
+
+    /// Badge text.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::InTransaction => "In transaction",
+            Self::Failed => "Transaction failed",
+        }
+    }
+}
+
+impl ConnectionStatus {
+    /// Connected status for `config`, showing the head of the search_path.
+    ///
+    /// `search_path` is the value from the last `SET search_path` run on the
+    /// connection, which its pool applies to every session; without one the
+    /// connection's default schema is shown.
+    #[cfg(feature = "persistence")]
+    pub fn connected(config: &ConnectionConfig, search_path: Option<&str>) -> Self {
+        let schema = match search_path {
+            Some(search_path) => search_path_head(search_path),
+            None => config.options.default_schema.clone().filter(|schema| !schema.is_empty()),
+        };
+        Self::Connected {
+            database: config.database.clone().into(),
+            host: config.host.clone().into(),
+            schema: schema.map(Into::into),
+            environment: EnvironmentLabel::for_environment(config.environment),
+        }
+    }
+
+    /// Text shown for this status in the status bar.
+    pub fn summary(&self) -> String {
+        match self {
+            Self::Disconnected => "Not connected".to_string(),
+            Self::Connected { database, host, schema: Some(schema), .. } => {
+                format!("{database}.{schema} @ {host}")
+            }
+            Self::Connected { database, host, schema: None, .. } => format!("{database} @ {host}"),
+            Self::Connecting => "Connecting...".to_string(),
+            Self::Error(msg) => format!("Error: {msg}"),
+        }
+    }
+}
+
+/// First schema on a search_path, skipping `$user`.
+///
+/// Quoted names keep their case; bare names fold to lowercase.
+#[cfg(feature = "persistence")]
+fn search_path_head(search_path: &str) -> Option<String> {
+    search_path
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(|name| {
+            if let Some(quoted) = name.strip_prefix('"').and_then(|n| n.strip_suffix('"')) {
+                quoted.replace("\"\"", "\"")
+            } else if let Some(literal) = name.strip_prefix('\'').and_then(|n| n.strip_suffix('\''))
+            {
+                literal.to_string()
+            } else {
+                name.to_lowercase()
+            }
+        })
+        .find(|name| name != "$user")
+}
+
 /// Execution state for the status bar.
 #[derive(Debug, Clone, PartialEq, Default)]
 pub enum ExecutionState {
@@ -37,18 +161,87 @@ pub enum ExecutionState {
     #[default]
     Idle,
     /// Query is executing.
-    Executing,
+    Running {
+        /// When execution started, for the live elapsed display.
+        started_at: Instant,
+        /// Milliseconds until the first row arrived, once it has.
+        first_row_ms: Option<u64>,
+    },
+    /// Cancellation of the running query was requested.
+    Cancelling,
     /// Query completed with results.
     Completed {
         /// Number of rows returned.
         rows: usize,
-        /// Execution time in milliseconds.
-        elapsed_ms: u64,
+        /// Execution time in microseconds.
+        elapsed_us: u64,
+        /// Milliseconds until the first row arrived (None for empty results).
+        first_row_ms: Option<u64>,
     },
     /// Query failed with error.
     Failed(SharedString),
 }
 
+impl ExecutionState {
+    /// Check if a query is running (including while it is being cancelled).
+    pub fn is_running(&self) -> bool {
+        matches!(self, Self::Running { .. } | Self::Cancelling)
+    }
+
+    /// Compute the state that follows an execution event.
+    pub fn apply(&self, event: ExecutionEvent) -> Self {
+        match event {
+            ExecutionEvent::Started => {
+                Self::Running { started_at: Instant::now(), first_row_ms: None }
+            }
+            ExecutionEvent::FirstRow { elapsed_ms } => match self {
+                Self::Running { started_at, .. } => {
+                    Self::Running { started_at: *started_at, first_row_ms: Some(elapsed_ms) }
+                }
+                other => other.clone(),
+            },
+            ExecutionEvent::CancelRequested => match self {
+                Self::Running { .. } => Self::Cancelling,
+                other => other.clone(),
+            },
+            ExecutionEvent::Completed { rows, elapsed_us } => {
+                let first_row_ms = match self {
+                    Self::Running { first_row_ms, .. } => *first_row_ms,
+                    _ => None,
+                };
+                Self::Completed { rows, elapsed_us, first_row_ms }
+            }
+            ExecutionEvent::Failed(message) => Self::Failed(message),
+            ExecutionEvent::Cancelled => Self::Idle,
+        }
+    }
+}
+
+/// Query lifecycle events that drive [`ExecutionState`] transitions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionEvent {
+    /// A query started executing.
+    Started,
+    /// The first result row arrived.
+    FirstRow {
+        /// Milliseconds from sending the query to its first row.
+        elapsed_ms: u64,
+    },
+    /// The user asked to cancel the running query.
+    CancelRequested,
+    /// The query finished successfully.
+    Completed {
+        /// Number of rows returned.
+        rows: usize,
+        /// Execution time in microseconds.
+        elapsed_us: u64,
+    },
+    /// The query failed.
+    Failed(SharedString),
+    /// The query stopped because it was cancelled.
+    Cancelled,
+}
+
 /// Status bar component displaying connection and execution state.
 #[derive(IntoElement)]
 pub struct StatusBar {
@@ -56,6 +249,12 @@ pub struct StatusBar {
     connection_status: ConnectionStatus,
     /// Current execution state.
     execution_state: ExecutionState,
+    /// Handler for the cancel button shown while executing.
+    on_cancel: Option<ClickHandler>,
+    /// Summary of the selected result cells.
+    selection_summary: Option<SelectionSummary>,
+    /// Explicit transaction open on the connection.
+    transaction: Option<TransactionIndicator>,
 }
 
 impl StatusBar {
@@ -64,6 +263,9 @@ impl StatusBar {
         Self {
             connection_status: ConnectionStatus::default(),
             execution_state: ExecutionState::default(),
+            on_cancel: None,
+            selection_summary: None,
+            transaction: None,
         }
     }
 
@@ -79,21 +281,40 @@ impl StatusBar {
         self
     }
 
+    /// Set the results selection summary.
+    pub fn selection_summary(mut self, summary: Option<SelectionSummary>) -> Self {
+        self.selection_summary = summary;
+        self
+    }
+
+    /// Set the transaction badge.
+    pub fn transaction(mut self, transaction: Option<TransactionIndicator>) -> Self {
+        self.transaction = transaction;
+        self
+    }
+
+    /// Set the handler invoked when the cancel button is clicked.
+    pub fn on_cancel(
+        mut self,
+        handler: impl Fn(&gpui::ClickEvent, &mut Window, &mut App) + 'static,
+    ) -> Self {
+        self.on_cancel = Some(Box::new(handler));
+        self
+    }
+
     /// Render the connection status section (left side).
     fn render_connection_status(&self, theme: &TuskTheme) -> impl IntoElement {
-        let (icon, text, color): (IconName, String, gpui::Hsla) = match &self.connection_status {
-            ConnectionStatus::Disconnected => {
-                (IconName::Database, "Not connected".to_string(), theme.colors.text_muted)
-            }
-            ConnectionStatus::Connected { database, host } => {
-                (IconName::Database, format!("{} @ {}", database, host), theme.colors.success)
-            }
-            ConnectionStatus::Connecting => {
-                (IconName::Database, "Connecting...".to_string(), theme.colors.warning)
-            }
-            ConnectionStatus::Error(msg) => {
-                (IconName::Database, format!("Error: {}", msg), theme.colors.error)
-            }
+        let color = match &self.connection_status {
+            ConnectionStatus::Disconnected => theme.colors.text_muted,
+            ConnectionStatus::Connected { .. } => theme.colors.success,
+            ConnectionStatus::Connecting => theme.colors.warning,
+            ConnectionStatus::Error(_) => theme.colors.error,
+        };
+        let icon = IconName::Database;
+        let text = self.connection_status.summary();
+        let environment = match &self.connection_status {
+            ConnectionStatus::Connected { environment, .. } => environment.clone(),
+            _ => None,
         };
 
         div()
@@ -102,24 +323,90 @@ impl StatusBar {
             .gap(px(6.0))
             .child(Icon::new(icon).size(IconSize::Small).color(color))
             .child(div().text_color(color).child(text))
+            .when_some(environment, |el, environment| {
+                let badge = div().px(px(6.0)).rounded(px(3.0)).child(environment.label);
+                // Production gets a solid red badge so it can't be missed
+                el.child(if environment.production {
+                    badge.bg(theme.colors.error).text_color(theme.colors.on_accent)
+                } else {
+                    badge.bg(theme.colors.warning.opacity(0.15)).text_color(theme.colors.warning)
+                })
+            })
+            .when_some(self.transaction, |el, transaction| {
+                let color = match transaction {
+                    TransactionIndicator::InTransaction => theme.colors.warning,
+                    TransactionIndicator::Failed => theme.colors.error,
+                };
+                el.child(
+                    div()
+                        .px(px(6.0))
+                        .rounded(px(3.0))
+                        .bg(color.opacity(0.15))
+                        .text_color(color)
+                        .child(transaction.label()),
+                )
+            })
     }
 
     /// Render the execution state section (right side).
-    fn render_execution_state(&self, theme: &TuskTheme) -> impl IntoElement {
+    fn render_execution_state(
+        &self,
+        theme: &TuskTheme,
+        on_cancel: Option<ClickHandler>,
+    ) -> impl IntoElement {
         match &self.execution_state {
             ExecutionState::Idle => {
                 div().flex().items_center().text_color(theme.colors.text_muted).child("Ready")
             }
-            ExecutionState::Executing => div()
+            ExecutionState::Running { started_at, first_row_ms } => {
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(6.0))
+                    .text_color(theme.colors.accent)
+                    .child(Spinner::new().size(SpinnerSize::Small))
+                    .child(format!(
+                        "Running… {}",
+                        format_running_elapsed(started_at.elapsed().as_millis() as u64)
+                    ))
+                    .when_some(*first_row_ms, |el, ms| {
+                        el.child(div().text_color(theme.colors.text_muted).child(format!(
+                            "first row in {}",
+                            format_elapsed(ms.saturating_mul(1000))
+                        )))
+                    })
+                    .when_some(on_cancel, |el, handler| {
+                        el.child(
+                            div()
+                                .id("status-bar-cancel")
+                                .flex()
+                                .items_center()
+                                .gap(px(4.0))
+                                .px(px(6.0))
+                                .rounded(px(3.0))
+                                .text_color(theme.colors.error)
+                                .hover(|s| s.bg(theme.colors.error.opacity(0.1)))
+                                .cursor_pointer()
+                                .on_click(move |event, window, cx| handler(event, window, cx))
+                                .child(
+                                    Icon::new(IconName::Stop)
+                                        .size(IconSize::XSmall)
+                                        .color(theme.colors.error),
+                                )
+                                .child("Cancel"),
+                        )
+                    })
+            }
+            ExecutionState::Cancelling => div()
                 .flex()
                 .items_center()
                 .gap(px(6.0))
-                .text_color(theme.colors.accent)
+                .text_color(theme.colors.warning)
                 .child(Spinner::new().size(SpinnerSize::Small))
-                .child("Executing..."),
-            ExecutionState::Completed { rows, elapsed_ms } => {
+                .child("Cancelling..."),
+            ExecutionState::Completed { rows, elapsed_us, first_row_ms } => {
                 let row_text = if *rows == 1 { "row" } else { "rows" };
-                let elapsed = format_elapsed(*elapsed_ms);
+                let elapsed = format_elapsed(*elapsed_us);
 
                 div()
                     .flex()
@@ -139,6 +426,12 @@ impl StatusBar {
                             .child(format!("{} {}", rows, row_text)),
                     )
                     .child(div().text_color(theme.colors.text_muted).child(elapsed))
+                    .when_some(*first_row_ms, |el, ms| {
+                        el.child(div().text_color(theme.colors.text_muted).child(format!(
+                            "first row in {}",
+                            format_elapsed(ms.saturating_mul(1000))
+                        )))
+                    })
             }
             ExecutionState::Failed(msg) => div()
                 .flex()
@@ -158,8 +451,9 @@ impl Default for StatusBar {
 }
 
 impl RenderOnce for StatusBar {
-    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+    fn render(mut self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
         let theme = cx.global::<TuskTheme>();
+        let on_cancel = self.on_cancel.take();
 
         div()
             .h(STATUS_BAR_HEIGHT)
@@ -174,18 +468,39 @@ impl RenderOnce for StatusBar {
             .text_size(px(12.0))
             // Left side: connection status
             .child(self.render_connection_status(theme))
-            // Right side: execution state
-            .child(self.render_execution_state(theme))
+            // Right side: selection summary and execution state
+            .child(
+                div()
+                    .flex()
+                    .items_center()
+                    .gap(px(16.0))
+                    .when_some(self.selection_summary.as_ref(), |el, summary| {
+                        el.child(div().text_color(theme.colors.text_muted).child(summary.display()))
+                    })
+                    .child(self.render_execution_state(theme, on_cancel)),
+            )
     }
 }
 
-/// Format elapsed time in a human-readable way.
-fn format_elapsed(ms: u64) -> String {
-    if ms < 1000 {
-        format!("{}ms", ms)
-    } else if ms < 60_000 {
-        let secs = ms as f64 / 1000.0;
-        format!("{:.2}s", secs)
+/// Format an elapsed time given in microseconds in a human-readable way.
+pub(crate) fn format_elapsed(us: u64) -> String {
+    match us {
+        0 => "0ms".to_string(),
+        1..=99 => "<0.1ms".to_string(),
+        // One decimal below a millisecond so fast queries don't show 0ms
+        100..=999 => format!("{:.1}ms", us as f64 / 1000.0),
+        1_000..=999_999 => format!("{}ms", us / 1000),
+        1_000_000..=59_999_999 => format!("{:.2}s", us as f64 / 1_000_000.0),
+        _ => format!("{}m {}s", us / 60_000_000, (us % 60_000_000) / 1_000_000),
+    }
+}
+
+/// Format elapsed time for a running query, updated live.
+///
+/// Uses a single decimal under a minute so the ticker stays readable.
+fn format_running_elapsed(ms: u64) -> String {
+    if ms < 60_000 {
+        format!("{:.1}s", ms as f64 / 1000.0)
     } else {
         let mins = ms / 60_000;
         let secs = (ms % 60_000) / 1000;
@@ -199,9 +514,132 @@ mod tests {
 
     #[test]
     fn test_format_elapsed() {
-        assert_eq!(format_elapsed(50), "50ms");
-        assert_eq!(format_elapsed(1500), "1.50s");
-        assert_eq!(format_elapsed(65000), "1m 5s");
+        assert_eq!(format_elapsed(50_000), "50ms");
+        assert_eq!(format_elapsed(1_500_000), "1.50s");
+        assert_eq!(format_elapsed(65_000_000), "1m 5s");
+    }
+
+    #[test]
+    fn test_format_elapsed_sub_millisecond() {
+        assert_eq!(format_elapsed(0), "0ms");
+        assert_eq!(format_elapsed(40), "<0.1ms");
+        assert_eq!(format_elapsed(300), "0.3ms");
+        assert_eq!(format_elapsed(1_000), "1ms");
+    }
+
+    #[test]
+    fn test_format_running_elapsed() {
+        assert_eq!(format_running_elapsed(400), "0.4s");
+        assert_eq!(format_running_elapsed(3200), "3.2s");
+        assert_eq!(format_running_elapsed(59_900), "59.9s");
+        assert_eq!(format_running_elapsed(65_000), "1m 5s");
+    }
+
+    #[test]
+    fn test_execution_state_cancel_transitions() {
+        let state = ExecutionState::Idle.apply(ExecutionEvent::Started);
+        assert!(matches!(state, ExecutionState::Running { .. }));
+        assert!(state.is_running());
+
+        let state = state.apply(ExecutionEvent::CancelRequested);
+        assert_eq!(state, ExecutionState::Cancelling);
+        assert!(state.is_running());
+
+        let state = state.apply(ExecutionEvent::Cancelled);
+        assert_eq!(state, ExecutionState::Idle);
+        assert!(!state.is_running());
+    }
+
+    #[test]
+    fn test_execution_state_complete_transitions() {
+        let state = ExecutionState::Idle
+            .apply(ExecutionEvent::Started)
+            .apply(ExecutionEvent::Completed { rows: 3, elapsed_us: 42_000 });
+        assert_eq!(
+            state,
+            ExecutionState::Completed { rows: 3, elapsed_us: 42_000, first_row_ms: None }
+        );
+
+        // Cancelling only applies to a running query
+        assert_eq!(state.apply(ExecutionEvent::CancelRequested), state);
+        assert_eq!(
+            ExecutionState::Idle.apply(ExecutionEvent::CancelRequested),
+            ExecutionState::Idle
+        );
+
+        // A query can still finish before the cancel request lands
+        let state = ExecutionState::Cancelling.apply(ExecutionEvent::Failed("boom".into()));
+        assert_eq!(state, ExecutionState::Failed("boom".into()));
+    }
+
+    #[test]
+    fn test_execution_state_keeps_first_row_time() {
+        let state = ExecutionState::Idle
+            .apply(ExecutionEvent::Started)
+            .apply(ExecutionEvent::FirstRow { elapsed_ms: 120 });
+        assert!(matches!(state, ExecutionState::Running { first_row_ms: Some(120), .. }));
+
+        let state = state.apply(ExecutionEvent::Completed { rows: 5, elapsed_us: 300_000 });
+        assert_eq!(
+            state,
+            ExecutionState::Completed { rows: 5, elapsed_us: 300_000, first_row_ms: Some(120) }
+        );
+
+        // A late FirstRow doesn't change a finished query
+        assert_eq!(state.apply(ExecutionEvent::FirstRow { elapsed_ms: 1 }), state);
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_connection_summary_shows_search_path_head() {
+        let mut config = ConnectionConfig::new("local", "localhost", "app", "postgres");
+        let summary = |config: &ConnectionConfig, search_path: Option<&str>| {
+            ConnectionStatus::connected(config, search_path).summary()
+        };
+
+        assert_eq!(summary(&config, None), "app @ localhost");
+
+        config.options.default_schema = Some("sales".to_string());
+        assert_eq!(summary(&config, None), "app.sales @ localhost");
+
+        // A SET search_path replaces the default schema
+        assert_eq!(summary(&config, Some("\"$user\", Reporting")), "app.reporting @ localhost");
+        assert_eq!(summary(&config, Some("\"Mixed Case\", public")), "app.Mixed Case @ localhost");
+        assert_eq!(summary(&config, Some("'$user', public")), "app.public @ localhost");
+        // RESET leaves the server default, which isn't known
+        assert_eq!(summary(&config, Some("")), "app @ localhost");
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_environment_badge() {
+        let mut config = ConnectionConfig::new("local", "localhost", "app", "postgres");
+        let badge = |config: &ConnectionConfig| match ConnectionStatus::connected(config, None) {
+            ConnectionStatus::Connected { environment, .. } => environment,
+            _ => None,
+        };
+
+        assert_eq!(badge(&config), None);
+        config.environment = ConnectionEnvironment::Staging;
+        assert_eq!(
+            badge(&config),
+            Some(EnvironmentLabel { label: "Staging".into(), production: false })
+        );
+        config.environment = ConnectionEnvironment::Prod;
+        assert_eq!(
+            badge(&config),
+            Some(EnvironmentLabel { label: "Prod".into(), production: true })
+        );
+    }
+
+    #[cfg(feature = "persistence")]
+    #[test]
+    fn test_transaction_badge() {
+        assert_eq!(TransactionIndicator::for_status(TransactionStatus::Idle), None);
+        let open = TransactionIndicator::for_status(TransactionStatus::InTransaction);
+        assert_eq!(open.map(|badge| badge.label()), Some("In transaction"));
+        let failed = TransactionIndicator::for_status(TransactionStatus::Failed);
+        assert_eq!(failed, Some(TransactionIndicator::Failed));
     }
 
     #[test]
@@ -210,8 +648,14 @@ mod tests {
             .connection_status(ConnectionStatus::Connected {
                 database: "postgres".into(),
                 host: "localhost".into(),
+                schema: None,
+                environment: None,
             })
-            .execution_state(ExecutionState::Completed { rows: 100, elapsed_ms: 150 });
+            .execution_state(ExecutionState::Completed {
+                rows: 100,
+                elapsed_us: 150_000,
+                first_row_ms: Some(12),
+            });
 
         assert!(matches!(status_bar.connection_status, ConnectionStatus::Connected { .. }));
         assert!(matches!(status_bar.execution_state, ExecutionState::Completed { .. }));
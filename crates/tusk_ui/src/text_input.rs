@@ -1,16 +1,21 @@
 //! Simple text input component for filter boxes and single-line inputs.
 
+use std::collections::VecDeque;
 use std::ops::Range;
 
 use gpui::{
-    actions, div, fill, point, prelude::*, px, relative, size, App, Bounds, ClipboardItem, Context,
-    ElementId, ElementInputHandler, Entity, EntityInputHandler, EventEmitter, FocusHandle,
-    Focusable, GlobalElementId, KeyBinding, LayoutId, MouseButton, MouseDownEvent, MouseMoveEvent,
-    MouseUpEvent, Pixels, ShapedLine, SharedString, Style, Subscription, TextRun, UTF16Selection,
-    Window,
+    actions, deferred, div, fill, point, prelude::*, px, relative, size, App, Bounds,
+    ClipboardItem, Context, ElementId, ElementInputHandler, Entity, EntityInputHandler,
+    EventEmitter, FocusHandle, Focusable, GlobalElementId, LayoutId, MouseButton, MouseDownEvent,
+    MouseMoveEvent, MouseUpEvent, Pixels, ShapedLine, SharedString, Style, Subscription, TextRun,
+    UTF16Selection, Window,
 };
 use unicode_segmentation::*;
 
+use crate::icon::{Icon, IconName, IconSize};
+use crate::sql_editing::{self, AutoPairs};
+use crate::sql_reference;
+use crate::tooltip::tooltip_box;
 use crate::TuskTheme;
 
 // Actions for text input
@@ -26,6 +31,8 @@ actions!(
         SelectAll,
         Home,
         End,
+        Up,
+        Down,
         Submit,
         // Standard edit operations (for menu integration)
         Undo,
@@ -36,28 +43,6 @@ actions!(
     ]
 );
 
-/// Register text input key bindings.
-pub fn register_text_input_bindings(cx: &mut App) {
-    cx.bind_keys([
-        KeyBinding::new("backspace", Backspace, Some("TextInput")),
-        KeyBinding::new("delete", Delete, Some("TextInput")),
-        KeyBinding::new("left", Left, Some("TextInput")),
-        KeyBinding::new("right", Right, Some("TextInput")),
-        KeyBinding::new("shift-left", SelectLeft, Some("TextInput")),
-        KeyBinding::new("shift-right", SelectRight, Some("TextInput")),
-        KeyBinding::new("cmd-a", SelectAll, Some("TextInput")),
-        KeyBinding::new("home", Home, Some("TextInput")),
-        KeyBinding::new("end", End, Some("TextInput")),
-        KeyBinding::new("enter", Submit, Some("TextInput")),
-        KeyBinding::new("cmd-c", Copy, Some("TextInput")),
-        KeyBinding::new("cmd-x", Cut, Some("TextInput")),
-        KeyBinding::new("cmd-v", Paste, Some("TextInput")),
-        // Note: Tab/Shift-Tab are intentionally NOT bound here.
-        // Parent components should handle focus navigation via form::Tab/form::TabPrev
-        // or their own capture_action handlers to control field ordering.
-    ]);
-}
-
 /// Events emitted by TextInput.
 #[derive(Clone, Debug)]
 pub enum TextInputEvent {
@@ -71,6 +56,205 @@ pub enum TextInputEvent {
     Blur,
 }
 
+/// What a text input accepts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InputKind {
+    /// Any text.
+    #[default]
+    Text,
+    /// A whole number between `min` and `max`, inclusive.
+    Integer { min: i64, max: i64 },
+}
+
+impl InputKind {
+    /// Check whether `text` may be typed into the input.
+    ///
+    /// Integer inputs accept digits with a leading minus sign when `min` is
+    /// negative. Partial input such as "" or "-" is accepted while typing.
+    pub fn accepts(&self, text: &str) -> bool {
+        match self {
+            Self::Text => true,
+            Self::Integer { min, .. } => {
+                let digits = match text.strip_prefix('-') {
+                    Some(rest) if *min < 0 => rest,
+                    Some(_) => return false,
+                    None => text,
+                };
+                digits.chars().all(|c| c.is_ascii_digit())
+            }
+        }
+    }
+
+    /// Check whether `text` is a complete, in-range value.
+    pub fn is_valid(&self, text: &str) -> bool {
+        match self {
+            Self::Text => true,
+            Self::Integer { min, max } => {
+                text.parse::<i64>().is_ok_and(|value| (*min..=*max).contains(&value))
+            }
+        }
+    }
+
+    /// Bring `text` into range, as done when the input loses focus.
+    ///
+    /// Out-of-range integers are clamped to the nearest bound. Text that
+    /// isn't a number is returned unchanged.
+    pub fn clamp(&self, text: &str) -> String {
+        let Self::Integer { min, max } = self else {
+            return text.to_string();
+        };
+        if !self.accepts(text) || text.trim_start_matches('-').is_empty() {
+            return text.to_string();
+        }
+        // Digits that overflow i64 are beyond either bound
+        let value = text.parse::<i64>().unwrap_or(if text.starts_with('-') { *min } else { *max });
+        value.clamp(*min, *max).to_string()
+    }
+}
+
+/// Previously submitted values of a text input, recalled with Up and Down.
+///
+/// Navigation cycles through the entries and the draft the user was typing
+/// before recalling one, wrapping at both ends.
+#[derive(Clone, Debug)]
+pub struct InputHistory {
+    /// Submitted values, oldest first.
+    entries: VecDeque<String>,
+    /// Most entries kept before the oldest is dropped.
+    capacity: usize,
+    /// Index of the recalled entry, or None while editing the draft.
+    position: Option<usize>,
+    /// Text typed before navigation began.
+    draft: String,
+}
+
+impl InputHistory {
+    /// Create an empty history holding up to `capacity` entries.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            capacity: capacity.max(1),
+            position: None,
+            draft: String::new(),
+        }
+    }
+
+    /// Record a submitted value and stop navigating.
+    ///
+    /// Blank values and repeats of the newest entry aren't recorded.
+    pub fn push(&mut self, value: &str) {
+        self.reset();
+        if value.trim().is_empty() || self.entries.back().is_some_and(|last| last == value) {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(value.to_string());
+    }
+
+    /// Recall the next older entry, wrapping from the oldest to the draft.
+    ///
+    /// `current` is saved as the draft when navigation begins. Returns the
+    /// text to show, or None if nothing has been submitted.
+    pub fn previous(&mut self, current: &str) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.position = match self.position {
+            None => {
+                self.draft = current.to_string();
+                Some(self.entries.len() - 1)
+            }
+            Some(0) => None,
+            Some(index) => Some(index - 1),
+        };
+        Some(self.current())
+    }
+
+    /// Recall the next newer entry, wrapping from the newest to the draft.
+    ///
+    /// `current` is saved as the draft when navigation begins. Returns the
+    /// text to show, or None if nothing has been submitted.
+    pub fn next(&mut self, current: &str) -> Option<&str> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.position = match self.position {
+            None => {
+                self.draft = current.to_string();
+                Some(0)
+            }
+            Some(index) if index + 1 == self.entries.len() => None,
+            Some(index) => Some(index + 1),
+        };
+        Some(self.current())
+    }
+
+    /// Stop navigating, as when the user edits a recalled value.
+    pub fn reset(&mut self) {
+        self.position = None;
+    }
+
+    /// Check whether an entry is recalled.
+    pub fn is_navigating(&self) -> bool {
+        self.position.is_some()
+    }
+
+    /// Get the submitted values, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(String::as_str)
+    }
+
+    fn current(&self) -> &str {
+        match self.position {
+            Some(index) => &self.entries[index],
+            None => &self.draft,
+        }
+    }
+}
+
+/// Masking of a password input's text.
+///
+/// Only the rendering changes; the stored text is always the real value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct PasswordMask {
+    /// Whether the input is a password field.
+    enabled: bool,
+    /// Whether the user has revealed the text.
+    revealed: bool,
+}
+
+impl PasswordMask {
+    /// Check whether text renders as bullets.
+    fn is_masked(&self) -> bool {
+        self.enabled && !self.revealed
+    }
+
+    /// Switch between masked and plain text. Does nothing for other inputs.
+    fn toggle(&mut self) {
+        self.revealed = self.enabled && !self.revealed;
+    }
+
+    /// Go back to masked text.
+    fn hide(&mut self) {
+        self.revealed = false;
+    }
+
+    /// Get the text to render for `content`.
+    fn display(&self, content: &str) -> String {
+        if self.is_masked() {
+            // Use bullet character for each grapheme
+            content.graphemes(true).map(|_| '•').collect()
+        } else {
+            content.to_string()
+        }
+    }
+}
+
+/// Finds hover docs for a word in the input text.
+pub type HoverDocs = fn(&str) -> Option<SharedString>;
+
 /// A simple single-line text input component.
 pub struct TextInput {
     focus_handle: FocusHandle,
@@ -81,8 +265,20 @@ pub struct TextInput {
     marked_range: Option<Range<usize>>,
     last_layout: Option<ShapedLine>,
     last_bounds: Option<Bounds<Pixels>>,
-    /// Whether this is a password field (displays bullets instead of text).
-    password_mode: bool,
+    /// Whether this is a password field and whether its text is revealed.
+    mask: PasswordMask,
+    /// Whether edits from the user are ignored (selection and copy still work).
+    read_only: bool,
+    /// What the user may type.
+    kind: InputKind,
+    /// Submitted values recalled with Up and Down, if enabled.
+    history: Option<InputHistory>,
+    /// Docs shown when hovering a word, if enabled.
+    hover_docs: Option<HoverDocs>,
+    /// Word under the mouse and its docs.
+    hovered_word: Option<(Range<usize>, SharedString)>,
+    /// Bracket auto-closing and matching, if enabled.
+    auto_pairs: Option<AutoPairs>,
     /// Whether user is currently selecting with mouse.
     is_selecting: bool,
     /// Optional tab index for form navigation.
@@ -108,7 +304,13 @@ impl TextInput {
             marked_range: None,
             last_layout: None,
             last_bounds: None,
-            password_mode: false,
+            mask: PasswordMask::default(),
+            read_only: false,
+            kind: InputKind::Text,
+            history: None,
+            hover_docs: None,
+            hovered_word: None,
+            auto_pairs: None,
             is_selecting: false,
             tab_index: None,
             focus_subscription: None,
@@ -123,28 +325,86 @@ impl TextInput {
 
     /// Set whether this is a password field (displays bullets instead of text).
     pub fn set_password(&mut self, password: bool) {
-        self.password_mode = password;
+        self.mask = PasswordMask { enabled: password, revealed: false };
     }
 
     /// Check if this is a password field.
     pub fn is_password(&self) -> bool {
-        self.password_mode
+        self.mask.enabled
+    }
+
+    /// Check if a password field's text is shown in plain text.
+    pub fn is_revealed(&self) -> bool {
+        self.mask.revealed
+    }
+
+    /// Switch a password field between masked and plain text.
+    ///
+    /// The text goes back to masked when the input loses focus.
+    pub fn toggle_reveal(&mut self, cx: &mut Context<Self>) {
+        self.mask.toggle();
+        cx.notify();
+    }
+
+    /// Set whether user edits are ignored.
+    ///
+    /// `set_text` and `clear` still work so the owner can update the content.
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Check if the input is read-only.
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Set what the user may type.
+    ///
+    /// Edits producing text the kind doesn't accept are rejected.
+    pub fn set_kind(&mut self, kind: InputKind) {
+        self.kind = kind;
+    }
+
+    /// Get what the user may type.
+    pub fn kind(&self) -> InputKind {
+        self.kind
+    }
+
+    /// Check whether the content is a valid value for the input's kind.
+    pub fn is_valid(&self) -> bool {
+        self.kind.is_valid(&self.content)
+    }
+
+    /// Keep up to `capacity` submitted values for recall with Up and Down.
+    pub fn enable_history(&mut self, capacity: usize) {
+        self.history = Some(InputHistory::new(capacity));
+    }
+
+    /// Get the submitted values, if history is enabled.
+    pub fn history(&self) -> Option<&InputHistory> {
+        self.history.as_ref()
+    }
+
+    /// Show a tooltip with docs for the word under the mouse.
+    pub fn set_hover_docs(&mut self, hover_docs: HoverDocs) {
+        self.hover_docs = Some(hover_docs);
+    }
+
+    /// Auto-close `(` and `'` as they are typed, and highlight the bracket
+    /// matching the one at the cursor.
+    pub fn enable_bracket_pairs(&mut self) {
+        self.auto_pairs = Some(AutoPairs::default());
     }
 
     /// Get the display text (obscured for password fields).
     pub fn display_text(&self) -> String {
-        if self.password_mode {
-            // Use bullet character for each grapheme
-            self.content.graphemes(true).map(|_| '•').collect()
-        } else {
-            self.content.clone()
-        }
+        self.mask.display(&self.content)
     }
 
     /// Convert a byte offset in the original content to a byte offset in the display text.
     /// In password mode, each grapheme becomes a bullet (•) which is 3 bytes.
     fn content_offset_to_display_offset(&self, content_offset: usize) -> usize {
-        if !self.password_mode {
+        if !self.mask.is_masked() {
             return content_offset;
         }
 
@@ -166,7 +426,7 @@ impl TextInput {
     /// Convert a byte offset in the display text to a byte offset in the original content.
     /// In password mode, each bullet (•) which is 3 bytes maps to one grapheme in the original.
     fn display_offset_to_content_offset(&self, display_offset: usize) -> usize {
-        if !self.password_mode {
+        if !self.mask.is_masked() {
             return display_offset;
         }
 
@@ -201,9 +461,13 @@ impl TextInput {
 
         if self.blur_subscription.is_none() {
             let blur_sub = cx.on_blur(&self.focus_handle, window, |this, _window, cx| {
+                let clamped = this.kind.clamp(&this.content);
+                if clamped != this.content {
+                    this.set_text(clamped, cx);
+                }
+                this.mask.hide();
                 cx.emit(TextInputEvent::Blur);
                 cx.notify();
-                let _ = this;
             });
             self.blur_subscription = Some(blur_sub);
         }
@@ -218,6 +482,39 @@ impl TextInput {
     pub fn set_text(&mut self, text: impl Into<String>, cx: &mut Context<Self>) {
         self.content = text.into();
         self.selected_range = self.content.len()..self.content.len();
+        self.hovered_word = None;
+        if let Some(pairs) = self.auto_pairs.as_mut() {
+            pairs.clear();
+        }
+        cx.emit(TextInputEvent::Changed(self.content.clone()));
+        cx.notify();
+    }
+
+    /// Get the selected byte range, empty when there is only a cursor.
+    pub fn selected_range(&self) -> Range<usize> {
+        self.selected_range.clone()
+    }
+
+    /// Replace the whole text as a user edit, then select `selection`.
+    ///
+    /// Used for editing commands that rewrite several lines at once. Has no
+    /// effect on read-only inputs.
+    pub fn edit_text(&mut self, text: String, selection: Range<usize>, cx: &mut Context<Self>) {
+        if self.read_only {
+            return;
+        }
+        self.content = text;
+        let end = selection.end.min(self.content.len());
+        self.selected_range = selection.start.min(end)..end;
+        self.selection_reversed = false;
+        self.marked_range = None;
+        self.hovered_word = None;
+        if let Some(pairs) = self.auto_pairs.as_mut() {
+            pairs.clear();
+        }
+        if let Some(history) = self.history.as_mut() {
+            history.reset();
+        }
         cx.emit(TextInputEvent::Changed(self.content.clone()));
         cx.notify();
     }
@@ -227,6 +524,10 @@ impl TextInput {
         self.content.clear();
         self.selected_range = 0..0;
         self.marked_range = None;
+        self.hovered_word = None;
+        if let Some(pairs) = self.auto_pairs.as_mut() {
+            pairs.clear();
+        }
         cx.emit(TextInputEvent::Changed(String::new()));
         cx.notify();
     }
@@ -268,7 +569,36 @@ impl TextInput {
         self.move_to(self.content.len(), cx);
     }
 
+    fn up(&mut self, _: &Up, _: &mut Window, cx: &mut Context<Self>) {
+        self.recall(true, cx);
+    }
+
+    fn down(&mut self, _: &Down, _: &mut Window, cx: &mut Context<Self>) {
+        self.recall(false, cx);
+    }
+
+    /// Replace the content with an older or newer history entry.
+    ///
+    /// Only applies while the cursor is at the start or end of the text, so
+    /// the keys pass through to the parent for inputs without history.
+    fn recall(&mut self, older: bool, cx: &mut Context<Self>) {
+        let at_edge = self.selected_range.is_empty()
+            && (self.cursor_offset() == 0 || self.cursor_offset() == self.content.len());
+        let Some(history) = self.history.as_mut().filter(|_| at_edge && !self.read_only) else {
+            cx.propagate();
+            return;
+        };
+        let recalled =
+            if older { history.previous(&self.content) } else { history.next(&self.content) };
+        if let Some(text) = recalled.map(str::to_string) {
+            self.set_text(text, cx);
+        }
+    }
+
     fn submit(&mut self, _: &Submit, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(history) = self.history.as_mut() {
+            history.push(&self.content);
+        }
         cx.emit(TextInputEvent::Submitted(self.content.clone()));
     }
 
@@ -295,6 +625,34 @@ impl TextInput {
         if self.is_selecting {
             self.select_to(self.index_for_mouse_position(event.position), cx);
         }
+        self.update_hovered_word(event.position, cx);
+    }
+
+    /// Track the word under the mouse and its docs for the hover tooltip.
+    fn update_hovered_word(&mut self, position: gpui::Point<Pixels>, cx: &mut Context<Self>) {
+        let hovered = match (self.hover_docs, self.last_bounds.as_ref()) {
+            (Some(docs), Some(bounds))
+                if !self.is_selecting && !self.mask.enabled && bounds.contains(&position) =>
+            {
+                let index = self.index_for_mouse_position(position);
+                sql_reference::word_at(&self.content, index).and_then(|range| {
+                    let text = docs(&self.content[range.clone()])?;
+                    Some((range, text))
+                })
+            }
+            _ => None,
+        };
+
+        if hovered != self.hovered_word {
+            self.hovered_word = hovered;
+            cx.notify();
+        }
+    }
+
+    fn clear_hovered_word(&mut self, hovered: &bool, _: &mut Window, cx: &mut Context<Self>) {
+        if !*hovered && self.hovered_word.take().is_some() {
+            cx.notify();
+        }
     }
 
     fn index_for_mouse_position(&self, position: gpui::Point<Pixels>) -> usize {
@@ -484,16 +842,51 @@ impl EntityInputHandler for TextInput {
         _: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
+
         let range = range_utf16
             .as_ref()
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        self.content =
+        let content =
             self.content[0..range.start].to_owned() + new_text + &self.content[range.end..];
-        self.selected_range = range.start + new_text.len()..range.start + new_text.len();
+        if !self.kind.accepts(&content) {
+            return;
+        }
+
+        // Typing `(` may insert `()`, and typing an auto-inserted closer
+        // moves over it
+        let typing = range.is_empty() && self.marked_range.is_none();
+        let paired = match self.auto_pairs.as_mut() {
+            Some(pairs) if typing => pairs.typed(&self.content, range.start, new_text),
+            _ => None,
+        };
+        let (content, cursor) = match paired {
+            Some(edit) => {
+                let content = self.content[0..range.start].to_owned()
+                    + edit.insert
+                    + &self.content[range.end..];
+                (content, edit.cursor)
+            }
+            None => {
+                if let Some(pairs) = self.auto_pairs.as_mut() {
+                    pairs.edited(range.clone(), new_text.len());
+                }
+                (content, range.start + new_text.len())
+            }
+        };
+
+        self.content = content;
+        if let Some(history) = self.history.as_mut() {
+            history.reset();
+        }
+        self.selected_range = cursor..cursor;
         self.marked_range.take();
+        self.hovered_word = None;
         cx.emit(TextInputEvent::Changed(self.content.clone()));
         cx.notify();
     }
@@ -506,14 +899,28 @@ impl EntityInputHandler for TextInput {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        if self.read_only {
+            return;
+        }
+
         let range = range_utf16
             .as_ref()
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .or(self.marked_range.clone())
             .unwrap_or(self.selected_range.clone());
 
-        self.content =
+        let content =
             self.content[0..range.start].to_owned() + new_text + &self.content[range.end..];
+        if !self.kind.accepts(&content) {
+            return;
+        }
+        self.content = content;
+        if let Some(history) = self.history.as_mut() {
+            history.reset();
+        }
+        if let Some(pairs) = self.auto_pairs.as_mut() {
+            pairs.edited(range.clone(), new_text.len());
+        }
         if !new_text.is_empty() {
             self.marked_range = Some(range.start..range.start + new_text.len());
         } else {
@@ -524,6 +931,7 @@ impl EntityInputHandler for TextInput {
             .map(|range_utf16| self.range_from_utf16(range_utf16))
             .map(|new_range| new_range.start + range.start..new_range.end + range.end)
             .unwrap_or_else(|| range.start + new_text.len()..range.start + new_text.len());
+        self.hovered_word = None;
 
         cx.emit(TextInputEvent::Changed(self.content.clone()));
         cx.notify();
@@ -578,6 +986,8 @@ struct PrepaintState {
     line: Option<ShapedLine>,
     cursor: Option<gpui::PaintQuad>,
     selection: Option<gpui::PaintQuad>,
+    /// Highlights for the bracket at the cursor and its match.
+    brackets: Vec<gpui::PaintQuad>,
 }
 
 impl IntoElement for TextInputElement {
@@ -626,13 +1036,13 @@ impl gpui::Element for TextInputElement {
         let content = input.content.clone();
         let selected_range = input.selected_range.clone();
         let cursor = input.cursor_offset();
-        let password_mode = input.password_mode;
+        let masked = input.mask.is_masked();
         let style = window.text_style();
         let theme = cx.global::<TuskTheme>();
 
         let (display_text, text_color): (SharedString, _) = if content.is_empty() {
             (input.placeholder.clone(), theme.colors.text_muted)
-        } else if password_mode {
+        } else if masked {
             // Display bullets for password mode
             (input.display_text().into(), theme.colors.text)
         } else {
@@ -687,7 +1097,33 @@ impl gpui::Element for TextInputElement {
                 None,
             )
         };
-        PrepaintState { line: Some(line), cursor: cursor_quad, selection }
+        let bracket_pair = match input.auto_pairs {
+            Some(_) if selected_range.is_empty() && !masked => {
+                sql_editing::bracket_pair_at(&input.content, cursor)
+            }
+            _ => None,
+        };
+        let brackets = bracket_pair
+            .map(|(bracket, partner)| {
+                [bracket, partner]
+                    .into_iter()
+                    .map(|offset| {
+                        fill(
+                            Bounds::from_corners(
+                                point(bounds.left() + line.x_for_index(offset), bounds.top()),
+                                point(
+                                    bounds.left() + line.x_for_index(offset + 1),
+                                    bounds.bottom(),
+                                ),
+                            ),
+                            theme.colors.accent.opacity(0.25),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        PrepaintState { line: Some(line), cursor: cursor_quad, selection, brackets }
     }
 
     fn paint(
@@ -709,6 +1145,11 @@ impl gpui::Element for TextInputElement {
         if let Some(selection) = prepaint.selection.take() {
             window.paint_quad(selection)
         }
+        if focus_handle.is_focused(window) {
+            for bracket in prepaint.brackets.drain(..) {
+                window.paint_quad(bracket);
+            }
+        }
         let line = prepaint.line.take().unwrap();
         line.paint(bounds.origin, window.line_height(), gpui::TextAlign::Left, None, window, cx)
             .unwrap();
@@ -726,6 +1167,51 @@ impl gpui::Element for TextInputElement {
     }
 }
 
+impl TextInput {
+    /// Render the docs tooltip below the hovered word.
+    fn render_hover_docs(&self, cx: &mut Context<Self>) -> Option<impl IntoElement> {
+        let (range, text) = self.hovered_word.as_ref()?;
+        let layout = self.last_layout.as_ref()?;
+        let x = layout.x_for_index(self.content_offset_to_display_offset(range.start));
+        let theme = cx.global::<TuskTheme>();
+
+        Some(
+            deferred(
+                div()
+                    .absolute()
+                    .top(px(26.0))
+                    .left(px(8.0) + x)
+                    .child(tooltip_box(theme).whitespace_normal().child(text.clone())),
+            )
+            .with_priority(1),
+        )
+    }
+
+    /// Render the eye button that reveals or masks a password.
+    fn render_reveal_toggle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.global::<TuskTheme>();
+        let (icon, color) = if self.mask.revealed {
+            (IconName::EyeOff, theme.colors.text)
+        } else {
+            (IconName::Eye, theme.colors.text_muted)
+        };
+
+        div()
+            .id("text-input-reveal")
+            .flex_none()
+            .ml(px(4.0))
+            .cursor_pointer()
+            .child(Icon::new(icon).size(IconSize::XSmall).color(color))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, _: &MouseDownEvent, _window, cx| {
+                    cx.stop_propagation();
+                    this.toggle_reveal(cx);
+                }),
+            )
+    }
+}
+
 impl Render for TextInput {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         // Subscribe to focus/blur events on first render
@@ -749,6 +1235,8 @@ impl Render for TextInput {
             .on_action(cx.listener(Self::select_all))
             .on_action(cx.listener(Self::home))
             .on_action(cx.listener(Self::end))
+            .on_action(cx.listener(Self::up))
+            .on_action(cx.listener(Self::down))
             .on_action(cx.listener(Self::submit))
             .on_action(cx.listener(Self::paste))
             .on_action(cx.listener(Self::copy))
@@ -757,6 +1245,8 @@ impl Render for TextInput {
             .on_mouse_up(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_up_out(MouseButton::Left, cx.listener(Self::on_mouse_up))
             .on_mouse_move(cx.listener(Self::on_mouse_move))
+            .on_hover(cx.listener(Self::clear_hovered_word))
+            .relative()
             .h(px(24.0))
             .w_full()
             .px(px(8.0))
@@ -767,7 +1257,162 @@ impl Render for TextInput {
             .border_1()
             .border_color(theme.colors.border)
             .when(is_focused, |d| d.border_color(theme.colors.accent))
+            .when(!self.is_valid(), |d| d.border_color(theme.colors.error))
             .text_sm()
             .child(TextInputElement { input: cx.entity() })
+            .when(self.mask.enabled, |d| d.child(self.render_reveal_toggle(cx)))
+            .children(self.render_hover_docs(cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PORT: InputKind = InputKind::Integer { min: 1, max: 65535 };
+
+    fn history(values: &[&str]) -> InputHistory {
+        let mut history = InputHistory::new(10);
+        for value in values {
+            history.push(value);
+        }
+        history
+    }
+
+    #[test]
+    fn test_history_navigation_wraps() {
+        let mut history = history(&["one", "two", "three"]);
+
+        assert_eq!(history.previous("draft"), Some("three"));
+        assert_eq!(history.previous("three"), Some("two"));
+        assert_eq!(history.previous("two"), Some("one"));
+        // Past the oldest entry comes back to the draft, then wraps around
+        assert_eq!(history.previous("one"), Some("draft"));
+        assert!(!history.is_navigating());
+        assert_eq!(history.previous("draft"), Some("three"));
+
+        assert_eq!(history.next("three"), Some("draft"));
+        assert_eq!(history.next("draft"), Some("one"));
+        assert_eq!(history.next("one"), Some("two"));
+
+        assert_eq!(InputHistory::new(10).previous("draft"), None);
+    }
+
+    #[test]
+    fn test_history_edit_resets_position() {
+        let mut history = history(&["one", "two"]);
+        assert_eq!(history.previous(""), Some("two"));
+        assert_eq!(history.previous("two"), Some("one"));
+
+        // Editing the recalled value starts over from the newest entry
+        history.reset();
+        assert!(!history.is_navigating());
+        assert_eq!(history.previous("one!"), Some("two"));
+        assert_eq!(history.previous("two"), Some("one"));
+        assert_eq!(history.previous("one"), Some("one!"));
+
+        // Submitting also resets navigation
+        history.previous("one!");
+        history.push("three");
+        assert_eq!(history.previous(""), Some("three"));
+    }
+
+    #[test]
+    fn test_history_skips_blanks_repeats_and_drops_oldest() {
+        let mut history = InputHistory::new(2);
+        history.push("a");
+        history.push("  ");
+        history.push("a");
+        history.push("b");
+        history.push("c");
+        assert_eq!(history.entries().collect::<Vec<_>>(), vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_password_reveal_toggle() {
+        let mut mask = PasswordMask { enabled: true, revealed: false };
+        assert!(mask.is_masked());
+        assert_eq!(mask.display("héllo"), "•••••");
+
+        mask.toggle();
+        assert!(mask.revealed);
+        assert!(!mask.is_masked());
+        assert_eq!(mask.display("héllo"), "héllo");
+
+        mask.toggle();
+        assert!(mask.is_masked());
+
+        // Losing focus masks the text again
+        mask.toggle();
+        mask.hide();
+        assert!(mask.is_masked());
+
+        // Plain inputs can't be toggled
+        let mut plain = PasswordMask::default();
+        plain.toggle();
+        assert!(!plain.revealed);
+        assert_eq!(plain.display("secret"), "secret");
+    }
+
+    #[gpui::test]
+    fn test_masking_keeps_the_real_value(cx: &mut gpui::TestAppContext) {
+        let input = cx.new(|cx| {
+            let mut input = TextInput::new("Password", cx);
+            input.set_password(true);
+            input.set_text("p@ss word", cx);
+            input
+        });
+
+        input.update(cx, |input, cx| {
+            assert_eq!(input.mask.display(input.text()), "•••••••••");
+
+            input.toggle_reveal(cx);
+            assert!(input.is_revealed());
+            assert_eq!(input.mask.display(input.text()), "p@ss word");
+
+            input.toggle_reveal(cx);
+            assert!(!input.is_revealed());
+            assert_eq!(input.text(), "p@ss word");
+        });
+    }
+
+    #[test]
+    fn test_integer_accepts_digits_only() {
+        assert!(PORT.accepts("5432"));
+        assert!(PORT.accepts(""));
+        assert!(!PORT.accepts("abc"));
+        assert!(!PORT.accepts("54a2"));
+        assert!(!PORT.accepts("5432 "));
+        // A minus sign only when negative values are allowed
+        assert!(!PORT.accepts("-1"));
+        assert!(InputKind::Integer { min: -10, max: 10 }.accepts("-"));
+        assert!(InputKind::Integer { min: -10, max: 10 }.accepts("-5"));
+        assert!(InputKind::Text.accepts("abc"));
+    }
+
+    #[test]
+    fn test_integer_validity() {
+        assert!(PORT.is_valid("5432"));
+        assert!(PORT.is_valid("1"));
+        assert!(PORT.is_valid("65535"));
+        assert!(!PORT.is_valid("abc"));
+        assert!(!PORT.is_valid(""));
+        assert!(!PORT.is_valid("0"));
+        assert!(!PORT.is_valid("65536"));
+        assert!(InputKind::Text.is_valid(""));
+    }
+
+    #[test]
+    fn test_integer_clamps_out_of_range() {
+        assert_eq!(PORT.clamp("5432"), "5432");
+        assert_eq!(PORT.clamp("0"), "1");
+        assert_eq!(PORT.clamp("70000"), "65535");
+        assert_eq!(PORT.clamp("99999999999999999999999"), "65535");
+        assert_eq!(PORT.clamp("007"), "7");
+        assert_eq!(InputKind::Integer { min: -10, max: 10 }.clamp("-50"), "-10");
+        // Incomplete input is left for the user to fix
+        assert_eq!(PORT.clamp(""), "");
+        assert_eq!(InputKind::Integer { min: -10, max: 10 }.clamp("-"), "-");
+        assert_eq!(InputKind::Text.clamp("abc"), "abc");
     }
 }
@@ -110,6 +110,12 @@ impl Toast {
         }
     }
 
+    /// Replace the message, e.g. to update progress.
+    pub fn set_message(&mut self, message: impl Into<SharedString>, cx: &mut Context<Self>) {
+        self.message = message.into();
+        cx.notify();
+    }
+
     /// Set the hint text.
     pub fn with_hint(mut self, hint: impl Into<SharedString>) -> Self {
         self.hint = Some(hint.into());
@@ -3,7 +3,7 @@
 //! This module provides a simple tooltip component that displays text
 //! when hovering over an element.
 
-use gpui::{div, prelude::*, px, AnyView, Context, IntoElement, Render, SharedString, Window};
+use gpui::{div, prelude::*, px, AnyView, Context, Div, IntoElement, Render, SharedString, Window};
 
 use crate::layout::spacing;
 use crate::TuskTheme;
@@ -21,24 +21,26 @@ impl Tooltip {
     }
 }
 
+/// Styled tooltip box, shared with tooltips that are positioned by hand.
+pub(crate) fn tooltip_box(theme: &TuskTheme) -> Div {
+    div()
+        .bg(theme.colors.elevated_surface_background)
+        .border_1()
+        .border_color(theme.colors.border)
+        .rounded(px(4.0))
+        .shadow_sm()
+        .py(spacing::XS)
+        .px(spacing::SM)
+        .text_sm()
+        .text_color(theme.colors.text)
+        .max_w(px(400.0))
+}
+
 impl Render for Tooltip {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let theme = cx.global::<TuskTheme>();
 
         // Offset from cursor position
-        div().pl(spacing::XS).pt(spacing::XS).child(
-            div()
-                .bg(theme.colors.elevated_surface_background)
-                .border_1()
-                .border_color(theme.colors.border)
-                .rounded(px(4.0))
-                .shadow_sm()
-                .py(spacing::XS)
-                .px(spacing::SM)
-                .text_sm()
-                .text_color(theme.colors.text)
-                .max_w(px(400.0))
-                .child(self.text.clone()),
-        )
+        div().pl(spacing::XS).pt(spacing::XS).child(tooltip_box(theme).child(self.text.clone()))
     }
 }
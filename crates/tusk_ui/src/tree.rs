@@ -3,6 +3,7 @@
 //! This module provides a reusable tree component that supports:
 //! - Virtualized rendering via GPUI's UniformList (60fps for 1000+ items)
 //! - Expand/collapse with keyboard navigation
+//! - Children loaded on first expand, with a spinner until they arrive
 //! - Single selection with click and keyboard
 //! - Filtering with recursive descendant matching
 //! - Event emission for selection, activation, and context menus
@@ -23,6 +24,7 @@ use crate::key_bindings::tree::{
     SelectPrevious,
 };
 use crate::layout::spacing;
+use crate::spinner::{Spinner, SpinnerSize};
 use crate::tooltip::Tooltip;
 use crate::TuskTheme;
 
@@ -47,6 +49,14 @@ pub trait TreeItem: Clone + 'static {
     fn is_expandable(&self) -> bool {
         self.children().is_some()
     }
+
+    /// Returns whether this item's children have been loaded.
+    ///
+    /// Expanding an unloaded item emits [`TreeEvent::LoadChildren`] once; the
+    /// owner then replaces it with a loaded item via [`Tree::set_items`].
+    fn is_loaded(&self) -> bool {
+        true
+    }
 }
 
 /// Events emitted by the Tree component.
@@ -60,6 +70,8 @@ pub enum TreeEvent<Id> {
     Expanded { id: Id },
     /// An item was collapsed.
     Collapsed { id: Id },
+    /// An unloaded item was expanded and needs its children loaded.
+    LoadChildren { id: Id },
     /// Context menu was requested for an item.
     ContextMenu { id: Id, position: gpui::Point<gpui::Pixels> },
 }
@@ -79,6 +91,8 @@ pub struct Tree<T: TreeItem> {
     items: Vec<T>,
     /// IDs of currently expanded items.
     expanded: HashSet<T::Id>,
+    /// IDs of unloaded items whose children have been requested.
+    loading: HashSet<T::Id>,
     /// Currently selected item ID.
     selected: Option<T::Id>,
     /// Focus handle for keyboard navigation.
@@ -97,6 +111,7 @@ impl<T: TreeItem> Tree<T> {
         let mut tree = Self {
             items,
             expanded: HashSet::new(),
+            loading: HashSet::new(),
             selected: None,
             focus_handle: cx.focus_handle(),
             scroll_handle: UniformListScrollHandle::new(),
@@ -113,8 +128,13 @@ impl<T: TreeItem> Tree<T> {
     }
 
     /// Set new root items.
+    ///
+    /// Items that are still unloaded keep their pending load; loaded ones stop
+    /// showing the spinner.
     pub fn set_items(&mut self, items: Vec<T>, cx: &mut Context<Self>) {
         self.items = items;
+        let items = &self.items;
+        self.loading.retain(|id| find_item(items, id).is_some_and(|item| !item.is_loaded()));
         self.rebuild_visible_entries();
         cx.notify();
     }
@@ -135,17 +155,35 @@ impl<T: TreeItem> Tree<T> {
         self.expanded.contains(id)
     }
 
-    /// Expand an item.
+    /// Find an item by ID, whether or not it's visible.
+    pub fn find(&self, id: &T::Id) -> Option<&T> {
+        find_item(&self.items, id)
+    }
+
+    /// Check if an item's children are being loaded.
+    pub fn is_loading(&self, id: &T::Id) -> bool {
+        self.loading.contains(id)
+    }
+
+    /// Expand an item, requesting its children if they aren't loaded yet.
     pub fn expand(&mut self, id: T::Id, cx: &mut Context<Self>) {
         if self.expanded.insert(id.clone()) {
             self.rebuild_visible_entries();
-            cx.emit(TreeEvent::Expanded { id });
+            cx.emit(TreeEvent::Expanded { id: id.clone() });
+            let unloaded = self.find(&id).is_some_and(|item| !item.is_loaded());
+            if unloaded && self.loading.insert(id.clone()) {
+                cx.emit(TreeEvent::LoadChildren { id });
+            }
             cx.notify();
         }
     }
 
     /// Collapse an item.
+    ///
+    /// Collapsing an item whose children are loading lets the next expand
+    /// request them again, e.g. after the load failed.
     pub fn collapse(&mut self, id: T::Id, cx: &mut Context<Self>) {
+        self.loading.remove(&id);
         if self.expanded.remove(&id) {
             self.rebuild_visible_entries();
             cx.emit(TreeEvent::Collapsed { id });
@@ -163,6 +201,8 @@ impl<T: TreeItem> Tree<T> {
     }
 
     /// Expand all items.
+    ///
+    /// Unloaded items are expanded without requesting their children.
     pub fn expand_all(&mut self, cx: &mut Context<Self>) {
         self.expand_all_recursive(&self.items.clone());
         self.rebuild_visible_entries();
@@ -341,6 +381,7 @@ impl<T: TreeItem> Tree<T> {
         let item_id = entry.item.id();
         let is_expandable = entry.item.is_expandable();
         let is_expanded = self.is_expanded(&item_id);
+        let is_loading = self.is_loading(&item_id);
         let indent = px(entry.depth as f32 * 16.0 + 4.0);
 
         let item_id_for_click = item_id.clone();
@@ -405,7 +446,7 @@ impl<T: TreeItem> Tree<T> {
                     }),
             )
             .child(
-                // Icon
+                // Icon, or a spinner while children load
                 div()
                     .w(px(16.0))
                     .h(px(16.0))
@@ -413,7 +454,8 @@ impl<T: TreeItem> Tree<T> {
                     .items_center()
                     .justify_center()
                     .mr(spacing::XS)
-                    .when_some(entry.item.icon(), |d, icon_name| {
+                    .when(is_loading, |d| d.child(Spinner::new().size(SpinnerSize::Small)))
+                    .when_some(entry.item.icon().filter(|_| !is_loading), |d, icon_name| {
                         d.child(Icon::new(icon_name).size(IconSize::Small).color(if is_selected {
                             theme.colors.text
                         } else {
@@ -442,6 +484,17 @@ impl<T: TreeItem> Tree<T> {
     }
 }
 
+/// Find an item by ID anywhere in `items`.
+fn find_item<'a, T: TreeItem>(items: &'a [T], id: &T::Id) -> Option<&'a T> {
+    items.iter().find_map(|item| {
+        if item.id() == *id {
+            Some(item)
+        } else {
+            item.children().and_then(|children| find_item(children, id))
+        }
+    })
+}
+
 impl<T: TreeItem> EventEmitter<TreeEvent<T::Id>> for Tree<T> {}
 
 impl<T: TreeItem> Render for Tree<T> {
@@ -5,33 +5,53 @@
 
 use gpui::{
     canvas, div, prelude::*, px, App, Axis, Bounds, Context, DragMoveEvent, Entity, EventEmitter,
-    FocusHandle, KeyContext, Pixels, Point, Render, Subscription, Window,
+    FocusHandle, Hsla, KeyContext, PathPromptOptions, Pixels, Point, Render, SharedString,
+    Subscription, Task, Window,
 };
 use serde::{Deserialize, Serialize};
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 
 #[cfg(not(target_os = "macos"))]
 use crate::application_menu::ApplicationMenu;
+use crate::confirm_dialog::ConfirmDialog;
 use crate::connection_dialog::{ConnectionDialog, ConnectionDialogEvent};
 use crate::context_menu::ContextMenuLayer;
+#[cfg(feature = "persistence")]
+use crate::debounce::Debouncer;
 use crate::dock::{Dock, DockEvent, DraggedDock};
+use crate::home::{HomeView, HomeViewEvent};
 use crate::icon::IconName;
 use crate::key_bindings::{
     ActivateTab1, ActivateTab2, ActivateTab3, ActivateTab4, ActivateTab5, ActivateTab6,
-    ActivateTab7, ActivateTab8, ActivateTab9, CloseActiveTab, ClosePane, FocusNextPane,
-    FocusPreviousPane, FocusResults, FocusSchemaBrowser, NewConnection, NewQueryTab, NextTab,
-    PreviousTab, SplitDown, SplitRight, ToggleBottomDock, ToggleLeftDock, ToggleRightDock,
+    ActivateTab7, ActivateTab8, ActivateTab9, CloseActiveTab, ClosePane, EstimateQuery,
+    ExportResults, FocusNextPane, FocusPaneDown, FocusPaneLeft, FocusPaneRight, FocusPaneUp,
+    FocusPreviousPane, FocusResults, FocusSchemaBrowser, GoToObject, NewConnection, NewQueryTab,
+    NextTab, OpenSqlFile, PreviousTab, SaveSqlFile, SaveSqlFileAs, SplitDown, SplitRight,
+    ToggleBottomDock, ToggleLeftDock, ToggleRightDock, ZoomPane,
 };
 use crate::layout::sizes::STATUS_BAR_HEIGHT;
 use crate::layout::spacing;
 use crate::modal::ModalLayer;
-use crate::pane::{Pane, PaneGroup, PaneGroupEvent, PaneLayout, TabItem};
+use crate::pane::{FocusDirection, Pane, PaneGroup, PaneGroupEvent, PaneLayout, TabItem};
 use crate::panel::{DockPosition, Focusable};
-use crate::panels::{MessagesPanel, ResultsPanel, SchemaBrowserEvent, SchemaBrowserPanel};
-use crate::query_editor::QueryEditor;
-use crate::status_bar::{ConnectionStatus, ExecutionState, StatusBar};
+use crate::panels::{
+    CommandStatus, DefinitionTarget, LargeResult, Message, MessagesPanel, ResultsEditEvent,
+    ResultsPanel, SchemaBrowserEvent, SchemaBrowserPanel, SelectionSummary, ServerNotice,
+};
+#[cfg(feature = "persistence")]
+use crate::panels::{ConnectionsPanel, ConnectionsPanelEvent, NotificationsPanel};
+use crate::query_editor::{QueryEditor, QueryEditorEvent};
+use crate::quick_open::{QuickOpen, QuickOpenEvent};
+use crate::sql_file::{default_sql_dir, DEFAULT_SQL_FILE_NAME};
+use crate::status_bar::{
+    ConnectionStatus, ExecutionEvent, ExecutionState, StatusBar, TransactionIndicator,
+};
+use crate::toast::{Toast, ToastLayer};
 use crate::TuskTheme;
+use tusk_core::services::schema::TableOperation;
 use uuid::Uuid;
 
 // ============================================================================
@@ -85,6 +105,14 @@ impl Render for QueryPlaceholderView {
 /// Key used to store workspace state in the UI state storage.
 pub const WORKSPACE_STATE_KEY: &str = "workspace_state";
 
+/// How often the status bar refreshes the elapsed time of a running query.
+const ELAPSED_TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Quiet interval before refreshing the schema browser after DDL, so
+/// back-to-back DDL runs cause a single reload.
+#[cfg(feature = "persistence")]
+const SCHEMA_REFRESH_DEBOUNCE: Duration = Duration::from_millis(300);
+
 /// Events emitted by the workspace.
 #[derive(Debug, Clone)]
 pub enum WorkspaceEvent {
@@ -149,14 +177,32 @@ pub struct Workspace {
     bottom_dock: Entity<Dock>,
     /// Center pane group.
     center: Entity<PaneGroup>,
+    /// Home view with recent connections, shown in empty panes.
+    home: Entity<HomeView>,
     /// Schema browser panel entity.
     schema_browser: Entity<SchemaBrowserPanel>,
+    /// Panel listing open connections.
+    #[cfg(feature = "persistence")]
+    connections_panel: Entity<ConnectionsPanel>,
     /// Results panel entity.
     results_panel: Entity<ResultsPanel>,
     /// Messages panel entity.
     messages_panel: Entity<MessagesPanel>,
+    /// Panel showing notifications from LISTEN subscriptions.
+    #[cfg(feature = "persistence")]
+    notifications_panel: Entity<NotificationsPanel>,
     /// Connection dialog entity.
     connection_dialog: Option<Entity<ConnectionDialog>>,
+    /// Confirmation for a destructive schema browser action.
+    confirm_dialog: Option<Entity<ConfirmDialog>>,
+    /// Subscription to `confirm_dialog` events.
+    _dialog_subscription: Option<Subscription>,
+    /// Go-to-object palette over the loaded schema.
+    quick_open: Option<Entity<QuickOpen>>,
+    /// Subscription to `quick_open` events.
+    _quick_open_subscription: Option<Subscription>,
+    /// Toast notifications shown above the status bar.
+    toast_layer: Entity<ToastLayer>,
     /// Focus handle for the workspace.
     focus_handle: FocusHandle,
     /// Subscriptions to child component events.
@@ -171,6 +217,25 @@ pub struct Workspace {
     connection_status: ConnectionStatus,
     /// Current query execution state for the status bar.
     execution_state: ExecutionState,
+    /// Results selection summary for the status bar.
+    selection_summary: Option<SelectionSummary>,
+    /// Timer re-rendering the status bar while a query runs.
+    _elapsed_ticker: Option<Task<()>>,
+    /// Handle of the running query, for cancelling from the status bar (FR-013).
+    #[cfg(feature = "persistence")]
+    active_query: Option<Arc<tusk_core::QueryHandle>>,
+    /// Handle of the schema load in progress, for cancelling it.
+    #[cfg(feature = "persistence")]
+    schema_load: Option<Arc<tusk_core::QueryHandle>>,
+    /// Handle of the connect-on-startup attempt, cancelled if the user connects manually.
+    #[cfg(feature = "persistence")]
+    startup_connect: Option<Arc<tusk_core::QueryHandle>>,
+    /// Handle of the column sample in progress, cancelled when another starts.
+    #[cfg(feature = "persistence")]
+    column_sample: Option<Arc<tusk_core::QueryHandle>>,
+    /// Pending schema browser refresh after DDL, keyed by connection.
+    #[cfg(feature = "persistence")]
+    schema_refresh: Debouncer<Uuid>,
     /// Current active connection ID.
     active_connection_id: Option<Uuid>,
 }
@@ -204,6 +269,14 @@ impl Workspace {
             dock.add_panel(Arc::new(schema_browser.clone()), cx);
         });
 
+        // Open connections are listed next to the schema browser
+        #[cfg(feature = "persistence")]
+        let connections_panel = cx.new(ConnectionsPanel::new);
+        #[cfg(feature = "persistence")]
+        left_dock.update(cx, |dock, cx| {
+            dock.add_panel(Arc::new(connections_panel.clone()), cx);
+        });
+
         // Create and register the results and messages panels with the bottom dock
         let results_panel = cx.new(ResultsPanel::new);
         let messages_panel = cx.new(MessagesPanel::new);
@@ -212,9 +285,21 @@ impl Workspace {
             dock.add_panel(Arc::new(messages_panel.clone()), cx);
         });
 
+        // LISTEN subscriptions and their notifications sit beside the messages
+        #[cfg(feature = "persistence")]
+        let notifications_panel = cx.new(NotificationsPanel::new);
+        #[cfg(feature = "persistence")]
+        bottom_dock.update(cx, |dock, cx| {
+            dock.add_panel(Arc::new(notifications_panel.clone()), cx);
+        });
+
         // Create center pane group with one initial pane
         let center = cx.new(|cx| PaneGroup::new(window, cx));
 
+        // Empty panes show recent connections instead of a bare placeholder
+        let home = cx.new(HomeView::new);
+        center.update(cx, |center, cx| center.set_empty_view(Some(home.clone().into()), cx));
+
         // Subscribe to dock events with persistence save
         let mut subscriptions = Vec::new();
 
@@ -256,6 +341,28 @@ impl Workspace {
             },
         ));
 
+        // One-click connect from the home view
+        subscriptions.push(cx.subscribe_in(
+            &home,
+            window,
+            |this, _home, event: &HomeViewEvent, window, cx| match event {
+                HomeViewEvent::Connect { connection_id } => {
+                    this.connect_saved(*connection_id, window, cx);
+                }
+                HomeViewEvent::NewConnection => this.show_connection_dialog(cx),
+            },
+        ));
+
+        #[cfg(feature = "persistence")]
+        subscriptions.push(cx.subscribe(
+            &connections_panel,
+            |this, _panel, event: &ConnectionsPanelEvent, cx| match event {
+                ConnectionsPanelEvent::Disconnected { connection_ids } => {
+                    this.connections_closed(connection_ids, cx);
+                }
+            },
+        ));
+
         // Subscribe to schema browser events (T056)
         subscriptions.push(cx.subscribe(
             &schema_browser,
@@ -265,6 +372,106 @@ impl Workspace {
                         this.refresh_schema(connection_id, cx);
                     }
                 }
+                SchemaBrowserEvent::ShowDefinition(target) => {
+                    this.show_definition(target.clone(), cx);
+                }
+                SchemaBrowserEvent::RefreshMaterializedView { schema, name, concurrently } => {
+                    this.refresh_materialized_view(schema.clone(), name.clone(), *concurrently, cx);
+                }
+                SchemaBrowserEvent::RunQuery { title, sql } => {
+                    if let Some(connection_id) = this.active_connection_id {
+                        this.run_sql(connection_id, title.clone().into(), sql.clone(), cx);
+                    }
+                }
+                SchemaBrowserEvent::CancelLoad => this.cancel_schema_load(cx),
+                SchemaBrowserEvent::PinnedTablesChanged(tables) => {
+                    this.save_pinned_tables(tables, cx);
+                }
+                SchemaBrowserEvent::TableOperation { operation, schema, name } => {
+                    this.confirm_table_operation(*operation, schema.clone(), name.clone(), cx);
+                }
+                SchemaBrowserEvent::SampleColumn { column_id, schema, table, column } => {
+                    this.sample_column(
+                        column_id.clone(),
+                        schema.clone(),
+                        table.clone(),
+                        column.clone(),
+                        cx,
+                    );
+                }
+                SchemaBrowserEvent::LoadColumns { schema, name } => {
+                    this.load_columns(schema.clone(), name.clone(), cx);
+                }
+            },
+        ));
+
+        // Terminal query events from the results panel drive the status bar
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, event: &ExecutionEvent, cx| {
+                this.apply_execution_event(event.clone(), cx);
+            },
+        ));
+
+        // Statements without a result set report their status as a message
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, status: &CommandStatus, cx| {
+                this.messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(Message::success(status.0.clone()), cx);
+                });
+            },
+        ));
+
+        // Notices raised by the server appear alongside results
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, notice: &ServerNotice, cx| {
+                this.messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(notice.0.clone(), cx);
+                });
+            },
+        ));
+
+        // Cell selection in the results grid feeds the status bar summary
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, summary: &SelectionSummary, cx| {
+                this.selection_summary = (summary.selected_cells > 0).then(|| summary.clone());
+                cx.notify();
+            },
+        ));
+
+        // Huge results are better exported than rendered
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, event: &LargeResult, cx| {
+                let message = event.message();
+                this.messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(Message::warning(message.clone()), cx);
+                });
+                let toast = cx.new(|cx| {
+                    Toast::warning(message, cx).with_hint(
+                        "Export the result to a file instead, or add a LIMIT to the query",
+                    )
+                });
+                this.show_toast(toast, cx);
+            },
+        ));
+
+        // Edit mode needs the result's source table; edits open as UPDATE tabs
+        subscriptions.push(cx.subscribe(
+            &results_panel,
+            |this, _panel, event: &ResultsEditEvent, cx| match event {
+                ResultsEditEvent::TargetRequested => this.load_edit_target(cx),
+                ResultsEditEvent::InsertsRequested => this.copy_selection_as_inserts(cx),
+                ResultsEditEvent::UpdateGenerated { table, sql } => {
+                    this.open_update_tab(table, sql.clone(), cx);
+                }
+                ResultsEditEvent::Failed(message) => {
+                    let toast = cx.new(|cx| Toast::error(message.clone(), cx));
+                    this.show_toast(toast, cx);
+                }
             },
         ));
 
@@ -275,10 +482,20 @@ impl Workspace {
             right_dock: None,
             bottom_dock,
             center,
+            home,
             schema_browser,
+            #[cfg(feature = "persistence")]
+            connections_panel,
             results_panel,
             messages_panel,
+            #[cfg(feature = "persistence")]
+            notifications_panel,
             connection_dialog: None,
+            confirm_dialog: None,
+            _dialog_subscription: None,
+            quick_open: None,
+            _quick_open_subscription: None,
+            toast_layer: cx.new(|_| ToastLayer::new()),
             focus_handle,
             _subscriptions: subscriptions,
             bounds: Bounds::default(),
@@ -286,6 +503,18 @@ impl Workspace {
             last_viewport_height: px(800.0), // Default, will be updated on first render
             connection_status: ConnectionStatus::default(),
             execution_state: ExecutionState::default(),
+            selection_summary: None,
+            _elapsed_ticker: None,
+            #[cfg(feature = "persistence")]
+            active_query: None,
+            #[cfg(feature = "persistence")]
+            schema_load: None,
+            #[cfg(feature = "persistence")]
+            startup_connect: None,
+            #[cfg(feature = "persistence")]
+            column_sample: None,
+            #[cfg(feature = "persistence")]
+            schema_refresh: Debouncer::new(SCHEMA_REFRESH_DEBOUNCE),
             active_connection_id: None,
         };
 
@@ -416,6 +645,9 @@ impl Workspace {
 
     /// Show the connection dialog (T046).
     pub fn show_connection_dialog(&mut self, cx: &mut Context<Self>) {
+        // Connecting by hand takes over from a pending startup connection
+        self.cancel_startup_connect(cx);
+
         // Create and show the connection dialog
         let dialog = cx.new(ConnectionDialog::new);
 
@@ -426,6 +658,22 @@ impl Workspace {
         cx.notify();
     }
 
+    /// Connect to a saved connection from the home view.
+    ///
+    /// Connects right away when the password is stored; otherwise the
+    /// connection dialog stays open to prompt for it.
+    fn connect_saved(&mut self, connection_id: Uuid, window: &mut Window, cx: &mut Context<Self>) {
+        self.show_connection_dialog(cx);
+        if let Some(dialog) = &self.connection_dialog {
+            dialog.update(cx, |dialog, cx| {
+                dialog.select_saved_connection(connection_id, window, cx);
+                if !dialog.state().needs_password() {
+                    dialog.connect(cx);
+                }
+            });
+        }
+    }
+
     /// Handle connection dialog events (T046, T047, T048).
     fn handle_connection_dialog_event(
         &mut self,
@@ -435,30 +683,10 @@ impl Workspace {
     ) {
         match event {
             ConnectionDialogEvent::Connected { connection_id } => {
-                // Store the active connection ID
-                self.active_connection_id = Some(*connection_id);
-
-                // Update status bar connection status (T046)
-                #[cfg(feature = "persistence")]
-                {
-                    use tusk_core::TuskState;
-                    if let Some(state) = cx.try_global::<TuskState>() {
-                        if let Some(config) = state.get_connection_config(connection_id) {
-                            self.connection_status = ConnectionStatus::Connected {
-                                database: config.database.clone().into(),
-                                host: config.host.clone().into(),
-                            };
-                        }
-                    }
-                }
-
                 // Close the connection dialog
                 self.connection_dialog = None;
 
-                // Trigger schema refresh (T048)
-                self.refresh_schema(*connection_id, cx);
-
-                cx.notify();
+                self.activate_connection(*connection_id, cx);
             }
             ConnectionDialogEvent::Cancelled => {
                 // Close the connection dialog
@@ -468,12 +696,181 @@ impl Workspace {
         }
     }
 
+    /// Make a newly registered connection the active one.
+    fn activate_connection(&mut self, connection_id: Uuid, cx: &mut Context<Self>) {
+        // Store the active connection ID
+        self.active_connection_id = Some(connection_id);
+
+        // Update status bar connection status (T046)
+        #[cfg(feature = "persistence")]
+        {
+            use tusk_core::TuskState;
+            if let Some(state) = cx.try_global::<TuskState>() {
+                if let Some(config) = state.get_connection_config(&connection_id) {
+                    let search_path =
+                        state.get_connection(&connection_id).and_then(|pool| pool.search_path());
+                    self.connection_status =
+                        ConnectionStatus::connected(&config, search_path.as_deref());
+                }
+            }
+        }
+
+        // The new connection moves to the top of the recent list
+        self.home.update(cx, |home, cx| home.reload(cx));
+        #[cfg(feature = "persistence")]
+        self.connections_panel.update(cx, |panel, cx| panel.refresh(cx));
+        #[cfg(feature = "persistence")]
+        self.notifications_panel
+            .update(cx, |panel, cx| panel.set_connection(Some(connection_id), cx));
+
+        // Trigger schema refresh (T048)
+        self.refresh_schema(connection_id, cx);
+
+        cx.notify();
+    }
+
+    /// Forget connections closed from the connections panel.
+    ///
+    /// If the active connection was among them, the status bar shows
+    /// disconnected and the schema browser is cleared.
+    #[cfg(feature = "persistence")]
+    fn connections_closed(&mut self, connection_ids: &[Uuid], cx: &mut Context<Self>) {
+        let message = match connection_ids {
+            [_] => "Disconnected 1 connection".to_string(),
+            ids => format!("Disconnected {} connections", ids.len()),
+        };
+        self.messages_panel.update(cx, |panel, cx| panel.add_message(Message::info(message), cx));
+        self.notifications_panel
+            .update(cx, |panel, cx| panel.connections_closed(connection_ids, cx));
+
+        if self.active_connection_id.is_some_and(|id| connection_ids.contains(&id)) {
+            self.active_connection_id = None;
+            self.connection_status = ConnectionStatus::Disconnected;
+            self.schema_browser.update(cx, |browser, cx| browser.set_schema(Vec::new(), cx));
+        }
+        cx.notify();
+    }
+
+    /// Connect to the saved connection marked "Connect on startup", if any.
+    ///
+    /// Uses the stored password and retries transient failures with
+    /// backoff, since the server may still be starting. Connections without
+    /// a stored password are left for the user to open from the dialog.
+    #[cfg(feature = "persistence")]
+    pub fn connect_on_startup(&mut self, cx: &mut Context<Self>) {
+        use tusk_core::services::{ConnectionPool, RetryPolicy};
+        use tusk_core::{QueryHandle, TuskState};
+
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let config = match state.load_saved_connections() {
+            Ok(configs) => configs.into_iter().find(|config| config.options.connect_on_startup),
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load saved connections");
+                return;
+            }
+        };
+        let Some(config) = config else {
+            return;
+        };
+        let password = match state.credentials().get_password(config.id) {
+            Ok(Some(password)) => password,
+            Ok(None) => {
+                tracing::info!(
+                    connection_id = %config.id,
+                    "No stored password, skipping connect on startup"
+                );
+                return;
+            }
+            Err(e) => {
+                tracing::warn!(connection_id = %config.id, error = %e, "Failed to read password");
+                return;
+            }
+        };
+
+        tracing::info!(connection_id = %config.id, "Connecting on startup");
+        let runtime_handle = state.runtime().handle().clone();
+        let handle = Arc::new(QueryHandle::new(config.id, "connect on startup"));
+        self.startup_connect = Some(handle.clone());
+        self.connection_status = ConnectionStatus::Connecting;
+        cx.notify();
+
+        cx.spawn(async move |this, cx| {
+            let connect_handle = handle.clone();
+            let connect_config = config.clone();
+            let result = runtime_handle
+                .spawn(async move {
+                    ConnectionPool::connect_with_retry(
+                        connect_config,
+                        &password,
+                        RetryPolicy::default(),
+                        &connect_handle,
+                    )
+                    .await
+                })
+                .await;
+
+            let _ = this.update(cx, |workspace, cx| {
+                // Cancelled when the user opened the connection dialog
+                if !workspace.startup_connect.as_ref().is_some_and(|h| h.id() == handle.id()) {
+                    return;
+                }
+                workspace.startup_connect = None;
+
+                let error = match result {
+                    Ok(Ok(pool)) => {
+                        if let Some(state) = cx.try_global::<TuskState>() {
+                            state.add_connection_arc(config.clone(), Arc::new(pool));
+                            state.record_connected(config.id);
+                        }
+                        workspace.activate_connection(config.id, cx);
+                        return;
+                    }
+                    Ok(Err(e)) if e.is_cancelled() => return,
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) => format!("Connection task failed: {e}"),
+                };
+                tracing::warn!(connection_id = %config.id, error = %error, "Connect on startup failed");
+                workspace.connection_status = ConnectionStatus::Error(error.clone().into());
+                workspace.messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(
+                        Message::error(format!("Could not connect to {}: {error}", config.name)),
+                        cx,
+                    );
+                });
+                cx.notify();
+            });
+        })
+        .detach();
+    }
+
+    /// Connect on startup placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn connect_on_startup(&mut self, _cx: &mut Context<Self>) {}
+
+    /// Stop a pending connect-on-startup attempt.
+    #[cfg(feature = "persistence")]
+    fn cancel_startup_connect(&mut self, cx: &mut Context<Self>) {
+        if let Some(handle) = self.startup_connect.take() {
+            handle.cancel();
+            self.connection_status = ConnectionStatus::Disconnected;
+            cx.notify();
+        }
+    }
+
+    /// Cancel startup connect placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn cancel_startup_connect(&mut self, _cx: &mut Context<Self>) {}
+
     /// Refresh schema data from the database (T048).
     #[cfg(feature = "persistence")]
     fn refresh_schema(&mut self, connection_id: Uuid, cx: &mut Context<Self>) {
-        use crate::panels::database_schema_to_tree;
         use tusk_core::services::SchemaService;
-        use tusk_core::TuskState;
+        use tusk_core::{QueryHandle, TuskState};
+
+        // A newer load replaces one still running
+        self.cancel_schema_load(cx);
 
         // Set loading state
         self.schema_browser.update(cx, |panel, cx| {
@@ -497,24 +894,42 @@ impl Workspace {
             return;
         };
 
+        let pinned = state.storage().load_pinned_tables(connection_id).unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to load pinned tables");
+            Vec::new()
+        });
+
         let runtime_handle = state.runtime().handle().clone();
         let schema_browser = self.schema_browser.clone();
+        let handle = Arc::new(QueryHandle::new(connection_id, "load schema"));
+        self.schema_load = Some(handle.clone());
 
-        cx.spawn(async move |_this, cx| {
+        cx.spawn(async move |this, cx| {
             // Fetch schema on tokio runtime
+            let load_handle = handle.clone();
             let result = runtime_handle
                 .spawn(async move {
                     let conn = pool.get().await?;
-                    SchemaService::load_schema(&conn).await
+                    SchemaService::load_schema(&conn, &load_handle).await
                 })
                 .await;
 
+            let _ = this.update(cx, |workspace, _| {
+                if workspace.schema_load.as_ref().is_some_and(|h| h.id() == handle.id()) {
+                    workspace.schema_load = None;
+                }
+            });
+            // A cancelled load was replaced or abandoned; its panel state is already set
+            if matches!(result, Ok(Err(ref e)) if e.is_cancelled()) {
+                return;
+            }
+
             schema_browser.update(cx, |panel, cx| {
                 panel.set_loading(false, cx);
                 match result {
                     Ok(Ok(schema)) => {
-                        let tree_items = database_schema_to_tree(&schema);
-                        panel.set_schema(tree_items, cx);
+                        panel.set_pinned_tables(pinned, cx);
+                        panel.set_database_schema(schema, cx);
                         panel.set_error(None, cx);
                     }
                     Ok(Err(e)) => {
@@ -529,69 +944,971 @@ impl Workspace {
         .detach();
     }
 
+    /// Cancel the schema load in progress, if any.
+    #[cfg(feature = "persistence")]
+    fn cancel_schema_load(&mut self, cx: &mut Context<Self>) {
+        if let Some(handle) = self.schema_load.take() {
+            handle.cancel();
+            self.schema_browser.update(cx, |panel, cx| panel.set_loading(false, cx));
+        }
+    }
+
+    /// Persist the schema browser's pinned tables for the active connection.
+    #[cfg(feature = "persistence")]
+    fn save_pinned_tables(&self, tables: &[String], cx: &App) {
+        use tusk_core::TuskState;
+
+        let Some(connection_id) = self.active_connection_id else {
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        if let Err(e) = state.storage().save_pinned_tables(connection_id, tables) {
+            tracing::warn!(error = %e, "Failed to save pinned tables");
+        }
+    }
+
+    /// Save pinned tables placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn save_pinned_tables(&self, _tables: &[String], _cx: &App) {}
+
+    /// Cancel schema load placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn cancel_schema_load(&mut self, _cx: &mut Context<Self>) {}
+
     /// Refresh schema placeholder for non-persistence builds.
     #[cfg(not(feature = "persistence"))]
     fn refresh_schema(&mut self, _connection_id: Uuid, _cx: &mut Context<Self>) {
         // No-op for non-persistence builds
     }
 
-    /// Get the current execution state.
-    pub fn execution_state(&self) -> &ExecutionState {
-        &self.execution_state
-    }
+    /// Fetch an object's DDL and open it in a read-only editor tab.
+    #[cfg(feature = "persistence")]
+    fn show_definition(&mut self, target: DefinitionTarget, cx: &mut Context<Self>) {
+        use tusk_core::services::SchemaService;
+        use tusk_core::TuskState;
 
-    /// Set the execution state (updates the status bar).
-    pub fn set_execution_state(&mut self, state: ExecutionState, cx: &mut Context<Self>) {
-        self.execution_state = state;
-        cx.notify();
-    }
+        let Some(connection_id) = self.active_connection_id else {
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
 
-    /// Get the active pane from the center pane group.
-    pub fn active_pane(&self, cx: &App) -> Entity<Pane> {
-        self.center.read(cx).active_pane().clone()
-    }
+        cx.spawn(async move |this, cx| {
+            let title = target.title();
+            let result = runtime_handle
+                .spawn(async move {
+                    let conn = pool.get().await?;
+                    match target {
+                        DefinitionTarget::Table { schema, name } => {
+                            SchemaService::get_table_ddl(&conn, &schema, &name).await
+                        }
+                        DefinitionTarget::View { schema, name } => {
+                            SchemaService::get_view_definition(&conn, &schema, &name).await
+                        }
+                        DefinitionTarget::Function { schema, name, arguments } => {
+                            SchemaService::get_function_definition(
+                                &conn, &schema, &name, &arguments,
+                            )
+                            .await
+                        }
+                    }
+                })
+                .await;
 
-    /// Toggle dock visibility by position.
-    pub fn toggle_dock(&mut self, position: DockPosition, cx: &mut Context<Self>) {
-        match position {
-            DockPosition::Left => {
-                self.left_dock.update(cx, |dock, cx| {
-                    dock.toggle_visibility(cx);
-                });
-            }
-            DockPosition::Right => {
-                if let Some(right_dock) = &self.right_dock {
-                    right_dock.update(cx, |dock, cx| {
-                        dock.toggle_visibility(cx);
-                    });
-                }
-            }
-            DockPosition::Bottom => {
-                self.bottom_dock.update(cx, |dock, cx| {
-                    dock.toggle_visibility(cx);
+            let _ = this.update(cx, |workspace, cx| {
+                let error = match result {
+                    Ok(Ok(definition)) => {
+                        workspace.open_definition_tab(title, definition, cx);
+                        return;
+                    }
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) => format!("Definition fetch failed: {e}"),
+                };
+                workspace.messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(Message::error(error), cx);
                 });
-            }
-        }
-    }
-
-    /// Open a new tab in the active pane.
-    pub fn open_tab(&mut self, item: TabItem, cx: &mut Context<Self>) {
-        self.center.update(cx, |pane_group, cx| {
-            let active_pane = pane_group.active_pane();
-            active_pane.update(cx, |pane, cx| {
-                pane.add_tab(item, cx);
             });
-        });
+        })
+        .detach();
     }
 
-    /// Split the active pane along the given axis.
-    pub fn split_pane(&mut self, axis: Axis, window: &mut Window, cx: &mut Context<Self>) {
-        self.center.update(cx, |pane_group, cx| {
-            pane_group.split_active_pane(axis, window, cx);
-        });
+    /// Show definition placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn show_definition(&mut self, _target: DefinitionTarget, _cx: &mut Context<Self>) {
+        // No-op for non-persistence builds
     }
 
-    /// Close the active tab.
+    /// Refresh a materialized view and report the outcome in the messages panel.
+    #[cfg(feature = "persistence")]
+    fn refresh_materialized_view(
+        &mut self,
+        schema: String,
+        name: String,
+        concurrently: bool,
+        cx: &mut Context<Self>,
+    ) {
+        use tusk_core::services::SchemaService;
+        use tusk_core::TuskState;
+
+        let Some(connection_id) = self.active_connection_id else {
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+        let messages_panel = self.messages_panel.clone();
+
+        cx.spawn(async move |_this, cx| {
+            let view = format!("{}.{}", schema, name);
+            let result = runtime_handle
+                .spawn(async move {
+                    SchemaService::refresh_materialized_view(&pool, &schema, &name, concurrently)
+                        .await
+                })
+                .await;
+
+            let messages = match result {
+                Ok(Ok(())) => vec![Message::success(format!("Refreshed materialized view {view}"))],
+                Ok(Err(e)) => {
+                    let mut messages = vec![Message::error(e.to_string())];
+                    if let Some(hint) = e.hint() {
+                        messages.push(Message::info(hint));
+                    }
+                    messages
+                }
+                Err(e) => vec![Message::error(format!("Refresh of {view} failed: {e}"))],
+            };
+            messages_panel.update(cx, |panel, cx| panel.add_messages(messages, cx));
+        })
+        .detach();
+    }
+
+    /// Materialized view refresh placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn refresh_materialized_view(
+        &mut self,
+        _schema: String,
+        _name: String,
+        _concurrently: bool,
+        _cx: &mut Context<Self>,
+    ) {
+        // No-op for non-persistence builds
+    }
+
+    /// Ask the user to type the table name before truncating or dropping it.
+    ///
+    /// Read-only connections are refused before the dialog is shown.
+    #[cfg(feature = "persistence")]
+    fn confirm_table_operation(
+        &mut self,
+        operation: TableOperation,
+        schema: String,
+        name: String,
+        cx: &mut Context<Self>,
+    ) {
+        use crate::confirm_dialog::ConfirmDialogEvent;
+        use tusk_core::TuskState;
+
+        let Some(connection_id) = self.active_connection_id else {
+            return;
+        };
+        let Some(pool) =
+            cx.try_global::<TuskState>().and_then(|state| state.get_connection(&connection_id))
+        else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            return;
+        };
+        if let Err(e) = operation.ensure_allowed(pool.config(), &schema, &name) {
+            let mut messages = vec![Message::error(e.to_string())];
+            if let Some(hint) = e.hint() {
+                messages.push(Message::info(hint));
+            }
+            self.messages_panel.update(cx, |panel, cx| panel.add_messages(messages, cx));
+            return;
+        }
+
+        let (title, message, confirm_label) = match operation {
+            TableOperation::Truncate => (
+                "Truncate Table?",
+                format!("This permanently deletes every row in {schema}.{name}."),
+                "Truncate",
+            ),
+            TableOperation::Drop => (
+                "Drop Table?",
+                format!("This permanently deletes {schema}.{name} and all of its data."),
+                "Drop Table",
+            ),
+        };
+        let dialog = cx.new(|cx| {
+            ConfirmDialog::type_to_confirm(title, message, name.clone(), cx)
+                .with_confirm_label(confirm_label)
+        });
+        let subscription = cx.subscribe(&dialog, move |this, _, event: &ConfirmDialogEvent, cx| {
+            this.confirm_dialog = None;
+            this._dialog_subscription = None;
+            // Runs like any query, so it lands in the history and Messages
+            if matches!(event, ConfirmDialogEvent::Confirmed) {
+                let sql = operation.sql(&schema, &name);
+                this.run_sql(connection_id, name.clone().into(), sql, cx);
+            }
+            cx.notify();
+        });
+
+        self.confirm_dialog = Some(dialog);
+        self._dialog_subscription = Some(subscription);
+        cx.notify();
+    }
+
+    /// Table operation placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn confirm_table_operation(
+        &mut self,
+        _operation: TableOperation,
+        _schema: String,
+        _name: String,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    /// Sample a column's most frequent values for the schema browser details.
+    ///
+    /// Starting another sample cancels the one in progress.
+    #[cfg(feature = "persistence")]
+    fn sample_column(
+        &mut self,
+        column_id: String,
+        schema: String,
+        table: String,
+        column: String,
+        cx: &mut Context<Self>,
+    ) {
+        use tusk_core::services::schema::DEFAULT_SAMPLE_LIMIT;
+        use tusk_core::services::SchemaService;
+        use tusk_core::{QueryHandle, TuskState};
+
+        let schema_browser = self.schema_browser.clone();
+        let Some(connection_id) = self.active_connection_id else {
+            schema_browser.update(cx, |panel, cx| panel.clear_column_sample(cx));
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            schema_browser.update(cx, |panel, cx| panel.clear_column_sample(cx));
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+
+        if let Some(previous) = self.column_sample.take() {
+            previous.cancel();
+        }
+        let handle = Arc::new(QueryHandle::new(connection_id, format!("sample {column_id}")));
+        self.column_sample = Some(handle.clone());
+        let messages_panel = self.messages_panel.clone();
+
+        cx.spawn(async move |this, cx| {
+            let task_handle = handle.clone();
+            let result = runtime_handle
+                .spawn(async move {
+                    SchemaService::sample_column(
+                        &pool,
+                        &schema,
+                        &table,
+                        &column,
+                        DEFAULT_SAMPLE_LIMIT,
+                        &task_handle,
+                    )
+                    .await
+                })
+                .await;
+
+            let _ = this.update(cx, |workspace, cx| {
+                if workspace.column_sample.as_ref().is_some_and(|h| h.id() == handle.id()) {
+                    workspace.column_sample = None;
+                }
+                let error = match result {
+                    Ok(Ok(sample)) => {
+                        schema_browser.update(cx, |panel, cx| {
+                            panel.set_column_sample(column_id, sample, cx);
+                        });
+                        return;
+                    }
+                    // A newer sample replaced this one
+                    Ok(Err(e)) if e.is_cancelled() => return,
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) => format!("Sampling {column_id} failed: {e}"),
+                };
+                schema_browser.update(cx, |panel, cx| panel.clear_column_sample(cx));
+                messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(Message::error(error), cx);
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Column sampling placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn sample_column(
+        &mut self,
+        _column_id: String,
+        _schema: String,
+        _table: String,
+        _column: String,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    /// Load the columns of a table or view the schema browser deferred.
+    #[cfg(feature = "persistence")]
+    fn load_columns(&mut self, schema: String, name: String, cx: &mut Context<Self>) {
+        use tusk_core::services::SchemaService;
+        use tusk_core::TuskState;
+
+        let schema_browser = self.schema_browser.clone();
+        let Some(connection_id) = self.active_connection_id else {
+            schema_browser.update(cx, |panel, cx| panel.cancel_column_load(&schema, &name, cx));
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            schema_browser.update(cx, |panel, cx| panel.cancel_column_load(&schema, &name, cx));
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+        let messages_panel = self.messages_panel.clone();
+
+        cx.spawn(async move |this, cx| {
+            let (task_schema, task_name) = (schema.clone(), name.clone());
+            let result = runtime_handle
+                .spawn(async move {
+                    SchemaService::load_table_columns(&pool, &task_schema, &task_name).await
+                })
+                .await;
+
+            let _ = this.update(cx, |workspace, cx| {
+                // The browser has moved on to another connection
+                if workspace.active_connection_id != Some(connection_id) {
+                    return;
+                }
+                let error = match result {
+                    Ok(Ok(columns)) => {
+                        schema_browser.update(cx, |panel, cx| {
+                            panel.set_columns(&schema, &name, columns, cx);
+                        });
+                        return;
+                    }
+                    Ok(Err(e)) => e.to_string(),
+                    Err(e) => e.to_string(),
+                };
+                let error = format!("Loading columns of {schema}.{name} failed: {error}");
+                schema_browser.update(cx, |panel, cx| panel.cancel_column_load(&schema, &name, cx));
+                messages_panel.update(cx, |panel, cx| {
+                    panel.add_message(Message::error(error), cx);
+                });
+            });
+        })
+        .detach();
+    }
+
+    /// Column loading placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn load_columns(&mut self, _schema: String, _name: String, _cx: &mut Context<Self>) {}
+
+    /// Show the planner's row and cost estimate for the active tab's query.
+    ///
+    /// Runs a plain `EXPLAIN`, so the query itself is never executed.
+    #[cfg(feature = "persistence")]
+    pub fn estimate_query(&mut self, cx: &mut Context<Self>) {
+        use tusk_core::services::QueryService;
+        use tusk_core::{QueryHandle, TuskState};
+
+        let Some(editor) = self.active_query_editor(cx) else {
+            return;
+        };
+        let (sql, editor_connection) = {
+            let editor = editor.read(cx);
+            (editor.content().to_string(), editor.connection_id())
+        };
+        let Some(connection_id) = editor_connection.or(self.active_connection_id) else {
+            let toast = cx.new(|cx| Toast::info("Connect to a database to estimate a query", cx));
+            self.show_toast(toast, cx);
+            return;
+        };
+        if let Err(e) = QueryService::estimate_sql(&sql) {
+            let hint = e.hint().map(str::to_string);
+            let toast = cx.new(|cx| {
+                let toast = Toast::warning(e.to_string(), cx);
+                match hint {
+                    Some(hint) => toast.with_hint(hint),
+                    None => toast,
+                }
+            });
+            self.show_toast(toast, cx);
+            return;
+        }
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+        let handle = QueryHandle::new(connection_id, sql.clone());
+
+        cx.spawn(async move |this, cx| {
+            let result = runtime_handle
+                .spawn(async move {
+                    let conn = pool.get().await?;
+                    QueryService::explain(&conn, &sql, &handle).await
+                })
+                .await;
+
+            let _ = this.update(cx, |this, cx| {
+                let toast = match result {
+                    Ok(Ok(estimate)) => {
+                        let message = format!("Estimate: {}", estimate.summary());
+                        cx.new(|cx| Toast::info(message, cx))
+                    }
+                    Ok(Err(e)) => {
+                        let hint = e.hint().map(str::to_string);
+                        cx.new(|cx| {
+                            let toast = Toast::error(format!("Estimate failed: {e}"), cx);
+                            match hint {
+                                Some(hint) => toast.with_hint(hint),
+                                None => toast,
+                            }
+                        })
+                    }
+                    Err(e) => cx.new(|cx| Toast::error(format!("Estimate failed: {e}"), cx)),
+                };
+                this.show_toast(toast, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Query estimate placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn estimate_query(&mut self, _cx: &mut Context<Self>) {}
+
+    /// Resolve the source table of the displayed result for edit mode.
+    ///
+    /// Editing is refused on read-only connections.
+    #[cfg(feature = "persistence")]
+    fn load_edit_target(&mut self, cx: &mut Context<Self>) {
+        use tusk_core::services::SchemaService;
+        use tusk_core::TuskState;
+
+        let results_panel = self.results_panel.clone();
+        let refuse = |reason: &str, cx: &mut Context<Self>| {
+            results_panel.update(cx, |panel, cx| panel.refuse_edit(reason, cx));
+        };
+
+        let Some(connection_id) = self.active_connection_id else {
+            refuse("Connect to a database to edit results", cx);
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            refuse("Connect to a database to edit results", cx);
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+        if pool.config().options.read_only {
+            refuse("Results can't be edited: connection is read-only", cx);
+            return;
+        }
+        let Some(data) = self.results_panel.read(cx).export_data() else {
+            refuse("Results can't be edited until the query completes", cx);
+            return;
+        };
+
+        cx.spawn(async move |_this, cx| {
+            let result = runtime_handle
+                .spawn(async move {
+                    let conn = pool.get().await?;
+                    SchemaService::load_edit_target(&conn, &data.columns).await
+                })
+                .await
+                .unwrap_or_else(|e| Err(tusk_core::TuskError::internal(e.to_string())));
+            results_panel.update(cx, |panel, cx| panel.set_edit_target(result, cx));
+        })
+        .detach();
+    }
+
+    /// Edit target placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn load_edit_target(&mut self, cx: &mut Context<Self>) {
+        self.results_panel.update(cx, |panel, cx| {
+            panel.refuse_edit("Connect to a database to edit results", cx);
+        });
+    }
+
+    /// Copy the selected result rows as `INSERT` statements into their source
+    /// table, which is looked up on the active connection.
+    #[cfg(feature = "persistence")]
+    fn copy_selection_as_inserts(&mut self, cx: &mut Context<Self>) {
+        use tusk_core::services::SchemaService;
+        use tusk_core::{TuskError, TuskState};
+
+        let Some(data) = self.results_panel.read(cx).selection_data() else {
+            return;
+        };
+        let Some(connection_id) = self.active_connection_id else {
+            let toast = cx.new(|cx| Toast::error("Connect to a database to copy INSERTs", cx));
+            self.show_toast(toast, cx);
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        let Some(pool) = state.get_connection(&connection_id) else {
+            tracing::warn!(connection_id = %connection_id, "Connection not found");
+            return;
+        };
+        let runtime_handle = state.runtime().handle().clone();
+
+        cx.spawn(async move |this, cx| {
+            let result = runtime_handle
+                .spawn(async move {
+                    let conn = pool.get().await?;
+                    let target = SchemaService::load_insert_target(&conn, &data.columns).await?;
+                    let table = format!("{}.{}", target.schema, target.table);
+                    Ok::<_, TuskError>((table, data.rows.len(), target.insert_sql(&data.rows)))
+                })
+                .await
+                .unwrap_or_else(|e| Err(TuskError::internal(e.to_string())));
+            let _ = this.update(cx, |this, cx| match result {
+                Ok((table, count, sql)) => {
+                    cx.write_to_clipboard(gpui::ClipboardItem::new_string(sql));
+                    let message = format!(
+                        "Copied {} INSERT statement{} for {}",
+                        count,
+                        if count == 1 { "" } else { "s" },
+                        table
+                    );
+                    this.messages_panel
+                        .update(cx, |panel, cx| panel.add_message(Message::info(message), cx));
+                }
+                Err(e) => {
+                    let hint = e.hint().map(str::to_string);
+                    let toast = cx.new(|cx| {
+                        let toast = Toast::error(e.to_string(), cx);
+                        match hint {
+                            Some(hint) => toast.with_hint(hint),
+                            None => toast,
+                        }
+                    });
+                    this.show_toast(toast, cx);
+                }
+            });
+        })
+        .detach();
+    }
+
+    /// Copy-as-INSERT placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn copy_selection_as_inserts(&mut self, _cx: &mut Context<Self>) {}
+
+    /// Open a query tab holding a generated `UPDATE` for review.
+    fn open_update_tab(&mut self, table: &str, sql: String, cx: &mut Context<Self>) {
+        let editor = self.create_query_editor(self.active_connection_id, cx);
+        editor.update(cx, |editor, cx| editor.set_content(sql, cx));
+
+        let tab = TabItem::new(format!("Update {}", table), editor)
+            .with_icon(IconName::Edit)
+            .with_accent(self.connection_accent(cx));
+        self.open_tab(tab, cx);
+    }
+
+    /// Run SQL on a connection in a new query tab titled `title`, streaming
+    /// its results into the results panel.
+    ///
+    /// The run goes through the tab's editor, so it gets the usual row limit,
+    /// destructive-query confirmation, status bar updates, and history entry.
+    pub fn run_sql(
+        &mut self,
+        connection_id: Uuid,
+        title: SharedString,
+        sql: String,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = self.create_query_editor(Some(connection_id), cx);
+        self.open_editor_tab(title, editor.clone(), cx);
+
+        tracing::debug!(connection_id = %connection_id, "Running SQL");
+        editor.update(cx, |editor, cx| {
+            editor.set_content(sql, cx);
+            editor.execute_query(cx);
+        });
+    }
+
+    /// Open a query tab for `editor` in the active pane.
+    fn open_editor_tab(
+        &mut self,
+        title: SharedString,
+        editor: Entity<QueryEditor>,
+        cx: &mut Context<Self>,
+    ) {
+        let tab = TabItem::new(title, editor)
+            .with_icon(IconName::Code)
+            .with_accent(self.connection_accent(cx));
+        self.open_tab(tab, cx);
+    }
+
+    /// Title for the next untitled query tab in the active pane.
+    fn next_query_title(&self, cx: &App) -> SharedString {
+        let query_count = self.center.read(cx).active_pane().read(cx).tabs().len() + 1;
+        format!("Query {}", query_count).into()
+    }
+
+    /// Open a read-only editor tab showing an object definition.
+    pub fn open_definition_tab(
+        &mut self,
+        title: impl Into<SharedString>,
+        definition: String,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = cx.new(|cx| {
+            let mut editor = QueryEditor::new(cx);
+            editor.set_content(definition, cx);
+            editor.set_read_only(true, cx);
+            editor
+        });
+
+        self.open_tab(TabItem::new(title, editor).with_icon(IconName::File), cx);
+    }
+
+    /// Get the current execution state.
+    pub fn execution_state(&self) -> &ExecutionState {
+        &self.execution_state
+    }
+
+    /// Set the execution state (updates the status bar).
+    pub fn set_execution_state(&mut self, state: ExecutionState, cx: &mut Context<Self>) {
+        self.execution_state = state;
+        cx.notify();
+    }
+
+    /// Advance the execution state with a query lifecycle event.
+    ///
+    /// Starts the elapsed-time ticker when a query starts, and drops the
+    /// ticker and held query handle once the query is no longer running.
+    fn apply_execution_event(&mut self, event: ExecutionEvent, cx: &mut Context<Self>) {
+        let started = event == ExecutionEvent::Started;
+        #[cfg(feature = "persistence")]
+        {
+            self.log_query(&event, cx);
+            self.record_history(&event, cx);
+        }
+        self.execution_state = self.execution_state.apply(event);
+
+        if started {
+            self.selection_summary = None;
+            self.start_elapsed_ticker(cx);
+        } else if !self.execution_state.is_running() {
+            self._elapsed_ticker = None;
+            #[cfg(feature = "persistence")]
+            {
+                self.active_query = None;
+            }
+        }
+        cx.notify();
+    }
+
+    /// Add a finished query to the Messages panel's query log.
+    #[cfg(feature = "persistence")]
+    fn log_query(&mut self, event: &ExecutionEvent, cx: &mut Context<Self>) {
+        use tusk_core::services::QueryService;
+
+        let results_panel = self.results_panel.read(cx);
+        let Some(sql) = results_panel.last_run_sql().map(str::to_string) else {
+            return;
+        };
+        let statements = QueryService::split_statements(&sql).len();
+        let (command, rows) = if statements > 1 {
+            (format!("{} statements", statements), None)
+        } else {
+            let tag = QueryService::command_tag(&sql);
+            let command = if tag.is_empty() { "query".to_string() } else { tag };
+            let rows = match event {
+                ExecutionEvent::Completed { rows, .. } => self.completed_rows(*rows, cx),
+                _ => None,
+            };
+            (command, rows)
+        };
+
+        let Some(message) = Message::query_log(&command, event, rows) else {
+            return;
+        };
+        let message = message
+            .with_timestamp(chrono::Local::now().format("%H:%M:%S").to_string())
+            .with_sql(QueryService::fingerprint(&sql), sql);
+        self.messages_panel.update(cx, |panel, cx| panel.add_message(message, cx));
+    }
+
+    /// Rows a completed run returned, or affected for commands without results.
+    #[cfg(feature = "persistence")]
+    fn completed_rows(&self, returned: usize, cx: &App) -> Option<u64> {
+        let state = self.results_panel.read(cx).state();
+        if state.command_status.is_some() {
+            state.rows_affected
+        } else {
+            Some(returned as u64)
+        }
+    }
+
+    /// Save a finished query to its connection's history.
+    #[cfg(feature = "persistence")]
+    fn record_history(&self, event: &ExecutionEvent, cx: &App) {
+        use tusk_core::{QueryHistoryEntry, TuskState};
+
+        let Some(handle) = &self.active_query else {
+            return;
+        };
+        let entry = match event {
+            ExecutionEvent::Completed { rows, elapsed_us } => {
+                let rows = self.completed_rows(*rows, cx);
+                let sql = handle.source_sql();
+                QueryHistoryEntry::from_run(handle.connection_id(), sql, rows, *elapsed_us)
+            }
+            ExecutionEvent::Failed(message) => {
+                QueryHistoryEntry::from_error(handle.connection_id(), handle.source_sql(), message)
+            }
+            _ => return,
+        };
+
+        if let Some(state) = cx.try_global::<TuskState>() {
+            if let Err(e) = state.storage().add_to_history(&entry) {
+                tracing::warn!(error = %e, "Failed to record query history");
+            }
+        }
+    }
+
+    /// Re-render the status bar periodically so the elapsed time stays live.
+    fn start_elapsed_ticker(&mut self, cx: &mut Context<Self>) {
+        self._elapsed_ticker = Some(cx.spawn(async move |this, cx| loop {
+            cx.background_executor().timer(ELAPSED_TICK_INTERVAL).await;
+            let still_running = this.update(cx, |workspace, cx| {
+                let running = workspace.execution_state.is_running();
+                if running {
+                    cx.notify();
+                }
+                running
+            });
+            if !matches!(still_running, Ok(true)) {
+                break;
+            }
+        }));
+    }
+
+    /// Handle query editor events (FR-013).
+    fn handle_query_editor_event(
+        &mut self,
+        editor: Entity<QueryEditor>,
+        event: &QueryEditorEvent,
+        cx: &mut Context<Self>,
+    ) {
+        match event {
+            #[cfg(feature = "persistence")]
+            QueryEditorEvent::Started(handle) => {
+                self.active_query = Some(handle.clone());
+                self.apply_execution_event(ExecutionEvent::Started, cx);
+            }
+            QueryEditorEvent::CancelRequested => {
+                self.apply_execution_event(ExecutionEvent::CancelRequested, cx);
+            }
+            QueryEditorEvent::Failed(message) => {
+                self.apply_execution_event(ExecutionEvent::Failed(message.clone().into()), cx);
+            }
+            QueryEditorEvent::FileStateChanged => self.sync_query_tab(&editor, cx),
+            QueryEditorEvent::SearchPathChanged(search_path) => {
+                self.update_search_path(&editor, search_path, cx);
+            }
+            QueryEditorEvent::SchemaChanged => self.schedule_schema_refresh(&editor, cx),
+            QueryEditorEvent::TransactionStatusChanged => cx.notify(),
+        }
+    }
+
+    /// Apply a search_path change to every connection in the editor's pool,
+    /// and show it in the status bar if that is the active connection.
+    #[cfg(feature = "persistence")]
+    fn update_search_path(
+        &mut self,
+        editor: &Entity<QueryEditor>,
+        search_path: &str,
+        cx: &mut Context<Self>,
+    ) {
+        use tusk_core::TuskState;
+
+        let Some(connection_id) = editor.read(cx).connection_id() else {
+            return;
+        };
+        let Some(state) = cx.try_global::<TuskState>() else {
+            return;
+        };
+        if let Some(pool) = state.get_connection(&connection_id) {
+            pool.set_search_path(search_path);
+        }
+        if self.active_connection_id != Some(connection_id) {
+            return;
+        }
+        let Some(config) = state.get_connection_config(&connection_id) else {
+            return;
+        };
+
+        self.connection_status = ConnectionStatus::connected(&config, Some(search_path));
+        cx.notify();
+    }
+
+    /// Update search_path placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn update_search_path(
+        &mut self,
+        _editor: &Entity<QueryEditor>,
+        _search_path: &str,
+        _cx: &mut Context<Self>,
+    ) {
+    }
+
+    /// Reload the schema browser once DDL on the active connection settles.
+    ///
+    /// The editor has already invalidated the connection's schema cache.
+    #[cfg(feature = "persistence")]
+    fn schedule_schema_refresh(&mut self, editor: &Entity<QueryEditor>, cx: &mut Context<Self>) {
+        let Some(connection_id) = editor.read(cx).connection_id() else {
+            return;
+        };
+        if self.active_connection_id != Some(connection_id) {
+            return;
+        }
+        self.schema_refresh.call(
+            connection_id,
+            cx,
+            |workspace| &mut workspace.schema_refresh,
+            |workspace, connection_id, cx| {
+                // Skip if the user switched connections while waiting
+                if workspace.active_connection_id == Some(connection_id) {
+                    tracing::debug!(%connection_id, "Refreshing schema after DDL");
+                    workspace.refresh_schema(connection_id, cx);
+                }
+            },
+        );
+    }
+
+    /// Schema refresh placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn schedule_schema_refresh(&mut self, _editor: &Entity<QueryEditor>, _cx: &mut Context<Self>) {}
+
+    /// Mirror an editor's file name and dirty state onto its tab.
+    fn sync_query_tab(&mut self, editor: &Entity<QueryEditor>, cx: &mut Context<Self>) {
+        let file = editor.read(cx).file();
+        let Some(title) = file.title() else {
+            return;
+        };
+        let dirty = file.is_dirty();
+        let title = SharedString::from(title);
+        let view_id = editor.entity_id();
+        let panes = self.center.read(cx).panes();
+        for pane in panes {
+            pane.update(cx, |pane, cx| pane.update_tab(view_id, title.clone(), dirty, cx));
+        }
+    }
+
+    /// Cancel the running query from the status bar (FR-013).
+    #[cfg(feature = "persistence")]
+    pub fn cancel_active_query(&mut self, cx: &mut Context<Self>) {
+        use crate::panels::Message;
+
+        let Some(handle) = self.active_query.clone() else {
+            return;
+        };
+
+        tracing::debug!(query_id = %handle.id(), "Cancelling query from status bar");
+        handle.cancel();
+
+        self.messages_panel.update(cx, |panel, cx| {
+            panel.add_message(Message::info("Query cancelled"), cx);
+        });
+
+        self.apply_execution_event(ExecutionEvent::CancelRequested, cx);
+    }
+
+    /// Cancel query placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn cancel_active_query(&mut self, cx: &mut Context<Self>) {
+        let _ = cx;
+    }
+
+    /// Get the active pane from the center pane group.
+    pub fn active_pane(&self, cx: &App) -> Entity<Pane> {
+        self.center.read(cx).active_pane().clone()
+    }
+
+    /// Toggle dock visibility by position.
+    pub fn toggle_dock(&mut self, position: DockPosition, cx: &mut Context<Self>) {
+        match position {
+            DockPosition::Left => {
+                self.left_dock.update(cx, |dock, cx| {
+                    dock.toggle_visibility(cx);
+                });
+            }
+            DockPosition::Right => {
+                if let Some(right_dock) = &self.right_dock {
+                    right_dock.update(cx, |dock, cx| {
+                        dock.toggle_visibility(cx);
+                    });
+                }
+            }
+            DockPosition::Bottom => {
+                self.bottom_dock.update(cx, |dock, cx| {
+                    dock.toggle_visibility(cx);
+                });
+            }
+        }
+    }
+
+    /// Open a new tab in the active pane.
+    pub fn open_tab(&mut self, item: TabItem, cx: &mut Context<Self>) {
+        self.center.update(cx, |pane_group, cx| {
+            let active_pane = pane_group.active_pane();
+            active_pane.update(cx, |pane, cx| {
+                pane.add_tab(item, cx);
+            });
+        });
+    }
+
+    /// Split the active pane along the given axis.
+    pub fn split_pane(&mut self, axis: Axis, window: &mut Window, cx: &mut Context<Self>) {
+        self.center.update(cx, |pane_group, cx| {
+            pane_group.split_active_pane(axis, window, cx);
+        });
+    }
+
+    /// Close the active tab.
     pub fn close_active_tab(&mut self, cx: &mut Context<Self>) {
         self.center.update(cx, |pane_group, cx| {
             let active_pane = pane_group.active_pane();
@@ -601,6 +1918,122 @@ impl Workspace {
         });
     }
 
+    /// Show a toast notification, replacing any current toast.
+    pub fn show_toast(&mut self, toast: Entity<Toast>, cx: &mut Context<Self>) {
+        self.toast_layer.update(cx, |layer, cx| layer.show_toast(toast, cx));
+    }
+
+    /// Export the displayed result to a file chosen in the native save dialog.
+    ///
+    /// The format follows the chosen extension (.csv, .json, .jsonl). The file
+    /// is written on the background executor while a toast shows progress.
+    #[cfg(feature = "persistence")]
+    pub fn export_results(&mut self, cx: &mut Context<Self>) {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+        use tusk_core::models::export::{default_export_dir, export_to_file, ExportFormat};
+
+        /// How often the progress toast is refreshed.
+        const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+        let Some(data) = self.results_panel.read(cx).export_data() else {
+            let toast = cx.new(|cx| Toast::info("No results to export", cx));
+            self.show_toast(toast, cx);
+            return;
+        };
+
+        let path_prompt = cx.prompt_for_new_path(&default_export_dir(), Some("results.csv"));
+
+        cx.spawn(async move |this, cx| {
+            let path = match path_prompt.await {
+                Ok(Ok(Some(path))) => path,
+                // Dialog dismissed
+                Ok(Ok(None)) | Err(_) => return,
+                Ok(Err(e)) => {
+                    let _ = this.update(cx, |this, cx| {
+                        let message = format!("Could not open the save dialog: {e}");
+                        let toast = cx.new(|cx| Toast::error(message, cx));
+                        this.show_toast(toast, cx);
+                    });
+                    return;
+                }
+            };
+
+            let Some(format) = ExportFormat::from_path(&path) else {
+                let _ = this.update(cx, |this, cx| {
+                    let toast = cx.new(|cx| {
+                        Toast::error("Unsupported export file type", cx)
+                            .with_hint("Use a .csv, .json, or .jsonl file name")
+                    });
+                    this.show_toast(toast, cx);
+                });
+                return;
+            };
+
+            let file_name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| path.display().to_string());
+            let total = data.rows.len();
+            let Ok(progress_toast) = this.update(cx, |this, cx| {
+                let message = format!("Exporting {total} rows to {file_name}...");
+                let toast = cx.new(|cx| Toast::info(message, cx));
+                this.show_toast(toast.clone(), cx);
+                toast
+            }) else {
+                return;
+            };
+
+            let written = Arc::new(AtomicUsize::new(0));
+            let finished = Arc::new(AtomicBool::new(false));
+            let export = cx.background_executor().spawn({
+                let written = written.clone();
+                let finished = finished.clone();
+                async move {
+                    let result = export_to_file(&path, format, &data.columns, &data.rows, |n| {
+                        written.store(n, Ordering::Relaxed)
+                    });
+                    finished.store(true, Ordering::Release);
+                    result
+                }
+            });
+
+            while !finished.load(Ordering::Acquire) {
+                cx.background_executor().timer(PROGRESS_INTERVAL).await;
+                let done = written.load(Ordering::Relaxed);
+                let message = format!("Exporting {done} of {total} rows to {file_name}...");
+                let _ = progress_toast.update(cx, |toast, cx| toast.set_message(message, cx));
+            }
+
+            let result = export.await;
+            let _ = this.update(cx, |this, cx| {
+                let toast = match result {
+                    Ok(rows) => {
+                        tracing::info!(rows, format = format.label(), "Exported results");
+                        let message = format!("Exported {rows} rows to {file_name}");
+                        cx.new(|cx| Toast::success(message, cx))
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to export results");
+                        let hint = e.hint().map(str::to_string);
+                        cx.new(|cx| {
+                            let toast = Toast::error(format!("Export failed: {e}"), cx);
+                            match hint {
+                                Some(hint) => toast.with_hint(hint),
+                                None => toast,
+                            }
+                        })
+                    }
+                };
+                this.show_toast(toast, cx);
+            });
+        })
+        .detach();
+    }
+
+    /// Export results placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    pub fn export_results(&mut self, _cx: &mut Context<Self>) {}
+
     /// Focus the next pane.
     pub fn focus_next_pane(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.center.update(cx, |pane_group, cx| {
@@ -615,6 +2048,23 @@ impl Workspace {
         });
     }
 
+    /// Focus the pane spatially adjacent to the active pane.
+    pub fn focus_pane_in_direction(
+        &mut self,
+        direction: FocusDirection,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.center.update(cx, |pane_group, cx| {
+            pane_group.focus_pane_in_direction(direction, window, cx);
+        });
+    }
+
+    /// Toggle zoom on the active pane.
+    pub fn toggle_pane_zoom(&mut self, cx: &mut Context<Self>) {
+        self.center.update(cx, |pane_group, cx| pane_group.toggle_zoom(cx));
+    }
+
     /// Close the active pane.
     ///
     /// If this is the last pane, the pane remains but its tabs are closed.
@@ -669,6 +2119,38 @@ impl Workspace {
         });
     }
 
+    /// Open the go-to-object palette over the schema browser's loaded schema.
+    pub fn open_quick_open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.quick_open.is_some() {
+            return;
+        }
+        let Some(schema) = self.schema_browser.read(cx).database_schema() else {
+            self.messages_panel.update(cx, |panel, cx| {
+                panel
+                    .add_messages(vec![Message::info("Connect to a database to go to objects")], cx)
+            });
+            return;
+        };
+
+        let palette = cx.new(|cx| QuickOpen::new(schema, cx));
+        let subscription =
+            cx.subscribe_in(&palette, window, |this, _, event: &QuickOpenEvent, window, cx| {
+                this.quick_open = None;
+                this._quick_open_subscription = None;
+                if let QuickOpenEvent::Selected(hit) = event {
+                    tracing::debug!(path = %hit.path(), "Going to object");
+                    this.focus_schema_browser(window, cx);
+                    this.schema_browser.update(cx, |sb, cx| sb.reveal_hit(hit, cx));
+                }
+                cx.notify();
+            });
+        palette.read(cx).focus_handle(cx).focus(window, cx);
+
+        self.quick_open = Some(palette);
+        self._quick_open_subscription = Some(subscription);
+        cx.notify();
+    }
+
     /// Focus the results panel in the bottom dock.
     pub fn focus_results(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         // Show the bottom dock if hidden, then focus it
@@ -685,14 +2167,40 @@ impl Workspace {
     /// Creates a QueryEditor with the active connection ID and links it
     /// to the results and messages panels for query output.
     pub fn new_query_tab(&mut self, cx: &mut Context<Self>) {
-        // Count existing tabs to generate a unique title
-        let query_count = self.center.read(cx).active_pane().read(cx).tabs().len() + 1;
-        let title = format!("Query {}", query_count);
+        let title = self.next_query_title(cx);
+        let query_editor = self.create_query_editor(self.active_connection_id, cx);
+        self.open_editor_tab(title, query_editor, cx);
+    }
+
+    /// Tab accent for the active connection's environment tag.
+    #[cfg(feature = "persistence")]
+    fn connection_accent(&self, cx: &App) -> Option<Hsla> {
+        use tusk_core::{ConnectionEnvironment, TuskState};
+
+        let connection_id = self.active_connection_id?;
+        let config = cx.try_global::<TuskState>()?.get_connection_config(&connection_id)?;
+        let colors = &cx.global::<TuskTheme>().colors;
+        match config.environment {
+            ConnectionEnvironment::Dev => None,
+            ConnectionEnvironment::Staging => Some(colors.warning),
+            ConnectionEnvironment::Prod => Some(colors.error),
+        }
+    }
+
+    /// Connection accent placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn connection_accent(&self, _cx: &App) -> Option<Hsla> {
+        None
+    }
 
-        // Create a QueryEditor with the active connection (T047)
+    /// Create a query editor wired to a connection and the output panels.
+    fn create_query_editor(
+        &mut self,
+        connection_id: Option<Uuid>,
+        cx: &mut Context<Self>,
+    ) -> Entity<QueryEditor> {
         let results_panel = self.results_panel.clone();
         let messages_panel = self.messages_panel.clone();
-        let connection_id = self.active_connection_id;
 
         let query_editor = cx.new(|cx| {
             let mut editor = if let Some(conn_id) = connection_id {
@@ -706,11 +2214,163 @@ impl Workspace {
             editor
         });
 
-        let tab = TabItem::new(title, query_editor).with_icon(IconName::Code);
+        // Track the editor's running query for the status bar (FR-013)
+        self._subscriptions.push(cx.subscribe(&query_editor, Self::handle_query_editor_event));
+        query_editor
+    }
+
+    /// Query editor shown in the active tab, if any.
+    fn active_query_editor(&self, cx: &App) -> Option<Entity<QueryEditor>> {
+        let view = self.active_pane(cx).read(cx).active_tab()?.view.clone();
+        view.downcast::<QueryEditor>().ok()
+    }
+
+    /// Open `.sql` files chosen in the native open dialog, one tab per file.
+    pub fn open_sql_file(&mut self, cx: &mut Context<Self>) {
+        let paths_prompt = cx.prompt_for_paths(PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: true,
+            prompt: None,
+        });
+
+        cx.spawn(async move |this, cx| {
+            let paths = match paths_prompt.await {
+                Ok(Ok(Some(paths))) => paths,
+                // Dialog dismissed
+                Ok(Ok(None)) | Err(_) => return,
+                Ok(Err(e)) => {
+                    let _ = this.update(cx, |this, cx| {
+                        let message = format!("Could not open the file dialog: {e}");
+                        let toast = cx.new(|cx| Toast::error(message, cx));
+                        this.show_toast(toast, cx);
+                    });
+                    return;
+                }
+            };
+
+            for path in paths {
+                let read = cx
+                    .background_executor()
+                    .spawn({
+                        let path = path.clone();
+                        async move { std::fs::read_to_string(&path) }
+                    })
+                    .await;
+                let _ = this.update(cx, |this, cx| match read {
+                    Ok(content) => this.open_sql_file_tab(path, content, cx),
+                    Err(e) => {
+                        tracing::warn!(path = %path.display(), error = %e, "Failed to open SQL file");
+                        let message = format!("Could not open {}: {e}", path.display());
+                        let toast = cx.new(|cx| Toast::error(message, cx));
+                        this.show_toast(toast, cx);
+                    }
+                });
+            }
+        })
+        .detach();
+    }
 
+    /// Open a tab with the contents of a `.sql` file, titled by its file name.
+    fn open_sql_file_tab(&mut self, path: PathBuf, content: String, cx: &mut Context<Self>) {
+        let query_editor = self.create_query_editor(self.active_connection_id, cx);
+        query_editor.update(cx, |editor, cx| editor.open_file(path, content, cx));
+        let title = query_editor
+            .read(cx)
+            .file()
+            .title()
+            .unwrap_or_else(|| DEFAULT_SQL_FILE_NAME.to_string());
+
+        let tab = TabItem::new(title, query_editor)
+            .with_icon(IconName::Code)
+            .with_accent(self.connection_accent(cx));
         self.open_tab(tab, cx);
     }
 
+    /// Save the active query tab to its file, asking for a path if it has none.
+    pub fn save_sql_file(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = self.active_query_editor(cx) else {
+            return;
+        };
+        match editor.read(cx).file().path().map(Path::to_path_buf) {
+            Some(path) => self.write_sql_file(editor, path, cx),
+            None => self.save_sql_file_as(cx),
+        }
+    }
+
+    /// Save the active query tab to a file chosen in the native save dialog.
+    pub fn save_sql_file_as(&mut self, cx: &mut Context<Self>) {
+        let Some(editor) = self.active_query_editor(cx) else {
+            return;
+        };
+
+        let current = editor.read(cx).file().path().map(Path::to_path_buf);
+        let directory = current
+            .as_deref()
+            .and_then(Path::parent)
+            .map(Path::to_path_buf)
+            .unwrap_or_else(default_sql_dir);
+        let file_name = current
+            .as_deref()
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| DEFAULT_SQL_FILE_NAME.to_string());
+        let path_prompt = cx.prompt_for_new_path(&directory, Some(&file_name));
+
+        cx.spawn(async move |this, cx| {
+            let path = match path_prompt.await {
+                Ok(Ok(Some(path))) => path,
+                // Dialog dismissed
+                Ok(Ok(None)) | Err(_) => return,
+                Ok(Err(e)) => {
+                    let _ = this.update(cx, |this, cx| {
+                        let message = format!("Could not open the save dialog: {e}");
+                        let toast = cx.new(|cx| Toast::error(message, cx));
+                        this.show_toast(toast, cx);
+                    });
+                    return;
+                }
+            };
+            let _ = this.update(cx, |this, cx| this.write_sql_file(editor, path, cx));
+        })
+        .detach();
+    }
+
+    /// Write an editor's buffer to `path` on the background executor.
+    fn write_sql_file(
+        &mut self,
+        editor: Entity<QueryEditor>,
+        path: PathBuf,
+        cx: &mut Context<Self>,
+    ) {
+        let content = editor.read(cx).content().to_string();
+
+        cx.spawn(async move |this, cx| {
+            let written = cx
+                .background_executor()
+                .spawn({
+                    let path = path.clone();
+                    let content = content.clone();
+                    async move { std::fs::write(&path, content) }
+                })
+                .await;
+
+            let _ = this.update(cx, |this, cx| match written {
+                Ok(()) => {
+                    tracing::debug!(path = %path.display(), "Saved SQL file");
+                    editor.update(cx, |editor, cx| editor.mark_saved(path, content, cx));
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path.display(), error = %e, "Failed to save SQL file");
+                    let message = format!("Could not save {}: {e}", path.display());
+                    let toast = cx.new(|cx| Toast::error(message, cx));
+                    this.show_toast(toast, cx);
+                }
+            });
+        })
+        .detach();
+    }
+
     /// Resize the left dock to the given size.
     pub fn resize_left_dock(&mut self, size: Pixels, cx: &mut Context<Self>) {
         self.left_dock.update(cx, |dock, cx| {
@@ -819,11 +2479,37 @@ impl Workspace {
         context
     }
 
+    /// Transaction badge for the active connection.
+    #[cfg(feature = "persistence")]
+    fn transaction_indicator(&self, cx: &App) -> Option<TransactionIndicator> {
+        let connection_id = self.active_connection_id?;
+        let pool = cx.try_global::<tusk_core::TuskState>()?.get_connection(&connection_id)?;
+        TransactionIndicator::for_status(pool.transaction_status())
+    }
+
+    /// Transaction badge placeholder for non-persistence builds.
+    #[cfg(not(feature = "persistence"))]
+    fn transaction_indicator(&self, _cx: &App) -> Option<TransactionIndicator> {
+        None
+    }
+
     /// Render the status bar.
-    fn render_status_bar(&self, _cx: &App) -> impl IntoElement {
-        StatusBar::new()
+    ///
+    /// Offers a cancel button while a query is executing.
+    fn render_status_bar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let status_bar = StatusBar::new()
             .connection_status(self.connection_status.clone())
             .execution_state(self.execution_state.clone())
+            .selection_summary(self.selection_summary.clone())
+            .transaction(self.transaction_indicator(cx));
+
+        if matches!(self.execution_state, ExecutionState::Running { .. }) {
+            status_bar.on_cancel(cx.listener(|this, _, _, cx| {
+                this.cancel_active_query(cx);
+            }))
+        } else {
+            status_bar
+        }
     }
 }
 
@@ -839,6 +2525,7 @@ impl Render for Workspace {
     /// Performance target: render within 16ms for 60fps
     #[tracing::instrument(level = "trace", skip_all, name = "workspace_render")]
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let status_bar = self.render_status_bar(cx);
         let theme = cx.global::<TuskTheme>();
         let dispatch_context = Self::dispatch_context();
 
@@ -933,12 +2620,36 @@ impl Render for Workspace {
             .on_action(cx.listener(|this, _: &FocusPreviousPane, window, cx| {
                 this.focus_previous_pane(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &FocusPaneLeft, window, cx| {
+                this.focus_pane_in_direction(FocusDirection::Left, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &FocusPaneRight, window, cx| {
+                this.focus_pane_in_direction(FocusDirection::Right, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &FocusPaneUp, window, cx| {
+                this.focus_pane_in_direction(FocusDirection::Up, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &FocusPaneDown, window, cx| {
+                this.focus_pane_in_direction(FocusDirection::Down, window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &ZoomPane, _window, cx| {
+                this.toggle_pane_zoom(cx);
+            }))
             .on_action(cx.listener(|this, _: &ClosePane, _window, cx| {
                 this.close_active_pane(cx);
             }))
             .on_action(cx.listener(|this, _: &NewQueryTab, _window, cx| {
                 this.new_query_tab(cx);
             }))
+            .on_action(cx.listener(|this, _: &OpenSqlFile, _window, cx| {
+                this.open_sql_file(cx);
+            }))
+            .on_action(cx.listener(|this, _: &SaveSqlFile, _window, cx| {
+                this.save_sql_file(cx);
+            }))
+            .on_action(cx.listener(|this, _: &SaveSqlFileAs, _window, cx| {
+                this.save_sql_file_as(cx);
+            }))
             // Tab activation by index (Cmd+1-9)
             .on_action(cx.listener(|this, _: &ActivateTab1, _window, cx| {
                 this.activate_tab_by_index(0, cx);
@@ -967,10 +2678,19 @@ impl Render for Workspace {
             .on_action(cx.listener(|this, _: &ActivateTab9, _window, cx| {
                 this.activate_tab_by_index(8, cx);
             }))
+            .on_action(cx.listener(|this, _: &ExportResults, _window, cx| {
+                this.export_results(cx);
+            }))
+            .on_action(cx.listener(|this, _: &EstimateQuery, _window, cx| {
+                this.estimate_query(cx);
+            }))
             // Panel focus shortcuts
             .on_action(cx.listener(|this, _: &FocusSchemaBrowser, window, cx| {
                 this.focus_schema_browser(window, cx);
             }))
+            .on_action(cx.listener(|this, _: &GoToObject, window, cx| {
+                this.open_quick_open(window, cx);
+            }))
             .on_action(cx.listener(|this, _: &FocusResults, window, cx| {
                 this.focus_results(window, cx);
             }))
@@ -1009,7 +2729,7 @@ impl Render for Workspace {
                     .children(self.right_dock.clone()),
             )
             // Status bar
-            .child(self.render_status_bar(cx))
+            .child(status_bar)
             // Connection dialog (shown as modal overlay)
             .when_some(self.connection_dialog.clone(), |el, dialog| {
                 el.child(
@@ -1023,6 +2743,12 @@ impl Render for Workspace {
                         .child(dialog),
                 )
             })
+            // Type-to-confirm dialog for destructive schema browser actions
+            .when_some(self.confirm_dialog.clone(), |el, dialog| el.child(dialog))
+            // Go-to-object palette
+            .when_some(self.quick_open.clone(), |el, palette| el.child(palette))
+            // Toast notifications (T061) - above the status bar
+            .child(self.toast_layer.clone())
             // Context menu layer (T104) - rendered above main content but below modals
             .children(cx.try_global::<ContextMenuLayer>().and_then(|layer| layer.render()))
             // Modal layer (T094) - rendered above all content